@@ -0,0 +1,70 @@
+//! 分配计数基准：统计稳定运行时每次循环迭代的堆分配次数（Rust 的分配，不是 Java 堆）
+//!
+//! 同一个方法分别运行 N 次和 2N 次迭代，两次的分配数之差除以 N 就是每次迭代的分配数，
+//! 类加载、解析和缓存建立的一次性开销被抵消掉。
+//!
+//! 先编译示例：`cd examples && javac -encoding UTF-8 --release 8 AllocLoop.java`，
+//! 再运行 `cargo bench --bench allocations`
+
+use rsjvm::{Jvm, JvmBuilder};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// 统计 alloc/realloc 次数的分配器
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+const N: i32 = 100_000;
+
+/// `AllocLoop` 的静态方法运行 N 次和 2N 次迭代的分配次数之差
+fn extra_allocations(jvm: &mut Jvm, method: &str) -> usize {
+    let mut run = |n: i32| {
+        let before = ALLOCATIONS.load(Ordering::Relaxed);
+        let _: i32 = jvm
+            .call_static_typed("AllocLoop", method, "(I)I", (n,))
+            .expect("AllocLoop failed");
+        ALLOCATIONS.load(Ordering::Relaxed) - before
+    };
+    let once = run(N);
+    let twice = run(2 * N);
+    twice.saturating_sub(once)
+}
+
+fn main() {
+    let mut jvm = JvmBuilder::new().class_path("examples").build();
+    jvm.load_class_file("examples/AllocLoop.class")
+        .expect("compile examples/AllocLoop.java first");
+    for (method, description) in [
+        ("sum", "frame-local loop"),
+        ("run", "static + virtual calls"),
+    ] {
+        let extra = extra_allocations(&mut jvm, method);
+        println!(
+            "{:<24} {:>9} allocations for {} extra iterations ({:.2} per iteration)",
+            description,
+            extra,
+            N,
+            extra as f64 / N as f64
+        );
+    }
+}
@@ -0,0 +1,34 @@
+//! 字段访问基准：每次迭代 8 次 getfield/putfield（含继承字段），字段按解析时缓存的槽位访问
+//!
+//! 先编译示例：`cd examples && javac -encoding UTF-8 --release 8 FieldLoop.java`，
+//! 再运行 `cargo bench --bench field_access`
+
+use rsjvm::JvmBuilder;
+use std::time::{Duration, Instant};
+
+const ITERATIONS: i32 = 1_000_000;
+
+fn main() {
+    let mut jvm = JvmBuilder::new()
+        .class_path("examples")
+        .build();
+    let mut best: Option<Duration> = None;
+    let mut expected = None;
+    for round in 1..=3 {
+        let start = Instant::now();
+        let result: i32 = jvm
+            .call_static_typed("FieldLoop", "run", "(I)I", (ITERATIONS,))
+            .expect("FieldLoop failed");
+        let elapsed = start.elapsed();
+        assert_eq!(*expected.get_or_insert(result), result);
+
+        println!(
+            "round {}: {:?} ({:.1} M field accesses/s)",
+            round,
+            elapsed,
+            ITERATIONS as f64 * 8.0 / elapsed.as_secs_f64() / 1e6
+        );
+        best = Some(best.map_or(elapsed, |b| b.min(elapsed)));
+    }
+    println!("best of 3: {:?} for {} iterations", best.unwrap(), ITERATIONS);
+}
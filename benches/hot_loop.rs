@@ -0,0 +1,57 @@
+//! 热循环基准：一千万次 `sum += i` 循环，只用栈帧内指令（执行前预解码，见 `interpreter::decoded`）
+//!
+//! 运行：`cargo bench --bench hot_loop`
+
+use rsjvm::interpreter::Interpreter;
+use rsjvm::runtime::frame::JvmValue;
+use std::time::Instant;
+
+const ITERATIONS: i64 = 10_000_000;
+
+/// int n = 10000 * 1000; int sum = 0; for (int i = 0; i < n; i++) sum += i; return sum;
+const LOOP: [u8; 29] = [
+    0x11, 0x27, 0x10, // 0: sipush 10000
+    0x11, 0x03, 0xe8, // 3: sipush 1000
+    0x68, // 6: imul
+    0x3d, // 7: istore_2
+    0x03, // 8: iconst_0
+    0x3b, // 9: istore_0
+    0x03, // 10: iconst_0
+    0x3c, // 11: istore_1
+    0x1a, // 12: iload_0
+    0x1c, // 13: iload_2
+    0xa2, 0x00, 0x0d, // 14: if_icmpge 27
+    0x1b, // 17: iload_1
+    0x1a, // 18: iload_0
+    0x60, // 19: iadd
+    0x3c, // 20: istore_1
+    0x84, 0x00, 0x01, // 21: iinc 0 1
+    0xa7, 0xff, 0xf4, // 24: goto 12
+    0x1b, // 27: iload_1
+    0xac, // 28: ireturn
+];
+
+fn main() {
+    let expected = (ITERATIONS * (ITERATIONS - 1) / 2) as i32;
+    let mut best = None;
+    for round in 1..=3 {
+        let mut interpreter = Interpreter::new();
+        let start = Instant::now();
+        let result = interpreter
+            .execute_method_with_class("HotLoop", "run:()I", &LOOP, 3, 2)
+            .expect("loop failed");
+        let elapsed = start.elapsed();
+        assert!(matches!(result, Some(JvmValue::Int(v)) if v == expected), "{:?}", result);
+
+        let instructions = interpreter.instructions_executed();
+        println!(
+            "round {}: {:?} ({} instructions, {:.1} M instructions/s)",
+            round,
+            elapsed,
+            instructions,
+            instructions as f64 / elapsed.as_secs_f64() / 1e6
+        );
+        best = Some(best.map_or(elapsed, |b: std::time::Duration| b.min(elapsed)));
+    }
+    println!("best of 3: {:?} for {} iterations", best.unwrap(), ITERATIONS);
+}
@@ -0,0 +1,29 @@
+//! 静态方法调用基准：`ProfileDemo.sumTo(1000000)` 调用 `add` 一百万次
+//!
+//! 先编译示例：`cd examples && javac -encoding UTF-8 --release 8 ProfileDemo.java`，
+//! 再运行 `cargo bench --bench invoke_static`
+
+use rsjvm::JvmBuilder;
+use std::time::{Duration, Instant};
+
+const CALLS: i32 = 1_000_000;
+
+fn main() {
+    let mut best: Option<Duration> = None;
+    for round in 1..=3 {
+        let mut jvm = JvmBuilder::new().class_path("examples").build();
+        jvm.load_class_file("examples/ProfileDemo.class")
+            .expect("compile examples/ProfileDemo.java first");
+        let start = Instant::now();
+        let sum: i32 = jvm
+            .call_static_typed("ProfileDemo", "sumTo", "(I)I", (CALLS,))
+            .expect("sumTo failed");
+        let elapsed = start.elapsed();
+        assert_eq!(sum, (CALLS as i64 * (CALLS as i64 - 1) / 2) as i32);
+
+        let hits = jvm.interpreter().metaspace.resolution_stats().call_site_hits;
+        println!("round {}: {:?} ({} call-site cache hits)", round, elapsed, hits);
+        best = Some(best.map_or(elapsed, |b| b.min(elapsed)));
+    }
+    println!("best of 3: {:?} for {} calls", best.unwrap(), CALLS);
+}
@@ -0,0 +1,24 @@
+/**
+ * 通过抽象类型调用方法：按对象的实际类型分派到具体实现
+ */
+public class AbstractDispatch {
+    public static int speakViaBase() {
+        Animal animal = new Dog();
+        return animal.speak();
+    }
+
+    // 抽象类中的具体方法调用抽象方法
+    public static int speakTwiceViaBase() {
+        Animal animal = new Dog();
+        return animal.speakTwice();
+    }
+
+    // 实际类型没有实现 speak() 时，AbstractMethodError 可以被捕获
+    public static int speakOrMinusOne(Animal animal) {
+        try {
+            return animal.speak();
+        } catch (AbstractMethodError e) {
+            return -1;
+        }
+    }
+}
@@ -0,0 +1,32 @@
+/**
+ * 分配计数基准：循环中的静态调用、虚方法调用和字段读写
+ */
+public class AllocLoop {
+    private int total;
+    static int calls;
+
+    void add(int value) {
+        total += value;
+    }
+
+    static int twice(int value) {
+        return value * 2;
+    }
+
+    static int sum(int n) {
+        int total = 0;
+        for (int i = 0; i < n; i++) {
+            total += i;
+        }
+        return total;
+    }
+
+    static int run(int n) {
+        AllocLoop loop = new AllocLoop();
+        for (int i = 0; i < n; i++) {
+            loop.add(twice(i));
+            calls++;
+        }
+        return loop.total;
+    }
+}
@@ -0,0 +1,10 @@
+/**
+ * 抽象类：speak() 没有字节码，不能直接运行
+ */
+public abstract class Animal {
+    public abstract int speak();
+
+    public int speakTwice() {
+        return speak() * 2;
+    }
+}
@@ -0,0 +1,14 @@
+/**
+ * 没有无参构造器的插件：newInstance 应该失败
+ */
+public class ArgPlugin implements Greeter {
+    private int bonus;
+
+    public ArgPlugin(int bonus) {
+        this.bonus = bonus;
+    }
+
+    public int greet(int x) {
+        return x + bonus;
+    }
+}
@@ -0,0 +1,37 @@
+/**
+ * aastore 的类型检查：数组记得自己的组件类型，存入不兼容的对象抛出 ArrayStoreException
+ */
+public class ArrayStoreCheck {
+    static class Base {
+    }
+
+    static class Derived extends Base {
+    }
+
+    // 编译期 Object[] 可以放 Integer，运行时数组是 String[]
+    static int wrongType() {
+        Object[] a = new String[1];
+        a[0] = Integer.valueOf(1);
+        return 0;
+    }
+
+    static int caught() {
+        Object[] a = new Derived[1];
+        try {
+            a[0] = new Base();
+            return 0;
+        } catch (ArrayStoreException e) {
+            return 1;
+        }
+    }
+
+    // 子类对象和 null 可以存入
+    static int compatible() {
+        Base[] a = new Base[3];
+        a[0] = new Derived();
+        a[1] = null;
+        Object[] numbers = new Number[1];
+        numbers[0] = Integer.valueOf(7);
+        return a.length + numbers.length;
+    }
+}
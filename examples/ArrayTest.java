@@ -0,0 +1,54 @@
+/**
+ * 基本类型数组示例
+ * 验证 newarray、iastore/iaload、arraylength 以及 byte/char/short 数组的截断
+ */
+public class ArrayTest {
+    // 循环填充 int[5] 并求和
+    public static int sumIntArray() {
+        int[] values = new int[5];
+        for (int i = 0; i < values.length; i++) {
+            values[i] = i * 10;
+        }
+        int sum = 0;
+        for (int i = 0; i < values.length; i++) {
+            sum += values[i];
+        }
+        return sum; // 0 + 10 + 20 + 30 + 40 = 100
+    }
+
+    // byte 数组（bastore/baload）
+    public static int byteArray() {
+        byte[] bytes = new byte[2];
+        bytes[0] = -56;
+        bytes[1] = 100;
+        return bytes[0] + bytes[1]; // 44
+    }
+
+    // char 和 short 数组
+    public static int charAndShort() {
+        char[] chars = new char[2];
+        chars[0] = 'A';
+        chars[1] = 'B';
+        short[] shorts = new short[1];
+        shorts[0] = 1000;
+        return chars[0] + chars[1] + shorts[0]; // 65 + 66 + 1000
+    }
+
+    // long 数组（两个槽位的元素）
+    public static long longArray() {
+        long[] longs = new long[3];
+        longs[2] = 5000000000L;
+        return longs[0] + longs[2];
+    }
+
+    public static int outOfBounds() {
+        int[] values = new int[5];
+        return values[5];
+    }
+
+    public static int negativeSize() {
+        int n = -1;
+        int[] values = new int[n];
+        return values.length;
+    }
+}
@@ -0,0 +1,28 @@
+import java.io.IOException;
+import java.util.List;
+
+/**
+ * 属性解析测试：ConstantValue、Exceptions、Signature、SourceFile
+ */
+public class Attributes<T extends Comparable<T>> {
+    static final int MAGIC = 42;
+    static final long BIG = 1L << 40;
+    static final double RATIO = 0.5;
+    static final String NAME = "attrs";
+
+    static List<String> names;
+
+    // <clinit> 运行后才会变成 7
+    static int initialized = 7;
+
+    static void risky() throws IOException, InterruptedException {
+    }
+
+    static <E> List<E> wrap(E element) {
+        return null;
+    }
+
+    static int initialized() {
+        return initialized;
+    }
+}
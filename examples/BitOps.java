@@ -0,0 +1,30 @@
+/**
+ * 位运算与移位：移位量超过位宽时只取低位
+ */
+public class BitOps {
+    static int shl(int a, int n) { return a << n; }
+    static int shr(int a, int n) { return a >> n; }
+    static int ushr(int a, int n) { return a >>> n; }
+    static int and(int a, int b) { return a & b; }
+    static int or(int a, int b) { return a | b; }
+    static int xor(int a, int b) { return a ^ b; }
+    static int rem(int a, int b) { return a % b; }
+
+    static long lshl(long a, int n) { return a << n; }
+    static long lshr(long a, int n) { return a >> n; }
+    static long lushr(long a, int n) { return a >>> n; }
+    static long land(long a, long b) { return a & b; }
+    static long lor(long a, long b) { return a | b; }
+    static long lxor(long a, long b) { return a ^ b; }
+
+    /** FNV-1a 风格的哈希：移位、异或和掩码组合在一起 */
+    static int hash(int seed, int rounds) {
+        int h = seed;
+        for (int i = 0; i < rounds; i++) {
+            h ^= h >>> 16;
+            h = (h << 5) | (h >>> 27);
+            h &= 0x7fffffff;
+        }
+        return h;
+    }
+}
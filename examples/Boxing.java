@@ -0,0 +1,65 @@
+/**
+ * 自动装箱和拆箱：包装类的 valueOf/xxxValue、Integer 缓存、parseInt/parseLong
+ */
+public class Boxing {
+    static int boxUnboxAdd() {
+        Integer x = 5;
+        int y = x;
+        return y + 1;
+    }
+
+    static Integer box(int value) {
+        return value;
+    }
+
+    static boolean smallValuesShared() {
+        Integer a = 100;
+        Integer b = 100;
+        return a == b;
+    }
+
+    static boolean largeValuesShared() {
+        Integer a = 1000;
+        Integer b = 1000;
+        return a == b;
+    }
+
+    static boolean equalsByValue() {
+        Integer a = 1000;
+        Integer b = 1000;
+        Long c = 1000L;
+        return a.equals(b) && !a.equals(c) && a.hashCode() == 1000;
+    }
+
+    static long longs() {
+        Long a = 40L;
+        return a + 2;
+    }
+
+    static double doubles() {
+        Double d = 1.25;
+        return d * 2;
+    }
+
+    static boolean booleans() {
+        Boolean b = true;
+        return b && b == Boolean.valueOf(true);
+    }
+
+    static char chars() {
+        Character c = 'a';
+        return (char) (c + 1);
+    }
+
+    static String describe() {
+        Integer i = -7;
+        Double d = 0.5;
+        Boolean b = false;
+        Character c = 'z';
+        return i.toString() + d.toString() + b.toString() + c.toString();
+    }
+
+    static long parseLong(String s) {
+        return Long.parseLong(s);
+    }
+}
@@ -0,0 +1,40 @@
+/**
+ * 内置类库：JDK 方法由解释器内置实现，不再假装调用成功
+ */
+public class BuiltinLibrary {
+    static int maxOfConstants() {
+        return Math.max(3, 7);
+    }
+
+    static int absPlusOne(int x) {
+        return Math.abs(x) + 1;
+    }
+
+    static long longMin(long a, long b) {
+        return Math.min(a, b);
+    }
+
+    static int parse(String s) {
+        return Integer.parseInt(s);
+    }
+
+    static int parseOrDefault(String s) {
+        try {
+            return Integer.parseInt(s);
+        } catch (NumberFormatException e) {
+            return -1;
+        }
+    }
+
+    static String message() {
+        try {
+            throw new IllegalStateException("boom");
+        } catch (IllegalStateException e) {
+            return e.getMessage();
+        }
+    }
+
+    static void unimplemented() {
+        Thread.yield();
+    }
+}
@@ -23,6 +23,15 @@ public class Calculator {
         return a / b;
     }
 
+    // 平均值：经过两层调用到达 divide，count 为 0 时在 divide 中抛出 ArithmeticException
+    public static int average(int total, int count) {
+        return percent(total, count) / 100;
+    }
+
+    static int percent(int total, int count) {
+        return divide(total * 100, count);
+    }
+
     // 复杂计算: (a + b) * (c - d)
     public static int complex(int a, int b, int c, int d) {
         int sum = a + b;
@@ -0,0 +1,60 @@
+/**
+ * 三层调用链：每层返回后调用者从自己保存的 pc 继续
+ */
+public class CallChain {
+    static int top(int x) {
+        int a = middle(x + 1);
+        int b = middle(x + 2);
+        return a * 100 + b;
+    }
+
+    static int middle(int x) {
+        int r = bottom(x) + 1;
+        return r;
+    }
+
+    static int bottom(int x) {
+        return x * 2;
+    }
+
+    int outer(int n) {
+        return inner(n) + 1;
+    }
+
+    int inner(int n) {
+        return deepest(n) * 10;
+    }
+
+    int deepest(int n) {
+        return n;
+    }
+
+    static int instanceChain() {
+        CallChain chain = new CallChain();
+        return chain.outer(3);
+    }
+
+    /** 第三层抛出的异常在第一层被捕获 */
+    static int catchFromDepth() {
+        try {
+            int v = second();
+            return v;
+        } catch (IllegalStateException e) {
+            return 42;
+        }
+    }
+
+    static int second() {
+        int v = third();
+        return v + 1;
+    }
+
+    static int third() {
+        throw new IllegalStateException();
+    }
+
+    /** 字节码与 third 完全相同，只能按方法键区分 */
+    static int twin() {
+        throw new IllegalStateException();
+    }
+}
@@ -0,0 +1,97 @@
+/**
+ * 测试 checkcast / instanceof
+ *
+ * 类层次：Vehicle ← Car ← SportsCar，Vehicle ← Bike，
+ * Car ← Tesla implements Electric ← ModelS（通过父类实现接口）
+ */
+interface Electric {
+}
+
+class Vehicle {
+}
+
+class Car extends Vehicle {
+}
+
+class SportsCar extends Car {
+}
+
+class Bike extends Vehicle {
+}
+
+class Tesla extends Car implements Electric {
+}
+
+class ModelS extends Tesla {
+}
+
+public class CastTest {
+    static Vehicle none;
+
+    static int bit(boolean b, int weight) {
+        return b ? weight : 0;
+    }
+
+    /** 每一位是一次 instanceof 的结果（位运算指令尚未实现，用加法组合） */
+    public static int instanceofPositive() {
+        Vehicle sports = new SportsCar();
+        Vehicle modelS = new ModelS();
+        return bit(sports instanceof Vehicle, 1)
+                + bit(sports instanceof Car, 2)
+                + bit(sports instanceof SportsCar, 4)
+                + bit(modelS instanceof Electric, 8)
+                + bit(modelS instanceof Tesla, 16)
+                + bit(sports instanceof Object, 32);
+    }
+
+    public static int instanceofNegative() {
+        Vehicle bike = new Bike();
+        Vehicle car = new Car();
+        return bit(bike instanceof Car, 1)
+                + bit(car instanceof SportsCar, 2)
+                + bit(car instanceof Electric, 4)
+                + bit(bike instanceof Electric, 8);
+    }
+
+    public static int instanceofNull() {
+        return bit(none instanceof Vehicle, 1) + bit(none instanceof Object, 2);
+    }
+
+    public static int instanceofArrays() {
+        Object cars = new SportsCar[1];
+        Object ints = new int[1];
+        return bit(cars instanceof Vehicle[], 1)
+                + bit(cars instanceof Object[], 2)
+                + bit(cars instanceof Bike[], 4)
+                + bit(ints instanceof int[], 8)
+                + bit(ints instanceof Object[], 16);
+    }
+
+    public static int castUp() {
+        Vehicle v = new SportsCar();
+        Car c = (Car) v;
+        Electric e = (Electric) (Vehicle) new ModelS();
+        return 1;
+    }
+
+    public static int castNull() {
+        Car c = (Car) none;
+        return 1;
+    }
+
+    public static int castFails() {
+        Vehicle v = new Bike();
+        Car c = (Car) v;
+        return 1;
+    }
+
+    public static int castFailsCaught() {
+        Vehicle v = new Car();
+        try {
+            Electric e = (Electric) v;
+            return 1;
+        } catch (ClassCastException ex) {
+            return -1;
+        }
+    }
+}
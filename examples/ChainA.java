@@ -0,0 +1,10 @@
+// 按需加载测试：只显式加载 ChainA，ChainB、ChainC、ChainBase 由类加载器在执行中加载
+public class ChainA {
+    public static void main(String[] args) {
+        System.out.println(run());
+    }
+
+    public static int run() {
+        return ChainB.compute(5);
+    }
+}
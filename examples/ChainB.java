@@ -0,0 +1,8 @@
+public class ChainB {
+    static int calls;
+
+    static int compute(int x) {
+        calls = calls + 1;
+        return ChainC.square(x) + new ChainC().offset();
+    }
+}
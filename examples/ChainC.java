@@ -0,0 +1,11 @@
+public class ChainC extends ChainBase {
+    static int square(int x) {
+        return x * x;
+    }
+}
+
+class ChainBase {
+    int offset() {
+        return 1;
+    }
+}
@@ -0,0 +1,26 @@
+/**
+ * 类字面量：Foo.class 编译成 ldc 一个 CONSTANT_Class 常量
+ */
+public class ClassLiteral {
+    public static String literalName() {
+        return ClassLiteral.class.getName();
+    }
+
+    // 其他包中的类：按需加载，但不初始化
+    public static String otherPackageName() {
+        return cpdemo.util.Greeting.class.getName();
+    }
+
+    public static String arrayName() {
+        return int[].class.getName();
+    }
+
+    // 两次 ldc 同一个类得到同一个 Class 对象
+    public static Object literal() {
+        return ClassLiteral.class;
+    }
+
+    public static boolean sameAsGetClass() {
+        return new ClassLiteral().getClass() == ClassLiteral.class;
+    }
+}
@@ -0,0 +1,37 @@
+/**
+ * 字符串拼接：Java 8 编译成 StringBuilder.append 链
+ */
+public class Concat {
+    public static void main(String[] args) {
+        int a = 10;
+        int b = 20;
+        System.out.println("sum=" + (a + b));
+    }
+
+    static String describe(int i, long l, boolean z, char c, String s) {
+        return "i=" + i + ", l=" + l + ", z=" + z + ", c=" + c + ", s=" + s;
+    }
+
+    static int length(String s) {
+        StringBuilder sb = new StringBuilder(s);
+        sb = sb.append(s);
+        return sb.length();
+    }
+
+    /** toString 每次返回新的 String，和字面量不是同一个对象 */
+    static boolean sameAsLiteral() {
+        StringBuilder sb = new StringBuilder();
+        sb.append("ab");
+        return sb.toString() == "ab";
+    }
+
+    /** 每轮拼接出一个新字符串，用完就不可达 */
+    static int churn(int n) {
+        int total = 0;
+        for (int i = 0; i < n; i++) {
+            String s = "v" + i;
+            total += s.length();
+        }
+        return total;
+    }
+}
@@ -0,0 +1,8 @@
+/**
+ * Animal 的具体实现
+ */
+public class Dog extends Animal {
+    public int speak() {
+        return 3;
+    }
+}
@@ -0,0 +1,37 @@
+/**
+ * 测试从 Rust 调用静态方法时的参数/返回值转换（invoke_static_typed）
+ */
+public class EmbedTest {
+    /** name 为 null 时 instanceof 为 false */
+    public static int score(boolean bonus, String name) {
+        int base = name instanceof String ? 10 : 0;
+        return bonus ? base + 5 : base;
+    }
+
+    public static boolean isPositive(int x) {
+        return x > 0;
+    }
+
+    public static char initial() {
+        return 'J';
+    }
+
+    public static byte echoByte(byte b) {
+        return b;
+    }
+
+    public static long twice(long x) {
+        return x * 2;
+    }
+
+    public static double half(double d) {
+        return d / 2;
+    }
+
+    public static String echo(String s) {
+        return s;
+    }
+
+    public static void nothing() {
+    }
+}
@@ -0,0 +1,76 @@
+/**
+ * 测试 athrow 和异常表
+ */
+public class ExceptionTest {
+    static void thrower() {
+        throw new MyException();
+    }
+
+    public static int caught() {
+        try {
+            throw new IllegalStateException();
+        } catch (IllegalStateException e) {
+            return 1;
+        }
+    }
+
+    public static int caughtBySuperclass() {
+        try {
+            throw new MyException();
+        } catch (RuntimeException e) {
+            return 2;
+        }
+    }
+
+    public static int caughtInCaller() {
+        int before = 5;
+        try {
+            thrower();
+            before = 0;
+        } catch (MyException e) {
+            return before + 3;
+        }
+        return -1;
+    }
+
+    public static int matchingHandler() {
+        try {
+            thrower();
+        } catch (ArithmeticException e) {
+            return -1;
+        } catch (MyException e) {
+            return 4;
+        }
+        return 0;
+    }
+
+    public static int finallyThenCatch() {
+        int x = 0;
+        try {
+            try {
+                x = 1;
+                thrower();
+            } finally {
+                x += 10;
+            }
+        } catch (MyException e) {
+            x += 100;
+        }
+        return x;
+    }
+
+    public static int finallyWithoutException() {
+        int x = 1;
+        try {
+            x = 2;
+        } finally {
+            x += 10;
+        }
+        return x;
+    }
+
+    public static int uncaught() {
+        thrower();
+        return 0;
+    }
+}
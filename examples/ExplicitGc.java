@@ -0,0 +1,34 @@
+/**
+ * Java 代码主动请求 GC：System.gc() 和 Runtime.getRuntime().gc()
+ *
+ * 每轮分配 100 个临时数组（都是垃圾），再往静态数组里放一个存活的对象
+ */
+public class ExplicitGc {
+    static Object[] kept = new Object[10];
+
+    public static int run(int rounds) {
+        int sum = 0;
+        for (int r = 0; r < rounds; r++) {
+            for (int i = 0; i < 100; i++) {
+                int[] tmp = new int[4];
+                tmp[0] = i;
+                sum += tmp[0];
+            }
+            kept[r % kept.length] = new int[1];
+            if (r % 2 == 0) {
+                System.gc();
+            } else {
+                Runtime.getRuntime().gc();
+            }
+        }
+        return sum;
+    }
+
+    public static boolean sameRuntime() {
+        return Runtime.getRuntime() == Runtime.getRuntime();
+    }
+
+    public static void main(String[] args) {
+        System.out.println(run(3));
+    }
+}
@@ -0,0 +1,25 @@
+/**
+ * 字段访问密集的循环：每次迭代若干次 getfield/putfield，用于字段布局基准（benches/field_access.rs）
+ *
+ * FieldLoopCounter 继承了 FieldLoopBase 的字段，访问继承字段和本类字段的槽位都在解析时算好
+ */
+public class FieldLoop {
+    public static int run(int n) {
+        FieldLoopCounter c = new FieldLoopCounter();
+        for (int i = 0; i < n; i++) {
+            c.a += i;
+            c.b = c.a ^ c.b;
+            c.c += c.b & 7;
+        }
+        return c.a + c.b + c.c;
+    }
+}
+
+class FieldLoopBase {
+    int a;
+    int b;
+}
+
+class FieldLoopCounter extends FieldLoopBase {
+    int c;
+}
@@ -0,0 +1,61 @@
+/**
+ * 字段遮蔽和字段默认值
+ *
+ * ShadowChild 声明了与 ShadowParent 同名的字段 x，两者是对象中的两个不同字段
+ */
+public class FieldShadowing {
+    public static int parentView() {
+        ShadowChild c = new ShadowChild();
+        ShadowParent p = c;
+        return p.x;
+    }
+
+    public static int childView() {
+        ShadowChild c = new ShadowChild();
+        return c.x;
+    }
+
+    public static int bothViews() {
+        ShadowChild c = new ShadowChild();
+        c.x = 5;
+        ((ShadowParent) c).x = 7;
+        return c.x * 100 + c.parentX();
+    }
+
+    public static int intDefault() {
+        FieldDefaults d = new FieldDefaults();
+        return d.count;
+    }
+
+    public static long longDefault() {
+        FieldDefaults d = new FieldDefaults();
+        return d.total;
+    }
+
+    public static Object referenceDefault() {
+        FieldDefaults d = new FieldDefaults();
+        return d.name;
+    }
+}
+
+class ShadowParent {
+    int x = 1;
+
+    int parentX() {
+        return x;
+    }
+}
+
+class ShadowChild extends ShadowParent {
+    int x = 2;
+}
+
+/** 构造器不给字段赋值，读到的都是默认值 */
+class FieldDefaults extends ShadowParent {
+    int count;
+    long total;
+    String name;
+
+    FieldDefaults() {
+    }
+}
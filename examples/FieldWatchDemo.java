@@ -0,0 +1,36 @@
+/**
+ * 字段监视演示：rsjvm run examples/FieldWatchDemo.class -m run --watch FieldWatchDemo.count
+ */
+public class FieldWatchDemo {
+    static int total;
+
+    int count = 0;
+    int other = 0;
+
+    void bump() {
+        count = count + 1;
+        other = 5;
+    }
+
+    public static int run() {
+        FieldWatchDemo c = new FieldWatchDemo();
+        for (int i = 0; i < 2; i++) {
+            c.bump();
+            total += 10;
+        }
+        return c.count;
+    }
+
+    public static int runSub() {
+        SubCounter c = new SubCounter();
+        c.bumpTwice();
+        return c.count;
+    }
+}
+
+/** 通过子类访问继承的字段，字段引用的类是 SubCounter，声明类是 FieldWatchDemo */
+class SubCounter extends FieldWatchDemo {
+    void bumpTwice() {
+        count += 2;
+    }
+}
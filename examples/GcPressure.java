@@ -0,0 +1,39 @@
+/**
+ * 分配压力下的自动 GC
+ *
+ * churn 分配大量临时对象；keepChain 把对象串成链表挂在静态字段上，它们必须全部存活
+ */
+public class GcPressure {
+    static GcPressure head;
+
+    int value;
+    GcPressure next;
+
+    public static int churn(int n) {
+        int sum = 0;
+        for (int i = 0; i < n; i++) {
+            GcPressure tmp = new GcPressure();
+            tmp.value = i;
+            int[] scratch = new int[2];
+            scratch[1] = tmp.value;
+            sum += scratch[1];
+        }
+        return sum;
+    }
+
+    public static int keepChain(int n) {
+        for (int i = 0; i < n; i++) {
+            GcPressure node = new GcPressure();
+            node.value = 1;
+            node.next = head;
+            head = node;
+            GcPressure garbage = new GcPressure();
+            garbage.value = -1;
+        }
+        int total = 0;
+        for (GcPressure node = head; node != null; node = node.next) {
+            total += node.value;
+        }
+        return total;
+    }
+}
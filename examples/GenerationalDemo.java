@@ -0,0 +1,81 @@
+/**
+ * 分代回收：长期存活的对象图和大量临时对象
+ *
+ * buildGraph 把节点串成链表挂在静态字段上（长期存活）；churn 只分配临时对象，
+ * boxChurn 和 concatChurn 通过内置方法（Integer.valueOf、StringBuilder.toString）分配；
+ * linkYoung 让老年代对象（graph 和 table）引用新分配的对象，局部变量随即清空，
+ * 这些新对象只能通过记忆集在 minor GC 中存活
+ */
+public class GenerationalDemo {
+    static GenNode graph;
+    static GenNode[] table = new GenNode[16];
+
+    public static int buildGraph(int n) {
+        for (int i = 0; i < n; i++) {
+            GenNode node = new GenNode();
+            node.value = 1;
+            node.next = graph;
+            graph = node;
+        }
+        return count();
+    }
+
+    public static int count() {
+        int total = 0;
+        for (GenNode node = graph; node != null; node = node.next) {
+            total += node.value;
+        }
+        return total;
+    }
+
+    public static int churn(int n) {
+        int sum = 0;
+        for (int i = 0; i < n; i++) {
+            GenNode tmp = new GenNode();
+            tmp.value = i;
+            sum += tmp.value;
+        }
+        return sum;
+    }
+
+    public static int boxChurn(int n) {
+        int sum = 0;
+        for (int i = 0; i < n; i++) {
+            Integer boxed = Integer.valueOf(i + 1000);
+            sum += boxed.intValue();
+        }
+        return sum;
+    }
+
+    public static int concatChurn(int n) {
+        int total = 0;
+        for (int i = 0; i < n; i++) {
+            String s = "v" + i;
+            total += s.length();
+        }
+        return total;
+    }
+
+    public static int linkYoung(int rounds) {
+        int sum = 0;
+        for (int r = 0; r < rounds; r++) {
+            GenNode fresh = new GenNode();
+            fresh.value = r;
+            graph.payload = fresh;
+            GenNode slot = new GenNode();
+            slot.value = 2 * r;
+            table[r % table.length] = slot;
+            fresh = null;
+            slot = null;
+            churn(300);
+            sum += graph.payload.value + table[r % table.length].value;
+        }
+        return sum;
+    }
+}
+
+class GenNode {
+    int value;
+    GenNode next;
+    GenNode payload;
+}
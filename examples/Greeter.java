@@ -0,0 +1,11 @@
+/**
+ * 插件接口：PluginMain 只通过这个接口调用插件
+ */
+public interface Greeter {
+    int greet(int x);
+
+    /** default 方法：插件没有覆盖时由 invokeinterface 找到接口中的实现 */
+    default int greetTwice(int x) {
+        return greet(greet(x));
+    }
+}
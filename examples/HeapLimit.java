@@ -0,0 +1,46 @@
+/**
+ * 堆上限：临时对象可以被回收，一直被引用的对象超过上限时抛出 OutOfMemoryError
+ */
+public class HeapLimit {
+    HeapLimit next;
+
+    public static int churn(int n) {
+        int count = 0;
+        for (int i = 0; i < n; i++) {
+            HeapLimit tmp = new HeapLimit();
+            tmp.next = null;
+            count++;
+        }
+        return count;
+    }
+
+    public static int retain(int n) {
+        HeapLimit head = null;
+        int count = 0;
+        for (int i = 0; i < n; i++) {
+            HeapLimit node = new HeapLimit();
+            node.next = head;
+            head = node;
+            count++;
+        }
+        return count;
+    }
+
+    public static int catchOom(int n) {
+        try {
+            return retain(n);
+        } catch (OutOfMemoryError e) {
+            return -1;
+        }
+    }
+
+    /** 只通过内置方法分配（Integer.valueOf 不经过 new 指令），临时对象同样可以被回收 */
+    public static int boxChurn(int n) {
+        int sum = 0;
+        for (int i = 0; i < n; i++) {
+            Integer x = Integer.valueOf(i + 1000);
+            sum += x.intValue();
+        }
+        return sum;
+    }
+}
@@ -0,0 +1,14 @@
+/**
+ * 插件实现：只通过 Class.forName("HelloPlugin") 按名字加载
+ */
+public class HelloPlugin implements Greeter {
+    private int bonus;
+
+    public HelloPlugin() {
+        bonus = 22;
+    }
+
+    public int greet(int x) {
+        return x + bonus;
+    }
+}
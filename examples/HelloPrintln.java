@@ -1,11 +1,11 @@
+/**
+ * 程序输出捕获：System.out 的输出可以重定向到内存
+ */
 public class HelloPrintln {
     public static void main(String[] args) {
+        System.out.println("Hello, println!");
         System.out.println(42);
-        System.out.println(100);
-        System.out.println(sum(10, 20));
-    }
-
-    public static int sum(int a, int b) {
-        return a + b;
+        System.out.println();
+        System.out.println("args=" + args.length);
     }
 }
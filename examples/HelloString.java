@@ -0,0 +1,25 @@
+public class HelloString {
+    public static void main(String[] args) {
+        System.out.println("Hello");
+        System.out.println(greeting());
+        System.out.println(42);
+    }
+
+    static String greeting() {
+        return "Hello, RSJVM";
+    }
+
+    static boolean sameLiteral() {
+        String a = "Hello";
+        String b = "Hello";
+        return a == b;
+    }
+
+    static boolean sameAcrossMethods() {
+        return greeting() == "Hello, RSJVM";
+    }
+
+    static boolean differentLiterals() {
+        return "Hello" == greeting();
+    }
+}
@@ -0,0 +1,49 @@
+/**
+ * Java 9+ 的字符串拼接：javac 生成 invokedynamic，
+ * 引导方法是 StringConcatFactory.makeConcatWithConstants
+ *
+ * 用 javac --release 11 编译（--release 8 会生成 StringBuilder.append 链）
+ */
+public class IndyConcat {
+    public static String wrap(int x) {
+        return "a" + x + "b";
+    }
+
+    public static String mixed(String name, long count, boolean flag, char c, double ratio) {
+        return name + ": " + count + " " + flag + " " + c + " " + ratio;
+    }
+
+    /** 字面量中含有配方的控制字符时，javac 把它作为静态常量传给引导方法 */
+    public static String withControlChars(int x) {
+        return "\u0001" + x + "\u0002";
+    }
+
+    public static String loop(int n) {
+        String s = "";
+        for (int i = 0; i < n; i++) {
+            s = s + i;
+        }
+        return s;
+    }
+
+    /** 拼接结果不驻留，和内容相同的字面量不是同一个对象 */
+    public static boolean sameAsLiteral(int x) {
+        return ("a" + x + "b") == "a1b";
+    }
+
+    public static int churn(int n) {
+        int total = 0;
+        for (int i = 0; i < n; i++) {
+            String s = "v" + i;
+            total += s.length();
+        }
+        return total;
+    }
+
+    /** lambda 也是 invokedynamic，但引导方法是 LambdaMetafactory */
+    public static int lambda() {
+        Runnable r = () -> {};
+        r.run();
+        return 1;
+    }
+}
@@ -0,0 +1,22 @@
+/**
+ * rsjvm info --deep 的例子：InfoRoot 和 InfoHelper 互相引用（依赖成环）
+ */
+public class InfoRoot {
+    static InfoHelper[] helpers = new InfoHelper[2];
+
+    public static int run() {
+        InfoHelper helper = new InfoHelper();
+        helpers[0] = helper;
+        return helper.owner().value();
+    }
+
+    int value() {
+        return 7;
+    }
+}
+
+class InfoHelper {
+    InfoRoot owner() {
+        return new InfoRoot();
+    }
+}
@@ -0,0 +1,22 @@
+// 继承解析测试：InheritChild 只有编译器生成的构造器，所有字段和方法都继承自这里
+public class InheritBase implements InheritIface {
+    static int calls;
+    int value;
+
+    static int helper(int x) {
+        calls = calls + 1;
+        return x * 2;
+    }
+
+    int get() {
+        return value;
+    }
+}
+
+interface InheritIface {
+    int ANSWER = 42;
+
+    default int answer() {
+        return ANSWER;
+    }
+}
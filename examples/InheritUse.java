@@ -0,0 +1,32 @@
+// 通过子类访问继承的静态方法、静态字段和实例字段
+public class InheritUse {
+    static int callHelper() {
+        return InheritChild.helper(21);
+    }
+
+    static int readCalls() {
+        return InheritChild.calls;
+    }
+
+    static int writeCalls(int v) {
+        InheritChild.calls = v;
+        return InheritBase.calls;
+    }
+
+    static int fields() {
+        InheritChild child = new InheritChild();
+        child.value = 5;
+        return child.value + child.get();
+    }
+
+    // answer() 是 InheritIface 的 default 方法：invokevirtual 和 invokeinterface 都要找到它
+    static int defaultViaClass() {
+        InheritChild child = new InheritChild();
+        return child.answer();
+    }
+
+    static int defaultViaInterface() {
+        InheritIface iface = new InheritChild();
+        return iface.answer();
+    }
+}
@@ -0,0 +1,18 @@
+// 测试 NEW 的 InstantiationError：测试中把 Widget 的访问标志改成 abstract/interface 再加载，
+// 模拟“编译时是具体类、运行时变成了抽象类/接口”的分离编译场景
+public class InstantiationTest {
+    static Object make() {
+        return new Widget();
+    }
+
+    static int makeAndUse() {
+        Widget w = new Widget();
+        return w.size();
+    }
+}
+
+class Widget {
+    int size() {
+        return 7;
+    }
+}
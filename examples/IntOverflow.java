@@ -0,0 +1,18 @@
+/**
+ * 整数溢出：Java 的 int/long 运算按补码回绕，不会出错
+ */
+public class IntOverflow {
+    static int add(int a, int b) { return a + b; }
+    static int sub(int a, int b) { return a - b; }
+    static int mul(int a, int b) { return a * b; }
+    static int div(int a, int b) { return a / b; }
+    static int rem(int a, int b) { return a % b; }
+    static int neg(int a) { return -a; }
+
+    static long ladd(long a, long b) { return a + b; }
+    static long lsub(long a, long b) { return a - b; }
+    static long lmul(long a, long b) { return a * b; }
+    static long ldiv(long a, long b) { return a / b; }
+    static long lrem(long a, long b) { return a % b; }
+    static long lneg(long a) { return -a; }
+}
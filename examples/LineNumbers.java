@@ -0,0 +1,12 @@
+/**
+ * 行号表和局部变量表测试（用 -g 编译才有 LocalVariableTable）
+ *
+ * 编译：javac -g -encoding UTF-8 --release 8 LineNumbers.java
+ */
+public class LineNumbers {
+    public static int compute(int a) {
+        int b = a * 2;
+        int c = b + 1;
+        return c;
+    }
+}
@@ -0,0 +1,47 @@
+/**
+ * long/double 循环示例
+ * 组合了 lload、lcmp、ifge、ladd、lconst、lstore 等指令，
+ * 用于验证 long/double 两个槽位的处理和比较指令 + 条件跳转的配合
+ */
+public class LongLoop {
+    // 循环变量和累加器都是 long，且数值超过 int 范围
+    public static long sumLongs() {
+        long sum = 0L;
+        for (long i = 3000000000L; i < 3000001000L; i++) {
+            sum += i;
+        }
+        return sum; // 3000000499500
+    }
+
+    // long 参数占用局部变量 0 和 1，局部变量从 2 开始
+    public static long countDown(long n) {
+        long steps = 0;
+        while (n > 0) {
+            n -= 3;
+            steps++;
+        }
+        return steps;
+    }
+
+    public static long countDownFromBig() {
+        return countDown(5000000000L / 1000000L);
+    }
+
+    // double 累加器 + double 比较（dcmpg）
+    public static double sumDoubles() {
+        double sum = 0.0;
+        for (double x = 0.5; x < 100.0; x += 0.5) {
+            sum += x;
+        }
+        return sum; // 9950.0
+    }
+
+    // float 累加器 + float 比较（fcmpg），int 计数器使用 iinc
+    public static float sumFloats() {
+        float sum = 0.0f;
+        for (int i = 0; i < 10; i++) {
+            sum += 0.1f;
+        }
+        return sum; // 约 1.0
+    }
+}
@@ -0,0 +1,45 @@
+/**
+ * 方法和字段的查询：重载、多维数组和对象参数、main 方法
+ */
+public class MethodQuery {
+    public static int count;
+    private String name;
+    long[][] grid;
+
+    public static void main(String[] args) {
+        count = args.length;
+    }
+
+    public static int sum(int a, int b) {
+        return a + b;
+    }
+
+    public static long sum(long[][] grid, String label) {
+        long total = 0;
+        for (long[] row : grid) {
+            for (long v : row) {
+                total += v;
+            }
+        }
+        return total;
+    }
+
+    public static int[] sum(int[]... arrays) {
+        int[] totals = new int[arrays.length];
+        for (int i = 0; i < arrays.length; i++) {
+            for (int v : arrays[i]) {
+                totals[i] += v;
+            }
+        }
+        return totals;
+    }
+
+    public static int rows(int[][] grid) {
+        return grid.length;
+    }
+
+    /** 参数是二维数组的调用：操作数栈上只有一个参数 */
+    public static int rowsOfNew() {
+        return 10 + rows(new int[3][2]);
+    }
+}
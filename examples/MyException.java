@@ -0,0 +1,8 @@
+/**
+ * 自定义异常：父类 RuntimeException 不会被加载，用内置继承关系判断
+ */
+public class MyException extends RuntimeException {
+    public MyException() {
+        super();
+    }
+}
@@ -0,0 +1,37 @@
+/**
+ * 本地方法示例：native 方法由宿主（Rust）注册实现
+ */
+public class NativeDemo {
+    int factor;
+
+    NativeDemo(int factor) {
+        this.factor = factor;
+    }
+
+    static native int twice(int x);
+
+    native int scale(int x);
+
+    static native void missing();
+
+    public static int callTwice(int x) {
+        return twice(x) + 1;
+    }
+
+    public static int callScale(int x) {
+        NativeDemo demo = new NativeDemo(3);
+        return demo.scale(x);
+    }
+
+    public static int clamp(int x) {
+        return Math.min(Math.max(x, 0), 100);
+    }
+
+    public static long now() {
+        return System.currentTimeMillis();
+    }
+
+    public static void callMissing() {
+        missing();
+    }
+}
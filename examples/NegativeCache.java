@@ -0,0 +1,38 @@
+// 测试否定解析缓存：同一个调用点反复执行时只解析一次
+public class NegativeCache {
+    // Thread.dumpStack() 没有内置实现，每次调用都抛出 UnsatisfiedLinkError
+    static int unlinkedLoop() {
+        int caught = 0;
+        for (int i = 0; i < 1000; i++) {
+            try {
+                Thread.dumpStack();
+            } catch (Throwable t) {
+                caught++;
+            }
+        }
+        return caught;
+    }
+
+    // 测试把 Ghost.class 从类路径中去掉，每次调用都抛出 ClassNotFoundException
+    static int missingLoop() {
+        int caught = 0;
+        for (int i = 0; i < 1000; i++) {
+            try {
+                int value = Ghost.haunt();
+            } catch (Throwable t) {
+                caught++;
+            }
+        }
+        return caught;
+    }
+
+    static int callGhost() {
+        return Ghost.haunt();
+    }
+}
+
+class Ghost {
+    static int haunt() {
+        return 13;
+    }
+}
@@ -0,0 +1,30 @@
+public class NullCheck {
+    static int check(Object o) {
+        return o == null ? 1 : 0;
+    }
+
+    static int same(Object a, Object b) {
+        return a == b ? 1 : 0;
+    }
+
+    static int differ(Object a, Object b) {
+        return a != b ? 1 : 0;
+    }
+
+    static int nullLocal() {
+        Object o = null;
+        return check(o) + (o != null ? 10 : 0);
+    }
+
+    static int freshObject() {
+        Object o = new NullCheck();
+        return check(o);
+    }
+
+    static int identity() {
+        NullCheck a = new NullCheck();
+        NullCheck b = a;
+        NullCheck c = new NullCheck();
+        return (a == b ? 1 : 0) + (a == c ? 10 : 0) + (a != c ? 100 : 0);
+    }
+}
@@ -0,0 +1,34 @@
+/**
+ * 构造一个小对象图，供堆转储测试使用
+ *
+ * root → left/right 两个子节点，right.left 指回 root（环），children 数组引用两个子节点
+ */
+public class ObjectGraph {
+    static GraphNode root;
+
+    public static void main(String[] args) {
+        GraphNode a = new GraphNode();
+        a.value = 1;
+        a.label = "root";
+        GraphNode b = new GraphNode();
+        b.value = 2;
+        GraphNode c = new GraphNode();
+        c.value = 3;
+        a.left = b;
+        a.right = c;
+        c.left = a;
+        GraphNode[] children = new GraphNode[2];
+        children[0] = b;
+        children[1] = c;
+        a.children = children;
+        root = a;
+    }
+}
+
+class GraphNode {
+    int value;
+    String label;
+    GraphNode left;
+    GraphNode right;
+    GraphNode[] children;
+}
@@ -0,0 +1,60 @@
+/**
+ * java/lang/Object 的内置方法：identity hashCode、equals、getClass、toString
+ */
+public class ObjectMethods {
+    static class Plain {
+    }
+
+    static class FixedHash {
+        public int hashCode() {
+            return 255;
+        }
+    }
+
+    static Object kept;
+
+    static boolean sameHashTwice() {
+        Plain p = new Plain();
+        return p.hashCode() == p.hashCode();
+    }
+
+    static boolean differentHashes() {
+        return new Plain().hashCode() != new Plain().hashCode();
+    }
+
+    static boolean equalsIsIdentity() {
+        Plain a = new Plain();
+        Plain b = new Plain();
+        return a.equals(a) && !a.equals(b) && !a.equals(null);
+    }
+
+    static boolean sameClassObject() {
+        Class<?> plain = new Plain().getClass();
+        return plain == new Plain().getClass() && plain != new FixedHash().getClass();
+    }
+
+    static String className() {
+        return new Plain().getClass().getName();
+    }
+
+    /** 先制造一些垃圾再创建对象，整理时它会被移动 */
+    static int keepAfterGarbage() {
+        for (int i = 0; i < 100; i++) {
+            new Plain();
+        }
+        kept = new Plain();
+        return kept.hashCode();
+    }
+
+    static int keptHash() {
+        return kept.hashCode();
+    }
+
+    static String keptToString() {
+        return kept.toString();
+    }
+
+    static String fixedHashToString() {
+        return new FixedHash().toString();
+    }
+}
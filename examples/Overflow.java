@@ -0,0 +1,19 @@
+/**
+ * 栈帧数上限测试：没有递归出口的方法必须以 StackOverflowError 结束
+ */
+public class Overflow {
+    static int depth;
+
+    public static int recurse() {
+        depth++;
+        return recurse();
+    }
+
+    public static int catchOverflow() {
+        try {
+            return recurse();
+        } catch (StackOverflowError e) {
+            return -1;
+        }
+    }
+}
@@ -0,0 +1,20 @@
+import java.io.PrintStream;
+
+/**
+ * System.out 是普通对象：可以存入局部变量和静态字段、作为参数传给其他方法
+ */
+public class PassPrintStream {
+    static PrintStream saved;
+
+    static void greet(PrintStream out, String name) {
+        out.println("hello " + name);
+    }
+
+    public static void main(String[] args) {
+        PrintStream out = System.out;
+        greet(out, "local");
+        saved = System.out;
+        greet(saved, "field");
+        System.out.println(out == System.out);
+    }
+}
@@ -0,0 +1,26 @@
+/**
+ * Class.forName + newInstance 插件加载模式
+ *
+ * 编译时 PluginMain 只依赖 Greeter 接口，实现类名是运行时的字符串
+ */
+public class PluginMain {
+    public static int run() throws Exception {
+        Greeter g = (Greeter) Class.forName("HelloPlugin").newInstance();
+        return g.greet(20);
+    }
+
+    public static int runDefault() throws Exception {
+        Greeter g = (Greeter) Class.forName("HelloPlugin").newInstance();
+        return g.greetTwice(20);
+    }
+
+    public static int loadMissing() throws Exception {
+        Object o = Class.forName("NoSuchPlugin").newInstance();
+        return 0;
+    }
+
+    public static int loadWithoutDefaultConstructor() throws Exception {
+        Object o = Class.forName("ArgPlugin").newInstance();
+        return 0;
+    }
+}
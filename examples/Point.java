@@ -0,0 +1,88 @@
+/**
+ * 嵌入 API 创建对象：Jvm::new_instance 运行构造器，Jvm::call_virtual 调用实例方法
+ */
+public class Point {
+    /** 由 <clinit> 设置，new_instance 之前必须完成类初始化 */
+    static int origin;
+    static int created;
+
+    static {
+        origin = 100;
+    }
+
+    protected int x;
+    protected int y;
+
+    public Point(int x, int y) {
+        this.x = origin + x;
+        this.y = origin + y;
+        created++;
+    }
+
+    public Point() {
+        this(0, 0);
+    }
+
+    public int getX() {
+        return x;
+    }
+
+    public int getY() {
+        return y;
+    }
+
+    public long distanceSquared(Point other) {
+        long dx = x - other.x;
+        long dy = y - other.y;
+        return dx * dx + dy * dy;
+    }
+
+    public void move(int dx, int dy) {
+        x += dx;
+        y += dy;
+    }
+
+    public String describe() {
+        return "(" + x + ", " + y + ")";
+    }
+
+    public static int created() {
+        return created;
+    }
+}
+
+/** 只有三参数构造器：父类的 (II)V 构造器不继承 */
+class Point3D extends Point {
+    int z;
+
+    Point3D(int x, int y, int z) {
+        super(x, y);
+        this.z = z;
+    }
+
+    @Override
+    public String describe() {
+        return "(" + x + ", " + y + ", " + z + ")";
+    }
+}
+
+/** 构造器中不断分配：GC 阈值很小时，构造器运行期间就会触发回收 */
+class Trail {
+    Point[] points;
+
+    Trail(int n) {
+        points = new Point[n];
+        for (int i = 0; i < n; i++) {
+            new Point(i, i);
+            points[i] = new Point(i, 0);
+        }
+    }
+
+    int sumX() {
+        int sum = 0;
+        for (Point p : points) {
+            sum += p.getX();
+        }
+        return sum;
+    }
+}
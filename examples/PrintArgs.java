@@ -0,0 +1,21 @@
+/**
+ * 命令行参数：main 的 String[] 由 JVM 在堆上创建
+ */
+public class PrintArgs {
+    public static void main(String[] args) {
+        System.out.println(args.length);
+        if (args.length > 0) {
+            System.out.println(args[0]);
+        }
+    }
+
+    /** long 参数占两个局部变量槽位：b 在槽位 2 */
+    public static int afterLong(long a, int b) {
+        return b;
+    }
+
+    /** 实例方法：槽位 0 是 this，long 参数从槽位 1 开始 */
+    public long secondLong(long a, long b) {
+        return b;
+    }
+}
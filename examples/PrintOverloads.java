@@ -0,0 +1,30 @@
+/**
+ * System.out.print/println 的各个重载：按描述符格式化参数
+ */
+public class PrintOverloads {
+    public static void main(String[] args) {
+        boolean flag = true;
+        char letter = 'A';
+        long big = 1234567890123L;
+        double one = 1.0;
+        float half = 0.5f;
+        double tiny = 1.0E-5;
+        Object nothing = null;
+        System.out.println(flag);
+        System.out.println(letter);
+        System.out.println(big);
+        System.out.println(one);
+        System.out.println(half);
+        System.out.println(tiny);
+        System.out.println("text");
+        System.out.println(nothing);
+        System.out.println();
+        System.out.print("a");
+        System.out.print(1);
+        System.out.print('b');
+        System.out.print(false);
+        System.out.print(2.5);
+        System.out.print(-3L);
+        System.out.println();
+    }
+}
@@ -0,0 +1,20 @@
+/**
+ * 性能剖析示例：循环调用一个小方法
+ */
+public class ProfileDemo {
+    static int sumTo(int n) {
+        int total = 0;
+        for (int i = 0; i < n; i++) {
+            total = add(total, i);  // 循环变量用 iinc 递增，iadd 只出现在 add 中
+        }
+        return total;
+    }
+
+    static int add(int a, int b) {
+        return a + b;
+    }
+
+    public static void main(String[] args) {
+        System.out.println(sumTo(100));
+    }
+}
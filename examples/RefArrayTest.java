@@ -0,0 +1,50 @@
+/**
+ * 引用数组示例
+ * 验证 anewarray、aaload/aastore 和 multianewarray
+ */
+public class RefArrayTest {
+    int value;
+
+    static Object[] missing;
+
+    // String[3]，元素默认为 null
+    public static String[] makeStrings() {
+        String[] names = new String[3];
+        return names;
+    }
+
+    // 用 new 创建的对象填充数组，再读回来
+    public static int storeAndLoad() {
+        RefArrayTest[] items = new RefArrayTest[3];
+        for (int i = 0; i < items.length; i++) {
+            items[i] = new RefArrayTest();
+            items[i].value = i * 10;
+        }
+        int sum = 0;
+        for (int i = 0; i < items.length; i++) {
+            sum += items[i].value;
+        }
+        return sum; // 0 + 10 + 20
+    }
+
+    // 二维数组 int[2][3]
+    public static int twoD() {
+        int[][] grid = new int[2][3];
+        for (int i = 0; i < grid.length; i++) {
+            for (int j = 0; j < grid[i].length; j++) {
+                grid[i][j] = i * 3 + j;
+            }
+        }
+        return grid[1][2] + grid.length * 100 + grid[0].length * 10; // 5 + 200 + 30
+    }
+
+    // 向 null 数组存储
+    public static void storeIntoNull() {
+        missing[0] = new Object();
+    }
+
+    public static Object loadOutOfBounds() {
+        Object[] arr = new Object[2];
+        return arr[2];
+    }
+}
@@ -0,0 +1,73 @@
+/**
+ * 测试 JVM 抛出的运行时异常可以被 catch 捕获
+ */
+public class RuntimeExceptionTest {
+    int value;
+
+    /** 未赋值的静态字段，默认为 null */
+    static RuntimeExceptionTest missing;
+
+    static int div(int a, int b) {
+        return a / b;
+    }
+
+    public static int divideByZero() {
+        try {
+            return 1 / 0;
+        } catch (ArithmeticException e) {
+            return -1;
+        }
+    }
+
+    public static int divideCaughtAsRuntimeException() {
+        int zero = 0;
+        try {
+            return 10 / zero;
+        } catch (RuntimeException e) {
+            return -2;
+        }
+    }
+
+    public static int nullFieldRead() {
+        try {
+            return missing.value;
+        } catch (NullPointerException e) {
+            return -3;
+        }
+    }
+
+    public static int arrayIndexOutOfBounds() {
+        int[] a = new int[3];
+        try {
+            return a[5];
+        } catch (ArrayIndexOutOfBoundsException e) {
+            return -4;
+        }
+    }
+
+    public static int caughtAsIndexOutOfBounds() {
+        int[] a = new int[3];
+        try {
+            a[-1] = 1;
+            return 0;
+        } catch (IndexOutOfBoundsException e) {
+            return -5;
+        }
+    }
+
+    public static int divideInCallee() {
+        try {
+            return div(1, 0);
+        } catch (ArithmeticException e) {
+            return -6;
+        }
+    }
+
+    public static int notCaughtByWrongType() {
+        try {
+            return 1 / 0;
+        } catch (NullPointerException e) {
+            return -7;
+        }
+    }
+}
@@ -0,0 +1,12 @@
+/**
+ * 局部变量槽位演示（用 javac -g 编译以生成 LocalVariableTable）
+ *
+ * javap -l SlotDemo 可以看到：big 占槽位 1-2，count 在槽位 3
+ */
+public class SlotDemo {
+    public static long mix(int base) {
+        long big = 5000000000L;
+        int count = base + 1;
+        return big + count;
+    }
+}
@@ -0,0 +1,19 @@
+/**
+ * 执行快照的测试程序：循环中修改局部变量、静态字段、数组和 StringBuilder
+ */
+public class SnapshotLoop {
+    static int calls;
+    static int[] history = new int[4];
+
+    public static int run(int n) {
+        StringBuilder digits = new StringBuilder();
+        int total = 0;
+        for (int i = 1; i <= n; i++) {
+            total += i * i;
+            history[i % history.length] = total;
+            digits.append(i);
+            calls++;
+        }
+        return total + history[1] + digits.length() * 1000 + calls * 100000;
+    }
+}
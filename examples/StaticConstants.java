@@ -0,0 +1,22 @@
+/**
+ * static final 常量示例
+ * 用于 rsjvm parse --constants
+ */
+public class StaticConstants {
+    // 常量表达式：javac 折叠后写入 ConstantValue 属性
+    static final int N = 10 * 1024;
+    static final String NAME = "rsjvm";
+
+    // 空白 final：在 <clinit> 中赋值，没有 ConstantValue 属性
+    static final int A;
+    static final long B;
+
+    // 依赖方法调用，只能在运行时确定
+    static final long START;
+
+    static {
+        A = 6;
+        B = A * 7L;
+        START = System.nanoTime();
+    }
+}
@@ -0,0 +1,23 @@
+/**
+ * 静态字段示例
+ * 验证 getstatic / putstatic 读写类的静态字段
+ */
+public class StaticCounter {
+    static int counter;
+    static long total;
+
+    public static int setAndGet() {
+        counter = 5;
+        return counter;
+    }
+
+    public static int increment() {
+        counter = counter + 1;
+        return counter;
+    }
+
+    // 从未赋值的静态字段取默认值 0L
+    public static long readDefault() {
+        return total;
+    }
+}
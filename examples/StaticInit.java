@@ -0,0 +1,85 @@
+// 测试 <clinit>：静态初始化器在类首次主动使用时执行
+public class StaticInit {
+    static int x = computeX();
+    static int[] table;
+
+    static {
+        table = new int[3];
+        table[2] = x + 1;
+    }
+
+    static int computeX() {
+        int result = 0;
+        for (int i = 0; i < 7; i++) {
+            result += 6;
+        }
+        return result;
+    }
+
+    public static void main(String[] args) {
+        System.out.println(x);
+    }
+
+    static int getX() {
+        return x;
+    }
+
+    static int tableValue() {
+        return table[2];
+    }
+
+    // 父类先于子类初始化：InitBase 拿到 1，InitDerived 拿到 2
+    static int initOrder() {
+        return InitDerived.order();
+    }
+
+    // new 也会触发初始化
+    static int newTriggersInit() {
+        InitCounted first = new InitCounted();
+        InitCounted second = new InitCounted();
+        return InitCounted.initRuns;
+    }
+
+    static int selfReference() {
+        return SelfRef.a;
+    }
+}
+
+class InitOrder {
+    static int next;
+
+    static int tick() {
+        next = next + 1;
+        return next;
+    }
+}
+
+class InitBase {
+    static int baseOrder = InitOrder.tick();
+}
+
+class InitDerived extends InitBase {
+    static int derivedOrder = InitOrder.tick();
+
+    static int order() {
+        return derivedOrder * 10 + InitBase.baseOrder;
+    }
+}
+
+class InitCounted {
+    static int initRuns;
+
+    static {
+        initRuns = initRuns + 1;
+    }
+}
+
+// <clinit> 里调用自己的静态方法：类正在初始化，不会再次触发初始化
+class SelfRef {
+    static int a = helper();
+    static int b = 5;
+
+    static int helper() {
+        return b + 1;
+    }
+}
@@ -0,0 +1,61 @@
+/**
+ * String 的内置方法：equals、hashCode、length、charAt、substring
+ */
+public class StringOps {
+    static int hash(String s) {
+        return s.hashCode();
+    }
+
+    static int length(String s) {
+        return s.length();
+    }
+
+    static int charAt(String s, int index) {
+        return s.charAt(index);
+    }
+
+    static String substring(String s, int begin, int end) {
+        return s.substring(begin, end);
+    }
+
+    /** 内容相同但不是同一个对象的两个字符串 */
+    static boolean equalsBuilt() {
+        String built = new StringBuilder("hel").append("lo").toString();
+        String other = "hello".substring(0, 5);
+        return built.equals(other) && other != "hello" && !"hello".equals("world") && !"hello".equals(null);
+    }
+
+    /** 通过 Object 引用调用，也要分派到 String 的实现 */
+    static boolean equalsViaObject(Object a, Object b) {
+        return a.equals(b) && a.hashCode() == b.hashCode();
+    }
+
+    /** 统计元音，用 charAt 逐个读取 */
+    static int vowels(String s) {
+        int count = 0;
+        for (int i = 0; i < s.length(); i++) {
+            char c = s.charAt(i);
+            if (c == 'a' || c == 'e' || c == 'i' || c == 'o' || c == 'u') {
+                count++;
+            }
+        }
+        return count;
+    }
+
+    static int outOfBounds(String s, int index) {
+        try {
+            return s.charAt(index);
+        } catch (StringIndexOutOfBoundsException e) {
+            return -1;
+        }
+    }
+
+    public static void main(String[] args) {
+        String s = "hello, world";
+        System.out.println(s.length());
+        System.out.println(s.hashCode());
+        System.out.println(s.substring(7, 12));
+        System.out.println(s.substring(7).equals("world"));
+        System.out.println(vowels(s));
+    }
+}
@@ -0,0 +1,38 @@
+/**
+ * switch 示例
+ * 稠密的 case 编译为 tableswitch，稀疏的 case 编译为 lookupswitch
+ */
+public class SwitchTest {
+    // case 0..3 连续 → tableswitch
+    public static int dense(int key) {
+        switch (key) {
+            case 0: return 10;
+            case 1: return 11;
+            case 2: return 12;
+            case 3: return 13;
+            default: return -1;
+        }
+    }
+
+    // case 1, 100, 10000 稀疏 → lookupswitch
+    public static int sparse(int key) {
+        switch (key) {
+            case 1: return 1;
+            case 100: return 2;
+            case 10000: return 3;
+            default: return 0;
+        }
+    }
+
+    public static int dense0() { return dense(0); }
+    public static int dense1() { return dense(1); }
+    public static int dense2() { return dense(2); }
+    public static int dense3() { return dense(3); }
+    public static int denseDefault() { return dense(7); }
+    public static int denseNegative() { return dense(-5); }
+
+    public static int sparse1() { return sparse(1); }
+    public static int sparse100() { return sparse(100); }
+    public static int sparse10000() { return sparse(10000); }
+    public static int sparseDefault() { return sparse(50); }
+}
@@ -0,0 +1,113 @@
+/**
+ * synchronized 方法和代码块：监视器的重入、多个线程对共享字段的互斥访问，
+ * 以及异常离开 synchronized 方法时释放监视器
+ */
+public class SyncDemo {
+    private int count;
+    private final int[] lock = new int[0];
+    private static int staticCount;
+
+    /** 读出字段后空转一段时间再写回：时间片很可能在中间用完 */
+    void incrementUnsafe() {
+        int c = count;
+        spin();
+        count = c + 1;
+    }
+
+    synchronized void increment() {
+        int c = count;
+        spin();
+        count = c + 1;
+    }
+
+    void incrementInBlock() {
+        synchronized (lock) {
+            int c = count;
+            spin();
+            count = c + 1;
+        }
+    }
+
+    static synchronized void incrementStatic() {
+        int c = staticCount;
+        spin();
+        staticCount = c + 1;
+    }
+
+    static void spin() {
+        for (int i = 0; i < 50; i++) {
+        }
+    }
+
+    synchronized int reenter(int depth) {
+        if (depth == 0) {
+            return 0;
+        }
+        synchronized (this) {
+            return 1 + reenter(depth - 1);
+        }
+    }
+
+    synchronized void fail() {
+        throw new IllegalStateException("inside synchronized");
+    }
+
+    static class Worker implements Runnable {
+        private final SyncDemo demo;
+        private final int mode;
+
+        Worker(SyncDemo demo, int mode) {
+            this.demo = demo;
+            this.mode = mode;
+        }
+
+        public void run() {
+            for (int i = 0; i < 200; i++) {
+                if (mode == 0) {
+                    demo.incrementUnsafe();
+                } else if (mode == 1) {
+                    demo.increment();
+                } else if (mode == 2) {
+                    demo.incrementInBlock();
+                } else {
+                    SyncDemo.incrementStatic();
+                }
+            }
+        }
+    }
+
+    /** 两个线程各调用 200 次自增，mode 选择自增的方式；返回最终的计数 */
+    static int race(int mode) throws InterruptedException {
+        SyncDemo demo = new SyncDemo();
+        staticCount = 0;
+        Thread a = new Thread(new Worker(demo, mode));
+        Thread b = new Thread(new Worker(demo, mode));
+        a.start();
+        b.start();
+        a.join();
+        b.join();
+        return mode == 3 ? staticCount : demo.count;
+    }
+
+    static int reentrant() {
+        return new SyncDemo().reenter(5);
+    }
+
+    /** 异常离开 synchronized 方法后，其他线程仍然可以获取同一个监视器 */
+    static int releasedOnException() throws InterruptedException {
+        SyncDemo demo = new SyncDemo();
+        try {
+            demo.fail();
+        } catch (IllegalStateException e) {
+            // 监视器应该已经释放
+        }
+        Thread t = new Thread(new Worker(demo, 1));
+        t.start();
+        t.join();
+        return demo.count;
+    }
+
+    public static void main(String[] args) throws InterruptedException {
+        System.out.println(race(1));
+    }
+}
@@ -0,0 +1,96 @@
+/**
+ * 线程：两个线程各自累加一个静态字段，主线程 join 等待它们结束
+ */
+public class ThreadDemo {
+    static int first;
+    static int second;
+
+    static int runBoth() throws InterruptedException {
+        Thread a = new Thread(new Incrementer());
+        Thread b = new SecondIncrementer();
+        a.start();
+        b.start();
+        a.join();
+        b.join();
+        return first * 10000 + second;
+    }
+
+    static boolean aliveAfterJoin() throws InterruptedException {
+        Thread t = new SecondIncrementer();
+        t.start();
+        t.join();
+        return t.isAlive();
+    }
+
+    static boolean startTwice() {
+        Thread t = new SecondIncrementer();
+        t.start();
+        try {
+            t.start();
+            return false;
+        } catch (IllegalThreadStateException e) {
+            return true;
+        }
+    }
+
+    /** 守护线程不会让程序等待：死循环的守护线程随入口方法一起结束 */
+    static int daemonSpinner() {
+        Thread t = new Thread(new Spinner(), "spinner");
+        t.setDaemon(true);
+        t.start();
+        return 7;
+    }
+
+    /** 线程中未捕获的异常只结束那个线程 */
+    static int survivesThrowingThread() throws InterruptedException {
+        Thread t = new Thread(new Thrower());
+        t.start();
+        t.join();
+        return 1;
+    }
+
+    /** main 返回后，程序等待没有 join 的非守护线程结束 */
+    public static void main(String[] args) {
+        new Thread(new Printer()).start();
+        System.out.println("main done");
+    }
+}
+
+class Incrementer implements Runnable {
+    public void run() {
+        for (int i = 0; i < 1000; i++) {
+            ThreadDemo.first++;
+        }
+    }
+}
+
+class SecondIncrementer extends Thread {
+    public void run() {
+        for (int i = 0; i < 1000; i++) {
+            ThreadDemo.second++;
+        }
+    }
+}
+
+class Printer implements Runnable {
+    public void run() {
+        int sum = 0;
+        for (int i = 0; i < 5000; i++) {
+            sum += i;
+        }
+        System.out.println("worker done " + sum);
+    }
+}
+
+class Spinner implements Runnable {
+    public void run() {
+        while (true) {
+        }
+    }
+}
+
+class Thrower implements Runnable {
+    public void run() {
+        throw new IllegalStateException("boom");
+    }
+}
@@ -0,0 +1,67 @@
+/**
+ * 测试 invokevirtual 动态分派
+ *
+ * 变量类型是 Shape，实际调用的是运行时类型的方法
+ */
+class Shape {
+    protected int size;
+
+    Shape(int size) {
+        this.size = size;
+    }
+
+    int area() {
+        return 0;
+    }
+
+    int doubleArea() {
+        return area() * 2;
+    }
+}
+
+class Square extends Shape {
+    Square(int size) {
+        super(size);
+    }
+
+    int area() {
+        return size * size;
+    }
+}
+
+/** 没有覆盖 area()，继承 Square 的实现 */
+class BigSquare extends Square {
+    BigSquare(int size) {
+        super(size * 10);
+    }
+}
+
+public class VirtualDispatch {
+    /** 未赋值的静态字段，默认为 null */
+    static Shape missing;
+
+    public static int squareArea() {
+        Shape s = new Square(5);
+        return s.area();
+    }
+
+    public static int baseArea() {
+        Shape s = new Shape(5);
+        return s.area();
+    }
+
+    public static int inheritedArea() {
+        Shape s = new BigSquare(2);
+        return s.area();
+    }
+
+    public static int selfCall() {
+        // Shape.doubleArea 里的 this.area() 也要分派到 Square
+        Shape s = new Square(3);
+        return s.doubleArea();
+    }
+
+    public static int callOnNull() {
+        return missing.area();
+    }
+}
@@ -0,0 +1,43 @@
+/**
+ * long/double 参数和局部变量占两个槽位
+ *
+ * f(int a, long b, int c) 的局部变量布局：a → 0，b → 1-2，c → 3
+ */
+public class WideArgs {
+    static long f(int a, long b, int c) {
+        long sum = b;
+        for (int i = a; i < c; i++) {
+            sum += b;
+        }
+        return sum;
+    }
+
+    static double g(double x, int n, double y) {
+        double result = x;
+        for (int i = 0; i < n; i++) {
+            result = result + y;
+        }
+        return result;
+    }
+
+    private long h(long a, int times) {
+        long result = 0L;
+        for (int i = 0; i < times; i++) {
+            result += a;
+        }
+        return result;
+    }
+
+    static long callF() {
+        return f(1, 10L, 4);
+    }
+
+    static double callG() {
+        return g(0.5, 3, 2.0);
+    }
+
+    static long callH() {
+        WideArgs w = new WideArgs();
+        return w.h(7L, 6);
+    }
+}
@@ -0,0 +1,15 @@
+package cpdemo;
+
+import cpdemo.util.Greeting;
+
+/**
+ * 类路径示例：入口类和它用到的类在不同的包里，
+ * 用 `rsjvm run -cp examples cpdemo.Main` 按类名启动
+ */
+public class Main {
+    public static void main(String[] args) {
+        String name = args.length > 0 ? args[0] : "world";
+        System.out.println(Greeting.greet(name));
+        System.out.println(Greeting.count());
+    }
+}
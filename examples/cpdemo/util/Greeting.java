@@ -0,0 +1,17 @@
+package cpdemo.util;
+
+/**
+ * 被 cpdemo.Main 按需加载的类（位于另一个包）
+ */
+public class Greeting {
+    private static int calls;
+
+    public static String greet(String name) {
+        calls++;
+        return "Hello, " + name + "!";
+    }
+
+    public static int count() {
+        return calls;
+    }
+}
@@ -18,7 +18,7 @@ fn main() {
 
     // 第1条指令: iconst_1 (0x04)
     println!("\n执行指令 PC={}: iconst_1 (0x{:02x})", pc, code[pc]);
-    frame.push(JvmValue::Int(1));
+    frame.push(JvmValue::Int(1)).unwrap();
     pc += 1;
     println!("  栈: push(1)");
     println!("  栈大小: {}", frame.stack_size());
@@ -0,0 +1,14 @@
+package modern.api;
+
+/**
+ * 用 --release 17 编译的普通类（major version 61）
+ */
+public class Greeter {
+    public static int answer() {
+        var base = 40;
+        return switch (base) {
+            case 40 -> base + 2;
+            default -> 0;
+        };
+    }
+}
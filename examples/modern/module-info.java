@@ -0,0 +1,8 @@
+/**
+ * Java 9+ 模块描述：编译出的 module-info.class 含有 CONSTANT_Module 和 CONSTANT_Package
+ *
+ * 编译：cd examples/modern && javac -encoding UTF-8 --release 17 -d . module-info.java modern/api/Greeter.java
+ */
+module modern {
+    exports modern.api;
+}
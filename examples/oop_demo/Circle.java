@@ -0,0 +1,13 @@
+public class Circle extends Shape {
+    private double radius;
+
+    public Circle(double radius) {
+        super(0);
+        this.radius = radius;
+    }
+
+    @Override
+    public double area() {
+        return Math.PI * radius * radius;
+    }
+}
@@ -0,0 +1,24 @@
+/**
+ * 面向对象里程碑的端到端场景
+ *
+ * 覆盖：new + 带参数的构造器、putfield/getfield、anewarray/aastore/aaload、
+ * invokevirtual 动态分派、arraylength 驱动的循环、double 运算、println(double)
+ */
+public class OopDemo {
+    public static void main(String[] args) {
+        Shape[] shapes = new Shape[3];
+        shapes[0] = new Circle(1.0);
+        shapes[1] = new Rect(3.0, 4.0);
+        shapes[2] = new Circle(0.5);
+
+        double total = 0.0;
+        for (int i = 0; i < shapes.length; i++) {
+            Shape shape = shapes[i];
+            double area = shape.area();
+            System.out.println(shape.getSides());
+            System.out.println(area);
+            total = total + area;
+        }
+        System.out.println(total);
+    }
+}
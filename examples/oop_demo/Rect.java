@@ -0,0 +1,15 @@
+public class Rect extends Shape {
+    private double width;
+    private double height;
+
+    public Rect(double width, double height) {
+        super(4);
+        this.width = width;
+        this.height = height;
+    }
+
+    @Override
+    public double area() {
+        return width * height;
+    }
+}
@@ -0,0 +1,16 @@
+/**
+ * 抽象基类：字段由子类构造器通过 super(...) 设置
+ */
+public abstract class Shape {
+    protected int sides;
+
+    protected Shape(int sides) {
+        this.sides = sides;
+    }
+
+    public abstract double area();
+
+    public int getSides() {
+        return sides;
+    }
+}
@@ -10,6 +10,8 @@
 //!
 //! 或者作为集成测试放到 tests/ 目录
 
+#![allow(deprecated)]
+
 use rsjvm::interpreter::Interpreter;
 
 fn main() {
@@ -25,6 +25,7 @@ fn main() -> Result<()> {
     println!("--- 程序输出开始 ---");
     let result = interpreter.execute_method_with_class(
         &class_name,
+        "main:([Ljava/lang/String;)V",
         &code,
         max_locals,
         max_stack,
@@ -1,12 +1,19 @@
 use std::mem;
 
+/// 对象引用：槽位索引 + 代数（同 rsjvm::runtime::ObjRef）
+#[derive(Debug, Clone, Copy)]
+pub struct ObjRef {
+    pub index: u32,
+    pub generation: u32,
+}
+
 #[derive(Debug, Clone)]
 pub enum JvmValue {
     Int(i32),
     Long(i64),
     Float(f32),
     Double(f64),
-    Reference(Option<usize>),
+    Reference(Option<ObjRef>),
 }
 
 fn main() {
@@ -18,7 +25,7 @@ fn main() {
     println!("  i64:          {} bytes", mem::size_of::<i64>());
     println!("  f32:          {} bytes", mem::size_of::<f32>());
     println!("  f64:          {} bytes", mem::size_of::<f64>());
-    println!("  Option<usize>:{} bytes", mem::size_of::<Option<usize>>());
+    println!("  Option<ObjRef>:{} bytes", mem::size_of::<Option<ObjRef>>());
 
     println!("\nJvmValue枚举大小:");
     println!("  整个枚举:     {} bytes", mem::size_of::<JvmValue>());
@@ -27,15 +34,15 @@ fn main() {
     println!("\n实际的内存布局:");
     println!("  判别标签(discriminant): 通常 1-8 bytes");
     println!("  数据部分: max(各variant) = {} bytes",
-             mem::size_of::<i64>().max(mem::size_of::<Option<usize>>()));
+             mem::size_of::<i64>().max(mem::size_of::<Option<ObjRef>>()));
     println!("  加上padding对齐");
 
     println!("\n创建不同variant:");
     let v_int = JvmValue::Int(42);
     let v_long = JvmValue::Long(42);
-    let v_float = JvmValue::Float(3.14);
-    let v_double = JvmValue::Double(3.14);
-    let v_ref = JvmValue::Reference(Some(0));
+    let v_float = JvmValue::Float(2.5);
+    let v_double = JvmValue::Double(2.5);
+    let v_ref = JvmValue::Reference(Some(ObjRef { index: 0, generation: 0 }));
 
     println!("  Int:       {:?} - 占用 {} bytes", v_int, mem::size_of_val(&v_int));
     println!("  Long:      {:?} - 占用 {} bytes", v_long, mem::size_of_val(&v_long));
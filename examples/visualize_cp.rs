@@ -30,38 +30,36 @@ fn main() -> anyhow::Result<()> {
 
             // 追踪 class_index
             println!("\n追踪类引用 #{}:", class_index);
-            match class_file.constant_pool.get(*class_index)? {
-                ConstantPoolEntry::Class { name_index } => {
-                    println!("  [#{}] Class", class_index);
-                    println!("    └─ name_index: #{}", name_index);
-
-                    // 追踪类名
-                    println!("\n  追踪类名 #{}:", name_index);
-                    let class_name = class_file.constant_pool.get_utf8(*name_index)?;
-                    println!("    [#{}] Utf8(\"{}\")", name_index, class_name);
-                }
-                _ => {}
+            if let ConstantPoolEntry::Class { name_index } =
+                class_file.constant_pool.get(*class_index)?
+            {
+                println!("  [#{}] Class", class_index);
+                println!("    └─ name_index: #{}", name_index);
+
+                // 追踪类名
+                println!("\n  追踪类名 #{}:", name_index);
+                let class_name = class_file.constant_pool.get_utf8(*name_index)?;
+                println!("    [#{}] Utf8(\"{}\")", name_index, class_name);
             }
 
             // 追踪 name_and_type_index
             println!("\n追踪名称和类型 #{}:", name_and_type_index);
-            match class_file.constant_pool.get(*name_and_type_index)? {
-                ConstantPoolEntry::NameAndType { name_index, descriptor_index } => {
-                    println!("  [#{}] NameAndType", name_and_type_index);
-                    println!("    ├─ name_index: #{}", name_index);
-                    println!("    └─ descriptor_index: #{}", descriptor_index);
-
-                    // 追踪方法名
-                    println!("\n  追踪方法名 #{}:", name_index);
-                    let method_name = class_file.constant_pool.get_utf8(*name_index)?;
-                    println!("    [#{}] Utf8(\"{}\")", name_index, method_name);
-
-                    // 追踪描述符
-                    println!("\n  追踪描述符 #{}:", descriptor_index);
-                    let descriptor = class_file.constant_pool.get_utf8(*descriptor_index)?;
-                    println!("    [#{}] Utf8(\"{}\")", descriptor_index, descriptor);
-                }
-                _ => {}
+            if let ConstantPoolEntry::NameAndType { name_index, descriptor_index } =
+                class_file.constant_pool.get(*name_and_type_index)?
+            {
+                println!("  [#{}] NameAndType", name_and_type_index);
+                println!("    ├─ name_index: #{}", name_index);
+                println!("    └─ descriptor_index: #{}", descriptor_index);
+
+                // 追踪方法名
+                println!("\n  追踪方法名 #{}:", name_index);
+                let method_name = class_file.constant_pool.get_utf8(*name_index)?;
+                println!("    [#{}] Utf8(\"{}\")", name_index, method_name);
+
+                // 追踪描述符
+                println!("\n  追踪描述符 #{}:", descriptor_index);
+                let descriptor = class_file.constant_pool.get_utf8(*descriptor_index)?;
+                println!("    [#{}] Utf8(\"{}\")", descriptor_index, descriptor);
             }
 
             println!("\n=== 完整引用链 ===");
@@ -77,26 +75,27 @@ fn main() -> anyhow::Result<()> {
     // 统计常量池的类型分布
     println!("\n\n=== 常量池类型统计 ===");
     let mut type_counts = std::collections::HashMap::new();
-    for entry in &class_file.constant_pool.entries {
-        if let Some(e) = entry {
-            let type_name = match e {
-                ConstantPoolEntry::Utf8(_) => "Utf8",
-                ConstantPoolEntry::Integer(_) => "Integer",
-                ConstantPoolEntry::Float(_) => "Float",
-                ConstantPoolEntry::Long(_) => "Long",
-                ConstantPoolEntry::Double(_) => "Double",
-                ConstantPoolEntry::Class { .. } => "Class",
-                ConstantPoolEntry::String { .. } => "String",
-                ConstantPoolEntry::FieldRef { .. } => "FieldRef",
-                ConstantPoolEntry::MethodRef { .. } => "MethodRef",
-                ConstantPoolEntry::InterfaceMethodRef { .. } => "InterfaceMethodRef",
-                ConstantPoolEntry::NameAndType { .. } => "NameAndType",
-                ConstantPoolEntry::MethodHandle { .. } => "MethodHandle",
-                ConstantPoolEntry::MethodType { .. } => "MethodType",
-                ConstantPoolEntry::InvokeDynamic { .. } => "InvokeDynamic",
-            };
-            *type_counts.entry(type_name).or_insert(0) += 1;
-        }
+    for e in class_file.constant_pool.entries.iter().flatten() {
+        let type_name = match e {
+            ConstantPoolEntry::Utf8(_) => "Utf8",
+            ConstantPoolEntry::Integer(_) => "Integer",
+            ConstantPoolEntry::Float(_) => "Float",
+            ConstantPoolEntry::Long(_) => "Long",
+            ConstantPoolEntry::Double(_) => "Double",
+            ConstantPoolEntry::Class { .. } => "Class",
+            ConstantPoolEntry::String { .. } => "String",
+            ConstantPoolEntry::FieldRef { .. } => "FieldRef",
+            ConstantPoolEntry::MethodRef { .. } => "MethodRef",
+            ConstantPoolEntry::InterfaceMethodRef { .. } => "InterfaceMethodRef",
+            ConstantPoolEntry::NameAndType { .. } => "NameAndType",
+            ConstantPoolEntry::MethodHandle { .. } => "MethodHandle",
+            ConstantPoolEntry::MethodType { .. } => "MethodType",
+            ConstantPoolEntry::InvokeDynamic { .. } => "InvokeDynamic",
+            ConstantPoolEntry::Dynamic { .. } => "Dynamic",
+            ConstantPoolEntry::Module { .. } => "Module",
+            ConstantPoolEntry::Package { .. } => "Package",
+        };
+        *type_counts.entry(type_name).or_insert(0) += 1;
     }
 
     for (type_name, count) in type_counts.iter() {
@@ -8,9 +8,18 @@
 //! - SourceFile: 源文件名
 //! - LineNumberTable: 行号表
 //! - LocalVariableTable: 局部变量表
+//! - ConstantValue: static final 字段的编译期常量
+//! - Exceptions: 方法声明抛出的异常（throws 子句）
+//! - Signature: 泛型签名（擦除前的类型信息）
+//! - BootstrapMethods: invokedynamic 调用点的引导方法
+//!
+//! ## 学习要点
+//! - 所有属性都是 `name_index + length + info` 的统一外壳，虚拟机不认识的属性可以直接跳过
+//! - `AttributeInfo::parse` 按名字把 info 解码成 `Attribute`，未识别的属性保留为 `Unknown`
 
+use super::constant_pool::ConstantPool;
 use crate::Result;
-use anyhow::Context;
+use anyhow::{anyhow, Context};
 use byteorder::{BigEndian, ReadBytesExt};
 use std::io::Cursor;
 
@@ -45,7 +54,158 @@ pub struct ExceptionHandler {
     pub catch_type: u16,
 }
 
+/// 解码后的属性
+#[derive(Debug)]
+pub enum Attribute {
+    Code(CodeAttribute),
+    /// 源文件名
+    SourceFile(String),
+    /// 方法声明抛出的异常类名（内部形式，如 `java/io/IOException`）
+    Exceptions(Vec<String>),
+    /// 常量池索引，指向 Integer/Long/Float/Double/String 常量
+    ConstantValue(u16),
+    /// 泛型签名，如 `<T:Ljava/lang/Object;>(TT;)Ljava/util/List<TT;>;`
+    Signature(String),
+    LineNumberTable(Vec<(u16, u16)>),
+    LocalVariableTable(Vec<LocalVariableEntry>),
+    /// 引导方法表，InvokeDynamic 常量的 `bootstrap_method_attr_index` 是这里的下标
+    BootstrapMethods(Vec<BootstrapMethod>),
+    /// 暂不解析的属性，只保留名字
+    Unknown(String),
+}
+
+/// LocalVariableTable 中的一项（javac -g 时生成）
+///
+/// 变量在 `[start_pc, start_pc + length)` 范围内有效，占用局部变量表的 `index` 槽位；
+/// long/double 变量还会占用 `index + 1`
+#[derive(Debug, Clone)]
+pub struct LocalVariableEntry {
+    pub start_pc: u16,
+    pub length: u16,
+    pub name_index: u16,
+    pub descriptor_index: u16,
+    pub index: u16,
+}
+
+/// BootstrapMethods 中的一项
+#[derive(Debug, Clone)]
+pub struct BootstrapMethod {
+    /// 常量池索引，指向引导方法的 MethodHandle
+    pub method_ref: u16,
+    /// 静态参数的常量池索引（如 StringConcatFactory 的配方字符串）
+    pub arguments: Vec<u16>,
+}
+
+impl CodeAttribute {
+    /// 行号表：(start_pc, 源代码行号)，按 start_pc 排序
+    ///
+    /// 一个 Code 属性可以有多个 LineNumberTable，这里合并在一起；没有时返回空列表
+    pub fn line_number_table(&self, cp: &ConstantPool) -> Result<Vec<(u16, u16)>> {
+        let mut table = Vec::new();
+        for attr in self.named_attributes(cp, "LineNumberTable")? {
+            table.extend(attr.parse_line_number_table()?);
+        }
+        table.sort_by_key(|&(start_pc, _)| start_pc);
+        Ok(table)
+    }
+
+    /// 局部变量表（javac -g 时才有）；没有时返回空列表
+    pub fn local_variable_table(&self, cp: &ConstantPool) -> Result<Vec<LocalVariableEntry>> {
+        let mut table = Vec::new();
+        for attr in self.named_attributes(cp, "LocalVariableTable")? {
+            table.extend(attr.parse_local_variable_table()?);
+        }
+        Ok(table)
+    }
+
+    fn named_attributes(&self, cp: &ConstantPool, name: &str) -> Result<Vec<&AttributeInfo>> {
+        let mut found = Vec::new();
+        for attr in &self.attributes {
+            if cp.get_utf8(attr.name_index)? == name {
+                found.push(attr);
+            }
+        }
+        Ok(found)
+    }
+}
+
+/// 在属性表中按名字查找第一个属性
+pub fn find_attribute<'a>(
+    attributes: &'a [AttributeInfo],
+    cp: &ConstantPool,
+    name: &str,
+) -> Result<Option<&'a AttributeInfo>> {
+    for attr in attributes {
+        if cp.get_utf8(attr.name_index)? == name {
+            return Ok(Some(attr));
+        }
+    }
+    Ok(None)
+}
+
 impl AttributeInfo {
+    /// 按属性名解码
+    pub fn parse(&self, cp: &ConstantPool) -> Result<Attribute> {
+        let name = cp.get_utf8(self.name_index)?;
+        let attribute = match name.as_str() {
+            "Code" => Attribute::Code(self.parse_code_attribute()?),
+            "SourceFile" => Attribute::SourceFile(self.parse_source_file(cp)?),
+            "Exceptions" => Attribute::Exceptions(self.parse_exceptions(cp)?),
+            "ConstantValue" => Attribute::ConstantValue(self.parse_constant_value()?),
+            "Signature" => Attribute::Signature(self.parse_signature(cp)?),
+            "LineNumberTable" => Attribute::LineNumberTable(self.parse_line_number_table()?),
+            "LocalVariableTable" => {
+                Attribute::LocalVariableTable(self.parse_local_variable_table()?)
+            }
+            "BootstrapMethods" => Attribute::BootstrapMethods(self.parse_bootstrap_methods()?),
+            _ => Attribute::Unknown(name),
+        };
+        Ok(attribute)
+    }
+
+    /// 读取只有一个 u16 的属性（SourceFile、ConstantValue、Signature）
+    fn single_index(&self, what: &str) -> Result<u16> {
+        if self.info.len() != 2 {
+            return Err(anyhow!(
+                "{} attribute must be 2 bytes long, got {}",
+                what,
+                self.info.len()
+            ));
+        }
+        Ok(u16::from_be_bytes([self.info[0], self.info[1]]))
+    }
+
+    /// 解析为 SourceFile 属性：源文件名
+    pub fn parse_source_file(&self, cp: &ConstantPool) -> Result<String> {
+        cp.get_utf8(self.single_index("SourceFile")?)
+    }
+
+    /// 解析为 ConstantValue 属性：常量池索引
+    pub fn parse_constant_value(&self) -> Result<u16> {
+        self.single_index("ConstantValue")
+    }
+
+    /// 解析为 Signature 属性：泛型签名字符串
+    pub fn parse_signature(&self, cp: &ConstantPool) -> Result<String> {
+        cp.get_utf8(self.single_index("Signature")?)
+    }
+
+    /// 解析为 Exceptions 属性：throws 子句中的类名
+    pub fn parse_exceptions(&self, cp: &ConstantPool) -> Result<Vec<String>> {
+        let mut reader = Cursor::new(&self.info);
+        let count = reader
+            .read_u16::<BigEndian>()
+            .context("Failed to read number_of_exceptions")?;
+        let mut exceptions = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            let index = reader
+                .read_u16::<BigEndian>()
+                .context("Truncated exception_index_table")?;
+            exceptions.push(cp.get_class_name(index)?);
+        }
+        Ok(exceptions)
+    }
+
     /// 解析为Code属性
     pub fn parse_code_attribute(&self) -> Result<CodeAttribute> {
         let mut reader = Cursor::new(&self.info);
@@ -92,4 +252,64 @@ impl AttributeInfo {
             attributes,
         })
     }
+
+    /// 解析为 LineNumberTable 属性（Code 属性的子属性）：(start_pc, line_number)
+    pub fn parse_line_number_table(&self) -> Result<Vec<(u16, u16)>> {
+        let mut reader = Cursor::new(&self.info);
+        let table_length = reader
+            .read_u16::<BigEndian>()
+            .context("Failed to read line_number_table_length")?;
+        let mut entries = Vec::with_capacity(table_length as usize);
+        for _ in 0..table_length {
+            entries.push((reader.read_u16::<BigEndian>()?, reader.read_u16::<BigEndian>()?));
+        }
+        Ok(entries)
+    }
+
+    /// 解析为 LocalVariableTable 属性（Code 属性的子属性）
+    pub fn parse_local_variable_table(&self) -> Result<Vec<LocalVariableEntry>> {
+        let mut reader = Cursor::new(&self.info);
+        let table_length = reader
+            .read_u16::<BigEndian>()
+            .context("Failed to read local_variable_table_length")?;
+        let mut entries = Vec::with_capacity(table_length as usize);
+        for _ in 0..table_length {
+            entries.push(LocalVariableEntry {
+                start_pc: reader.read_u16::<BigEndian>()?,
+                length: reader.read_u16::<BigEndian>()?,
+                name_index: reader.read_u16::<BigEndian>()?,
+                descriptor_index: reader.read_u16::<BigEndian>()?,
+                index: reader.read_u16::<BigEndian>()?,
+            });
+        }
+        Ok(entries)
+    }
+
+    /// 解析为 BootstrapMethods 属性（类的属性）
+    pub fn parse_bootstrap_methods(&self) -> Result<Vec<BootstrapMethod>> {
+        let mut reader = Cursor::new(&self.info);
+        let count = reader
+            .read_u16::<BigEndian>()
+            .context("Failed to read num_bootstrap_methods")?;
+        let mut methods = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            let method_ref = reader
+                .read_u16::<BigEndian>()
+                .context("Truncated bootstrap_methods")?;
+            let argument_count = reader.read_u16::<BigEndian>()?;
+            let mut arguments = Vec::with_capacity(argument_count as usize);
+            for _ in 0..argument_count {
+                arguments.push(
+                    reader
+                        .read_u16::<BigEndian>()
+                        .context("Truncated bootstrap_arguments")?,
+                );
+            }
+            methods.push(BootstrapMethod {
+                method_ref,
+                arguments,
+            });
+        }
+        Ok(methods)
+    }
 }
@@ -0,0 +1,508 @@
+//! # Class文件构造器
+//!
+//! 在代码里直接拼出一个 [`ClassFile`]，测试用例不再需要 JDK 和预编译的 .class 文件。
+//!
+//! ```
+//! use rsjvm::classfile::builder::ClassFileBuilder;
+//! use rsjvm::classfile::access_flags::{ACC_PUBLIC, ACC_STATIC};
+//!
+//! let class_file = ClassFileBuilder::new("Five")
+//!     .method(ACC_PUBLIC | ACC_STATIC, "five", "()I", 1, 1, |code| {
+//!         code.iconst(5).istore(0).iload(0).ireturn();
+//!     })
+//!     .build()
+//!     .unwrap();
+//! assert_eq!(class_file.get_class_name().unwrap(), "Five");
+//! ```
+//!
+//! ## 学习要点
+//! - 常量池项按需追加并去重：同一个类名、方法引用只占一个索引
+//! - 跳转用 [`Label`] 表示，先占位、绑定位置后回填 16 位偏移
+//! - max_stack/max_locals 由调用方给出（不做栈深度推导）
+
+use super::attribute::AttributeInfo;
+use super::constant_pool::{ConstantPool, ConstantPoolEntry};
+use super::{access_flags, ClassFile, FieldInfo, MethodInfo};
+use crate::interpreter::instructions::opcodes::*;
+use crate::Result;
+use anyhow::anyhow;
+
+/// 跳转目标，由 [`CodeBuilder::new_label`] 创建，[`CodeBuilder::bind`] 绑定位置
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Label(usize);
+
+/// 待回填的跳转：(指令 pc, 偏移所在位置, 目标)
+struct Fixup {
+    pc: usize,
+    at: usize,
+    label: Label,
+}
+
+/// 方法体构造器，指令方法都返回 `&mut Self` 以便链式调用
+///
+/// 参数错误（如局部变量索引超过 255）不会立即报告，而是在 [`ClassFileBuilder::build`] 时返回
+pub struct CodeBuilder<'a> {
+    pool: &'a mut ConstantPool,
+    code: Vec<u8>,
+    labels: Vec<Option<usize>>,
+    fixups: Vec<Fixup>,
+    /// (start, end, handler, catch_type 常量池索引)
+    handlers: Vec<(Label, Label, Label, u16)>,
+    error: Option<anyhow::Error>,
+}
+
+impl<'a> CodeBuilder<'a> {
+    fn new(pool: &'a mut ConstantPool) -> Self {
+        CodeBuilder {
+            pool,
+            code: Vec::new(),
+            labels: Vec::new(),
+            fixups: Vec::new(),
+            handlers: Vec::new(),
+            error: None,
+        }
+    }
+
+    /// 记录第一个错误
+    fn fail(&mut self, error: anyhow::Error) -> &mut Self {
+        self.error.get_or_insert(error);
+        self
+    }
+
+    /// 当前位置（下一条指令的 pc）
+    pub fn pc(&self) -> usize {
+        self.code.len()
+    }
+
+    /// 追加一条没有操作数的指令
+    pub fn op(&mut self, opcode: u8) -> &mut Self {
+        self.code.push(opcode);
+        self
+    }
+
+    /// 追加原始字节（操作码或操作数）
+    pub fn bytes(&mut self, bytes: &[u8]) -> &mut Self {
+        self.code.extend_from_slice(bytes);
+        self
+    }
+
+    /// 带一个常量池索引的指令
+    fn op_cp(&mut self, opcode: u8, index: u16) -> &mut Self {
+        self.code.push(opcode);
+        self.code.extend_from_slice(&index.to_be_bytes());
+        self
+    }
+
+    // ============ 常量 ============
+
+    /// 压入 int 常量，按大小选择 iconst_n / bipush / sipush / ldc
+    pub fn iconst(&mut self, value: i32) -> &mut Self {
+        match value {
+            -1..=5 => self.op((ICONST_0 as i32 + value) as u8),
+            -128..=127 => self.bytes(&[BIPUSH, value as i8 as u8]),
+            -32768..=32767 => self.op_cp(SIPUSH, value as i16 as u16),
+            _ => {
+                let index = self.pool.add(ConstantPoolEntry::Integer(value));
+                self.ldc_index(index)
+            }
+        }
+    }
+
+    /// 压入 long 常量（lconst_0/1 或 ldc2_w）
+    pub fn lconst(&mut self, value: i64) -> &mut Self {
+        match value {
+            0 => self.op(LCONST_0),
+            1 => self.op(LCONST_1),
+            _ => {
+                let index = self.pool.add(ConstantPoolEntry::Long(value));
+                self.op_cp(LDC2_W, index)
+            }
+        }
+    }
+
+    /// ldc 字符串常量
+    pub fn ldc_string(&mut self, s: &str) -> &mut Self {
+        let index = self.pool.add_string(s);
+        self.ldc_index(index)
+    }
+
+    /// 索引小于 256 用 ldc，否则用 ldc_w
+    fn ldc_index(&mut self, index: u16) -> &mut Self {
+        match u8::try_from(index) {
+            Ok(index) => self.bytes(&[LDC, index]),
+            Err(_) => self.op_cp(LDC_W, index),
+        }
+    }
+
+    // ============ 局部变量 ============
+
+    /// 0..=3 用 xload_n/xstore_n 短格式，其余用带 u8 索引的格式
+    fn local(&mut self, opcode: u8, short_base: u8, index: u16) -> &mut Self {
+        match index {
+            0..=3 => self.op(short_base + index as u8),
+            4..=255 => self.bytes(&[opcode, index as u8]),
+            _ => self.fail(anyhow!(
+                "Local variable index {} does not fit in one byte (wide is not supported)",
+                index
+            )),
+        }
+    }
+
+    pub fn iload(&mut self, index: u16) -> &mut Self {
+        self.local(ILOAD, ILOAD_0, index)
+    }
+
+    pub fn lload(&mut self, index: u16) -> &mut Self {
+        self.local(LLOAD, LLOAD_0, index)
+    }
+
+    pub fn aload(&mut self, index: u16) -> &mut Self {
+        self.local(ALOAD, ALOAD_0, index)
+    }
+
+    pub fn istore(&mut self, index: u16) -> &mut Self {
+        self.local(ISTORE, ISTORE_0, index)
+    }
+
+    pub fn lstore(&mut self, index: u16) -> &mut Self {
+        self.local(LSTORE, LSTORE_0, index)
+    }
+
+    pub fn astore(&mut self, index: u16) -> &mut Self {
+        self.local(ASTORE, ASTORE_0, index)
+    }
+
+    /// iinc index, delta
+    pub fn iinc(&mut self, index: u8, delta: i8) -> &mut Self {
+        self.bytes(&[IINC, index, delta as u8])
+    }
+
+    // ============ 运算和栈操作 ============
+
+    pub fn iadd(&mut self) -> &mut Self {
+        self.op(IADD)
+    }
+
+    pub fn isub(&mut self) -> &mut Self {
+        self.op(ISUB)
+    }
+
+    pub fn imul(&mut self) -> &mut Self {
+        self.op(IMUL)
+    }
+
+    pub fn idiv(&mut self) -> &mut Self {
+        self.op(IDIV)
+    }
+
+    pub fn ladd(&mut self) -> &mut Self {
+        self.op(LADD)
+    }
+
+    pub fn dup(&mut self) -> &mut Self {
+        self.op(DUP)
+    }
+
+    pub fn pop(&mut self) -> &mut Self {
+        self.op(POP)
+    }
+
+    // ============ 返回 ============
+
+    pub fn ireturn(&mut self) -> &mut Self {
+        self.op(IRETURN)
+    }
+
+    pub fn lreturn(&mut self) -> &mut Self {
+        self.op(LRETURN)
+    }
+
+    pub fn areturn(&mut self) -> &mut Self {
+        self.op(ARETURN)
+    }
+
+    /// void 方法的 return
+    pub fn vreturn(&mut self) -> &mut Self {
+        self.op(RETURN)
+    }
+
+    pub fn athrow(&mut self) -> &mut Self {
+        self.op(ATHROW)
+    }
+
+    // ============ 跳转 ============
+
+    /// 创建一个还没有绑定位置的标签
+    pub fn new_label(&mut self) -> Label {
+        self.labels.push(None);
+        Label(self.labels.len() - 1)
+    }
+
+    /// 把标签绑定到当前位置
+    pub fn bind(&mut self, label: Label) -> &mut Self {
+        if self.labels[label.0].is_some() {
+            return self.fail(anyhow!("Label {} is bound twice", label.0));
+        }
+        self.labels[label.0] = Some(self.code.len());
+        self
+    }
+
+    /// 16 位偏移的跳转指令（goto、ifeq、if_icmpge 等）
+    pub fn branch(&mut self, opcode: u8, label: Label) -> &mut Self {
+        let pc = self.code.len();
+        self.code.extend_from_slice(&[opcode, 0, 0]);
+        self.fixups.push(Fixup { pc, at: pc + 1, label });
+        self
+    }
+
+    pub fn goto(&mut self, label: Label) -> &mut Self {
+        self.branch(GOTO, label)
+    }
+
+    pub fn ifeq(&mut self, label: Label) -> &mut Self {
+        self.branch(IFEQ, label)
+    }
+
+    pub fn ifne(&mut self, label: Label) -> &mut Self {
+        self.branch(IFNE, label)
+    }
+
+    pub fn if_icmplt(&mut self, label: Label) -> &mut Self {
+        self.branch(IF_ICMPLT, label)
+    }
+
+    pub fn if_icmpge(&mut self, label: Label) -> &mut Self {
+        self.branch(IF_ICMPGE, label)
+    }
+
+    /// 异常表项：[start, end) 范围内抛出的 catch_type（None 表示 any）跳到 handler
+    pub fn exception_handler(
+        &mut self,
+        start: Label,
+        end: Label,
+        handler: Label,
+        catch_type: Option<&str>,
+    ) -> &mut Self {
+        let catch_type = catch_type.map_or(0, |name| self.pool.add_class(name));
+        self.handlers.push((start, end, handler, catch_type));
+        self
+    }
+
+    // ============ 字段和方法引用 ============
+
+    pub fn getstatic(&mut self, class: &str, name: &str, descriptor: &str) -> &mut Self {
+        let index = self.pool.add_field_ref(class, name, descriptor);
+        self.op_cp(GETSTATIC, index)
+    }
+
+    pub fn putstatic(&mut self, class: &str, name: &str, descriptor: &str) -> &mut Self {
+        let index = self.pool.add_field_ref(class, name, descriptor);
+        self.op_cp(PUTSTATIC, index)
+    }
+
+    pub fn getfield(&mut self, class: &str, name: &str, descriptor: &str) -> &mut Self {
+        let index = self.pool.add_field_ref(class, name, descriptor);
+        self.op_cp(GETFIELD, index)
+    }
+
+    pub fn putfield(&mut self, class: &str, name: &str, descriptor: &str) -> &mut Self {
+        let index = self.pool.add_field_ref(class, name, descriptor);
+        self.op_cp(PUTFIELD, index)
+    }
+
+    pub fn invokestatic(&mut self, class: &str, name: &str, descriptor: &str) -> &mut Self {
+        let index = self.pool.add_method_ref(class, name, descriptor);
+        self.op_cp(INVOKESTATIC, index)
+    }
+
+    pub fn invokevirtual(&mut self, class: &str, name: &str, descriptor: &str) -> &mut Self {
+        let index = self.pool.add_method_ref(class, name, descriptor);
+        self.op_cp(INVOKEVIRTUAL, index)
+    }
+
+    pub fn invokespecial(&mut self, class: &str, name: &str, descriptor: &str) -> &mut Self {
+        let index = self.pool.add_method_ref(class, name, descriptor);
+        self.op_cp(INVOKESPECIAL, index)
+    }
+
+    /// new 指令（只分配对象，还需要 dup + invokespecial <init>）
+    pub fn new_object(&mut self, class: &str) -> &mut Self {
+        let index = self.pool.add_class(class);
+        self.op_cp(NEW, index)
+    }
+
+    /// 回填跳转偏移，生成 Code 属性的内容
+    fn finish(mut self, max_stack: u16, max_locals: u16) -> Result<Vec<u8>> {
+        if let Some(error) = self.error.take() {
+            return Err(error);
+        }
+        let position = |labels: &[Option<usize>], label: Label| {
+            labels[label.0].ok_or_else(|| anyhow!("Label {} is never bound", label.0))
+        };
+        for fixup in &self.fixups {
+            let target = position(&self.labels, fixup.label)?;
+            let offset = i16::try_from(target as i64 - fixup.pc as i64)
+                .map_err(|_| anyhow!("Branch at pc {} is too far from its target", fixup.pc))?;
+            self.code[fixup.at..fixup.at + 2].copy_from_slice(&offset.to_be_bytes());
+        }
+
+        let mut info = Vec::new();
+        info.extend_from_slice(&max_stack.to_be_bytes());
+        info.extend_from_slice(&max_locals.to_be_bytes());
+        info.extend_from_slice(&(self.code.len() as u32).to_be_bytes());
+        info.extend_from_slice(&self.code);
+        info.extend_from_slice(&(self.handlers.len() as u16).to_be_bytes());
+        for &(start, end, handler, catch_type) in &self.handlers {
+            for label in [start, end, handler] {
+                info.extend_from_slice(&(position(&self.labels, label)? as u16).to_be_bytes());
+            }
+            info.extend_from_slice(&catch_type.to_be_bytes());
+        }
+        info.extend_from_slice(&0u16.to_be_bytes()); // Code 属性没有子属性
+        Ok(info)
+    }
+}
+
+/// Class文件构造器
+pub struct ClassFileBuilder {
+    pool: ConstantPool,
+    major_version: u16,
+    access_flags: u16,
+    this_class: u16,
+    super_class: u16,
+    interfaces: Vec<u16>,
+    fields: Vec<FieldInfo>,
+    methods: Vec<MethodInfo>,
+    error: Option<anyhow::Error>,
+}
+
+impl ClassFileBuilder {
+    /// 新建一个 public 类，父类为 java/lang/Object，版本为 Java 8
+    pub fn new(name: &str) -> Self {
+        let mut pool = ConstantPool::empty();
+        let this_class = pool.add_class(name);
+        let super_class = pool.add_class("java/lang/Object");
+        ClassFileBuilder {
+            pool,
+            major_version: 52,
+            access_flags: access_flags::ACC_PUBLIC | access_flags::ACC_SUPER,
+            this_class,
+            super_class,
+            interfaces: Vec::new(),
+            fields: Vec::new(),
+            methods: Vec::new(),
+            error: None,
+        }
+    }
+
+    /// 设置父类
+    pub fn super_class(mut self, name: &str) -> Self {
+        self.super_class = self.pool.add_class(name);
+        self
+    }
+
+    /// 设置类的访问标志
+    pub fn access_flags(mut self, flags: u16) -> Self {
+        self.access_flags = flags;
+        self
+    }
+
+    /// 设置主版本号（52 = Java 8）
+    pub fn major_version(mut self, major_version: u16) -> Self {
+        self.major_version = major_version;
+        self
+    }
+
+    /// 添加实现的接口
+    pub fn interface(mut self, name: &str) -> Self {
+        let index = self.pool.add_class(name);
+        self.interfaces.push(index);
+        self
+    }
+
+    /// 添加字段
+    pub fn field(mut self, access_flags: u16, name: &str, descriptor: &str) -> Self {
+        let name_index = self.pool.add_utf8(name);
+        let descriptor_index = self.pool.add_utf8(descriptor);
+        self.fields.push(FieldInfo {
+            access_flags,
+            name_index,
+            descriptor_index,
+            attributes: Vec::new(),
+        });
+        self
+    }
+
+    /// 添加带方法体的方法，`body` 里用 [`CodeBuilder`] 追加指令
+    pub fn method(
+        mut self,
+        access_flags: u16,
+        name: &str,
+        descriptor: &str,
+        max_stack: u16,
+        max_locals: u16,
+        body: impl FnOnce(&mut CodeBuilder),
+    ) -> Self {
+        let name_index = self.pool.add_utf8(name);
+        let descriptor_index = self.pool.add_utf8(descriptor);
+        let code_index = self.pool.add_utf8("Code");
+
+        let mut code = CodeBuilder::new(&mut self.pool);
+        body(&mut code);
+        match code.finish(max_stack, max_locals) {
+            Ok(info) => self.methods.push(MethodInfo {
+                access_flags,
+                name_index,
+                descriptor_index,
+                attributes: vec![AttributeInfo {
+                    name_index: code_index,
+                    info,
+                }],
+            }),
+            Err(e) => {
+                self.error
+                    .get_or_insert(e.context(format!("in method {}{}", name, descriptor)));
+            }
+        }
+        self
+    }
+
+    /// 添加没有方法体的方法（native 或 abstract）
+    pub fn method_without_code(mut self, access_flags: u16, name: &str, descriptor: &str) -> Self {
+        let name_index = self.pool.add_utf8(name);
+        let descriptor_index = self.pool.add_utf8(descriptor);
+        self.methods.push(MethodInfo {
+            access_flags,
+            name_index,
+            descriptor_index,
+            attributes: Vec::new(),
+        });
+        self
+    }
+
+    /// 生成 ClassFile；常量池总是经过 [`ConstantPool::validate`] 检查
+    pub fn build(self) -> Result<ClassFile> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+        self.pool.validate()?;
+        Ok(ClassFile {
+            magic: 0xCAFEBABE,
+            minor_version: 0,
+            major_version: self.major_version,
+            constant_pool: self.pool,
+            access_flags: self.access_flags,
+            this_class: self.this_class,
+            super_class: self.super_class,
+            interfaces: self.interfaces,
+            fields: self.fields,
+            methods: self.methods,
+            attributes: Vec::new(),
+        })
+    }
+
+    /// 生成并序列化为字节
+    pub fn to_bytes(self) -> Result<Vec<u8>> {
+        self.build()?.to_bytes()
+    }
+}
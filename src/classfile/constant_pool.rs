@@ -8,17 +8,25 @@
 //! - 常量池索引从1开始（0保留）
 //! - Long和Double占用两个索引位
 //! - 常量池项之间会相互引用
+//! - 新版本不断增加常量类型：Java 7 的 MethodHandle/MethodType/InvokeDynamic，
+//!   Java 9 的 Module/Package，Java 11 的 Dynamic
+//! - 引用其他项的常量（Class → Utf8、MethodRef → Class + NameAndType 等）只在 [`ConstantPool::validate`]
+//!   中检查类型和范围；不检查时，指错了项要到使用它时才报出令人困惑的错误
+//! - Utf8 项用的是 Modified UTF-8：U+0000 写成 0xC0 0x80，
+//!   补充平面字符拆成 UTF-16 代理对、每个代理各占 3 字节
 
 use crate::Result;
 use anyhow::anyhow;
 
 /// 常量池
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ConstantPool {
     pub entries: Vec<Option<ConstantPoolEntry>>,
 }
 
 /// 常量池项
+///
+/// 相等比较按 class 文件中的编码：浮点数逐位比较，`0.0` 和 `-0.0` 是不同的常量
 #[derive(Debug, Clone)]
 pub enum ConstantPoolEntry {
     /// UTF-8字符串
@@ -67,6 +75,92 @@ pub enum ConstantPoolEntry {
         bootstrap_method_attr_index: u16,
         name_and_type_index: u16,
     },
+    /// 动态计算常量（condy，Java 11+），由引导方法在首次 ldc 时计算
+    Dynamic {
+        bootstrap_method_attr_index: u16,
+        name_and_type_index: u16,
+    },
+    /// 模块（只出现在 module-info.class 中，Java 9+）
+    Module { name_index: u16 },
+    /// 包（只出现在 module-info.class 中，Java 9+）
+    Package { name_index: u16 },
+}
+
+impl PartialEq for ConstantPoolEntry {
+    fn eq(&self, other: &Self) -> bool {
+        use ConstantPoolEntry::*;
+        match (self, other) {
+            (Float(a), Float(b)) => a.to_bits() == b.to_bits(),
+            (Double(a), Double(b)) => a.to_bits() == b.to_bits(),
+            (Utf8(a), Utf8(b)) => a == b,
+            (Integer(a), Integer(b)) => a == b,
+            (Long(a), Long(b)) => a == b,
+            (Class { name_index: a }, Class { name_index: b })
+            | (String { string_index: a }, String { string_index: b })
+            | (MethodType { descriptor_index: a }, MethodType { descriptor_index: b })
+            | (Module { name_index: a }, Module { name_index: b })
+            | (Package { name_index: a }, Package { name_index: b }) => a == b,
+            (
+                FieldRef { class_index: a1, name_and_type_index: a2 },
+                FieldRef { class_index: b1, name_and_type_index: b2 },
+            )
+            | (
+                MethodRef { class_index: a1, name_and_type_index: a2 },
+                MethodRef { class_index: b1, name_and_type_index: b2 },
+            )
+            | (
+                InterfaceMethodRef { class_index: a1, name_and_type_index: a2 },
+                InterfaceMethodRef { class_index: b1, name_and_type_index: b2 },
+            )
+            | (
+                NameAndType { name_index: a1, descriptor_index: a2 },
+                NameAndType { name_index: b1, descriptor_index: b2 },
+            )
+            | (
+                InvokeDynamic { bootstrap_method_attr_index: a1, name_and_type_index: a2 },
+                InvokeDynamic { bootstrap_method_attr_index: b1, name_and_type_index: b2 },
+            )
+            | (
+                Dynamic { bootstrap_method_attr_index: a1, name_and_type_index: a2 },
+                Dynamic { bootstrap_method_attr_index: b1, name_and_type_index: b2 },
+            ) => a1 == b1 && a2 == b2,
+            (
+                MethodHandle { reference_kind: a1, reference_index: a2 },
+                MethodHandle { reference_kind: b1, reference_index: b2 },
+            ) => a1 == b1 && a2 == b2,
+            _ => false,
+        }
+    }
+}
+
+impl ConstantPoolEntry {
+    /// 常量类型名（JVM 规范中 CONSTANT_ 之后的部分），用于错误信息
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ConstantPoolEntry::Utf8(_) => "Utf8",
+            ConstantPoolEntry::Integer(_) => "Integer",
+            ConstantPoolEntry::Float(_) => "Float",
+            ConstantPoolEntry::Long(_) => "Long",
+            ConstantPoolEntry::Double(_) => "Double",
+            ConstantPoolEntry::Class { .. } => "Class",
+            ConstantPoolEntry::String { .. } => "String",
+            ConstantPoolEntry::FieldRef { .. } => "Fieldref",
+            ConstantPoolEntry::MethodRef { .. } => "Methodref",
+            ConstantPoolEntry::InterfaceMethodRef { .. } => "InterfaceMethodref",
+            ConstantPoolEntry::NameAndType { .. } => "NameAndType",
+            ConstantPoolEntry::MethodHandle { .. } => "MethodHandle",
+            ConstantPoolEntry::MethodType { .. } => "MethodType",
+            ConstantPoolEntry::InvokeDynamic { .. } => "InvokeDynamic",
+            ConstantPoolEntry::Dynamic { .. } => "Dynamic",
+            ConstantPoolEntry::Module { .. } => "Module",
+            ConstantPoolEntry::Package { .. } => "Package",
+        }
+    }
+
+    /// 是否占两个索引（Long、Double）
+    pub fn is_wide(&self) -> bool {
+        matches!(self, ConstantPoolEntry::Long(_) | ConstantPoolEntry::Double(_))
+    }
 }
 
 impl ConstantPool {
@@ -77,6 +171,235 @@ impl ConstantPool {
         }
     }
 
+    /// 空常量池（只有保留的 0 号索引），之后用 [`push`](Self::push) 和 `add_*` 追加
+    pub fn empty() -> Self {
+        Self::new(1)
+    }
+
+    /// 在末尾追加一项，返回它的索引；Long/Double 之后留出第二个（不可用的）索引
+    ///
+    /// 不去重。超过 65535 项时索引会回绕，[`validate`](Self::validate) 会报告常量池过大
+    pub fn push(&mut self, entry: ConstantPoolEntry) -> u16 {
+        let index = self.entries.len() as u16;
+        let wide = entry.is_wide();
+        self.entries.push(Some(entry));
+        if wide {
+            self.entries.push(None);
+        }
+        index
+    }
+
+    /// 已有相同的项时返回它的索引，否则追加
+    pub fn add(&mut self, entry: ConstantPoolEntry) -> u16 {
+        match self.entries.iter().position(|e| e.as_ref() == Some(&entry)) {
+            Some(index) => index as u16,
+            None => self.push(entry),
+        }
+    }
+
+    /// 追加（或找到已有的）Utf8 项
+    pub fn add_utf8(&mut self, s: &str) -> u16 {
+        self.add(ConstantPoolEntry::Utf8(s.to_string()))
+    }
+
+    /// 追加（或找到已有的）Class 项，类名用内部形式（`java/lang/Object`）
+    pub fn add_class(&mut self, name: &str) -> u16 {
+        let name_index = self.add_utf8(name);
+        self.add(ConstantPoolEntry::Class { name_index })
+    }
+
+    /// 追加（或找到已有的）String 项
+    pub fn add_string(&mut self, s: &str) -> u16 {
+        let string_index = self.add_utf8(s);
+        self.add(ConstantPoolEntry::String { string_index })
+    }
+
+    /// 追加（或找到已有的）NameAndType 项
+    pub fn add_name_and_type(&mut self, name: &str, descriptor: &str) -> u16 {
+        let name_index = self.add_utf8(name);
+        let descriptor_index = self.add_utf8(descriptor);
+        self.add(ConstantPoolEntry::NameAndType {
+            name_index,
+            descriptor_index,
+        })
+    }
+
+    /// 追加（或找到已有的）Fieldref 项及其引用的 Class、NameAndType
+    pub fn add_field_ref(&mut self, class: &str, name: &str, descriptor: &str) -> u16 {
+        let class_index = self.add_class(class);
+        let name_and_type_index = self.add_name_and_type(name, descriptor);
+        self.add(ConstantPoolEntry::FieldRef {
+            class_index,
+            name_and_type_index,
+        })
+    }
+
+    /// 追加（或找到已有的）Methodref 项及其引用的 Class、NameAndType
+    pub fn add_method_ref(&mut self, class: &str, name: &str, descriptor: &str) -> u16 {
+        let class_index = self.add_class(class);
+        let name_and_type_index = self.add_name_and_type(name, descriptor);
+        self.add(ConstantPoolEntry::MethodRef {
+            class_index,
+            name_and_type_index,
+        })
+    }
+
+    /// 追加（或找到已有的）InterfaceMethodref 项及其引用的 Class、NameAndType
+    pub fn add_interface_method_ref(&mut self, class: &str, name: &str, descriptor: &str) -> u16 {
+        let class_index = self.add_class(class);
+        let name_and_type_index = self.add_name_and_type(name, descriptor);
+        self.add(ConstantPoolEntry::InterfaceMethodRef {
+            class_index,
+            name_and_type_index,
+        })
+    }
+
+    /// 检查常量池的结构：
+    ///
+    /// - 大小不超过 65535 项，0 号索引为空
+    /// - Long/Double 之后的索引为空（第二个槽位），其余索引都有项
+    /// - 每个引用其他项的索引都在范围内，并且指向期望的类型
+    ///   （如 Class 的 name_index 指向 Utf8，Methodref 指向 Class 和 NameAndType）
+    pub fn validate(&self) -> Result<()> {
+        if self.entries.len() > u16::MAX as usize {
+            return Err(anyhow!(
+                "Constant pool has {} entries, more than a class file can hold",
+                self.entries.len()
+            ));
+        }
+        if matches!(self.entries.first(), Some(Some(_))) {
+            return Err(anyhow!("Constant pool index 0 is reserved but holds an entry"));
+        }
+        let mut index = 1;
+        while index < self.entries.len() {
+            let entry = self.entries[index]
+                .as_ref()
+                .ok_or_else(|| anyhow!("Constant pool entry #{} is missing", index))?;
+            self.validate_entry(index as u16, entry)?;
+            if entry.is_wide() {
+                match self.entries.get(index + 1) {
+                    Some(None) => index += 1,
+                    Some(Some(_)) => {
+                        return Err(anyhow!(
+                            "Constant pool entry #{} ({}) must be followed by an unused slot",
+                            index,
+                            entry.kind()
+                        ))
+                    }
+                    None => {
+                        return Err(anyhow!(
+                            "Constant pool entry #{} ({}) has no second slot",
+                            index,
+                            entry.kind()
+                        ))
+                    }
+                }
+            }
+            index += 1;
+        }
+        Ok(())
+    }
+
+    fn validate_entry(&self, index: u16, entry: &ConstantPoolEntry) -> Result<()> {
+        use ConstantPoolEntry::*;
+
+        let expect = |target: u16, expected: &[&str]| self.expect_kind(index, entry, target, expected);
+        match entry {
+            Utf8(_) | Integer(_) | Float(_) | Long(_) | Double(_) => Ok(()),
+            Class { name_index } | Module { name_index } | Package { name_index } => {
+                expect(*name_index, &["Utf8"])
+            }
+            String { string_index } => expect(*string_index, &["Utf8"]),
+            MethodType { descriptor_index } => expect(*descriptor_index, &["Utf8"]),
+            NameAndType {
+                name_index,
+                descriptor_index,
+            } => {
+                expect(*name_index, &["Utf8"])?;
+                expect(*descriptor_index, &["Utf8"])
+            }
+            FieldRef {
+                class_index,
+                name_and_type_index,
+            }
+            | MethodRef {
+                class_index,
+                name_and_type_index,
+            }
+            | InterfaceMethodRef {
+                class_index,
+                name_and_type_index,
+            } => {
+                expect(*class_index, &["Class"])?;
+                expect(*name_and_type_index, &["NameAndType"])
+            }
+            InvokeDynamic {
+                name_and_type_index,
+                ..
+            }
+            | Dynamic {
+                name_and_type_index,
+                ..
+            } => expect(*name_and_type_index, &["NameAndType"]),
+            MethodHandle {
+                reference_kind,
+                reference_index,
+            } => {
+                // JVM 规范 4.4.8：1-4 是字段访问，5-9 是方法调用
+                let expected: &[&str] = match reference_kind {
+                    1..=4 => &["Fieldref"],
+                    5 | 8 => &["Methodref"],
+                    6 | 7 => &["Methodref", "InterfaceMethodref"],
+                    9 => &["InterfaceMethodref"],
+                    _ => {
+                        return Err(anyhow!(
+                            "Constant pool entry #{} (MethodHandle) has invalid reference kind {}",
+                            index,
+                            reference_kind
+                        ))
+                    }
+                };
+                expect(*reference_index, expected)
+            }
+        }
+    }
+
+    /// `entry`（位于 `index`）引用的 `target` 必须在范围内，并且是 `expected` 中的一种
+    fn expect_kind(&self, index: u16, entry: &ConstantPoolEntry, target: u16, expected: &[&str]) -> Result<()> {
+        let found = match self.entries.get(target as usize) {
+            _ if target == 0 => None,
+            Some(Some(found)) => Some(found),
+            Some(None) => None,
+            None => {
+                return Err(anyhow!(
+                    "Constant pool entry #{} ({}) refers to #{}, out of range (pool has {} entries)",
+                    index,
+                    entry.kind(),
+                    target,
+                    self.entries.len()
+                ))
+            }
+        };
+        match found {
+            Some(found) if expected.contains(&found.kind()) => Ok(()),
+            Some(found) => Err(anyhow!(
+                "Constant pool entry #{} ({}) refers to #{}, expected {} but found {}",
+                index,
+                entry.kind(),
+                target,
+                expected.join(" or "),
+                found.kind()
+            )),
+            None => Err(anyhow!(
+                "Constant pool entry #{} ({}) refers to #{}, expected {} but the slot is empty",
+                index,
+                entry.kind(),
+                target,
+                expected.join(" or ")
+            )),
+        }
+    }
+
     /// 获取常量池项
     pub fn get(&self, index: u16) -> Result<&ConstantPoolEntry> {
         if index == 0 || index as usize >= self.entries.len() {
@@ -103,6 +426,46 @@ impl ConstantPool {
         }
     }
 
+    /// 获取 Integer 常量
+    pub fn get_integer(&self, index: u16) -> Result<i32> {
+        match self.get(index)? {
+            ConstantPoolEntry::Integer(v) => Ok(*v),
+            other => Err(anyhow!("Expected Integer at index {}, got {}", index, other.kind())),
+        }
+    }
+
+    /// 获取 Float 常量
+    pub fn get_float(&self, index: u16) -> Result<f32> {
+        match self.get(index)? {
+            ConstantPoolEntry::Float(v) => Ok(*v),
+            other => Err(anyhow!("Expected Float at index {}, got {}", index, other.kind())),
+        }
+    }
+
+    /// 获取 Long 常量
+    pub fn get_long(&self, index: u16) -> Result<i64> {
+        match self.get(index)? {
+            ConstantPoolEntry::Long(v) => Ok(*v),
+            other => Err(anyhow!("Expected Long at index {}, got {}", index, other.kind())),
+        }
+    }
+
+    /// 获取 Double 常量
+    pub fn get_double(&self, index: u16) -> Result<f64> {
+        match self.get(index)? {
+            ConstantPoolEntry::Double(v) => Ok(*v),
+            other => Err(anyhow!("Expected Double at index {}, got {}", index, other.kind())),
+        }
+    }
+
+    /// 获取 String 常量的内容（String 项引用的 Utf8）
+    pub fn get_string(&self, index: u16) -> Result<String> {
+        match self.get(index)? {
+            ConstantPoolEntry::String { string_index } => self.get_utf8(*string_index),
+            other => Err(anyhow!("Expected String at index {}, got {}", index, other.kind())),
+        }
+    }
+
     /// 获取名称和类型
     pub fn get_name_and_type(&self, index: u16) -> Result<(String, String)> {
         match self.get(index)? {
@@ -118,9 +481,17 @@ impl ConstantPool {
         }
     }
 
-    /// 设置常量池项
-    pub fn set(&mut self, index: u16, entry: ConstantPoolEntry) {
+    /// 设置常量池项；索引必须在 1..常量池大小 之内（0 号索引保留）
+    pub fn set(&mut self, index: u16, entry: ConstantPoolEntry) -> Result<()> {
+        if index == 0 || index as usize >= self.entries.len() {
+            return Err(anyhow!(
+                "Constant pool index {} out of range (pool has {} entries)",
+                index,
+                self.entries.len()
+            ));
+        }
         self.entries[index as usize] = Some(entry);
+        Ok(())
     }
 
     /// 调试用：打印常量池的所有内容
@@ -140,6 +511,68 @@ impl ConstantPool {
     }
 }
 
+/// 解码 CONSTANT_Utf8 的 Modified UTF-8 字节
+///
+/// - 1 字节形式：0x01..=0x7F（不允许出现 0 字节）
+/// - 2 字节形式：包括表示 U+0000 的 0xC0 0x80
+/// - 3 字节形式：一个 UTF-16 码元；相邻的高、低代理合成一个补充平面字符
+///
+/// 不成对的代理无法放进 Rust 字符串，替换为 U+FFFD
+pub fn decode_mutf8(bytes: &[u8]) -> Result<String> {
+    let mut units = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let b = bytes[i];
+        let continuation = |offset: usize| -> Result<u16> {
+            match bytes.get(i + offset) {
+                Some(&c) if c & 0xC0 == 0x80 => Ok((c & 0x3F) as u16),
+                _ => Err(anyhow!("Invalid MUTF-8: truncated sequence at byte {}", i)),
+            }
+        };
+        match b {
+            0 => return Err(anyhow!("Invalid MUTF-8: zero byte at {}", i)),
+            0x01..=0x7F => {
+                units.push(b as u16);
+                i += 1;
+            }
+            0xC0..=0xDF => {
+                units.push(((b & 0x1F) as u16) << 6 | continuation(1)?);
+                i += 2;
+            }
+            0xE0..=0xEF => {
+                units.push(((b & 0x0F) as u16) << 12 | continuation(1)? << 6 | continuation(2)?);
+                i += 3;
+            }
+            _ => return Err(anyhow!("Invalid MUTF-8: unexpected byte 0x{:02X} at {}", b, i)),
+        }
+    }
+    Ok(char::decode_utf16(units)
+        .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+        .collect())
+}
+
+/// 把字符串编码成 Modified UTF-8（[`decode_mutf8`] 的逆过程）
+///
+/// U+0000 写成 0xC0 0x80，补充平面字符先拆成 UTF-16 代理对再逐个编码成 3 字节
+pub fn encode_mutf8(s: &str) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(s.len());
+    for unit in s.encode_utf16() {
+        match unit {
+            0x0001..=0x007F => bytes.push(unit as u8),
+            0x0000 | 0x0080..=0x07FF => {
+                bytes.push(0xC0 | (unit >> 6) as u8);
+                bytes.push(0x80 | (unit & 0x3F) as u8);
+            }
+            _ => {
+                bytes.push(0xE0 | (unit >> 12) as u8);
+                bytes.push(0x80 | ((unit >> 6) & 0x3F) as u8);
+                bytes.push(0x80 | (unit & 0x3F) as u8);
+            }
+        }
+    }
+    bytes
+}
+
 /// 常量池标签常量
 pub mod tags {
     pub const CONSTANT_UTF8: u8 = 1;
@@ -155,5 +588,55 @@ pub mod tags {
     pub const CONSTANT_NAME_AND_TYPE: u8 = 12;
     pub const CONSTANT_METHOD_HANDLE: u8 = 15;
     pub const CONSTANT_METHOD_TYPE: u8 = 16;
+    pub const CONSTANT_DYNAMIC: u8 = 17;
     pub const CONSTANT_INVOKE_DYNAMIC: u8 = 18;
+    pub const CONSTANT_MODULE: u8 = 19;
+    pub const CONSTANT_PACKAGE: u8 = 20;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_mutf8, encode_mutf8};
+
+    #[test]
+    fn test_ascii_and_embedded_nul() {
+        assert_eq!(decode_mutf8(b"main").unwrap(), "main");
+        assert_eq!(decode_mutf8(&[b'a', 0xC0, 0x80, b'b']).unwrap(), "a\0b");
+    }
+
+    #[test]
+    fn test_two_and_three_byte_forms() {
+        // é = U+00E9，中 = U+4E2D（与标准 UTF-8 相同）
+        assert_eq!(decode_mutf8(&[0xC3, 0xA9]).unwrap(), "é");
+        assert_eq!(decode_mutf8(&[0xE4, 0xB8, 0xAD]).unwrap(), "中");
+    }
+
+    #[test]
+    fn test_encode_round_trip() {
+        assert_eq!(encode_mutf8("a\0b"), [b'a', 0xC0, 0x80, b'b']);
+        assert_eq!(encode_mutf8("😀"), [0xED, 0xA0, 0xBD, 0xED, 0xB8, 0x80]);
+        for s in ["main", "é中", "(Ljava/lang/String;)V", "x\0😀y"] {
+            assert_eq!(decode_mutf8(&encode_mutf8(s)).unwrap(), s);
+        }
+    }
+
+    #[test]
+    fn test_surrogate_pair() {
+        // 😀 = U+1F600 = D83D DE00，每个代理编码成 3 字节
+        let bytes = [0xED, 0xA0, 0xBD, 0xED, 0xB8, 0x80];
+        assert_eq!(decode_mutf8(&bytes).unwrap(), "😀");
+        // 单独的高代理替换为 U+FFFD
+        assert_eq!(decode_mutf8(&[0xED, 0xA0, 0xBD, b'x']).unwrap(), "\u{FFFD}x");
+    }
+
+    #[test]
+    fn test_invalid_sequences() {
+        let err = decode_mutf8(&[b'a', 0x00]).unwrap_err().to_string();
+        assert_eq!(err, "Invalid MUTF-8: zero byte at 1");
+        let err = decode_mutf8(&[0xE4, 0xB8]).unwrap_err().to_string();
+        assert_eq!(err, "Invalid MUTF-8: truncated sequence at byte 0");
+        // 标准 UTF-8 的 4 字节形式在 MUTF-8 中不存在
+        let err = decode_mutf8(&[0xF0, 0x9F, 0x98, 0x80]).unwrap_err().to_string();
+        assert_eq!(err, "Invalid MUTF-8: unexpected byte 0xF0 at 0");
+    }
 }
@@ -0,0 +1,458 @@
+//! # 描述符
+//!
+//! 字段描述符描述一个类型：`I`、`J`、`Ljava/lang/String;`、`[[I`；
+//! 方法描述符是参数类型和返回类型：`(I[JLjava/lang/String;)V`。
+//! 这里把描述符解析成 [`FieldType`] / [`MethodDescriptor`]，供方法元数据、解释器（参数个数、字段默认值）、
+//! 校验器和嵌入 API 共用；也提供不分配内存、直接切分字符串的辅助函数。
+//!
+//! ## 学习要点
+//! - 数组类型是若干个 `[` 加上元素类型，`[[I` 是一个类型（int[][]），不是两个
+//! - 对象类型以 `L` 开头、`;` 结尾，中间是内部形式的类名（`/` 分隔包名）
+//! - 返回类型可以是 `V`（void），参数类型不可以
+//! - long/double 在局部变量表中占两个槽位，操作数栈上算一个值（见 `param_slot_count`）
+//! - 格式错误时报告出错的位置（从 0 开始的字节偏移），如 `(Ljava/lang/String` 在位置 1 缺少 `;`
+
+use crate::runtime::frame::JvmValue;
+use crate::Result;
+use std::fmt;
+
+/// 数组最多 255 维（JVM 规范 4.3.2）
+const MAX_ARRAY_DIMENSIONS: usize = 255;
+
+/// 字段类型（也是参数和返回值的类型）
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum FieldType {
+    Byte,
+    Char,
+    Double,
+    Float,
+    Int,
+    Long,
+    Short,
+    Boolean,
+    /// 对象类型，内部形式的类名，如 "java/lang/String"
+    Object(String),
+    /// 数组类型，元素类型
+    Array(Box<FieldType>),
+}
+
+impl FieldType {
+    /// 解析字段描述符，整个字符串必须恰好是一个类型
+    pub fn parse(descriptor: &str) -> std::result::Result<FieldType, DescriptorError> {
+        let mut parser = Parser::new(descriptor);
+        let field_type = parser.field_type()?;
+        parser.expect_end("unexpected characters after field type")?;
+        Ok(field_type)
+    }
+
+    /// 在局部变量表中占的槽位数：long/double 为 2，其余为 1
+    pub fn slot_count(&self) -> usize {
+        match self {
+            FieldType::Long | FieldType::Double => 2,
+            _ => 1,
+        }
+    }
+
+    /// 是否是引用类型（对象或数组）
+    pub fn is_reference(&self) -> bool {
+        matches!(self, FieldType::Object(_) | FieldType::Array(_))
+    }
+
+    /// 字段描述符形式，如 `Array(Int)` → "[I"
+    pub fn descriptor(&self) -> String {
+        self.to_string()
+    }
+
+    /// 已经检查过格式的单个类型描述符转成 FieldType
+    fn from_checked(descriptor: &str) -> FieldType {
+        match descriptor.as_bytes()[0] {
+            b'B' => FieldType::Byte,
+            b'C' => FieldType::Char,
+            b'D' => FieldType::Double,
+            b'F' => FieldType::Float,
+            b'I' => FieldType::Int,
+            b'J' => FieldType::Long,
+            b'S' => FieldType::Short,
+            b'Z' => FieldType::Boolean,
+            b'[' => FieldType::Array(Box::new(FieldType::from_checked(&descriptor[1..]))),
+            _ => FieldType::Object(descriptor[1..descriptor.len() - 1].to_string()),
+        }
+    }
+}
+
+impl fmt::Display for FieldType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FieldType::Byte => write!(f, "B"),
+            FieldType::Char => write!(f, "C"),
+            FieldType::Double => write!(f, "D"),
+            FieldType::Float => write!(f, "F"),
+            FieldType::Int => write!(f, "I"),
+            FieldType::Long => write!(f, "J"),
+            FieldType::Short => write!(f, "S"),
+            FieldType::Boolean => write!(f, "Z"),
+            FieldType::Object(class_name) => write!(f, "L{};", class_name),
+            FieldType::Array(element) => write!(f, "[{}", element),
+        }
+    }
+}
+
+/// 方法描述符：参数类型和返回类型（`None` 表示 void）
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MethodDescriptor {
+    pub params: Vec<FieldType>,
+    pub ret: Option<FieldType>,
+}
+
+impl MethodDescriptor {
+    /// 解析方法描述符，如 `([[Ljava/lang/String;JD)V`
+    pub fn parse(descriptor: &str) -> std::result::Result<MethodDescriptor, DescriptorError> {
+        let mut parser = Parser::new(descriptor);
+        let mut params = Vec::new();
+        parser.open_params()?;
+        while !parser.close_params()? {
+            params.push(parser.field_type()?);
+        }
+        let ret = parser.return_type()?;
+        Ok(MethodDescriptor { params, ret })
+    }
+
+    /// 参数在局部变量表中占的槽位数（不含 this），如 `(IJ)V` → 3
+    pub fn param_slot_count(&self) -> usize {
+        self.params.iter().map(FieldType::slot_count).sum()
+    }
+}
+
+impl fmt::Display for MethodDescriptor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "(")?;
+        for param in &self.params {
+            write!(f, "{}", param)?;
+        }
+        match &self.ret {
+            Some(ret) => write!(f, "){}", ret),
+            None => write!(f, ")V"),
+        }
+    }
+}
+
+/// 描述符格式错误：出错的描述符、出错位置（字节偏移）和原因
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DescriptorError {
+    pub descriptor: String,
+    pub position: usize,
+    pub message: String,
+}
+
+impl fmt::Display for DescriptorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "invalid descriptor {:?} at position {}: {}",
+            self.descriptor, self.position, self.message
+        )
+    }
+}
+
+impl std::error::Error for DescriptorError {}
+
+/// 字段类型的默认值（未赋值的字段、新数组的元素取此值）
+///
+/// - B/C/S/I/Z → 0
+/// - J → 0L, F → 0.0f, D → 0.0
+/// - 对象/数组 → null
+pub fn jvm_default_value(field_type: &FieldType) -> JvmValue {
+    match field_type {
+        FieldType::Long => JvmValue::Long(0),
+        FieldType::Float => JvmValue::Float(0.0),
+        FieldType::Double => JvmValue::Double(0.0),
+        FieldType::Object(_) | FieldType::Array(_) => JvmValue::Reference(None),
+        FieldType::Byte | FieldType::Char | FieldType::Short | FieldType::Int | FieldType::Boolean => {
+            JvmValue::Int(0)
+        }
+    }
+}
+
+/// 描述符的游标：解析 [`FieldType`]、[`MethodDescriptor`] 和切分字符串的函数共用同一套语法检查
+struct Parser<'a> {
+    descriptor: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(descriptor: &'a str) -> Self {
+        Parser { descriptor, pos: 0 }
+    }
+
+    fn error_at(&self, position: usize, message: impl Into<String>) -> DescriptorError {
+        DescriptorError {
+            descriptor: self.descriptor.to_string(),
+            position,
+            message: message.into(),
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.descriptor.as_bytes().get(self.pos).copied()
+    }
+
+    /// 跳过一个类型，返回它的描述符；`allow_void` 为 true 时也接受 `V`（返回类型）
+    fn skip_type(&mut self, allow_void: bool) -> std::result::Result<&'a str, DescriptorError> {
+        let start = self.pos;
+        while self.peek() == Some(b'[') {
+            self.pos += 1;
+        }
+        let dims = self.pos - start;
+        if dims > MAX_ARRAY_DIMENSIONS {
+            return Err(self.error_at(start, format!("array type has {} dimensions (max 255)", dims)));
+        }
+        match self.peek() {
+            Some(b'B' | b'C' | b'D' | b'F' | b'I' | b'J' | b'S' | b'Z') => self.pos += 1,
+            Some(b'V') if allow_void && dims == 0 => self.pos += 1,
+            Some(b'V') => return Err(self.error_at(self.pos, "void is only allowed as a return type")),
+            Some(b'L') => {
+                let name_start = self.pos + 1;
+                let end = self.descriptor[name_start..]
+                    .find(';')
+                    .ok_or_else(|| self.error_at(self.pos, "class name is missing its closing ';'"))?;
+                if end == 0 {
+                    return Err(self.error_at(self.pos, "empty class name"));
+                }
+                self.pos = name_start + end + 1;
+            }
+            Some(other) => {
+                return Err(self.error_at(
+                    self.pos,
+                    format!("unexpected {:?}, expected a field type", other as char),
+                ))
+            }
+            None => return Err(self.error_at(self.pos, "unexpected end, expected a field type")),
+        }
+        Ok(&self.descriptor[start..self.pos])
+    }
+
+    fn field_type(&mut self) -> std::result::Result<FieldType, DescriptorError> {
+        self.skip_type(false).map(FieldType::from_checked)
+    }
+
+    /// 方法描述符开头的 `(`
+    fn open_params(&mut self) -> std::result::Result<(), DescriptorError> {
+        if self.peek() != Some(b'(') {
+            return Err(self.error_at(0, "method descriptor must start with '('"));
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    /// 参数列表是否到了 `)`（到了就跳过它）；没有 `)` 就结束时报错
+    fn close_params(&mut self) -> std::result::Result<bool, DescriptorError> {
+        match self.peek() {
+            Some(b')') => {
+                self.pos += 1;
+                Ok(true)
+            }
+            None => Err(self.error_at(self.pos, "missing ')' after parameter types")),
+            Some(_) => Ok(false),
+        }
+    }
+
+    /// `)` 之后的返回类型，必须到描述符结尾
+    fn return_type(&mut self) -> std::result::Result<Option<FieldType>, DescriptorError> {
+        let ret = self.skip_type(true)?;
+        self.expect_end("unexpected characters after return type")?;
+        Ok((ret != "V").then(|| FieldType::from_checked(ret)))
+    }
+
+    fn expect_end(&self, message: &str) -> std::result::Result<(), DescriptorError> {
+        if self.pos == self.descriptor.len() {
+            Ok(())
+        } else {
+            Err(self.error_at(self.pos, message))
+        }
+    }
+}
+
+/// 是否是合法的字段描述符（整个字符串恰好是一个类型）
+pub fn is_field_descriptor(descriptor: &str) -> bool {
+    let mut parser = Parser::new(descriptor);
+    parser.skip_type(false).is_ok() && parser.expect_end("").is_ok()
+}
+
+/// 方法描述符中的参数类型，如 `([[ILjava/lang/String;J)V` → `["[[I", "Ljava/lang/String;", "J"]`
+pub fn parameter_descriptors(descriptor: &str) -> Result<Vec<&str>> {
+    let mut parser = Parser::new(descriptor);
+    let mut types = Vec::new();
+    parser.open_params()?;
+    while !parser.close_params()? {
+        types.push(parser.skip_type(false)?);
+    }
+    Ok(types)
+}
+
+/// 参数个数（操作数栈上的值个数，long/double 也算一个）；和 [`parameter_descriptors`] 一样检查格式，但不分配内存
+pub fn parameter_count(descriptor: &str) -> Result<usize> {
+    let mut parser = Parser::new(descriptor);
+    let mut count = 0;
+    parser.open_params()?;
+    while !parser.close_params()? {
+        parser.skip_type(false)?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+/// 方法描述符的返回类型，如 `(I)[J` → `[J`，`()V` → `V`
+pub fn return_descriptor(descriptor: &str) -> Result<&str> {
+    let mut parser = Parser::new(descriptor);
+    parser.open_params()?;
+    while !parser.close_params()? {
+        parser.skip_type(false)?;
+    }
+    let ret = parser.skip_type(true)?;
+    parser.expect_end("unexpected characters after return type")?;
+    Ok(ret)
+}
+
+/// 检查整个方法描述符：参数和返回类型都合法
+pub fn validate_method_descriptor(descriptor: &str) -> Result<()> {
+    MethodDescriptor::parse(descriptor)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{jvm_default_value, FieldType, MethodDescriptor};
+    use crate::runtime::frame::JvmValue;
+
+    fn object(name: &str) -> FieldType {
+        FieldType::Object(name.to_string())
+    }
+
+    fn array(element: FieldType) -> FieldType {
+        FieldType::Array(Box::new(element))
+    }
+
+    /// 解析失败时的 (位置, 原因)
+    fn error(descriptor: &str) -> (usize, String) {
+        let err = MethodDescriptor::parse(descriptor).unwrap_err();
+        assert_eq!(err.descriptor, descriptor);
+        (err.position, err.message)
+    }
+
+    #[test]
+    fn test_nested_arrays_and_wide_params() {
+        let desc = MethodDescriptor::parse("([[Ljava/lang/String;JD)V").unwrap();
+        assert_eq!(
+            desc.params,
+            [array(array(object("java/lang/String"))), FieldType::Long, FieldType::Double]
+        );
+        assert_eq!(desc.ret, None);
+        assert_eq!(desc.param_slot_count(), 5);
+        assert_eq!(desc.to_string(), "([[Ljava/lang/String;JD)V");
+    }
+
+    #[test]
+    fn test_array_return_without_params() {
+        let desc = MethodDescriptor::parse("()[I").unwrap();
+        assert!(desc.params.is_empty());
+        assert_eq!(desc.ret, Some(array(FieldType::Int)));
+        assert_eq!(desc.param_slot_count(), 0);
+    }
+
+    #[test]
+    fn test_every_primitive() {
+        let desc = MethodDescriptor::parse("(BCDFIJSZ)Z").unwrap();
+        assert_eq!(
+            desc.params,
+            [
+                FieldType::Byte,
+                FieldType::Char,
+                FieldType::Double,
+                FieldType::Float,
+                FieldType::Int,
+                FieldType::Long,
+                FieldType::Short,
+                FieldType::Boolean,
+            ]
+        );
+        assert_eq!(desc.param_slot_count(), 10);
+        assert_eq!(desc.ret, Some(FieldType::Boolean));
+    }
+
+    #[test]
+    fn test_class_names_that_look_like_descriptors() {
+        // 类名中的 I、V、[ 之类的字母不是类型
+        let desc = MethodDescriptor::parse("(LIV;[LJ;)LV;").unwrap();
+        assert_eq!(desc.params, [object("IV"), array(object("J"))]);
+        assert_eq!(desc.ret, Some(object("V")));
+    }
+
+    #[test]
+    fn test_field_types() {
+        assert_eq!(FieldType::parse("J").unwrap(), FieldType::Long);
+        assert_eq!(
+            FieldType::parse("[[[D").unwrap(),
+            array(array(array(FieldType::Double)))
+        );
+        assert_eq!(FieldType::parse("Ljava/util/Map$Entry;").unwrap(), object("java/util/Map$Entry"));
+        assert_eq!(array(object("java/lang/Object")).descriptor(), "[Ljava/lang/Object;");
+        assert_eq!(FieldType::parse("V").unwrap_err().position, 0);
+        assert_eq!(FieldType::parse("II").unwrap_err().position, 1);
+        assert_eq!(FieldType::parse("").unwrap_err().position, 0);
+        assert_eq!(FieldType::parse("[").unwrap_err().position, 1);
+    }
+
+    #[test]
+    fn test_missing_semicolon() {
+        let (position, message) = error("(Ljava/lang/String)V");
+        assert_eq!(position, 1);
+        assert!(message.contains("';'"), "{}", message);
+        // 数组元素的类名：位置指向 L 而不是 [
+        assert_eq!(error("(I[[Ljava/lang/Object)V").0, 4);
+        assert_eq!(error("()Ljava/lang/String").0, 2);
+    }
+
+    #[test]
+    fn test_missing_close_paren() {
+        let (position, message) = error("([[Ljava/lang/String;JD");
+        assert_eq!(position, 23);
+        assert!(message.contains("')'"), "{}", message);
+        assert_eq!(error("(").0, 1);
+    }
+
+    #[test]
+    fn test_other_malformed_descriptors() {
+        assert_eq!(error("").0, 0);
+        assert_eq!(error("I)V").0, 0);
+        assert_eq!(error("()").0, 2); // 缺少返回类型
+        assert_eq!(error("(V)V").0, 1); // void 参数
+        assert_eq!(error("()[V").0, 3); // void 数组
+        assert_eq!(error("(L;)V").0, 1); // 空类名
+        assert_eq!(error("(Q)V").0, 1);
+        assert_eq!(error("()VV").0, 3);
+        assert_eq!(error("(I)I;").0, 4);
+        let deep = format!("({}I)V", "[".repeat(256));
+        assert_eq!(error(&deep).0, 1);
+        assert!(MethodDescriptor::parse(&format!("({}I)V", "[".repeat(255))).is_ok());
+    }
+
+    #[test]
+    fn test_error_display() {
+        let err = MethodDescriptor::parse("(I").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid descriptor \"(I\" at position 2: missing ')' after parameter types"
+        );
+    }
+
+    #[test]
+    fn test_default_values() {
+        assert!(matches!(jvm_default_value(&FieldType::Boolean), JvmValue::Int(0)));
+        assert!(matches!(jvm_default_value(&FieldType::Char), JvmValue::Int(0)));
+        assert!(matches!(jvm_default_value(&FieldType::Long), JvmValue::Long(0)));
+        assert!(matches!(jvm_default_value(&FieldType::Float), JvmValue::Float(v) if v == 0.0));
+        assert!(matches!(jvm_default_value(&FieldType::Double), JvmValue::Double(v) if v == 0.0));
+        assert!(matches!(jvm_default_value(&object("java/lang/String")), JvmValue::Reference(None)));
+        assert!(matches!(jvm_default_value(&array(FieldType::Long)), JvmValue::Reference(None)));
+    }
+}
@@ -26,8 +26,16 @@
 //! ```
 
 pub mod parser;
+pub mod writer;
+pub mod builder;
 pub mod constant_pool;
+pub mod descriptor;
 pub mod attribute;
+pub mod static_constants;
+pub mod report;
+pub mod version;
+
+pub use parser::ParserOptions;
 
 use crate::Result;
 use std::path::Path;
@@ -111,6 +119,22 @@ impl ClassFile {
         parser::parse_class_file(bytes)
     }
 
+    /// 序列化为class文件字节（解析的逆过程）
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        writer::write_class_file(self)
+    }
+
+    /// 从文件路径加载class文件，使用指定的解析限制
+    pub fn from_file_with_options<P: AsRef<Path>>(path: P, options: &ParserOptions) -> Result<Self> {
+        let bytes = std::fs::read(path)?;
+        parser::parse_class_file_with_options(&bytes, options)
+    }
+
+    /// 从字节数组解析class文件，使用指定的解析限制
+    pub fn from_bytes_with_options(bytes: &[u8], options: &ParserOptions) -> Result<Self> {
+        parser::parse_class_file_with_options(bytes, options)
+    }
+
     /// 获取类名
     pub fn get_class_name(&self) -> Result<String> {
         self.constant_pool.get_class_name(self.this_class)
@@ -125,14 +149,24 @@ impl ClassFile {
         }
     }
 
+    /// SourceFile 属性给出的源文件名（如 "Foo.java"），没有该属性时为 None
+    pub fn get_source_file(&self) -> Result<Option<String>> {
+        match attribute::find_attribute(&self.attributes, &self.constant_pool, "SourceFile")? {
+            Some(attr) => Ok(Some(attr.parse_source_file(&self.constant_pool)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// BootstrapMethods 属性中的引导方法；没有 invokedynamic 的类没有这个属性，返回空列表
+    pub fn get_bootstrap_methods(&self) -> Result<Vec<attribute::BootstrapMethod>> {
+        match attribute::find_attribute(&self.attributes, &self.constant_pool, "BootstrapMethods")? {
+            Some(attr) => attr.parse_bootstrap_methods(),
+            None => Ok(Vec::new()),
+        }
+    }
+
     /// 获取Java版本
     pub fn get_java_version(&self) -> String {
-        match self.major_version {
-            52 => "Java 8".to_string(),
-            53 => "Java 9".to_string(),
-            54 => "Java 10".to_string(),
-            55 => "Java 11".to_string(),
-            _ => format!("Java (version {})", self.major_version),
-        }
+        version::java_version_name(self.major_version)
     }
 }
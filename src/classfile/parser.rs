@@ -6,6 +6,7 @@
 //! - Java class文件使用大端字节序（Big-Endian）
 //! - 需要按照JVM规范的顺序依次读取各个部分
 //! - 错误处理很重要，要能够识别无效的class文件
+//! - 不可信的输入需要限制大小：长度字段要在分配内存之前检查（见 [`ParserOptions`]）
 
 use super::*;
 use crate::Result;
@@ -16,17 +17,99 @@ use std::io::Cursor;
 /// Class文件魔数
 const MAGIC: u32 = 0xCAFEBABE;
 
-/// 解析class文件
+/// 解析限制
+///
+/// class 文件中的长度字段都来自输入本身，恶意或生成的文件可以声明巨大的常量池、
+/// 超长的 Utf8 或方法体。解析时在分配内存之前逐项检查这些限制，超出即报错。
+/// 默认值足够宽松，正常 javac 生成的 class 文件不会触发。
+#[derive(Debug, Clone)]
+pub struct ParserOptions {
+    /// 常量池总字节数上限（按 class 文件中的编码大小累计）
+    pub max_constant_pool_bytes: usize,
+    /// 单个 Utf8 常量的最大字节长度
+    pub max_utf8_length: usize,
+    /// 每个类的最大方法数
+    pub max_methods: usize,
+    /// 单个方法 Code 属性中字节码的最大长度
+    pub max_code_length: usize,
+    /// 解析完常量池后检查项之间的引用（见 [`ConstantPool::validate`](super::constant_pool::ConstantPool::validate)）
+    pub validate_constant_pool: bool,
+}
+
+impl Default for ParserOptions {
+    fn default() -> Self {
+        Self {
+            max_constant_pool_bytes: 16 * 1024 * 1024,
+            max_utf8_length: u16::MAX as usize,
+            max_methods: u16::MAX as usize,
+            // JVM 规范要求 code_length < 65536
+            max_code_length: u16::MAX as usize,
+            validate_constant_pool: false,
+        }
+    }
+}
+
+/// 检查某一项是否超出限制，错误信息包含限制名和实际值
+fn check_limit(name: &str, observed: usize, limit: usize) -> Result<()> {
+    if observed > limit {
+        return Err(anyhow!(
+            "Parse limit exceeded: {} = {} (limit {})",
+            name,
+            observed,
+            limit
+        ));
+    }
+    Ok(())
+}
+
+/// 检查输入是否像一个 class 文件，给出比 "Invalid magic number" 更具体的错误
+fn check_magic(bytes: &[u8]) -> Result<()> {
+    if bytes.is_empty() {
+        return Err(anyhow!(
+            "Empty input: expected a class file starting with 0x{:08X}",
+            MAGIC
+        ));
+    }
+    if bytes.len() < 4 {
+        return Err(anyhow!(
+            "Input too short to be a class file: {} bytes, expected at least the 4-byte magic 0x{:08X}",
+            bytes.len(),
+            MAGIC
+        ));
+    }
+    let magic = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    if magic != MAGIC {
+        let mut message = format!(
+            "Not a class file: invalid magic number 0x{:08X}, expected 0x{:08X}",
+            magic, MAGIC
+        );
+        // 常见错误：传入了 .java 源码或十六进制文本
+        let head = &bytes[..bytes.len().min(64)];
+        if head
+            .iter()
+            .all(|b| b.is_ascii_graphic() || b.is_ascii_whitespace())
+        {
+            message.push_str(" (input looks like text; expected compiled class file bytes)");
+        }
+        return Err(anyhow!(message));
+    }
+    Ok(())
+}
+
+/// 解析class文件（使用默认限制）
 pub fn parse_class_file(bytes: &[u8]) -> Result<ClassFile> {
+    parse_class_file_with_options(bytes, &ParserOptions::default())
+}
+
+/// 解析class文件，使用指定的解析限制
+pub fn parse_class_file_with_options(bytes: &[u8], options: &ParserOptions) -> Result<ClassFile> {
+    check_magic(bytes)?;
     let mut reader = Cursor::new(bytes);
 
     // 1. 读取魔数
     let magic = reader
         .read_u32::<BigEndian>()
         .context("Failed to read magic number")?;
-    if magic != MAGIC {
-        return Err(anyhow!("Invalid magic number: 0x{:X}", magic));
-    }
 
     // 2. 读取版本号
     let minor_version = reader
@@ -37,7 +120,10 @@ pub fn parse_class_file(bytes: &[u8]) -> Result<ClassFile> {
         .context("Failed to read major version")?;
 
     // 3. 解析常量池
-    let constant_pool = parse_constant_pool(&mut reader)?;
+    let constant_pool = parse_constant_pool(&mut reader, options)?;
+    if options.validate_constant_pool {
+        constant_pool.validate().context("Invalid constant pool")?;
+    }
 
     // 4. 读取访问标志
     let access_flags = reader
@@ -56,13 +142,13 @@ pub fn parse_class_file(bytes: &[u8]) -> Result<ClassFile> {
     let interfaces = parse_interfaces(&mut reader)?;
 
     // 7. 读取字段
-    let fields = parse_fields(&mut reader, &constant_pool)?;
+    let fields = parse_fields(&mut reader, &constant_pool, options)?;
 
     // 8. 读取方法
-    let methods = parse_methods(&mut reader, &constant_pool)?;
+    let methods = parse_methods(&mut reader, &constant_pool, options)?;
 
     // 9. 读取属性
-    let attributes = parse_attributes(&mut reader, &constant_pool)?;
+    let attributes = parse_attributes(&mut reader, &constant_pool, options)?;
 
     Ok(ClassFile {
         magic,
@@ -80,15 +166,25 @@ pub fn parse_class_file(bytes: &[u8]) -> Result<ClassFile> {
 }
 
 /// 解析常量池
-fn parse_constant_pool(reader: &mut Cursor<&[u8]>) -> Result<constant_pool::ConstantPool> {
+fn parse_constant_pool(
+    reader: &mut Cursor<&[u8]>,
+    options: &ParserOptions,
+) -> Result<constant_pool::ConstantPool> {
     let count = reader
         .read_u16::<BigEndian>()
         .context("Failed to read constant pool count")?;
 
+    if count == 0 {
+        return Err(anyhow!("Invalid constant pool count 0 (index 0 is reserved, so the count is at least 1)"));
+    }
     let mut pool = constant_pool::ConstantPool::new(count as usize);
 
+    // 已读取的常量池字节数（tag + 内容）
+    let mut pool_bytes = 0usize;
+
     let mut i = 1;
     while i < count {
+        let start = reader.position();
         let tag = reader
             .read_u8()
             .context(format!("Failed to read constant pool tag at {}", i))?;
@@ -99,11 +195,17 @@ fn parse_constant_pool(reader: &mut Cursor<&[u8]>) -> Result<constant_pool::Cons
         let entry = match tag {
             CONSTANT_UTF8 => {
                 let length = reader.read_u16::<BigEndian>()?;
+                // 先检查限制，再分配缓冲区
+                check_limit("max_utf8_length", length as usize, options.max_utf8_length)?;
+                check_limit(
+                    "max_constant_pool_bytes",
+                    pool_bytes + 3 + length as usize,
+                    options.max_constant_pool_bytes,
+                )?;
                 let mut buf = vec![0u8; length as usize];
                 std::io::Read::read_exact(reader, &mut buf)?;
-                // Java使用修改过的UTF-8编码，这里简化处理
-                let s = String::from_utf8(buf)
-                    .context(format!("Invalid UTF-8 at constant pool index {}", i))?;
+                let s = constant_pool::decode_mutf8(&buf)
+                    .context(format!("Invalid Utf8 constant at constant pool index {}", i))?;
                 ConstantPoolEntry::Utf8(s)
             }
             CONSTANT_INTEGER => {
@@ -116,14 +218,20 @@ fn parse_constant_pool(reader: &mut Cursor<&[u8]>) -> Result<constant_pool::Cons
             }
             CONSTANT_LONG => {
                 let value = reader.read_i64::<BigEndian>()?;
-                pool.set(i, ConstantPoolEntry::Long(value));
-                i += 1; // Long占两个位置
+                pool_bytes += (reader.position() - start) as usize;
+                check_limit("max_constant_pool_bytes", pool_bytes, options.max_constant_pool_bytes)?;
+                check_second_slot(i, count, "Long")?;
+                pool.set(i, ConstantPoolEntry::Long(value))?;
+                i += 2; // Long占两个位置（continue 会跳过循环末尾的 i += 1）
                 continue;
             }
             CONSTANT_DOUBLE => {
                 let value = reader.read_f64::<BigEndian>()?;
-                pool.set(i, ConstantPoolEntry::Double(value));
-                i += 1; // Double占两个位置
+                pool_bytes += (reader.position() - start) as usize;
+                check_limit("max_constant_pool_bytes", pool_bytes, options.max_constant_pool_bytes)?;
+                check_second_slot(i, count, "Double")?;
+                pool.set(i, ConstantPoolEntry::Double(value))?;
+                i += 2; // Double占两个位置
                 continue;
             }
             CONSTANT_CLASS => {
@@ -186,16 +294,48 @@ fn parse_constant_pool(reader: &mut Cursor<&[u8]>) -> Result<constant_pool::Cons
                     name_and_type_index,
                 }
             }
+            CONSTANT_DYNAMIC => {
+                let bootstrap_method_attr_index = reader.read_u16::<BigEndian>()?;
+                let name_and_type_index = reader.read_u16::<BigEndian>()?;
+                ConstantPoolEntry::Dynamic {
+                    bootstrap_method_attr_index,
+                    name_and_type_index,
+                }
+            }
+            CONSTANT_MODULE => {
+                let name_index = reader.read_u16::<BigEndian>()?;
+                ConstantPoolEntry::Module { name_index }
+            }
+            CONSTANT_PACKAGE => {
+                let name_index = reader.read_u16::<BigEndian>()?;
+                ConstantPoolEntry::Package { name_index }
+            }
             _ => return Err(anyhow!("Unknown constant pool tag: {}", tag)),
         };
 
-        pool.set(i, entry);
+        pool_bytes += (reader.position() - start) as usize;
+        check_limit("max_constant_pool_bytes", pool_bytes, options.max_constant_pool_bytes)?;
+
+        pool.set(i, entry)?;
         i += 1;
     }
 
     Ok(pool)
 }
 
+/// Long/Double 占两个索引，第二个索引也必须在常量池范围内
+fn check_second_slot(index: u16, count: u16, kind: &str) -> Result<()> {
+    if index + 1 >= count {
+        return Err(anyhow!(
+            "{} constant at constant pool index {} needs two slots but the pool has only {} entries",
+            kind,
+            index,
+            count
+        ));
+    }
+    Ok(())
+}
+
 /// 解析接口表
 fn parse_interfaces(reader: &mut Cursor<&[u8]>) -> Result<Vec<u16>> {
     let count = reader.read_u16::<BigEndian>()?;
@@ -210,11 +350,12 @@ fn parse_interfaces(reader: &mut Cursor<&[u8]>) -> Result<Vec<u16>> {
 fn parse_fields(
     reader: &mut Cursor<&[u8]>,
     pool: &constant_pool::ConstantPool,
+    options: &ParserOptions,
 ) -> Result<Vec<FieldInfo>> {
     let count = reader.read_u16::<BigEndian>()?;
     let mut fields = Vec::with_capacity(count as usize);
     for _ in 0..count {
-        fields.push(parse_field(reader, pool)?);
+        fields.push(parse_field(reader, pool, options)?);
     }
     Ok(fields)
 }
@@ -223,11 +364,12 @@ fn parse_fields(
 fn parse_field(
     reader: &mut Cursor<&[u8]>,
     pool: &constant_pool::ConstantPool,
+    options: &ParserOptions,
 ) -> Result<FieldInfo> {
     let access_flags = reader.read_u16::<BigEndian>()?;
     let name_index = reader.read_u16::<BigEndian>()?;
     let descriptor_index = reader.read_u16::<BigEndian>()?;
-    let attributes = parse_attributes(reader, pool)?;
+    let attributes = parse_attributes(reader, pool, options)?;
 
     Ok(FieldInfo {
         access_flags,
@@ -241,11 +383,13 @@ fn parse_field(
 fn parse_methods(
     reader: &mut Cursor<&[u8]>,
     pool: &constant_pool::ConstantPool,
+    options: &ParserOptions,
 ) -> Result<Vec<MethodInfo>> {
     let count = reader.read_u16::<BigEndian>()?;
+    check_limit("max_methods", count as usize, options.max_methods)?;
     let mut methods = Vec::with_capacity(count as usize);
     for _ in 0..count {
-        methods.push(parse_method(reader, pool)?);
+        methods.push(parse_method(reader, pool, options)?);
     }
     Ok(methods)
 }
@@ -254,11 +398,12 @@ fn parse_methods(
 fn parse_method(
     reader: &mut Cursor<&[u8]>,
     pool: &constant_pool::ConstantPool,
+    options: &ParserOptions,
 ) -> Result<MethodInfo> {
     let access_flags = reader.read_u16::<BigEndian>()?;
     let name_index = reader.read_u16::<BigEndian>()?;
     let descriptor_index = reader.read_u16::<BigEndian>()?;
-    let attributes = parse_attributes(reader, pool)?;
+    let attributes = parse_attributes(reader, pool, options)?;
 
     Ok(MethodInfo {
         access_flags,
@@ -272,11 +417,12 @@ fn parse_method(
 fn parse_attributes(
     reader: &mut Cursor<&[u8]>,
     pool: &constant_pool::ConstantPool,
+    options: &ParserOptions,
 ) -> Result<Vec<attribute::AttributeInfo>> {
     let count = reader.read_u16::<BigEndian>()?;
     let mut attributes = Vec::with_capacity(count as usize);
     for _ in 0..count {
-        attributes.push(parse_attribute(reader, pool)?);
+        attributes.push(parse_attribute(reader, pool, options)?);
     }
     Ok(attributes)
 }
@@ -284,10 +430,31 @@ fn parse_attributes(
 /// 解析单个属性
 fn parse_attribute(
     reader: &mut Cursor<&[u8]>,
-    _pool: &constant_pool::ConstantPool,
+    pool: &constant_pool::ConstantPool,
+    options: &ParserOptions,
 ) -> Result<attribute::AttributeInfo> {
     let name_index = reader.read_u16::<BigEndian>()?;
     let length = reader.read_u32::<BigEndian>()?;
+
+    // 声明的长度不能超过剩余字节，避免按伪造的长度分配内存
+    let remaining = reader.get_ref().len() as u64 - reader.position();
+    if length as u64 > remaining {
+        return Err(anyhow!(
+            "Attribute length {} exceeds remaining {} bytes",
+            length,
+            remaining
+        ));
+    }
+
+    // Code 属性：max_stack(u2) max_locals(u2) code_length(u4) ...
+    // 在读取整个属性之前检查 code_length
+    if length >= 8 && matches!(pool.get_utf8(name_index), Ok(name) if name == "Code") {
+        let at = reader.position() as usize + 4;
+        let bytes = &reader.get_ref()[at..at + 4];
+        let code_length = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        check_limit("max_code_length", code_length as usize, options.max_code_length)?;
+    }
+
     let mut info = vec![0u8; length as usize];
     std::io::Read::read_exact(reader, &mut info)?;
 
@@ -0,0 +1,267 @@
+//! # 结构化报告
+//!
+//! 把解析出的 [`ClassFile`] 转成只包含名字和数值的视图结构，再序列化成 JSON，
+//! 供其他工具读取（`rsjvm parse --format json`）。
+//! 常量池索引都被解析成符号形式，读者不需要自己查常量池。
+//!
+//! ## 学习要点
+//! - 同一个标志位在不同位置含义不同：0x0020 对类是 ACC_SUPER，对方法是 ACC_SYNCHRONIZED；
+//!   0x0040 对字段是 volatile，对方法是 bridge
+//! - long/double 常量占两个常量池槽位，第二个槽位不可用，报告中跳过
+//! - 视图结构与核心类型分开，核心类型不需要实现序列化
+
+use super::attribute::{find_attribute, CodeAttribute};
+use super::constant_pool::{ConstantPool, ConstantPoolEntry};
+use super::{access_flags, ClassFile};
+use crate::interpreter::disasm::{disassemble, symbol};
+use crate::Result;
+use serde::Serialize;
+
+/// 标志位所属的结构（决定同一个位的名字）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlagTarget {
+    Class,
+    Field,
+    Method,
+}
+
+/// 整个 class 文件的报告
+#[derive(Debug, Clone, Serialize)]
+pub struct ClassReport {
+    pub version: VersionReport,
+    pub access_flags: FlagsReport,
+    pub this_class: String,
+    /// java/lang/Object 没有父类
+    pub super_class: Option<String>,
+    pub interfaces: Vec<String>,
+    pub source_file: Option<String>,
+    pub constant_pool: Vec<ConstantReport>,
+    pub fields: Vec<MemberReport>,
+    pub methods: Vec<MemberReport>,
+}
+
+/// class 文件版本
+#[derive(Debug, Clone, Serialize)]
+pub struct VersionReport {
+    pub major: u16,
+    pub minor: u16,
+    /// 对应的 Java 版本，如 "Java 8"
+    pub java: String,
+}
+
+/// 访问标志：原始值和各个标志的名字
+#[derive(Debug, Clone, Serialize)]
+pub struct FlagsReport {
+    pub raw: u16,
+    /// 如 ["ACC_PUBLIC", "ACC_STATIC"]，按位从低到高
+    pub names: Vec<&'static str>,
+}
+
+/// 一个常量池项
+#[derive(Debug, Clone, Serialize)]
+pub struct ConstantReport {
+    pub index: u16,
+    /// JVMS 中的 tag 名，如 "Methodref"
+    pub tag: &'static str,
+    /// 解析后的符号形式，如 "Method java/lang/Object.<init>:()V"
+    pub value: String,
+}
+
+/// 字段或方法
+#[derive(Debug, Clone, Serialize)]
+pub struct MemberReport {
+    pub name: String,
+    pub descriptor: String,
+    pub access_flags: FlagsReport,
+    /// 方法的 Code 属性；字段、抽象方法和本地方法没有
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<CodeReport>,
+}
+
+/// Code 属性的概要
+#[derive(Debug, Clone, Serialize)]
+pub struct CodeReport {
+    pub max_stack: u16,
+    pub max_locals: u16,
+    pub code_length: usize,
+    /// 反汇编后的指令（javap 风格，每条一行）；只在要求时生成
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disassembly: Option<Vec<String>>,
+}
+
+impl ClassReport {
+    /// 生成报告；`disassembly` 为 true 时每个方法附带反汇编结果
+    pub fn new(class_file: &ClassFile, disassembly: bool) -> Result<Self> {
+        let cp = &class_file.constant_pool;
+        let interfaces = class_file
+            .interfaces
+            .iter()
+            .map(|&index| cp.get_class_name(index))
+            .collect::<Result<_>>()?;
+        let fields = class_file
+            .fields
+            .iter()
+            .map(|field| {
+                Ok(MemberReport {
+                    name: cp.get_utf8(field.name_index)?,
+                    descriptor: cp.get_utf8(field.descriptor_index)?,
+                    access_flags: FlagsReport::new(field.access_flags, FlagTarget::Field),
+                    code: None,
+                })
+            })
+            .collect::<Result<_>>()?;
+        let methods = class_file
+            .methods
+            .iter()
+            .map(|method| {
+                let code = match find_attribute(&method.attributes, cp, "Code")? {
+                    Some(attr) => Some(CodeReport::new(&attr.parse_code_attribute()?, cp, disassembly)?),
+                    None => None,
+                };
+                Ok(MemberReport {
+                    name: cp.get_utf8(method.name_index)?,
+                    descriptor: cp.get_utf8(method.descriptor_index)?,
+                    access_flags: FlagsReport::new(method.access_flags, FlagTarget::Method),
+                    code,
+                })
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(ClassReport {
+            version: VersionReport {
+                major: class_file.major_version,
+                minor: class_file.minor_version,
+                java: class_file.get_java_version(),
+            },
+            access_flags: FlagsReport::new(class_file.access_flags, FlagTarget::Class),
+            this_class: class_file.get_class_name()?,
+            super_class: match class_file.super_class {
+                0 => None,
+                index => Some(cp.get_class_name(index)?),
+            },
+            interfaces,
+            source_file: class_file.get_source_file()?,
+            constant_pool: constant_pool_report(cp)?,
+            fields,
+            methods,
+        })
+    }
+
+    /// 格式化（缩进）的 JSON 文本
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+impl FlagsReport {
+    pub fn new(raw: u16, target: FlagTarget) -> Self {
+        FlagsReport {
+            raw,
+            names: flag_names(raw, target),
+        }
+    }
+}
+
+impl CodeReport {
+    fn new(code: &CodeAttribute, cp: &ConstantPool, disassembly: bool) -> Result<Self> {
+        let disassembly = if disassembly {
+            Some(disassemble(&code.code, cp)?.iter().map(ToString::to_string).collect())
+        } else {
+            None
+        };
+        Ok(CodeReport {
+            max_stack: code.max_stack,
+            max_locals: code.max_locals,
+            code_length: code.code.len(),
+            disassembly,
+        })
+    }
+}
+
+/// 访问标志中各个位的名字（按位从低到高）
+pub fn flag_names(flags: u16, target: FlagTarget) -> Vec<&'static str> {
+    use access_flags::*;
+    let table: &[(u16, &str)] = match target {
+        FlagTarget::Class => &[
+            (ACC_PUBLIC, "ACC_PUBLIC"),
+            (ACC_FINAL, "ACC_FINAL"),
+            (ACC_SUPER, "ACC_SUPER"),
+            (ACC_INTERFACE, "ACC_INTERFACE"),
+            (ACC_ABSTRACT, "ACC_ABSTRACT"),
+            (ACC_SYNTHETIC, "ACC_SYNTHETIC"),
+            (ACC_ANNOTATION, "ACC_ANNOTATION"),
+            (ACC_ENUM, "ACC_ENUM"),
+            (0x8000, "ACC_MODULE"),
+        ],
+        FlagTarget::Field => &[
+            (ACC_PUBLIC, "ACC_PUBLIC"),
+            (ACC_PRIVATE, "ACC_PRIVATE"),
+            (ACC_PROTECTED, "ACC_PROTECTED"),
+            (ACC_STATIC, "ACC_STATIC"),
+            (ACC_FINAL, "ACC_FINAL"),
+            (ACC_VOLATILE, "ACC_VOLATILE"),
+            (ACC_TRANSIENT, "ACC_TRANSIENT"),
+            (ACC_SYNTHETIC, "ACC_SYNTHETIC"),
+            (ACC_ENUM, "ACC_ENUM"),
+        ],
+        FlagTarget::Method => &[
+            (ACC_PUBLIC, "ACC_PUBLIC"),
+            (ACC_PRIVATE, "ACC_PRIVATE"),
+            (ACC_PROTECTED, "ACC_PROTECTED"),
+            (ACC_STATIC, "ACC_STATIC"),
+            (ACC_FINAL, "ACC_FINAL"),
+            (ACC_SYNCHRONIZED, "ACC_SYNCHRONIZED"),
+            (ACC_BRIDGE, "ACC_BRIDGE"),
+            (ACC_VARARGS, "ACC_VARARGS"),
+            (ACC_NATIVE, "ACC_NATIVE"),
+            (ACC_ABSTRACT, "ACC_ABSTRACT"),
+            (ACC_STRICT, "ACC_STRICT"),
+            (ACC_SYNTHETIC, "ACC_SYNTHETIC"),
+        ],
+    };
+    table
+        .iter()
+        .filter(|&&(bit, _)| flags & bit != 0)
+        .map(|&(_, name)| name)
+        .collect()
+}
+
+/// 所有可用的常量池项（跳过索引 0 和 long/double 的第二个槽位）
+fn constant_pool_report(cp: &ConstantPool) -> Result<Vec<ConstantReport>> {
+    let mut constants = Vec::new();
+    for (index, entry) in cp.entries.iter().enumerate() {
+        let Some(entry) = entry else {
+            continue;
+        };
+        let index = index as u16;
+        constants.push(ConstantReport {
+            index,
+            tag: tag_name(entry),
+            value: symbol(cp, index)?,
+        });
+    }
+    Ok(constants)
+}
+
+/// JVMS 表 4.4-B 中的 tag 名
+fn tag_name(entry: &ConstantPoolEntry) -> &'static str {
+    match entry {
+        ConstantPoolEntry::Utf8(_) => "Utf8",
+        ConstantPoolEntry::Integer(_) => "Integer",
+        ConstantPoolEntry::Float(_) => "Float",
+        ConstantPoolEntry::Long(_) => "Long",
+        ConstantPoolEntry::Double(_) => "Double",
+        ConstantPoolEntry::Class { .. } => "Class",
+        ConstantPoolEntry::String { .. } => "String",
+        ConstantPoolEntry::FieldRef { .. } => "Fieldref",
+        ConstantPoolEntry::MethodRef { .. } => "Methodref",
+        ConstantPoolEntry::InterfaceMethodRef { .. } => "InterfaceMethodref",
+        ConstantPoolEntry::NameAndType { .. } => "NameAndType",
+        ConstantPoolEntry::MethodHandle { .. } => "MethodHandle",
+        ConstantPoolEntry::MethodType { .. } => "MethodType",
+        ConstantPoolEntry::InvokeDynamic { .. } => "InvokeDynamic",
+        ConstantPoolEntry::Dynamic { .. } => "Dynamic",
+        ConstantPoolEntry::Module { .. } => "Module",
+        ConstantPoolEntry::Package { .. } => "Package",
+    }
+}
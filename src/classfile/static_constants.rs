@@ -0,0 +1,336 @@
+//! # 静态常量分析
+//!
+//! 列出类中所有 `static final` 字段的编译期常量值，供 `rsjvm parse --constants` 使用。
+//!
+//! 常量值有两个来源：
+//! - 字段的 ConstantValue 属性（javac 对常量表达式的折叠结果）
+//! - `<clinit>` 中只由常量和算术组成的赋值序列（手写或学生编译器生成的 class 文件
+//!   可能没有折叠），用一个很小的抽象求值器在不执行代码的情况下算出结果
+//!
+//! ## 学习要点
+//! - 只有 `static final` 且初始值是常量表达式的字段才有 ConstantValue 属性
+//! - 求值器只模拟操作数栈，遇到方法调用、分支、未知字段读取等立即放弃，
+//!   此后未赋值的字段报告为"运行时初始化"
+
+use super::attribute::find_attribute;
+use super::constant_pool::ConstantPoolEntry;
+use super::{access_flags, ClassFile};
+use crate::interpreter::instructions::opcodes::*;
+use crate::Result;
+use anyhow::anyhow;
+use std::collections::HashMap;
+use std::fmt;
+
+/// 常量值
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConstValue {
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    String(String),
+}
+
+impl fmt::Display for ConstValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConstValue::Int(v) => write!(f, "{}", v),
+            ConstValue::Long(v) => write!(f, "{}L", v),
+            ConstValue::Float(v) => write!(f, "{}f", v),
+            ConstValue::Double(v) => write!(f, "{}", v),
+            ConstValue::String(s) => write!(f, "{:?}", s),
+        }
+    }
+}
+
+/// static final 字段的分析结果
+#[derive(Debug, Clone, PartialEq)]
+pub enum StaticFinalValue {
+    /// 来自 ConstantValue 属性
+    ConstantValue(ConstValue),
+    /// 由 `<clinit>` 的常量赋值序列算出
+    Computed(ConstValue),
+    /// 依赖运行时（方法调用等），无法静态确定
+    RuntimeInitialized,
+}
+
+impl fmt::Display for StaticFinalValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StaticFinalValue::ConstantValue(v) => write!(f, "{} (ConstantValue)", v),
+            StaticFinalValue::Computed(v) => write!(f, "{} (computed from <clinit>)", v),
+            StaticFinalValue::RuntimeInitialized => write!(f, "runtime-initialized"),
+        }
+    }
+}
+
+/// 一个 static final 字段
+#[derive(Debug, Clone)]
+pub struct StaticConstant {
+    pub name: String,
+    pub descriptor: String,
+    pub value: StaticFinalValue,
+}
+
+/// 列出类中所有 static final 字段及其常量值（按字段声明顺序）
+pub fn static_final_constants(class_file: &ClassFile) -> Result<Vec<StaticConstant>> {
+    let pool = &class_file.constant_pool;
+    let this_class = class_file.get_class_name()?;
+    let mask = access_flags::ACC_STATIC | access_flags::ACC_FINAL;
+
+    // 1. ConstantValue 属性
+    let mut constants = Vec::new();
+    for field in &class_file.fields {
+        if field.access_flags & mask != mask {
+            continue;
+        }
+        let name = pool.get_utf8(field.name_index)?;
+        let descriptor = pool.get_utf8(field.descriptor_index)?;
+
+        let value = match find_attribute(&field.attributes, pool, "ConstantValue")? {
+            Some(attr) => Some(StaticFinalValue::ConstantValue(constant_at(
+                class_file,
+                attr.parse_constant_value()?,
+            )?)),
+            None => None,
+        };
+        constants.push((name, descriptor, value));
+    }
+
+    // 2. 对剩余字段求值 <clinit>
+    let mut computed = HashMap::new();
+    if constants.iter().any(|(_, _, v)| v.is_none()) {
+        if let Some(code) = clinit_code(class_file)? {
+            let known: HashMap<String, ConstValue> = constants
+                .iter()
+                .filter_map(|(name, _, v)| match v {
+                    Some(StaticFinalValue::ConstantValue(c)) => Some((name.clone(), c.clone())),
+                    _ => None,
+                })
+                .collect();
+            computed = evaluate_clinit(class_file, &this_class, &code, known);
+        }
+    }
+
+    Ok(constants
+        .into_iter()
+        .map(|(name, descriptor, value)| {
+            let value = value.unwrap_or_else(|| match computed.get(&name) {
+                Some(c) => StaticFinalValue::Computed(c.clone()),
+                None => StaticFinalValue::RuntimeInitialized,
+            });
+            StaticConstant {
+                name,
+                descriptor,
+                value,
+            }
+        })
+        .collect())
+}
+
+/// 读取常量池中的字面量
+fn constant_at(class_file: &ClassFile, index: u16) -> Result<ConstValue> {
+    match class_file.constant_pool.get(index)? {
+        ConstantPoolEntry::Integer(v) => Ok(ConstValue::Int(*v)),
+        ConstantPoolEntry::Long(v) => Ok(ConstValue::Long(*v)),
+        ConstantPoolEntry::Float(v) => Ok(ConstValue::Float(*v)),
+        ConstantPoolEntry::Double(v) => Ok(ConstValue::Double(*v)),
+        ConstantPoolEntry::String { string_index } => Ok(ConstValue::String(
+            class_file.constant_pool.get_utf8(*string_index)?,
+        )),
+        other => Err(anyhow!("Not a literal constant at #{}: {:?}", index, other)),
+    }
+}
+
+/// 查找 `<clinit>` 的字节码
+fn clinit_code(class_file: &ClassFile) -> Result<Option<Vec<u8>>> {
+    let pool = &class_file.constant_pool;
+    for method in &class_file.methods {
+        if pool.get_utf8(method.name_index)? != "<clinit>" {
+            continue;
+        }
+        for attr in &method.attributes {
+            if pool.get_utf8(attr.name_index)? == "Code" {
+                return Ok(Some(attr.parse_code_attribute()?.code));
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// 抽象求值 `<clinit>`：返回在第一条无法处理的指令之前赋值的本类静态字段
+///
+/// 直线执行，不跟随任何分支，因此已记录的赋值都一定会执行
+fn evaluate_clinit(
+    class_file: &ClassFile,
+    this_class: &str,
+    code: &[u8],
+    mut known: HashMap<String, ConstValue>,
+) -> HashMap<String, ConstValue> {
+    let mut assigned = HashMap::new();
+    let mut stack: Vec<ConstValue> = Vec::new();
+    let mut pc = 0;
+
+    while pc < code.len() {
+        match step(class_file, this_class, code, pc, &mut stack, &mut known, &mut assigned) {
+            Some(next) => pc = next,
+            None => break, // 非常量操作，放弃
+        }
+    }
+
+    assigned
+}
+
+/// 执行一条指令，返回下一条指令的位置；遇到无法静态求值的指令返回 None
+fn step(
+    class_file: &ClassFile,
+    this_class: &str,
+    code: &[u8],
+    pc: usize,
+    stack: &mut Vec<ConstValue>,
+    known: &mut HashMap<String, ConstValue>,
+    assigned: &mut HashMap<String, ConstValue>,
+) -> Option<usize> {
+    let opcode = code[pc];
+    let u8_at = |at: usize| code.get(at).copied();
+    let u16_at = |at: usize| Some(u16::from_be_bytes([u8_at(at)?, u8_at(at + 1)?]));
+
+    match opcode {
+        ICONST_M1..=ICONST_5 => stack.push(ConstValue::Int(opcode as i32 - ICONST_0 as i32)),
+        LCONST_0 | LCONST_1 => stack.push(ConstValue::Long((opcode - LCONST_0) as i64)),
+        FCONST_0..=FCONST_2 => stack.push(ConstValue::Float((opcode - FCONST_0) as f32)),
+        DCONST_0 | DCONST_1 => stack.push(ConstValue::Double((opcode - DCONST_0) as f64)),
+        BIPUSH => {
+            stack.push(ConstValue::Int(u8_at(pc + 1)? as i8 as i32));
+            return Some(pc + 2);
+        }
+        SIPUSH => {
+            stack.push(ConstValue::Int(u16_at(pc + 1)? as i16 as i32));
+            return Some(pc + 3);
+        }
+        LDC => {
+            stack.push(constant_at(class_file, u8_at(pc + 1)? as u16).ok()?);
+            return Some(pc + 2);
+        }
+        LDC_W | LDC2_W => {
+            stack.push(constant_at(class_file, u16_at(pc + 1)?).ok()?);
+            return Some(pc + 3);
+        }
+        DUP => {
+            let top = stack.last()?.clone();
+            stack.push(top);
+        }
+        INEG => match stack.pop()? {
+            ConstValue::Int(v) => stack.push(ConstValue::Int(v.wrapping_neg())),
+            _ => return None,
+        },
+        LNEG => match stack.pop()? {
+            ConstValue::Long(v) => stack.push(ConstValue::Long(v.wrapping_neg())),
+            _ => return None,
+        },
+        I2L => match stack.pop()? {
+            ConstValue::Int(v) => stack.push(ConstValue::Long(v as i64)),
+            _ => return None,
+        },
+        L2I => match stack.pop()? {
+            ConstValue::Long(v) => stack.push(ConstValue::Int(v as i32)),
+            _ => return None,
+        },
+        IADD | ISUB | IMUL | IDIV | IREM | ISHL | ISHR | IUSHR | IAND | IOR | IXOR => {
+            let (ConstValue::Int(b), ConstValue::Int(a)) = (stack.pop()?, stack.pop()?) else {
+                return None;
+            };
+            // 除零在运行时抛 ArithmeticException，不能静态求值
+            if b == 0 && (opcode == IDIV || opcode == IREM) {
+                return None;
+            }
+            let result = match opcode {
+                IADD => a.wrapping_add(b),
+                ISUB => a.wrapping_sub(b),
+                IMUL => a.wrapping_mul(b),
+                IDIV => a.wrapping_div(b),
+                IREM => a.wrapping_rem(b),
+                ISHL => a.wrapping_shl(b as u32 & 0x1f),
+                ISHR => a.wrapping_shr(b as u32 & 0x1f),
+                IUSHR => ((a as u32) >> (b as u32 & 0x1f)) as i32,
+                IAND => a & b,
+                IOR => a | b,
+                _ => a ^ b,
+            };
+            stack.push(ConstValue::Int(result));
+        }
+        LADD | LSUB | LMUL | LAND | LOR | LXOR => {
+            let (ConstValue::Long(b), ConstValue::Long(a)) = (stack.pop()?, stack.pop()?) else {
+                return None;
+            };
+            let result = match opcode {
+                LADD => a.wrapping_add(b),
+                LSUB => a.wrapping_sub(b),
+                LMUL => a.wrapping_mul(b),
+                LAND => a & b,
+                LOR => a | b,
+                _ => a ^ b,
+            };
+            stack.push(ConstValue::Long(result));
+        }
+        FADD | FSUB | FMUL | FDIV => {
+            let (ConstValue::Float(b), ConstValue::Float(a)) = (stack.pop()?, stack.pop()?) else {
+                return None;
+            };
+            let result = match opcode {
+                FADD => a + b,
+                FSUB => a - b,
+                FMUL => a * b,
+                _ => a / b,
+            };
+            stack.push(ConstValue::Float(result));
+        }
+        DADD | DSUB | DMUL | DDIV => {
+            let (ConstValue::Double(b), ConstValue::Double(a)) = (stack.pop()?, stack.pop()?)
+            else {
+                return None;
+            };
+            let result = match opcode {
+                DADD => a + b,
+                DSUB => a - b,
+                DMUL => a * b,
+                _ => a / b,
+            };
+            stack.push(ConstValue::Double(result));
+        }
+        GETSTATIC | PUTSTATIC => {
+            let (class_name, field_name) = field_ref(class_file, u16_at(pc + 1)?)?;
+            if class_name != this_class {
+                return None;
+            }
+            if opcode == GETSTATIC {
+                // 只能读取已知常量
+                stack.push(known.get(&field_name)?.clone());
+            } else {
+                let value = stack.pop()?;
+                known.insert(field_name.clone(), value.clone());
+                assigned.insert(field_name, value);
+            }
+            return Some(pc + 3);
+        }
+        // return、方法调用、分支等：结束求值
+        _ => return None,
+    }
+    Some(pc + 1)
+}
+
+/// 解析字段引用，返回 (类名, 字段名)
+fn field_ref(class_file: &ClassFile, index: u16) -> Option<(String, String)> {
+    let pool = &class_file.constant_pool;
+    match pool.get(index).ok()? {
+        ConstantPoolEntry::FieldRef {
+            class_index,
+            name_and_type_index,
+        } => {
+            let class_name = pool.get_class_name(*class_index).ok()?;
+            let (name, _) = pool.get_name_and_type(*name_and_type_index).ok()?;
+            Some((class_name, name))
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,143 @@
+//! # class 文件版本检查
+//!
+//! 解析器接受任何版本的 class 文件，但新版本的 javac 会生成这里执行不了的字节码
+//! （lambda 的 invokedynamic、动态常量等），执行到那里才报错时很难看出原因。
+//! 加载类时先检查主版本号，超过支持的最高版本就拒绝加载，并在错误信息中列出预检发现的不支持的指令。
+//!
+//! ## 学习要点
+//! - 主版本号 = Java 版本 + 44（Java 8 是 52，Java 17 是 61），真实 JVM 遇到更新的版本抛出
+//!   UnsupportedClassVersionError
+//! - 版本号只是"可能不支持"的信号：Java 17 编译的简单类照样能运行，所以提供 `--force-version` 跳过检查
+//! - 预检不执行代码，只扫描每个方法的指令：引导方法不是已实现的 shim 的 invokedynamic、
+//!   ldc 动态常量（condy）都执行不了
+
+use super::attribute::{find_attribute, BootstrapMethod};
+use super::constant_pool::ConstantPoolEntry;
+use super::ClassFile;
+use crate::interpreter::decoded::{decode_method, Instruction};
+use crate::runtime::JavaException;
+use crate::Result;
+use anyhow::anyhow;
+
+/// 默认支持的最高主版本号（Java 8）
+pub const DEFAULT_MAX_MAJOR_VERSION: u16 = 52;
+
+/// 解释器实现了的 invokedynamic 引导方法：(类名, 方法名)
+const SUPPORTED_BOOTSTRAPS: &[(&str, &str)] =
+    &[("java/lang/invoke/StringConcatFactory", "makeConcatWithConstants")];
+
+/// 引导方法是否有解释器中的实现（见 `interpreter::indy`）
+pub fn is_supported_bootstrap(class_name: &str, method_name: &str) -> bool {
+    SUPPORTED_BOOTSTRAPS.contains(&(class_name, method_name))
+}
+
+/// 主版本号对应的 Java 版本名，如 52 → "Java 8"
+pub fn java_version_name(major_version: u16) -> String {
+    match major_version {
+        45 => "Java 1.1".to_string(),
+        46..=48 => format!("Java 1.{}", major_version - 44),
+        49..=69 => format!("Java {}", major_version - 44),
+        _ => format!("Java (version {})", major_version),
+    }
+}
+
+/// 主版本号超过 `max_major_version` 时抛出 UnsupportedClassVersionError
+pub fn check_class_version(class_file: &ClassFile, max_major_version: u16) -> Result<()> {
+    if class_file.major_version <= max_major_version {
+        return Ok(());
+    }
+    let mut message = format!(
+        "class {} was compiled for {} (major {}); rsjvm currently supports up to {} \
+         — pass --force-version to try anyway",
+        class_file.get_class_name()?,
+        class_file.get_java_version(),
+        class_file.major_version,
+        java_version_name(max_major_version)
+    );
+    let unsupported = unsupported_bytecode(class_file)?;
+    if !unsupported.is_empty() {
+        message.push_str(&format!(" (unsupported bytecode: {})", unsupported.join(", ")));
+    }
+    Err(JavaException::new("java/lang/UnsupportedClassVersionError", message).into())
+}
+
+/// 预检：扫描方法的指令，列出执行不了的指令，如 "invokedynamic java/lang/invoke/LambdaMetafactory.metafactory in run()V"
+///
+/// 同一个方法中的同一种问题只列一次；解码失败的方法跳过（执行时会报告）
+pub fn unsupported_bytecode(class_file: &ClassFile) -> Result<Vec<String>> {
+    let cp = &class_file.constant_pool;
+    let bootstrap_methods = class_file.get_bootstrap_methods()?;
+    let mut found = Vec::new();
+    for method in &class_file.methods {
+        let Some(attr) = find_attribute(&method.attributes, cp, "Code")? else {
+            continue;
+        };
+        let Ok(instructions) = decode_method(&attr.parse_code_attribute()?.code) else {
+            continue;
+        };
+        let method_name = format!(
+            "{}{}",
+            cp.get_utf8(method.name_index)?,
+            cp.get_utf8(method.descriptor_index)?
+        );
+        for (_, instruction) in instructions {
+            let problem = match instruction {
+                Instruction::InvokeDynamic { cp_index } => {
+                    let (class_name, name) = bootstrap_target(class_file, &bootstrap_methods, cp_index)?;
+                    if is_supported_bootstrap(&class_name, &name) {
+                        continue;
+                    }
+                    format!("invokedynamic {}.{}", class_name, name)
+                }
+                Instruction::Ldc { cp_index, .. }
+                    if matches!(cp.get(cp_index)?, ConstantPoolEntry::Dynamic { .. }) =>
+                {
+                    "ldc of a dynamic constant".to_string()
+                }
+                _ => continue,
+            };
+            let problem = format!("{} in {}", problem, method_name);
+            if !found.contains(&problem) {
+                found.push(problem);
+            }
+        }
+    }
+    Ok(found)
+}
+
+/// InvokeDynamic 常量的引导方法：(类名, 方法名)
+fn bootstrap_target(
+    class_file: &ClassFile,
+    bootstrap_methods: &[BootstrapMethod],
+    index: u16,
+) -> Result<(String, String)> {
+    let cp = &class_file.constant_pool;
+    let bootstrap_index = match cp.get(index)? {
+        ConstantPoolEntry::InvokeDynamic {
+            bootstrap_method_attr_index,
+            ..
+        } => *bootstrap_method_attr_index,
+        other => return Err(anyhow!("Expected InvokeDynamic at index {}, got {:?}", index, other)),
+    };
+    let bootstrap = bootstrap_methods
+        .get(bootstrap_index as usize)
+        .ok_or_else(|| anyhow!("Invalid bootstrap method index {}", bootstrap_index))?;
+    let reference_index = match cp.get(bootstrap.method_ref)? {
+        ConstantPoolEntry::MethodHandle { reference_index, .. } => *reference_index,
+        other => return Err(anyhow!("Expected MethodHandle at index {}, got {:?}", bootstrap.method_ref, other)),
+    };
+    match cp.get(reference_index)? {
+        ConstantPoolEntry::MethodRef {
+            class_index,
+            name_and_type_index,
+        }
+        | ConstantPoolEntry::InterfaceMethodRef {
+            class_index,
+            name_and_type_index,
+        } => Ok((
+            cp.get_class_name(*class_index)?,
+            cp.get_name_and_type(*name_and_type_index)?.0,
+        )),
+        other => Err(anyhow!("Expected MethodRef at index {}, got {:?}", reference_index, other)),
+    }
+}
@@ -0,0 +1,227 @@
+//! # Class文件序列化
+//!
+//! 把 [`ClassFile`] 写回字节数组，是 parser.rs 的逆过程。
+//!
+//! ## 学习要点
+//! - 写入顺序和读取顺序完全一致，同样使用大端字节序
+//! - 常量池计数是 `entries.len()`（包括保留的索引 0）
+//! - Long/Double 之后的槽位不写任何内容，其余空槽位说明常量池不完整
+//! - 属性保留原始字节，所以解析后再写回可以逐字节还原 javac 的输出
+
+use super::*;
+use crate::Result;
+use anyhow::anyhow;
+use byteorder::{BigEndian, WriteBytesExt};
+
+/// 序列化class文件
+pub fn write_class_file(class_file: &ClassFile) -> Result<Vec<u8>> {
+    let mut out = Vec::new();
+
+    // 1. 魔数和版本号
+    out.write_u32::<BigEndian>(class_file.magic)?;
+    out.write_u16::<BigEndian>(class_file.minor_version)?;
+    out.write_u16::<BigEndian>(class_file.major_version)?;
+
+    // 2. 常量池
+    write_constant_pool(&mut out, &class_file.constant_pool)?;
+
+    // 3. 访问标志、类索引
+    out.write_u16::<BigEndian>(class_file.access_flags)?;
+    out.write_u16::<BigEndian>(class_file.this_class)?;
+    out.write_u16::<BigEndian>(class_file.super_class)?;
+
+    // 4. 接口
+    write_count(&mut out, "interfaces", class_file.interfaces.len())?;
+    for &interface in &class_file.interfaces {
+        out.write_u16::<BigEndian>(interface)?;
+    }
+
+    // 5. 字段
+    write_count(&mut out, "fields", class_file.fields.len())?;
+    for field in &class_file.fields {
+        write_member(
+            &mut out,
+            field.access_flags,
+            field.name_index,
+            field.descriptor_index,
+            &field.attributes,
+        )?;
+    }
+
+    // 6. 方法
+    write_count(&mut out, "methods", class_file.methods.len())?;
+    for method in &class_file.methods {
+        write_member(
+            &mut out,
+            method.access_flags,
+            method.name_index,
+            method.descriptor_index,
+            &method.attributes,
+        )?;
+    }
+
+    // 7. 属性
+    write_attributes(&mut out, &class_file.attributes)?;
+
+    Ok(out)
+}
+
+/// 写入 u2 计数，超出范围时报错
+fn write_count(out: &mut Vec<u8>, what: &str, count: usize) -> Result<()> {
+    let count = u16::try_from(count).map_err(|_| anyhow!("Too many {}: {}", what, count))?;
+    out.write_u16::<BigEndian>(count)?;
+    Ok(())
+}
+
+/// 写入常量池
+fn write_constant_pool(out: &mut Vec<u8>, pool: &constant_pool::ConstantPool) -> Result<()> {
+    use constant_pool::tags::*;
+    use constant_pool::ConstantPoolEntry;
+
+    write_count(out, "constant pool entries", pool.entries.len())?;
+
+    let mut i = 1;
+    while i < pool.entries.len() {
+        let entry = pool.entries[i]
+            .as_ref()
+            .ok_or_else(|| anyhow!("Constant pool entry at {} is None", i))?;
+
+        match entry {
+            ConstantPoolEntry::Utf8(s) => {
+                let bytes = constant_pool::encode_mutf8(s);
+                out.write_u8(CONSTANT_UTF8)?;
+                let length = u16::try_from(bytes.len()).map_err(|_| {
+                    anyhow!("Utf8 constant at {} is too long: {} bytes", i, bytes.len())
+                })?;
+                out.write_u16::<BigEndian>(length)?;
+                out.extend_from_slice(&bytes);
+            }
+            ConstantPoolEntry::Integer(value) => {
+                out.write_u8(CONSTANT_INTEGER)?;
+                out.write_i32::<BigEndian>(*value)?;
+            }
+            ConstantPoolEntry::Float(value) => {
+                out.write_u8(CONSTANT_FLOAT)?;
+                out.write_f32::<BigEndian>(*value)?;
+            }
+            ConstantPoolEntry::Long(value) => {
+                out.write_u8(CONSTANT_LONG)?;
+                out.write_i64::<BigEndian>(*value)?;
+                i += 2; // Long占两个位置
+                continue;
+            }
+            ConstantPoolEntry::Double(value) => {
+                out.write_u8(CONSTANT_DOUBLE)?;
+                out.write_f64::<BigEndian>(*value)?;
+                i += 2; // Double占两个位置
+                continue;
+            }
+            ConstantPoolEntry::Class { name_index } => {
+                out.write_u8(CONSTANT_CLASS)?;
+                out.write_u16::<BigEndian>(*name_index)?;
+            }
+            ConstantPoolEntry::String { string_index } => {
+                out.write_u8(CONSTANT_STRING)?;
+                out.write_u16::<BigEndian>(*string_index)?;
+            }
+            ConstantPoolEntry::FieldRef {
+                class_index,
+                name_and_type_index,
+            } => {
+                out.write_u8(CONSTANT_FIELDREF)?;
+                out.write_u16::<BigEndian>(*class_index)?;
+                out.write_u16::<BigEndian>(*name_and_type_index)?;
+            }
+            ConstantPoolEntry::MethodRef {
+                class_index,
+                name_and_type_index,
+            } => {
+                out.write_u8(CONSTANT_METHODREF)?;
+                out.write_u16::<BigEndian>(*class_index)?;
+                out.write_u16::<BigEndian>(*name_and_type_index)?;
+            }
+            ConstantPoolEntry::InterfaceMethodRef {
+                class_index,
+                name_and_type_index,
+            } => {
+                out.write_u8(CONSTANT_INTERFACE_METHODREF)?;
+                out.write_u16::<BigEndian>(*class_index)?;
+                out.write_u16::<BigEndian>(*name_and_type_index)?;
+            }
+            ConstantPoolEntry::NameAndType {
+                name_index,
+                descriptor_index,
+            } => {
+                out.write_u8(CONSTANT_NAME_AND_TYPE)?;
+                out.write_u16::<BigEndian>(*name_index)?;
+                out.write_u16::<BigEndian>(*descriptor_index)?;
+            }
+            ConstantPoolEntry::MethodHandle {
+                reference_kind,
+                reference_index,
+            } => {
+                out.write_u8(CONSTANT_METHOD_HANDLE)?;
+                out.write_u8(*reference_kind)?;
+                out.write_u16::<BigEndian>(*reference_index)?;
+            }
+            ConstantPoolEntry::MethodType { descriptor_index } => {
+                out.write_u8(CONSTANT_METHOD_TYPE)?;
+                out.write_u16::<BigEndian>(*descriptor_index)?;
+            }
+            ConstantPoolEntry::InvokeDynamic {
+                bootstrap_method_attr_index,
+                name_and_type_index,
+            } => {
+                out.write_u8(CONSTANT_INVOKE_DYNAMIC)?;
+                out.write_u16::<BigEndian>(*bootstrap_method_attr_index)?;
+                out.write_u16::<BigEndian>(*name_and_type_index)?;
+            }
+            ConstantPoolEntry::Dynamic {
+                bootstrap_method_attr_index,
+                name_and_type_index,
+            } => {
+                out.write_u8(CONSTANT_DYNAMIC)?;
+                out.write_u16::<BigEndian>(*bootstrap_method_attr_index)?;
+                out.write_u16::<BigEndian>(*name_and_type_index)?;
+            }
+            ConstantPoolEntry::Module { name_index } => {
+                out.write_u8(CONSTANT_MODULE)?;
+                out.write_u16::<BigEndian>(*name_index)?;
+            }
+            ConstantPoolEntry::Package { name_index } => {
+                out.write_u8(CONSTANT_PACKAGE)?;
+                out.write_u16::<BigEndian>(*name_index)?;
+            }
+        }
+        i += 1;
+    }
+
+    Ok(())
+}
+
+/// 写入字段或方法（两者结构相同）
+fn write_member(
+    out: &mut Vec<u8>,
+    access_flags: u16,
+    name_index: u16,
+    descriptor_index: u16,
+    attributes: &[attribute::AttributeInfo],
+) -> Result<()> {
+    out.write_u16::<BigEndian>(access_flags)?;
+    out.write_u16::<BigEndian>(name_index)?;
+    out.write_u16::<BigEndian>(descriptor_index)?;
+    write_attributes(out, attributes)
+}
+
+/// 写入属性表
+fn write_attributes(out: &mut Vec<u8>, attributes: &[attribute::AttributeInfo]) -> Result<()> {
+    write_count(out, "attributes", attributes.len())?;
+    for attr in attributes {
+        out.write_u16::<BigEndian>(attr.name_index)?;
+        let length = u32::try_from(attr.info.len())
+            .map_err(|_| anyhow!("Attribute is too long: {} bytes", attr.info.len()))?;
+        out.write_u32::<BigEndian>(length)?;
+        out.extend_from_slice(&attr.info);
+    }
+    Ok(())
+}
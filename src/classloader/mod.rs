@@ -9,30 +9,151 @@
 //!
 //! ## 简化设计
 //! 这个实现简化了类加载过程，主要关注加载和基本验证
+//!
+//! ## 类路径
+//! 类路径的每一项可以是目录，也可以是 `.jar` 文件，按添加顺序查找。
+//! jar 就是 zip：`com/foo/Bar` 对应条目 `com/foo/Bar.class`。
+//! jar 在第一次查找时才打开并读取中央目录，损坏的 jar 在那时报错。
+//! 命令行上的类路径（`-cp a:b.jar`）用系统的路径分隔符隔开，见 [`split_class_path`]。
 
-use crate::classfile::ClassFile;
+use crate::classfile::{access_flags, ClassFile};
+use crate::runtime::JavaException;
 use crate::Result;
 use anyhow::{anyhow, Context};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::Read;
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use zip::result::ZipError;
+use zip::ZipArchive;
+
+/// 类路径中的一项
+enum ClassPathEntry {
+    /// 目录：包名对应子目录
+    Dir(PathBuf),
+    /// jar 文件
+    Jar(JarFile),
+}
+
+impl ClassPathEntry {
+    fn new(path: PathBuf) -> Self {
+        let is_jar = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("jar"));
+        if is_jar {
+            ClassPathEntry::Jar(JarFile {
+                path,
+                archive: Mutex::new(None),
+            })
+        } else {
+            ClassPathEntry::Dir(path)
+        }
+    }
+
+    fn path(&self) -> &Path {
+        match self {
+            ClassPathEntry::Dir(path) => path,
+            ClassPathEntry::Jar(jar) => &jar.path,
+        }
+    }
+}
+
+/// jar 文件，第一次使用时打开
+struct JarFile {
+    path: PathBuf,
+    archive: Mutex<Option<ZipArchive<File>>>,
+}
+
+impl JarFile {
+    /// 打开（或复用已打开的）zip 中央目录后执行 `f`
+    fn with_archive<T>(&self, f: impl FnOnce(&mut ZipArchive<File>) -> Result<T>) -> Result<T> {
+        let mut archive = self
+            .archive
+            .lock()
+            .map_err(|_| anyhow!("Jar file lock poisoned: {}", self.path.display()))?;
+        if archive.is_none() {
+            let file = File::open(&self.path)
+                .context(format!("Failed to open jar file {}", self.path.display()))?;
+            let opened = ZipArchive::new(file)
+                .context(format!("Corrupt jar file {}", self.path.display()))?;
+            *archive = Some(opened);
+        }
+        f(archive.as_mut().expect("archive opened above"))
+    }
+
+    /// 读取条目内容，条目不存在时返回 None
+    fn read_entry(&self, name: &str) -> Result<Option<Vec<u8>>> {
+        self.with_archive(|archive| {
+            let mut entry = match archive.by_name(name) {
+                Ok(entry) => entry,
+                Err(ZipError::FileNotFound) => return Ok(None),
+                Err(e) => {
+                    return Err(anyhow!(e).context(format!(
+                        "Failed to read {} from jar file {}",
+                        name,
+                        self.path.display()
+                    )))
+                }
+            };
+            let mut bytes = Vec::with_capacity(entry.size() as usize);
+            entry.read_to_end(&mut bytes).context(format!(
+                "Failed to decompress {} from jar file {}",
+                name,
+                self.path.display()
+            ))?;
+            Ok(Some(bytes))
+        })
+    }
+
+    /// jar 中所有 .class 条目的名字（按名字排序）
+    fn class_entries(&self) -> Result<Vec<String>> {
+        self.with_archive(|archive| {
+            let mut names: Vec<String> = archive
+                .file_names()
+                .filter(|name| name.ends_with(".class"))
+                .map(str::to_string)
+                .collect();
+            names.sort();
+            Ok(names)
+        })
+    }
+}
+
+/// 拆分类路径字符串：各项用系统的路径分隔符隔开（Unix 上是 `:`，Windows 上是 `;`），空项被忽略
+pub fn split_class_path(class_path: &str) -> Vec<PathBuf> {
+    std::env::split_paths(class_path)
+        .filter(|path| !path.as_os_str().is_empty())
+        .collect()
+}
+
+/// 类名的内部形式：`com.example.Main` → `com/example/Main`
+pub fn internal_class_name(name: &str) -> String {
+    name.replace('.', "/")
+}
 
 /// 类加载器
 pub struct ClassLoader {
-    /// 类路径
-    class_paths: Vec<PathBuf>,
+    /// 类路径（目录或 jar）
+    class_paths: Vec<ClassPathEntry>,
     /// 已加载的类
     loaded_classes: HashMap<String, ClassFile>,
 }
 
 impl ClassLoader {
-    /// 创建新的类加载器
+    /// 创建新的类加载器，以 `.jar` 结尾的路径按 jar 文件处理
     pub fn new(class_paths: Vec<PathBuf>) -> Self {
         ClassLoader {
-            class_paths,
+            class_paths: class_paths.into_iter().map(ClassPathEntry::new).collect(),
             loaded_classes: HashMap::new(),
         }
     }
 
+    /// 类路径（按查找顺序）
+    pub fn class_paths(&self) -> Vec<&Path> {
+        self.class_paths.iter().map(ClassPathEntry::path).collect()
+    }
+
     /// 加载类
     pub fn load_class(&mut self, class_name: &str) -> Result<&ClassFile> {
         // 检查是否已加载
@@ -40,33 +161,153 @@ impl ClassLoader {
             return Ok(&self.loaded_classes[class_name]);
         }
 
+        let class_file = self.read_class(class_name)?;
+        self.loaded_classes
+            .insert(class_name.to_string(), class_file);
+        Ok(&self.loaded_classes[class_name])
+    }
+
+    /// 在类路径中查找并解析class文件（不缓存，返回新的 ClassFile）
+    ///
+    /// 解释器用它把类交给 Metaspace（Metaspace 需要拥有 ClassFile）
+    pub fn read_class(&self, class_name: &str) -> Result<ClassFile> {
         // 将类名转换为文件路径（例如：java/lang/Object -> java/lang/Object.class）
         let class_file_name = format!("{}.class", class_name);
 
         // 在类路径中搜索
         for class_path in &self.class_paths {
-            let class_file_path = class_path.join(&class_file_name);
-            if class_file_path.exists() {
-                let class_file = ClassFile::from_file(&class_file_path)
-                    .context(format!("Failed to load class: {}", class_name))?;
-
-                // 验证类名是否匹配
-                let loaded_name = class_file.get_class_name()?;
-                if loaded_name != class_name {
-                    return Err(anyhow!(
-                        "Class name mismatch: expected {}, got {}",
-                        class_name,
-                        loaded_name
-                    ));
+            let class_file = match class_path {
+                ClassPathEntry::Dir(dir) => {
+                    let class_file_path = dir.join(&class_file_name);
+                    if !class_file_path.exists() {
+                        continue;
+                    }
+                    ClassFile::from_file(&class_file_path)
                 }
+                ClassPathEntry::Jar(jar) => match jar.read_entry(&class_file_name)? {
+                    Some(bytes) => ClassFile::from_bytes(&bytes)
+                        .context(format!("in jar file {}", jar.path.display())),
+                    None => continue,
+                },
+            }
+            .context(format!("Failed to load class: {}", class_name))?;
+
+            // 验证类名是否匹配
+            let loaded_name = class_file.get_class_name()?;
+            if loaded_name != class_name {
+                return Err(anyhow!(
+                    "Class name mismatch: expected {}, got {}",
+                    class_name,
+                    loaded_name
+                ));
+            }
+
+            return Ok(class_file);
+        }
+
+        Err(JavaException::new(
+            "java/lang/ClassNotFoundException",
+            format!("{} (searched {:?})", class_name, self.class_paths()),
+        )
+        .into())
+    }
+
+    /// 扫描类路径下的所有 class 文件（递归子目录）
+    ///
+    /// 无法解析的文件会被跳过；同一目录内按文件名顺序扫描，结果顺序固定
+    pub fn scan_class_path(&self) -> Vec<ClassFile> {
+        let mut classes = Vec::new();
+        for class_path in &self.class_paths {
+            match class_path {
+                ClassPathEntry::Dir(dir) => Self::scan_dir(dir, &mut classes),
+                ClassPathEntry::Jar(jar) => Self::scan_jar(jar, &mut classes),
+            }
+        }
+        classes
+    }
+
+    fn scan_jar(jar: &JarFile, classes: &mut Vec<ClassFile>) {
+        let names = match jar.class_entries() {
+            Ok(names) => names,
+            Err(e) => {
+                log::debug!("跳过无法打开的jar {:?}: {:#}", jar.path, e);
+                return;
+            }
+        };
+        for name in names {
+            let parsed = jar
+                .read_entry(&name)
+                .and_then(|bytes| ClassFile::from_bytes(&bytes.unwrap_or_default()));
+            match parsed {
+                Ok(class_file) => classes.push(class_file),
+                Err(e) => log::debug!("跳过jar中无法解析的class文件 {}: {}", name, e),
+            }
+        }
+    }
+
+    fn scan_dir(dir: &Path, classes: &mut Vec<ClassFile>) {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return;
+        };
+        // read_dir 的顺序由文件系统决定，排序后输出才稳定
+        let mut paths: Vec<_> = entries.flatten().map(|entry| entry.path()).collect();
+        paths.sort();
+        for path in paths {
+            if path.is_dir() {
+                Self::scan_dir(&path, classes);
+            } else if path.extension().is_some_and(|ext| ext == "class") {
+                match ClassFile::from_file(&path) {
+                    Ok(class_file) => classes.push(class_file),
+                    Err(e) => log::debug!("跳过无法解析的class文件 {:?}: {}", path, e),
+                }
+            }
+        }
+    }
 
-                self.loaded_classes
-                    .insert(class_name.to_string(), class_file);
-                return Ok(&self.loaded_classes[class_name]);
+    /// 在类路径中查找实现/继承了 `class_name` 的具体类（非抽象、非接口）
+    ///
+    /// 沿父类和接口向上追溯，间接实现也算；结果按类名排序
+    pub fn find_implementations(&self, class_name: &str) -> Result<Vec<String>> {
+        // 类名 → (父类 + 接口, 是否具体类)
+        let mut supertypes = HashMap::new();
+        for class_file in self.scan_class_path() {
+            let name = class_file.get_class_name()?;
+            let mut parents = Vec::new();
+            if class_file.super_class != 0 {
+                parents.push(class_file.get_super_class_name()?);
+            }
+            for &index in &class_file.interfaces {
+                parents.push(class_file.constant_pool.get_class_name(index)?);
             }
+            let concrete = class_file.access_flags
+                & (access_flags::ACC_ABSTRACT | access_flags::ACC_INTERFACE)
+                == 0;
+            supertypes.insert(name, (parents, concrete));
         }
 
-        Err(anyhow!("Class not found: {}", class_name))
+        let mut implementations = Vec::new();
+        for (name, (_, concrete)) in &supertypes {
+            if !concrete || name == class_name {
+                continue;
+            }
+            let mut visited = HashSet::new();
+            let mut pending = vec![name.clone()];
+            while let Some(current) = pending.pop() {
+                if current == class_name {
+                    implementations.push(name.clone());
+                    break;
+                }
+                if let Some((parents, _)) = supertypes.get(&current) {
+                    for parent in parents {
+                        if visited.insert(parent.clone()) {
+                            pending.push(parent.clone());
+                        }
+                    }
+                }
+            }
+        }
+        implementations.sort();
+        Ok(implementations)
     }
 
     /// 获取已加载的类
@@ -74,8 +315,9 @@ impl ClassLoader {
         self.loaded_classes.get(class_name)
     }
 
-    /// 添加类路径
+    /// 添加类路径（目录或 `.jar` 文件）
     pub fn add_class_path<P: AsRef<Path>>(&mut self, path: P) {
-        self.class_paths.push(path.as_ref().to_path_buf());
+        self.class_paths
+            .push(ClassPathEntry::new(path.as_ref().to_path_buf()));
     }
 }
@@ -0,0 +1,189 @@
+//! # 调试器
+//!
+//! 把栈帧的内部状态整理成和 javap 一致的视图，方便对照字节码学习。
+//!
+//! ## 槽位编号
+//! JVM 规范中 long/double 占两个局部变量槽位和两个操作数栈单位：
+//! `lstore_1` 之后，下一个变量从槽位 3 开始。快照按规范编号，
+//! 第二个槽位显示为 "(2nd half)"，而不是内部 Vec 中的占位值。
+//!
+//! ## 学习要点
+//! - javap -l 的 LocalVariableTable 中 Slot 列就是这里的槽位号
+//! - 同一个槽位在不同 pc 处可能属于不同变量（作用域不同）
+//! - max_stack 按槽位计数，一个 long 占 2
+//!
+//! 交互式调试命令（`rsjvm debug`）见 [`repl`]。
+
+pub mod repl;
+
+use crate::runtime::frame::{Frame, JvmValue};
+use crate::runtime::LocalVariable;
+use std::fmt;
+
+/// 栈帧快照
+#[derive(Debug, Clone)]
+pub struct FrameSnapshot {
+    /// 所属类
+    pub class_name: String,
+    /// 快照时的 pc
+    pub pc: usize,
+    /// 局部变量表大小（槽位数）
+    pub max_locals: usize,
+    /// 操作数栈最大深度（槽位数）
+    pub max_stack: usize,
+    /// 按槽位编号的局部变量
+    pub locals: Vec<LocalSlot>,
+    /// 操作数栈，栈底在前
+    pub stack: Vec<StackEntry>,
+}
+
+/// 局部变量表中的一个槽位
+#[derive(Debug, Clone)]
+pub struct LocalSlot {
+    /// 槽位号（与 javap 一致）
+    pub slot: usize,
+    /// LocalVariableTable 中在当前 pc 有效的变量名
+    pub name: Option<String>,
+    /// 变量的类型描述符
+    pub descriptor: Option<String>,
+    /// 槽位内容
+    pub value: SlotValue,
+}
+
+/// 槽位内容
+#[derive(Debug, Clone)]
+pub enum SlotValue {
+    /// 普通值，或 long/double 的第一个槽位
+    Value(JvmValue),
+    /// long/double 的第二个槽位
+    SecondHalf,
+}
+
+/// 操作数栈中的一项
+#[derive(Debug, Clone)]
+pub struct StackEntry {
+    /// 占用的第一个槽位深度（栈底为 0）
+    pub depth: usize,
+    /// 占用的槽位数（1 或 2）
+    pub width: usize,
+    /// 值
+    pub value: JvmValue,
+}
+
+impl FrameSnapshot {
+    /// 为栈帧拍快照
+    ///
+    /// `local_variables` 是方法的 LocalVariableTable（可为空），
+    /// 用来给槽位标注变量名；当前 pc 处声明为 long/double 的变量
+    /// 即使还没赋值也按两个槽位显示
+    pub fn capture(frame: &Frame, pc: usize, local_variables: &[LocalVariable]) -> Self {
+        let values = frame.locals();
+        let mut locals = Vec::with_capacity(values.len());
+        let mut slot = 0;
+        while slot < values.len() {
+            let var = local_variables
+                .iter()
+                .find(|v| v.slot == slot && v.is_live_at(pc));
+            let wide = var.map_or(values[slot].is_wide(), |v| v.is_wide());
+            locals.push(LocalSlot {
+                slot,
+                name: var.map(|v| v.name.clone()),
+                descriptor: var.map(|v| v.descriptor.clone()),
+                value: SlotValue::Value(values[slot].clone()),
+            });
+            if wide && slot + 1 < values.len() {
+                locals.push(LocalSlot {
+                    slot: slot + 1,
+                    name: var.map(|v| v.name.clone()),
+                    descriptor: var.map(|v| v.descriptor.clone()),
+                    value: SlotValue::SecondHalf,
+                });
+                slot += 2;
+            } else {
+                slot += 1;
+            }
+        }
+
+        let mut stack = Vec::with_capacity(frame.stack_size());
+        let mut depth = 0;
+        for value in frame.operands() {
+            let width = if value.is_wide() { 2 } else { 1 };
+            stack.push(StackEntry {
+                depth,
+                width,
+                value: value.clone(),
+            });
+            depth += width;
+        }
+
+        FrameSnapshot {
+            class_name: frame.class_name.to_string(),
+            pc,
+            max_locals: frame.max_locals,
+            max_stack: frame.max_stack,
+            locals,
+            stack,
+        }
+    }
+
+    /// 操作数栈当前深度（槽位数）
+    pub fn stack_depth(&self) -> usize {
+        self.stack.iter().map(|e| e.width).sum()
+    }
+}
+
+/// 按 Java 字面量的习惯显示值
+pub(crate) fn format_value(value: &JvmValue) -> String {
+    match value {
+        JvmValue::Int(v) => v.to_string(),
+        JvmValue::Long(v) => format!("{}L", v),
+        JvmValue::Float(v) => format!("{:?}f", v),
+        JvmValue::Double(v) => format!("{:?}", v),
+        JvmValue::Reference(Some(ptr)) => format!("ref@{}", ptr),
+        JvmValue::Reference(None) => "null".to_string(),
+    }
+}
+
+impl fmt::Display for LocalSlot {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[{}]", self.slot)?;
+        match (&self.value, &self.name, &self.descriptor) {
+            (SlotValue::SecondHalf, Some(name), _) => write!(f, " {} (2nd half)", name),
+            (SlotValue::SecondHalf, None, _) => write!(f, " (2nd half)"),
+            (SlotValue::Value(v), Some(name), Some(desc)) => {
+                write!(f, " {}: {} = {}", name, desc, format_value(v))
+            }
+            (SlotValue::Value(v), _, _) => write!(f, " {}", format_value(v)),
+        }
+    }
+}
+
+impl fmt::Display for StackEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.width == 2 {
+            write!(f, "[{}-{}] {}", self.depth, self.depth + 1, format_value(&self.value))
+        } else {
+            write!(f, "[{}] {}", self.depth, format_value(&self.value))
+        }
+    }
+}
+
+impl fmt::Display for FrameSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Frame {} @ pc={}", self.class_name, self.pc)?;
+        writeln!(f, "  locals (max_locals={}):", self.max_locals)?;
+        for local in &self.locals {
+            writeln!(f, "    {}", local)?;
+        }
+        writeln!(
+            f,
+            "  operand stack (depth {}/{}):",
+            self.stack_depth(),
+            self.max_stack
+        )?;
+        for entry in &self.stack {
+            writeln!(f, "    {}", entry)?;
+        }
+        Ok(())
+    }
+}
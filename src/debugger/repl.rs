@@ -0,0 +1,262 @@
+//! # 交互式调试器
+//!
+//! `rsjvm debug` 的命令解释：每行一条命令，输出写到给定的 writer。
+//! 命令行交互和 `--script` 脚本走同一条路径，脚本模式会回显每条命令。
+//!
+//! | 命令 | 作用 |
+//! |------|------|
+//! | `break <pc>` / `break <name:descriptor> <pc>` | 在被调试方法（或指定方法）的 pc 处设置断点 |
+//! | `run` | 运行到下一个断点或方法返回（尚未开始时先开始执行） |
+//! | `step` | 执行一条指令 |
+//! | `locals` / `stack` | 栈顶栈帧的局部变量表 / 操作数栈 |
+//! | `frames` | 调用栈，栈顶在前 |
+//! | `disasm` | 反汇编栈顶方法，`=>` 标出下一条要执行的指令 |
+//! | `heap` | 堆转储 |
+//! | `quit` | 退出 |
+//!
+//! ## 学习要点
+//! - 调试器本身不执行字节码，只是在 `step()`/`resume()` 之间查看解释器的状态
+//! - 暂停时 pc 指向的是下一条要执行的指令，它还没有执行
+
+use super::{format_value, FrameSnapshot};
+use crate::interpreter::disasm;
+use crate::interpreter::stepping::RunOutcome;
+use crate::interpreter::Interpreter;
+use crate::runtime::frame::JvmValue;
+use crate::runtime::metaspace::method_key;
+use crate::Result;
+use anyhow::anyhow;
+use std::io::{BufRead, Write};
+
+/// 提示符
+pub const PROMPT: &str = "(rsjvm) ";
+
+/// 一条命令执行后是否继续读取命令
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Control {
+    Continue,
+    Quit,
+}
+
+/// 调试一个方法的会话
+pub struct DebugSession {
+    interpreter: Interpreter,
+    class_name: String,
+    /// 被调试的方法（"name:descriptor"）
+    method_key: String,
+    /// 方法参数（`run`/`step` 开始执行时传入）
+    args: Vec<JvmValue>,
+}
+
+impl DebugSession {
+    /// 调试 `class_name` 的 `method_key` 方法，类需要已经加载到解释器
+    pub fn new(
+        interpreter: Interpreter,
+        class_name: &str,
+        method_key: &str,
+        args: Vec<JvmValue>,
+    ) -> Self {
+        DebugSession {
+            interpreter,
+            class_name: class_name.to_string(),
+            method_key: method_key.to_string(),
+            args,
+        }
+    }
+
+    pub fn interpreter(&self) -> &Interpreter {
+        &self.interpreter
+    }
+
+    /// 逐行读取命令直到 `quit` 或输入结束
+    ///
+    /// `echo` 为 true 时在提示符后回显命令（脚本模式，输入不来自终端）
+    pub fn run<R: BufRead>(&mut self, input: R, out: &mut dyn Write, echo: bool) -> Result<()> {
+        write!(out, "{}", PROMPT)?;
+        out.flush()?;
+        for line in input.lines() {
+            let line = line?;
+            let command = line.trim();
+            if command.is_empty() || command.starts_with('#') {
+                continue;
+            }
+            if echo {
+                writeln!(out, "{}", command)?;
+            }
+            if self.execute(command, out)? == Control::Quit {
+                return Ok(());
+            }
+            write!(out, "{}", PROMPT)?;
+            out.flush()?;
+        }
+        writeln!(out)?;
+        Ok(())
+    }
+
+    /// 执行一条命令；命令本身的错误（参数不对、程序出错）写到输出，不中断会话
+    pub fn execute(&mut self, line: &str, out: &mut dyn Write) -> Result<Control> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let result = match words.as_slice() {
+            [] => Ok(()),
+            ["quit" | "q" | "exit"] => return Ok(Control::Quit),
+            ["help" | "h"] => Self::help(out),
+            ["break" | "b", pc] => self.set_breakpoint(None, pc, out),
+            ["break" | "b", method, pc] => self.set_breakpoint(Some(method), pc, out),
+            ["run" | "r" | "continue" | "c"] => self.run_to_breakpoint(out),
+            ["step" | "s"] => self.step(out),
+            ["locals"] => self.snapshot().and_then(|snapshot| {
+                for local in &snapshot.locals {
+                    writeln!(out, "  {}", local)?;
+                }
+                Ok(())
+            }),
+            ["stack"] => self.snapshot().and_then(|snapshot| {
+                if snapshot.stack.is_empty() {
+                    writeln!(out, "  (empty)")?;
+                }
+                for entry in &snapshot.stack {
+                    writeln!(out, "  {}", entry)?;
+                }
+                Ok(())
+            }),
+            ["frames" | "bt"] => self.frames(out),
+            ["disasm"] => self.disasm(out),
+            ["heap"] => writeln!(out, "{}", self.interpreter.heap_dump()).map_err(Into::into),
+            [command, ..] => Err(anyhow!("unknown command: {} (type 'help' for a list)", command)),
+        };
+        if let Err(e) = result {
+            writeln!(out, "error: {:#}", e)?;
+        }
+        Ok(Control::Continue)
+    }
+
+    fn help(out: &mut dyn Write) -> Result<()> {
+        writeln!(out, "  break <pc> | break <name:descriptor> <pc>   set a breakpoint")?;
+        writeln!(out, "  run                                         run to the next breakpoint")?;
+        writeln!(out, "  step                                        execute one instruction")?;
+        writeln!(out, "  locals | stack | frames                     inspect the current frame")?;
+        writeln!(out, "  disasm                                      disassemble the current method")?;
+        writeln!(out, "  heap                                        dump the heap")?;
+        writeln!(out, "  quit                                        exit")?;
+        Ok(())
+    }
+
+    fn set_breakpoint(&mut self, method: Option<&str>, pc: &str, out: &mut dyn Write) -> Result<()> {
+        let pc: usize = pc.parse().map_err(|_| anyhow!("invalid pc: {}", pc))?;
+        let method_key = method.unwrap_or(&self.method_key).to_string();
+        let method = self
+            .interpreter
+            .metaspace
+            .get_class(&self.class_name)?
+            .methods
+            .get(method_key.as_str())
+            .ok_or_else(|| anyhow!("no method {} in {}", method_key, self.class_name))?;
+        if pc >= method.code.len() {
+            return Err(anyhow!(
+                "pc {} is past the end of {} (code length {})",
+                pc,
+                method_key,
+                method.code.len()
+            ));
+        }
+        self.interpreter.set_breakpoint(&self.class_name, &method_key, pc);
+        writeln!(out, "Breakpoint at {}.{} pc={}", self.class_name, method_key, pc)?;
+        Ok(())
+    }
+
+    /// 尚未开始执行时压入被调试方法的栈帧
+    fn ensure_started(&mut self, out: &mut dyn Write) -> Result<()> {
+        if !self.interpreter.is_paused() {
+            self.interpreter
+                .start_method(&self.class_name, &self.method_key, self.args.clone())?;
+            writeln!(out, "Starting {}.{}", self.class_name, self.method_key)?;
+        }
+        Ok(())
+    }
+
+    fn run_to_breakpoint(&mut self, out: &mut dyn Write) -> Result<()> {
+        self.ensure_started(out)?;
+        match self.interpreter.resume()? {
+            RunOutcome::Breakpoint(hit) => {
+                writeln!(out, "Breakpoint hit: {}.{} pc={}", hit.class_name, hit.method_key, hit.pc)?;
+                self.show_current(out)
+            }
+            RunOutcome::Finished(value) => Self::finished(value, out),
+        }
+    }
+
+    fn step(&mut self, out: &mut dyn Write) -> Result<()> {
+        self.ensure_started(out)?;
+        let step = self.interpreter.step()?;
+        writeln!(
+            out,
+            "Executed {}.{} pc={}: {}",
+            step.class_name, step.method_name, step.pc, step.mnemonic
+        )?;
+        if step.is_finished() {
+            return Self::finished(step.return_value, out);
+        }
+        self.show_current(out)
+    }
+
+    fn finished(value: Option<JvmValue>, out: &mut dyn Write) -> Result<()> {
+        match value {
+            Some(value) => writeln!(out, "Returned {}", format_value(&value))?,
+            None => writeln!(out, "Returned (void)")?,
+        }
+        Ok(())
+    }
+
+    /// 栈顶栈帧的快照（变量名取自方法的 LocalVariableTable）
+    fn snapshot(&self) -> Result<FrameSnapshot> {
+        let frame = self.current_frame()?;
+        let key = method_key(&frame.method_name, &frame.descriptor);
+        let class = self.interpreter.metaspace.get_class(&frame.class_name).ok();
+        let local_variables = class
+            .and_then(|class| class.methods.get(key.as_str()))
+            .map(|method| method.local_variables.as_slice())
+            .unwrap_or_default();
+        Ok(FrameSnapshot::capture(frame, frame.pc, local_variables))
+    }
+
+    fn frames(&self, out: &mut dyn Write) -> Result<()> {
+        self.current_frame()?;
+        for (i, frame) in self.interpreter.stack_trace().iter().enumerate() {
+            writeln!(out, "  #{} {}", i, frame)?;
+        }
+        Ok(())
+    }
+
+    /// 打印下一条要执行的指令
+    fn show_current(&self, out: &mut dyn Write) -> Result<()> {
+        let pc = self.current_frame()?.pc;
+        let instructions = self.disassemble_current()?;
+        if let Some(instruction) = instructions.iter().find(|i| i.pc == pc) {
+            writeln!(out, "=> {}", instruction)?;
+        }
+        Ok(())
+    }
+
+    fn disasm(&self, out: &mut dyn Write) -> Result<()> {
+        let frame = self.current_frame()?;
+        writeln!(out, "{}.{}{}", frame.class_name, frame.method_name, frame.descriptor)?;
+        for instruction in self.disassemble_current()? {
+            let marker = if instruction.pc == frame.pc { "=>" } else { "  " };
+            writeln!(out, "{} {}", marker, instruction)?;
+        }
+        Ok(())
+    }
+
+    fn disassemble_current(&self) -> Result<Vec<disasm::DisassembledInstruction>> {
+        let frame = self.current_frame()?;
+        let class = self.interpreter.metaspace.get_class(&frame.class_name)?;
+        disasm::disassemble(&frame.code, &class.constant_pool)
+    }
+
+    fn current_frame(&self) -> Result<&crate::runtime::Frame> {
+        if !self.interpreter.is_paused() {
+            return Err(anyhow!("the program is not running (use 'run' or 'step')"));
+        }
+        self.interpreter.thread.current_frame()
+    }
+}
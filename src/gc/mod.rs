@@ -8,84 +8,323 @@
 //! - GC Roots的概念
 //! - 可达性分析
 //!
+//! - GC 停顿的阶段划分：根扫描、标记、清除
+//! - 解释器的根来自线程栈（局部变量、操作数栈）和类的静态字段
+//! - 每次回收在 info 级别输出一行 `[gc] ...` 日志（`run --gc-log` 打开），Java 代码可以用 `System.gc()` 请求回收
+//!
 //! ## 简化设计
-//! 这个实现使用最简单的标记-清除算法
+//! 默认使用最简单的标记-清除算法，标记时沿实例字段和数组元素遍历引用；
+//! 也可以选择标记-整理（`GcStrategy::MarkCompact`），清除后消除堆中的空洞；
+//! 或者分代（`GcStrategy::Generational`）：新生代满时只回收新生代（minor GC，见 `runtime::nursery`），
+//! 晋升到老年代的对象累计到阈值时再做完整的标记-清除
+
+pub mod roots;
+pub mod timeline;
+
+pub use roots::GcRootSet;
+pub use timeline::{GcEvent, GcKind, GcStats};
 
-use crate::runtime::Heap;
+use crate::runtime::nursery::{DEFAULT_NURSERY_SIZE, DEFAULT_PROMOTION_AGE};
+use crate::runtime::{Heap, JvmThread, Metaspace, ObjRef};
 use std::collections::HashSet;
+use std::time::Instant;
+
+/// 回收算法
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GcStrategy {
+    /// 标记-清除：对象不移动，回收的槽位进入空闲列表
+    #[default]
+    MarkSweep,
+    /// 标记-整理：清除后把存活对象滑到堆的前部，并改写所有引用
+    MarkCompact,
+    /// 分代：新对象进入新生代，新生代满时 minor GC，完整回收使用标记-清除
+    Generational {
+        /// 新生代对象数达到这个值时做 minor GC
+        nursery_size: usize,
+        /// 熬过多少次 minor GC 后晋升到老年代
+        promotion_age: u8,
+    },
+}
+
+impl GcStrategy {
+    /// 使用默认新生代大小和晋升年龄的分代回收
+    pub fn generational() -> Self {
+        GcStrategy::Generational {
+            nursery_size: DEFAULT_NURSERY_SIZE,
+            promotion_age: DEFAULT_PROMOTION_AGE,
+        }
+    }
+
+    /// 按算法配置新建的堆（分代时打开新生代）
+    pub fn configure_heap(&self, heap: &mut Heap) {
+        if let GcStrategy::Generational {
+            nursery_size,
+            promotion_age,
+        } = *self
+        {
+            heap.enable_nursery(nursery_size, promotion_age);
+        }
+    }
+}
 
 /// 垃圾回收器
 pub struct GarbageCollector {
+    /// 回收算法
+    strategy: GcStrategy,
     /// 根对象集合（GC Roots）
-    roots: HashSet<usize>,
+    roots: HashSet<ObjRef>,
+    /// 事件时间的起点
+    epoch: Instant,
+    /// 每次回收的记录
+    events: Vec<GcEvent>,
+    /// 累计统计
+    stats: GcStats,
 }
 
 impl GarbageCollector {
-    /// 创建新的垃圾回收器
+    /// 创建新的垃圾回收器（标记-清除）
     pub fn new() -> Self {
+        Self::with_strategy(GcStrategy::default())
+    }
+
+    /// 创建使用指定算法的垃圾回收器
+    pub fn with_strategy(strategy: GcStrategy) -> Self {
         GarbageCollector {
+            strategy,
             roots: HashSet::new(),
+            epoch: Instant::now(),
+            events: Vec::new(),
+            stats: GcStats::default(),
         }
     }
 
+    /// 回收算法
+    pub fn strategy(&self) -> GcStrategy {
+        self.strategy
+    }
+
     /// 添加GC Root
-    pub fn add_root(&mut self, object_ref: usize) {
+    pub fn add_root(&mut self, object_ref: ObjRef) {
         self.roots.insert(object_ref);
     }
 
     /// 移除GC Root
-    pub fn remove_root(&mut self, object_ref: usize) {
+    pub fn remove_root(&mut self, object_ref: ObjRef) {
         self.roots.remove(&object_ref);
     }
 
-    /// 执行垃圾回收
+    /// 执行垃圾回收（只使用手动注册的根）
     ///
     /// ## 标记-清除算法步骤
-    /// 1. 标记阶段：从GC Roots开始，标记所有可达对象
-    /// 2. 清除阶段：回收所有未被标记的对象
+    /// 1. 根扫描：收集仍然存活的GC Roots
+    /// 2. 标记阶段：从GC Roots开始，标记所有可达对象
+    /// 3. 清除阶段：回收所有未被标记的对象
+    /// 4. 整理阶段（仅 `MarkCompact`）：存活对象滑到堆的前部，改写所有引用
+    ///
+    /// 每个阶段单独计时，结果记入 `events()` 和 `stats()`；分代时存活的新生代对象全部晋升
     pub fn collect(&mut self, heap: &mut Heap) -> usize {
-        // 第一步：标记所有可达对象
-        let reachable = self.mark(heap);
+        self.collect_roots(heap, GcRootSet::new())
+    }
 
-        // 第二步：清除不可达对象
-        self.sweep(heap, &reachable)
+    /// 执行垃圾回收，除了手动注册的根，线程栈和静态字段中的引用也是根
+    ///
+    /// 根包括每个栈帧的局部变量表和操作数栈，以及方法区中所有类的静态字段
+    pub fn collect_with_thread(
+        &mut self,
+        heap: &mut Heap,
+        thread: &mut JvmThread,
+        metaspace: &mut Metaspace,
+    ) -> usize {
+        let mut roots = GcRootSet::new();
+        roots.add_thread(thread);
+        roots.add_statics(metaspace);
+        self.collect_roots(heap, roots)
+    }
+
+    /// 以 `roots` 加上手动注册的根执行垃圾回收
+    ///
+    /// 整理移动对象后，`roots` 和手动注册的根都会被改写为新引用
+    pub fn collect_roots(&mut self, heap: &mut Heap, mut roots: GcRootSet) -> usize {
+        let started = Instant::now();
+        let before = heap.fragmentation();
+
+        // 第一步：扫描根
+        let candidates: HashSet<ObjRef> = self.roots.iter().copied().chain(roots.refs()).collect();
+        let live_roots = Self::scan_roots(&candidates, heap);
+        let root_scanned = Instant::now();
+
+        // 第二步：标记所有可达对象
+        let reachable = self.mark(&live_roots, heap, false);
+        let marked = Instant::now();
+
+        // 第三步：清除不可达对象
+        let collected = self.sweep(heap, &reachable);
+        let promoted = heap.promote_nursery();
+        heap.reset_allocation_count();
+        let swept = Instant::now();
+
+        // 第四步：整理
+        let compact = match self.strategy {
+            GcStrategy::MarkSweep | GcStrategy::Generational { .. } => None,
+            GcStrategy::MarkCompact => {
+                let moved = heap.compact();
+                heap.relocate_references(&moved);
+                roots.relocate(&moved);
+                self.roots = self
+                    .roots
+                    .iter()
+                    .map(|root| moved.get(root).copied().unwrap_or(*root))
+                    .collect();
+                Some(swept.elapsed())
+            }
+        };
+
+        let event = GcEvent {
+            id: self.events.len() + 1,
+            kind: GcKind::Full,
+            start: started - self.epoch,
+            root_scan: root_scanned - started,
+            mark: marked - root_scanned,
+            sweep: swept - marked,
+            compact,
+            roots: live_roots.len(),
+            marked: reachable.len(),
+            collected,
+            promoted,
+            before,
+            after: heap.fragmentation(),
+        };
+        self.record(event);
+        collected
+    }
+
+    /// minor GC：只回收新生代（堆没有打开分代时什么也不做）
+    ///
+    /// 根是 `roots`、手动注册的根和记忆集中老年代对象的引用，其中只有新生代对象参与标记；
+    /// 标记时也不进入老年代对象。存活的对象年龄加一，达到晋升年龄的晋升到老年代
+    pub fn collect_minor(&mut self, heap: &mut Heap, roots: GcRootSet) -> usize {
+        let Some(nursery) = heap.nursery() else {
+            return 0;
+        };
+        let started = Instant::now();
+        let before = heap.fragmentation();
+
+        // 第一步：扫描根，记忆集中的老年代对象引用的对象也是根
+        let remembered: Vec<ObjRef> = nursery
+            .remembered()
+            .filter_map(|holder| heap.get(holder).ok())
+            .flat_map(|object| object.references())
+            .collect();
+        let candidates: HashSet<ObjRef> = self
+            .roots
+            .iter()
+            .copied()
+            .chain(roots.refs())
+            .chain(remembered)
+            .filter(|&root| heap.is_young(root))
+            .collect();
+        let live_roots = Self::scan_roots(&candidates, heap);
+        let root_scanned = Instant::now();
+
+        // 第二步：只标记新生代对象
+        let reachable = self.mark(&live_roots, heap, true);
+        let marked = Instant::now();
+
+        // 第三步：清除新生代中不可达的对象，晋升年龄足够的对象
+        let (collected, promoted) = heap.sweep_nursery(&reachable);
+        let swept = Instant::now();
+
+        let event = GcEvent {
+            id: self.events.len() + 1,
+            kind: GcKind::Minor,
+            start: started - self.epoch,
+            root_scan: root_scanned - started,
+            mark: marked - root_scanned,
+            sweep: swept - marked,
+            compact: None,
+            roots: live_roots.len(),
+            marked: reachable.len(),
+            collected,
+            promoted,
+            before,
+            after: heap.fragmentation(),
+        };
+        self.record(event);
+        collected
+    }
+
+    /// 记录一次回收：输出日志，计入统计
+    fn record(&mut self, event: GcEvent) {
+        jvm_debug!("{}", event);
+        // GC 日志总是走 log crate（`run --gc-log`），启用 tracing 时也一样
+        log::info!("{}", timeline::log_line(&event));
+        self.stats.record(&event);
+        self.events.push(event);
+    }
+
+    /// 所有回收事件，按发生顺序
+    pub fn events(&self) -> &[GcEvent] {
+        &self.events
+    }
+
+    /// 累计统计
+    pub fn stats(&self) -> &GcStats {
+        &self.stats
+    }
+
+    /// 把 GC 事件导出为 Chrome trace JSON（GC 单独一条轨道）
+    pub fn chrome_trace(&self) -> String {
+        timeline::chrome_trace(&self.events)
+    }
+
+    /// 根扫描阶段：过滤掉已经失效的根
+    fn scan_roots(candidates: &HashSet<ObjRef>, heap: &Heap) -> Vec<ObjRef> {
+        candidates
+            .iter()
+            .copied()
+            .filter(|&root| heap.is_live(root))
+            .collect()
     }
 
     /// 标记阶段：标记所有可达对象
-    fn mark(&self, _heap: &Heap) -> HashSet<usize> {
+    ///
+    /// 用显式的工作列表代替递归，很长的引用链（如链表）也不会耗尽 Rust 栈。
+    /// `young_only` 时（minor GC）不标记、也不遍历老年代对象
+    fn mark(&self, roots: &[ObjRef], heap: &Heap, young_only: bool) -> HashSet<ObjRef> {
         let mut reachable = HashSet::new();
 
         // 从GC Roots开始标记
-        for &root in &self.roots {
-            self.mark_object(root, &mut reachable, _heap);
+        let mut worklist = roots.to_vec();
+        while let Some(object_ref) = worklist.pop() {
+            let Ok(object) = heap.get(object_ref) else {
+                continue; // 失效的引用
+            };
+            if !reachable.insert(object_ref) {
+                continue; // 已标记
+            }
+            // 遍历对象的字段（数组则是元素），把引用的对象加入工作列表
+            worklist.extend(object.references().filter(|referent| {
+                !reachable.contains(referent) && (!young_only || heap.is_young(*referent))
+            }));
         }
 
         reachable
     }
 
-    /// 递归标记对象及其引用的对象
-    fn mark_object(&self, object_ref: usize, reachable: &mut HashSet<usize>, _heap: &Heap) {
-        if reachable.contains(&object_ref) {
-            return; // 已标记
-        }
-
-        reachable.insert(object_ref);
-
-        // TODO: 这里应该遍历对象的字段，标记所有引用的对象
-        // 简化处理，暂不实现
-    }
-
     /// 清除阶段：回收未标记的对象
-    fn sweep(&self, heap: &mut Heap, reachable: &HashSet<usize>) -> usize {
-        let mut collected = 0;
+    fn sweep(&self, heap: &mut Heap, reachable: &HashSet<ObjRef>) -> usize {
+        // 遍历堆中的存活对象，找出不可达的
+        let garbage: Vec<ObjRef> = heap
+            .iter()
+            .map(|(object_ref, _)| object_ref)
+            .filter(|object_ref| !reachable.contains(object_ref))
+            .collect();
 
-        // 遍历堆中的所有对象
-        for i in 0..heap.object_count() {
-            if !reachable.contains(&i) {
-                // 对象不可达，回收
-                if heap.free(i).is_ok() {
-                    collected += 1;
-                }
+        let mut collected = 0;
+        for object_ref in garbage {
+            // 对象不可达，回收
+            if heap.free(object_ref).is_ok() {
+                collected += 1;
             }
         }
 
@@ -102,6 +341,7 @@ impl Default for GarbageCollector {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::runtime::frame::JvmValue;
 
     #[test]
     fn test_gc_basic() {
@@ -109,9 +349,9 @@ mod tests {
         let mut gc = GarbageCollector::new();
 
         // 分配一些对象
-        let obj1 = heap.allocate("TestClass".to_string());
-        let _obj2 = heap.allocate("TestClass".to_string());
-        let _obj3 = heap.allocate("TestClass".to_string());
+        let obj1 = heap.allocate("TestClass".to_string()).unwrap();
+        let _obj2 = heap.allocate("TestClass".to_string()).unwrap();
+        let _obj3 = heap.allocate("TestClass".to_string()).unwrap();
 
         // 只有obj1是GC Root
         gc.add_root(obj1);
@@ -119,7 +359,56 @@ mod tests {
         // 执行GC，应该回收obj2和obj3
         let collected = gc.collect(&mut heap);
 
-        // 由于简化实现，这里的测试可能需要调整
-        println!("Collected {} objects", collected);
+        assert_eq!(collected, 2);
+        assert_eq!(heap.object_count(), 1);
+
+        let event = &gc.events()[0];
+        assert_eq!((event.roots, event.marked, event.collected), (1, 1, 2));
+        assert_eq!(gc.stats().collections, 1);
+        assert_eq!(gc.stats().total_pause, event.pause());
+    }
+
+    #[test]
+    fn test_gc_follows_field_references() {
+        let mut heap = Heap::new();
+        let mut gc = GarbageCollector::new();
+
+        // head -> middle -> tail，只有 head 是根
+        let head = heap.allocate("Node".to_string()).unwrap();
+        let middle = heap.allocate("Node".to_string()).unwrap();
+        let tail = heap.allocate("Node".to_string()).unwrap();
+        let unrelated = heap.allocate("Node".to_string()).unwrap();
+        let next = |n| JvmValue::Reference(Some(n));
+        heap.set_field(head, "Node", "next", next(middle)).unwrap();
+        heap.set_field(middle, "Node", "next", next(tail)).unwrap();
+        heap.set_field(tail, "Node", "next", JvmValue::Reference(None)).unwrap();
+        gc.add_root(head);
+
+        assert_eq!(gc.collect(&mut heap), 1);
+        assert!(heap.is_live(head) && heap.is_live(middle) && heap.is_live(tail));
+        assert!(!heap.is_live(unrelated));
+        assert_eq!(gc.events()[0].marked, 3);
+    }
+
+    #[test]
+    fn test_gc_follows_array_elements_and_long_chains() {
+        let mut heap = Heap::new();
+        let mut gc = GarbageCollector::new();
+
+        let array = heap.allocate_reference_array("Node", 2).unwrap();
+        let element = heap.allocate("Node".to_string()).unwrap();
+        heap.array_set(array, 1, JvmValue::Reference(Some(element))).unwrap();
+
+        // 十万个节点的链表，递归标记会栈溢出
+        let mut previous = element;
+        for _ in 0..100_000 {
+            let node = heap.allocate("Node".to_string()).unwrap();
+            heap.set_field(previous, "Node", "next", JvmValue::Reference(Some(node))).unwrap();
+            previous = node;
+        }
+        gc.add_root(array);
+
+        assert_eq!(gc.collect(&mut heap), 0);
+        assert_eq!(heap.object_count(), 100_002);
     }
 }
@@ -0,0 +1,75 @@
+//! # GC 根集合
+//!
+//! 回收前先把所有根收集成一个 `GcRootSet`。它持有根所在位置的可变引用，
+//! 整理（compact）移动对象之后可以原地改写这些引用。
+//!
+//! ## 学习要点
+//! - 根是「不经过其他对象就能访问到」的引用：局部变量、操作数栈、静态字段等
+//! - 移动式 GC 必须知道每个根的位置，而不仅仅是根的值，才能在对象移动后更新它
+//! - 借用检查器保证收集期间没有别的代码能改动这些根
+
+use crate::runtime::frame::JvmValue;
+use crate::runtime::heap::relocate_value;
+use crate::runtime::{JvmThread, Metaspace, ObjRef};
+use std::collections::HashMap;
+
+/// 一次回收用到的全部根（可变引用）
+#[derive(Default)]
+pub struct GcRootSet<'a> {
+    /// 存放在 JvmValue 槽位中的根（局部变量、操作数栈、静态字段）
+    values: Vec<&'a mut JvmValue>,
+    /// 直接以句柄形式保存的根（如驻留字符串、Class 对象）
+    handles: Vec<&'a mut ObjRef>,
+}
+
+impl<'a> GcRootSet<'a> {
+    /// 创建空的根集合
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 添加一个值槽位（不是引用的值会被忽略）
+    pub fn add_value(&mut self, value: &'a mut JvmValue) {
+        self.values.push(value);
+    }
+
+    /// 添加一个句柄
+    pub fn add_handle(&mut self, handle: &'a mut ObjRef) {
+        self.handles.push(handle);
+    }
+
+    /// 添加线程栈中每个栈帧的局部变量表、操作数栈和 synchronized 方法锁住的对象
+    pub fn add_thread(&mut self, thread: &'a mut JvmThread) {
+        for frame in thread.frames_mut() {
+            let (values, monitor) = frame.roots_mut();
+            self.values.extend(values);
+            self.handles.extend(monitor);
+        }
+    }
+
+    /// 添加方法区中所有类的静态字段
+    pub fn add_statics(&mut self, metaspace: &'a mut Metaspace) {
+        self.values.extend(metaspace.static_values_mut());
+    }
+
+    /// 根引用的所有对象（可能有重复）
+    pub fn refs(&self) -> impl Iterator<Item = ObjRef> + '_ {
+        let values = self.values.iter().filter_map(|value| match **value {
+            JvmValue::Reference(Some(object_ref)) => Some(object_ref),
+            _ => None,
+        });
+        values.chain(self.handles.iter().map(|handle| **handle))
+    }
+
+    /// 对象移动后按 `moved`（旧引用 → 新引用）改写所有根
+    pub fn relocate(&mut self, moved: &HashMap<ObjRef, ObjRef>) {
+        for value in &mut self.values {
+            relocate_value(value, moved);
+        }
+        for handle in &mut self.handles {
+            if let Some(&new) = moved.get(*handle) {
+                **handle = new;
+            }
+        }
+    }
+}
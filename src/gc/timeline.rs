@@ -0,0 +1,242 @@
+//! # GC 事件与统计
+//!
+//! 每次回收记录一个 `GcEvent`（各阶段耗时和对象数），累计到 `GcStats`，
+//! 并可以导出为 Chrome trace（chrome://tracing / Perfetto）时间线上单独的一条 GC 轨道。
+//!
+//! ## 学习要点
+//! - GC 停顿（pause）= 根扫描 + 标记 + 清除，期间应用线程不能运行
+//! - 吞吐量看总停顿时间，响应性看最长的一次停顿
+//! - Chrome trace 的 "X" 事件用起始时间 `ts` 和持续时间 `dur`（单位微秒）描述一段区间
+
+use crate::runtime::heap::Fragmentation;
+use std::fmt;
+use std::time::Duration;
+
+/// Chrome trace 中 GC 轨道使用的 tid（方法执行使用其他 tid）
+pub const GC_TRACK_TID: u32 = 2;
+
+/// 回收的种类
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GcKind {
+    /// 完整回收：标记整个堆
+    Full,
+    /// minor GC：只回收新生代
+    Minor,
+}
+
+/// 一次垃圾回收的记录
+#[derive(Debug, Clone, PartialEq)]
+pub struct GcEvent {
+    /// 第几次回收（从 1 开始）
+    pub id: usize,
+    /// 完整回收还是 minor GC
+    pub kind: GcKind,
+    /// 开始时间，相对于回收器创建的时刻
+    pub start: Duration,
+    /// 根扫描耗时
+    pub root_scan: Duration,
+    /// 标记耗时
+    pub mark: Duration,
+    /// 清除耗时
+    pub sweep: Duration,
+    /// 整理耗时（只有标记-整理才有这个阶段）
+    pub compact: Option<Duration>,
+    /// 根的数量
+    pub roots: usize,
+    /// 标记为可达的对象数
+    pub marked: usize,
+    /// 回收的对象数
+    pub collected: usize,
+    /// 晋升到老年代的对象数（不分代时为 0）
+    pub promoted: usize,
+    /// 回收前的碎片情况
+    pub before: Fragmentation,
+    /// 回收后的碎片情况
+    pub after: Fragmentation,
+}
+
+impl GcEvent {
+    /// 整次停顿时间（各阶段之和）
+    pub fn pause(&self) -> Duration {
+        self.root_scan + self.mark + self.sweep + self.compact.unwrap_or_default()
+    }
+}
+
+impl fmt::Display for GcEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "gc #{}{}: pause {:?} (roots {:?}, mark {:?}, sweep {:?}",
+            self.id,
+            if self.kind == GcKind::Minor { " (minor)" } else { "" },
+            self.pause(),
+            self.root_scan,
+            self.mark,
+            self.sweep
+        )?;
+        if let Some(compact) = self.compact {
+            write!(f, ", compact {:?}", compact)?;
+        }
+        write!(
+            f,
+            "), {} roots, {} marked, {} collected, {} promoted, fragmentation {:.1}% -> {:.1}%",
+            self.roots,
+            self.marked,
+            self.collected,
+            self.promoted,
+            self.before.ratio() * 100.0,
+            self.after.ratio() * 100.0
+        )
+    }
+}
+
+/// 累计的 GC 统计
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GcStats {
+    /// 回收次数（包括 minor GC）
+    pub collections: usize,
+    /// 其中 minor GC 的次数
+    pub minor_collections: usize,
+    /// 总停顿时间
+    pub total_pause: Duration,
+    /// 最长的一次停顿
+    pub worst_pause: Duration,
+    /// 最近一次停顿
+    pub last_pause: Duration,
+    /// 最近一次回收之后的存活对象数
+    pub live_objects: usize,
+    /// 各阶段累计耗时
+    pub root_scan_time: Duration,
+    pub mark_time: Duration,
+    pub sweep_time: Duration,
+    pub compact_time: Duration,
+    /// 累计标记的对象数
+    pub objects_marked: usize,
+    /// 累计回收的对象数
+    pub objects_collected: usize,
+    /// 累计晋升到老年代的对象数
+    pub objects_promoted: usize,
+}
+
+impl GcStats {
+    /// 计入一次回收
+    pub fn record(&mut self, event: &GcEvent) {
+        let pause = event.pause();
+        self.collections += 1;
+        if event.kind == GcKind::Minor {
+            self.minor_collections += 1;
+        }
+        self.total_pause += pause;
+        self.worst_pause = self.worst_pause.max(pause);
+        self.last_pause = pause;
+        self.live_objects = event.after.live;
+        self.root_scan_time += event.root_scan;
+        self.mark_time += event.mark;
+        self.sweep_time += event.sweep;
+        self.compact_time += event.compact.unwrap_or_default();
+        self.objects_marked += event.marked;
+        self.objects_collected += event.collected;
+        self.objects_promoted += event.promoted;
+    }
+}
+
+impl fmt::Display for GcStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "GC: {} collections, total pause {:?}, worst pause {:?}, last pause {:?}, {} live",
+            self.collections, self.total_pause, self.worst_pause, self.last_pause, self.live_objects
+        )?;
+        write!(
+            f,
+            "  root scan {:?}, mark {:?} ({} objects), sweep {:?} ({} objects)",
+            self.root_scan_time,
+            self.mark_time,
+            self.objects_marked,
+            self.sweep_time,
+            self.objects_collected
+        )?;
+        if !self.compact_time.is_zero() {
+            write!(f, ", compact {:?}", self.compact_time)?;
+        }
+        if self.minor_collections > 0 {
+            write!(
+                f,
+                "\n  {} minor collections, {} objects promoted",
+                self.minor_collections, self.objects_promoted
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// 一次回收的日志行，格式仿照 JVM 的 GC 日志：`[gc] collected 123 objects in 0.4ms, 456 live`，
+/// minor GC 在末尾加上 `(minor, N promoted)`
+pub fn log_line(event: &GcEvent) -> String {
+    let line = format!(
+        "[gc] collected {} objects in {:.1}ms, {} live",
+        event.collected,
+        event.pause().as_secs_f64() * 1000.0,
+        event.after.live
+    );
+    match event.kind {
+        GcKind::Full => line,
+        GcKind::Minor => format!("{} (minor, {} promoted)", line, event.promoted),
+    }
+}
+
+/// 把 GC 事件导出为 Chrome trace JSON
+///
+/// 每次回收是 GC 轨道上的一个 "X" 事件，各阶段（三个，标记-整理是四个）作为嵌套的子事件
+pub fn chrome_trace(events: &[GcEvent]) -> String {
+    let mut entries = vec![format!(
+        r#"{{"name":"thread_name","ph":"M","pid":1,"tid":{},"args":{{"name":"GC"}}}}"#,
+        GC_TRACK_TID
+    )];
+
+    for event in events {
+        let start = micros(event.start);
+        entries.push(format!(
+            r#"{{"name":"{} #{}","cat":"gc","ph":"X","pid":1,"tid":{},"ts":{},"dur":{},"args":{{"roots":{},"marked":{},"collected":{}}}}}"#,
+            match event.kind {
+                GcKind::Full => "GC",
+                GcKind::Minor => "Minor GC",
+            },
+            event.id,
+            GC_TRACK_TID,
+            start,
+            micros(event.pause()),
+            event.roots,
+            event.marked,
+            event.collected
+        ));
+
+        let mut offset = event.start;
+        let phases = [
+            ("root scan", Some(event.root_scan)),
+            ("mark", Some(event.mark)),
+            ("sweep", Some(event.sweep)),
+            ("compact", event.compact),
+        ];
+        for (name, duration) in phases
+            .into_iter()
+            .filter_map(|(name, duration)| Some((name, duration?)))
+        {
+            entries.push(format!(
+                r#"{{"name":"{}","cat":"gc.phase","ph":"X","pid":1,"tid":{},"ts":{},"dur":{}}}"#,
+                name,
+                GC_TRACK_TID,
+                micros(offset),
+                micros(duration)
+            ));
+            offset += duration;
+        }
+    }
+
+    format!("{{\"traceEvents\":[\n{}\n]}}\n", entries.join(",\n"))
+}
+
+/// Chrome trace 的时间单位是微秒，保留三位小数（纳秒精度）
+fn micros(duration: Duration) -> String {
+    format!("{:.3}", duration.as_nanos() as f64 / 1000.0)
+}
@@ -0,0 +1,109 @@
+//! # 访问检查
+//!
+//! 解析方法和字段引用时检查调用者能否访问目标成员（JVMS 5.4.4），
+//! 不能访问时抛出 IllegalAccessError；写 final 字段也要检查是否在声明类的初始化方法中。
+//! 可以通过 [`InterpreterOptions::check_access`](super::InterpreterOptions) 关闭。
+//!
+//! ## 学习要点
+//! - 访问控制由 javac 和 JVM 共同保证：手写或修改过的字节码绕过了 javac，只能靠 JVM 在链接时拦住
+//! - 包由类的内部名决定：`a/b/C` 的包是 `a/b`，同一个包的类可以访问彼此的默认（包访问）成员
+//! - final 实例字段只能在声明类的 `<init>` 中赋值，final 静态字段只能在 `<clinit>` 中赋值
+//! - 访问检查只在解析时做一次（invokestatic 的调用点缓存、invokevirtual 的 `access_checked`、
+//!   字段的 `declared_fields`），之后执行同一条指令不再检查；只有 final 字段的写入每次都要看当前方法
+
+use super::Interpreter;
+use crate::classfile::access_flags::{ACC_FINAL, ACC_PRIVATE, ACC_PROTECTED};
+use crate::runtime::metaspace::ResolvedFieldRef;
+use crate::runtime::{JavaException, ResolvedMethodRef};
+use crate::Result;
+
+impl Interpreter {
+    /// `accessor` 类能否访问 `owner` 类中访问标志为 `flags` 的成员；不能时抛出 IllegalAccessError
+    ///
+    /// `member` 是错误信息中成员的描述，如 "method Foo.bar()V"
+    pub(super) fn check_member_access(
+        &self,
+        accessor: &str,
+        owner: &str,
+        flags: u16,
+        member: impl FnOnce() -> String,
+    ) -> Result<()> {
+        if !self.options.check_access || self.metaspace.is_member_accessible(accessor, owner, flags) {
+            return Ok(());
+        }
+        Err(JavaException::new(
+            "java/lang/IllegalAccessError",
+            format!("class {} tried to access {} {}", accessor, visibility(flags), member()),
+        )
+        .into())
+    }
+
+    /// invokevirtual：按方法引用解析到的方法（而不是按对象的实际类型选中的方法）检查访问，
+    /// 通过后记入 `access_checked`，同一个常量池项不再检查
+    ///
+    /// JDK 类的方法和找不到的方法不检查（后者在分派时报错）
+    pub(super) fn check_method_ref_access(
+        &mut self,
+        accessor: &str,
+        index: u16,
+        method_ref: &ResolvedMethodRef,
+    ) -> Result<()> {
+        if !self.options.check_access || method_ref.class_name.starts_with("java/") {
+            return Ok(());
+        }
+        if self.metaspace.get_class(accessor)?.runtime_pool.access_checked.contains(&index) {
+            return Ok(());
+        }
+        if let Ok((owner, method)) = self.metaspace.resolve_method_in_hierarchy(
+            &method_ref.class_name,
+            &method_ref.method_name,
+            &method_ref.descriptor,
+        ) {
+            self.check_member_access(accessor, &owner, method.access_flags, || {
+                format!("method {}.{}{}", owner, method.name, method.descriptor)
+            })?;
+        }
+        self.metaspace
+            .get_class_mut(accessor)?
+            .runtime_pool
+            .access_checked
+            .insert(index);
+        Ok(())
+    }
+
+    /// PUTFIELD/PUTSTATIC 写 final 字段：只允许在声明类的 `<init>`（实例字段）或 `<clinit>`（静态字段）中
+    pub(super) fn check_final_field_write(&self, field_ref: &ResolvedFieldRef, is_static: bool) -> Result<()> {
+        if !self.options.check_access || field_ref.access_flags & ACC_FINAL == 0 {
+            return Ok(());
+        }
+        let frame = self.thread.current_frame()?;
+        let initializer = if is_static { "<clinit>" } else { "<init>" };
+        if frame.class_name == field_ref.class_name && frame.method_name == initializer {
+            return Ok(());
+        }
+        Err(JavaException::new(
+            "java/lang/IllegalAccessError",
+            format!(
+                "Update to {}final field {}.{} attempted from a different method ({}.{}) than the initializer method {}",
+                if is_static { "static " } else { "non-static " },
+                field_ref.class_name,
+                field_ref.field_name,
+                frame.class_name,
+                frame.method_name,
+                initializer
+            ),
+        )
+        .into())
+    }
+}
+
+/// 错误信息中的可见性
+fn visibility(flags: u16) -> &'static str {
+    if flags & ACC_PRIVATE != 0 {
+        "private"
+    } else if flags & ACC_PROTECTED != 0 {
+        "protected"
+    } else {
+        "package-private"
+    }
+}
@@ -0,0 +1,767 @@
+//! # 内置类库
+//!
+//! 没有真正的 JDK，常用的 `java/*` 方法在这里用 Rust 实现，注册为本地方法。
+//! 没有注册的 JDK 方法调用会抛出 UnsatisfiedLinkError，而不是假装调用成功。
+//!
+//! ## 学习要点
+//! - 跳过一次调用不等于"什么都没发生"：调用会消耗参数、压入返回值，
+//!   跳过后操作数栈就和字节码的预期对不上了
+//! - 异常类的构造器只需要设置 `Throwable.detailMessage`，
+//!   子类（如 IllegalStateException）沿内置继承关系找到 Throwable 的实现
+//! - `Object.hashCode()` 的 identity hash 第一次使用时算出并保存在对象里，
+//!   整理（compact）移动对象后也不变
+//! - 自动装箱 `Integer x = 5` 编译成 `Integer.valueOf(5)`，拆箱是 `x.intValue()`；
+//!   -128..=127 的 Integer 有缓存，所以两个装箱对象用 `==` 比较只在这个范围内碰巧相等
+//! - `System.out` 是第一次读取时创建的 java/io/PrintStream 对象，
+//!   打印写到 `Interpreter::stdout()`，不直接写进程的 stdout
+//! - `System.gc()` 在真实 JVM 中只是建议，这里总是立即执行一次完整回收
+//! - `Thread.start()` 只是把线程放入调度队列，`join()` 让当前线程暂停，
+//!   实际的切换由解释器的执行循环完成（见 `threads`）
+//! - Java 的 String 是 UTF-16 编码单元序列：`length()`、`charAt()` 和 `substring()` 的下标
+//!   都按编码单元计，一个非 BMP 字符（如 emoji）占两个单元（代理对），与 Rust 字符串的字节下标不同
+//! - `String.hashCode()` 是 `s[0]*31^(n-1) + ... + s[n-1]`，按 int 溢出回绕；
+//!   switch-on-string 编译成 hashCode + equals，所以必须和 JDK 的值完全一致
+//! - Java 8 的字符串拼接 `"x = " + x` 编译成 StringBuilder.append 链，
+//!   append 返回 `this`，所以可以连续调用
+
+use super::native::{NativeMethod, NativeRegistry};
+use super::threads::THREAD_CLASS;
+use super::{format, Interpreter};
+use crate::runtime::frame::JvmValue;
+use crate::runtime::{JavaException, ObjRef};
+use crate::Result;
+use anyhow::anyhow;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+/// 注册全部内置方法
+pub(super) fn register_all(registry: &mut NativeRegistry) {
+    register_object(registry);
+    register_throwable(registry);
+    register_math(registry);
+    register_integer(registry);
+    register_boxes(registry);
+    register_system(registry);
+    register_string(registry);
+    register_string_builder(registry);
+    register_print_stream(registry);
+    register_thread(registry);
+}
+
+/// java/lang/Object：用户类没有覆盖的方法沿继承链找到这里的实现
+fn register_object(registry: &mut NativeRegistry) {
+    const CLASS: &str = "java/lang/Object";
+    registry.register(CLASS, "<init>", "()V", Box::new(|_, _| Ok(None)));
+    registry.register(
+        CLASS,
+        "hashCode",
+        "()I",
+        Box::new(|interpreter, args| {
+            Ok(Some(JvmValue::Int(interpreter.heap.identity_hash(receiver(args)?)?)))
+        }),
+    );
+    registry.register(
+        CLASS,
+        "equals",
+        "(Ljava/lang/Object;)Z",
+        Box::new(|_, args| match args {
+            [JvmValue::Reference(this), JvmValue::Reference(other)] => {
+                Ok(Some(JvmValue::Int((this == other) as i32)))
+            }
+            _ => Err(bad_args("Object.equals", args)),
+        }),
+    );
+    registry.register(
+        CLASS,
+        "getClass",
+        "()Ljava/lang/Class;",
+        Box::new(|interpreter, args| {
+            let class_name = interpreter.heap.get(receiver(args)?)?.class_name.clone();
+            Ok(Some(JvmValue::Reference(Some(interpreter.class_mirror(&class_name)?))))
+        }),
+    );
+    // 与 JDK 一样是 getClass().getName() + "@" + Integer.toHexString(hashCode())
+    registry.register(
+        CLASS,
+        "toString",
+        "()Ljava/lang/String;",
+        Box::new(|interpreter, args| {
+            let this = receiver(args)?;
+            let hash = hash_code(interpreter, this)?;
+            let class_name = interpreter.heap.get(this)?.class_name.replace('/', ".");
+            let text = interpreter.new_string(&format!("{}@{:x}", class_name, hash))?;
+            Ok(Some(JvmValue::Reference(Some(text))))
+        }),
+    );
+
+    registry.register(
+        "java/lang/Class",
+        "getName",
+        "()Ljava/lang/String;",
+        Box::new(|interpreter, args| {
+            Ok(Some(interpreter.heap.get_field(receiver(args)?, "java/lang/Class", "name")?))
+        }),
+    );
+}
+
+/// 对象的 hashCode()：用户类覆盖了 hashCode 时调用它，否则是 identity hash
+fn hash_code(interpreter: &mut Interpreter, object: ObjRef) -> Result<i32> {
+    let class_name = interpreter.heap.get(object)?.class_name.clone();
+    let overridden = interpreter
+        .metaspace
+        .resolve_virtual_method(&class_name, "hashCode", "()I")
+        .ok()
+        .filter(|(_, method)| !method.is_abstract);
+    let Some((owner, _)) = overridden else {
+        if let Ok(text) = interpreter.heap.get_string(object) {
+            return Ok(string_hash(text));
+        }
+        return interpreter.heap.identity_hash(object);
+    };
+    let args = vec![JvmValue::Reference(Some(object))];
+    match interpreter.execute_method_with_args(&owner, "hashCode:()I", args)? {
+        Some(JvmValue::Int(hash)) => Ok(hash),
+        other => Err(anyhow!("{}.hashCode() returned {:?}", owner, other)),
+    }
+}
+
+fn register_throwable(registry: &mut NativeRegistry) {
+    registry.register(
+        "java/lang/Throwable",
+        "<init>",
+        "()V",
+        Box::new(|interpreter, args| {
+            let this = receiver(args)?;
+            interpreter.heap.set_field(
+                this,
+                "java/lang/Throwable",
+                "detailMessage",
+                JvmValue::Reference(None),
+            )?;
+            Ok(None)
+        }),
+    );
+    registry.register(
+        "java/lang/Throwable",
+        "<init>",
+        "(Ljava/lang/String;)V",
+        Box::new(|interpreter, args| {
+            let this = receiver(args)?;
+            let message = args.get(1).cloned().unwrap_or(JvmValue::Reference(None));
+            interpreter
+                .heap
+                .set_field(this, "java/lang/Throwable", "detailMessage", message)?;
+            Ok(None)
+        }),
+    );
+    registry.register(
+        "java/lang/Throwable",
+        "getMessage",
+        "()Ljava/lang/String;",
+        Box::new(|interpreter, args| {
+            let this = receiver(args)?;
+            let message = interpreter
+                .heap
+                .get_field(this, "java/lang/Throwable", "detailMessage")
+                .unwrap_or(JvmValue::Reference(None));
+            Ok(Some(message))
+        }),
+    );
+}
+
+fn register_math(registry: &mut NativeRegistry) {
+    registry.register("java/lang/Math", "max", "(II)I", int_binary(i32::max));
+    registry.register("java/lang/Math", "min", "(II)I", int_binary(i32::min));
+    registry.register("java/lang/Math", "max", "(JJ)J", long_binary(i64::max));
+    registry.register("java/lang/Math", "min", "(JJ)J", long_binary(i64::min));
+    // Math.abs(Integer.MIN_VALUE) 仍是 MIN_VALUE
+    registry.register(
+        "java/lang/Math",
+        "abs",
+        "(I)I",
+        Box::new(|_, args| match args {
+            [JvmValue::Int(v)] => Ok(Some(JvmValue::Int(v.wrapping_abs()))),
+            _ => Err(bad_args("Math.abs(I)I", args)),
+        }),
+    );
+    registry.register(
+        "java/lang/Math",
+        "abs",
+        "(J)J",
+        Box::new(|_, args| match args {
+            [JvmValue::Long(v)] => Ok(Some(JvmValue::Long(v.wrapping_abs()))),
+            _ => Err(bad_args("Math.abs(J)J", args)),
+        }),
+    );
+}
+
+fn register_integer(registry: &mut NativeRegistry) {
+    registry.register(
+        "java/lang/Integer",
+        "parseInt",
+        "(Ljava/lang/String;)I",
+        Box::new(|interpreter, args| {
+            let text = parse_input(interpreter, "Integer.parseInt", args)?;
+            let value = text
+                .parse::<i32>()
+                .map_err(|_| number_format(&format!("For input string: \"{}\"", text)))?;
+            Ok(Some(JvmValue::Int(value)))
+        }),
+    );
+    registry.register(
+        "java/lang/Long",
+        "parseLong",
+        "(Ljava/lang/String;)J",
+        Box::new(|interpreter, args| {
+            let text = parse_input(interpreter, "Long.parseLong", args)?;
+            let value = text
+                .parse::<i64>()
+                .map_err(|_| number_format(&format!("For input string: \"{}\"", text)))?;
+            Ok(Some(JvmValue::Long(value)))
+        }),
+    );
+}
+
+/// parseInt/parseLong 的参数；null 抛出 NumberFormatException
+fn parse_input(interpreter: &Interpreter, method: &str, args: &[JvmValue]) -> Result<String> {
+    match args {
+        [JvmValue::Reference(Some(s))] => Ok(interpreter.heap.get_string(*s)?.to_string()),
+        [JvmValue::Reference(None)] => Err(number_format("Cannot parse null string: null")),
+        _ => Err(bad_args(method, args)),
+    }
+}
+
+/// 包装类：(类名, 基本类型描述符, 拆箱方法名)
+const BOXES: [(&str, &str, &str); 5] = [
+    ("java/lang/Integer", "I", "intValue"),
+    ("java/lang/Long", "J", "longValue"),
+    ("java/lang/Double", "D", "doubleValue"),
+    ("java/lang/Boolean", "Z", "booleanValue"),
+    ("java/lang/Character", "C", "charValue"),
+];
+
+/// 包装类：装箱（valueOf）、拆箱（xxxValue）以及按值比较的 equals/hashCode/toString
+///
+/// 包装类对象的 `value` 字段保存基本类型的值，boolean/char 和在操作数栈上一样是 int
+fn register_boxes(registry: &mut NativeRegistry) {
+    for (class, param, unbox) in BOXES {
+        registry.register(
+            class,
+            "valueOf",
+            &format!("({})L{};", param, class),
+            Box::new(move |interpreter, args| match args {
+                [value] => Ok(Some(JvmValue::Reference(Some(
+                    interpreter.box_value(class, value.clone())?,
+                )))),
+                _ => Err(bad_args("valueOf", args)),
+            }),
+        );
+        registry.register(
+            class,
+            "<init>",
+            &format!("({})V", param),
+            Box::new(move |interpreter, args| {
+                let value = args.get(1).cloned().ok_or_else(|| bad_args("<init>", args))?;
+                interpreter.heap.set_field(receiver(args)?, class, "value", value)?;
+                Ok(None)
+            }),
+        );
+        registry.register(
+            class,
+            unbox,
+            &format!("(){}", param),
+            Box::new(move |interpreter, args| {
+                Ok(Some(interpreter.heap.get_field(receiver(args)?, class, "value")?))
+            }),
+        );
+        registry.register(
+            class,
+            "equals",
+            "(Ljava/lang/Object;)Z",
+            Box::new(move |interpreter, args| {
+                let value = interpreter.heap.get_field(receiver(args)?, class, "value")?;
+                let equal = match args.get(1) {
+                    Some(JvmValue::Reference(Some(other)))
+                        if interpreter.heap.get(*other)?.class_name == class =>
+                    {
+                        let other = interpreter.heap.get_field(*other, class, "value")?;
+                        match (value, other) {
+                            (JvmValue::Int(a), JvmValue::Int(b)) => a == b,
+                            (JvmValue::Long(a), JvmValue::Long(b)) => a == b,
+                            // Double.equals 比较位模式：NaN 等于 NaN，0.0 不等于 -0.0
+                            (JvmValue::Double(a), JvmValue::Double(b)) => a.to_bits() == b.to_bits(),
+                            _ => false,
+                        }
+                    }
+                    _ => false,
+                };
+                Ok(Some(JvmValue::Int(equal as i32)))
+            }),
+        );
+        registry.register(
+            class,
+            "hashCode",
+            "()I",
+            Box::new(move |interpreter, args| {
+                let hash = match interpreter.heap.get_field(receiver(args)?, class, "value")? {
+                    // Boolean.hashCode：true 是 1231，false 是 1237
+                    JvmValue::Int(0) if param == "Z" => 1237,
+                    JvmValue::Int(_) if param == "Z" => 1231,
+                    JvmValue::Int(v) => v,
+                    JvmValue::Long(v) => (v ^ (v >> 32)) as i32,
+                    JvmValue::Double(v) => {
+                        let bits = v.to_bits();
+                        (bits ^ (bits >> 32)) as i32
+                    }
+                    other => return Err(anyhow!("{}.value is {:?}", class, other)),
+                };
+                Ok(Some(JvmValue::Int(hash)))
+            }),
+        );
+        registry.register(
+            class,
+            "toString",
+            "()Ljava/lang/String;",
+            Box::new(move |interpreter, args| {
+                let value = interpreter.heap.get_field(receiver(args)?, class, "value")?;
+                let text = typed_text(interpreter, param, &value);
+                Ok(Some(JvmValue::Reference(Some(interpreter.new_string(&text)?))))
+            }),
+        );
+    }
+}
+
+fn register_system(registry: &mut NativeRegistry) {
+    registry.register(
+        "java/lang/System",
+        "currentTimeMillis",
+        "()J",
+        Box::new(|_, _| {
+            let millis = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis();
+            Ok(Some(JvmValue::Long(millis as i64)))
+        }),
+    );
+    registry.register(
+        "java/lang/System",
+        "nanoTime",
+        "()J",
+        Box::new(|_, _| {
+            thread_local!(static START: Instant = Instant::now());
+            let nanos = START.with(|start| start.elapsed().as_nanos());
+            Ok(Some(JvmValue::Long(nanos as i64)))
+        }),
+    );
+    // System.gc() 和 Runtime.getRuntime().gc() 都立即回收，根与自动回收相同
+    registry.register(
+        "java/lang/System",
+        "gc",
+        "()V",
+        Box::new(|interpreter, _| {
+            interpreter.collect_garbage();
+            Ok(None)
+        }),
+    );
+    registry.register(
+        "java/lang/Runtime",
+        "getRuntime",
+        "()Ljava/lang/Runtime;",
+        Box::new(|interpreter, _| {
+            // 单例保存在方法区的 JDK 静态字段中，也是 GC 根
+            let metaspace = &mut interpreter.metaspace;
+            if let Some(runtime) = metaspace.jdk_static_field("java/lang/Runtime", "currentRuntime") {
+                return Ok(Some(runtime.clone()));
+            }
+            let runtime = interpreter.allocate_with(|heap| heap.allocate("java/lang/Runtime"))?;
+            let runtime = JvmValue::Reference(Some(runtime));
+            interpreter
+                .metaspace
+                .set_jdk_static_field("java/lang/Runtime", "currentRuntime", runtime.clone());
+            Ok(Some(runtime))
+        }),
+    );
+    registry.register(
+        "java/lang/Runtime",
+        "gc",
+        "()V",
+        Box::new(|interpreter, _| {
+            interpreter.collect_garbage();
+            Ok(None)
+        }),
+    );
+}
+
+/// java/lang/String：直接操作堆中 String 对象保存的 Rust 字符串
+fn register_string(registry: &mut NativeRegistry) {
+    const CLASS: &str = "java/lang/String";
+    registry.register(
+        CLASS,
+        "equals",
+        "(Ljava/lang/Object;)Z",
+        Box::new(|interpreter, args| {
+            let this = interpreter.heap.get_string(receiver(args)?)?;
+            let equal = match args.get(1) {
+                Some(JvmValue::Reference(Some(other))) => {
+                    interpreter.heap.get_string(*other).ok() == Some(this)
+                }
+                Some(JvmValue::Reference(None)) => false,
+                _ => return Err(bad_args("String.equals", args)),
+            };
+            Ok(Some(JvmValue::Int(equal as i32)))
+        }),
+    );
+    registry.register(
+        CLASS,
+        "hashCode",
+        "()I",
+        Box::new(|interpreter, args| {
+            let text = interpreter.heap.get_string(receiver(args)?)?;
+            Ok(Some(JvmValue::Int(string_hash(text))))
+        }),
+    );
+    registry.register(
+        CLASS,
+        "length",
+        "()I",
+        Box::new(|interpreter, args| {
+            let text = interpreter.heap.get_string(receiver(args)?)?;
+            Ok(Some(JvmValue::Int(text.encode_utf16().count() as i32)))
+        }),
+    );
+    registry.register(
+        CLASS,
+        "isEmpty",
+        "()Z",
+        Box::new(|interpreter, args| {
+            let text = interpreter.heap.get_string(receiver(args)?)?;
+            Ok(Some(JvmValue::Int(text.is_empty() as i32)))
+        }),
+    );
+    registry.register(
+        CLASS,
+        "charAt",
+        "(I)C",
+        Box::new(|interpreter, args| {
+            let index = match args.get(1) {
+                Some(JvmValue::Int(index)) => *index,
+                _ => return Err(bad_args("String.charAt", args)),
+            };
+            let units: Vec<u16> = interpreter.heap.get_string(receiver(args)?)?.encode_utf16().collect();
+            match usize::try_from(index).ok().and_then(|i| units.get(i)) {
+                Some(unit) => Ok(Some(JvmValue::Int(*unit as i32))),
+                None => Err(JavaException::new(
+                    "java/lang/StringIndexOutOfBoundsException",
+                    format!("Index {} out of bounds for length {}", index, units.len()),
+                )
+                .into()),
+            }
+        }),
+    );
+    registry.register(
+        CLASS,
+        "substring",
+        "(I)Ljava/lang/String;",
+        Box::new(|interpreter, args| match args {
+            [_, JvmValue::Int(begin)] => substring(interpreter, receiver(args)?, *begin, None),
+            _ => Err(bad_args("String.substring", args)),
+        }),
+    );
+    registry.register(
+        CLASS,
+        "substring",
+        "(II)Ljava/lang/String;",
+        Box::new(|interpreter, args| match args {
+            [_, JvmValue::Int(begin), JvmValue::Int(end)] => {
+                substring(interpreter, receiver(args)?, *begin, Some(*end))
+            }
+            _ => Err(bad_args("String.substring", args)),
+        }),
+    );
+}
+
+/// JDK 的 String.hashCode()：对 UTF-16 编码单元做 `h = 31 * h + c`
+fn string_hash(text: &str) -> i32 {
+    text.encode_utf16()
+        .fold(0i32, |hash, unit| hash.wrapping_mul(31).wrapping_add(unit as i32))
+}
+
+/// String.substring：下标按 UTF-16 编码单元计，结果是新分配的 String
+///
+/// 从代理对中间切开时，落单的代理项在 Rust 字符串中无法表示，被替换成 U+FFFD
+fn substring(
+    interpreter: &mut Interpreter,
+    this: ObjRef,
+    begin: i32,
+    end: Option<i32>,
+) -> Result<Option<JvmValue>> {
+    let units: Vec<u16> = interpreter.heap.get_string(this)?.encode_utf16().collect();
+    let length = units.len() as i32;
+    let end = end.unwrap_or(length);
+    if begin < 0 || begin > end || end > length {
+        return Err(JavaException::new(
+            "java/lang/StringIndexOutOfBoundsException",
+            format!("begin {}, end {}, length {}", begin, end, length),
+        )
+        .into());
+    }
+    let text = String::from_utf16_lossy(&units[begin as usize..end as usize]);
+    Ok(Some(JvmValue::Reference(Some(interpreter.new_string(&text)?))))
+}
+
+fn register_string_builder(registry: &mut NativeRegistry) {
+    const CLASS: &str = "java/lang/StringBuilder";
+    registry.register(
+        CLASS,
+        "<init>",
+        "()V",
+        Box::new(|interpreter, args| {
+            interpreter.heap.init_string_builder(receiver(args)?, "")?;
+            Ok(None)
+        }),
+    );
+    registry.register(
+        CLASS,
+        "<init>",
+        "(Ljava/lang/String;)V",
+        Box::new(|interpreter, args| {
+            let this = receiver(args)?;
+            let initial = match args.get(1) {
+                Some(JvmValue::Reference(Some(s))) => interpreter.heap.get_string(*s)?.to_string(),
+                _ => {
+                    return Err(JavaException::null_pointer(
+                        "StringBuilder(String) called with null".to_string(),
+                    )
+                    .into())
+                }
+            };
+            interpreter.heap.init_string_builder(this, &initial)?;
+            Ok(None)
+        }),
+    );
+
+    // append 的各个重载：把参数转成文本追加到缓冲区，返回 this
+    for param in ["Ljava/lang/String;", "I", "J", "Z", "C"] {
+        registry.register(
+            CLASS,
+            "append",
+            &format!("({})Ljava/lang/StringBuilder;", param),
+            Box::new(move |interpreter, args| {
+                let this = receiver(args)?;
+                let value = args.get(1).ok_or_else(|| bad_args("StringBuilder.append", args))?;
+                let text = typed_text(interpreter, param, value);
+                interpreter.heap.string_builder_mut(this)?.push_str(&text);
+                Ok(Some(JvmValue::Reference(Some(this))))
+            }),
+        );
+    }
+
+    registry.register(
+        CLASS,
+        "length",
+        "()I",
+        Box::new(|interpreter, args| {
+            let buffer = interpreter.heap.string_builder_mut(receiver(args)?)?;
+            Ok(Some(JvmValue::Int(buffer.encode_utf16().count() as i32)))
+        }),
+    );
+    registry.register(
+        CLASS,
+        "toString",
+        "()Ljava/lang/String;",
+        Box::new(|interpreter, args| {
+            let text = interpreter.heap.string_builder_mut(receiver(args)?)?.clone();
+            // 每次都是新的 String：驻留表是 GC 根，驻留拼接结果会让它们永远存活
+            let string = interpreter.new_string(&text)?;
+            Ok(Some(JvmValue::Reference(Some(string))))
+        }),
+    );
+}
+
+/// PrintStream.print/println：按描述符的参数类型格式化
+fn register_print_stream(registry: &mut NativeRegistry) {
+    registry.register(
+        "java/io/PrintStream",
+        "println",
+        "()V",
+        Box::new(|interpreter, _| {
+            writeln!(interpreter.stdout())?;
+            Ok(None)
+        }),
+    );
+    let params = ["Z", "C", "I", "J", "F", "D", "Ljava/lang/String;", "Ljava/lang/Object;"];
+    for (method, newline) in [("print", false), ("println", true)] {
+        for param in params {
+            registry.register(
+                "java/io/PrintStream",
+                method,
+                &format!("({})V", param),
+                Box::new(move |interpreter, args| {
+                    let value = args.get(1).ok_or_else(|| bad_args("PrintStream.print", args))?;
+                    let mut text = typed_text(interpreter, param, value);
+                    if newline {
+                        text.push('\n');
+                    }
+                    interpreter.stdout().write_all(text.as_bytes())?;
+                    Ok(None)
+                }),
+            );
+        }
+    }
+}
+
+/// java/lang/Thread：构造时分配线程 id，start/join 交给调度器（见 `threads`）
+fn register_thread(registry: &mut NativeRegistry) {
+    let constructors = [
+        ("()V", false, false),
+        ("(Ljava/lang/Runnable;)V", true, false),
+        ("(Ljava/lang/String;)V", false, true),
+        ("(Ljava/lang/Runnable;Ljava/lang/String;)V", true, true),
+    ];
+    for (descriptor, has_target, has_name) in constructors {
+        registry.register(
+            THREAD_CLASS,
+            "<init>",
+            descriptor,
+            Box::new(move |interpreter, args| {
+                let null = JvmValue::Reference(None);
+                let target = if has_target { args.get(1).cloned() } else { None };
+                let this = JvmValue::Reference(Some(receiver(args)?));
+                let mut fields = [this, target.unwrap_or(null)];
+                let id = interpreter.threads.next_id();
+                let name = match args.last() {
+                    Some(name) if has_name => name.clone(),
+                    _ => {
+                        // 参数已经弹出操作数栈：分配默认名字期间 this 和 target 要作为根
+                        let name = format!("Thread-{}", id);
+                        let name = interpreter.with_roots(&mut fields, |i| i.new_string(&name))?;
+                        JvmValue::Reference(Some(name))
+                    }
+                };
+                let this = receiver(&fields)?;
+                let [_, target] = fields;
+                let heap = &mut interpreter.heap;
+                heap.set_field(this, THREAD_CLASS, "tid", JvmValue::Long(id))?;
+                heap.set_field(this, THREAD_CLASS, "target", target)?;
+                heap.set_field(this, THREAD_CLASS, "name", name)?;
+                heap.set_field(this, THREAD_CLASS, "daemon", JvmValue::Int(0))?;
+                Ok(None)
+            }),
+        );
+    }
+    registry.register(
+        THREAD_CLASS,
+        "start",
+        "()V",
+        Box::new(|interpreter, args| {
+            interpreter.start_thread(receiver(args)?)?;
+            Ok(None)
+        }),
+    );
+    registry.register(
+        THREAD_CLASS,
+        "join",
+        "()V",
+        Box::new(|interpreter, args| {
+            let id = interpreter.thread_id(receiver(args)?)?;
+            interpreter.threads.join(id);
+            Ok(None)
+        }),
+    );
+    registry.register(
+        THREAD_CLASS,
+        "isAlive",
+        "()Z",
+        Box::new(|interpreter, args| {
+            let id = interpreter.thread_id(receiver(args)?)?;
+            Ok(Some(JvmValue::Int(interpreter.threads.is_alive(id) as i32)))
+        }),
+    );
+    registry.register(
+        THREAD_CLASS,
+        "setDaemon",
+        "(Z)V",
+        Box::new(|interpreter, args| {
+            let this = receiver(args)?;
+            if interpreter.threads.is_started(interpreter.thread_id(this)?) {
+                return Err(JavaException::new(
+                    "java/lang/IllegalThreadStateException",
+                    "setDaemon called after start".to_string(),
+                )
+                .into());
+            }
+            let daemon = args.get(1).cloned().ok_or_else(|| bad_args("Thread.setDaemon", args))?;
+            interpreter.heap.set_field(this, THREAD_CLASS, "daemon", daemon)?;
+            Ok(None)
+        }),
+    );
+    for (method, descriptor, field) in [
+        ("isDaemon", "()Z", "daemon"),
+        ("getName", "()Ljava/lang/String;", "name"),
+        ("getId", "()J", "tid"),
+    ] {
+        registry.register(
+            THREAD_CLASS,
+            method,
+            descriptor,
+            Box::new(move |interpreter, args| {
+                Ok(Some(interpreter.heap.get_field(receiver(args)?, THREAD_CLASS, field)?))
+            }),
+        );
+    }
+}
+
+/// 按参数类型描述符转换成文本：boolean/char 在栈上是 int，需要按描述符解释
+pub(super) fn typed_text(interpreter: &Interpreter, param: &str, value: &JvmValue) -> String {
+    match (param, value) {
+        ("Z", JvmValue::Int(v)) => (*v != 0).to_string(),
+        ("C", JvmValue::Int(v)) => char::from_u32(*v as u16 as u32)
+            .unwrap_or(char::REPLACEMENT_CHARACTER)
+            .to_string(),
+        _ => display_value(interpreter, value),
+    }
+}
+
+/// 按值本身的类型转换成文本（int/long/float/double、字符串或 null）
+fn display_value(interpreter: &Interpreter, value: &JvmValue) -> String {
+    match value {
+        JvmValue::Int(v) => v.to_string(),
+        JvmValue::Long(v) => v.to_string(),
+        JvmValue::Float(v) => format::java_float_to_string(*v),
+        JvmValue::Double(v) => format::java_double_to_string(*v),
+        JvmValue::Reference(Some(addr)) => match interpreter.heap.get_string(*addr) {
+            Ok(text) => text.to_string(),
+            Err(_) => format!("Reference@{:x}", addr),
+        },
+        JvmValue::Reference(None) => "null".to_string(),
+    }
+}
+
+/// 实例方法的 `this`（第一个参数）
+fn receiver(args: &[JvmValue]) -> Result<ObjRef> {
+    match args.first() {
+        Some(JvmValue::Reference(Some(this))) => Ok(*this),
+        other => Err(anyhow!("Expected non-null receiver, got {:?}", other)),
+    }
+}
+
+/// (II)I 形式的方法
+fn int_binary(op: fn(i32, i32) -> i32) -> NativeMethod {
+    Box::new(move |_, args| match args {
+        [JvmValue::Int(a), JvmValue::Int(b)] => Ok(Some(JvmValue::Int(op(*a, *b)))),
+        _ => Err(bad_args("(II)I", args)),
+    })
+}
+
+/// (JJ)J 形式的方法
+fn long_binary(op: fn(i64, i64) -> i64) -> NativeMethod {
+    Box::new(move |_, args| match args {
+        [JvmValue::Long(a), JvmValue::Long(b)] => Ok(Some(JvmValue::Long(op(*a, *b)))),
+        _ => Err(bad_args("(JJ)J", args)),
+    })
+}
+
+fn bad_args(method: &str, args: &[JvmValue]) -> anyhow::Error {
+    anyhow!("{}: bad arguments {:?}", method, args)
+}
+
+fn number_format(message: &str) -> anyhow::Error {
+    JavaException::new("java/lang/NumberFormatException", message.to_string()).into()
+}
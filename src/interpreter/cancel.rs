@@ -0,0 +1,50 @@
+//! # 协作式取消
+//!
+//! 在工作线程上运行 Java 程序时，其他线程（比如用户按了"停止"）可以通过
+//! `CancelHandle` 请求停止。解释器每执行 `check_interval` 条指令查看一次标志，
+//! 发现取消请求后弹出本次运行的栈帧，返回 `ExecutionCancelled`。
+//!
+//! ```no_run
+//! # use rsjvm::interpreter::Interpreter;
+//! # let mut interpreter = Interpreter::new();
+//! let cancel = interpreter.cancellation_token();
+//! std::thread::spawn(move || {
+//!     std::thread::sleep(std::time::Duration::from_secs(1));
+//!     cancel.cancel();
+//! });
+//! // 一秒后返回 ExecutionCancelled
+//! let result = interpreter.invoke_static("Spin", "forever", "()V", vec![]);
+//! # let _ = result;
+//! ```
+//!
+//! ## 学习要点
+//! - "协作式"：解释器只在两条指令之间检查标志，不会在指令执行到一半时停下
+//! - 标志是 `Arc<AtomicBool>`，检查只是一次原子读；再按间隔检查，
+//!   从不取消时主循环的开销几乎为零
+//! - 取消请求在返回错误时被消耗，解释器之后可以继续使用
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// 取消句柄：可以克隆、发送到其他线程，所有克隆共享同一个标志
+#[derive(Debug, Clone, Default)]
+pub struct CancelHandle {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancelHandle {
+    /// 请求取消正在进行（或下一次）的运行
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// 是否有尚未处理的取消请求
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// 处理取消请求：清除标志，返回之前是否被设置
+    pub(super) fn take(&self) -> bool {
+        self.cancelled.swap(false, Ordering::Relaxed)
+    }
+}
@@ -0,0 +1,436 @@
+//! # 预解码的指令流
+//!
+//! 类加载时把方法的字节码解码成 `Instruction` 列表，操作数已经读出并带上类型：
+//! 分支偏移换算成绝对 pc，switch 的跳转表展开成目标列表，wide 前缀并入被扩展的指令。
+//! 主循环执行预解码的指令时不再逐字节读取操作数；反汇编器也建立在这里的解码之上。
+//!
+//! ```
+//! use rsjvm::interpreter::decoded::{decode_method, Instruction};
+//! use rsjvm::interpreter::instructions::opcodes::*;
+//!
+//! // 0: bipush 5   2: ifeq 7   5: iconst_1   6: ireturn   7: iconst_0   8: ireturn
+//! let code = [BIPUSH, 5, IFEQ, 0, 5, ICONST_1, IRETURN, ICONST_0, IRETURN];
+//! let instructions = decode_method(&code)?;
+//! assert_eq!(instructions[0], (0, Instruction::Bipush(5)));
+//! assert_eq!(instructions[1], (2, Instruction::Branch { opcode: IFEQ, target: 7 }));
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+//!
+//! ## 学习要点
+//! - 操作数的形状只有十几种，`Instruction` 按形状分类并保留操作码，
+//!   执行时仍按操作码区分同一形状里的不同指令（如各种条件跳转）
+//! - 分支目标在解码时检查：必须落在 code 数组内、并且是某条指令的起点，
+//!   执行时不需要再做边界检查
+//! - 解码失败（截断、未知操作码）的方法没有预解码形式，执行时走逐字节解码的路径，
+//!   错误在执行到那条指令时报告
+
+use super::instructions::{get_instruction_name, opcodes::*};
+use crate::Result;
+use anyhow::anyhow;
+
+/// 一条解码后的指令
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Instruction {
+    /// 没有操作数的指令（iadd、iload_0、areturn 等）
+    Simple(u8),
+    Bipush(i8),
+    Sipush(i16),
+    /// ldc、ldc_w、ldc2_w
+    Ldc { opcode: u8, cp_index: u16 },
+    /// 带局部变量索引的 load/store 和 ret；`wide` 表示带 wide 前缀（索引是 2 字节）
+    Local { opcode: u8, index: u16, wide: bool },
+    Iinc { index: u16, delta: i16, wide: bool },
+    /// 条件跳转、goto、jsr 以及它们的 `_w` 形式，`target` 是绝对 pc
+    Branch { opcode: u8, target: usize },
+    /// `targets[i]` 是键 `low + i` 的跳转目标
+    TableSwitch {
+        low: i32,
+        high: i32,
+        targets: Vec<usize>,
+        default: usize,
+    },
+    /// (键, 跳转目标)，按 class 文件中的顺序
+    LookupSwitch {
+        pairs: Vec<(i32, usize)>,
+        default: usize,
+    },
+    /// 操作数是常量池索引的指令：字段访问、invokevirtual/special/static、
+    /// new、anewarray、checkcast、instanceof
+    ConstantPool { opcode: u8, cp_index: u16 },
+    InvokeInterface { cp_index: u16, count: u8 },
+    InvokeDynamic { cp_index: u16 },
+    NewArray { atype: u8 },
+    MultiANewArray { cp_index: u16, dimensions: u8 },
+}
+
+impl Instruction {
+    /// 操作码（wide 形式时是被扩展的指令）
+    pub fn opcode(&self) -> u8 {
+        match self {
+            Instruction::Simple(opcode)
+            | Instruction::Ldc { opcode, .. }
+            | Instruction::Local { opcode, .. }
+            | Instruction::Branch { opcode, .. }
+            | Instruction::ConstantPool { opcode, .. } => *opcode,
+            Instruction::Bipush(_) => BIPUSH,
+            Instruction::Sipush(_) => SIPUSH,
+            Instruction::Iinc { .. } => IINC,
+            Instruction::TableSwitch { .. } => TABLESWITCH,
+            Instruction::LookupSwitch { .. } => LOOKUPSWITCH,
+            Instruction::InvokeInterface { .. } => INVOKEINTERFACE,
+            Instruction::InvokeDynamic { .. } => INVOKEDYNAMIC,
+            Instruction::NewArray { .. } => NEWARRAY,
+            Instruction::MultiANewArray { .. } => MULTIANEWARRAY,
+        }
+    }
+
+    /// 是否带 wide 前缀
+    pub fn is_wide(&self) -> bool {
+        matches!(
+            self,
+            Instruction::Local { wide: true, .. } | Instruction::Iinc { wide: true, .. }
+        )
+    }
+
+    /// 常量池索引操作数
+    pub fn cp_index(&self) -> Option<u16> {
+        match self {
+            Instruction::Ldc { cp_index, .. }
+            | Instruction::ConstantPool { cp_index, .. }
+            | Instruction::InvokeInterface { cp_index, .. }
+            | Instruction::InvokeDynamic { cp_index }
+            | Instruction::MultiANewArray { cp_index, .. } => Some(*cp_index),
+            _ => None,
+        }
+    }
+
+    /// 跳转目标（绝对 pc）；switch 指令按 case 顺序排列，最后是 default
+    pub fn branch_targets(&self) -> Vec<usize> {
+        match self {
+            Instruction::Branch { target, .. } => vec![*target],
+            Instruction::TableSwitch {
+                targets, default, ..
+            } => targets.iter().chain([default]).copied().collect(),
+            Instruction::LookupSwitch { pairs, default } => pairs
+                .iter()
+                .map(|&(_, target)| target)
+                .chain([*default])
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// 解码一个方法的字节码，返回 (pc, 指令) 列表
+///
+/// 操作数被截断、遇到未知操作码或分支目标超出 code 数组时返回错误
+pub fn decode_method(code: &[u8]) -> Result<Vec<(usize, Instruction)>> {
+    let mut instructions = Vec::new();
+    let mut pc = 0;
+    while pc < code.len() {
+        let (instruction, size) = decode(code, pc)?;
+        instructions.push((pc, instruction));
+        pc += size;
+    }
+    Ok(instructions)
+}
+
+/// pc 不是指令起点
+const NOT_AN_INSTRUCTION: u32 = u32::MAX;
+
+/// 方法的预解码形式：指令列表和 pc → 指令编号的映射
+#[derive(Debug, Clone)]
+pub struct DecodedMethod {
+    instructions: Vec<(usize, Instruction)>,
+    /// 每个 pc 对应的指令编号，不是指令起点的位置为 `NOT_AN_INSTRUCTION`
+    pc_index: Vec<u32>,
+}
+
+impl DecodedMethod {
+    /// 解码方法，并检查每个分支目标都是某条指令的起点
+    pub fn new(code: &[u8]) -> Result<Self> {
+        let instructions = decode_method(code)?;
+        let mut pc_index = vec![NOT_AN_INSTRUCTION; code.len()];
+        for (index, (pc, _)) in instructions.iter().enumerate() {
+            pc_index[*pc] = index as u32;
+        }
+        for (pc, instruction) in &instructions {
+            for target in instruction.branch_targets() {
+                if pc_index[target] == NOT_AN_INSTRUCTION {
+                    return Err(anyhow!(
+                        "Branch target {} of {} at pc {} is not the start of an instruction",
+                        target,
+                        get_instruction_name(instruction.opcode()),
+                        pc
+                    ));
+                }
+            }
+        }
+        Ok(DecodedMethod {
+            instructions,
+            pc_index,
+        })
+    }
+
+    pub fn instructions(&self) -> &[(usize, Instruction)] {
+        &self.instructions
+    }
+
+    /// 字节码长度
+    pub fn code_len(&self) -> usize {
+        self.pc_index.len()
+    }
+
+    /// 从 `pc` 开始的指令在 `instructions()` 中的编号
+    pub fn index_of(&self, pc: usize) -> Option<usize> {
+        match self.pc_index.get(pc) {
+            Some(&index) if index != NOT_AN_INSTRUCTION => Some(index as usize),
+            _ => None,
+        }
+    }
+
+    /// 第 `index` 条指令之后的 pc（最后一条指令之后是字节码长度）
+    pub fn next_pc(&self, index: usize) -> usize {
+        self.instructions
+            .get(index + 1)
+            .map_or(self.code_len(), |&(pc, _)| pc)
+    }
+}
+
+/// newarray 的 atype 对应的类型名
+pub fn array_type_name(atype: u8) -> Option<&'static str> {
+    Some(match atype {
+        4 => "boolean",
+        5 => "char",
+        6 => "float",
+        7 => "double",
+        8 => "byte",
+        9 => "short",
+        10 => "int",
+        11 => "long",
+        _ => return None,
+    })
+}
+
+/// 解码 `pc` 处的一条指令，返回指令和它占用的字节数
+pub(super) fn decode(code: &[u8], pc: usize) -> Result<(Instruction, usize)> {
+    let opcode = code[pc];
+    let name = get_instruction_name(opcode);
+    let reader = Reader { code, pc, name };
+
+    if opcode == WIDE {
+        return decode_wide(code, pc);
+    }
+    if name == "unknown" {
+        return Err(anyhow!("Unknown opcode 0x{:02X} at pc {}", opcode, pc));
+    }
+
+    let (instruction, length) = match opcode {
+        BIPUSH => (Instruction::Bipush(reader.u8(1)? as i8), 1),
+        SIPUSH => (Instruction::Sipush(reader.u16(1)? as i16), 2),
+        LDC => {
+            let cp_index = reader.u8(1)? as u16;
+            (Instruction::Ldc { opcode, cp_index }, 1)
+        }
+        LDC_W | LDC2_W => {
+            let cp_index = reader.u16(1)?;
+            (Instruction::Ldc { opcode, cp_index }, 2)
+        }
+        ILOAD | LLOAD | FLOAD | DLOAD | ALOAD | ISTORE | LSTORE | FSTORE | DSTORE | ASTORE | RET => {
+            let index = reader.u8(1)? as u16;
+            (
+                Instruction::Local {
+                    opcode,
+                    index,
+                    wide: false,
+                },
+                1,
+            )
+        }
+        IINC => {
+            let index = reader.u8(1)? as u16;
+            let delta = reader.u8(2)? as i8 as i16;
+            (
+                Instruction::Iinc {
+                    index,
+                    delta,
+                    wide: false,
+                },
+                2,
+            )
+        }
+        IFEQ..=JSR | IFNULL | IFNONNULL => {
+            let target = reader.branch_target(reader.u16(1)? as i16 as i32)?;
+            (Instruction::Branch { opcode, target }, 2)
+        }
+        GOTO_W | JSR_W => {
+            let target = reader.branch_target(reader.i32(1)?)?;
+            (Instruction::Branch { opcode, target }, 4)
+        }
+        TABLESWITCH | LOOKUPSWITCH => decode_switch(&reader, opcode)?,
+        GETSTATIC..=INVOKESTATIC | NEW | ANEWARRAY | CHECKCAST | INSTANCEOF => {
+            let cp_index = reader.u16(1)?;
+            (Instruction::ConstantPool { opcode, cp_index }, 2)
+        }
+        INVOKEINTERFACE => {
+            let cp_index = reader.u16(1)?;
+            let count = reader.u8(3)?;
+            reader.u8(4)?;
+            (Instruction::InvokeInterface { cp_index, count }, 4)
+        }
+        INVOKEDYNAMIC => {
+            let cp_index = reader.u16(1)?;
+            reader.u16(3)?;
+            (Instruction::InvokeDynamic { cp_index }, 4)
+        }
+        NEWARRAY => {
+            let atype = reader.u8(1)?;
+            if array_type_name(atype).is_none() {
+                return Err(anyhow!("Invalid newarray type {} at pc {}", atype, pc));
+            }
+            (Instruction::NewArray { atype }, 1)
+        }
+        MULTIANEWARRAY => {
+            let cp_index = reader.u16(1)?;
+            let dimensions = reader.u8(3)?;
+            (
+                Instruction::MultiANewArray {
+                    cp_index,
+                    dimensions,
+                },
+                3,
+            )
+        }
+        _ => (Instruction::Simple(opcode), 0),
+    };
+    Ok((instruction, 1 + length))
+}
+
+/// wide 前缀：局部变量索引变成 2 字节，iinc 的增量也变成 2 字节
+fn decode_wide(code: &[u8], pc: usize) -> Result<(Instruction, usize)> {
+    let reader = Reader {
+        code,
+        pc,
+        name: "wide",
+    };
+    let opcode = reader.u8(1)?;
+    match opcode {
+        IINC => {
+            let index = reader.u16(2)?;
+            let delta = reader.u16(4)? as i16;
+            Ok((
+                Instruction::Iinc {
+                    index,
+                    delta,
+                    wide: true,
+                },
+                6,
+            ))
+        }
+        ILOAD | LLOAD | FLOAD | DLOAD | ALOAD | ISTORE | LSTORE | FSTORE | DSTORE | ASTORE | RET => {
+            let index = reader.u16(2)?;
+            Ok((
+                Instruction::Local {
+                    opcode,
+                    index,
+                    wide: true,
+                },
+                4,
+            ))
+        }
+        other => Err(anyhow!(
+            "Invalid instruction {} after wide at pc {}",
+            get_instruction_name(other),
+            pc
+        )),
+    }
+}
+
+/// tableswitch/lookupswitch：操作数先填充到 4 字节对齐（相对于方法字节码开头），
+/// 返回的长度不含操作码
+fn decode_switch(reader: &Reader, opcode: u8) -> Result<(Instruction, usize)> {
+    let start = 1 + (3 - reader.pc % 4);
+    let default = reader.branch_target(reader.i32(start)?)?;
+
+    if opcode == TABLESWITCH {
+        let low = reader.i32(start + 4)?;
+        let high = reader.i32(start + 8)?;
+        if low > high {
+            return Err(anyhow!(
+                "Invalid tableswitch at pc {}: low {} > high {}",
+                reader.pc,
+                low,
+                high
+            ));
+        }
+        let count = (high as i64 - low as i64 + 1) as usize;
+        let mut targets = Vec::new();
+        for i in 0..count {
+            let offset = reader.i32(start + 12 + i * 4)?;
+            targets.push(reader.branch_target(offset)?);
+        }
+        let instruction = Instruction::TableSwitch {
+            low,
+            high,
+            targets,
+            default,
+        };
+        Ok((instruction, start + 11 + count * 4))
+    } else {
+        let npairs = reader.i32(start + 4)?;
+        if npairs < 0 {
+            return Err(anyhow!("Invalid lookupswitch at pc {}: {} pairs", reader.pc, npairs));
+        }
+        let mut pairs = Vec::new();
+        for i in 0..npairs as usize {
+            let key = reader.i32(start + 8 + i * 8)?;
+            let offset = reader.i32(start + 12 + i * 8)?;
+            pairs.push((key, reader.branch_target(offset)?));
+        }
+        let instruction = Instruction::LookupSwitch { pairs, default };
+        Ok((instruction, start + 7 + npairs as usize * 8))
+    }
+}
+
+/// 读取指令操作数，越界时报告被截断的指令
+struct Reader<'a> {
+    code: &'a [u8],
+    pc: usize,
+    name: &'static str,
+}
+
+impl Reader<'_> {
+    fn bytes(&self, offset: usize, len: usize) -> Result<&[u8]> {
+        self.code
+            .get(self.pc + offset..self.pc + offset + len)
+            .ok_or_else(|| anyhow!("Truncated operand for {} at pc {}", self.name, self.pc))
+    }
+
+    fn u8(&self, offset: usize) -> Result<u8> {
+        Ok(self.bytes(offset, 1)?[0])
+    }
+
+    fn u16(&self, offset: usize) -> Result<u16> {
+        let b = self.bytes(offset, 2)?;
+        Ok(u16::from_be_bytes([b[0], b[1]]))
+    }
+
+    fn i32(&self, offset: usize) -> Result<i32> {
+        let b = self.bytes(offset, 4)?;
+        Ok(i32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    /// 相对偏移换算成绝对 pc
+    fn branch_target(&self, offset: i32) -> Result<usize> {
+        let target = self.pc as i64 + offset as i64;
+        if target < 0 || target >= self.code.len() as i64 {
+            return Err(anyhow!(
+                "Branch target {} of {} at pc {} is outside the code",
+                target,
+                self.name,
+                self.pc
+            ));
+        }
+        Ok(target as usize)
+    }
+}
@@ -0,0 +1,273 @@
+//! # 反汇编器
+//!
+//! 把方法的字节码还原成 javap 风格的指令列表：每条指令的 pc、助记符、操作数，
+//! 常量池索引操作数解析成符号名（如 `Method java/lang/Object.<init>:()V`），
+//! 跳转目标换算成绝对 pc。指令的解码由 [`decode_method`] 完成，
+//! 这里只负责把操作数转换成文字。
+//!
+//! ## 学习要点
+//! - 大多数指令的长度是固定的，由操作码决定
+//! - tableswitch/lookupswitch 先填充到 4 字节对齐（相对方法开头），再跟变长的跳转表
+//! - wide 前缀把后面的局部变量索引扩展成 2 字节（iinc 的增量也变成 2 字节）
+//! - 跳转偏移量相对于跳转指令自己的 pc
+
+use super::decoded::{array_type_name, decode, decode_method, Instruction};
+use super::instructions::get_instruction_name;
+use crate::classfile::constant_pool::{ConstantPool, ConstantPoolEntry};
+use crate::Result;
+use std::fmt;
+
+/// 一条反汇编后的指令
+#[derive(Debug, Clone, PartialEq)]
+pub struct DisassembledInstruction {
+    /// 指令在方法字节码中的位置
+    pub pc: usize,
+    /// 操作码（wide 形式时是被扩展的指令）
+    pub opcode: u8,
+    /// 是否带 wide 前缀
+    pub wide: bool,
+    /// 助记符；wide 形式加 `_w` 后缀，如 `iinc_w`
+    pub mnemonic: String,
+    /// 操作码之后的原始字节（不含 wide 前缀）
+    pub operand_bytes: Vec<u8>,
+    /// 操作数的文字形式，如 `#7`、`1, -1`、跳转目标 `42`
+    pub operands: String,
+    /// 常量池操作数解析出的符号，如 `Method java/lang/Object.<init>:()V`
+    pub symbol: Option<String>,
+    /// 跳转目标（绝对 pc）；switch 指令按 case 顺序排列，最后是 default
+    pub branch_targets: Vec<usize>,
+}
+
+impl DisassembledInstruction {
+    /// 指令占用的字节数（包括 wide 前缀和操作码）
+    pub fn size(&self) -> usize {
+        usize::from(self.wide) + 1 + self.operand_bytes.len()
+    }
+}
+
+impl fmt::Display for DisassembledInstruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let text = if self.operands.is_empty() {
+            format!("{:>5}: {}", self.pc, self.mnemonic)
+        } else {
+            format!("{:>5}: {:<13} {}", self.pc, self.mnemonic, self.operands)
+        };
+        match &self.symbol {
+            Some(symbol) => write!(f, "{:<40}// {}", text, symbol),
+            None => write!(f, "{}", text),
+        }
+    }
+}
+
+/// 反汇编一个方法的字节码
+///
+/// 操作数被截断、遇到未知操作码或常量池索引无效时返回错误
+pub fn disassemble(code: &[u8], cp: &ConstantPool) -> Result<Vec<DisassembledInstruction>> {
+    let decoded = decode_method(code)?;
+    let ends = decoded
+        .iter()
+        .skip(1)
+        .map(|&(pc, _)| pc)
+        .chain([code.len()]);
+    decoded
+        .iter()
+        .zip(ends)
+        .map(|((pc, instruction), end)| describe(code, *pc, end, instruction, cp))
+        .collect()
+}
+
+/// 反汇编 `pc` 处的指令及其前后各 `radius` 条指令，用于说明出错的位置
+///
+/// `pc` 处的指令无法解码（如未知操作码）时按一个字节列出，之后的指令从下一个字节继续解码，
+/// 遇到无法解码的字节就停下；之前的指令从方法开头顺序解码，中途失败时不列出
+pub fn disassemble_around(
+    code: &[u8],
+    cp: &ConstantPool,
+    pc: usize,
+    radius: usize,
+) -> Vec<DisassembledInstruction> {
+    let Some(&opcode) = code.get(pc) else {
+        return Vec::new();
+    };
+    // 从方法开头顺序解码到 `pc`，只保留最后 `radius` 条
+    let mut context = Vec::new();
+    let mut at = 0;
+    while at < pc {
+        let Some(instruction) = disassemble_one(code, at, cp) else {
+            context.clear();
+            break;
+        };
+        at += instruction.size();
+        context.push(instruction);
+    }
+    context.drain(..context.len().saturating_sub(radius));
+    let current = disassemble_one(code, pc, cp).unwrap_or_else(|| DisassembledInstruction {
+        pc,
+        opcode,
+        wide: false,
+        mnemonic: get_instruction_name(opcode).to_string(),
+        operand_bytes: Vec::new(),
+        operands: format!("0x{:02x}", opcode),
+        symbol: None,
+        branch_targets: Vec::new(),
+    });
+    let mut next = pc + current.size();
+    context.push(current);
+    for _ in 0..radius {
+        let Some(instruction) = disassemble_one(code, next, cp) else {
+            break;
+        };
+        next += instruction.size();
+        context.push(instruction);
+    }
+    context
+}
+
+/// 反汇编 `pc` 处的一条指令，无法解码时返回 None
+fn disassemble_one(code: &[u8], pc: usize, cp: &ConstantPool) -> Option<DisassembledInstruction> {
+    if pc >= code.len() {
+        return None;
+    }
+    let (instruction, size) = decode(code, pc).ok()?;
+    describe(code, pc, pc + size, &instruction, cp).ok()
+}
+
+/// 把 `pc..end` 处解码好的指令转换成文字形式
+fn describe(
+    code: &[u8],
+    pc: usize,
+    end: usize,
+    instruction: &Instruction,
+    cp: &ConstantPool,
+) -> Result<DisassembledInstruction> {
+    let opcode = instruction.opcode();
+    let wide = instruction.is_wide();
+    let name = get_instruction_name(opcode);
+
+    let operands = match instruction {
+        Instruction::Simple(_) => String::new(),
+        Instruction::Bipush(value) => value.to_string(),
+        Instruction::Sipush(value) => value.to_string(),
+        Instruction::Local { index, .. } => index.to_string(),
+        Instruction::Iinc { index, delta, .. } => format!("{}, {}", index, delta),
+        Instruction::Branch { target, .. } => target.to_string(),
+        Instruction::TableSwitch {
+            low,
+            high,
+            targets,
+            default,
+        } => {
+            let keys = (*low as i64..).zip(targets.iter().copied());
+            switch_operands(format!("{{ // {} to {}", low, high), keys, *default)
+        }
+        Instruction::LookupSwitch { pairs, default } => {
+            let keys = pairs.iter().map(|&(key, target)| (key as i64, target));
+            switch_operands(format!("{{ // {}", pairs.len()), keys, *default)
+        }
+        Instruction::Ldc { cp_index, .. } | Instruction::ConstantPool { cp_index, .. } => {
+            format!("#{}", cp_index)
+        }
+        Instruction::InvokeInterface { cp_index, count } => format!("#{},  {}", cp_index, count),
+        Instruction::InvokeDynamic { cp_index } => format!("#{},  0", cp_index),
+        Instruction::NewArray { atype } => array_type_name(*atype).unwrap_or_default().to_string(),
+        Instruction::MultiANewArray {
+            cp_index,
+            dimensions,
+        } => format!("#{},  {}", cp_index, dimensions),
+    };
+    let symbol = instruction
+        .cp_index()
+        .map(|index| symbol(cp, index))
+        .transpose()?;
+
+    Ok(DisassembledInstruction {
+        pc,
+        opcode,
+        wide,
+        mnemonic: if wide {
+            format!("{}_w", name)
+        } else {
+            name.to_string()
+        },
+        operand_bytes: code[pc + 1 + usize::from(wide)..end].to_vec(),
+        operands,
+        symbol,
+        branch_targets: instruction.branch_targets(),
+    })
+}
+
+/// switch 指令的操作数：每个 case 一行，最后是 default
+fn switch_operands(
+    mut operands: String,
+    cases: impl Iterator<Item = (i64, usize)>,
+    default: usize,
+) -> String {
+    for (key, target) in cases {
+        operands.push_str(&format!("\n{:>22}: {}", key, target));
+    }
+    operands.push_str(&format!("\n{:>22}: {}\n{:>7}}}", "default", default, ""));
+    operands
+}
+
+/// 常量池项的符号形式（javap 注释里的写法）
+pub fn symbol(cp: &ConstantPool, index: u16) -> Result<String> {
+    let member = |kind: &str, class_index: u16, nat_index: u16| -> Result<String> {
+        let class_name = cp.get_class_name(class_index)?;
+        let (name, descriptor) = cp.get_name_and_type(nat_index)?;
+        Ok(format!("{} {}.{}:{}", kind, class_name, name, descriptor))
+    };
+
+    Ok(match cp.get(index)? {
+        ConstantPoolEntry::Utf8(s) => format!("Utf8 {}", s),
+        ConstantPoolEntry::Integer(v) => format!("int {}", v),
+        ConstantPoolEntry::Float(v) => format!("float {:?}f", v),
+        ConstantPoolEntry::Long(v) => format!("long {}l", v),
+        ConstantPoolEntry::Double(v) => format!("double {:?}d", v),
+        ConstantPoolEntry::Class { name_index } => format!("class {}", cp.get_utf8(*name_index)?),
+        ConstantPoolEntry::String { string_index } => {
+            format!("String {}", cp.get_utf8(*string_index)?)
+        }
+        ConstantPoolEntry::FieldRef {
+            class_index,
+            name_and_type_index,
+        } => member("Field", *class_index, *name_and_type_index)?,
+        ConstantPoolEntry::MethodRef {
+            class_index,
+            name_and_type_index,
+        } => member("Method", *class_index, *name_and_type_index)?,
+        ConstantPoolEntry::InterfaceMethodRef {
+            class_index,
+            name_and_type_index,
+        } => member("InterfaceMethod", *class_index, *name_and_type_index)?,
+        ConstantPoolEntry::NameAndType { .. } => {
+            let (name, descriptor) = cp.get_name_and_type(index)?;
+            format!("NameAndType {}:{}", name, descriptor)
+        }
+        ConstantPoolEntry::MethodHandle {
+            reference_kind,
+            reference_index,
+        } => format!("MethodHandle {}:#{}", reference_kind, reference_index),
+        ConstantPoolEntry::MethodType { descriptor_index } => {
+            format!("MethodType {}", cp.get_utf8(*descriptor_index)?)
+        }
+        ConstantPoolEntry::InvokeDynamic {
+            bootstrap_method_attr_index,
+            name_and_type_index,
+        } => {
+            let (name, descriptor) = cp.get_name_and_type(*name_and_type_index)?;
+            format!(
+                "InvokeDynamic #{}:{}:{}",
+                bootstrap_method_attr_index, name, descriptor
+            )
+        }
+        ConstantPoolEntry::Dynamic {
+            bootstrap_method_attr_index,
+            name_and_type_index,
+        } => {
+            let (name, descriptor) = cp.get_name_and_type(*name_and_type_index)?;
+            format!("Dynamic #{}:{}:{}", bootstrap_method_attr_index, name, descriptor)
+        }
+        ConstantPoolEntry::Module { name_index } => format!("Module {}", cp.get_utf8(*name_index)?),
+        ConstantPoolEntry::Package { name_index } => format!("Package {}", cp.get_utf8(*name_index)?),
+    })
+}
@@ -0,0 +1,327 @@
+//! # 嵌入 API
+//!
+//! 从 Rust 调用 Java 静态方法时，按方法描述符自动转换参数和返回值：
+//! 传入 `JArg::Bool(true)` 给 `Z` 参数、把 `C` 返回值读成 `char`。
+//!
+//! ## 学习要点
+//! - JVM 内部没有 boolean/char/byte/short 类型的值，它们在栈上都是 int
+//! - 描述符决定了 int 应该被解释成什么：`Z` 是 0/1，`C` 是 UTF-16 码元
+//! - 方法调用允许基本类型拓宽（byte → short → int → long → float → double）
+//! - `IntoJvmValue`/`FromJvmValue` 把 Rust 类型映射到 JArg/JResult，
+//!   `Jvm::call_static_typed` 借此直接接收 `(1, true, "s")` 这样的参数、返回 `i32`/`String`
+
+use super::Interpreter;
+use crate::classfile::descriptor::{FieldType, MethodDescriptor};
+use crate::runtime::frame::JvmValue;
+use crate::runtime::ObjRef;
+use crate::Result;
+use anyhow::anyhow;
+
+/// 传给 Java 方法的参数
+#[derive(Debug, Clone, Copy)]
+pub enum JArg<'a> {
+    Bool(bool),
+    Char(char),
+    Byte(i8),
+    Short(i16),
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    /// 字符串，调用前在堆上分配 java/lang/String
+    Str(&'a str),
+    /// null 引用
+    Null,
+    /// 已有的堆对象
+    Ref(ObjRef),
+}
+
+/// 按返回类型描述符解释后的返回值
+#[derive(Debug, Clone, PartialEq)]
+pub enum JResult {
+    /// `V`
+    Void,
+    Bool(bool),
+    Char(char),
+    Byte(i8),
+    Short(i16),
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    /// 返回类型是 String 且不为 null
+    Str(String),
+    /// 其他非 null 引用
+    Ref(ObjRef),
+    /// null 引用
+    Null,
+}
+
+/// 可以作为 Java 方法参数的 Rust 值
+pub trait IntoJvmValue<'a> {
+    fn into_jarg(self) -> JArg<'a>;
+}
+
+macro_rules! into_jvm_value {
+    ($($ty:ty => $variant:ident),* $(,)?) => {
+        $(impl IntoJvmValue<'_> for $ty {
+            fn into_jarg(self) -> JArg<'static> {
+                JArg::$variant(self)
+            }
+        })*
+    };
+}
+
+into_jvm_value! {
+    bool => Bool,
+    char => Char,
+    i8 => Byte,
+    i16 => Short,
+    i32 => Int,
+    i64 => Long,
+    f32 => Float,
+    f64 => Double,
+    ObjRef => Ref,
+}
+
+impl<'a> IntoJvmValue<'a> for &'a str {
+    fn into_jarg(self) -> JArg<'a> {
+        JArg::Str(self)
+    }
+}
+
+impl<'a> IntoJvmValue<'a> for &'a String {
+    fn into_jarg(self) -> JArg<'a> {
+        JArg::Str(self)
+    }
+}
+
+impl IntoJvmValue<'_> for Option<ObjRef> {
+    fn into_jarg(self) -> JArg<'static> {
+        self.map_or(JArg::Null, JArg::Ref)
+    }
+}
+
+impl<'a> IntoJvmValue<'a> for JArg<'a> {
+    fn into_jarg(self) -> JArg<'a> {
+        self
+    }
+}
+
+/// 一组方法参数：元组、数组或 JArg 切片
+pub trait IntoJvmArgs<'a> {
+    fn into_jargs(self) -> Vec<JArg<'a>>;
+}
+
+macro_rules! into_jvm_args {
+    ($($name:ident),*) => {
+        impl<'a, $($name: IntoJvmValue<'a>),*> IntoJvmArgs<'a> for ($($name,)*) {
+            #[allow(non_snake_case)]
+            fn into_jargs(self) -> Vec<JArg<'a>> {
+                let ($($name,)*) = self;
+                vec![$($name.into_jarg()),*]
+            }
+        }
+    };
+}
+
+into_jvm_args!();
+into_jvm_args!(A);
+into_jvm_args!(A, B);
+into_jvm_args!(A, B, C);
+into_jvm_args!(A, B, C, D);
+into_jvm_args!(A, B, C, D, E);
+into_jvm_args!(A, B, C, D, E, F);
+
+impl<'a, T: IntoJvmValue<'a>, const N: usize> IntoJvmArgs<'a> for [T; N] {
+    fn into_jargs(self) -> Vec<JArg<'a>> {
+        self.into_iter().map(IntoJvmValue::into_jarg).collect()
+    }
+}
+
+impl<'a> IntoJvmArgs<'a> for &[JArg<'a>] {
+    fn into_jargs(self) -> Vec<JArg<'a>> {
+        self.to_vec()
+    }
+}
+
+impl<'a> IntoJvmArgs<'a> for Vec<JArg<'a>> {
+    fn into_jargs(self) -> Vec<JArg<'a>> {
+        self
+    }
+}
+
+/// 可以从 Java 返回值转换得到的 Rust 类型
+pub trait FromJvmValue: Sized {
+    fn from_jresult(result: JResult) -> Result<Self>;
+}
+
+macro_rules! from_jvm_value {
+    ($($ty:ty: $($pattern:pat => $value:expr),+;)*) => {
+        $(impl FromJvmValue for $ty {
+            fn from_jresult(result: JResult) -> Result<Self> {
+                match result {
+                    $($pattern => Ok($value),)+
+                    other => Err(anyhow!(
+                        "return value {:?} cannot be converted to {}",
+                        other,
+                        stringify!($ty)
+                    )),
+                }
+            }
+        })*
+    };
+}
+
+from_jvm_value! {
+    (): JResult::Void => ();
+    bool: JResult::Bool(v) => v;
+    char: JResult::Char(v) => v;
+    i8: JResult::Byte(v) => v;
+    i16: JResult::Short(v) => v, JResult::Byte(v) => v as i16;
+    i32: JResult::Int(v) => v, JResult::Short(v) => v as i32, JResult::Byte(v) => v as i32;
+    i64: JResult::Long(v) => v;
+    f32: JResult::Float(v) => v;
+    f64: JResult::Double(v) => v;
+    String: JResult::Str(v) => v;
+    Option<String>: JResult::Str(v) => Some(v), JResult::Null => None;
+    ObjRef: JResult::Ref(v) => v;
+    Option<ObjRef>: JResult::Ref(v) => Some(v), JResult::Null => None;
+}
+
+impl FromJvmValue for JResult {
+    fn from_jresult(result: JResult) -> Result<Self> {
+        Ok(result)
+    }
+}
+
+impl Interpreter {
+    /// 调用静态方法，参数和返回值按描述符自动转换
+    ///
+    /// 参数个数或类型与描述符不符时返回错误，不会执行方法
+    pub fn invoke_static_typed(
+        &mut self,
+        class_name: &str,
+        method_name: &str,
+        descriptor: &str,
+        args: &[JArg],
+    ) -> Result<JResult> {
+        let MethodDescriptor { params, ret } = MethodDescriptor::parse(descriptor)?;
+        if params.len() != args.len() {
+            return Err(anyhow!(
+                "{}.{}{} expects {} arguments, got {}",
+                class_name,
+                method_name,
+                descriptor,
+                params.len(),
+                args.len()
+            ));
+        }
+
+        let mut values = Vec::with_capacity(args.len());
+        for (i, (param, arg)) in params.iter().zip(args).enumerate() {
+            // 字符串参数的分配可能触发 GC，前面已经转换好的参数要作为根
+            let value = self.with_roots(&mut values, |i| i.coerce_arg(param, *arg))?;
+            let value = value.ok_or_else(|| {
+                anyhow!(
+                    "argument {} of {}.{}{}: cannot pass {:?} as {}",
+                    i,
+                    class_name,
+                    method_name,
+                    descriptor,
+                    arg,
+                    param
+                )
+            })?;
+            values.push(value);
+        }
+
+        let value = self.invoke_static(class_name, method_name, descriptor, values)?;
+        self.interpret_result(ret.as_ref(), value)
+    }
+
+    /// 按参数类型描述符转换参数，类型不兼容时返回 None
+    ///
+    /// 字符串参数在堆上分配，堆满时返回错误
+    fn coerce_arg(&mut self, param: &FieldType, arg: JArg) -> Result<Option<JvmValue>> {
+        match (param, arg) {
+            (FieldType::Object(class_name), JArg::Str(s))
+                if matches!(
+                    class_name.as_str(),
+                    "java/lang/String" | "java/lang/Object" | "java/lang/CharSequence"
+                ) =>
+            {
+                Ok(Some(JvmValue::Reference(Some(self.new_string(s)?))))
+            }
+            _ => Ok(coerce_value(param, arg)),
+        }
+    }
+
+    /// 按返回类型解释返回值（`None` 是 void）
+    fn interpret_result(&self, ret: Option<&FieldType>, value: Option<JvmValue>) -> Result<JResult> {
+        use FieldType::*;
+
+        let result = match (ret, value) {
+            (None, None) => JResult::Void,
+            (Some(Boolean), Some(JvmValue::Int(v))) => JResult::Bool(v != 0),
+            (Some(Char), Some(JvmValue::Int(v))) => JResult::Char(
+                char::from_u32(v as u16 as u32).unwrap_or(char::REPLACEMENT_CHARACTER),
+            ),
+            (Some(Byte), Some(JvmValue::Int(v))) => JResult::Byte(v as i8),
+            (Some(Short), Some(JvmValue::Int(v))) => JResult::Short(v as i16),
+            (Some(Int), Some(JvmValue::Int(v))) => JResult::Int(v),
+            (Some(Long), Some(JvmValue::Long(v))) => JResult::Long(v),
+            (Some(Float), Some(JvmValue::Float(v))) => JResult::Float(v),
+            (Some(Double), Some(JvmValue::Double(v))) => JResult::Double(v),
+            (Some(r), Some(JvmValue::Reference(None))) if r.is_reference() => JResult::Null,
+            (Some(Object(class_name)), Some(JvmValue::Reference(Some(ptr))))
+                if class_name == "java/lang/String" =>
+            {
+                JResult::Str(self.heap.get_string(ptr)?.to_string())
+            }
+            (Some(r), Some(JvmValue::Reference(Some(ptr)))) if r.is_reference() => JResult::Ref(ptr),
+            (r, value) => {
+                return Err(anyhow!(
+                    "return value {:?} does not match return type {}",
+                    value,
+                    r.map_or_else(|| "V".to_string(), FieldType::descriptor)
+                ))
+            }
+        };
+        Ok(result)
+    }
+}
+
+/// 不需要分配对象的参数转换，类型不兼容时返回 None
+fn coerce_value(param: &FieldType, arg: JArg) -> Option<JvmValue> {
+    use FieldType::{Boolean, Byte as B, Char as C, Double as D, Float as F, Int as I, Long as J, Short as S};
+    use JArg::*;
+
+    let value = match (param, arg) {
+        (Boolean, Bool(b)) => JvmValue::Int(b as i32),
+        (C, Char(c)) => JvmValue::Int(u16::try_from(c as u32).ok()? as i32),
+        (B, Byte(v)) => JvmValue::Int(v as i32),
+        (S, Byte(v)) => JvmValue::Int(v as i32),
+        (S, Short(v)) => JvmValue::Int(v as i32),
+        (I, Byte(v)) => JvmValue::Int(v as i32),
+        (I, Short(v)) => JvmValue::Int(v as i32),
+        (I, Char(c)) => JvmValue::Int(u16::try_from(c as u32).ok()? as i32),
+        (I, Int(v)) => JvmValue::Int(v),
+        (J, Byte(v)) => JvmValue::Long(v as i64),
+        (J, Short(v)) => JvmValue::Long(v as i64),
+        (J, Int(v)) => JvmValue::Long(v as i64),
+        (J, Long(v)) => JvmValue::Long(v),
+        (F, Int(v)) => JvmValue::Float(v as f32),
+        (F, Long(v)) => JvmValue::Float(v as f32),
+        (F, Float(v)) => JvmValue::Float(v),
+        (D, Int(v)) => JvmValue::Double(v as f64),
+        (D, Long(v)) => JvmValue::Double(v as f64),
+        (D, Float(v)) => JvmValue::Double(v as f64),
+        (D, Double(v)) => JvmValue::Double(v),
+        (p, Null) if p.is_reference() => JvmValue::Reference(None),
+        (p, Ref(ptr)) if p.is_reference() => JvmValue::Reference(Some(ptr)),
+        _ => return None,
+    };
+    Some(value)
+}
+
@@ -0,0 +1,80 @@
+//! # Java 风格的数值格式化
+//!
+//! `System.out.println(double)` 输出的是 `Double.toString` 的结果，
+//! 和 Rust 的 `Display` 不同：`12.0` 不会被写成 `12`，很大或很小的数用 `E` 记法。
+//!
+//! ## 学习要点
+//! - 10^-3 <= |x| < 10^7 时用普通小数，至少保留一位小数："12.0"、"0.001"
+//! - 其余用科学记数法："1.0E7"、"1.5E-4"
+//! - 特殊值写作 "NaN"、"Infinity"、"-Infinity"，负零是 "-0.0"
+
+/// 按 `Double.toString` 的规则格式化 double
+pub fn java_double_to_string(value: f64) -> String {
+    if value.is_nan() {
+        return "NaN".to_string();
+    }
+    if value.is_infinite() {
+        return if value > 0.0 { "Infinity" } else { "-Infinity" }.to_string();
+    }
+    let abs = value.abs();
+    if abs == 0.0 || (1e-3..1e7).contains(&abs) {
+        plain(format!("{}", value))
+    } else {
+        scientific(format!("{:e}", value))
+    }
+}
+
+/// 按 `Float.toString` 的规则格式化 float
+pub fn java_float_to_string(value: f32) -> String {
+    if value.is_nan() {
+        return "NaN".to_string();
+    }
+    if value.is_infinite() {
+        return if value > 0.0 { "Infinity" } else { "-Infinity" }.to_string();
+    }
+    let abs = value.abs();
+    if abs == 0.0 || (1e-3..1e7).contains(&abs) {
+        plain(format!("{}", value))
+    } else {
+        scientific(format!("{:e}", value))
+    }
+}
+
+/// 普通小数：整数值补上 ".0"
+fn plain(text: String) -> String {
+    if text.contains('.') {
+        text
+    } else {
+        text + ".0"
+    }
+}
+
+/// 科学记数法："1e7" → "1.0E7"，"1.5e-4" → "1.5E-4"
+fn scientific(text: String) -> String {
+    let (mantissa, exponent) = text.split_once('e').unwrap_or((&text, "0"));
+    format!("{}E{}", plain(mantissa.to_string()), exponent)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_double_to_string() {
+        assert_eq!(java_double_to_string(12.0), "12.0");
+        assert_eq!(java_double_to_string(-0.0), "-0.0");
+        assert_eq!(java_double_to_string(std::f64::consts::PI), "3.141592653589793");
+        assert_eq!(java_double_to_string(0.001), "0.001");
+        assert_eq!(java_double_to_string(1e7), "1.0E7");
+        assert_eq!(java_double_to_string(-1.5e-4), "-1.5E-4");
+        assert_eq!(java_double_to_string(f64::NEG_INFINITY), "-Infinity");
+        assert_eq!(java_double_to_string(f64::NAN), "NaN");
+    }
+
+    #[test]
+    fn test_float_to_string() {
+        assert_eq!(java_float_to_string(0.1), "0.1");
+        assert_eq!(java_float_to_string(3.0), "3.0");
+        assert_eq!(java_float_to_string(1.0e10), "1.0E10");
+    }
+}
@@ -0,0 +1,655 @@
+//! # 栈帧内指令
+//!
+//! 常量、局部变量、操作数栈、算术、比较和分支指令只读写当前栈帧，
+//! 不碰堆、方法区和线程栈。它们是热循环里执行最多的指令，
+//! 由 `execute` 直接在 `&mut Frame` 上执行：
+//! 不需要每条指令都重新查找栈顶栈帧，也不需要复制字节码和类名。
+//! 方法有预解码形式（见 `decoded` 模块）时执行解码好的指令，否则逐字节读取操作数。
+//!
+//! ## 学习要点
+//! - 解释器的开销主要在"分派"（取指令、找到处理代码）而不是指令本身，
+//!   热路径上的每一次内存分配都会成倍放大
+//! - 字节码由 `Arc<[u8]>` 在方法元数据和栈帧之间共享，借用它不需要复制
+//! - 会抛出的指令（如 idiv 除零）仍然返回 `JavaException`，由主循环统一创建异常对象
+
+use super::decoded::{DecodedMethod, Instruction};
+use super::instructions::{self, opcodes::*};
+use super::Interpreter;
+use crate::runtime::frame::JvmValue;
+use crate::runtime::{Frame, JavaException};
+use crate::Result;
+use anyhow::{anyhow, Context};
+use std::sync::Arc;
+
+/// 执行只涉及当前栈帧的指令，返回 false 表示 `opcode` 不是这类指令
+pub(super) fn execute(frame: &mut Frame, opcode: u8) -> Result<bool> {
+    let Some(decoded) = frame.decoded.take() else {
+        let code = Arc::clone(&frame.code);
+        return execute_frame_local(frame, &code, opcode);
+    };
+    // 执行期间暂时从栈帧中取出，借用它不需要增加引用计数
+    let result = match decoded.index_of(frame.pc) {
+        Some(index) => execute_decoded(frame, &decoded, index),
+        // pc 不是解码时的指令起点，逐字节解码
+        None => {
+            let code = Arc::clone(&frame.code);
+            execute_frame_local(frame, &code, opcode)
+        }
+    };
+    frame.decoded = Some(decoded);
+    result
+}
+
+/// 执行预解码的第 `index` 条指令：操作数已经读出，分支目标已经检查过
+fn execute_decoded(frame: &mut Frame, decoded: &DecodedMethod, index: usize) -> Result<bool> {
+    let (pc, instruction) = &decoded.instructions()[index];
+    match instruction {
+        Instruction::Simple(opcode) => return execute_simple(frame, *opcode),
+        Instruction::Bipush(value) => frame.push(JvmValue::Int(*value as i32))?,
+        Instruction::Sipush(value) => frame.push(JvmValue::Int(*value as i32))?,
+        Instruction::Local { opcode, index, .. } => {
+            if !access_local(frame, *opcode, *index as usize)? {
+                return Ok(false);
+            }
+        }
+        Instruction::Iinc { index, delta, .. } => iinc(frame, *index as usize, *delta as i32)?,
+        Instruction::Branch { opcode, target } => {
+            let Some(taken) = branch_taken(frame, *opcode, *pc)? else {
+                return Ok(false);
+            };
+            if taken {
+                frame.pc = *target;
+                return Ok(true);
+            }
+        }
+        Instruction::TableSwitch {
+            low,
+            targets,
+            default,
+            ..
+        } => {
+            let key = frame.pop_int()?;
+            let offset = key as i64 - *low as i64;
+            frame.pc = match usize::try_from(offset).ok().and_then(|i| targets.get(i)) {
+                Some(target) => *target,
+                None => *default,
+            };
+            return Ok(true);
+        }
+        Instruction::LookupSwitch { pairs, default } => {
+            let key = frame.pop_int()?;
+            frame.pc = pairs
+                .iter()
+                .find(|&&(k, _)| k == key)
+                .map_or(*default, |&(_, target)| target);
+            return Ok(true);
+        }
+        _ => return Ok(false),
+    }
+    frame.pc = decoded.next_pc(index);
+    Ok(true)
+}
+
+/// 逐字节解码执行，返回 false 表示 `opcode` 不是这类指令
+///
+/// `code` 是栈帧正在执行的字节码（`frame.code`），单独传入以免和 `frame` 的可变借用冲突
+pub(super) fn execute_frame_local(frame: &mut Frame, code: &[u8], opcode: u8) -> Result<bool> {
+    let pc = frame.pc;
+    match opcode {
+        BIPUSH => {
+            let value = Interpreter::read_u8(code, pc, 1)? as i8;
+            frame.push(JvmValue::Int(value as i32))?;
+            frame.pc += 2;
+        }
+
+        SIPUSH => {
+            let value = Interpreter::read_i16(code, pc)?;
+            frame.push(JvmValue::Int(value as i32))?;
+            frame.pc += 3;
+        }
+
+        ILOAD | LLOAD | FLOAD | DLOAD | ALOAD | ISTORE | LSTORE | FSTORE | DSTORE | ASTORE => {
+            let index = Interpreter::read_u8(code, pc, 1)? as usize;
+            access_local(frame, opcode, index)?;
+            frame.pc += 2;
+        }
+
+        // iinc <index> <const>: 局部变量自增，不经过操作数栈
+        IINC => {
+            let index = Interpreter::read_u8(code, pc, 1)? as usize;
+            let delta = Interpreter::read_u8(code, pc, 2)? as i8 as i32;
+            iinc(frame, index, delta)?;
+            frame.pc += 3;
+        }
+
+        // wide <opcode> <u16 index> [<i16 const>]：局部变量索引扩展到 2 字节
+        // （超过 255 个局部变量的方法），iinc 的增量也扩展到 2 字节
+        WIDE => {
+            let target = Interpreter::read_u8(code, pc, 1)?;
+            let index = Interpreter::operand_bytes(code, pc, pc + 2, 2)?;
+            let index = u16::from_be_bytes([index[0], index[1]]) as usize;
+            if target == IINC {
+                let delta = Interpreter::operand_bytes(code, pc, pc + 4, 2)?;
+                iinc(frame, index, i16::from_be_bytes([delta[0], delta[1]]) as i32)?;
+            } else if !access_local(frame, target, index)? {
+                return Err(anyhow!("Invalid instruction 0x{:02X} after wide at pc {}", target, pc));
+            }
+            frame.pc += if target == IINC { 6 } else { 4 };
+        }
+
+        // ==================== 控制流指令 ====================
+        IFEQ..=IF_ACMPNE | GOTO | IFNULL | IFNONNULL => {
+            let offset = Interpreter::read_i16(code, pc)?;
+            if branch_taken(frame, opcode, pc)? == Some(true) {
+                frame.pc = Interpreter::branch_target(code, pc, offset as i64)?;
+            } else {
+                frame.pc += 3;
+            }
+        }
+
+        // tableswitch: <0-3字节填充> default low high offsets[high-low+1]
+        // 填充使操作数相对于 code 数组起始 4 字节对齐
+        TABLESWITCH => {
+            let key = frame.pop_int()?;
+            let base = (pc + 4) & !3;
+            let default = Interpreter::read_i32(code, pc, base)?;
+            let low = Interpreter::read_i32(code, pc, base + 4)?;
+            let high = Interpreter::read_i32(code, pc, base + 8)?;
+
+            let offset = if key >= low && key <= high {
+                let index = (key as i64 - low as i64) as usize;
+                Interpreter::read_i32(code, pc, base + 12 + index * 4)?
+            } else {
+                default
+            };
+            frame.pc = Interpreter::branch_target(code, pc, offset as i64)?;
+        }
+
+        // lookupswitch: <0-3字节填充> default npairs (match, offset)[npairs]
+        LOOKUPSWITCH => {
+            let key = frame.pop_int()?;
+            let base = (pc + 4) & !3;
+            let default = Interpreter::read_i32(code, pc, base)?;
+            let npairs = Interpreter::read_i32(code, pc, base + 4)?;
+            if npairs < 0 {
+                return Err(anyhow!("lookupswitch: negative npairs {}", npairs));
+            }
+
+            let mut offset = default;
+            for i in 0..npairs as usize {
+                let pair = base + 8 + i * 8;
+                if Interpreter::read_i32(code, pc, pair)? == key {
+                    offset = Interpreter::read_i32(code, pc, pair + 4)?;
+                    break;
+                }
+            }
+            frame.pc = Interpreter::branch_target(code, pc, offset as i64)?;
+        }
+        _ => return execute_simple(frame, opcode),
+    }
+    Ok(true)
+}
+
+/// 带显式索引的 load/store，返回 false 表示 `opcode` 不是这类指令
+///
+/// long/double 占两个槽位，值存放在第一个槽位（index），index+1 不单独使用
+fn access_local(frame: &mut Frame, opcode: u8, index: usize) -> Result<bool> {
+    match opcode {
+        ILOAD | FLOAD | ALOAD => {
+            let value = frame.get_local(index)?.clone();
+            frame.push(value)?;
+        }
+        LLOAD | DLOAD => {
+            let value = frame.get_local_wide(index)?.clone();
+            frame.push(value)?;
+        }
+        ISTORE | FSTORE | ASTORE => {
+            let value = frame.pop()?;
+            frame.set_local(index, value)?;
+        }
+        LSTORE | DSTORE => {
+            let value = frame.pop()?;
+            frame.set_local_wide(index, value)?;
+        }
+        _ => return Ok(false),
+    }
+    Ok(true)
+}
+
+/// iinc：局部变量自增，不经过操作数栈
+fn iinc(frame: &mut Frame, index: usize, delta: i32) -> Result<()> {
+    let value = match frame.get_local(index)? {
+        JvmValue::Int(v) => *v,
+        other => return Err(anyhow!("iinc on non-int local {}: {:?}", index, other)),
+    };
+    frame.set_local(index, JvmValue::Int(value.wrapping_add(delta)))
+}
+
+/// 弹出条件跳转的操作数并判断是否跳转；goto 总是跳转。
+/// 返回 None 表示 `opcode` 不是这类指令（如 jsr）
+fn branch_taken(frame: &mut Frame, opcode: u8, pc: usize) -> Result<Option<bool>> {
+    let taken = match opcode {
+        IFEQ..=IFLE => {
+            let value = frame.pop_int()?;
+            match opcode {
+                IFEQ => value == 0,
+                IFNE => value != 0,
+                IFLT => value < 0,
+                IFGE => value >= 0,
+                IFGT => value > 0,
+                _ => value <= 0,
+            }
+        }
+        IF_ICMPEQ..=IF_ICMPLE => {
+            let v2 = frame.pop_int()?;
+            let v1 = frame.pop_int()?;
+            match opcode {
+                IF_ICMPEQ => v1 == v2,
+                IF_ICMPNE => v1 != v2,
+                IF_ICMPLT => v1 < v2,
+                IF_ICMPGE => v1 >= v2,
+                IF_ICMPGT => v1 > v2,
+                _ => v1 <= v2,
+            }
+        }
+        // 引用比较：比较的是堆索引（对象身份），两个 null 相等
+        IF_ACMPEQ | IF_ACMPNE => {
+            let context = || format!("{} at pc {}", instructions::get_instruction_name(opcode), pc);
+            let v2 = frame.pop_ref().with_context(context)?;
+            let v1 = frame.pop_ref().with_context(context)?;
+            (v1 == v2) == (opcode == IF_ACMPEQ)
+        }
+        IFNULL | IFNONNULL => {
+            let context = || format!("{} at pc {}", instructions::get_instruction_name(opcode), pc);
+            let value = frame.pop_ref().with_context(context)?;
+            value.is_none() == (opcode == IFNULL)
+        }
+        GOTO => true,
+        _ => return Ok(None),
+    };
+    Ok(Some(taken))
+}
+
+/// 执行没有操作数、只涉及当前栈帧的指令，返回 false 表示 `opcode` 不是这类指令
+fn execute_simple(frame: &mut Frame, opcode: u8) -> Result<bool> {
+    match opcode {
+        // 栈操作指令按槽位计数：long/double 是一个条目、占两个槽位（见 Frame::pop_slots）
+        POP | POP2 => {
+            frame.pop_slots(if opcode == POP { 1 } else { 2 })?;
+            frame.pc += 1;
+        }
+        DUP | DUP_X1 | DUP_X2 | DUP2 | DUP2_X1 | DUP2_X2 => {
+            let (slots, depth) = match opcode {
+                DUP => (1, 0),
+                DUP_X1 => (1, 1),
+                DUP_X2 => (1, 2),
+                DUP2 => (2, 0),
+                DUP2_X1 => (2, 1),
+                _ => (2, 2),
+            };
+            frame.dup_slots(slots, depth)?;
+            frame.pc += 1;
+        }
+        SWAP => {
+            frame.swap()?;
+            frame.pc += 1;
+        }
+
+        // ==================== 常量指令 ====================
+        ACONST_NULL => {
+            frame.push(JvmValue::Reference(None))?;
+            frame.pc += 1;
+        }
+        ICONST_M1 => {
+            frame.push(JvmValue::Int(-1))?;
+            frame.pc += 1;
+        }
+        ICONST_0 => {
+            frame.push(JvmValue::Int(0))?;
+            frame.pc += 1;
+        }
+        ICONST_1 => {
+            frame.push(JvmValue::Int(1))?;
+            frame.pc += 1;
+        }
+        ICONST_2 => {
+            frame.push(JvmValue::Int(2))?;
+            frame.pc += 1;
+        }
+        ICONST_3 => {
+            frame.push(JvmValue::Int(3))?;
+            frame.pc += 1;
+        }
+        ICONST_4 => {
+            frame.push(JvmValue::Int(4))?;
+            frame.pc += 1;
+        }
+        ICONST_5 => {
+            frame.push(JvmValue::Int(5))?;
+            frame.pc += 1;
+        }
+        LCONST_0 | LCONST_1 => {
+            let value = (opcode - LCONST_0) as i64;
+            frame.push(JvmValue::Long(value))?;
+            frame.pc += 1;
+        }
+        FCONST_0 | FCONST_1 | FCONST_2 => {
+            let value = (opcode - FCONST_0) as f32;
+            frame.push(JvmValue::Float(value))?;
+            frame.pc += 1;
+        }
+        DCONST_0 | DCONST_1 => {
+            let value = (opcode - DCONST_0) as f64;
+            frame.push(JvmValue::Double(value))?;
+            frame.pc += 1;
+        }
+
+        ALOAD_0 | ALOAD_1 | ALOAD_2 | ALOAD_3 => {
+            let index = (opcode - ALOAD_0) as usize;
+            let value = frame.get_local(index)?.clone();
+            frame.push(value)?;
+            frame.pc += 1;
+        }
+        // ==================== 加载指令 ====================
+        ILOAD_0 | ILOAD_1 | ILOAD_2 | ILOAD_3 => {
+            let index = (opcode - ILOAD_0) as usize;
+            let value = frame.get_local(index)?.clone();
+            frame.push(value)?;
+            frame.pc += 1;
+        }
+        // long/double 占两个槽位，值存放在第一个槽位（index），index+1 不单独使用
+        LLOAD_0 | LLOAD_1 | LLOAD_2 | LLOAD_3 => {
+            let index = (opcode - LLOAD_0) as usize;
+            let value = frame.get_local_wide(index)?.clone();
+            frame.push(value)?;
+            frame.pc += 1;
+        }
+        FLOAD_0 | FLOAD_1 | FLOAD_2 | FLOAD_3 => {
+            let index = (opcode - FLOAD_0) as usize;
+            let value = frame.get_local(index)?.clone();
+            frame.push(value)?;
+            frame.pc += 1;
+        }
+        DLOAD_0 | DLOAD_1 | DLOAD_2 | DLOAD_3 => {
+            let index = (opcode - DLOAD_0) as usize;
+            let value = frame.get_local_wide(index)?.clone();
+            frame.push(value)?;
+            frame.pc += 1;
+        }
+
+        ASTORE_0 | ASTORE_1 | ASTORE_2 | ASTORE_3 => {
+            let index = (opcode - ASTORE_0) as usize;
+            let value = frame.pop()?;
+            frame.set_local(index, value)?;
+            frame.pc += 1;
+        }
+        // ==================== 存储指令 ====================
+        ISTORE_0 | ISTORE_1 | ISTORE_2 | ISTORE_3 => {
+            let index = (opcode - ISTORE_0) as usize;
+            let value = frame.pop()?;
+            frame.set_local(index, value)?;
+            frame.pc += 1;
+        }
+        LSTORE_0 | LSTORE_1 | LSTORE_2 | LSTORE_3 => {
+            let index = (opcode - LSTORE_0) as usize;
+            let value = frame.pop()?;
+            frame.set_local_wide(index, value)?;
+            frame.pc += 1;
+        }
+        FSTORE_0 | FSTORE_1 | FSTORE_2 | FSTORE_3 => {
+            let index = (opcode - FSTORE_0) as usize;
+            let value = frame.pop()?;
+            frame.set_local(index, value)?;
+            frame.pc += 1;
+        }
+        DSTORE_0 | DSTORE_1 | DSTORE_2 | DSTORE_3 => {
+            let index = (opcode - DSTORE_0) as usize;
+            let value = frame.pop()?;
+            frame.set_local_wide(index, value)?;
+            frame.pc += 1;
+        }
+        // ==================== 运算指令 ====================
+        IADD => {
+            let v2 = frame.pop_int()?;
+            let v1 = frame.pop_int()?;
+            frame.push(JvmValue::Int(v1.wrapping_add(v2)))?;
+            frame.pc += 1;
+        }
+
+        ISUB => {
+            let v2 = frame.pop_int()?;
+            let v1 = frame.pop_int()?;
+            frame.push(JvmValue::Int(v1.wrapping_sub(v2)))?;
+            frame.pc += 1;
+        }
+
+        IMUL => {
+            let v2 = frame.pop_int()?;
+            let v1 = frame.pop_int()?;
+            frame.push(JvmValue::Int(v1.wrapping_mul(v2)))?;
+            frame.pc += 1;
+        }
+
+        IDIV => {
+            let v2 = frame.pop_int()?;
+            let v1 = frame.pop_int()?;
+            if v2 == 0 {
+                return Err(JavaException::arithmetic("/ by zero").into());
+            }
+            frame.push(JvmValue::Int(v1.wrapping_div(v2)))?;
+            frame.pc += 1;
+        }
+
+        LADD => {
+            let v2 = frame.pop_long()?;
+            let v1 = frame.pop_long()?;
+            frame.push(JvmValue::Long(v1.wrapping_add(v2)))?;
+            frame.pc += 1;
+        }
+
+        LSUB => {
+            let v2 = frame.pop_long()?;
+            let v1 = frame.pop_long()?;
+            frame.push(JvmValue::Long(v1.wrapping_sub(v2)))?;
+            frame.pc += 1;
+        }
+
+        LMUL => {
+            let v2 = frame.pop_long()?;
+            let v1 = frame.pop_long()?;
+            frame.push(JvmValue::Long(v1.wrapping_mul(v2)))?;
+            frame.pc += 1;
+        }
+
+        // Long.MIN_VALUE / -1 溢出回 MIN_VALUE，余数为 0
+        LDIV | LREM => {
+            let v2 = frame.pop_long()?;
+            let v1 = frame.pop_long()?;
+            if v2 == 0 {
+                return Err(JavaException::arithmetic("/ by zero").into());
+            }
+            let result = if opcode == LDIV {
+                v1.wrapping_div(v2)
+            } else {
+                v1.wrapping_rem(v2)
+            };
+            frame.push(JvmValue::Long(result))?;
+            frame.pc += 1;
+        }
+
+        // 取反 MIN_VALUE 仍是 MIN_VALUE
+        INEG => {
+            let v = frame.pop_int()?;
+            frame.push(JvmValue::Int(v.wrapping_neg()))?;
+            frame.pc += 1;
+        }
+
+        LNEG => {
+            let v = frame.pop_long()?;
+            frame.push(JvmValue::Long(v.wrapping_neg()))?;
+            frame.pc += 1;
+        }
+
+        FADD | FSUB | FMUL | FDIV => {
+            let v2 = frame.pop_float()?;
+            let v1 = frame.pop_float()?;
+            let result = match opcode {
+                FADD => v1 + v2,
+                FSUB => v1 - v2,
+                FMUL => v1 * v2,
+                _ => v1 / v2, // 浮点除零得到 Infinity/NaN，不抛异常
+            };
+            frame.push(JvmValue::Float(result))?;
+            frame.pc += 1;
+        }
+
+        DADD | DSUB | DMUL | DDIV => {
+            let v2 = frame.pop_double()?;
+            let v1 = frame.pop_double()?;
+            let result = match opcode {
+                DADD => v1 + v2,
+                DSUB => v1 - v2,
+                DMUL => v1 * v2,
+                _ => v1 / v2,
+            };
+            frame.push(JvmValue::Double(result))?;
+            frame.pc += 1;
+        }
+
+        // 余数的符号与被除数相同：-7 % 2 == -1
+        IREM => {
+            let v2 = frame.pop_int()?;
+            let v1 = frame.pop_int()?;
+            if v2 == 0 {
+                return Err(JavaException::arithmetic("/ by zero").into());
+            }
+            frame.push(JvmValue::Int(v1.wrapping_rem(v2)))?;
+            frame.pc += 1;
+        }
+
+        // ==================== 位运算指令 ====================
+        // 移位量只取低 5 位（int）或低 6 位（long），ushr 按无符号位模式右移
+        ISHL | ISHR | IUSHR | IAND | IOR | IXOR => {
+            let v2 = frame.pop_int()?;
+            let v1 = frame.pop_int()?;
+            let shift = (v2 & 0x1f) as u32;
+            let result = match opcode {
+                ISHL => v1 << shift,
+                ISHR => v1 >> shift,
+                IUSHR => ((v1 as u32) >> shift) as i32,
+                IAND => v1 & v2,
+                IOR => v1 | v2,
+                _ => v1 ^ v2,
+            };
+            frame.push(JvmValue::Int(result))?;
+            frame.pc += 1;
+        }
+
+        // long 移位的移位量是 int，不是 long
+        LSHL | LSHR | LUSHR => {
+            let v2 = frame.pop_int()?;
+            let v1 = frame.pop_long()?;
+            let shift = (v2 & 0x3f) as u32;
+            let result = match opcode {
+                LSHL => v1 << shift,
+                LSHR => v1 >> shift,
+                _ => ((v1 as u64) >> shift) as i64,
+            };
+            frame.push(JvmValue::Long(result))?;
+            frame.pc += 1;
+        }
+
+        LAND | LOR | LXOR => {
+            let v2 = frame.pop_long()?;
+            let v1 = frame.pop_long()?;
+            let result = match opcode {
+                LAND => v1 & v2,
+                LOR => v1 | v2,
+                _ => v1 ^ v2,
+            };
+            frame.push(JvmValue::Long(result))?;
+            frame.pc += 1;
+        }
+
+        // ==================== 类型转换指令 ====================
+        // Rust 的 `as` 与 Java 语义一致：整数窄化保留低位，
+        // 浮点转整数向零取整并在 MIN/MAX 处饱和，NaN 转成 0
+        I2L | I2F | I2D | I2B | I2C | I2S => {
+            let v = frame.pop_int()?;
+            let result = match opcode {
+                I2L => JvmValue::Long(v as i64),
+                I2F => JvmValue::Float(v as f32),
+                I2D => JvmValue::Double(v as f64),
+                I2B => JvmValue::Int(v as i8 as i32),
+                I2C => JvmValue::Int(v as u16 as i32), // char 无符号，零扩展
+                _ => JvmValue::Int(v as i16 as i32),
+            };
+            frame.push(result)?;
+            frame.pc += 1;
+        }
+
+        L2I | L2F | L2D => {
+            let v = frame.pop_long()?;
+            let result = match opcode {
+                L2I => JvmValue::Int(v as i32),
+                L2F => JvmValue::Float(v as f32),
+                _ => JvmValue::Double(v as f64),
+            };
+            frame.push(result)?;
+            frame.pc += 1;
+        }
+
+        F2I | F2L | F2D => {
+            let v = frame.pop_float()?;
+            let result = match opcode {
+                F2I => JvmValue::Int(v as i32),
+                F2L => JvmValue::Long(v as i64),
+                _ => JvmValue::Double(v as f64),
+            };
+            frame.push(result)?;
+            frame.pc += 1;
+        }
+
+        D2I | D2L | D2F => {
+            let v = frame.pop_double()?;
+            let result = match opcode {
+                D2I => JvmValue::Int(v as i32),
+                D2L => JvmValue::Long(v as i64),
+                _ => JvmValue::Float(v as f32),
+            };
+            frame.push(result)?;
+            frame.pc += 1;
+        }
+
+        // ==================== 比较指令 ====================
+        // 比较结果 -1/0/1 压栈，后续由 ifxx 指令决定跳转
+        LCMP => {
+            let v2 = frame.pop_long()?;
+            let v1 = frame.pop_long()?;
+            frame.push(JvmValue::Int(v1.cmp(&v2) as i32))?;
+            frame.pc += 1;
+        }
+
+        // fcmpl/fcmpg 只在 NaN 的处理上不同：l 压入 -1，g 压入 1
+        FCMPL | FCMPG => {
+            let v2 = frame.pop_float()?;
+            let v1 = frame.pop_float()?;
+            let nan_result = if opcode == FCMPG { 1 } else { -1 };
+            let result = v1.partial_cmp(&v2).map_or(nan_result, |o| o as i32);
+            frame.push(JvmValue::Int(result))?;
+            frame.pc += 1;
+        }
+
+        DCMPL | DCMPG => {
+            let v2 = frame.pop_double()?;
+            let v1 = frame.pop_double()?;
+            let nan_result = if opcode == DCMPG { 1 } else { -1 };
+            let result = v1.partial_cmp(&v2).map_or(nan_result, |o| o as i32);
+            frame.push(JvmValue::Int(result))?;
+            frame.pc += 1;
+        }
+
+        _ => return Ok(false),
+    }
+    Ok(true)
+}
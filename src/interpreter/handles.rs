@@ -0,0 +1,107 @@
+//! # 宿主句柄
+//!
+//! 嵌入方（Rust 代码）手里的 `ObjRef` 不在任何栈帧、字段或缓存中，GC 看不到它：
+//! 标记-清除会把对象当作垃圾回收，标记-整理会移动对象而不改写宿主手里的引用。
+//! [`Interpreter::pin`] 把对象登记到句柄表，返回 [`ObjectHandle`]；
+//! 句柄表是 GC 根的一部分，每次使用时用 [`Interpreter::resolve`] 取出对象当前的引用。
+//!
+//! ## 学习要点
+//! - JNI 也是这样：本地代码拿到的 `jobject` 是句柄（局部/全局引用），不是对象地址
+//! - 整理移动对象后只改写句柄表中的引用，句柄编号不变，宿主无需感知移动
+//! - 不再需要的句柄要 [`Interpreter::unpin`]，否则对象一直存活（类似忘记 `DeleteGlobalRef`）
+//! - 解释器内部也用句柄表：本地方法的参数已经弹出操作数栈，分配新对象（可能触发 GC）期间
+//!   用 `with_roots` 把它们临时登记为根
+
+use super::Interpreter;
+use crate::runtime::frame::JvmValue;
+use crate::runtime::ObjRef;
+use crate::Result;
+use anyhow::anyhow;
+use std::collections::HashMap;
+
+/// 宿主持有的对象句柄：对象被 GC 移动后仍然有效，直到 `unpin`
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct ObjectHandle(u64);
+
+/// 句柄表（句柄编号 → 对象当前的引用）
+#[derive(Debug, Default)]
+pub(super) struct HostHandles {
+    objects: HashMap<u64, ObjRef>,
+    next_id: u64,
+}
+
+impl HostHandles {
+    /// 句柄表中的引用，GC 把它们作为根并在整理后原地改写
+    pub(super) fn roots_mut(&mut self) -> impl Iterator<Item = &mut ObjRef> {
+        self.objects.values_mut()
+    }
+
+    /// 清空句柄表（堆被丢弃时，旧句柄全部失效）
+    pub(super) fn clear(&mut self) {
+        self.objects.clear();
+    }
+}
+
+impl Interpreter {
+    /// 把对象登记为 GC 根，返回句柄
+    pub fn pin(&mut self, object: ObjRef) -> ObjectHandle {
+        let handles = &mut self.handles;
+        let id = handles.next_id;
+        handles.next_id += 1;
+        handles.objects.insert(id, object);
+        ObjectHandle(id)
+    }
+
+    /// 句柄指向的对象当前的引用（只在下一次 GC 之前有效）
+    pub fn resolve(&self, handle: &ObjectHandle) -> Result<ObjRef> {
+        self.handles
+            .objects
+            .get(&handle.0)
+            .copied()
+            .ok_or_else(|| anyhow!("stale object handle {:?}", handle))
+    }
+
+    /// 句柄指向的对象，作为参数传给 Java 方法
+    pub fn resolve_value(&self, handle: &ObjectHandle) -> Result<JvmValue> {
+        Ok(JvmValue::Reference(Some(self.resolve(handle)?)))
+    }
+
+    /// 释放句柄，对象不再因它而存活；返回对象当前的引用
+    pub fn unpin(&mut self, handle: ObjectHandle) -> Option<ObjRef> {
+        self.handles.objects.remove(&handle.0)
+    }
+
+    /// 在 `f` 运行期间把 `object` 登记为根；返回对象之后（可能已被移动）的引用和 `f` 的结果
+    pub(super) fn with_pinned<R>(
+        &mut self,
+        object: ObjRef,
+        f: impl FnOnce(&mut Self, &ObjectHandle) -> Result<R>,
+    ) -> Result<(ObjRef, R)> {
+        let handle = self.pin(object);
+        let result = f(self, &handle);
+        let object = self.unpin(handle).expect("with_pinned: handle was pinned above");
+        Ok((object, result?))
+    }
+
+    /// 在 `f` 运行期间把 `values` 中的引用登记为根，之后写回它们（可能已被移动）的引用
+    pub(super) fn with_roots<R>(
+        &mut self,
+        values: &mut [JvmValue],
+        f: impl FnOnce(&mut Self) -> Result<R>,
+    ) -> Result<R> {
+        let handles: Vec<_> = values
+            .iter()
+            .map(|value| match value {
+                JvmValue::Reference(Some(object)) => Some(self.pin(*object)),
+                _ => None,
+            })
+            .collect();
+        let result = f(self);
+        for (value, handle) in values.iter_mut().zip(handles) {
+            if let Some(handle) = handle {
+                *value = JvmValue::Reference(self.unpin(handle));
+            }
+        }
+        result
+    }
+}
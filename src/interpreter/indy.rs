@@ -0,0 +1,145 @@
+//! # invokedynamic
+//!
+//! Java 9+ 的 javac 把字符串拼接 `"a" + x + "b"` 编译成一条 invokedynamic，
+//! 引导方法是 `java/lang/invoke/StringConcatFactory.makeConcatWithConstants`。
+//! 这里没有方法句柄，直接按引导方法的静态参数（配方）拼接字符串；其他引导方法报错。
+//!
+//! ## 学习要点
+//! - invokedynamic 的常量池项只给出名字和描述符，真正调用什么由 BootstrapMethods 属性中的引导方法决定
+//! - 调用点第一次执行时链接（真实 JVM 调用引导方法得到 CallSite），之后直接使用链接结果
+//! - 配方中 `\u{1}` 表示下一个动态参数（从操作数栈弹出），`\u{2}` 表示下一个静态常量，其余字符原样输出
+//! - Java 8 的 javac 不会生成 invokedynamic 拼接，而是 StringBuilder.append 链（见 `builtins`）
+
+use super::builtins::typed_text;
+use super::Interpreter;
+use crate::classfile::constant_pool::ConstantPoolEntry;
+use crate::classfile::descriptor;
+use crate::classfile::version::is_supported_bootstrap;
+use crate::runtime::frame::JvmValue;
+use crate::runtime::{ClassMetadata, ConcatPart, StringConcatSite, Symbol};
+use crate::Result;
+use anyhow::anyhow;
+use std::sync::Arc;
+
+impl Interpreter {
+    /// invokedynamic #index 0 0：弹出动态参数，按配方拼接，压入新分配的字符串
+    ///
+    /// 与 JDK 一样不驻留结果：`("v" + i) == "v1"` 为 false，循环中的临时字符串可以被回收
+    pub(super) fn invoke_dynamic(&mut self, class_name: &Symbol, index: u16) -> Result<()> {
+        let site = self.concat_site(class_name, index)?;
+        let frame = self.thread.current_frame_mut()?;
+        let mut args = Vec::with_capacity(site.arg_types.len());
+        for _ in 0..site.arg_types.len() {
+            args.push(frame.pop()?);
+        }
+        args.reverse();
+
+        let mut text = String::new();
+        for part in &site.parts {
+            match part {
+                ConcatPart::Literal(literal) => text.push_str(literal),
+                ConcatPart::Arg(n) => text.push_str(&typed_text(self, &site.arg_types[*n], &args[*n])),
+            }
+        }
+        let string = self.new_string(&text)?;
+        let frame = self.thread.current_frame_mut()?;
+        frame.push(JvmValue::Reference(Some(string)))?;
+        frame.pc += 5;
+        Ok(())
+    }
+
+    /// 已链接的调用点；第一次执行时链接并缓存
+    fn concat_site(&mut self, class_name: &str, index: u16) -> Result<Arc<StringConcatSite>> {
+        let class = self.metaspace.get_class_mut(class_name)?;
+        if let Some(site) = class.runtime_pool.concat_sites.get(&index) {
+            return Ok(site.clone());
+        }
+        let site = Arc::new(link_concat_site(class, index)?);
+        class.runtime_pool.concat_sites.insert(index, site.clone());
+        Ok(site)
+    }
+}
+
+/// 解析 InvokeDynamic 常量和它的引导方法，展开 makeConcatWithConstants 的配方
+fn link_concat_site(class: &mut ClassMetadata, index: u16) -> Result<StringConcatSite> {
+    let (bootstrap_index, name_and_type_index) = match entry(class, index)? {
+        ConstantPoolEntry::InvokeDynamic {
+            bootstrap_method_attr_index,
+            name_and_type_index,
+        } => (*bootstrap_method_attr_index, *name_and_type_index),
+        other => return Err(anyhow!("Expected InvokeDynamic at index {}, got {:?}", index, other)),
+    };
+    let (_, descriptor) = class.resolve_name_and_type(name_and_type_index)?;
+    let bootstrap = class
+        .bootstrap_methods
+        .get(bootstrap_index as usize)
+        .cloned()
+        .ok_or_else(|| anyhow!("Invalid bootstrap method index {} in {}", bootstrap_index, class.name))?;
+    let handle_target = match entry(class, bootstrap.method_ref)? {
+        ConstantPoolEntry::MethodHandle { reference_index, .. } => *reference_index,
+        other => return Err(anyhow!("Expected MethodHandle at index {}, got {:?}", bootstrap.method_ref, other)),
+    };
+    let method = class.resolve_method_ref(handle_target)?;
+    if !is_supported_bootstrap(&method.class_name, &method.method_name) {
+        return Err(anyhow!(
+            "unsupported invokedynamic bootstrap: {}.{}",
+            method.class_name,
+            method.method_name
+        ));
+    }
+
+    let (recipe_index, constant_indexes) = bootstrap
+        .arguments
+        .split_first()
+        .ok_or_else(|| anyhow!("makeConcatWithConstants without a recipe in {}", class.name))?;
+    let recipe = constant_text(class, *recipe_index)?;
+    let arg_types: Vec<String> = descriptor::parameter_descriptors(&descriptor)?
+        .into_iter()
+        .map(str::to_string)
+        .collect();
+    let mut constants = constant_indexes.iter();
+    let mut parts = Vec::new();
+    let mut literal = String::new();
+    let mut next_arg = 0;
+    for ch in recipe.chars() {
+        match ch {
+            '\u{1}' => {
+                if next_arg >= arg_types.len() {
+                    return Err(anyhow!("Concat recipe has more arguments than {}", descriptor));
+                }
+                if !literal.is_empty() {
+                    parts.push(ConcatPart::Literal(std::mem::take(&mut literal)));
+                }
+                parts.push(ConcatPart::Arg(next_arg));
+                next_arg += 1;
+            }
+            '\u{2}' => {
+                let constant = constants
+                    .next()
+                    .ok_or_else(|| anyhow!("Concat recipe has more constants than bootstrap arguments"))?;
+                literal.push_str(&constant_text(class, *constant)?);
+            }
+            _ => literal.push(ch),
+        }
+    }
+    if !literal.is_empty() {
+        parts.push(ConcatPart::Literal(literal));
+    }
+    Ok(StringConcatSite { arg_types, parts })
+}
+
+fn entry(class: &ClassMetadata, index: u16) -> Result<&ConstantPoolEntry> {
+    class.constant_pool.get(index)
+}
+
+/// 引导方法的静态参数（String 或数值常量）转成文本
+fn constant_text(class: &ClassMetadata, index: u16) -> Result<String> {
+    Ok(match entry(class, index)? {
+        ConstantPoolEntry::String { .. } => class.constant_pool.get_string(index)?,
+        ConstantPoolEntry::Integer(v) => v.to_string(),
+        ConstantPoolEntry::Long(v) => v.to_string(),
+        ConstantPoolEntry::Float(v) => super::format::java_float_to_string(*v),
+        ConstantPoolEntry::Double(v) => super::format::java_double_to_string(*v),
+        other => return Err(anyhow!("Unsupported concat constant at index {}: {:?}", index, other)),
+    })
+}
@@ -213,6 +213,27 @@ pub mod opcodes {
     pub const ASTORE_2: u8 = 0x4d;
     pub const ASTORE_3: u8 = 0x4e;
 
+    // ============ 数组存储指令 (Array Store) ============
+    // 与数组加载相反：弹出值、索引、数组引用，把值存入arrayref[index]
+
+    /// 0x4f - 存储int到数组
+    /// 栈变化: ..., arrayref, index, value → ...
+    pub const IASTORE: u8 = 0x4f;
+    /// 0x50 - 存储long到数组
+    pub const LASTORE: u8 = 0x50;
+    /// 0x51 - 存储float到数组
+    pub const FASTORE: u8 = 0x51;
+    /// 0x52 - 存储double到数组
+    pub const DASTORE: u8 = 0x52;
+    /// 0x53 - 存储引用到数组
+    pub const AASTORE: u8 = 0x53;
+    /// 0x54 - 存储byte/boolean到数组（截断为8位）
+    pub const BASTORE: u8 = 0x54;
+    /// 0x55 - 存储char到数组（截断为16位无符号）
+    pub const CASTORE: u8 = 0x55;
+    /// 0x56 - 存储short到数组（截断为16位有符号）
+    pub const SASTORE: u8 = 0x56;
+
     // ============ 栈操作指令 (Stack) ============
     // 直接操作操作数栈，不涉及局部变量表
 
@@ -665,6 +686,14 @@ pub fn get_instruction_name(opcode: u8) -> &'static str {
         FSTORE => "fstore",
         DSTORE => "dstore",
         ASTORE => "astore",
+        IASTORE => "iastore",
+        LASTORE => "lastore",
+        FASTORE => "fastore",
+        DASTORE => "dastore",
+        AASTORE => "aastore",
+        BASTORE => "bastore",
+        CASTORE => "castore",
+        SASTORE => "sastore",
         ISTORE_0 => "istore_0",
         ISTORE_1 => "istore_1",
         ISTORE_2 => "istore_2",
@@ -18,13 +18,53 @@
 //! - 控制转移：分支和跳转（if_icmpeq, goto等）
 //! - 返回指令：方法返回（ireturn, return等）
 
+mod access;
+mod builtins;
+pub mod cancel;
+pub mod decoded;
+pub mod disasm;
+mod frame_ops;
+mod indy;
+pub mod embed;
+pub mod format;
+pub mod handles;
 pub mod instructions;
-
+mod monitors;
+pub mod native;
+pub mod opcode_policy;
+pub mod profile;
+pub mod snapshot;
+pub mod stats;
+pub mod stdio;
+pub mod stepping;
+pub mod threads;
+pub mod trace;
+pub mod verifier;
+pub mod watch;
+
+use crate::classfile::descriptor::{jvm_default_value, MethodDescriptor};
 use crate::classfile::ClassFile;
+use crate::classloader::ClassLoader;
+use crate::gc::{GarbageCollector, GcEvent, GcKind, GcRootSet, GcStats, GcStrategy};
 use crate::runtime::frame::JvmValue;
-use crate::runtime::{Frame, Heap, JvmThread, Metaspace};
+use crate::runtime::heap::ArrayType;
+use crate::runtime::metaspace::{
+    method_key, ClassState, NegativeResolution, ResolvedFieldRef, StaticCallSite,
+};
+use crate::runtime::{
+    ExecutionCancelled, ExecutionError, ExecutionLimit, ExecutionLimitExceeded, Frame, FrameInfo,
+    Heap, HeapDump, JavaException, JvmThread, Metaspace, ObjRef, Symbol,
+};
 use crate::Result;
 use anyhow::anyhow;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use trace::{PendingTrace, PrintTrace, TraceEvent, TraceHook};
+use decoded::DecodedMethod;
+use native::{NativeMethod, NativeRegistry};
+use opcode_policy::{UnknownOpcodePolicy, UnknownOpcodeReport};
+use watch::{FieldAccessEvent, FieldAccessKind, FieldWatch};
 
 /// 指令执行控制
 enum InstructionControl {
@@ -34,6 +74,55 @@ enum InstructionControl {
     Return(Option<JvmValue>),
 }
 
+/// 默认每执行这么多条指令读取一次时钟、查看一次取消请求
+pub const DEFAULT_CHECK_INTERVAL: u64 = 1024;
+
+/// 解释器的可配置选项
+#[derive(Debug, Clone)]
+pub struct InterpreterOptions {
+    /// 线程栈的栈帧数上限，超过时抛出 StackOverflowError
+    pub max_frames: usize,
+    /// 加载类时是否校验方法的字节码（见 `verifier`）
+    pub verify: bool,
+    /// 允许加载的最高 class 文件主版本号，None 表示不检查（见 `classfile::version`）
+    pub max_major_version: Option<u16>,
+    /// 解析方法和字段引用时是否做访问检查（private/protected/包访问、final 字段写入），
+    /// 不能访问时抛出 IllegalAccessError（见 `access`）
+    pub check_access: bool,
+    /// 两次自动 GC 之间最多分配的对象数，None 表示不自动回收
+    pub gc_threshold: Option<usize>,
+    /// 回收算法（默认标记-清除；`GcStrategy::generational()` 打开新生代和 minor GC）
+    pub gc_strategy: GcStrategy,
+    /// 堆中存活对象数上限，None 表示不限制（超过时抛出 OutOfMemoryError）
+    pub max_heap_objects: Option<usize>,
+    /// 一次运行最多执行的指令条数，None 表示不限制（防止死循环）
+    pub max_instructions: Option<u64>,
+    /// 一次运行最长的时间，None 表示不限制；每执行 `check_interval` 条指令检查一次
+    pub time_limit: Option<Duration>,
+    /// 每执行多少条指令检查一次运行时间和取消请求（见 `cancel`）
+    pub check_interval: u64,
+    /// 遇到不支持的操作码时怎么办（见 `opcode_policy`）
+    pub unknown_opcode_policy: UnknownOpcodePolicy,
+}
+
+impl Default for InterpreterOptions {
+    fn default() -> Self {
+        InterpreterOptions {
+            max_frames: crate::runtime::thread::DEFAULT_MAX_FRAMES,
+            verify: true,
+            max_major_version: Some(crate::classfile::version::DEFAULT_MAX_MAJOR_VERSION),
+            check_access: true,
+            gc_threshold: Some(crate::runtime::heap::DEFAULT_GC_THRESHOLD),
+            gc_strategy: GcStrategy::default(),
+            max_heap_objects: None,
+            max_instructions: None,
+            time_limit: None,
+            check_interval: DEFAULT_CHECK_INTERVAL,
+            unknown_opcode_policy: UnknownOpcodePolicy::Error,
+        }
+    }
+}
+
 /// 解释器
 pub struct Interpreter {
     /// 堆
@@ -42,159 +131,996 @@ pub struct Interpreter {
     pub thread: JvmThread,
     /// 方法区 - 存储所有类的元数据
     pub metaspace: Metaspace,
+    /// 类加载器（可选）- 按名字加载类时使用（如 Class.forName）
+    pub class_loader: Option<ClassLoader>,
+    /// 每个类对应的 java/lang/Class 对象（类名 → 堆引用），保证同一个类只有一个 Class 对象
+    class_mirrors: HashMap<String, ObjRef>,
+    /// 字符串常量池（内容 → 堆引用），同一个字面量 ldc 多次得到同一个对象
+    interned_strings: HashMap<String, ObjRef>,
+    /// 装箱缓存（(包装类, 值) → 堆引用），如 Integer.valueOf(100) 每次得到同一个对象
+    boxed_values: HashMap<(&'static str, i64), ObjRef>,
+    /// 宿主通过 `pin` 持有的对象（见 `handles`）
+    handles: handles::HostHandles,
+    /// 垃圾回收器（class_mirrors、interned_strings、boxed_values 和 handles 中的对象也是根）
+    gc: GarbageCollector,
+    /// 字段监视（为空时字段指令不做额外工作）
+    field_watches: Vec<FieldWatch>,
+    /// 指令跟踪钩子（为 None 时主循环不做额外工作）
+    trace_hook: Option<Box<dyn TraceHook>>,
+    /// 本地方法实现
+    natives: NativeRegistry,
+    /// Java 程序的标准输出（System.out）
+    stdout: Box<dyn std::io::Write + Send>,
+    /// Java 程序的错误输出（线程中未捕获的异常）
+    stderr: Box<dyn std::io::Write + Send>,
+    /// 本次运行已执行的指令条数（`max_instructions` 计数）
+    instructions_executed: u64,
+    /// 执行到这条指令时检查运行时间和取消请求
+    next_check: u64,
+    /// 本次运行的截止时间（设置了 `time_limit` 时）
+    deadline: Option<Instant>,
+    /// 其他线程请求取消运行的标志
+    cancel: cancel::CancelHandle,
+    /// 性能剖析数据（为 None 时主循环不做额外工作）
+    profile: Option<profile::ProfileData>,
+    /// 断点（只在 `resume()` 时检查）
+    breakpoints: HashSet<stepping::Breakpoint>,
+    /// `start_method` 开始、尚未结束的单步执行
+    execution: Option<stepping::Execution>,
+    /// Java 程序启动的线程（见 `threads`）
+    threads: threads::Threads,
+    /// 创建解释器时给出的选项
+    options: InterpreterOptions,
 }
 
 impl Interpreter {
     /// 创建新的解释器
     pub fn new() -> Self {
+        Self::new_with_options(InterpreterOptions::default())
+    }
+
+    /// 按给定的选项创建解释器
+    pub fn new_with_options(options: InterpreterOptions) -> Self {
+        let mut metaspace = Metaspace::new();
+        metaspace.set_verify(options.verify);
+        metaspace.set_max_major_version(options.max_major_version);
+        let mut heap = Heap::new();
+        heap.set_gc_threshold(options.gc_threshold);
+        heap.set_max_objects(options.max_heap_objects);
+        options.gc_strategy.configure_heap(&mut heap);
+        Interpreter {
+            heap,
+            thread: JvmThread::with_max_frames(options.max_frames),
+            metaspace,
+            class_loader: None,
+            class_mirrors: HashMap::new(),
+            interned_strings: HashMap::new(),
+            boxed_values: HashMap::new(),
+            handles: handles::HostHandles::default(),
+            gc: GarbageCollector::with_strategy(options.gc_strategy),
+            field_watches: Vec::new(),
+            trace_hook: None,
+            natives: NativeRegistry::with_builtins(),
+            stdout: stdio::default_stdout(),
+            stderr: stdio::default_stderr(),
+            instructions_executed: 0,
+            next_check: 0,
+            deadline: None,
+            cancel: cancel::CancelHandle::default(),
+            profile: None,
+            breakpoints: HashSet::new(),
+            execution: None,
+            threads: threads::Threads::default(),
+            options,
+        }
+    }
+
+    /// 创建解释器时给出的选项
+    pub fn options(&self) -> &InterpreterOptions {
+        &self.options
+    }
+
+    /// 注册本地方法：调用 `class_name.method_name descriptor` 时执行 `f`
+    ///
+    /// 既可以实现用户类中声明为 `native` 的方法，也可以替换 JDK 方法（如 `java/lang/Math.max`）
+    pub fn register_native(
+        &mut self,
+        class_name: &str,
+        method_name: &str,
+        descriptor: &str,
+        f: NativeMethod,
+    ) {
+        self.natives.register(class_name, method_name, descriptor, f);
+    }
+
+    /// 已注册的本地方法
+    pub fn natives(&self) -> &NativeRegistry {
+        &self.natives
+    }
+
+    /// 替换 Java 程序的标准输出，返回原来的 writer
+    ///
+    /// 需要读取输出时传入 `stdio::SharedBuffer` 的一份克隆
+    pub fn set_stdout(&mut self, out: Box<dyn std::io::Write + Send>) -> Box<dyn std::io::Write + Send> {
+        std::mem::replace(&mut self.stdout, out)
+    }
+
+    /// Java 程序的标准输出，System.out 的所有打印都写到这里
+    pub fn stdout(&mut self) -> &mut dyn std::io::Write {
+        &mut *self.stdout
+    }
+
+    /// 替换 Java 程序的错误输出，返回原来的 writer
+    pub fn set_stderr(&mut self, err: Box<dyn std::io::Write + Send>) -> Box<dyn std::io::Write + Send> {
+        std::mem::replace(&mut self.stderr, err)
+    }
+
+    /// Java 程序的错误输出，线程中未捕获的异常报告写到这里
+    pub fn stderr(&mut self) -> &mut dyn std::io::Write {
+        &mut *self.stderr
+    }
+
+    /// 创建带类加载器的解释器
+    pub fn with_class_loader(class_loader: ClassLoader) -> Self {
         Interpreter {
-            heap: Heap::new(),
-            thread: JvmThread::new(),
-            metaspace: Metaspace::new(),
+            class_loader: Some(class_loader),
+            ..Self::new()
         }
     }
 
+    /// 给类加载器添加类路径（没有类加载器时创建一个）
+    ///
+    /// 之前找不到的类现在可能找得到了，所以清空否定解析缓存
+    pub fn add_class_path<P: AsRef<std::path::Path>>(&mut self, path: P) {
+        self.class_loader
+            .get_or_insert_with(|| ClassLoader::new(Vec::new()))
+            .add_class_path(path);
+        self.metaspace.clear_negative_resolutions();
+    }
+
+    /// 监视字段访问：执行 GETFIELD/PUTFIELD/GETSTATIC/PUTSTATIC 访问
+    /// `class_name.field_name`（按声明字段的类匹配）时调用 `callback`
+    ///
+    /// 类名可以用 `.` 或 `/` 分隔
+    pub fn set_field_watch<F>(&mut self, class_name: &str, field_name: &str, callback: F)
+    where
+        F: FnMut(&FieldAccessEvent) + 'static,
+    {
+        self.field_watches.push(FieldWatch {
+            class_name: class_name.replace('.', "/"),
+            field_name: field_name.to_string(),
+            callback: Box::new(callback),
+        });
+    }
+
+    /// 移除所有字段监视
+    pub fn clear_field_watches(&mut self) {
+        self.field_watches.clear();
+    }
+
+    /// 安装指令跟踪钩子：每执行完一条指令调用一次（替换之前的钩子）
+    pub fn set_trace_hook<H: TraceHook + 'static>(&mut self, hook: H) {
+        self.trace_hook = Some(Box::new(hook));
+    }
+
+    /// 打开或关闭打印到标准错误的指令跟踪
+    pub fn set_trace(&mut self, enabled: bool) {
+        self.trace_hook = if enabled {
+            Some(Box::new(PrintTrace))
+        } else {
+            None
+        };
+    }
+
+    /// 移除指令跟踪钩子
+    pub fn clear_trace_hook(&mut self) {
+        self.trace_hook = None;
+    }
+
+    /// 重置单次运行的状态，便于在同一个解释器上连续运行多个程序
+    ///
+    /// 清除的内容：
+    /// - 堆上的所有对象
+    /// - 线程栈和 PC（包括暂停中的单步执行）
+    /// - 所有类的静态字段（恢复为 ConstantValue 或默认值）
+    /// - 类初始化状态（Initializing/Initialized 回到 Linked）
+    /// - java/lang/Class 对象（它们在堆上）
+    /// - `pin` 得到的宿主句柄
+    ///
+    /// 保留的内容：
+    /// - 已加载的类元数据（方法、字段、字节码、常量池）
+    /// - 运行时常量池中已解析的符号引用缓存
+    /// - 类加载器
+    pub fn reset_run_state(&mut self) {
+        self.heap = Heap::new();
+        self.heap.set_gc_threshold(self.options.gc_threshold);
+        self.heap.set_max_objects(self.options.max_heap_objects);
+        self.options.gc_strategy.configure_heap(&mut self.heap);
+        self.thread = JvmThread::with_max_frames(self.options.max_frames);
+        self.execution = None;
+        self.threads = threads::Threads::default();
+        self.class_mirrors.clear();
+        self.interned_strings.clear();
+        self.boxed_values.clear();
+        self.handles.clear();
+        self.gc = GarbageCollector::with_strategy(self.options.gc_strategy);
+        self.metaspace.reset_run_state();
+    }
+
     /// 执行方法（带类名上下文）- 新版显式栈实现
     /// 返回方法的返回值（如果有）
+    ///
+    /// `method_key`（"name:descriptor"）给出栈帧的方法名和描述符；它是 `code` 所属的已加载方法时，
+    /// 复用方法的异常表和解码结果。手写的字节码不属于任何方法，键只用于栈轨迹和 span
     pub fn execute_method_with_class(
         &mut self,
         class_name: &str,
+        method_key: &str,
         code: &[u8],
         max_locals: usize,
         max_stack: usize,
     ) -> Result<Option<JvmValue>> {
         // 创建初始栈帧
-        let frame = Frame::new_with_context(
+        let mut frame = Frame::new_with_context(
             max_locals,
             max_stack,
             class_name.to_string(),
             code.to_vec(),
-            None, // 顶层方法没有返回地址
         );
 
-        // 压入栈帧到线程
-        self.thread.push_frame(frame);
-        self.thread.pc = 0;
+        let method = self
+            .metaspace
+            .get_class(class_name)
+            .ok()
+            .and_then(|class| class.methods.get(method_key))
+            .filter(|method| *method.code == *code);
+        if let Some(method) = method {
+            frame.method_name = method.name.clone();
+            frame.descriptor = method.descriptor.clone();
+            frame.exception_table = method.exception_table.clone();
+            frame.decoded = method.decoded.clone();
+        } else {
+            // 手写的字节码在这里解码；无法解码时逐字节执行
+            let (name, descriptor) = method_key.split_once(':').unwrap_or((method_key, ""));
+            frame.method_name = Symbol::new(name);
+            frame.descriptor = Symbol::new(descriptor);
+            frame.decoded = DecodedMethod::new(code).ok().map(Arc::new);
+        }
 
-        // 主执行循环：运行直到栈为空
-        let mut return_value = None;
-        while self.thread.stack_depth() > 0 {
-            // 获取当前字节码
-            let code = self.thread.current_code()?.to_vec();
-            let pc = self.thread.pc;
+        #[cfg(feature = "tracing")]
+        {
+            let (method_name, descriptor) = (frame.method_name.clone(), frame.descriptor.clone());
+            frame.enter_span(&method_name, &descriptor);
+        }
+
+        self.run_frame(frame)
+    }
+
+    /// 按方法键（"name:descriptor"）执行方法，参数依次放入局部变量表
+    ///
+    /// long/double 参数占两个槽位；实例方法的第一个参数是 `this`。
+    /// 参数个数与描述符不符时返回错误，不会执行方法
+    pub fn execute_method_with_args(
+        &mut self,
+        class_name: &str,
+        method_key: &str,
+        args: Vec<JvmValue>,
+    ) -> Result<Option<JvmValue>> {
+        let frame = self.method_frame(class_name, method_key, args)?;
+        self.run_frame(frame)
+    }
+
+    /// 为 `class_name` 的方法创建入口栈帧，参数放入局部变量表
+    fn method_frame(
+        &mut self,
+        class_name: &str,
+        method_key: &str,
+        args: Vec<JvmValue>,
+    ) -> Result<Frame> {
+        let method = self
+            .metaspace
+            .get_class(class_name)?
+            .methods
+            .get(method_key)
+            .cloned()
+            .ok_or_else(|| anyhow!("Method not found: {}.{}", class_name, method_key))?;
+        if method.is_abstract {
+            return Err(JavaException::abstract_method(class_name, &method.name, &method.descriptor).into());
+        }
+        let expected = MethodDescriptor::parse(&method.descriptor)?.params.len() + usize::from(!method.is_static);
+        if args.len() != expected {
+            return Err(anyhow!(
+                "{}.{}{} expects {} arguments, got {}",
+                class_name,
+                method.name,
+                method.descriptor,
+                expected,
+                args.len()
+            ));
+        }
+
+        let mut frame = Frame::new_with_context(
+            method.max_locals,
+            method.max_stack,
+            class_name.to_string(),
+            method.code.clone(),
+        );
+        frame.method_name = method.name.clone();
+        frame.descriptor = method.descriptor.clone();
+        frame.exception_table = method.exception_table.clone();
+        frame.decoded = method.decoded.clone();
+        let mut args = args.into_iter();
+        let start = if method.is_static {
+            0
+        } else {
+            frame.set_local(0, args.next().unwrap_or(JvmValue::Reference(None)))?;
+            1
+        };
+        Self::store_args(&mut frame, start, &method.descriptor, args.collect())?;
+        self.set_method_monitor(&mut frame, &method)?;
+        #[cfg(feature = "tracing")]
+        frame.enter_span(&method.name, &method.descriptor);
+        Ok(frame)
+    }
+
+    /// 运行 `public static void main(String[] args)`：命令行参数作为 String[] 放入 local 0
+    pub fn run_main(&mut self, class_name: &str, args: &[String]) -> Result<()> {
+        let args_array = self.new_string_array(args)?;
+        self.execute_method_with_args(
+            class_name,
+            "main:([Ljava/lang/String;)V",
+            vec![JvmValue::Reference(Some(args_array))],
+        )?;
+        Ok(())
+    }
+
+    /// 在堆上创建 String[]，元素是新分配的字符串
+    pub fn new_string_array(&mut self, values: &[String]) -> Result<ObjRef> {
+        let array = self.allocate_with(|heap| {
+            heap.allocate_reference_array("java/lang/String", values.len() as i32)
+        })?;
+        // 分配元素时数组还不在任何根中：登记为根，每次分配后重新取出（可能已被移动）
+        let (array, ()) = self.with_pinned(array, |interpreter, handle| {
+            for (i, value) in values.iter().enumerate() {
+                let string = interpreter.new_string(value)?;
+                let array = interpreter.resolve(handle)?;
+                interpreter
+                    .heap
+                    .array_set(array, i as i32, JvmValue::Reference(Some(string)))?;
+            }
+            Ok(())
+        })?;
+        Ok(array)
+    }
+
+    /// 调用静态方法：参数按描述符放入局部变量表（long/double 占两个槽位），执行到返回
+    ///
+    /// 参数需要是描述符对应的 JvmValue（boolean/char/byte/short 都用 Int），
+    /// 需要自动转换时使用 `invoke_static_typed`
+    pub fn invoke_static(
+        &mut self,
+        class_name: &str,
+        method_name: &str,
+        descriptor: &str,
+        args: Vec<JvmValue>,
+    ) -> Result<Option<JvmValue>> {
+        // 类本身必须已加载；方法可以继承自父类
+        self.metaspace.get_class(class_name)?;
+        let (owner, method) =
+            self.metaspace
+                .resolve_method_in_hierarchy(class_name, method_name, descriptor)?;
+        if !method.is_static {
+            return Err(anyhow!(
+                "{}.{}{} is not a static method",
+                class_name,
+                method_name,
+                descriptor
+            ));
+        }
+        if method.is_native {
+            return self.call_native(&owner, method_name, descriptor, &args);
+        }
+        let method_key = format!("{}:{}", method_name, descriptor);
+        self.execute_method_with_args(&owner, &method_key, args)
+    }
+
+    /// 创建对象并运行构造器，相当于 Java 的 `new C(args)`，返回新对象
+    ///
+    /// 返回的引用不是 GC 根，只在下一次 GC 之前有效；需要长期持有时用 `pin` 登记。
+    /// 类按需加载，构造器运行前先完成类初始化（`<clinit>`）。
+    /// 构造器不继承：`<init>` 只在 `class_name` 自身中查找，找不到时抛出 NoSuchMethodError。
+    /// JDK 类使用内置的构造器实现
+    pub fn instantiate(
+        &mut self,
+        class_name: &str,
+        descriptor: &str,
+        mut args: Vec<JvmValue>,
+    ) -> Result<ObjRef> {
+        self.check_instantiable(class_name)?;
+        let method_key = format!("<init>:{}", descriptor);
+        let native_owner = if self.metaspace.is_class_loaded(class_name) {
+            if !self.metaspace.get_class(class_name)?.methods.contains_key(method_key.as_str()) {
+                return Err(JavaException::new(
+                    "java/lang/NoSuchMethodError",
+                    format!(
+                        "{}.<init>{} (constructors are not inherited)",
+                        class_name, descriptor
+                    ),
+                )
+                .into());
+            }
+            None
+        } else {
+            let owner = self
+                .natives
+                .find_jdk_owner(class_name, "<init>", descriptor)
+                .ok_or_else(|| Self::unsatisfied_link(class_name, "<init>", descriptor))?;
+            Some(owner)
+        };
+
+        // 分配可能触发 GC：参数中的引用在分配期间登记为根
+        let object = self.with_roots(&mut args, |i| i.allocate_object(class_name.into()))?;
+        // 构造器中的分配同样可能触发 GC：新对象登记为根，整理移动它之后从句柄表读回
+        let (object, _) = self.with_pinned(object, |interpreter, _| {
+            let args: Vec<_> = std::iter::once(JvmValue::Reference(Some(object)))
+                .chain(args)
+                .collect();
+            match native_owner {
+                Some(owner) => interpreter.call_native(&owner, "<init>", descriptor, &args),
+                None => interpreter.execute_method_with_args(class_name, &method_key, args),
+            }
+        })?;
+        Ok(object)
+    }
 
-            if pc >= code.len() {
-                return Err(anyhow!("PC out of bounds: {} >= {}", pc, code.len()));
+    /// 调用实例方法：按 `object` 的运行时类型做虚方法分派，与 invokevirtual 相同
+    ///
+    /// 用户类没有覆盖的 JDK 方法（如 `hashCode`）使用内置实现。
+    /// 参数不包括 `this`，返回值为 None 表示 void
+    pub fn invoke_virtual(
+        &mut self,
+        object: ObjRef,
+        method_name: &str,
+        descriptor: &str,
+        args: Vec<JvmValue>,
+    ) -> Result<Option<JvmValue>> {
+        let runtime_class = self.heap.get(object)?.class_name.clone();
+        let args: Vec<_> = std::iter::once(JvmValue::Reference(Some(object)))
+            .chain(args)
+            .collect();
+        let user_method = if self.metaspace.is_class_loaded(&runtime_class) {
+            Some(
+                self.metaspace
+                    .resolve_virtual_method(&runtime_class, method_name, descriptor),
+            )
+        } else {
+            None
+        };
+        match user_method {
+            Some(Ok((owner, method))) => {
+                if method.is_static {
+                    return Err(anyhow!(
+                        "{}.{}{} is a static method",
+                        owner,
+                        method_name,
+                        descriptor
+                    ));
+                }
+                if method.is_native {
+                    return self.call_native(&owner, method_name, descriptor, &args);
+                }
+                let method_key = format!("{}:{}", method_name, descriptor);
+                self.execute_method_with_args(&owner, &method_key, args)
+            }
+            other => {
+                let ancestor = self.first_unloaded_ancestor(&runtime_class);
+                match self.natives.find_jdk_owner(&ancestor, method_name, descriptor) {
+                    Some(owner) => self.call_native(&owner, method_name, descriptor, &args),
+                    None => Err(match other {
+                        Some(Err(e)) => e,
+                        _ => Self::unsatisfied_link(&runtime_class, method_name, descriptor).into(),
+                    }),
+                }
             }
+        }
+    }
 
-            let opcode = code[pc];
-            let control = self.execute_instruction_explicit(opcode)?;
+    /// 以 `frame` 为顶层栈帧运行，直到它返回
+    ///
+    /// 出错时弹出本次运行压入的栈帧，线程栈恢复到运行之前的样子
+    fn run_frame(&mut self, frame: Frame) -> Result<Option<JvmValue>> {
+        let base_depth = self.thread.stack_depth();
+        let caller_stack = match base_depth {
+            0 => 0,
+            _ => self.thread.current_frame()?.stack_size(),
+        };
+        let result = self.enter_frame(frame).and_then(|()| {
+            // 最外层的运行调度 Java 程序启动的线程
+            if base_depth == 0 {
+                return self.run_threads();
+            }
+            // 嵌套的运行（如本地方法回调 Java）：运行直到栈回到调用前的深度
+            while self.thread.stack_depth() > base_depth {
+                // 嵌套的运行不调度其他线程，等不到被其他线程持有的监视器
+                if self.threads.is_blocked() {
+                    return Err(anyhow!(
+                        "deadlock: a monitor needed by a nested call is held by another thread"
+                    ));
+                }
+                self.step_instruction()?;
+            }
+            // 被调用者返回时把返回值压入了调用者的操作数栈，在这里取回
+            let caller = self.thread.current_frame_mut()?;
+            if caller.stack_size() > caller_stack {
+                Ok(Some(caller.pop()?))
+            } else {
+                Ok(None)
+            }
+        });
+        if result.is_err() {
+            self.thread.unwind_to(base_depth);
+        }
+        result
+    }
 
-            match control {
-                InstructionControl::Continue => {}
-                InstructionControl::Return(val) => {
-                    // 方法返回
-                    return_value = val;
-                    break;
+    /// 压入入口栈帧；执行入口方法是对所属类的主动使用，先运行 <clinit>
+    ///
+    /// 每次从入口方法开始执行都重新计算指令条数和截止时间
+    fn enter_frame(&mut self, frame: Frame) -> Result<()> {
+        // 只有最外层的调用开始新的计数，嵌套调用（如本地方法回调 Java）共用一个预算
+        if self.thread.stack_depth() == 0 {
+            self.instructions_executed = 0;
+            self.next_check = 0;
+            self.deadline = self.options.time_limit.map(|limit| Instant::now() + limit);
+            self.threads.clear();
+            self.heap.release_monitors();
+        }
+        let class_name = frame.class_name.clone();
+        self.thread.push_frame(frame)?;
+        self.enter_method_monitor()?;
+        self.initialize_class(&class_name, 0)?;
+        Ok(())
+    }
+
+    /// 执行栈顶栈帧的一条指令
+    fn step_instruction(&mut self) -> Result<InstructionControl> {
+        self.check_execution_limits()?;
+        let frame = self.thread.current_frame()?;
+        let pc = frame.pc;
+        let opcode = *frame
+            .code
+            .get(pc)
+            .ok_or_else(|| anyhow!("PC out of bounds: {} >= {}", pc, frame.code.len()))?;
+        jvm_trace!(
+            "pc={} opcode={}",
+            pc,
+            instructions::get_instruction_name(opcode)
+        );
+        if self.profile.is_some() {
+            self.record_profile(opcode)?;
+        }
+        let pending = match self.trace_hook {
+            Some(_) => Some(self.begin_trace(opcode)?),
+            None => None,
+        };
+        // 只涉及当前栈帧的指令直接在栈帧上执行，其余的走完整的分派
+        let result = match frame_ops::execute(self.thread.current_frame_mut()?, opcode) {
+            Ok(true) => Ok(InstructionControl::Continue),
+            Ok(false) => self.execute_instruction_explicit(opcode),
+            Err(e) => Err(e),
+        };
+        let control = match result {
+            Ok(control) => control,
+            // JVM 抛出的异常：创建异常对象，和 athrow 一样查找处理器
+            Err(e) => match e.downcast::<JavaException>() {
+                Ok(exception) => {
+                    let ptr = self.allocate_exception(&exception)?;
+                    self.throw_exception(ptr)?;
+                    InstructionControl::Continue
+                }
+                // 其他错误：附上出错时的栈轨迹（未捕获的异常在 throw_exception 中已经附上）
+                Err(e) if e.is::<ExecutionError>() || e.is::<UnknownOpcodeReport>() => return Err(e),
+                Err(e) => {
+                    return Err(ExecutionError {
+                        cause: e,
+                        frames: self.stack_trace(),
+                    }
+                    .into())
+                }
+            },
+        };
+        if let Some(pending) = pending {
+            self.finish_trace(pending);
+        }
+        Ok(control)
+    }
+
+    /// 本次运行已执行的指令条数
+    pub fn instructions_executed(&self) -> u64 {
+        self.instructions_executed
+    }
+
+    /// 执行下一条指令之前检查指令条数和时间限制
+    fn check_execution_limits(&mut self) -> Result<()> {
+        // 用下一次检查的位置代替取模，热循环里每条指令只多一次比较
+        let periodic = self.instructions_executed >= self.next_check;
+        if periodic {
+            self.next_check = self.instructions_executed + self.options.check_interval.max(1);
+            if self.cancel.take() {
+                return Err(ExecutionCancelled {
+                    instructions: self.instructions_executed,
+                    frames: self.stack_trace(),
                 }
+                .into());
+            }
+        }
+        let limit = match (self.options.max_instructions, self.deadline) {
+            (Some(max), _) if self.instructions_executed >= max => ExecutionLimit::Instructions(max),
+            (_, Some(deadline)) if periodic && Instant::now() >= deadline => {
+                ExecutionLimit::Time(self.options.time_limit.unwrap_or_default())
             }
+            _ => {
+                self.instructions_executed += 1;
+                return Ok(());
+            }
+        };
+        let location = self.trace_frame(self.thread.current_frame()?, true);
+        Err(ExecutionLimitExceeded {
+            limit,
+            instructions: self.instructions_executed,
+            location,
         }
+        .into())
+    }
 
-        Ok(return_value)
+    /// 取消句柄：在其他线程上调用 `cancel()` 会让正在进行的运行返回 `ExecutionCancelled`
+    pub fn cancellation_token(&self) -> cancel::CancelHandle {
+        self.cancel.clone()
+    }
+
+    /// 栈帧当前位置的 Java 风格描述，如 `com.example.Foo.bar(Foo.java:12)`
+    ///
+    /// 行号取行号表中 start_pc 不超过 pc 的最后一项；
+    /// 没有 SourceFile 属性时写 `Unknown Source`，没有行号表时只写文件名
+    pub fn frame_location(&self, frame: &Frame) -> String {
+        let info = self.frame_info(frame, frame.pc);
+        let source = match (info.source_file, info.line) {
+            (Some(file), Some(line)) => format!("{}:{}", file, line),
+            (Some(file), None) => file,
+            (None, _) => "Unknown Source".to_string(),
+        };
+        format!(
+            "{}.{}({})",
+            frame.class_name.replace('/', "."),
+            frame.method_name,
+            source
+        )
+    }
+
+    /// 当前线程的栈轨迹，栈顶（正在执行的方法）在前
+    pub fn stack_trace(&self) -> Vec<FrameInfo> {
+        self.thread
+            .frames()
+            .enumerate()
+            .map(|(i, frame)| self.trace_frame(frame, i == 0))
+            .collect()
+    }
+
+    /// 栈顶栈帧的 pc 是出错的指令；调用者的 pc 在调用指令之后，
+    /// 减 1 落在调用指令内部，按它查行号
+    fn trace_frame(&self, frame: &Frame, is_top: bool) -> FrameInfo {
+        let line_pc = if is_top { frame.pc } else { frame.pc.saturating_sub(1) };
+        self.frame_info(frame, line_pc)
+    }
+
+    /// 栈帧的类、方法和源代码位置；行号按 `line_pc` 查找
+    fn frame_info(&self, frame: &Frame, line_pc: usize) -> FrameInfo {
+        let class = self.metaspace.get_class(&frame.class_name).ok();
+        let line = class
+            .and_then(|class| {
+                class
+                    .methods
+                    .get(method_key(&frame.method_name, &frame.descriptor).as_str())
+            })
+            .and_then(|method| method.line_number_at(line_pc));
+        FrameInfo {
+            source_file: class.and_then(|class| class.source_file.clone()),
+            line,
+            ..frame.info()
+        }
+    }
+
+    /// 记录指令执行前的状态（仅在安装了跟踪钩子时调用）
+    fn begin_trace(&self, opcode: u8) -> Result<PendingTrace> {
+        let frame = self.thread.current_frame()?;
+        Ok(PendingTrace {
+            depth: self.thread.stack_depth(),
+            class_name: frame.class_name.clone(),
+            method_name: frame.method_name.clone(),
+            pc: frame.pc,
+            opcode,
+            locals: frame.locals().to_vec(),
+        })
     }
 
-    /// 执行单条指令 - 显式栈版本（使用线程级PC）
+    /// 指令执行完毕：和执行前的状态比较，通知跟踪钩子
+    fn finish_trace(&mut self, pending: PendingTrace) {
+        // 调用指令压入了新栈帧、返回指令弹出了栈帧，所以按深度找回执行指令的栈帧
+        let frame = self.thread.frames().rev().nth(pending.depth - 1);
+        let (stack_top, stack_size, changed_locals) = match frame {
+            Some(frame) => {
+                let stack = frame.operands();
+                let top = stack[stack.len().saturating_sub(trace::TRACE_STACK_VALUES)..].to_vec();
+                let locals = frame.locals();
+                // long/double 第二个槽位里的占位值不算被改写
+                let changed = locals
+                    .iter()
+                    .zip(&pending.locals)
+                    .enumerate()
+                    .filter(|(slot, (after, before))| {
+                        let second_half = *slot > 0 && locals[slot - 1].is_wide();
+                        !second_half && !trace::same_value(after, before)
+                    })
+                    .map(|(slot, (after, _))| (slot, after.clone()))
+                    .collect();
+                (top, stack.len(), changed)
+            }
+            None => (Vec::new(), 0, Vec::new()),
+        };
+        let event = TraceEvent {
+            depth: pending.depth,
+            class_name: pending.class_name.into(),
+            method_name: pending.method_name.into(),
+            pc: pending.pc,
+            opcode: pending.opcode,
+            mnemonic: instructions::get_instruction_name(pending.opcode),
+            stack_top,
+            stack_size,
+            changed_locals,
+        };
+        if let Some(hook) = self.trace_hook.as_mut() {
+            hook.on_instruction(&event);
+        }
+    }
+
+    /// 执行单条指令 - 显式栈版本（使用当前栈帧的 pc）
     fn execute_instruction_explicit(&mut self, opcode: u8) -> Result<InstructionControl> {
         use instructions::opcodes::*;
 
-        // 克隆需要的数据以避免借用冲突
-        let code = self.thread.current_code()?.to_vec();
-        let pc = self.thread.pc;
+        // 字节码和类名都是共享的，克隆只增加引用计数
+        let code = Arc::clone(&self.thread.current_frame()?.code);
+        let pc = self.thread.current_frame()?.pc;
         let class_name = self.thread.current_frame()?.class_name.clone();
 
         match opcode {
             NOP => {
-                self.thread.pc += 1;
+                self.thread.current_frame_mut()?.pc += 1;
             }
             NEW => {
-                let class_index = u16::from_be_bytes([code[pc + 1], code[pc + 2]]);
+                let class_index = Self::read_u16(&code, pc)?;
                 // 使用 ClassMetadata 的 resolve_class_ref
                 let target_class_name = {
                     let class_meta: &mut crate::runtime::ClassMetadata =
                         self.metaspace.get_class_mut(&class_name)?;
                     class_meta.resolve_class_ref(class_index)?
                 };
-                let ptr = self.heap.allocate(target_class_name);
+                if !target_class_name.starts_with('[') {
+                    self.resolve_class_at(&class_name, class_index, &target_class_name)?;
+                }
+                self.check_instantiable(&target_class_name)?;
+                if self.initialize_class(&target_class_name, pc)? {
+                    return Ok(InstructionControl::Continue);
+                }
+                let ptr = self.allocate_object(target_class_name)?;
                 self.thread
                     .current_frame_mut()?
-                    .push(JvmValue::Reference(Some(ptr)));
-                self.thread.pc += 3;
+                    .push(JvmValue::Reference(Some(ptr)))?;
+                self.thread.current_frame_mut()?.pc += 3;
             }
             PUTFIELD => {
-                let field_index = u16::from_be_bytes([code[pc + 1], code[pc + 2]]);
+                let field_index = Self::read_u16(&code, pc)?;
                 let class_meta: &mut crate::runtime::ClassMetadata =
                     self.metaspace.get_class_mut(&class_name)?;
                 let field_ref = class_meta.resolve_field_ref(field_index)?;
-                let value = self.thread.current_frame_mut()?.pop()?;
+                let field_ref = self.declaring_field_ref(&class_name, field_index, field_ref)?;
+                self.check_final_field_write(&field_ref, false)?;
+                let value = self.pop_field_value(&field_ref)?;
                 let obj_ref = self
                     .thread
                     .current_frame_mut()?
                     .pop_ref()?
-                    .ok_or(anyhow!("invalid ref"))?;
-                self.heap
-                    .set_field(obj_ref, field_ref.field_name.clone(), value)?;
-                self.thread.pc += 3;
+                    .ok_or_else(|| {
+                        JavaException::null_pointer(format!(
+                            "Cannot assign field \"{}\" because value is null",
+                            field_ref.field_name
+                        ))
+                    })?;
+                if !self.field_watches.is_empty() {
+                    let old_value = self
+                        .heap
+                        .get_field(obj_ref, &field_ref.class_name, &field_ref.field_name)
+                        .unwrap_or_else(|_| jvm_default_value(&field_ref.field_type));
+                    self.notify_field_access(
+                        FieldAccessKind::Write,
+                        &field_ref,
+                        Some(obj_ref),
+                        old_value,
+                        Some(value.clone()),
+                        pc,
+                    )?;
+                }
+                match field_ref.slot {
+                    Some(slot) => self.heap.set_field_at(
+                        obj_ref,
+                        slot,
+                        &field_ref.class_name,
+                        &field_ref.field_name,
+                        value,
+                    )?,
+                    None => self
+                        .heap
+                        .set_field(obj_ref, &field_ref.class_name, &field_ref.field_name, value)?,
+                }
+                self.thread.current_frame_mut()?.pc += 3;
             }
             GETFIELD => {
-                let field_index: u16 = u16::from_be_bytes([code[pc + 1], code[pc + 2]]);
+                let field_index: u16 = Self::read_u16(&code, pc)?;
                 let class_meta: &mut crate::runtime::ClassMetadata =
                     self.metaspace.get_class_mut(&class_name)?;
                 let field_ref = class_meta.resolve_field_ref(field_index)?;
+                let field_ref = self.declaring_field_ref(&class_name, field_index, field_ref)?;
                 let obj_ref = self
                     .thread
                     .current_frame_mut()?
                     .pop_ref()?
-                    .ok_or(anyhow!("invalid ref"))?;
-                let val = self.heap.get_field(obj_ref, &field_ref.field_name)?;
-                self.thread.current_frame_mut()?.push(val.clone());
-                self.thread.pc += 3;
+                    .ok_or_else(|| {
+                        JavaException::null_pointer(format!(
+                            "Cannot read field \"{}\" because value is null",
+                            field_ref.field_name
+                        ))
+                    })?;
+                let val = match field_ref.slot {
+                    Some(slot) => self.heap.get_field_at(
+                        obj_ref,
+                        slot,
+                        &field_ref.class_name,
+                        &field_ref.field_name,
+                    )?,
+                    None => self
+                        .heap
+                        .get_field(obj_ref, &field_ref.class_name, &field_ref.field_name)?,
+                };
+                if !self.field_watches.is_empty() {
+                    self.notify_field_access(
+                        FieldAccessKind::Read,
+                        &field_ref,
+                        Some(obj_ref),
+                        val.clone(),
+                        None,
+                        pc,
+                    )?;
+                }
+                self.thread.current_frame_mut()?.push(val.clone())?;
+                self.thread.current_frame_mut()?.pc += 3;
+            }
+
+            // ==================== 数组指令 ====================
+            // newarray <atype>: 弹出长度，创建基本类型数组
+            NEWARRAY => {
+                let element_type = ArrayType::from_atype(Self::read_u8(&code, pc, 1)?)?;
+                let length = self.thread.current_frame_mut()?.pop_int()?;
+                let ptr = self.allocate_with(|heap| heap.allocate_array(element_type, length))?;
+                self.thread
+                    .current_frame_mut()?
+                    .push(JvmValue::Reference(Some(ptr)))?;
+                self.thread.current_frame_mut()?.pc += 2;
+            }
+
+            // anewarray #index: 组件类型来自常量池的类引用
+            ANEWARRAY => {
+                let class_index = Self::read_u16(&code, pc)?;
+                let component = {
+                    let class_meta = self.metaspace.get_class_mut(&class_name)?;
+                    class_meta.resolve_class_ref(class_index)?
+                };
+                let length = self.thread.current_frame_mut()?.pop_int()?;
+                let ptr =
+                    self.allocate_with(|heap| heap.allocate_reference_array(&component, length))?;
+                self.thread
+                    .current_frame_mut()?
+                    .push(JvmValue::Reference(Some(ptr)))?;
+                self.thread.current_frame_mut()?.pc += 3;
+            }
+
+            // multianewarray #index <dimensions>: 类引用是完整的数组描述符（如 "[[I"）
+            MULTIANEWARRAY => {
+                let class_index = Self::read_u16(&code, pc)?;
+                let dimensions = Self::read_u8(&code, pc, 3)? as usize;
+                let descriptor = {
+                    let class_meta = self.metaspace.get_class_mut(&class_name)?;
+                    class_meta.resolve_class_ref(class_index)?
+                };
+                // 栈上依次是 count1, count2, ...，最后一维在栈顶
+                let mut counts = Vec::with_capacity(dimensions);
+                for _ in 0..dimensions {
+                    counts.push(self.thread.current_frame_mut()?.pop_int()?);
+                }
+                counts.reverse();
+                let ptr =
+                    self.allocate_with(|heap| heap.allocate_multi_array(&descriptor, &counts))?;
+                self.thread
+                    .current_frame_mut()?
+                    .push(JvmValue::Reference(Some(ptr)))?;
+                self.thread.current_frame_mut()?.pc += 4;
+            }
+
+            ARRAYLENGTH => {
+                let array_ref = self.pop_non_null_ref()?;
+                let length = self.heap.array_length(array_ref)?;
+                self.thread
+                    .current_frame_mut()?
+                    .push(JvmValue::Int(length as i32))?;
+                self.thread.current_frame_mut()?.pc += 1;
+            }
+
+            // xaload: ..., arrayref, index → ..., value
+            IALOAD | LALOAD | FALOAD | DALOAD | AALOAD | BALOAD | CALOAD | SALOAD => {
+                let index = self.thread.current_frame_mut()?.pop_int()?;
+                let array_ref = self.pop_non_null_ref()?;
+                let value = self.heap.array_get(array_ref, index)?;
+                self.thread.current_frame_mut()?.push(value)?;
+                self.thread.current_frame_mut()?.pc += 1;
+            }
+
+            // xastore: ..., arrayref, index, value → ...
+            IASTORE | LASTORE | FASTORE | DASTORE | AASTORE | BASTORE | CASTORE | SASTORE => {
+                let value = self.thread.current_frame_mut()?.pop()?;
+                let index = self.thread.current_frame_mut()?.pop_int()?;
+                let array_ref = self.pop_non_null_ref()?;
+                if opcode == AASTORE {
+                    self.check_array_store(array_ref, index, &value)?;
+                }
+                self.heap.array_set(array_ref, index, value)?;
+                self.thread.current_frame_mut()?.pc += 1;
             }
 
             INVOKESPECIAL => {
-                let method_index: u16 = u16::from_be_bytes([code[pc + 1], code[pc + 2]]);
+                let method_index: u16 = Self::read_u16(&code, pc)?;
+                self.check_negative_resolution(&class_name, method_index)?;
                 let class_meta: &mut crate::runtime::ClassMetadata =
                     self.metaspace.get_class_mut(&class_name)?;
                 let method_ref = class_meta.resolve_method_ref(method_index)?;
-                // 2. 检查目标类是否已加载
-                // 作弊版：跳过 java.* 系统类检查
+                // 2. 确保目标类已加载（按需通过类加载器加载）
+                // 作弊版：java.* 系统类不加载
                 let is_system_class = method_ref.class_name.starts_with("java/");
-                if !is_system_class && !self.metaspace.is_class_loaded(&method_ref.class_name) {
-                    return Err(anyhow!(
-                        "Class {} not loaded. Please load it first using interpreter.load_class()",
-                        method_ref.class_name
-                    ));
-                }
+                self.resolve_class_at(&class_name, method_index, &method_ref.class_name)?;
 
-                // 3. 查找目标方法（如果是系统类，跳过）
+                // 3. 系统类方法使用内置实现（如 super() 调用 Object.<init>）
                 if is_system_class {
-                    // 系统类方法调用：假装调用成功，什么都不做
-                    // 这适用于 super() 调用 Object.<init>
-                    self.thread.pc += 3;
+                    let owner = self.resolve_jdk_method(&class_name, method_index, &method_ref)?;
+                    let mut args = self.pop_args(method_ref.arg_count)?;
+                    args.insert(0, self.thread.current_frame_mut()?.pop()?);
+                    self.invoke_native_at(
+                        &owner,
+                        &method_ref.method_name,
+                        &method_ref.descriptor,
+                        args,
+                        pc + 3,
+                    )?;
                     return Ok(InstructionControl::Continue);
                 }
 
-                // 4. 查找目标方法（用户类）
-                let target_class = self.metaspace.get_class(&method_ref.class_name)?;
-                let method_key = format!("{}:{}", method_ref.method_name, method_ref.descriptor);
-                let method = target_class
-                    .methods
-                    .get(&method_key)
-                    .ok_or_else(|| {
-                        anyhow!("Method not found: {}.{}", method_ref.class_name, method_key)
-                    })?
-                    .clone();
+                // 4. 查找目标方法（用户类），super.m() 的 m 可能声明在更上层的父类
+                let (owner, method) = self.metaspace.resolve_method_in_hierarchy(
+                    &method_ref.class_name,
+                    &method_ref.method_name,
+                    &method_ref.descriptor,
+                )?;
+                self.check_member_access(&class_name, &owner, method.access_flags, || {
+                    format!("method {}.{}{}", owner, method.name, method.descriptor)
+                })?;
                 // 4. 从操作数栈弹出参数
-                let arg_count = Self::parse_arg_count(&method.descriptor);
+                let arg_count = method_ref.arg_count;
                 let mut args: Vec<JvmValue> = Vec::new();
                 for _ in 0..arg_count {
                     args.push(self.thread.current_frame_mut()?.pop()?);
@@ -202,493 +1128,1263 @@ impl Interpreter {
                 args.reverse(); // 栈是LIFO，需要反转
                                 // 5. ⭐ 关键区别：弹出 objectref (this 引用)
                 let objectref = self.thread.current_frame_mut()?.pop()?;
+                if method.is_native {
+                    args.insert(0, objectref);
+                    self.invoke_native_at(&owner, &method.name, &method.descriptor, args, pc + 3)?;
+                    return Ok(InstructionControl::Continue);
+                }
+                if method.is_abstract {
+                    return Err(
+                        JavaException::abstract_method(&owner, &method.name, &method.descriptor).into(),
+                    );
+                }
 
                 // 6. 创建新栈帧并设置参数
                 let mut new_frame = Frame::new_with_context(
                     method.max_locals,
                     method.max_stack,
-                    method_ref.class_name.clone(),
+                    owner,
                     method.code.clone(),
-                    Some(pc + 3), // 返回地址
                 );
+                new_frame.decoded = method.decoded.clone();
+                new_frame.exception_table = method.exception_table.clone();
+                new_frame.method_name = method.name.clone();
+                new_frame.descriptor = method.descriptor.clone();
 
                 // 7. ⭐ 关键区别：设置 this (local[0])
                 new_frame.set_local(0, objectref)?;
-                // 8. 设置参数（从 local[1] 开始）
-                for (i, arg) in args.into_iter().enumerate() {
-                    new_frame.set_local(i + 1, arg)?; // ← 注意：i+1，因为 local[0] 是 this
-                }
-                // 9. 压入新栈帧到线程栈
-                self.thread.push_frame(new_frame);
-                // 10. 设置PC为0，开始执行被调用方法
-                self.thread.pc = 0;
-            }
-            DUP => {
-                let value = self.thread.current_frame_mut()?.pop()?;
-                self.thread.current_frame_mut()?.push(value.clone());
-                self.thread.current_frame_mut()?.push(value);
-                self.thread.pc += 1;
+                // 8. 设置参数（从 local[1] 开始，因为 local[0] 是 this）
+                Self::store_args(&mut new_frame, 1, &method.descriptor, args)?;
+                #[cfg(feature = "tracing")]
+                new_frame.enter_span(&method.name, &method.descriptor);
+                self.set_method_monitor(&mut new_frame, &method)?;
+                // 9. 调用者从 invokespecial 之后继续，新栈帧从 pc 0 开始执行
+                self.thread.push_callee(new_frame, pc + 3)?;
+                self.enter_method_monitor()?;
+            }
+            // ldc: 1字节常量池索引；ldc_w / ldc2_w: 2字节索引
+            LDC => {
+                let index = Self::read_u8(&code, pc, 1)? as u16;
+                let value = self.load_constant(&class_name, index)?;
+                self.thread.current_frame_mut()?.push(value)?;
+                self.thread.current_frame_mut()?.pc += 2;
+            }
+            LDC_W | LDC2_W => {
+                let index = Self::read_u16(&code, pc)?;
+                let value = self.load_constant(&class_name, index)?;
+                self.thread.current_frame_mut()?.push(value)?;
+                self.thread.current_frame_mut()?.pc += 3;
             }
 
-            // ==================== 常量指令 ====================
-            ICONST_M1 => {
-                self.thread.current_frame_mut()?.push(JvmValue::Int(-1));
-                self.thread.pc += 1;
-            }
-            ICONST_0 => {
-                self.thread.current_frame_mut()?.push(JvmValue::Int(0));
-                self.thread.pc += 1;
-            }
-            ICONST_1 => {
-                self.thread.current_frame_mut()?.push(JvmValue::Int(1));
-                self.thread.pc += 1;
-            }
-            ICONST_2 => {
-                self.thread.current_frame_mut()?.push(JvmValue::Int(2));
-                self.thread.pc += 1;
+            // ==================== 方法调用指令 ====================
+            INVOKESTATIC => {
+                let index = Self::read_u16(&code, pc)?;
+                // 已链接的调用点：直接使用缓存的方法，不再解析、查找
+                if let Some(site) = self.metaspace.static_call_site(&class_name, index) {
+                    if self.initialize_class(&site.owner, pc)? {
+                        return Ok(InstructionControl::Continue);
+                    }
+                    self.push_static_call(&site, pc + 3)?;
+                    return Ok(InstructionControl::Continue);
+                }
+                self.check_negative_resolution(&class_name, index)?;
+
+                // 1. 解析方法引用
+                let method_ref = {
+                    let class_meta = self.metaspace.get_class_mut(&class_name)?;
+                    class_meta.resolve_method_ref(index)?
+                };
+
+                // 2. 确保类已加载（按需通过类加载器加载）
+                // 作弊版：java.* 系统类不加载
+                let is_system_class = method_ref.class_name.starts_with("java/");
+                self.resolve_class_at(&class_name, index, &method_ref.class_name)?;
+
+                // 3. 查找目标方法（系统类使用内置实现）
+                if self.invoke_builtin(&method_ref, pc + 3)? {
+                    return Ok(InstructionControl::Continue);
+                }
+                if is_system_class {
+                    // 系统类静态方法使用内置实现；没有实现时的异常记入否定缓存
+                    let owner = self.resolve_jdk_method(&class_name, index, &method_ref)?;
+                    let args = self.pop_args(method_ref.arg_count)?;
+                    self.invoke_native_at(
+                        &owner,
+                        &method_ref.method_name,
+                        &method_ref.descriptor,
+                        args,
+                        pc + 3,
+                    )?;
+                    return Ok(InstructionControl::Continue);
+                }
+
+                // 4. 查找目标方法（用户类），可能继承自父类；初始化的是声明方法的类
+                let (owner, method) = self.metaspace.resolve_method_in_hierarchy(
+                    &method_ref.class_name,
+                    &method_ref.method_name,
+                    &method_ref.descriptor,
+                )?;
+                self.check_member_access(&class_name, &owner, method.access_flags, || {
+                    format!("method {}.{}{}", owner, method.name, method.descriptor)
+                })?;
+                if self.initialize_class(&owner, pc)? {
+                    return Ok(InstructionControl::Continue);
+                }
+
+                // 5. 本地方法：从操作数栈弹出参数直接调用
+                if method.is_native {
+                    let args = self.pop_args(method_ref.arg_count)?;
+                    self.invoke_native_at(&owner, &method.name, &method.descriptor, args, pc + 3)?;
+                    return Ok(InstructionControl::Continue);
+                }
+
+                // 6. 链接调用点，之后执行这条指令时跳过 1-4 步
+                let site = StaticCallSite {
+                    arg_count: method_ref.arg_count,
+                    method_ref,
+                    owner,
+                    method,
+                };
+                self.push_static_call(&site, pc + 3)?;
+                self.metaspace.cache_static_call_site(&class_name, index, site)?;
             }
-            ICONST_3 => {
-                self.thread.current_frame_mut()?.push(JvmValue::Int(3));
-                self.thread.pc += 1;
+
+            // ==================== 字段访问指令 (作弊版调试支持) ====================
+            GETSTATIC => {
+                // 格式: getstatic #index
+                let index = Self::read_u16(&code, pc)?;
+                self.check_negative_resolution(&class_name, index)?;
+                let field_ref = {
+                    let class_meta = self.metaspace.get_class_mut(&class_name)?;
+                    class_meta.resolve_field_ref(index)?
+                };
+                self.resolve_class_at(&class_name, index, &field_ref.class_name)?;
+                // 静态字段存放在声明它的类中，初始化的也是声明类
+                let field_ref = self.declaring_field_ref(&class_name, index, field_ref)?;
+                if self.initialize_class(&field_ref.class_name, pc)? {
+                    return Ok(InstructionControl::Continue);
+                }
+
+                let value = if field_ref.class_name.starts_with("java/") {
+                    // JDK 类没有加载，静态字段由内置类库提供（如 System.out）
+                    self.jdk_static_field(&field_ref)?
+                } else {
+                    // 从所属类的静态字段表读取，未赋值时取默认值
+                    let owner = self.metaspace.get_class(&field_ref.class_name)?;
+                    owner
+                        .static_fields
+                        .get(&field_ref.field_name)
+                        .cloned()
+                        .unwrap_or_else(|| jvm_default_value(&field_ref.field_type))
+                };
+                if !self.field_watches.is_empty() {
+                    self.notify_field_access(
+                        FieldAccessKind::Read,
+                        &field_ref,
+                        None,
+                        value.clone(),
+                        None,
+                        pc,
+                    )?;
+                }
+                self.thread.current_frame_mut()?.push(value)?;
+
+                self.thread.current_frame_mut()?.pc += 3;
             }
-            ICONST_4 => {
-                self.thread.current_frame_mut()?.push(JvmValue::Int(4));
-                self.thread.pc += 1;
+
+            PUTSTATIC => {
+                // 格式: putstatic #index
+                let index = Self::read_u16(&code, pc)?;
+                self.check_negative_resolution(&class_name, index)?;
+                let field_ref = {
+                    let class_meta = self.metaspace.get_class_mut(&class_name)?;
+                    class_meta.resolve_field_ref(index)?
+                };
+                self.resolve_class_at(&class_name, index, &field_ref.class_name)?;
+                // 静态字段存放在声明它的类中，初始化的也是声明类
+                let field_ref = self.declaring_field_ref(&class_name, index, field_ref)?;
+                if self.initialize_class(&field_ref.class_name, pc)? {
+                    return Ok(InstructionControl::Continue);
+                }
+
+                self.check_final_field_write(&field_ref, true)?;
+                let value = self.pop_field_value(&field_ref)?;
+                if !self.field_watches.is_empty() {
+                    let old_value = self
+                        .metaspace
+                        .get_class(&field_ref.class_name)?
+                        .static_fields
+                        .get(&field_ref.field_name)
+                        .cloned()
+                        .unwrap_or_else(|| jvm_default_value(&field_ref.field_type));
+                    self.notify_field_access(
+                        FieldAccessKind::Write,
+                        &field_ref,
+                        None,
+                        old_value,
+                        Some(value.clone()),
+                        pc,
+                    )?;
+                }
+                let owner = self.metaspace.get_class_mut(&field_ref.class_name)?;
+                owner.static_fields.insert(field_ref.field_name.clone(), value);
+
+                self.thread.current_frame_mut()?.pc += 3;
             }
-            ICONST_5 => {
-                self.thread.current_frame_mut()?.push(JvmValue::Int(5));
-                self.thread.pc += 1;
+
+            // invokeinterface #index <count> 0：按对象的实际类型查找实现方法，
+            // 类链中没有实现时使用接口的 default 方法
+            INVOKEINTERFACE => {
+                let index = Self::read_u16(&code, pc)?;
+                let method_ref = {
+                    let class_meta = self.metaspace.get_class_mut(&class_name)?;
+                    class_meta.resolve_method_ref(index)?
+                };
+
+                let arg_count = method_ref.arg_count;
+                let mut args = Vec::with_capacity(arg_count);
+                for _ in 0..arg_count {
+                    args.push(self.thread.current_frame_mut()?.pop()?);
+                }
+                args.reverse();
+                let objectref = self.pop_non_null_ref()?;
+
+                let runtime_class = self.heap.get(objectref)?.class_name.clone();
+                let (owner, method) =
+                    self.metaspace.resolve_virtual_method_ref(&runtime_class, &method_ref)?;
+                self.push_method_frame(
+                    &owner,
+                    &method,
+                    Some(JvmValue::Reference(Some(objectref))),
+                    args,
+                    pc + 5,
+                )?;
+            }
+
+            // invokedynamic #index 0 0：只支持字符串拼接（StringConcatFactory）
+            INVOKEDYNAMIC => {
+                let index = Self::read_u16(&code, pc)?;
+                self.invoke_dynamic(&class_name, index)?;
+            }
+
+            // checkcast #index：null 直接通过；类型不符时抛出 ClassCastException
+            // 检查通过时引用原样保留在栈上
+            CHECKCAST => {
+                let index = Self::read_u16(&code, pc)?;
+                let target = self.metaspace.get_class_mut(&class_name)?.resolve_class_ref(index)?;
+                if let JvmValue::Reference(Some(ptr)) = self.thread.current_frame()?.peek()? {
+                    let object_class = &self.heap.get(*ptr)?.class_name;
+                    if !self.metaspace.is_assignable_from(object_class, &target) {
+                        return Err(JavaException::new(
+                            "java/lang/ClassCastException",
+                            format!(
+                                "class {} cannot be cast to class {}",
+                                object_class.replace('/', "."),
+                                target.replace('/', ".")
+                            ),
+                        )
+                        .into());
+                    }
+                }
+                self.thread.current_frame_mut()?.pc += 3;
             }
 
-            BIPUSH => {
-                let value = code[pc + 1] as i8;
+            // instanceof #index：null 压入 0，否则按类型检查压入 1 或 0
+            INSTANCEOF => {
+                let index = Self::read_u16(&code, pc)?;
+                let target = self.metaspace.get_class_mut(&class_name)?.resolve_class_ref(index)?;
+                let result = match self.thread.current_frame_mut()?.pop_ref()? {
+                    Some(ptr) => {
+                        let object_class = &self.heap.get(ptr)?.class_name;
+                        self.metaspace.is_assignable_from(object_class, &target)
+                    }
+                    None => false,
+                };
                 self.thread
                     .current_frame_mut()?
-                    .push(JvmValue::Int(value as i32));
-                self.thread.pc += 2;
+                    .push(JvmValue::Int(result as i32))?;
+                self.thread.current_frame_mut()?.pc += 3;
             }
 
-            SIPUSH => {
-                let value = i16::from_be_bytes([code[pc + 1], code[pc + 2]]);
-                self.thread
-                    .current_frame_mut()?
-                    .push(JvmValue::Int(value as i32));
-                self.thread.pc += 3;
-            }
-            ALOAD | ILOAD => {
-                let index = code[pc + 1] as usize;
-                let value = self.thread.current_frame()?.get_local(index)?.clone();
-                self.thread.current_frame_mut()?.push(value);
-                self.thread.pc += 2;
-            }
+            INVOKEVIRTUAL => {
+                // 格式: invokevirtual #index
+                let index = Self::read_u16(&code, pc)?;
 
-            ALOAD_0 | ALOAD_1 | ALOAD_2 | ALOAD_3 => {
-                let index = (opcode - ALOAD_0) as usize;
-                let value = self.thread.current_frame()?.get_local(index)?.clone();
-                self.thread.current_frame_mut()?.push(value);
-                self.thread.pc += 1;
-            }
-            // ==================== 加载指令 ====================
-            ILOAD_0 | ILOAD_1 | ILOAD_2 | ILOAD_3 => {
-                let index = (opcode - ILOAD_0) as usize;
-                let value = self.thread.current_frame()?.get_local(index)?.clone();
-                self.thread.current_frame_mut()?.push(value);
-                self.thread.pc += 1;
-            }
+                let method_ref = {
+                    let class_meta = self.metaspace.get_class_mut(&class_name)?;
+                    class_meta.resolve_method_ref(index)?
+                };
 
-            ASTORE_0 | ASTORE_1 | ASTORE_2 | ASTORE_3 => {
-                let index = (opcode - ASTORE_0) as usize;
-                let value = self.thread.current_frame_mut()?.pop()?;
-                self.thread.current_frame_mut()?.set_local(index, value)?;
-                self.thread.pc += 1;
-            }
-            // ==================== 存储指令 ====================
-            ISTORE_0 | ISTORE_1 | ISTORE_2 | ISTORE_3 => {
-                let index = (opcode - ISTORE_0) as usize;
-                let value = self.thread.current_frame_mut()?.pop()?;
-                self.thread.current_frame_mut()?.set_local(index, value)?;
-                self.thread.pc += 1;
-            }
+                if self.invoke_builtin(&method_ref, pc + 3)? {
+                    return Ok(InstructionControl::Continue);
+                }
+                self.check_method_ref_access(&class_name, index, &method_ref)?;
+
+                // 动态分派：弹出参数和 objectref
+                let args = self.pop_args(method_ref.arg_count)?;
+                let objectref = match self.thread.current_frame_mut()?.pop()? {
+                    JvmValue::Reference(Some(ptr)) => ptr,
+                    JvmValue::Reference(None) => {
+                        return Err(JavaException::null_pointer(format!(
+                            "cannot invoke {}.{}{} on null",
+                            method_ref.class_name, method_ref.method_name, method_ref.descriptor
+                        ))
+                        .into())
+                    }
+                    other => return Err(anyhow!("Expected reference, got {:?}", other)),
+                };
 
-            // ==================== 运算指令 ====================
-            IADD => {
-                let v2 = self.thread.current_frame_mut()?.pop_int()?;
-                let v1 = self.thread.current_frame_mut()?.pop_int()?;
-                self.thread
-                    .current_frame_mut()?
-                    .push(JvmValue::Int(v1 + v2));
-                self.thread.pc += 1;
+                // 按对象的运行时类型查找方法，沿 super_class 向上
+                let runtime_class = self.heap.get(objectref)?.class_name.clone();
+                let user_method = if self.metaspace.is_class_loaded(&runtime_class) {
+                    Some(self.metaspace.resolve_virtual_method_ref(&runtime_class, &method_ref))
+                } else {
+                    None
+                };
+                match user_method {
+                    Some(Ok((owner, method))) => {
+                        self.push_method_frame(
+                            &owner,
+                            &method,
+                            Some(JvmValue::Reference(Some(objectref))),
+                            args,
+                            pc + 3,
+                        )?;
+                    }
+                    other => {
+                        // JDK 类的对象，或者用户类继承而没有覆盖的 JDK 方法（如 getMessage）：使用内置实现
+                        let ancestor = self.first_unloaded_ancestor(&runtime_class);
+                        let owner = match self.natives.find_jdk_owner(
+                            &ancestor,
+                            &method_ref.method_name,
+                            &method_ref.descriptor,
+                        ) {
+                            Some(owner) => owner,
+                            None => {
+                                return Err(match other {
+                                    Some(Err(e)) => e,
+                                    _ => Self::unsatisfied_link(
+                                        &runtime_class,
+                                        &method_ref.method_name,
+                                        &method_ref.descriptor,
+                                    )
+                                    .into(),
+                                })
+                            }
+                        };
+                        let args = std::iter::once(JvmValue::Reference(Some(objectref)))
+                            .chain(args)
+                            .collect();
+                        self.invoke_native_at(
+                            &owner,
+                            &method_ref.method_name,
+                            &method_ref.descriptor,
+                            args,
+                            pc + 3,
+                        )?;
+                    }
+                }
             }
 
-            ISUB => {
-                let v2 = self.thread.current_frame_mut()?.pop_int()?;
-                let v1 = self.thread.current_frame_mut()?.pop_int()?;
-                self.thread
-                    .current_frame_mut()?
-                    .push(JvmValue::Int(v1 - v2));
-                self.thread.pc += 1;
-            }
+            // ==================== 同步 ====================
+            MONITORENTER => self.monitor_enter()?,
+            MONITOREXIT => self.monitor_exit()?,
 
-            IMUL => {
-                let v2 = self.thread.current_frame_mut()?.pop_int()?;
-                let v1 = self.thread.current_frame_mut()?.pop_int()?;
-                self.thread
-                    .current_frame_mut()?
-                    .push(JvmValue::Int(v1 * v2));
-                self.thread.pc += 1;
+            // ==================== 异常 ====================
+            ATHROW => {
+                let exception = self.pop_non_null_ref()?;
+                self.throw_exception(exception)?;
             }
 
-            IDIV => {
-                let v2 = self.thread.current_frame_mut()?.pop_int()?;
-                let v1 = self.thread.current_frame_mut()?.pop_int()?;
-                if v2 == 0 {
-                    return Err(anyhow!("Division by zero"));
-                }
-                self.thread
-                    .current_frame_mut()?
-                    .push(JvmValue::Int(v1 / v2));
-                self.thread.pc += 1;
-            }
+            // ==================== 返回指令 ====================
+            // 各类型的返回指令处理方式相同：返回值已是带类型的 JvmValue
+            IRETURN | LRETURN | FRETURN | DRETURN | ARETURN => {
+                // 1. 弹出返回值
+                let return_value = self.thread.current_frame_mut()?.pop()?;
 
-            // ==================== 控制流指令 ====================
-            IFEQ => {
-                let offset = i16::from_be_bytes([code[pc + 1], code[pc + 2]]);
-                let value = self.thread.current_frame_mut()?.pop_int()?;
-                if value == 0 {
-                    self.thread.pc = (pc as i32 + offset as i32) as usize;
-                } else {
-                    self.thread.pc += 3;
-                }
-            }
+                // 2. 弹出当前栈帧，synchronized 方法释放监视器
+                let old_frame = self.thread.pop_frame()?;
+                self.exit_method_monitor(&old_frame)?;
 
-            IFNE => {
-                let offset = i16::from_be_bytes([code[pc + 1], code[pc + 2]]);
-                let value = self.thread.current_frame_mut()?.pop_int()?;
-                if value != 0 {
-                    self.thread.pc = (pc as i32 + offset as i32) as usize;
+                // 3. 如果还有调用者栈帧，压入返回值；调用者从自己保存的 pc 继续
+                if self.thread.stack_depth() > 0 {
+                    self.thread.current_frame_mut()?.push(return_value)?;
                 } else {
-                    self.thread.pc += 3;
+                    // 顶层方法返回，携带返回值
+                    return Ok(InstructionControl::Return(Some(return_value)));
                 }
             }
 
-            IFLT => {
-                let offset = i16::from_be_bytes([code[pc + 1], code[pc + 2]]);
-                let value = self.thread.current_frame_mut()?.pop_int()?;
-                if value < 0 {
-                    self.thread.pc = (pc as i32 + offset as i32) as usize;
-                } else {
-                    self.thread.pc += 3;
+            RETURN => {
+                // void返回
+                let old_frame = self.thread.pop_frame()?;
+                self.exit_method_monitor(&old_frame)?;
+                if let Some(initialized) = &old_frame.initializing_class {
+                    self.metaspace.get_class_mut(initialized)?.state = ClassState::Initialized;
+                    jvm_debug!("initialized class {}", initialized);
                 }
-            }
 
-            IFGE => {
-                let offset = i16::from_be_bytes([code[pc + 1], code[pc + 2]]);
-                let value = self.thread.current_frame_mut()?.pop_int()?;
-                if value >= 0 {
-                    self.thread.pc = (pc as i32 + offset as i32) as usize;
-                } else {
-                    self.thread.pc += 3;
+                if self.thread.stack_depth() == 0 {
+                    // 顶层方法返回
+                    return Ok(InstructionControl::Return(None));
                 }
             }
 
-            IFGT => {
-                let offset = i16::from_be_bytes([code[pc + 1], code[pc + 2]]);
-                let value = self.thread.current_frame_mut()?.pop_int()?;
-                if value > 0 {
-                    self.thread.pc = (pc as i32 + offset as i32) as usize;
-                } else {
-                    self.thread.pc += 3;
-                }
-            }
+            _ => return self.unknown_opcode(opcode, pc),
+        }
 
-            IFLE => {
-                let offset = i16::from_be_bytes([code[pc + 1], code[pc + 2]]);
-                let value = self.thread.current_frame_mut()?.pop_int()?;
-                if value <= 0 {
-                    self.thread.pc = (pc as i32 + offset as i32) as usize;
-                } else {
-                    self.thread.pc += 3;
-                }
-            }
+        Ok(InstructionControl::Continue)
+    }
 
-            IF_ICMPEQ => {
-                let offset = i16::from_be_bytes([code[pc + 1], code[pc + 2]]);
-                let v2 = self.thread.current_frame_mut()?.pop_int()?;
-                let v1 = self.thread.current_frame_mut()?.pop_int()?;
-                if v1 == v2 {
-                    self.thread.pc = (pc as i32 + offset as i32) as usize;
-                } else {
-                    self.thread.pc += 3;
-                }
-            }
+    /// 在给定栈帧中执行方法（向后兼容，旧测试用）
+    #[deprecated(note = "use execute_method_with_class instead")]
+    #[allow(deprecated)]
+    pub fn execute_method_in_frame(
+        &mut self,
+        code: &[u8],
+        frame: &mut Frame,
+        class_name: &str,
+    ) -> Result<Option<JvmValue>> {
+        // 创建临时 PC（旧架构）
+        let mut pc = 0;
+        let mut return_value = None;
 
-            IF_ICMPNE => {
-                let offset = i16::from_be_bytes([code[pc + 1], code[pc + 2]]);
-                let v2 = self.thread.current_frame_mut()?.pop_int()?;
-                let v1 = self.thread.current_frame_mut()?.pop_int()?;
-                if v1 != v2 {
-                    self.thread.pc = (pc as i32 + offset as i32) as usize;
-                } else {
-                    self.thread.pc += 3;
+        while pc < code.len() {
+            let opcode = code[pc];
+            let control =
+                self.execute_instruction_legacy(opcode, code, frame, &mut pc, class_name)?;
+            match control {
+                InstructionControl::Continue => {}
+                InstructionControl::Return(val) => {
+                    return_value = val;
+                    break;
                 }
             }
+        }
 
-            IF_ICMPLT => {
-                let offset = i16::from_be_bytes([code[pc + 1], code[pc + 2]]);
-                let v2 = self.thread.current_frame_mut()?.pop_int()?;
-                let v1 = self.thread.current_frame_mut()?.pop_int()?;
-                if v1 < v2 {
-                    self.thread.pc = (pc as i32 + offset as i32) as usize;
-                } else {
-                    self.thread.pc += 3;
-                }
-            }
+        Ok(return_value)
+    }
 
-            IF_ICMPGE => {
-                let offset = i16::from_be_bytes([code[pc + 1], code[pc + 2]]);
-                let v2 = self.thread.current_frame_mut()?.pop_int()?;
-                let v1 = self.thread.current_frame_mut()?.pop_int()?;
-                if v1 >= v2 {
-                    self.thread.pc = (pc as i32 + offset as i32) as usize;
-                } else {
-                    self.thread.pc += 3;
+    /// 从当前类的常量池加载常量（ldc / ldc_w / ldc2_w）
+    fn load_constant(&mut self, class_name: &str, index: u16) -> Result<JvmValue> {
+        use crate::classfile::constant_pool::ConstantPoolEntry;
+
+        let cp = &self.metaspace.get_class(class_name)?.constant_pool;
+        match cp.get(index)? {
+            ConstantPoolEntry::Integer(_) => Ok(JvmValue::Int(cp.get_integer(index)?)),
+            ConstantPoolEntry::Float(_) => Ok(JvmValue::Float(cp.get_float(index)?)),
+            ConstantPoolEntry::Long(_) => Ok(JvmValue::Long(cp.get_long(index)?)),
+            ConstantPoolEntry::Double(_) => Ok(JvmValue::Double(cp.get_double(index)?)),
+            ConstantPoolEntry::String { .. } => {
+                let value = cp.get_string(index)?;
+                Ok(JvmValue::Reference(Some(self.intern_string(&value)?)))
+            }
+            // 类字面量（Foo.class）：加载类（不初始化），压入它唯一的 Class 对象
+            ConstantPoolEntry::Class { .. } => {
+                self.check_negative_resolution(class_name, index)?;
+                let target = self.metaspace.get_class_mut(class_name)?.resolve_class_ref(index)?;
+                if !target.starts_with('[') {
+                    self.resolve_class_at(class_name, index, &target)?;
                 }
+                Ok(JvmValue::Reference(Some(self.class_mirror(&target)?)))
             }
+            other => Err(anyhow!("ldc of {:?} not supported yet", other)),
+        }
+    }
 
-            IF_ICMPGT => {
-                let offset = i16::from_be_bytes([code[pc + 1], code[pc + 2]]);
-                let v2 = self.thread.current_frame_mut()?.pop_int()?;
-                let v1 = self.thread.current_frame_mut()?.pop_int()?;
-                if v1 > v2 {
-                    self.thread.pc = (pc as i32 + offset as i32) as usize;
-                } else {
-                    self.thread.pc += 3;
-                }
+    /// 确保类已加载到 Metaspace：未加载时通过类加载器按名字查找
+    ///
+    /// 父类和接口（JDK 类除外）随之递归加载。
+    /// 注意：目前只做加载和链接，不执行 `<clinit>`
+    pub fn ensure_class_loaded(&mut self, class_name: &str) -> Result<()> {
+        if self.metaspace.is_class_loaded(class_name) {
+            return Ok(());
+        }
+        let loader = self.class_loader.as_ref().ok_or_else(|| {
+            JavaException::new(
+                "java/lang/ClassNotFoundException",
+                format!("{} (no class loader attached)", class_name),
+            )
+        })?;
+        let class_file = loader.read_class(class_name)?;
+        self.metaspace.load_class(class_file)?;
+        jvm_debug!("loaded class {} on demand", class_name);
+
+        let class_meta = self.metaspace.get_class(class_name)?;
+        let supertypes: Vec<String> = class_meta
+            .super_class
+            .iter()
+            .chain(&class_meta.interfaces)
+            .filter(|name| !name.starts_with("java/"))
+            .cloned()
+            .collect();
+        for supertype in supertypes {
+            self.ensure_class_loaded(&supertype)?;
+        }
+        Ok(())
+    }
+
+    /// 类初始化：首次主动使用（new、静态字段读写、静态方法调用）时运行 `<clinit>`
+    ///
+    /// 父类先于子类初始化：子类的 `<clinit>` 栈帧先压栈，父类的压在上面先执行。
+    /// 最先压栈的栈帧返回到 `resume_pc`，即重新执行触发初始化的指令。
+    /// 正在初始化（Initializing）的类直接视为可用，避免 `<clinit>` 访问自身时无限递归。
+    ///
+    /// 压入了 `<clinit>` 栈帧时返回 true，此时调用者不应再修改 PC
+    fn initialize_class(&mut self, class_name: &str, resume_pc: usize) -> Result<bool> {
+        // 最常见的情况：类已经初始化（或正在初始化），不需要任何分配
+        if self
+            .metaspace
+            .get_class(class_name)
+            .is_ok_and(|class| matches!(class.state, ClassState::Initializing | ClassState::Initialized))
+        {
+            return Ok(false);
+        }
+        // 沿父类链收集需要初始化的类（子类在前）
+        let mut pending = Vec::new();
+        let mut current = Some(class_name.to_string());
+        while let Some(name) = current {
+            if name.starts_with("java/") || name.starts_with('[') {
+                break;
+            }
+            let Ok(class_meta) = self.metaspace.get_class_mut(&name) else {
+                break;
+            };
+            if matches!(
+                class_meta.state,
+                ClassState::Initializing | ClassState::Initialized
+            ) {
+                break;
+            }
+            class_meta.state = ClassState::Initializing;
+            current = class_meta.super_class.clone();
+            pending.push(name);
+        }
+
+        let mut resume_at = resume_pc;
+        let mut pushed = false;
+        for name in pending {
+            let class_meta = self.metaspace.get_class_mut(&name)?;
+            let Ok(clinit) = class_meta.find_method("<clinit>", "()V") else {
+                // 没有静态初始化器，直接完成初始化
+                class_meta.state = ClassState::Initialized;
+                continue;
+            };
+            let clinit = clinit.clone();
+            let owner = class_meta.name.clone();
+            jvm_debug!("initializing class {}", name);
+            self.push_method_frame(&owner, &clinit, None, Vec::new(), resume_at)?;
+            self.thread.current_frame_mut()?.initializing_class = Some(name);
+            // 之后压入的（父类的）<clinit> 返回后，刚压入的栈帧从开头执行
+            resume_at = 0;
+            pushed = true;
+        }
+        Ok(pushed)
+    }
+
+    /// 调用点命中否定缓存时直接抛出缓存的异常，不再重复解析
+    fn check_negative_resolution(&mut self, class_name: &str, index: u16) -> Result<()> {
+        match self.metaspace.negative_resolution(class_name, index) {
+            None => Ok(()),
+            Some(NegativeResolution::Failed(exception)) => Err(exception.into()),
+        }
+    }
+
+    /// 查找 JDK 方法的内置实现，找不到时抛出 UnsatisfiedLinkError 并记入调用点的否定缓存
+    fn resolve_jdk_method(
+        &mut self,
+        class_name: &str,
+        index: u16,
+        method_ref: &crate::runtime::ResolvedMethodRef,
+    ) -> Result<String> {
+        match self.natives.find_jdk_owner(
+            &method_ref.class_name,
+            &method_ref.method_name,
+            &method_ref.descriptor,
+        ) {
+            Some(owner) => Ok(owner),
+            None => {
+                let exception = Self::unsatisfied_link(
+                    &method_ref.class_name,
+                    &method_ref.method_name,
+                    &method_ref.descriptor,
+                );
+                self.metaspace.record_negative_resolution(
+                    class_name,
+                    index,
+                    NegativeResolution::Failed(exception.clone()),
+                )?;
+                Err(exception.into())
             }
+        }
+    }
 
-            IF_ICMPLE => {
-                let offset = i16::from_be_bytes([code[pc + 1], code[pc + 2]]);
-                let v2 = self.thread.current_frame_mut()?.pop_int()?;
-                let v1 = self.thread.current_frame_mut()?.pop_int()?;
-                if v1 <= v2 {
-                    self.thread.pc = (pc as i32 + offset as i32) as usize;
-                } else {
-                    self.thread.pc += 3;
-                }
+    /// 沿 super_class 向上找到第一个没有加载到 Metaspace 的类（内置类库中的 JDK 类）
+    fn first_unloaded_ancestor(&self, class_name: &str) -> String {
+        let mut current = class_name.to_string();
+        while let Ok(class) = self.metaspace.get_class(&current) {
+            match &class.super_class {
+                Some(super_class) => current = super_class.clone(),
+                None => break,
             }
+        }
+        current
+    }
 
-            GOTO => {
-                let offset = i16::from_be_bytes([code[pc + 1], code[pc + 2]]);
-                self.thread.pc = (pc as i32 + offset as i32) as usize;
+    fn unsatisfied_link(class_name: &str, method_name: &str, descriptor: &str) -> JavaException {
+        JavaException::new(
+            "java/lang/UnsatisfiedLinkError",
+            format!("{}.{}{}", class_name.replace('/', "."), method_name, descriptor),
+        )
+    }
+
+    /// 同 `resolve_class`，找不到类时把异常记入调用点（常量池 `index`）的否定缓存
+    fn resolve_class_at(&mut self, class_name: &str, index: u16, target: &str) -> Result<()> {
+        let result = self.resolve_class(target);
+        if let Err(e) = &result {
+            if let Some(exception) = e.downcast_ref::<JavaException>() {
+                self.metaspace.record_negative_resolution(
+                    class_name,
+                    index,
+                    NegativeResolution::Failed(exception.clone()),
+                )?;
             }
+        }
+        result
+    }
 
-            // ==================== 方法调用指令 ====================
-            INVOKESTATIC => {
-                let index = u16::from_be_bytes([code[pc + 1], code[pc + 2]]);
+    /// 解析符号引用前确保目标类已加载
+    ///
+    /// JDK 类（java/...）和数组类不需要加载；没有类加载器时要求类已经手动加载
+    fn resolve_class(&mut self, class_name: &str) -> Result<()> {
+        if class_name.starts_with("java/")
+            || class_name.starts_with('[')
+            || self.metaspace.is_class_loaded(class_name)
+        {
+            return Ok(());
+        }
+        if self.class_loader.is_none() {
+            return Err(anyhow!(
+                "Class {} not loaded. Load it first using interpreter.load_class() or attach a ClassLoader with Interpreter::with_class_loader()",
+                class_name
+            ));
+        }
+        self.ensure_class_loaded(class_name)
+    }
 
-                // 1. 解析方法引用
-                let method_ref = {
-                    let class_meta = self.metaspace.get_class_mut(&class_name)?;
-                    class_meta.resolve_method_ref(index)?
-                };
+    /// NEW 之前加载目标类并检查它能否实例化
+    ///
+    /// 接口、抽象类和数组类不能用 new 创建，抛出 InstantiationError。
+    /// JDK 类（java/...）没有 class 文件，不加载也不检查
+    fn check_instantiable(&mut self, class_name: &str) -> Result<()> {
+        use crate::classfile::access_flags::{ACC_ABSTRACT, ACC_INTERFACE};
+
+        // 数组只能用 newarray/anewarray/multianewarray 创建
+        if class_name.starts_with('[') {
+            return Err(JavaException::new(
+                "java/lang/InstantiationError",
+                format!("{} is an array class", class_name),
+            )
+            .into());
+        }
+        if class_name.starts_with("java/") {
+            return Ok(());
+        }
+        self.resolve_class(class_name)?;
+        let class_meta = self.metaspace.get_class(class_name)?;
+        if class_meta.access_flags & (ACC_ABSTRACT | ACC_INTERFACE) != 0 {
+            let kind = if class_meta.access_flags & ACC_INTERFACE != 0 {
+                "interface"
+            } else {
+                "abstract class"
+            };
+            return Err(JavaException::new(
+                "java/lang/InstantiationError",
+                format!("{} is an {}", class_name.replace('/', "."), kind),
+            )
+            .into());
+        }
+        Ok(())
+    }
 
-                // 2. 检查类是否已加载
-                // 作弊版：跳过 java.* 系统类检查
-                let is_system_class = method_ref.class_name.starts_with("java/");
-                if !is_system_class && !self.metaspace.is_class_loaded(&method_ref.class_name) {
-                    return Err(anyhow!(
-                        "Class {} not loaded. Please load it first using interpreter.load_class()",
-                        method_ref.class_name
-                    ));
-                }
+    /// 获取字符串常量对应的 String 对象（字符串驻留，相同内容只分配一次）
+    ///
+    /// 驻留的字符串是 GC 根，永远不会被回收：只用于 ldc 字面量，运行时拼接出的字符串用 `new_string`
+    pub fn intern_string(&mut self, value: &str) -> Result<ObjRef> {
+        if let Some(&ptr) = self.interned_strings.get(value) {
+            return Ok(ptr);
+        }
+        let ptr = self.new_string(value)?;
+        self.interned_strings.insert(value.to_string(), ptr);
+        Ok(ptr)
+    }
 
-                // 3. 查找目标方法（如果是系统类，跳过）
-                if is_system_class {
-                    // 系统类静态方法调用：假装调用成功，什么都不做
-                    self.thread.pc += 3;
-                    return Ok(InstructionControl::Continue);
-                }
+    /// 内置 JDK 类的静态字段；`System.out` 在第一次读取时创建
+    ///
+    /// System.out 是一个普通的 java/io/PrintStream 堆对象，引用保存在方法区，
+    /// 可以像其他对象一样存入变量、作为参数传递
+    fn jdk_static_field(&mut self, field_ref: &ResolvedFieldRef) -> Result<JvmValue> {
+        let (class_name, field_name) = (&field_ref.class_name, &field_ref.field_name);
+        if let Some(value) = self.metaspace.jdk_static_field(class_name, field_name) {
+            return Ok(value.clone());
+        }
+        match (class_name.as_str(), field_name.as_str()) {
+            ("java/lang/System", "out") => {
+                let out = self.allocate_with(|heap| heap.allocate("java/io/PrintStream"))?;
+                let value = JvmValue::Reference(Some(out));
+                self.metaspace
+                    .set_jdk_static_field(class_name, field_name, value.clone());
+                Ok(value)
+            }
+            _ => Err(JavaException::new(
+                "java/lang/NoSuchFieldError",
+                format!("{}.{}:{}", class_name, field_name, field_ref.descriptor),
+            )
+            .into()),
+        }
+    }
 
-                // 4. 查找目标方法（用户类）
-                let target_class = self.metaspace.get_class(&method_ref.class_name)?;
-                let method_key = format!("{}:{}", method_ref.method_name, method_ref.descriptor);
-                let method = target_class
-                    .methods
-                    .get(&method_key)
-                    .ok_or_else(|| {
-                        anyhow!("Method not found: {}.{}", method_ref.class_name, method_key)
-                    })?
-                    .clone();
+    /// aastore 的类型检查：引用数组只能存入组件类型（或其子类型）的对象，否则抛出 ArrayStoreException
+    ///
+    /// null 总能存入；下标越界时不检查，由 `array_set` 抛出 ArrayIndexOutOfBoundsException
+    fn check_array_store(&self, array: ObjRef, index: i32, value: &JvmValue) -> Result<()> {
+        let Some(object) = value.as_object_ref() else {
+            return Ok(());
+        };
+        if index < 0 || index as usize >= self.heap.array_length(array)? {
+            return Ok(());
+        }
+        let array_class = &self.heap.get(array)?.class_name;
+        let Some(component) = array_class.strip_prefix('[') else {
+            return Ok(());
+        };
+        let component = component
+            .strip_prefix('L')
+            .and_then(|name| name.strip_suffix(';'))
+            .unwrap_or(component);
+        let object_class = &self.heap.get(object)?.class_name;
+        if !self.metaspace.is_assignable_from(object_class, component) {
+            return Err(JavaException::new(
+                "java/lang/ArrayStoreException",
+                object_class.replace('/', "."),
+            )
+            .into());
+        }
+        Ok(())
+    }
 
-                // 4. 从操作数栈弹出参数
-                let arg_count = Self::parse_arg_count(&method.descriptor);
-                let mut args: Vec<JvmValue> = Vec::new();
-                for _ in 0..arg_count {
-                    args.push(self.thread.current_frame_mut()?.pop()?);
-                }
-                args.reverse(); // 栈是LIFO，需要反转
+    /// 装箱：创建包装类（如 java/lang/Integer）的对象，`value` 字段保存基本类型的值
+    ///
+    /// 与 JDK 一样，Integer/Long 的 -128..=127、Character 的 0..=127 和 Boolean 的两个值
+    /// 使用缓存的对象，其余的值每次都分配新对象
+    pub fn box_value(&mut self, class_name: &'static str, value: JvmValue) -> Result<ObjRef> {
+        let cache_key = match (class_name, &value) {
+            ("java/lang/Integer", JvmValue::Int(v)) if (-128..=127).contains(v) => Some(*v as i64),
+            ("java/lang/Long", JvmValue::Long(v)) if (-128..=127).contains(v) => Some(*v),
+            ("java/lang/Character", JvmValue::Int(v)) if (0..=127).contains(v) => Some(*v as i64),
+            ("java/lang/Boolean", JvmValue::Int(v)) => Some(*v as i64),
+            _ => None,
+        };
+        if let Some(&ptr) = cache_key.and_then(|key| self.boxed_values.get(&(class_name, key))) {
+            return Ok(ptr);
+        }
+        let ptr = self.allocate_with(|heap| heap.allocate(class_name))?;
+        self.heap.set_field(ptr, class_name, "value", value)?;
+        if let Some(key) = cache_key {
+            self.boxed_values.insert((class_name, key), ptr);
+        }
+        Ok(ptr)
+    }
 
-                // 5. 创建新栈帧并设置参数和返回地址
-                let mut new_frame = Frame::new_with_context(
-                    method.max_locals,
-                    method.max_stack,
-                    method_ref.class_name.clone(),
-                    method.code.clone(),
-                    Some(pc + 3), // 返回地址：invokestatic 后的下一条指令
-                );
+    /// 立即执行一次完整的垃圾回收，返回回收的对象数
+    ///
+    /// 根是线程栈（包括暂停中的线程）、静态字段、驻留的字符串、装箱缓存和 Class 对象
+    pub fn collect_garbage(&mut self) -> usize {
+        self.collect(GcKind::Full)
+    }
 
-                for (i, arg) in args.into_iter().enumerate() {
-                    new_frame.set_local(i, arg)?;
-                }
+    /// 立即执行一次 minor GC（只回收新生代，根与完整回收相同），返回回收的对象数；
+    /// 没有使用分代回收时什么也不做
+    pub fn collect_minor_garbage(&mut self) -> usize {
+        self.collect(GcKind::Minor)
+    }
 
-                // 6. 压入新栈帧到线程栈
-                self.thread.push_frame(new_frame);
+    fn collect(&mut self, kind: GcKind) -> usize {
+        let mut roots = GcRootSet::new();
+        roots.add_thread(&mut self.thread);
+        for stack in self.threads.parked_stacks_mut() {
+            roots.add_thread(stack);
+        }
+        roots.add_statics(&mut self.metaspace);
+        let handles = self
+            .class_mirrors
+            .values_mut()
+            .chain(self.interned_strings.values_mut())
+            .chain(self.boxed_values.values_mut())
+            .chain(self.handles.roots_mut());
+        for handle in handles {
+            roots.add_handle(handle);
+        }
+        match kind {
+            GcKind::Full => self.gc.collect_roots(&mut self.heap, roots),
+            GcKind::Minor => self.gc.collect_minor(&mut self.heap, roots),
+        }
+    }
 
-                // 7. 设置PC为0，开始执行被调用方法
-                self.thread.pc = 0;
-            }
+    /// 在堆上分配：先按需回收（见 `collect_if_needed`）；堆放不下时完整回收一次再重试，
+    /// 仍然放不下才抛出 OutOfMemoryError。解释器和内置方法的所有分配都经过这里
+    ///
+    /// 回收发生在 `alloc` 之前：调用方手里还不在任何根中的引用要先登记
+    /// （`pin`、`with_roots`），否则可能被回收，或者被整理移动后失效
+    pub fn allocate_with<T>(&mut self, mut alloc: impl FnMut(&mut Heap) -> Result<T>) -> Result<T> {
+        self.collect_if_needed();
+        match alloc(&mut self.heap) {
+            Err(e) if Self::is_out_of_memory(&e) => {
+                self.collect_garbage();
+                alloc(&mut self.heap)
+            }
+            result => result,
+        }
+    }
 
-            // ==================== 字段访问指令 (作弊版调试支持) ====================
-            GETSTATIC => {
-                // 作弊版：专门处理 System.out
-                // 格式: getstatic #index
-                let _index = u16::from_be_bytes([code[pc + 1], code[pc + 2]]);
+    /// 在堆上分配 java/lang/String（经过 `allocate_with`）
+    pub fn new_string(&mut self, value: &str) -> Result<ObjRef> {
+        self.allocate_with(|heap| heap.allocate_string(value))
+    }
 
-                // 简化实现：我们不真正解析常量池，直接假设这是 System.out
-                // 压入一个特殊的引用值作为 PrintStream 对象
-                self.thread
-                    .current_frame_mut()?
-                    .push(JvmValue::Reference(Some(0xFFFF))); // 特殊标记值
+    fn is_out_of_memory(error: &anyhow::Error) -> bool {
+        error
+            .downcast_ref::<JavaException>()
+            .is_some_and(|e| e.class_name == "java/lang/OutOfMemoryError")
+    }
 
-                self.thread.pc += 3;
-            }
+    /// 分配压力达到阈值或堆已满时完整回收，否则新生代满时 minor GC
+    /// （在分配新对象之前调用，新对象还不在任何根中）
+    fn collect_if_needed(&mut self) {
+        if self.heap.needs_gc() || self.heap.is_full() {
+            self.collect_garbage();
+        } else if self.heap.needs_minor_gc() {
+            self.collect_minor_garbage();
+        }
+    }
 
-            INVOKEVIRTUAL => {
-                // 作弊版：专门处理 println
-                // 格式: invokevirtual #index
-                let index = u16::from_be_bytes([code[pc + 1], code[pc + 2]]);
+    /// 转储堆中的所有对象（见 [`Heap::dump`]）
+    pub fn heap_dump(&self) -> HeapDump {
+        self.heap.dump()
+    }
 
-                // 解析方法引用，检查是否是 println
-                let method_ref = {
-                    let class_meta = self.metaspace.get_class_mut(&class_name)?;
-                    class_meta.resolve_method_ref(index)?
-                };
+    /// 累计的 GC 统计（包括自动触发的回收）
+    pub fn gc_stats(&self) -> &GcStats {
+        self.gc.stats()
+    }
 
-                if method_ref.method_name == "println" {
-                    // 这是 println 调用！
-                    // 参数顺序：objectref, [args...]
+    /// 每次回收的记录，按发生顺序（包括自动触发的回收）
+    pub fn gc_events(&self) -> &[GcEvent] {
+        self.gc.events()
+    }
 
-                    // 弹出参数（根据描述符判断）
-                    let arg_count = Self::parse_arg_count(&method_ref.descriptor);
-                    let mut args = Vec::new();
-                    for _ in 0..arg_count {
-                        args.push(self.thread.current_frame_mut()?.pop()?);
-                    }
-                    args.reverse();
-
-                    // 弹出 objectref (System.out)
-                    let _objectref = self.thread.current_frame_mut()?.pop()?;
-
-                    // 打印参数（作弊版：直接打印值）
-                    if args.len() == 1 {
-                        match &args[0] {
-                            JvmValue::Int(val) => println!("{}", val),
-                            JvmValue::Long(val) => println!("{}", val),
-                            JvmValue::Float(val) => println!("{}", val),
-                            JvmValue::Double(val) => println!("{}", val),
-                            JvmValue::Reference(Some(addr)) => println!("Reference@{:x}", addr),
-                            JvmValue::Reference(None) => println!("null"),
-                        }
-                    } else if args.is_empty() {
-                        // println() 无参数，打印空行
-                        println!();
-                    }
-                    self.thread.pc += 3;
-                } else {
-                    return Err(anyhow!(
-                        "INVOKEVIRTUAL not implemented for method: {}.{}",
-                        method_ref.class_name,
-                        method_ref.method_name
-                    ));
-                }
-            }
+    /// 卸载类（见 [`Metaspace::unload_class`]），同时丢弃它的 Class 对象缓存
+    pub fn unload_class(&mut self, class_name: &str) -> Result<()> {
+        self.metaspace.unload_class(class_name, &self.thread)?;
+        self.class_mirrors.remove(class_name);
+        Ok(())
+    }
 
-            // ==================== 返回指令 ====================
-            IRETURN => {
-                // 1. 弹出返回值
-                let return_value = self.thread.current_frame_mut()?.pop()?;
+    /// 获取类的 java/lang/Class 对象（每个类只创建一次）
+    ///
+    /// Class 对象的 `name` 字段是点分隔的类名字符串（如 "java.lang.String"）
+    pub fn class_mirror(&mut self, class_name: &str) -> Result<ObjRef> {
+        if let Some(&ptr) = self.class_mirrors.get(class_name) {
+            return Ok(ptr);
+        }
+        // 先放进缓存（GC 根），分配名字字符串时 Class 对象不会被回收
+        let mirror = self.allocate_with(|heap| heap.allocate("java/lang/Class"))?;
+        self.class_mirrors.insert(class_name.to_string(), mirror);
+        let name = match self.new_string(&class_name.replace('/', ".")) {
+            Ok(name) => name,
+            Err(e) => {
+                self.class_mirrors.remove(class_name);
+                return Err(e);
+            }
+        };
+        // 分配名字时 Class 对象可能已被整理移动
+        let mirror = self.class_mirrors[class_name];
+        self.heap
+            .set_field(mirror, "java/lang/Class", "name", JvmValue::Reference(Some(name)))?;
+        Ok(mirror)
+    }
 
-                // 2. 弹出当前栈帧
-                let old_frame = self.thread.pop_frame()?;
+    /// 由 Class 对象反查类名（内部名，斜杠分隔）
+    fn mirror_class_name(&self, mirror: ObjRef) -> Result<String> {
+        match self.heap.get_field(mirror, "java/lang/Class", "name")? {
+            JvmValue::Reference(Some(name)) => Ok(self.heap.get_string(name)?.replace('.', "/")),
+            other => Err(anyhow!("Invalid Class object name field: {:?}", other)),
+        }
+    }
 
-                // 3. 如果还有调用者栈帧，恢复PC并压入返回值
-                if self.thread.stack_depth() > 0 {
-                    // 恢复调用者的PC
-                    if let Some(return_addr) = old_frame.return_address {
-                        self.thread.pc = return_addr;
-                    } else {
-                        return Err(anyhow!("Missing return address in frame"));
-                    }
+    /// 内置的 JDK 方法（作弊版本地方法）
+    ///
+    /// 如果 `method_ref` 是已知的内置方法，弹出参数、完成调用并返回 true；
+    /// 否则不修改任何状态并返回 false
+    fn invoke_builtin(
+        &mut self,
+        method_ref: &crate::runtime::ResolvedMethodRef,
+        next_pc: usize,
+    ) -> Result<bool> {
+        match (
+            method_ref.class_name.as_str(),
+            method_ref.method_name.as_str(),
+            method_ref.descriptor.as_str(),
+        ) {
+            // static Class forName(String name)
+            ("java/lang/Class", "forName", "(Ljava/lang/String;)Ljava/lang/Class;") => {
+                let name_ref = self.pop_non_null_ref()?;
+                let class_name = self.heap.get_string(name_ref)?.replace('.', "/");
+                self.ensure_class_loaded(&class_name)?;
+                let mirror = self.class_mirror(&class_name)?;
+                self.thread
+                    .current_frame_mut()?
+                    .push(JvmValue::Reference(Some(mirror)))?;
+                self.thread.current_frame_mut()?.pc = next_pc;
+                // forName(String) 会初始化类
+                self.initialize_class(&class_name, next_pc)?;
+                Ok(true)
+            }
+            // Object newInstance()：分配对象并调用无参构造器
+            ("java/lang/Class", "newInstance", "()Ljava/lang/Object;") => {
+                let mirror = self.pop_non_null_ref()?;
+                let class_name = self.mirror_class_name(mirror)?;
+                self.ensure_class_loaded(&class_name)?;
+
+                let class_meta = self.metaspace.get_class(&class_name)?;
+                let flags = class_meta.access_flags;
+                if flags & (crate::classfile::access_flags::ACC_ABSTRACT
+                    | crate::classfile::access_flags::ACC_INTERFACE)
+                    != 0
+                {
+                    return Err(JavaException::new(
+                        "java/lang/InstantiationException",
+                        format!("{} is abstract or an interface", class_name),
+                    )
+                    .into());
+                }
+                // 构造器不继承，只在类本身查找
+                let init = class_meta.find_method("<init>", "()V").map_err(|_| {
+                    JavaException::new(
+                        "java/lang/InstantiationException",
+                        format!("{} has no no-arg constructor", class_name),
+                    )
+                })?;
+                let init = init.clone();
+                let owner = class_meta.name.clone();
+
+                // 先把新对象压入调用者的栈，<init> 返回后它就是 newInstance 的返回值
+                let obj = self.allocate_object(owner.clone())?;
+                self.thread
+                    .current_frame_mut()?
+                    .push(JvmValue::Reference(Some(obj)))?;
+                self.push_method_frame(
+                    &owner,
+                    &init,
+                    Some(JvmValue::Reference(Some(obj))),
+                    Vec::new(),
+                    next_pc,
+                )?;
+                // 构造器运行前先初始化类
+                self.initialize_class(&class_name, 0)?;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
 
-                    // 将返回值压入调用者的操作数栈
-                    self.thread.current_frame_mut()?.push(return_value);
+    /// 把 `class_name` 常量池 `index` 处的字段引用改写为声明字段的类（字段可能继承自父类或接口），
+    /// 结果缓存在运行时常量池中
+    ///
+    /// 第一次解析时检查调用者能否访问这个字段（见 `access`）；
+    /// JDK 类和在已加载的类中找不到的字段保持原样
+    fn declaring_field_ref(
+        &mut self,
+        class_name: &str,
+        index: u16,
+        field_ref: Arc<ResolvedFieldRef>,
+    ) -> Result<Arc<ResolvedFieldRef>> {
+        if field_ref.class_name.starts_with("java/") {
+            return Ok(field_ref);
+        }
+        let class_meta = self.metaspace.get_class(class_name)?;
+        if let Some(declared) = class_meta.runtime_pool.declared_fields.get(&index) {
+            return Ok(declared.clone());
+        }
+        let declared = match self.metaspace.resolve_field_in_hierarchy(
+            &field_ref.class_name,
+            &field_ref.field_name,
+            &field_ref.descriptor,
+        ) {
+            Ok((owner, field)) => {
+                self.check_member_access(class_name, &owner, field.access_flags, || {
+                    format!("field {}.{}", owner, field.name)
+                })?;
+                let slot = if !field.is_static {
+                    self.metaspace
+                        .field_layout(&owner)
+                        .slot(&(owner.clone(), field_ref.field_name.clone()))
                 } else {
-                    // 顶层方法返回，携带返回值
-                    return Ok(InstructionControl::Return(Some(return_value)));
-                }
+                    None
+                };
+                Arc::new(ResolvedFieldRef {
+                    class_name: owner,
+                    field_name: field_ref.field_name.clone(),
+                    descriptor: field_ref.descriptor.clone(),
+                    field_type: field_ref.field_type.clone(),
+                    access_flags: field.access_flags,
+                    slot,
+                })
+            }
+            Err(_) => field_ref,
+        };
+        self.metaspace
+            .get_class_mut(class_name)?
+            .runtime_pool
+            .declared_fields
+            .insert(index, declared.clone());
+        Ok(declared)
+    }
+
+    /// 把字段访问通知给匹配的监视（调用前应先检查 `field_watches` 非空）
+    fn notify_field_access(
+        &mut self,
+        kind: FieldAccessKind,
+        field_ref: &ResolvedFieldRef,
+        object: Option<ObjRef>,
+        old_value: JvmValue,
+        new_value: Option<JvmValue>,
+        pc: usize,
+    ) -> Result<()> {
+        let owner = self.metaspace.resolve_field_owner(
+            &field_ref.class_name,
+            &field_ref.field_name,
+            &field_ref.descriptor,
+        );
+        if !self
+            .field_watches
+            .iter()
+            .any(|w| w.matches(&owner, &field_ref.field_name))
+        {
+            return Ok(());
+        }
+
+        let frame = self.thread.current_frame()?;
+        let event = FieldAccessEvent {
+            kind,
+            class_name: owner.into(),
+            field_name: field_ref.field_name.to_string(),
+            object,
+            old_value,
+            new_value,
+            accessor_class: frame.class_name.to_string(),
+            accessor_method: frame.method_name.to_string(),
+            pc,
+        };
+        for watch in &mut self.field_watches {
+            if watch.matches(&event.class_name, &event.field_name) {
+                (watch.callback)(&event);
             }
+        }
+        Ok(())
+    }
 
-            RETURN => {
-                // void返回
-                let old_frame = self.thread.pop_frame()?;
+    /// 在堆上创建对象，沿父类链把所有实例字段初始化为默认值
+    fn allocate_object(&mut self, class_name: Symbol) -> Result<ObjRef> {
+        let layout = self.metaspace.field_layout(&class_name);
+        self.allocate_with(|heap| heap.allocate_instance(class_name.clone(), layout.clone()))
+    }
 
-                if self.thread.stack_depth() > 0 {
-                    // 恢复调用者的PC
-                    if let Some(return_addr) = old_frame.return_address {
-                        self.thread.pc = return_addr;
-                    } else {
-                        return Err(anyhow!("Missing return address in frame"));
+    /// 在堆上创建异常对象，异常信息存入 detailMessage 字段
+    ///
+    /// 不受堆上限约束，否则堆满时连 OutOfMemoryError 都抛不出来
+    fn allocate_exception(&mut self, exception: &JavaException) -> Result<ObjRef> {
+        let layout = self.metaspace.field_layout(&exception.class_name);
+        self.heap.without_limit(|heap| {
+            let ptr = heap.allocate_instance(exception.class_name.clone(), layout)?;
+            let message = match &exception.message {
+                Some(message) => JvmValue::Reference(Some(heap.allocate_string(message)?)),
+                None => JvmValue::Reference(None),
+            };
+            heap.set_field(ptr, "java/lang/Throwable", "detailMessage", message)?;
+            Ok(ptr)
+        })
+    }
+
+    /// 抛出异常：从当前 pc 开始查找异常处理器，找不到就弹出栈帧继续向调用者查找
+    ///
+    /// 找到处理器时清空操作数栈、压入异常引用并跳转到 handler_pc；
+    /// 栈帧全部弹出仍未捕获时返回带异常类名的错误
+    fn throw_exception(&mut self, exception: ObjRef) -> Result<()> {
+        let exception_class = self.heap.get(exception)?.class_name.clone();
+        let mut throw_pc = self.thread.current_frame()?.pc;
+        // 异常经过（被弹出）的栈帧，未捕获时就是完整的栈轨迹
+        let mut unwound = Vec::new();
+        loop {
+            let handler_pc = self
+                .thread
+                .current_frame()?
+                .exception_table
+                .iter()
+                .find(|entry| {
+                    entry.covers(throw_pc)
+                        && entry.catch_type.as_ref().is_none_or(|catch_type| {
+                            self.metaspace.is_assignable_from(&exception_class, catch_type)
+                        })
+                })
+                .map(|entry| entry.handler_pc);
+
+            if let Some(handler_pc) = handler_pc {
+                let frame = self.thread.current_frame_mut()?;
+                frame.clear_stack();
+                frame.push(JvmValue::Reference(Some(exception)))?;
+                frame.pc = handler_pc;
+                return Ok(());
+            }
+
+            // 当前方法没有处理器：弹出栈帧，在调用者中继续查找
+            let info = self.trace_frame(self.thread.current_frame()?, unwound.is_empty());
+            unwound.push(info);
+            let frame = self.thread.pop_frame()?;
+            self.exit_method_monitor(&frame)?;
+            match self.thread.current_frame() {
+                Ok(caller) => {
+                    // 调用者保存的 pc 指向 invoke 之后；减 1 落在 invoke 指令内部，
+                    // 而异常表范围以指令为边界，所以结果与用 invoke 的 pc 相同
+                    throw_pc = caller.pc.saturating_sub(1);
+                }
+                Err(_) => {
+                    let message = match self.heap.get_field(exception, "java/lang/Throwable", "detailMessage") {
+                        Ok(JvmValue::Reference(Some(ptr))) => {
+                            self.heap.get_string(ptr).ok().map(str::to_string)
+                        }
+                        _ => None,
+                    };
+                    let cause = anyhow!(
+                        "Uncaught exception: {}",
+                        JavaException {
+                            class_name: exception_class.into(),
+                            message,
+                        }
+                    );
+                    return Err(ExecutionError {
+                        cause,
+                        frames: unwound,
                     }
-                } else {
-                    // 顶层方法返回
-                    return Ok(InstructionControl::Return(None));
+                    .into());
                 }
             }
+        }
+    }
 
-            _ => {
-                return Err(anyhow!("Unknown opcode: 0x{:02X} at pc {}", opcode, pc));
-            }
+    /// 为被调用方法创建栈帧并开始执行
+    ///
+    /// 实例方法的 `receiver` 放在 local[0]，参数依次放在后面；
+    /// 当前栈帧（调用者）返回后从 `resume_pc` 继续
+    fn push_method_frame(
+        &mut self,
+        class_name: &Symbol,
+        method: &crate::runtime::MethodMetadata,
+        receiver: Option<JvmValue>,
+        args: Vec<JvmValue>,
+        resume_pc: usize,
+    ) -> Result<()> {
+        if method.is_native {
+            let args = receiver.into_iter().chain(args).collect();
+            return self.invoke_native_at(class_name, &method.name, &method.descriptor, args, resume_pc);
+        }
+        // 抽象方法没有字节码，不能创建栈帧
+        if method.is_abstract {
+            return Err(
+                JavaException::abstract_method(class_name, &method.name, &method.descriptor).into(),
+            );
         }
+        let mut new_frame = Frame::new_with_context(
+            method.max_locals,
+            method.max_stack,
+            class_name.clone(),
+            method.code.clone(),
+        );
+        new_frame.decoded = method.decoded.clone();
+        new_frame.exception_table = method.exception_table.clone();
+        new_frame.method_name = method.name.clone();
+        new_frame.descriptor = method.descriptor.clone();
+        let start = match receiver {
+            Some(this) => {
+                new_frame.set_local(0, this)?;
+                1
+            }
+            None => 0,
+        };
+        Self::store_args(&mut new_frame, start, &method.descriptor, args)?;
+        #[cfg(feature = "tracing")]
+        new_frame.enter_span(&method.name, &method.descriptor);
+        self.set_method_monitor(&mut new_frame, method)?;
+        self.thread.push_callee(new_frame, resume_pc)?;
+        self.enter_method_monitor()
+    }
 
-        Ok(InstructionControl::Continue)
+    /// putfield/putstatic：弹出要存入字段的值，按字段描述符检查种类并截断
+    fn pop_field_value(&mut self, field_ref: &ResolvedFieldRef) -> Result<JvmValue> {
+        let value = self.thread.current_frame_mut()?.pop()?;
+        value.coerce_to_descriptor(&field_ref.descriptor).map_err(|e| {
+            anyhow!(
+                "Cannot store into field {}.{}: {}",
+                field_ref.class_name,
+                field_ref.field_name,
+                e
+            )
+        })
     }
 
-    /// 在给定栈帧中执行方法（向后兼容，旧测试用）
-    #[deprecated(note = "use execute_method_with_class instead")]
-    pub fn execute_method_in_frame(
+    /// 从操作数栈弹出 `arg_count` 个参数（按声明顺序返回）
+    fn pop_args(&mut self, arg_count: usize) -> Result<Vec<JvmValue>> {
+        let frame = self.thread.current_frame_mut()?;
+        let mut args = Vec::with_capacity(arg_count);
+        for _ in 0..arg_count {
+            args.push(frame.pop()?);
+        }
+        args.reverse();
+        Ok(args)
+    }
+
+    /// 调用本地方法，没有注册实现时抛出 UnsatisfiedLinkError
+    fn call_native(
         &mut self,
-        code: &[u8],
-        frame: &mut Frame,
         class_name: &str,
+        method_name: &str,
+        descriptor: &str,
+        args: &[JvmValue],
     ) -> Result<Option<JvmValue>> {
-        // 创建临时 PC（旧架构）
-        let mut pc = 0;
-        let mut return_value = None;
+        let f = self
+            .natives
+            .get(class_name, method_name, descriptor)
+            .ok_or_else(|| Self::unsatisfied_link(class_name, method_name, descriptor))?;
+        jvm_debug!("native call {}.{}{}", class_name, method_name, descriptor);
+        f(self, args)
+    }
 
-        while pc < code.len() {
-            let opcode = code[pc];
-            let control =
-                self.execute_instruction_legacy(opcode, code, frame, &mut pc, class_name)?;
-            match control {
-                InstructionControl::Continue => {}
-                InstructionControl::Return(val) => {
-                    return_value = val;
-                    break;
-                }
-            }
+    /// 在调用者栈帧上执行本地方法：返回值压栈，PC 移到 `next_pc`
+    fn invoke_native_at(
+        &mut self,
+        class_name: &str,
+        method_name: &str,
+        descriptor: &str,
+        args: Vec<JvmValue>,
+        next_pc: usize,
+    ) -> Result<()> {
+        let result = self.call_native(class_name, method_name, descriptor, &args)?;
+        let frame = self.thread.current_frame_mut()?;
+        if let Some(value) = result {
+            frame.push(value)?;
         }
-
-        Ok(return_value)
+        frame.pc = next_pc;
+        Ok(())
     }
 
     /// 加载类到 Metaspace（如果尚未加载）
@@ -703,48 +2399,130 @@ impl Interpreter {
         Ok(class_name)
     }
 
-    /// 从常量池解析方法描述符中的参数个数
-    /// 例如: "(II)I" -> 2, "(JD)V" -> 2 (long和double各占1个参数位)
-    fn parse_arg_count(descriptor: &str) -> usize {
-        let mut count = 0;
-        let mut chars = descriptor.chars().skip(1); // 跳过开头的 '('
-
-        while let Some(ch) = chars.next() {
-            match ch {
-                ')' => break, // 参数列表结束
-                'B' | 'C' | 'S' | 'I' | 'F' | 'Z' => count += 1,
-                'J' | 'D' => count += 1, // long 和 double
-                'L' => {
-                    // 引用类型，跳到分号
-                    while let Some(c) = chars.next() {
-                        if c == ';' {
-                            break;
-                        }
-                    }
-                    count += 1;
-                }
-                '[' => {
-                    // 数组，需要读取后面的类型
-                    if let Some(next) = chars.next() {
-                        if next == 'L' {
-                            while let Some(c) = chars.next() {
-                                if c == ';' {
-                                    break;
-                                }
-                            }
-                        }
-                    }
-                    count += 1;
-                }
-                _ => {}
+    /// 弹出一个非 null 引用，null 时抛出 NullPointerException
+    fn pop_non_null_ref(&mut self) -> Result<ObjRef> {
+        self.thread
+            .current_frame_mut()?
+            .pop_ref()?
+            .ok_or_else(|| JavaException::of("java/lang/NullPointerException").into())
+    }
+
+    /// 取出 pc 处指令从 `at` 开始的 `len` 个操作数字节，越过 code 末尾时返回错误
+    fn operand_bytes(code: &[u8], pc: usize, at: usize, len: usize) -> Result<&[u8]> {
+        code.get(at..at + len).ok_or_else(|| {
+            anyhow!(
+                "truncated instruction {} at pc {} (code length {})",
+                instructions::get_instruction_name(code[pc]),
+                pc,
+                code.len()
+            )
+        })
+    }
+
+    /// 读取 pc 处指令的第 `offset` 个操作数字节
+    fn read_u8(code: &[u8], pc: usize, offset: usize) -> Result<u8> {
+        Ok(Self::operand_bytes(code, pc, pc + offset, 1)?[0])
+    }
+
+    /// 读取紧跟在操作码后的大端序 u16（常量池索引）
+    fn read_u16(code: &[u8], pc: usize) -> Result<u16> {
+        let bytes = Self::operand_bytes(code, pc, pc + 1, 2)?;
+        Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    /// 读取紧跟在操作码后的大端序 i16（SIPUSH 的值、分支偏移）
+    fn read_i16(code: &[u8], pc: usize) -> Result<i16> {
+        let bytes = Self::operand_bytes(code, pc, pc + 1, 2)?;
+        Ok(i16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    /// 从字节码中读取大端序 i32（switch 指令的操作数，`at` 是绝对位置）
+    fn read_i32(code: &[u8], pc: usize, at: usize) -> Result<i32> {
+        let bytes = Self::operand_bytes(code, pc, at, 4)?;
+        Ok(i32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    /// 计算 pc 处分支指令的跳转目标，目标必须落在 code 数组内
+    fn branch_target(code: &[u8], pc: usize, offset: i64) -> Result<usize> {
+        let target = pc as i64 + offset;
+        if target < 0 || target >= code.len() as i64 {
+            return Err(anyhow!(
+                "branch target {} of {} at pc {} is outside the code (code length {})",
+                target,
+                instructions::get_instruction_name(code[pc]),
+                pc,
+                code.len()
+            ));
+        }
+        Ok(target as usize)
+    }
+
+    /// 把参数依次放入新栈帧的局部变量表
+    ///
+    /// 槽位按描述符计算：J/D 参数占两个槽位（值放在第一个槽位），下一个参数从 +2 开始。
+    /// 参数值的宽度与描述符不符时返回错误
+    fn store_args(
+        frame: &mut Frame,
+        start: usize,
+        descriptor: &str,
+        args: Vec<JvmValue>,
+    ) -> Result<()> {
+        let params = MethodDescriptor::parse(descriptor)?.params;
+        let mut slot = start;
+        for (param, arg) in params.iter().zip(args) {
+            let wide = param.slot_count() == 2;
+            if arg.is_wide() != wide {
+                return Err(anyhow!(
+                    "Argument for {} parameter of {} cannot be {:?}",
+                    param,
+                    descriptor,
+                    arg
+                ));
+            }
+            if wide {
+                frame.set_local_wide(slot, arg)?;
+                slot += 2;
+            } else {
+                frame.set_local(slot, arg)?;
+                slot += 1;
             }
         }
+        Ok(())
+    }
 
-        count
+    /// 按已链接的调用点调用静态方法：弹出参数，压入被调用者的栈帧（方法体使用声明类的常量池）
+    fn push_static_call(&mut self, site: &StaticCallSite, next_pc: usize) -> Result<()> {
+        let method = &site.method;
+        let caller = self.thread.current_frame_mut()?;
+        let mut args = Vec::with_capacity(site.arg_count);
+        for _ in 0..site.arg_count {
+            args.push(caller.pop()?);
+        }
+        args.reverse(); // 栈是LIFO，需要反转
+
+        let mut new_frame = Frame::new_with_context(
+            method.max_locals,
+            method.max_stack,
+            site.owner.clone(),
+            Arc::clone(&method.code),
+        );
+        new_frame.decoded = method.decoded.clone();
+        new_frame.exception_table = method.exception_table.clone();
+        new_frame.method_name = method.name.clone();
+        new_frame.descriptor = method.descriptor.clone();
+        Self::store_args(&mut new_frame, 0, &method.descriptor, args)?;
+        #[cfg(feature = "tracing")]
+        new_frame.enter_span(&method.name, &method.descriptor);
+        self.set_method_monitor(&mut new_frame, method)?;
+
+        // 调用者从调用指令之后继续，新栈帧从 pc 0 开始执行
+        self.thread.push_callee(new_frame, next_pc)?;
+        self.enter_method_monitor()
     }
 
     /// 执行方法（向后兼容，旧测试用）
     #[deprecated(note = "use execute_method_with_class instead")]
+    #[allow(deprecated)]
     pub fn execute_method(
         &mut self,
         code: &[u8],
@@ -778,7 +2556,7 @@ impl Interpreter {
         code: &[u8],
         frame: &mut Frame,
         pc: &mut usize,
-        current_class: &str,
+        _current_class: &str,
     ) -> Result<InstructionControl> {
         use instructions::opcodes::*;
 
@@ -790,43 +2568,43 @@ impl Interpreter {
 
             // ==================== 常量指令 ====================
             ICONST_M1 => {
-                frame.push(crate::runtime::frame::JvmValue::Int(-1));
+                frame.push(crate::runtime::frame::JvmValue::Int(-1))?;
                 *pc += 1;
             }
             ICONST_0 => {
-                frame.push(crate::runtime::frame::JvmValue::Int(0));
+                frame.push(crate::runtime::frame::JvmValue::Int(0))?;
                 *pc += 1;
             }
             ICONST_1 => {
-                frame.push(crate::runtime::frame::JvmValue::Int(1));
+                frame.push(crate::runtime::frame::JvmValue::Int(1))?;
                 *pc += 1;
             }
             ICONST_2 => {
-                frame.push(crate::runtime::frame::JvmValue::Int(2));
+                frame.push(crate::runtime::frame::JvmValue::Int(2))?;
                 *pc += 1;
             }
             ICONST_3 => {
-                frame.push(crate::runtime::frame::JvmValue::Int(3));
+                frame.push(crate::runtime::frame::JvmValue::Int(3))?;
                 *pc += 1;
             }
             ICONST_4 => {
-                frame.push(crate::runtime::frame::JvmValue::Int(4));
+                frame.push(crate::runtime::frame::JvmValue::Int(4))?;
                 *pc += 1;
             }
             ICONST_5 => {
-                frame.push(crate::runtime::frame::JvmValue::Int(5));
+                frame.push(crate::runtime::frame::JvmValue::Int(5))?;
                 *pc += 1;
             }
 
             BIPUSH => {
                 let value = code[*pc + 1] as i8;
-                frame.push(crate::runtime::frame::JvmValue::Int(value as i32));
+                frame.push(crate::runtime::frame::JvmValue::Int(value as i32))?;
                 *pc += 2;
             }
 
             SIPUSH => {
                 let value = i16::from_be_bytes([code[*pc + 1], code[*pc + 2]]);
-                frame.push(crate::runtime::frame::JvmValue::Int(value as i32));
+                frame.push(crate::runtime::frame::JvmValue::Int(value as i32))?;
                 *pc += 3;
             }
 
@@ -834,7 +2612,7 @@ impl Interpreter {
             ILOAD_0 | ILOAD_1 | ILOAD_2 | ILOAD_3 => {
                 let index = (opcode - ILOAD_0) as usize;
                 let value = frame.get_local(index)?.clone();
-                frame.push(value);
+                frame.push(value)?;
                 *pc += 1;
             }
 
@@ -850,21 +2628,21 @@ impl Interpreter {
             IADD => {
                 let v2 = frame.pop_int()?;
                 let v1 = frame.pop_int()?;
-                frame.push(crate::runtime::frame::JvmValue::Int(v1 + v2));
+                frame.push(crate::runtime::frame::JvmValue::Int(v1.wrapping_add(v2)))?;
                 *pc += 1;
             }
 
             ISUB => {
                 let v2 = frame.pop_int()?;
                 let v1 = frame.pop_int()?;
-                frame.push(crate::runtime::frame::JvmValue::Int(v1 - v2));
+                frame.push(crate::runtime::frame::JvmValue::Int(v1.wrapping_sub(v2)))?;
                 *pc += 1;
             }
 
             IMUL => {
                 let v2 = frame.pop_int()?;
                 let v1 = frame.pop_int()?;
-                frame.push(crate::runtime::frame::JvmValue::Int(v1 * v2));
+                frame.push(crate::runtime::frame::JvmValue::Int(v1.wrapping_mul(v2)))?;
                 *pc += 1;
             }
 
@@ -874,7 +2652,7 @@ impl Interpreter {
                 if v2 == 0 {
                     return Err(anyhow!("Division by zero"));
                 }
-                frame.push(crate::runtime::frame::JvmValue::Int(v1 / v2));
+                frame.push(crate::runtime::frame::JvmValue::Int(v1.wrapping_div(v2)))?;
                 *pc += 1;
             }
 
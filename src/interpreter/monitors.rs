@@ -0,0 +1,114 @@
+//! # 监视器（synchronized）
+//!
+//! 每个对象都带一个监视器（见 [`Monitor`](crate::runtime::heap::Monitor)），记录持有它的线程和重入次数。
+//! `monitorenter`/`monitorexit` 指令和 synchronized 方法都通过它实现互斥：
+//! synchronized 实例方法锁住 this，静态方法锁住类的 Class 对象。
+//!
+//! 监视器被其他线程持有时，当前线程被阻塞：调度器切换到其他线程，
+//! 直到监视器可以获取时才再调度回这个线程。
+//!
+//! ## 学习要点
+//! - 监视器可以重入：持有者再次获取只增加计数，计数减到 0 才真正释放
+//! - 不是持有者却执行 `monitorexit` 会抛出 IllegalMonitorStateException
+//! - javac 为 synchronized 代码块生成一个捕获所有异常的处理器，保证异常时也执行 `monitorexit`；
+//!   synchronized 方法没有这样的字节码，由虚拟机在方法返回和异常离开栈帧时释放
+//! - 阻塞在 `monitorenter` 的线程不前进 pc，调度回来时重新执行这条指令
+
+use super::Interpreter;
+use crate::runtime::frame::JvmValue;
+use crate::runtime::metaspace::MethodMetadata;
+use crate::runtime::{Frame, JavaException, JvmThread, ObjRef};
+use crate::Result;
+use anyhow::anyhow;
+
+impl Interpreter {
+    /// monitorenter：获取栈顶对象的监视器；被其他线程持有时阻塞当前线程，pc 留在这条指令上
+    pub(super) fn monitor_enter(&mut self) -> Result<()> {
+        let frame = self.thread.current_frame()?;
+        let object = match frame.peek()? {
+            JvmValue::Reference(Some(object)) => *object,
+            JvmValue::Reference(None) => {
+                return Err(JavaException::null_pointer("cannot enter the monitor of null").into())
+            }
+            other => return Err(anyhow!("monitorenter expects a reference, got {:?}", other)),
+        };
+        if self.heap.monitor_enter(object, self.threads.current())? {
+            let frame = self.thread.current_frame_mut()?;
+            frame.pop()?;
+            frame.pc += 1;
+        } else {
+            self.threads.block();
+        }
+        Ok(())
+    }
+
+    /// monitorexit：释放一次栈顶对象的监视器
+    pub(super) fn monitor_exit(&mut self) -> Result<()> {
+        let object = self.thread.current_frame_mut()?.pop_ref()?.ok_or_else(|| {
+            JavaException::null_pointer("cannot exit the monitor of null")
+        })?;
+        self.heap.monitor_exit(object, self.threads.current())?;
+        self.thread.current_frame_mut()?.pc += 1;
+        Ok(())
+    }
+
+    /// 记录 synchronized 方法的栈帧要锁住的对象（还不获取，见 `enter_method_monitor`）
+    ///
+    /// 实例方法的 this 已经放在局部变量 0 中
+    pub(super) fn set_method_monitor(&mut self, frame: &mut Frame, method: &MethodMetadata) -> Result<()> {
+        if !method.is_synchronized() {
+            return Ok(());
+        }
+        let object = if method.is_static {
+            self.class_mirror(&frame.class_name)?
+        } else {
+            match frame.get_local(0)? {
+                JvmValue::Reference(Some(this)) => *this,
+                other => return Err(anyhow!("synchronized method without receiver: {:?}", other)),
+            }
+        };
+        frame.monitor = Some(object);
+        Ok(())
+    }
+
+    /// 栈顶栈帧是 synchronized 方法且还没有获取监视器时获取它；
+    /// 监视器被其他线程持有时阻塞当前线程，方法等到调度回来时才开始执行
+    pub(super) fn enter_method_monitor(&mut self) -> Result<()> {
+        let owner = self.threads.current();
+        let frame = self.thread.current_frame_mut()?;
+        let Some(object) = frame.monitor.filter(|_| !frame.monitor_entered) else {
+            return Ok(());
+        };
+        if self.heap.monitor_enter(object, owner)? {
+            frame.monitor_entered = true;
+        } else {
+            self.threads.block();
+        }
+        Ok(())
+    }
+
+    /// 栈帧离开时（正常返回或异常）释放 synchronized 方法的监视器
+    pub(super) fn exit_method_monitor(&mut self, frame: &Frame) -> Result<()> {
+        match frame.monitor {
+            Some(object) if frame.monitor_entered => {
+                self.heap.monitor_exit(object, self.threads.current())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// 被阻塞的线程在等待哪个对象的监视器
+///
+/// 栈顶栈帧是还没有获取监视器的 synchronized 方法时是方法锁住的对象，
+/// 否则线程停在 `monitorenter` 上，等待的是操作数栈顶的对象
+pub(super) fn waiting_monitor(thread: &JvmThread) -> Option<ObjRef> {
+    let frame = thread.current_frame().ok()?;
+    match frame.monitor {
+        Some(object) if !frame.monitor_entered => Some(object),
+        _ => match frame.peek() {
+            Ok(JvmValue::Reference(Some(object))) => Some(*object),
+            _ => None,
+        },
+    }
+}
@@ -0,0 +1,96 @@
+//! # 本地方法
+//!
+//! `native` 方法没有 Code 属性，调用时由注册的 Rust 函数完成。
+//!
+//! ## 学习要点
+//! - 本地方法按"类名.方法名:描述符"查找，重载方法分别注册
+//! - 参数按调用约定排列：实例方法的第一个参数是 `this`，long/double 只占一个元素
+//! - 找不到实现时抛出 UnsatisfiedLinkError（真实 JVM 在 System.loadLibrary 的库里找不到符号时也是如此）
+//! - 本地方法不应再执行字节码：它们在调用者的栈帧上同步完成
+
+use super::Interpreter;
+use crate::runtime::frame::JvmValue;
+use crate::runtime::metaspace::builtin_super_class;
+use crate::Result;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// 本地方法的实现：参数（实例方法含 `this`）→ 返回值，void 方法返回 None
+pub type NativeFn = dyn Fn(&mut Interpreter, &[JvmValue]) -> Result<Option<JvmValue>>;
+
+/// 注册时传入的本地方法
+pub type NativeMethod = Box<NativeFn>;
+
+/// 已注册的本地方法
+#[derive(Default)]
+pub struct NativeRegistry {
+    methods: HashMap<String, Rc<NativeFn>>,
+}
+
+impl NativeRegistry {
+    /// 空注册表
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 带内置类库（见 `builtins`）的注册表
+    pub fn with_builtins() -> Self {
+        let mut registry = Self::new();
+        super::builtins::register_all(&mut registry);
+        registry
+    }
+
+    /// 注册（或替换）一个本地方法
+    pub fn register(&mut self, class_name: &str, method_name: &str, descriptor: &str, f: NativeMethod) {
+        self.methods
+            .insert(native_key(class_name, method_name, descriptor), Rc::from(f));
+    }
+
+    pub fn contains(&self, class_name: &str, method_name: &str, descriptor: &str) -> bool {
+        self.methods
+            .contains_key(&native_key(class_name, method_name, descriptor))
+    }
+
+    pub fn len(&self) -> usize {
+        self.methods.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.methods.is_empty()
+    }
+
+    /// 取出实现（克隆 Rc，调用时不再借用注册表）
+    pub(crate) fn get(
+        &self,
+        class_name: &str,
+        method_name: &str,
+        descriptor: &str,
+    ) -> Option<Rc<NativeFn>> {
+        self.methods
+            .get(&native_key(class_name, method_name, descriptor))
+            .cloned()
+    }
+
+    /// 查找 JDK 方法的实现类：本类没有注册时沿内置继承关系向上查找
+    ///
+    /// 例如 `IllegalStateException.<init>()V` 找到的是 `java/lang/Throwable`
+    pub(crate) fn find_jdk_owner(
+        &self,
+        class_name: &str,
+        method_name: &str,
+        descriptor: &str,
+    ) -> Option<String> {
+        let mut current = Some(class_name);
+        while let Some(name) = current {
+            if self.contains(name, method_name, descriptor) {
+                return Some(name.to_string());
+            }
+            current = builtin_super_class(name);
+        }
+        None
+    }
+}
+
+fn native_key(class_name: &str, method_name: &str, descriptor: &str) -> String {
+    format!("{}.{}:{}", class_name, method_name, descriptor)
+}
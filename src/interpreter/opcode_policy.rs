@@ -0,0 +1,166 @@
+//! # 未知操作码的处理策略
+//!
+//! 解释器还不支持的操作码（如 jsr/ret，或者根本不存在的操作码）默认直接报错。
+//! [`UnknownOpcodePolicy`] 让使用者选择别的做法：
+//!
+//! - `Error`：报错（默认）
+//! - `WarnAndStop`：停止运行，返回 [`UnknownOpcodeReport`]，说明执行到了哪里、
+//!   出错指令前后的反汇编和当时的栈轨迹
+//! - `Trap`：交给嵌入方的处理函数，它可以直接修改栈帧来模拟这条指令，然后继续执行
+//!
+//! ```
+//! use rsjvm::interpreter::opcode_policy::{TrapAction, UnknownOpcodePolicy};
+//!
+//! // 把 0xCB 当作 swap
+//! let policy = UnknownOpcodePolicy::trap(|opcode, pc, frame| {
+//!     if opcode != 0xCB {
+//!         return Ok(TrapAction::Abort);
+//!     }
+//!     let (top, below) = (frame.pop()?, frame.pop()?);
+//!     frame.push(top)?;
+//!     frame.push(below)?;
+//!     Ok(TrapAction::Emulated { next_pc: pc + 1 })
+//! });
+//! # let _ = policy;
+//! ```
+//!
+//! ## 学习要点
+//! - 静默跳过不认识的指令看起来"能跑"，但它本该消耗和压入的操作数没有变化，
+//!   之后的每条指令都在错误的栈上执行；所以要么报错，要么由知道语义的人模拟
+//! - 加载时的字节码校验会拒绝含未知操作码的方法，要执行到它们需要关闭 `verify`
+//! - 处理函数返回 Java 异常（`JavaException`）时，和其他指令抛出的异常一样可以被捕获
+
+use super::disasm::{disassemble_around, DisassembledInstruction};
+use super::instructions::get_instruction_name;
+use super::{InstructionControl, Interpreter};
+use crate::classfile::constant_pool::ConstantPool;
+use crate::runtime::{Frame, FrameInfo};
+use crate::Result;
+use anyhow::anyhow;
+use std::fmt;
+use std::sync::Arc;
+
+/// 出错指令前后各列出几条指令
+const CONTEXT_INSTRUCTIONS: usize = 3;
+
+/// 未知操作码的处理函数：参数是操作码、它的 pc 和当前栈帧
+pub type OpcodeTrap = dyn Fn(u8, usize, &mut Frame) -> Result<TrapAction> + Send + Sync;
+
+/// 遇到未知操作码时怎么办
+#[derive(Clone, Default)]
+pub enum UnknownOpcodePolicy {
+    /// 报错（默认）
+    #[default]
+    Error,
+    /// 停止运行，返回说明执行位置的 [`UnknownOpcodeReport`]
+    WarnAndStop,
+    /// 调用处理函数；它返回 `Abort` 时按 `Error` 处理
+    Trap(Arc<OpcodeTrap>),
+}
+
+impl UnknownOpcodePolicy {
+    /// 用闭包创建 `Trap` 策略
+    pub fn trap(
+        handler: impl Fn(u8, usize, &mut Frame) -> Result<TrapAction> + Send + Sync + 'static,
+    ) -> Self {
+        UnknownOpcodePolicy::Trap(Arc::new(handler))
+    }
+}
+
+impl fmt::Debug for UnknownOpcodePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            UnknownOpcodePolicy::Error => write!(f, "Error"),
+            UnknownOpcodePolicy::WarnAndStop => write!(f, "WarnAndStop"),
+            UnknownOpcodePolicy::Trap(_) => write!(f, "Trap(..)"),
+        }
+    }
+}
+
+/// 处理函数的决定
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrapAction {
+    /// 已经在栈帧上模拟了这条指令，从 `next_pc` 继续执行
+    Emulated { next_pc: usize },
+    /// 不处理，按 `Error` 策略报错
+    Abort,
+}
+
+/// `WarnAndStop` 策略停止运行时返回的错误
+#[derive(Debug, Clone)]
+pub struct UnknownOpcodeReport {
+    pub opcode: u8,
+    /// 停下之前已执行的指令条数
+    pub instructions: u64,
+    /// 出错指令前后的反汇编，包括它自己
+    pub context: Vec<DisassembledInstruction>,
+    /// 停下时的栈轨迹，`frames[0]` 是出错的方法
+    pub frames: Vec<FrameInfo>,
+}
+
+impl UnknownOpcodeReport {
+    /// 出错指令的 pc
+    pub fn pc(&self) -> usize {
+        self.frames.first().map_or(0, |frame| frame.pc)
+    }
+}
+
+impl fmt::Display for UnknownOpcodeReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "unknown opcode 0x{:02X} ({}) after {} instructions",
+            self.opcode,
+            get_instruction_name(self.opcode),
+            self.instructions
+        )?;
+        if let Some(top) = self.frames.first() {
+            write!(f, " at {}", top)?;
+        }
+        for instruction in &self.context {
+            let marker = if instruction.pc == self.pc() { "=>" } else { "  " };
+            write!(f, "\n{} {}", marker, instruction)?;
+        }
+        for frame in &self.frames {
+            write!(f, "\n\tat {}", frame)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for UnknownOpcodeReport {}
+
+impl Interpreter {
+    /// 栈顶栈帧 `pc` 处的操作码不被支持：按 `unknown_opcode_policy` 处理
+    pub(super) fn unknown_opcode(&mut self, opcode: u8, pc: usize) -> Result<InstructionControl> {
+        let unknown = || anyhow!("Unknown opcode: 0x{:02X} at pc {}", opcode, pc);
+        match self.options.unknown_opcode_policy.clone() {
+            UnknownOpcodePolicy::Error => Err(unknown()),
+            UnknownOpcodePolicy::WarnAndStop => {
+                let frame = self.thread.current_frame()?;
+                let empty = ConstantPool::empty();
+                let cp = match self.metaspace.get_class(&frame.class_name) {
+                    Ok(class) => &class.constant_pool,
+                    Err(_) => &empty,
+                };
+                Err(UnknownOpcodeReport {
+                    opcode,
+                    instructions: self.instructions_executed,
+                    context: disassemble_around(&frame.code, cp, pc, CONTEXT_INSTRUCTIONS),
+                    frames: self.stack_trace(),
+                }
+                .into())
+            }
+            UnknownOpcodePolicy::Trap(handler) => {
+                let frame = self.thread.current_frame_mut()?;
+                match handler(opcode, pc, frame)? {
+                    TrapAction::Emulated { next_pc } => {
+                        frame.pc = next_pc;
+                        Ok(InstructionControl::Continue)
+                    }
+                    TrapAction::Abort => Err(unknown()),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,194 @@
+//! # 性能剖析
+//!
+//! 开启后（`set_profiling(true)`，CLI 的 `--profile`）统计：
+//! - 每种操作码执行了多少次
+//! - 每个方法被调用了多少次、自身执行了多少条指令（不含被调用者）
+//! - 总共执行了多少条指令
+//!
+//! ## 学习要点
+//! - 解释器的开销和执行的指令条数成正比，指令数是比耗时更稳定的度量
+//! - 操作码计数用 256 项的数组，不需要哈希；方法在栈帧第一次执行指令时登记一次，
+//!   之后按栈帧里记下的编号计数
+//! - 关闭时主循环只多一次 `Option` 判断
+
+use super::{instructions, Interpreter};
+use crate::Result;
+use std::collections::HashMap;
+use std::fmt;
+
+/// 剖析表中显示的操作码和方法数
+const TOP_ENTRIES: usize = 10;
+
+/// 一个方法的剖析数据
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MethodProfile {
+    pub class_name: String,
+    /// "name:descriptor"
+    pub method_key: String,
+    /// 调用次数（创建的栈帧数）
+    pub invocations: u64,
+    /// 在这个方法的栈帧中执行的指令数
+    pub instructions: u64,
+}
+
+/// 剖析数据
+#[derive(Debug, Clone)]
+pub struct ProfileData {
+    opcode_counts: [u64; 256],
+    methods: Vec<MethodProfile>,
+    /// (类名, 方法键) → `methods` 中的编号
+    method_index: HashMap<(String, String), usize>,
+    total_instructions: u64,
+}
+
+impl Default for ProfileData {
+    fn default() -> Self {
+        ProfileData {
+            opcode_counts: [0; 256],
+            methods: Vec::new(),
+            method_index: HashMap::new(),
+            total_instructions: 0,
+        }
+    }
+}
+
+impl ProfileData {
+    pub fn total_instructions(&self) -> u64 {
+        self.total_instructions
+    }
+
+    /// 操作码执行次数
+    pub fn opcode_count(&self, opcode: u8) -> u64 {
+        self.opcode_counts[opcode as usize]
+    }
+
+    /// 执行次数最多的 `n` 个操作码，次数相同时按操作码排列
+    pub fn top_opcodes(&self, n: usize) -> Vec<(u8, u64)> {
+        let mut counts: Vec<(u8, u64)> = (0..=255u8)
+            .map(|opcode| (opcode, self.opcode_counts[opcode as usize]))
+            .filter(|&(_, count)| count > 0)
+            .collect();
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        counts.truncate(n);
+        counts
+    }
+
+    pub fn method(&self, class_name: &str, method_key: &str) -> Option<&MethodProfile> {
+        self.methods
+            .iter()
+            .find(|m| m.class_name == class_name && m.method_key == method_key)
+    }
+
+    /// 执行指令最多的 `n` 个方法，相同时按类名、方法键排列
+    pub fn hottest_methods(&self, n: usize) -> Vec<&MethodProfile> {
+        let mut methods: Vec<&MethodProfile> = self.methods.iter().collect();
+        methods.sort_by(|a, b| {
+            b.instructions
+                .cmp(&a.instructions)
+                .then_with(|| a.class_name.cmp(&b.class_name))
+                .then_with(|| a.method_key.cmp(&b.method_key))
+        });
+        methods.truncate(n);
+        methods
+    }
+
+    /// 登记一次方法调用，返回方法编号
+    fn enter_method(&mut self, class_name: &str, method_key: String) -> usize {
+        let key = (class_name.to_string(), method_key);
+        let slot = match self.method_index.get(&key) {
+            Some(&slot) => slot,
+            None => {
+                let slot = self.methods.len();
+                self.methods.push(MethodProfile {
+                    class_name: key.0.clone(),
+                    method_key: key.1.clone(),
+                    invocations: 0,
+                    instructions: 0,
+                });
+                self.method_index.insert(key, slot);
+                slot
+            }
+        };
+        self.methods[slot].invocations += 1;
+        slot
+    }
+}
+
+impl fmt::Display for ProfileData {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "=== 性能剖析 ({} 条指令) ===", self.total_instructions)?;
+        writeln!(f, "  {:<16} {:>10} {:>7}", "opcode", "count", "%")?;
+        for (opcode, count) in self.top_opcodes(TOP_ENTRIES) {
+            let percent = count as f64 * 100.0 / self.total_instructions.max(1) as f64;
+            writeln!(
+                f,
+                "  {:<16} {:>10} {:>6.1}%",
+                instructions::get_instruction_name(opcode),
+                count,
+                percent
+            )?;
+        }
+
+        writeln!(f, "\n=== 热点方法 ===")?;
+        writeln!(f, "  {:<40} {:>8} {:>12}", "method", "calls", "instructions")?;
+        for method in self.hottest_methods(TOP_ENTRIES) {
+            let name = format!("{}.{}", method.class_name, method.method_key);
+            writeln!(
+                f,
+                "  {:<40} {:>8} {:>12}",
+                name, method.invocations, method.instructions
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Interpreter {
+    /// 开启或关闭性能剖析；关闭时丢弃已收集的数据
+    pub fn set_profiling(&mut self, enabled: bool) {
+        match (enabled, self.profile.is_some()) {
+            (true, false) => self.profile = Some(ProfileData::default()),
+            (false, true) => {
+                self.profile = None;
+                self.forget_profile_slots();
+            }
+            _ => {}
+        }
+    }
+
+    /// 取出目前收集的剖析数据，之后从零开始统计；未开启剖析时返回 None
+    pub fn take_profile(&mut self) -> Option<ProfileData> {
+        let data = self.profile.replace(ProfileData::default())?;
+        self.forget_profile_slots();
+        Some(data)
+    }
+
+    /// 记录即将执行的一条指令（只在开启剖析时调用）
+    pub(super) fn record_profile(&mut self, opcode: u8) -> Result<()> {
+        let Some(profile) = self.profile.as_mut() else {
+            return Ok(());
+        };
+        let frame = self.thread.current_frame_mut()?;
+        let slot = match frame.profile_slot {
+            Some(slot) => slot,
+            // 栈帧执行的第一条指令：登记一次调用
+            None => {
+                let method_key = format!("{}:{}", frame.method_name, frame.descriptor);
+                let slot = profile.enter_method(&frame.class_name, method_key);
+                frame.profile_slot = Some(slot);
+                slot
+            }
+        };
+        profile.opcode_counts[opcode as usize] += 1;
+        profile.methods[slot].instructions += 1;
+        profile.total_instructions += 1;
+        Ok(())
+    }
+
+    /// 剖析数据重新开始时，栈帧里记下的方法编号不再有效
+    fn forget_profile_slots(&mut self) {
+        for frame in self.thread.frames_mut() {
+            frame.profile_slot = None;
+        }
+    }
+}
@@ -0,0 +1,466 @@
+//! # 执行快照
+//!
+//! [`Interpreter::snapshot`] 记下运行到一半的程序的全部状态：线程栈的每个栈帧（类、方法、pc、
+//! 局部变量表、操作数栈）、堆上的对象和所有类的静态字段；[`Interpreter::restore`] 把解释器放回这个状态。
+//! 和单步执行（`stepping`）配合就能"时间旅行"：在任意两条指令之间存档，之后回到存档处重新执行。
+//!
+//! ```no_run
+//! # use rsjvm::interpreter::{Interpreter, stepping::RunOutcome};
+//! # let mut interpreter = Interpreter::new();
+//! interpreter.start_method("Loop", "sum:(I)I", vec![])?;
+//! for _ in 0..20 {
+//!     interpreter.step()?;
+//! }
+//! let checkpoint = interpreter.snapshot()?;
+//! let json = checkpoint.to_json()?;  // 可以保存到文件
+//! let first = interpreter.resume()?;
+//! interpreter.restore(&checkpoint)?;
+//! let second = interpreter.resume()?;  // 与 first 相同
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+//!
+//! ## 学习要点
+//! - 字节码和类元数据在运行中不会改变，快照只记类名和方法键，恢复时到方法区重新查找，
+//!   所以恢复前这些类必须已经加载
+//! - 对象引用是「槽位索引 + 代数」，快照原样保存每个槽位（包括空槽位的代数），
+//!   恢复后栈帧、字段和数组中的引用不需要改写
+//! - 字符串常量池、Class 对象和装箱缓存也指向堆上的对象，必须随堆一起保存，
+//!   否则恢复后同一个字面量会得到不同的对象
+//! - 多线程程序的其他线程还有自己的栈和调度状态，这里只支持入口线程
+
+use super::stepping::Execution;
+use super::Interpreter;
+use crate::runtime::frame::JvmValue;
+use crate::runtime::heap::{ArrayType, Monitor, Object, ObjectKind};
+use crate::runtime::layout::FieldLayout;
+use crate::runtime::metaspace::{method_key, ClassState};
+use crate::runtime::{Frame, Heap, JvmThread, ObjRef};
+use crate::Result;
+use anyhow::{anyhow, Context};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Instant;
+
+/// 装箱缓存中的包装类（缓存的键是 `&'static str`）
+const BOXED_CLASSES: [&str; 4] = [
+    "java/lang/Integer",
+    "java/lang/Long",
+    "java/lang/Character",
+    "java/lang/Boolean",
+];
+
+/// 解释器在两条指令之间的状态
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecutionSnapshot {
+    /// 线程栈，栈底（入口方法）在前
+    pub frames: Vec<SavedFrame>,
+    pub heap: SavedHeap,
+    /// 已加载类的初始化状态和静态字段，按加载顺序
+    pub classes: Vec<SavedClass>,
+    /// 内置 JDK 类的静态字段：类名 → 字段名 → 值
+    pub jdk_static_fields: BTreeMap<String, BTreeMap<String, JvmValue>>,
+    /// 驻留的字符串：内容 → 对象
+    pub interned_strings: BTreeMap<String, ObjRef>,
+    /// 每个类的 java/lang/Class 对象
+    pub class_mirrors: BTreeMap<String, ObjRef>,
+    /// 装箱缓存
+    pub boxed_values: Vec<BoxedValue>,
+    /// 本次运行已执行的指令条数
+    pub instructions: u64,
+}
+
+/// 一个栈帧
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedFrame {
+    pub class_name: String,
+    pub method_name: String,
+    pub descriptor: String,
+    /// 下一条要执行的指令
+    pub pc: usize,
+    pub locals: Vec<JvmValue>,
+    /// 操作数栈，栈底在前
+    pub operands: Vec<JvmValue>,
+    /// 正在执行哪个类的 `<clinit>`
+    pub initializing_class: Option<String>,
+    /// synchronized 方法锁住的对象，以及是否已经获取
+    pub monitor: Option<ObjRef>,
+    pub monitor_entered: bool,
+}
+
+/// 堆的所有槽位
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedHeap {
+    /// 按槽位索引排列，已回收的槽位也保存（它的代数让旧引用继续失效）
+    pub slots: Vec<SavedSlot>,
+    /// 空闲列表
+    pub free_list: Vec<u32>,
+    /// 上次回收以来分配的对象数
+    pub allocated_since_gc: usize,
+    /// 新生代对象（分代回收时）
+    pub nursery: Option<Vec<ObjRef>>,
+    /// 记忆集（分代回收时）
+    pub remembered: Option<Vec<ObjRef>>,
+}
+
+/// 一个堆槽位
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedSlot {
+    pub generation: u32,
+    /// 槽位中的对象，已回收时为 None
+    pub object: Option<SavedObject>,
+    /// 新生代对象熬过的 minor GC 次数
+    pub age: Option<u8>,
+}
+
+/// 一个对象
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedObject {
+    pub class_name: String,
+    pub kind: SavedObjectKind,
+    pub monitor: Option<Monitor>,
+    pub identity_hash: Option<i32>,
+}
+
+/// 对象内容；实例的字段布局不保存，恢复时从方法区重新计算
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum SavedObjectKind {
+    /// 按布局槽位排列的字段：(声明字段的类, 字段名, 值)
+    Instance { fields: Vec<(String, String, JvmValue)> },
+    Array {
+        element_type: ArrayType,
+        elements: Vec<JvmValue>,
+    },
+    String(String),
+    StringBuilder(String),
+}
+
+/// 一个类的运行时状态
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedClass {
+    pub class_name: String,
+    pub state: ClassState,
+    pub static_fields: BTreeMap<String, JvmValue>,
+}
+
+/// 装箱缓存的一项：包装类、值和缓存的对象
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BoxedValue {
+    pub class_name: String,
+    pub value: i64,
+    pub object: ObjRef,
+}
+
+impl ExecutionSnapshot {
+    /// JSON 文本
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    /// 从 JSON 文本读取
+    pub fn from_json(json: &str) -> Result<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+}
+
+impl Interpreter {
+    /// 记下当前的执行状态；只支持单线程程序
+    pub fn snapshot(&self) -> Result<ExecutionSnapshot> {
+        if !self.threads.is_idle() {
+            return Err(anyhow!("cannot snapshot a program with running threads"));
+        }
+        let frames = self
+            .thread
+            .frames()
+            .rev()
+            .map(|frame| SavedFrame {
+                class_name: frame.class_name.to_string(),
+                method_name: frame.method_name.to_string(),
+                descriptor: frame.descriptor.to_string(),
+                pc: frame.pc,
+                locals: frame.locals().to_vec(),
+                operands: frame.operands().to_vec(),
+                initializing_class: frame.initializing_class.clone(),
+                monitor: frame.monitor,
+                monitor_entered: frame.monitor_entered,
+            })
+            .collect();
+        let classes = self
+            .metaspace
+            .classes_in_load_order()
+            .into_iter()
+            .map(|class| SavedClass {
+                class_name: class.name.to_string(),
+                state: class.state,
+                static_fields: class
+                    .static_fields
+                    .iter()
+                    .map(|(name, value)| (name.to_string(), value.clone()))
+                    .collect(),
+            })
+            .collect();
+        let jdk_static_fields = self
+            .metaspace
+            .jdk_static_fields()
+            .iter()
+            .map(|(class, fields)| (class.clone(), fields.clone().into_iter().collect()))
+            .collect();
+        let boxed_values = self
+            .boxed_values
+            .iter()
+            .map(|(&(class_name, value), &object)| BoxedValue {
+                class_name: class_name.to_string(),
+                value,
+                object,
+            })
+            .collect();
+        Ok(ExecutionSnapshot {
+            frames,
+            heap: snapshot_heap(&self.heap),
+            classes,
+            jdk_static_fields,
+            interned_strings: self.interned_strings.clone().into_iter().collect(),
+            class_mirrors: self.class_mirrors.clone().into_iter().collect(),
+            boxed_values,
+            instructions: self.instructions_executed,
+        })
+    }
+
+    /// 回到快照记下的状态；栈帧不为空时可以接着 `step()`/`resume()`
+    ///
+    /// 快照引用的类（栈帧、静态字段、对象的类）必须已经加载，方法必须存在，否则返回错误，
+    /// 解释器保持不变。快照之后才初始化的类回到 Linked，下次使用时重新执行 `<clinit>`。
+    /// 之前 `pin` 得到的句柄全部失效
+    pub fn restore(&mut self, snapshot: &ExecutionSnapshot) -> Result<()> {
+        // 先检查并构造好所有内容，出错时不改动解释器
+        let mut thread = JvmThread::with_max_frames(self.options.max_frames);
+        for frame in &snapshot.frames {
+            let restored = self.restore_frame(frame).with_context(|| {
+                format!(
+                    "cannot restore frame {}.{}{}",
+                    frame.class_name, frame.method_name, frame.descriptor
+                )
+            })?;
+            thread.push_frame(restored)?;
+        }
+        for class in &snapshot.classes {
+            self.metaspace.get_class(&class.class_name)?;
+        }
+        let mut slots = Vec::with_capacity(snapshot.heap.slots.len());
+        for slot in &snapshot.heap.slots {
+            let object = match &slot.object {
+                Some(object) => Some(self.restore_object(object)?),
+                None => None,
+            };
+            slots.push((slot.generation, object, slot.age));
+        }
+        let mut boxed_values = HashMap::new();
+        for boxed in &snapshot.boxed_values {
+            let class_name = BOXED_CLASSES
+                .into_iter()
+                .find(|&name| name == boxed.class_name)
+                .ok_or_else(|| anyhow!("not a cached wrapper class: {}", boxed.class_name))?;
+            boxed_values.insert((class_name, boxed.value), boxed.object);
+        }
+
+        let snapshot_classes: HashSet<&str> = snapshot
+            .classes
+            .iter()
+            .map(|class| class.class_name.as_str())
+            .collect();
+        let loaded_later: Vec<String> = self
+            .metaspace
+            .classes_in_load_order()
+            .into_iter()
+            .map(|class| class.name.to_string())
+            .filter(|name| !snapshot_classes.contains(name.as_str()))
+            .collect();
+        for name in loaded_later {
+            let class = self.metaspace.get_class_mut(&name)?;
+            class.reset_static_fields();
+            if matches!(class.state, ClassState::Initializing | ClassState::Initialized) {
+                class.state = ClassState::Linked;
+            }
+        }
+        for saved in &snapshot.classes {
+            let class = self.metaspace.get_class_mut(&saved.class_name)?;
+            class.state = saved.state;
+            class.static_fields = saved
+                .static_fields
+                .iter()
+                .map(|(name, value)| (name.as_str().into(), value.clone()))
+                .collect();
+        }
+        self.metaspace.replace_jdk_static_fields(
+            snapshot
+                .jdk_static_fields
+                .iter()
+                .map(|(class, fields)| (class.clone(), fields.clone().into_iter().collect()))
+                .collect(),
+        );
+
+        let young = snapshot.heap.nursery.as_ref().map(|objects| {
+            let remembered = snapshot.heap.remembered.iter().flatten().copied().collect();
+            (objects.clone(), remembered)
+        });
+        self.heap.restore_slots(
+            slots,
+            snapshot.heap.free_list.clone(),
+            snapshot.heap.allocated_since_gc,
+            young,
+        );
+        self.thread = thread;
+        self.threads.clear();
+        self.interned_strings = snapshot.interned_strings.clone().into_iter().collect();
+        self.class_mirrors = snapshot.class_mirrors.clone().into_iter().collect();
+        self.boxed_values = boxed_values;
+        // 宿主句柄指向的是被替换掉的堆
+        self.handles.clear();
+        self.instructions_executed = snapshot.instructions;
+        self.next_check = snapshot.instructions;
+        self.deadline = self.options.time_limit.map(|limit| Instant::now() + limit);
+        self.execution = (!snapshot.frames.is_empty()).then(Execution::default);
+        Ok(())
+    }
+
+    /// 按类名和方法键重新查找方法，创建栈帧
+    fn restore_frame(&self, saved: &SavedFrame) -> Result<Frame> {
+        let key = method_key(&saved.method_name, &saved.descriptor);
+        let method = self
+            .metaspace
+            .get_class(&saved.class_name)?
+            .methods
+            .get(key.as_str())
+            .cloned()
+            .ok_or_else(|| anyhow!("Method not found: {}.{}", saved.class_name, key))?;
+        if saved.pc >= method.code.len() {
+            return Err(anyhow!("pc {} is beyond the code ({} bytes)", saved.pc, method.code.len()));
+        }
+        let mut frame = Frame::new_with_context(
+            method.max_locals,
+            method.max_stack,
+            saved.class_name.as_str(),
+            method.code.clone(),
+        );
+        frame.method_name = method.name.clone();
+        frame.descriptor = method.descriptor.clone();
+        frame.exception_table = method.exception_table.clone();
+        frame.decoded = method.decoded.clone();
+        frame.pc = saved.pc;
+        frame.initializing_class = saved.initializing_class.clone();
+        frame.monitor = saved.monitor;
+        frame.monitor_entered = saved.monitor_entered;
+        frame.restore_values(saved.locals.clone(), saved.operands.clone())?;
+        Ok(frame)
+    }
+
+    /// 创建对象；实例共享方法区中类的字段布局，内置 JDK 对象（以及运行中追加过字段的对象）
+    /// 按快照中的字段重建布局
+    fn restore_object(&mut self, saved: &SavedObject) -> Result<Object> {
+        let kind = match &saved.kind {
+            SavedObjectKind::Instance { fields } => {
+                let shared = if self.metaspace.is_class_loaded(&saved.class_name) {
+                    Some(self.metaspace.field_layout(&saved.class_name))
+                } else if saved.class_name.starts_with("java/") {
+                    None
+                } else {
+                    return Err(anyhow!("Class not found: {}", saved.class_name));
+                };
+                let same_fields = |layout: &Arc<FieldLayout>| {
+                    layout.len() == fields.len()
+                        && layout.keys().iter().zip(fields).all(|((class, field), saved)| {
+                            class.as_ref() == saved.0 && field.as_ref() == saved.1
+                        })
+                };
+                let layout = match shared.filter(same_fields) {
+                    Some(layout) => layout,
+                    None => {
+                        let mut layout = FieldLayout::new();
+                        for (class_name, name, value) in fields {
+                            let key = (class_name.as_str().into(), name.as_str().into());
+                            layout.push(key, zero_value(value));
+                        }
+                        Arc::new(layout)
+                    }
+                };
+                ObjectKind::Instance {
+                    layout,
+                    fields: fields.iter().map(|(_, _, value)| value.clone()).collect(),
+                }
+            }
+            SavedObjectKind::Array {
+                element_type,
+                elements,
+            } => ObjectKind::Array {
+                element_type: *element_type,
+                elements: elements.clone(),
+            },
+            SavedObjectKind::String(text) => ObjectKind::String(text.clone()),
+            SavedObjectKind::StringBuilder(text) => ObjectKind::StringBuilder(text.clone()),
+        };
+        Ok(Object {
+            class_name: saved.class_name.as_str().into(),
+            kind,
+            monitor: saved.monitor,
+            identity_hash: saved.identity_hash,
+        })
+    }
+}
+
+fn snapshot_heap(heap: &Heap) -> SavedHeap {
+    let slots = heap
+        .slot_states()
+        .map(|(generation, object, age)| SavedSlot {
+            generation,
+            object: object.map(snapshot_object),
+            age,
+        })
+        .collect();
+    let nursery = heap.nursery();
+    SavedHeap {
+        slots,
+        free_list: heap.free_list().to_vec(),
+        allocated_since_gc: heap.allocated_since_gc(),
+        nursery: nursery.map(|nursery| nursery.objects.clone()),
+        remembered: nursery.map(|nursery| nursery.remembered().collect()),
+    }
+}
+
+fn snapshot_object(object: &Object) -> SavedObject {
+    let kind = match &object.kind {
+        ObjectKind::Instance { layout, fields } => SavedObjectKind::Instance {
+            fields: layout
+                .keys()
+                .iter()
+                .zip(fields)
+                .map(|((class, name), value)| (class.to_string(), name.to_string(), value.clone()))
+                .collect(),
+        },
+        ObjectKind::Array {
+            element_type,
+            elements,
+        } => SavedObjectKind::Array {
+            element_type: *element_type,
+            elements: elements.clone(),
+        },
+        ObjectKind::String(text) => SavedObjectKind::String(text.clone()),
+        ObjectKind::StringBuilder(text) => SavedObjectKind::StringBuilder(text.clone()),
+    };
+    SavedObject {
+        class_name: object.class_name.to_string(),
+        kind,
+        monitor: object.monitor,
+        identity_hash: object.identity_hash,
+    }
+}
+
+/// 与 `value` 同类的零值，用作重建布局的默认值
+fn zero_value(value: &JvmValue) -> JvmValue {
+    match value {
+        JvmValue::Int(_) => JvmValue::Int(0),
+        JvmValue::Long(_) => JvmValue::Long(0),
+        JvmValue::Float(_) => JvmValue::Float(0.0),
+        JvmValue::Double(_) => JvmValue::Double(0.0),
+        JvmValue::Reference(_) => JvmValue::Reference(None),
+    }
+}
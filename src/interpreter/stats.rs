@@ -0,0 +1,129 @@
+//! # 运行统计
+//!
+//! 运行结束后汇总方法区和堆的状态，供 CLI 的 `--stats` 打印。
+//!
+//! ## 学习要点
+//! - 方法区按类保存元数据，堆按对象保存实例数据
+//! - 按类统计存活对象数是分析内存占用的第一步（类似 `jmap -histo`）
+//!
+//! ## 顺序保证
+//! 输出只依赖运行内容，同样的程序运行两次得到完全相同的文本：
+//! - 类表按加载顺序排列
+//! - 堆直方图按对象数从多到少排列，数量相同时按类名排列
+
+use super::Interpreter;
+use crate::runtime::{MetaspaceStats, ResolutionStats};
+use std::collections::HashMap;
+use std::fmt;
+
+/// 一个已加载类的统计
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClassStats {
+    /// 类名
+    pub name: String,
+    /// 方法数
+    pub methods: usize,
+    /// 字段数
+    pub fields: usize,
+}
+
+/// 堆直方图中的一行
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeapHistogramEntry {
+    /// 类名（数组为描述符）
+    pub class_name: String,
+    /// 存活对象数
+    pub count: usize,
+    /// 这些对象占用的槽位总数（实例字段数或数组长度之和），不含对象头
+    pub slots: usize,
+}
+
+/// 一次运行的统计
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunStats {
+    /// 已加载的类，按加载顺序
+    pub classes: Vec<ClassStats>,
+    /// 堆直方图，按对象数降序、类名升序
+    pub heap_histogram: Vec<HeapHistogramEntry>,
+    /// 堆中存活对象总数
+    pub live_objects: usize,
+    /// 符号引用解析的否定结果和否定缓存命中次数
+    pub resolution: ResolutionStats,
+    /// 方法区占用
+    pub metaspace: MetaspaceStats,
+}
+
+impl Interpreter {
+    /// 汇总当前方法区和堆的统计
+    pub fn run_stats(&self) -> RunStats {
+        let classes = self
+            .metaspace
+            .classes_in_load_order()
+            .into_iter()
+            .map(|class| ClassStats {
+                name: class.name.to_string(),
+                methods: class.methods.len(),
+                fields: class.fields.len(),
+            })
+            .collect();
+
+        let mut counts: HashMap<&str, (usize, usize)> = HashMap::new();
+        for (_, obj) in self.heap.iter() {
+            let entry = counts.entry(&obj.class_name).or_default();
+            entry.0 += 1;
+            entry.1 += obj.slot_count();
+        }
+        let mut heap_histogram: Vec<_> = counts
+            .into_iter()
+            .map(|(class_name, (count, slots))| HeapHistogramEntry {
+                class_name: class_name.to_string(),
+                count,
+                slots,
+            })
+            .collect();
+        heap_histogram.sort_by(|a, b| {
+            b.count
+                .cmp(&a.count)
+                .then_with(|| a.class_name.cmp(&b.class_name))
+        });
+
+        RunStats {
+            classes,
+            heap_histogram,
+            live_objects: self.heap.object_count(),
+            resolution: self.metaspace.resolution_stats(),
+            metaspace: self.metaspace.stats(),
+        }
+    }
+}
+
+impl fmt::Display for RunStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "=== 已加载的类 ({}) ===", self.classes.len())?;
+        writeln!(f, "  {:>3}  {:<32} {:>7} {:>6}", "#", "class", "methods", "fields")?;
+        for (i, class) in self.classes.iter().enumerate() {
+            writeln!(
+                f,
+                "  {:>3}  {:<32} {:>7} {:>6}",
+                i, class.name, class.methods, class.fields
+            )?;
+        }
+
+        writeln!(f, "\n=== 堆 ({} 个存活对象) ===", self.live_objects)?;
+        writeln!(f, "  {:>7}  class", "objects")?;
+        for entry in &self.heap_histogram {
+            writeln!(f, "  {:>7}  {}", entry.count, entry.class_name)?;
+        }
+
+        writeln!(f, "\n=== 符号解析 ===")?;
+        writeln!(f, "  否定结果: {}", self.resolution.misses)?;
+        writeln!(f, "  否定缓存命中: {}", self.resolution.negative_hits)?;
+
+        writeln!(f, "\n=== 方法区 ===")?;
+        writeln!(f, "  类: {}", self.metaspace.classes)?;
+        writeln!(f, "  方法: {}", self.metaspace.methods)?;
+        writeln!(f, "  字节码: {} 字节", self.metaspace.bytecode_bytes)?;
+        writeln!(f, "  已缓存的解析结果: {}", self.metaspace.resolved_entries)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,59 @@
+//! # 标准输出
+//!
+//! Java 程序的输出（`System.out.println` 等）写到解释器持有的 writer，
+//! 默认是进程的 stdout，嵌入方和测试可以换成内存缓冲区。
+//! 线程中未捕获异常的报告（`Exception in thread ...`）同样写到解释器持有的 stderr writer。
+//!
+//! ## 学习要点
+//! - 程序输出和解释器自身的诊断信息分开：日志、`--trace` 都写到 stderr，
+//!   捕获到的输出只包含 Java 程序打印的内容
+//! - 默认 writer 与 Rust 的 `println!` 共用同一个 stdout 句柄，两者的输出顺序不会错乱
+
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex};
+
+/// 可以共享的内存缓冲区：一份交给解释器写入，另一份留给调用方读取
+#[derive(Debug, Clone, Default)]
+pub struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl SharedBuffer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 取出目前写入的全部字节并清空缓冲区
+    pub fn take(&self) -> Vec<u8> {
+        std::mem::take(&mut *self.lock())
+    }
+
+    /// 目前写入的内容（按 UTF-8 解码）
+    pub fn contents(&self) -> String {
+        String::from_utf8_lossy(&self.lock()).into_owned()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<u8>> {
+        // 写入方 panic 时缓冲区里的字节仍然可用
+        self.0.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.lock().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// 默认的程序输出：进程的 stdout
+pub(crate) fn default_stdout() -> Box<dyn Write + Send> {
+    Box::new(io::stdout())
+}
+
+/// 默认的错误输出：进程的 stderr
+pub(crate) fn default_stderr() -> Box<dyn Write + Send> {
+    Box::new(io::stderr())
+}
@@ -0,0 +1,201 @@
+//! # 单步执行与断点
+//!
+//! `start_method` 只压入入口栈帧，不执行任何指令；之后由调用方推进：
+//! `step()` 执行一条指令，`resume()` 一直运行到下一个断点或入口方法返回。
+//! 暂停期间可以用 `current_locals()`/`current_stack()` 查看栈顶栈帧。
+//!
+//! ```no_run
+//! # use rsjvm::interpreter::{Interpreter, stepping::RunOutcome};
+//! # let mut interpreter = Interpreter::new();
+//! interpreter.set_breakpoint("Calculator", "divide:(II)I", 2);
+//! interpreter.start_method("Calculator", "average:(II)I", vec![])?;
+//! if let RunOutcome::Breakpoint(hit) = interpreter.resume()? {
+//!     println!("paused at {} pc={}", hit.method_key, hit.pc);
+//!     println!("locals: {:?}", interpreter.current_locals()?);
+//! }
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+//!
+//! ## 学习要点
+//! - 解释器的状态全部在线程栈里（每个栈帧的 pc、局部变量、操作数栈），
+//!   所以主循环可以在任意两条指令之间停下，之后原样继续
+//! - 断点在指令执行之前命中：停在断点上时 pc 指向的指令还没有执行
+//! - 从断点继续时先执行断点处的指令，否则会一直停在同一个断点上
+
+use super::{instructions, InstructionControl, Interpreter};
+use crate::runtime::frame::JvmValue;
+use crate::Result;
+use anyhow::anyhow;
+
+/// 断点位置：类名、方法键（"name:descriptor"）和 pc
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Breakpoint {
+    pub class_name: String,
+    pub method_key: String,
+    pub pc: usize,
+}
+
+/// 进行中的单步执行
+#[derive(Debug, Default)]
+pub(super) struct Execution {
+    /// 当前停在断点上：下次 `resume()` 先执行这条指令，不再次命中
+    at_breakpoint: bool,
+}
+
+/// `step()` 执行的一条指令
+#[derive(Debug, Clone)]
+pub struct StepResult {
+    /// 执行指令的栈帧
+    pub class_name: String,
+    pub method_name: String,
+    /// 执行的指令位置
+    pub pc: usize,
+    pub opcode: u8,
+    pub mnemonic: &'static str,
+    /// 执行后栈顶栈帧的 pc；入口方法返回后为 None
+    pub next_pc: Option<usize>,
+    /// 入口方法返回时的返回值
+    pub return_value: Option<JvmValue>,
+}
+
+impl StepResult {
+    /// 入口方法是否已返回
+    pub fn is_finished(&self) -> bool {
+        self.next_pc.is_none()
+    }
+}
+
+/// `resume()` 停下的原因
+#[derive(Debug, Clone)]
+pub enum RunOutcome {
+    /// 停在断点上，断点处的指令还没有执行
+    Breakpoint(Breakpoint),
+    /// 入口方法返回，携带返回值（如果有）
+    Finished(Option<JvmValue>),
+}
+
+impl Interpreter {
+    /// 在方法 `method_key`（"name:descriptor"）的 `pc` 处设置断点
+    pub fn set_breakpoint(&mut self, class_name: &str, method_key: &str, pc: usize) {
+        self.breakpoints.insert(Breakpoint {
+            class_name: class_name.to_string(),
+            method_key: method_key.to_string(),
+            pc,
+        });
+    }
+
+    /// 删除断点，返回断点是否存在
+    pub fn remove_breakpoint(&mut self, class_name: &str, method_key: &str, pc: usize) -> bool {
+        self.breakpoints.remove(&Breakpoint {
+            class_name: class_name.to_string(),
+            method_key: method_key.to_string(),
+            pc,
+        })
+    }
+
+    /// 开始单步执行方法：压入入口栈帧（以及需要运行的 <clinit>），不执行任何指令
+    pub fn start_method(&mut self, class_name: &str, method_key: &str, args: Vec<JvmValue>) -> Result<()> {
+        if self.execution.is_some() {
+            return Err(anyhow!("An execution is already in progress"));
+        }
+        let frame = self.method_frame(class_name, method_key, args)?;
+        self.enter_frame(frame)?;
+        self.execution = Some(Execution::default());
+        Ok(())
+    }
+
+    /// 是否有暂停中的执行
+    pub fn is_paused(&self) -> bool {
+        self.execution.is_some()
+    }
+
+    /// 执行一条指令
+    pub fn step(&mut self) -> Result<StepResult> {
+        if self.execution.is_none() {
+            return Err(anyhow!("No execution in progress; call start_method first"));
+        }
+        let frame = self.thread.current_frame()?;
+        let (class_name, method_name, pc) = (frame.class_name.clone(), frame.method_name.clone(), frame.pc);
+        let opcode = *frame
+            .code
+            .get(pc)
+            .ok_or_else(|| anyhow!("PC out of bounds: {} >= {}", pc, frame.code.len()))?;
+
+        let control = match self.step_instruction() {
+            Ok(control) => control,
+            Err(e) => {
+                // 出错后执行无法继续，丢弃未完成的栈帧
+                self.execution = None;
+                self.thread.unwind_to(0);
+                return Err(e);
+            }
+        };
+        let (next_pc, return_value) = match control {
+            InstructionControl::Return(value) => (None, value),
+            InstructionControl::Continue => match self.thread.current_frame() {
+                Ok(frame) => (Some(frame.pc), None),
+                Err(_) => (None, None),
+            },
+        };
+        if next_pc.is_none() {
+            self.execution = None;
+        } else if let Some(execution) = &mut self.execution {
+            execution.at_breakpoint = false;
+        }
+        Ok(StepResult {
+            class_name: class_name.into(),
+            method_name: method_name.into(),
+            pc,
+            opcode,
+            mnemonic: instructions::get_instruction_name(opcode),
+            next_pc,
+            return_value,
+        })
+    }
+
+    /// 运行到下一个断点或入口方法返回
+    pub fn resume(&mut self) -> Result<RunOutcome> {
+        loop {
+            let at_breakpoint = match &self.execution {
+                Some(execution) => execution.at_breakpoint,
+                None => return Err(anyhow!("No execution in progress; call start_method first")),
+            };
+            if !at_breakpoint {
+                if let Some(hit) = self.current_breakpoint()? {
+                    if let Some(execution) = &mut self.execution {
+                        execution.at_breakpoint = true;
+                    }
+                    return Ok(RunOutcome::Breakpoint(hit));
+                }
+            }
+            let step = self.step()?;
+            if step.is_finished() {
+                return Ok(RunOutcome::Finished(step.return_value));
+            }
+        }
+    }
+
+    /// 栈顶栈帧的局部变量表（暂停时查看）
+    pub fn current_locals(&self) -> Result<&[JvmValue]> {
+        Ok(self.thread.current_frame()?.locals())
+    }
+
+    /// 栈顶栈帧的操作数栈，栈底在前（暂停时查看）
+    pub fn current_stack(&self) -> Result<&[JvmValue]> {
+        Ok(self.thread.current_frame()?.operands())
+    }
+
+    /// 栈顶栈帧的 pc 处是否有断点
+    fn current_breakpoint(&self) -> Result<Option<Breakpoint>> {
+        if self.breakpoints.is_empty() {
+            return Ok(None);
+        }
+        let frame = self.thread.current_frame()?;
+        let location = Breakpoint {
+            class_name: frame.class_name.to_string(),
+            method_key: format!("{}:{}", frame.method_name, frame.descriptor),
+            pc: frame.pc,
+        };
+        Ok(self.breakpoints.contains(&location).then_some(location))
+    }
+}
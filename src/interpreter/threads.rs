@@ -0,0 +1,358 @@
+//! # Java 线程
+//!
+//! `java/lang/Thread` 的 `start()` 为线程创建自己的 `JvmThread`（虚拟机栈），
+//! 堆、方法区和静态字段由所有线程共享。`join()` 让当前线程暂停，直到目标线程结束；
+//! 入口方法返回后，执行循环继续运行，直到所有非守护线程结束。
+//!
+//! 线程由解释器在最外层的执行循环中调度，各线程轮流执行一个时间片（`TIME_SLICE` 条指令）。
+//! 时间片可能在任意两条指令之间用完，共享数据仍然需要 synchronized（见 `monitors` 模块）保护；
+//! 等待监视器的线程被阻塞，直到监视器可以获取时才再被调度。
+//!
+//! 线程不映射到操作系统线程，同一时刻只有一个线程在执行字节码：解释器本身
+//! （本地方法表、跟踪钩子、字段监视）不是 `Send`，不能跨操作系统线程共享。
+//! 映射到操作系统线程需要把堆和方法区放进 `Arc<RwLock<…>>`，每个线程有自己的执行上下文；
+//! GC 前让所有线程停在安全点并交出栈（根）；监视器改用操作系统的锁和条件变量，
+//! `join()` 阻塞在线程的 `JoinHandle` 上；单步执行、快照和调度相关的测试也不再是确定的。
+//!
+//! ## 学习要点
+//! - 线程私有的是虚拟机栈（每个栈帧的 pc、局部变量、操作数栈），
+//!   所以切换线程只需要换一个 `JvmThread`，指令本身不需要知道有多个线程
+//! - 时间片按执行的指令条数计算，调度结果是确定的，便于测试
+//! - 被暂停线程的栈也是 GC 根
+//! - 线程对象可能被 GC 移动，所以线程表按线程 id（`Thread.tid` 字段）而不是堆引用记录线程
+
+use super::{monitors, InstructionControl, Interpreter};
+use crate::runtime::frame::JvmValue;
+use crate::runtime::{ExecutionError, JavaException, JvmThread, ObjRef};
+use crate::Result;
+use anyhow::anyhow;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// 每个线程一次最多连续执行的指令条数
+pub const TIME_SLICE: u64 = 1000;
+
+/// `java/lang/Thread` 的类名
+pub(super) const THREAD_CLASS: &str = "java/lang/Thread";
+
+/// 没有在运行的线程
+#[derive(Debug)]
+struct ParkedThread {
+    /// 线程 id；None 是入口线程（执行入口方法的线程）
+    id: Option<i64>,
+    thread: JvmThread,
+    /// 正在 join 的线程
+    joining: Option<i64>,
+    /// 是否在等待监视器
+    blocked: bool,
+}
+
+/// 已启动的线程
+#[derive(Debug, Clone)]
+struct ThreadInfo {
+    name: String,
+    daemon: bool,
+}
+
+/// 线程表
+#[derive(Debug, Default)]
+pub(super) struct Threads {
+    /// 下一个创建的 Thread 对象的 id
+    next_id: i64,
+    /// 调用过 start() 的线程
+    started: HashSet<i64>,
+    /// 已启动、还没有结束的线程
+    alive: HashMap<i64, ThreadInfo>,
+    /// 等待调度的线程（不含当前线程）
+    parked: VecDeque<ParkedThread>,
+    /// 当前线程；None 是入口线程
+    current: Option<i64>,
+    /// 当前线程调用 join() 等待的线程
+    current_joining: Option<i64>,
+    /// 当前线程在等待被其他线程持有的监视器
+    current_blocked: bool,
+    /// 当前线程的时间片在执行到这条指令时用完
+    slice_end: u64,
+}
+
+impl Threads {
+    /// 为新的 Thread 对象分配 id
+    pub(super) fn next_id(&mut self) -> i64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        id
+    }
+
+    /// 线程是否调用过 start()
+    pub(super) fn is_started(&self, id: i64) -> bool {
+        self.started.contains(&id)
+    }
+
+    /// 线程是否已启动且还没有结束
+    pub(super) fn is_alive(&self, id: i64) -> bool {
+        self.alive.contains_key(&id)
+    }
+
+    /// 当前线程等待 `id` 结束；线程已经结束（或还没有启动）时立即返回
+    pub(super) fn join(&mut self, id: i64) {
+        if self.is_alive(id) {
+            self.current_joining = Some(id);
+        }
+    }
+
+    /// 当前线程的 id（监视器的持有者）；None 是入口线程
+    pub(super) fn current(&self) -> Option<i64> {
+        self.current
+    }
+
+    /// 当前线程需要的监视器被其他线程持有：在下一条指令之前切换线程
+    pub(super) fn block(&mut self) {
+        self.current_blocked = true;
+    }
+
+    /// 当前线程是否在等待监视器
+    pub(super) fn is_blocked(&self) -> bool {
+        self.current_blocked
+    }
+
+    /// 除入口线程外是否没有正在运行或等待调度的线程
+    pub(super) fn is_idle(&self) -> bool {
+        self.current.is_none() && self.alive.is_empty() && self.parked.is_empty()
+    }
+
+    /// 暂停中的线程的栈（GC 根）
+    pub(super) fn parked_stacks_mut(&mut self) -> impl Iterator<Item = &mut JvmThread> {
+        self.parked.iter_mut().map(|parked| &mut parked.thread)
+    }
+
+    /// 丢弃所有线程（开始新的运行时，或运行出错后）；线程 id 继续递增
+    pub(super) fn clear(&mut self) {
+        *self = Threads {
+            next_id: self.next_id,
+            ..Threads::default()
+        };
+    }
+
+    /// 最外层的执行循环是否需要在下一条指令之前切换线程
+    fn should_switch(&self, instructions: u64) -> bool {
+        self.current_joining.is_some()
+            || self.current_blocked
+            || (!self.parked.is_empty() && instructions >= self.slice_end)
+    }
+
+    /// 是否还有没结束的非守护线程
+    fn has_user_threads(&self) -> bool {
+        self.alive.values().any(|info| !info.daemon)
+    }
+
+    /// 当前线程的名字（未捕获异常的提示中使用）
+    fn current_name(&self) -> String {
+        match self.current.and_then(|id| self.alive.get(&id)) {
+            Some(info) => info.name.clone(),
+            None => "main".to_string(),
+        }
+    }
+}
+
+impl Interpreter {
+    /// 启动线程：为 `run()` 创建栈帧，放入调度队列
+    ///
+    /// `Thread` 的子类覆盖了 `run()` 时执行子类的方法，否则执行构造时传入的 Runnable 的 `run()`
+    pub(super) fn start_thread(&mut self, thread: ObjRef) -> Result<()> {
+        let id = self.thread_id(thread)?;
+        if !self.threads.started.insert(id) {
+            return Err(JavaException::new(
+                "java/lang/IllegalThreadStateException",
+                format!("Thread-{} has already been started", id),
+            )
+            .into());
+        }
+
+        let runtime_class = self.heap.get(thread)?.class_name.clone();
+        let receiver = match self.find_run_method(&runtime_class) {
+            Some(_) => Some(thread),
+            None => match self.heap.get_field(thread, THREAD_CLASS, "target")? {
+                JvmValue::Reference(target) => target,
+                other => return Err(anyhow!("Thread.target is not a reference: {:?}", other)),
+            },
+        };
+        // 没有覆盖 run() 也没有 Runnable：线程什么也不做，立即结束
+        let Some(receiver) = receiver else {
+            return Ok(());
+        };
+        let receiver_class = self.heap.get(receiver)?.class_name.clone();
+        let owner = self
+            .find_run_method(&receiver_class)
+            .ok_or_else(|| JavaException::abstract_method(&receiver_class, "run", "()V"))?;
+        let frame = self.method_frame(&owner, "run:()V", vec![JvmValue::Reference(Some(receiver))])?;
+        // synchronized 的 run() 在线程第一次被调度时获取监视器
+        let blocked = frame.monitor.is_some();
+
+        let mut stack = JvmThread::with_max_frames(self.options.max_frames);
+        stack.push_frame(frame)?;
+        let info = ThreadInfo {
+            name: self.thread_name(thread),
+            daemon: matches!(
+                self.heap.get_field(thread, THREAD_CLASS, "daemon"),
+                Ok(JvmValue::Int(1))
+            ),
+        };
+        self.threads.alive.insert(id, info);
+        self.threads.parked.push_back(ParkedThread {
+            id: Some(id),
+            thread: stack,
+            joining: None,
+            blocked,
+        });
+        Ok(())
+    }
+
+    /// Thread 对象的 id（构造时分配）
+    pub(super) fn thread_id(&self, thread: ObjRef) -> Result<i64> {
+        match self.heap.get_field(thread, THREAD_CLASS, "tid") {
+            Ok(JvmValue::Long(id)) => Ok(id),
+            _ => Err(anyhow!("Thread object {:?} was not constructed", thread)),
+        }
+    }
+
+    /// Thread 对象的名字
+    fn thread_name(&self, thread: ObjRef) -> String {
+        match self.heap.get_field(thread, THREAD_CLASS, "name") {
+            Ok(JvmValue::Reference(Some(name))) => self
+                .heap
+                .get_string(name)
+                .map(str::to_string)
+                .unwrap_or_default(),
+            _ => String::new(),
+        }
+    }
+
+    /// 用户类（或它的父类）中 `run()V` 的实现所在的类；只有 JDK 的实现时返回 None
+    fn find_run_method(&self, class_name: &str) -> Option<String> {
+        if !self.metaspace.is_class_loaded(class_name) {
+            return None;
+        }
+        self.metaspace
+            .resolve_virtual_method(class_name, "run", "()V")
+            .ok()
+            .filter(|(_, method)| !method.is_abstract)
+            .map(|(owner, _)| owner.into())
+    }
+
+    /// 最外层的执行循环：入口线程和它启动的线程轮流执行，
+    /// 直到入口方法返回并且所有非守护线程都已结束
+    pub(super) fn run_threads(&mut self) -> Result<Option<JvmValue>> {
+        let mut result = None;
+        let outcome = loop {
+            if self.thread.stack_depth() == 0 {
+                // 当前线程结束
+                if let Some(id) = self.threads.current.take() {
+                    self.threads.alive.remove(&id);
+                }
+                match self.switch_thread(true) {
+                    Ok(true) => continue,
+                    Ok(false) => break Ok(result),
+                    Err(e) => break Err(e),
+                }
+            }
+            if self.threads.should_switch(self.instructions_executed) {
+                if let Err(e) = self.switch_thread(false) {
+                    break Err(e);
+                }
+            }
+            match self.step_instruction() {
+                Ok(InstructionControl::Return(value)) => {
+                    if self.threads.current.is_none() {
+                        result = value;
+                    }
+                }
+                Ok(InstructionControl::Continue) => {}
+                // 线程中未捕获的异常只结束这个线程，报告写到解释器的 stderr（可以被捕获）
+                Err(e) if self.threads.current.is_some() && e.is::<ExecutionError>() => {
+                    let name = self.threads.current_name();
+                    let report = format!("Exception in thread \"{}\" {}", name, e);
+                    if let Err(e) = writeln!(self.stderr(), "{}", report) {
+                        break Err(e.into());
+                    }
+                    self.thread.unwind_to(0);
+                }
+                Err(e) => break Err(e),
+            }
+        };
+        if outcome.is_err() && self.threads.current.is_some() {
+            // 出错时停在其他线程上：换回一个空的入口线程
+            self.thread = JvmThread::with_max_frames(self.options.max_frames);
+        }
+        // 剩下的只有守护线程（或者出错了）：随入口方法一起结束
+        self.threads.clear();
+        outcome
+    }
+
+    /// 切换到下一个可以运行的线程，返回 false 表示没有线程需要继续运行
+    ///
+    /// `finished` 表示当前线程已经结束，不再放回队列
+    fn switch_thread(&mut self, finished: bool) -> Result<bool> {
+        let threads = &mut self.threads;
+        // 入口线程不在队列里说明它已经结束（当前结束的线程不会放回队列）
+        if finished
+            && !threads.parked.iter().any(|parked| parked.id.is_none())
+            && !threads.has_user_threads()
+        {
+            return Ok(false);
+        }
+
+        let heap = &self.heap;
+        let runnable = threads.parked.iter().position(|parked| {
+            parked.joining.is_none_or(|id| !threads.alive.contains_key(&id))
+                && (!parked.blocked
+                    || monitors::waiting_monitor(&parked.thread)
+                        .is_none_or(|object| heap.monitor_available(object, parked.id)))
+        });
+        let Some(position) = runnable else {
+            if finished {
+                return Err(anyhow!(
+                    "deadlock: {} thread(s) are waiting in Thread.join() or for a monitor and none can run",
+                    threads.parked.len()
+                ));
+            }
+            if threads.current_joining.is_some_and(|id| threads.alive.contains_key(&id)) {
+                return Err(anyhow!(
+                    "deadlock: thread \"{}\" is waiting in Thread.join() and no other thread can run",
+                    threads.current_name()
+                ));
+            }
+            if threads.current_blocked {
+                return Err(anyhow!(
+                    "deadlock: thread \"{}\" is waiting for a monitor and no other thread can run",
+                    threads.current_name()
+                ));
+            }
+            // 只有当前线程可以运行：继续执行
+            threads.current_joining = None;
+            threads.slice_end = self.instructions_executed + TIME_SLICE;
+            return Ok(true);
+        };
+
+        let next = threads
+            .parked
+            .remove(position)
+            .expect("position comes from the parked queue");
+        let previous = std::mem::replace(&mut self.thread, next.thread);
+        if !finished {
+            threads.parked.push_back(ParkedThread {
+                id: threads.current,
+                thread: previous,
+                joining: threads.current_joining,
+                blocked: threads.current_blocked,
+            });
+        }
+        threads.current = next.id;
+        threads.current_joining = None;
+        threads.current_blocked = false;
+        threads.slice_end = self.instructions_executed + TIME_SLICE;
+        if next.blocked {
+            // 监视器现在可以获取：synchronized 方法在这里获取，monitorenter 重新执行时获取
+            self.enter_method_monitor()?;
+        }
+        Ok(true)
+    }
+}
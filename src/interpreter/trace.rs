@@ -0,0 +1,122 @@
+//! # 指令跟踪
+//!
+//! 每执行完一条指令调用一次跟踪钩子，报告指令位置、助记符、
+//! 执行后操作数栈顶的几个值和被改写的局部变量。
+//!
+//! ## 学习要点
+//! - 逐条观察操作数栈的变化，是理解"基于栈的虚拟机"最直接的方式
+//! - store 指令把栈顶写进局部变量表，load 指令反过来
+//! - 没有安装钩子时，主循环只多一次 `Option` 判断，不做任何格式化
+
+use crate::runtime::frame::JvmValue;
+use crate::runtime::Symbol;
+use std::fmt;
+
+/// 跟踪输出中显示的栈顶值个数
+pub const TRACE_STACK_VALUES: usize = 3;
+
+/// 一条已执行指令的跟踪信息
+#[derive(Debug, Clone)]
+pub struct TraceEvent {
+    /// 调用深度（入口方法为 1）
+    pub depth: usize,
+    /// 指令所属的类
+    pub class_name: String,
+    /// 指令所属的方法
+    pub method_name: String,
+    /// 指令的 pc
+    pub pc: usize,
+    /// 操作码
+    pub opcode: u8,
+    /// 助记符（`get_instruction_name`）
+    pub mnemonic: &'static str,
+    /// 执行后操作数栈顶的值（栈顶在后），最多 `TRACE_STACK_VALUES` 个；
+    /// 指令所在的栈帧已经返回时为空
+    pub stack_top: Vec<JvmValue>,
+    /// 执行后操作数栈的总深度（值的个数）
+    pub stack_size: usize,
+    /// 这条指令改写的局部变量（槽位, 新值）
+    pub changed_locals: Vec<(usize, JvmValue)>,
+}
+
+/// 跟踪钩子
+///
+/// 闭包 `FnMut(&TraceEvent)` 自动实现这个 trait
+pub trait TraceHook {
+    /// 每执行完一条指令调用一次
+    fn on_instruction(&mut self, event: &TraceEvent);
+}
+
+impl<F: FnMut(&TraceEvent)> TraceHook for F {
+    fn on_instruction(&mut self, event: &TraceEvent) {
+        self(event)
+    }
+}
+
+/// 把每条指令打印到标准错误（`rsjvm run --trace`）
+pub struct PrintTrace;
+
+impl TraceHook for PrintTrace {
+    fn on_instruction(&mut self, event: &TraceEvent) {
+        eprintln!("[trace] {}", event);
+    }
+}
+
+/// 指令执行前记录的状态，执行后与栈帧比较得出改写的局部变量
+pub(crate) struct PendingTrace {
+    pub depth: usize,
+    pub class_name: Symbol,
+    pub method_name: Symbol,
+    pub pc: usize,
+    pub opcode: u8,
+    pub locals: Vec<JvmValue>,
+}
+
+impl fmt::Display for TraceEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:indent$}{}.{} {:>4}: {:<15}",
+            "",
+            self.class_name,
+            self.method_name,
+            self.pc,
+            self.mnemonic,
+            indent = (self.depth.saturating_sub(1)) * 2
+        )?;
+        let hidden = self.stack_size - self.stack_top.len();
+        let values: Vec<String> = self.stack_top.iter().map(format_value).collect();
+        if hidden > 0 {
+            write!(f, " stack=[.., {}]", values.join(", "))?;
+        } else {
+            write!(f, " stack=[{}]", values.join(", "))?;
+        }
+        for (slot, value) in &self.changed_locals {
+            write!(f, " local[{}]={}", slot, format_value(value))?;
+        }
+        Ok(())
+    }
+}
+
+/// 槽位内容是否相同（浮点数按位比较，NaN 与自身相同）
+pub(crate) fn same_value(a: &JvmValue, b: &JvmValue) -> bool {
+    match (a, b) {
+        (JvmValue::Int(x), JvmValue::Int(y)) => x == y,
+        (JvmValue::Long(x), JvmValue::Long(y)) => x == y,
+        (JvmValue::Float(x), JvmValue::Float(y)) => x.to_bits() == y.to_bits(),
+        (JvmValue::Double(x), JvmValue::Double(y)) => x.to_bits() == y.to_bits(),
+        (JvmValue::Reference(x), JvmValue::Reference(y)) => x == y,
+        _ => false,
+    }
+}
+
+fn format_value(value: &JvmValue) -> String {
+    match value {
+        JvmValue::Int(v) => v.to_string(),
+        JvmValue::Long(v) => format!("{}L", v),
+        JvmValue::Float(v) => format!("{:?}f", v),
+        JvmValue::Double(v) => format!("{:?}", v),
+        JvmValue::Reference(Some(ptr)) => format!("ref@{}", ptr),
+        JvmValue::Reference(None) => "null".to_string(),
+    }
+}
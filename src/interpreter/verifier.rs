@@ -0,0 +1,297 @@
+//! # 字节码校验
+//!
+//! 类加载时对每个方法做一遍静态检查，把"跳进指令中间""从空栈弹出"
+//! "访问不存在的局部变量"这类问题在执行前报告出来，而不是运行到一半才出错。
+//!
+//! ## 学习要点
+//! - 线性扫描一遍字节码就能确定所有指令的起点（反汇编器做的就是这件事）
+//! - 跳转目标和异常处理器入口必须落在指令起点上
+//! - 栈深度模拟：沿所有控制流路径计算每条指令执行前的栈深度（按槽位计），
+//!   同一条指令从不同路径到达时深度必须相同
+//! - 这里只检查栈深度，不检查栈上值的类型（真正的 JVM 用 StackMapTable 做类型检查）
+
+use super::disasm::{disassemble, DisassembledInstruction};
+use super::instructions::opcodes::*;
+use crate::classfile::constant_pool::{ConstantPool, ConstantPoolEntry};
+use crate::classfile::descriptor::{FieldType, MethodDescriptor};
+use crate::runtime::MethodMetadata;
+use crate::Result;
+use anyhow::anyhow;
+
+/// 校验方法的字节码
+///
+/// 没有字节码的方法（native/abstract）直接通过
+pub fn verify_method(method: &MethodMetadata, cp: &ConstantPool) -> Result<()> {
+    if method.code.is_empty() {
+        return Ok(());
+    }
+    let instructions = disassemble(&method.code, cp)?;
+
+    // 指令起点 → 指令序号
+    let mut index_at = vec![None; method.code.len()];
+    for (i, instruction) in instructions.iter().enumerate() {
+        index_at[instruction.pc] = Some(i);
+    }
+    let start_of = |pc: usize| index_at.get(pc).copied().flatten();
+
+    for instruction in &instructions {
+        for &target in &instruction.branch_targets {
+            if start_of(target).is_none() {
+                return Err(anyhow!(
+                    "Branch target {} of {} at pc {} is not the start of an instruction",
+                    target,
+                    instruction.mnemonic,
+                    instruction.pc
+                ));
+            }
+        }
+        if let Some((index, slots)) = local_access(instruction) {
+            if index + slots > method.max_locals {
+                return Err(anyhow!(
+                    "Local variable {} of {} at pc {} is out of range (max_locals {})",
+                    index + slots - 1,
+                    instruction.mnemonic,
+                    instruction.pc,
+                    method.max_locals
+                ));
+            }
+        }
+    }
+
+    for entry in &method.exception_table {
+        let end_ok = entry.end_pc == method.code.len() || start_of(entry.end_pc).is_some();
+        if start_of(entry.start_pc).is_none() || !end_ok || entry.start_pc >= entry.end_pc {
+            return Err(anyhow!(
+                "Invalid exception table range [{}, {})",
+                entry.start_pc,
+                entry.end_pc
+            ));
+        }
+        if start_of(entry.handler_pc).is_none() {
+            return Err(anyhow!(
+                "Exception handler pc {} is not the start of an instruction",
+                entry.handler_pc
+            ));
+        }
+    }
+
+    simulate_stack(method, cp, &instructions, &index_at)
+}
+
+/// 沿控制流模拟栈深度，检查下溢、超过 max_stack 和汇合点深度不一致
+fn simulate_stack(
+    method: &MethodMetadata,
+    cp: &ConstantPool,
+    instructions: &[DisassembledInstruction],
+    index_at: &[Option<usize>],
+) -> Result<()> {
+    let mut depth_before: Vec<Option<usize>> = vec![None; instructions.len()];
+    let mut pending = vec![(0, 0)];
+
+    while let Some((i, depth)) = pending.pop() {
+        let instruction = &instructions[i];
+        match depth_before[i] {
+            Some(known) if known == depth => continue,
+            Some(known) => {
+                return Err(anyhow!(
+                    "Inconsistent stack depth at pc {}: {} vs {}",
+                    instruction.pc,
+                    known,
+                    depth
+                ))
+            }
+            None => depth_before[i] = Some(depth),
+        }
+
+        // 保护范围内的每条指令都可能跳到处理器，处理器入口的栈上只有异常对象
+        for entry in &method.exception_table {
+            if entry.covers(instruction.pc) {
+                check_max_stack(instruction, 1, method.max_stack)?;
+                if let Some(handler) = index_at[entry.handler_pc] {
+                    pending.push((handler, 1));
+                }
+            }
+        }
+
+        let (pop, push) = stack_effect(instruction, cp)?;
+        if pop > depth {
+            return Err(anyhow!(
+                "Stack underflow at pc {} ({}): needs {} slots, stack has {}",
+                instruction.pc,
+                instruction.mnemonic,
+                pop,
+                depth
+            ));
+        }
+        let after = depth - pop + push;
+        check_max_stack(instruction, after, method.max_stack)?;
+
+        for &target in &instruction.branch_targets {
+            if let Some(next) = index_at[target] {
+                pending.push((next, after));
+            }
+        }
+        if falls_through(instruction.opcode) {
+            if i + 1 == instructions.len() {
+                return Err(anyhow!(
+                    "Execution falls off the end of the code after {} at pc {}",
+                    instruction.mnemonic,
+                    instruction.pc
+                ));
+            }
+            pending.push((i + 1, after));
+        }
+    }
+    Ok(())
+}
+
+fn check_max_stack(instruction: &DisassembledInstruction, depth: usize, max_stack: usize) -> Result<()> {
+    if depth > max_stack {
+        return Err(anyhow!(
+            "Stack overflow at pc {} ({}): depth {} exceeds max_stack {}",
+            instruction.pc,
+            instruction.mnemonic,
+            depth,
+            max_stack
+        ));
+    }
+    Ok(())
+}
+
+/// 执行完这条指令后是否继续执行下一条
+fn falls_through(opcode: u8) -> bool {
+    !matches!(
+        opcode,
+        GOTO | GOTO_W
+            | TABLESWITCH
+            | LOOKUPSWITCH
+            | IRETURN
+            | LRETURN
+            | FRETURN
+            | DRETURN
+            | ARETURN
+            | RETURN
+            | ATHROW
+    )
+}
+
+/// 指令访问的局部变量：(起始槽位, 槽位数)
+fn local_access(instruction: &DisassembledInstruction) -> Option<(usize, usize)> {
+    let opcode = instruction.opcode;
+    let explicit = || {
+        let bytes = &instruction.operand_bytes;
+        if instruction.wide {
+            u16::from_be_bytes([bytes[0], bytes[1]]) as usize
+        } else {
+            bytes[0] as usize
+        }
+    };
+    let slots = |wide_value: bool| if wide_value { 2 } else { 1 };
+    Some(match opcode {
+        ILOAD | FLOAD | ALOAD | ISTORE | FSTORE | ASTORE | IINC | RET => (explicit(), 1),
+        LLOAD | DLOAD | LSTORE | DSTORE => (explicit(), 2),
+        ILOAD_0..=ALOAD_3 => {
+            let group = (opcode - ILOAD_0) / 4;
+            ((opcode - ILOAD_0) as usize % 4, slots(group == 1 || group == 3))
+        }
+        ISTORE_0..=ASTORE_3 => {
+            let group = (opcode - ISTORE_0) / 4;
+            ((opcode - ISTORE_0) as usize % 4, slots(group == 1 || group == 3))
+        }
+        _ => return None,
+    })
+}
+
+/// 指令弹出和压入的槽位数（long/double 占两个槽位）
+fn stack_effect(instruction: &DisassembledInstruction, cp: &ConstantPool) -> Result<(usize, usize)> {
+    let opcode = instruction.opcode;
+    let cp_index = || u16::from_be_bytes([instruction.operand_bytes[0], instruction.operand_bytes[1]]);
+    Ok(match opcode {
+        NOP | IINC | GOTO | GOTO_W | RETURN => (0, 0),
+        ACONST_NULL | ICONST_M1..=ICONST_5 | FCONST_0..=FCONST_2 | BIPUSH | SIPUSH => (0, 1),
+        LCONST_0 | LCONST_1 | DCONST_0 | DCONST_1 | LDC2_W => (0, 2),
+        LDC | LDC_W | NEW => (0, 1),
+        ILOAD | FLOAD | ALOAD | ILOAD_0..=ILOAD_3 | FLOAD_0..=FLOAD_3 | ALOAD_0..=ALOAD_3 => (0, 1),
+        LLOAD | DLOAD | LLOAD_0..=LLOAD_3 | DLOAD_0..=DLOAD_3 => (0, 2),
+        IALOAD | FALOAD | AALOAD | BALOAD | CALOAD | SALOAD => (2, 1),
+        LALOAD | DALOAD => (2, 2),
+        ISTORE | FSTORE | ASTORE | ISTORE_0..=ISTORE_3 | FSTORE_0..=FSTORE_3 | ASTORE_0..=ASTORE_3 => {
+            (1, 0)
+        }
+        LSTORE | DSTORE | LSTORE_0..=LSTORE_3 | DSTORE_0..=DSTORE_3 => (2, 0),
+        IASTORE | FASTORE | AASTORE | BASTORE | CASTORE | SASTORE => (3, 0),
+        LASTORE | DASTORE => (4, 0),
+        POP => (1, 0),
+        POP2 => (2, 0),
+        DUP => (1, 2),
+        DUP_X1 => (2, 3),
+        DUP_X2 => (3, 4),
+        DUP2 => (2, 4),
+        DUP2_X1 => (3, 5),
+        DUP2_X2 => (4, 6),
+        SWAP => (2, 2),
+        IADD | ISUB | IMUL | IDIV | IREM | ISHL | ISHR | IUSHR | IAND | IOR | IXOR => (2, 1),
+        FADD | FSUB | FMUL | FDIV | FREM | FCMPL | FCMPG => (2, 1),
+        LADD | LSUB | LMUL | LDIV | LREM | LAND | LOR | LXOR => (4, 2),
+        DADD | DSUB | DMUL | DDIV | DREM => (4, 2),
+        LSHL | LSHR | LUSHR => (3, 2),
+        INEG | FNEG | I2F | F2I | I2B | I2C | I2S => (1, 1),
+        LNEG | DNEG | L2D | D2L => (2, 2),
+        I2L | I2D | F2L | F2D => (1, 2),
+        L2I | L2F | D2I | D2F => (2, 1),
+        LCMP | DCMPL | DCMPG => (4, 1),
+        IFEQ..=IFLE | IFNULL | IFNONNULL | TABLESWITCH | LOOKUPSWITCH => (1, 0),
+        IF_ICMPEQ..=IF_ACMPNE => (2, 0),
+        IRETURN | FRETURN | ARETURN | ATHROW | MONITORENTER | MONITOREXIT => (1, 0),
+        LRETURN | DRETURN => (2, 0),
+        NEWARRAY | ANEWARRAY | ARRAYLENGTH | CHECKCAST | INSTANCEOF => (1, 1),
+        MULTIANEWARRAY => (instruction.operand_bytes[2] as usize, 1),
+        GETSTATIC | PUTSTATIC | GETFIELD | PUTFIELD => {
+            let (_, descriptor) = member_descriptor(cp, cp_index())?;
+            let width = FieldType::parse(&descriptor)?.slot_count();
+            match opcode {
+                GETSTATIC => (0, width),
+                PUTSTATIC => (width, 0),
+                GETFIELD => (1, width),
+                _ => (1 + width, 0),
+            }
+        }
+        INVOKEVIRTUAL | INVOKESPECIAL | INVOKESTATIC | INVOKEINTERFACE | INVOKEDYNAMIC => {
+            let (_, descriptor) = member_descriptor(cp, cp_index())?;
+            let descriptor = MethodDescriptor::parse(&descriptor)?;
+            let receiver = usize::from(opcode != INVOKESTATIC && opcode != INVOKEDYNAMIC);
+            let ret = descriptor.ret.as_ref().map_or(0, FieldType::slot_count);
+            (receiver + descriptor.param_slot_count(), ret)
+        }
+        _ => {
+            return Err(anyhow!(
+                "{} at pc {} is not supported by the verifier",
+                instruction.mnemonic,
+                instruction.pc
+            ))
+        }
+    })
+}
+
+/// 字段/方法引用（以及 invokedynamic）的名称和描述符
+fn member_descriptor(cp: &ConstantPool, index: u16) -> Result<(String, String)> {
+    match cp.get(index)? {
+        ConstantPoolEntry::FieldRef {
+            name_and_type_index,
+            ..
+        }
+        | ConstantPoolEntry::MethodRef {
+            name_and_type_index,
+            ..
+        }
+        | ConstantPoolEntry::InterfaceMethodRef {
+            name_and_type_index,
+            ..
+        }
+        | ConstantPoolEntry::InvokeDynamic {
+            name_and_type_index,
+            ..
+        } => cp.get_name_and_type(*name_and_type_index),
+        other => Err(anyhow!("Constant pool entry {} is not a member reference: {:?}", index, other)),
+    }
+}
@@ -0,0 +1,80 @@
+//! # 字段监视
+//!
+//! 在 GETFIELD/PUTFIELD/GETSTATIC/PUTSTATIC 访问指定字段时回调，
+//! 用来观察字节码如何读写堆和方法区。
+//!
+//! ## 学习要点
+//! - 实例字段存放在堆上的对象里，静态字段存放在方法区的类元数据里
+//! - 字段按"声明它的类"匹配：子类对象访问父类字段时，声明类是父类
+//! - 没有注册监视时，字段指令只多一次空列表判断
+
+use crate::runtime::frame::JvmValue;
+use crate::runtime::ObjRef;
+use std::fmt;
+
+/// 字段访问类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldAccessKind {
+    /// GETFIELD / GETSTATIC
+    Read,
+    /// PUTFIELD / PUTSTATIC
+    Write,
+}
+
+/// 一次字段访问
+#[derive(Debug, Clone)]
+pub struct FieldAccessEvent {
+    /// 读还是写
+    pub kind: FieldAccessKind,
+    /// 声明字段的类
+    pub class_name: String,
+    /// 字段名
+    pub field_name: String,
+    /// 实例字段所属对象；静态字段为 None
+    pub object: Option<ObjRef>,
+    /// 访问前的值（读操作即读到的值）
+    pub old_value: JvmValue,
+    /// 写入的新值（仅写操作）
+    pub new_value: Option<JvmValue>,
+    /// 执行访问的类
+    pub accessor_class: String,
+    /// 执行访问的方法
+    pub accessor_method: String,
+    /// 字段指令的 pc
+    pub pc: usize,
+}
+
+/// 已注册的字段监视
+pub(crate) struct FieldWatch {
+    pub class_name: String,
+    pub field_name: String,
+    pub callback: Box<dyn FnMut(&FieldAccessEvent)>,
+}
+
+impl FieldWatch {
+    pub fn matches(&self, class_name: &str, field_name: &str) -> bool {
+        self.class_name == class_name && self.field_name == field_name
+    }
+}
+
+impl fmt::Display for FieldAccessEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let target = match self.object {
+            Some(ptr) => format!("{}.{} (obj@{:#x})", self.class_name, self.field_name, ptr),
+            None => format!("{}.{} (static)", self.class_name, self.field_name),
+        };
+        match (&self.kind, &self.new_value) {
+            (FieldAccessKind::Write, Some(new_value)) => write!(
+                f,
+                "write {}: {:?} -> {:?}",
+                target, self.old_value, new_value
+            )?,
+            _ => write!(f, "read  {}: {:?}", target, self.old_value)?,
+        }
+        write!(
+            f,
+            " at {}.{} pc={}",
+            self.accessor_class, self.accessor_method, self.pc
+        )
+    }
+}
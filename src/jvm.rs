@@ -0,0 +1,361 @@
+//! # 嵌入式 API
+//!
+//! [`Jvm`] 把解释器、类加载器和运行选项包装在一起，
+//! 嵌入方只需要类路径和类名，不用关心 Metaspace、栈帧这些内部细节：
+//!
+//! ```no_run
+//! use rsjvm::{JvmBuilder, runtime::frame::JvmValue};
+//!
+//! let mut jvm = JvmBuilder::new().class_path("examples").max_heap_objects(1000).build();
+//! let args = [JvmValue::Int(1), JvmValue::Int(2)];
+//! let sum = jvm.call_static("TestInvokeStatic", "sum_a_and_b", "(II)I", &args)?;
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+//!
+//! ## 学习要点
+//! - 类按需加载：调用前先在类路径中查找并链接目标类及其父类
+//! - `capture_output()` 把 Java 程序的输出写进内存，之后用 `take_captured_output()` 取出，
+//!   错误输出（线程中未捕获的异常）用 `take_captured_errors()` 取出
+//! - `new_instance` 相当于 `new` + `invokespecial <init>`，之后用 `call_virtual` 调用实例方法；
+//!   它返回 GC 根句柄（`ObjectHandle`），对象被回收器移动后句柄仍然有效
+//! - `call_static_typed` 接收普通 Rust 值，返回值按描述符转换成调用方要求的类型
+//! - 需要更底层的控制（字段监视、GC、堆转储）时，通过 `interpreter_mut()` 访问解释器
+
+use crate::classfile::descriptor::MethodDescriptor;
+use crate::classfile::ClassFile;
+use crate::classloader::{internal_class_name, ClassLoader};
+use crate::gc::GcStrategy;
+use crate::interpreter::cancel::CancelHandle;
+use crate::interpreter::embed::{FromJvmValue, IntoJvmArgs};
+use crate::interpreter::handles::ObjectHandle;
+use crate::interpreter::opcode_policy::UnknownOpcodePolicy;
+use crate::interpreter::stdio::SharedBuffer;
+use crate::interpreter::{Interpreter, InterpreterOptions};
+use crate::runtime::frame::JvmValue;
+use crate::runtime::JavaException;
+use crate::Result;
+use anyhow::anyhow;
+use std::path::{Path, PathBuf};
+
+/// [`Jvm`] 的构建器
+#[derive(Debug, Default)]
+pub struct JvmBuilder {
+    class_paths: Vec<PathBuf>,
+    options: InterpreterOptions,
+    capture_output: bool,
+}
+
+impl JvmBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 添加类路径（目录或 `.jar` 文件），可多次调用
+    pub fn class_path<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.class_paths.push(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// 依次添加多个类路径，如 [`split_class_path`](crate::classloader::split_class_path) 拆分出的各项
+    pub fn class_paths<I, P>(mut self, paths: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        self.class_paths
+            .extend(paths.into_iter().map(|path| path.as_ref().to_path_buf()));
+        self
+    }
+
+    /// 一次性替换全部解释器选项
+    pub fn options(mut self, options: InterpreterOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// 堆中存活对象数上限
+    pub fn max_heap_objects(mut self, max: usize) -> Self {
+        self.options.max_heap_objects = Some(max);
+        self
+    }
+
+    /// 线程栈的栈帧数上限
+    pub fn max_frames(mut self, max: usize) -> Self {
+        self.options.max_frames = max;
+        self
+    }
+
+    /// 两次自动 GC 之间最多分配的对象数，None 表示不自动回收
+    pub fn gc_threshold(mut self, threshold: Option<usize>) -> Self {
+        self.options.gc_threshold = threshold;
+        self
+    }
+
+    /// 回收算法
+    pub fn gc_strategy(mut self, strategy: GcStrategy) -> Self {
+        self.options.gc_strategy = strategy;
+        self
+    }
+
+    /// 一次运行最多执行的指令条数
+    pub fn max_instructions(mut self, max: u64) -> Self {
+        self.options.max_instructions = Some(max);
+        self
+    }
+
+    /// 一次运行最长的时间
+    pub fn time_limit(mut self, limit: std::time::Duration) -> Self {
+        self.options.time_limit = Some(limit);
+        self
+    }
+
+    /// 加载类时是否校验字节码
+    pub fn verify(mut self, verify: bool) -> Self {
+        self.options.verify = verify;
+        self
+    }
+
+    /// 允许加载的最高 class 文件主版本号（默认 52，即 Java 8）
+    pub fn max_major_version(mut self, max: u16) -> Self {
+        self.options.max_major_version = Some(max);
+        self
+    }
+
+    /// 跳过版本检查，加载任何版本的 class 文件（命令行的 `--force-version`）
+    pub fn force_version(mut self, force: bool) -> Self {
+        self.options.max_major_version = if force {
+            None
+        } else {
+            self.options
+                .max_major_version
+                .or(Some(crate::classfile::version::DEFAULT_MAX_MAJOR_VERSION))
+        };
+        self
+    }
+
+    /// 遇到不支持的操作码时怎么办（默认报错）
+    pub fn unknown_opcode_policy(mut self, policy: UnknownOpcodePolicy) -> Self {
+        self.options.unknown_opcode_policy = policy;
+        self
+    }
+
+    /// 是否检查成员的访问权限（private/protected/包访问、final 字段写入）
+    pub fn check_access(mut self, check_access: bool) -> Self {
+        self.options.check_access = check_access;
+        self
+    }
+
+    /// 把 Java 程序的输出和错误输出写进内存缓冲区而不是 stdout/stderr
+    pub fn capture_output(mut self) -> Self {
+        self.capture_output = true;
+        self
+    }
+
+    pub fn build(self) -> Jvm {
+        let mut interpreter = Interpreter::new_with_options(self.options);
+        interpreter.class_loader = Some(ClassLoader::new(self.class_paths));
+        let captured = self.capture_output.then(|| {
+            let (out, err) = (SharedBuffer::new(), SharedBuffer::new());
+            interpreter.set_stdout(Box::new(out.clone()));
+            interpreter.set_stderr(Box::new(err.clone()));
+            (out, err)
+        });
+        Jvm {
+            interpreter,
+            captured,
+        }
+    }
+}
+
+/// 一个可嵌入的虚拟机实例
+pub struct Jvm {
+    interpreter: Interpreter,
+    /// `capture_output()` 时的输出缓冲区（stdout, stderr）
+    captured: Option<(SharedBuffer, SharedBuffer)>,
+}
+
+impl Jvm {
+    pub fn builder() -> JvmBuilder {
+        JvmBuilder::new()
+    }
+
+    pub fn interpreter(&self) -> &Interpreter {
+        &self.interpreter
+    }
+
+    pub fn interpreter_mut(&mut self) -> &mut Interpreter {
+        &mut self.interpreter
+    }
+
+    /// 取出解释器（例如交给调试会话），捕获的输出缓冲区随之丢弃
+    pub fn into_interpreter(self) -> Interpreter {
+        self.interpreter
+    }
+
+    /// 取消句柄：可以发送到其他线程，用来停止正在运行的 Java 代码
+    pub fn cancellation_token(&self) -> CancelHandle {
+        self.interpreter.cancellation_token()
+    }
+
+    /// 取出目前捕获的程序输出并清空缓冲区；没有开启 `capture_output()` 时返回空
+    pub fn take_captured_output(&mut self) -> Vec<u8> {
+        self.captured.as_ref().map(|(out, _)| out.take()).unwrap_or_default()
+    }
+
+    /// 取出目前捕获的错误输出并清空缓冲区；没有开启 `capture_output()` 时返回空
+    pub fn take_captured_errors(&mut self) -> Vec<u8> {
+        self.captured.as_ref().map(|(_, err)| err.take()).unwrap_or_default()
+    }
+
+    /// 加载一个已解析的类，返回类名
+    pub fn load_class(&mut self, class_file: ClassFile) -> Result<String> {
+        self.interpreter.load_class(class_file)
+    }
+
+    /// 从文件加载类，返回类名
+    ///
+    /// 文件所在目录加入类路径，它引用的其他类从那里按需加载
+    pub fn load_class_file<P: AsRef<Path>>(&mut self, path: P) -> Result<String> {
+        let path = path.as_ref();
+        let class_file = ClassFile::from_file(path)?;
+        let dir = path
+            .parent()
+            .filter(|p| !p.as_os_str().is_empty())
+            .unwrap_or(Path::new("."));
+        let known = self
+            .interpreter
+            .class_loader
+            .as_ref()
+            .is_some_and(|loader| loader.class_paths().contains(&dir));
+        if !known {
+            self.interpreter.add_class_path(dir);
+        }
+        self.load_class(class_file)
+    }
+
+    /// 按类名从类路径加载类（连同它的父类和接口），返回内部类名
+    ///
+    /// 类名可以写成 `com.example.Main` 或 `com/example/Main`；找不到时的
+    /// ClassNotFoundException 列出查找过的类路径
+    pub fn load_class_by_name(&mut self, name: &str) -> Result<String> {
+        let class_name = internal_class_name(name);
+        self.interpreter.ensure_class_loaded(&class_name)?;
+        Ok(class_name)
+    }
+
+    /// 运行 `public static void main(String[] args)`
+    pub fn run_main(&mut self, class_name: &str, args: &[String]) -> Result<()> {
+        self.interpreter.ensure_class_loaded(class_name)?;
+        self.interpreter.run_main(class_name, args)
+    }
+
+    /// 调用静态方法，返回值为 None 表示 void
+    pub fn call_static(
+        &mut self,
+        class_name: &str,
+        method_name: &str,
+        descriptor: &str,
+        args: &[JvmValue],
+    ) -> Result<Option<JvmValue>> {
+        self.interpreter.ensure_class_loaded(class_name)?;
+        self.interpreter
+            .invoke_static(class_name, method_name, descriptor, args.to_vec())
+    }
+
+    /// 创建对象并运行构造器，相当于 Java 的 `new C(args)`，返回新对象的句柄
+    ///
+    /// ```no_run
+    /// # use rsjvm::runtime::frame::JvmValue;
+    /// # let mut jvm = rsjvm::JvmBuilder::new().class_path("examples").build();
+    /// let point = jvm.new_instance("Point", "(II)V", &[JvmValue::Int(3), JvmValue::Int(4)])?;
+    /// let x = jvm.call_virtual(&point, "getX", "()I", &[])?;
+    /// jvm.release(point);
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    ///
+    /// 构造器不继承，`class_name` 自身没有这个描述符的 `<init>` 时返回 NoSuchMethodError。
+    /// 句柄是 GC 根：对象在 `release` 之前不会被回收，被整理移动后句柄仍然指向它
+    pub fn new_instance(
+        &mut self,
+        class_name: &str,
+        descriptor: &str,
+        args: &[JvmValue],
+    ) -> Result<ObjectHandle> {
+        if !class_name.starts_with("java/") {
+            self.interpreter.ensure_class_loaded(class_name)?;
+        }
+        let object = self
+            .interpreter
+            .instantiate(class_name, descriptor, args.to_vec())?;
+        Ok(self.interpreter.pin(object))
+    }
+
+    /// 调用实例方法，按对象的运行时类型分派
+    pub fn call_virtual(
+        &mut self,
+        object: &ObjectHandle,
+        method_name: &str,
+        descriptor: &str,
+        args: &[JvmValue],
+    ) -> Result<Option<JvmValue>> {
+        let object = self.interpreter.resolve(object)?;
+        self.interpreter
+            .invoke_virtual(object, method_name, descriptor, args.to_vec())
+    }
+
+    /// 把方法返回的对象登记为 GC 根，之后可以用于 `call_virtual`；
+    /// `value` 为 null 时抛出 NullPointerException
+    pub fn pin(&mut self, value: &JvmValue) -> Result<ObjectHandle> {
+        match value {
+            JvmValue::Reference(Some(object)) => Ok(self.interpreter.pin(*object)),
+            JvmValue::Reference(None) => {
+                Err(JavaException::null_pointer("cannot pin a null reference").into())
+            }
+            other => Err(anyhow!("pin: expected an object, got {:?}", other)),
+        }
+    }
+
+    /// 句柄指向的对象，用作 Java 方法的参数（只在下一次 GC 之前有效，应在调用前取出）
+    pub fn value(&self, object: &ObjectHandle) -> Result<JvmValue> {
+        self.interpreter.resolve_value(object)
+    }
+
+    /// 释放句柄，对象之后可以被回收
+    pub fn release(&mut self, object: ObjectHandle) {
+        self.interpreter.unpin(object);
+    }
+
+    /// 调用静态方法，参数是普通 Rust 值，返回值转换成 `R`
+    ///
+    /// ```no_run
+    /// # let mut jvm = rsjvm::JvmBuilder::new().class_path("examples").build();
+    /// let sum: i32 = jvm.call_static_typed("TestInvokeStatic", "sum_a_and_b", "(II)I", (1, 2))?;
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    ///
+    /// 参数个数、类型与描述符不符，或返回值不能转换成 `R` 时返回错误
+    pub fn call_static_typed<'a, R: FromJvmValue>(
+        &mut self,
+        class_name: &str,
+        method_name: &str,
+        descriptor: &str,
+        args: impl IntoJvmArgs<'a>,
+    ) -> Result<R> {
+        let args = args.into_jargs();
+        let params = MethodDescriptor::parse(descriptor)?.params;
+        if params.len() != args.len() {
+            return Err(anyhow!(
+                "{}.{}: descriptor expects {} but {} args supplied",
+                class_name,
+                method_name,
+                descriptor,
+                args.len()
+            ));
+        }
+        self.interpreter.ensure_class_loaded(class_name)?;
+        let result = self
+            .interpreter
+            .invoke_static_typed(class_name, method_name, descriptor, &args)?;
+        R::from_jresult(result)
+    }
+}
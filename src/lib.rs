@@ -9,12 +9,27 @@
 //! - `interpreter`: 字节码解释器，执行指令
 //! - `classloader`: 类加载器，负责加载class文件
 //! - `gc`: 垃圾回收器（简化版）
+//! - `debugger`: 调试视图，按 JVM 槽位编号展示栈帧
+//! - `jvm`: 嵌入式 API（`Jvm`/`JvmBuilder`），按需加载类并调用方法
+//!
+//! ## Features
+//!
+//! - `tracing`: 每次方法调用打开一个 tracing span，指令/GC 事件作为 tracing 事件输出
+//!   （默认构建仍使用 `log` crate）
+
+#[macro_use]
+mod logging;
 
 pub mod classfile;
 pub mod runtime;
 pub mod interpreter;
 pub mod classloader;
 pub mod gc;
+pub mod debugger;
+pub mod jvm;
+
+pub use interpreter::embed::{FromJvmValue, IntoJvmArgs, IntoJvmValue};
+pub use jvm::{Jvm, JvmBuilder};
 
 /// 通用错误类型
 pub type Result<T> = anyhow::Result<T>;
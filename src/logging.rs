@@ -0,0 +1,65 @@
+//! # 日志与 tracing 集成
+//!
+//! 默认构建使用 `log` crate 输出指令执行、GC 等事件；
+//! 启用 `tracing` feature 后，这些事件改为 tracing 事件，
+//! 并且每次方法调用都会打开一个 span（class / method / descriptor），
+//! 使解释器的活动嵌套在宿主程序（如异步服务的请求 span）之下。
+//!
+//! ## 学习要点
+//! - span 的层级与 JVM 调用栈一一对应：栈帧压入时进入 span，弹出时退出
+//! - 没有安装 subscriber 时，span 的创建只是一次 callsite 检查，开销可以忽略
+
+/// 输出 trace 级别事件（指令级别的详细日志）
+macro_rules! jvm_trace {
+    ($($arg:tt)+) => {
+        #[cfg(feature = "tracing")]
+        tracing::trace!($($arg)+);
+        #[cfg(not(feature = "tracing"))]
+        log::trace!($($arg)+);
+    };
+}
+
+/// 输出 debug 级别事件（GC、类加载等）
+macro_rules! jvm_debug {
+    ($($arg:tt)+) => {
+        #[cfg(feature = "tracing")]
+        tracing::debug!($($arg)+);
+        #[cfg(not(feature = "tracing"))]
+        log::debug!($($arg)+);
+    };
+}
+
+/// 方法调用 span
+///
+/// 不直接使用 `tracing::span::EnteredSpan`，因为它不是 `Send`，
+/// 会让 Frame/JvmThread 也失去 `Send`。这里手动通过 dispatcher 进入/退出。
+#[cfg(feature = "tracing")]
+#[derive(Debug)]
+pub struct InvocationSpan(tracing::Span);
+
+#[cfg(feature = "tracing")]
+impl InvocationSpan {
+    /// 创建并进入一个方法调用 span
+    /// 新 span 的父级是当前已进入的 span（即调用者栈帧的 span）
+    pub fn enter(class_name: &str, method_name: &str, descriptor: &str) -> Self {
+        let span = tracing::debug_span!(
+            "invoke",
+            class = %class_name,
+            method = %method_name,
+            descriptor = %descriptor
+        );
+        if let Some(id) = span.id() {
+            tracing::dispatcher::get_default(|dispatch| dispatch.enter(&id));
+        }
+        InvocationSpan(span)
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl Drop for InvocationSpan {
+    fn drop(&mut self) {
+        if let Some(id) = self.0.id() {
+            tracing::dispatcher::get_default(|dispatch| dispatch.exit(&id));
+        }
+    }
+}
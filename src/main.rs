@@ -3,9 +3,11 @@
 //! 命令行工具，用于加载和执行Java class文件
 
 use anyhow::Result;
-use clap::Parser;
-use rsjvm::classfile::ClassFile;
-use std::path::PathBuf;
+use clap::{Args, Parser, ValueEnum};
+use rsjvm::classfile::{ClassFile, ParserOptions};
+use rsjvm::classloader::{internal_class_name, split_class_path, ClassLoader};
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
 
 #[derive(Parser)]
 #[command(name = "rsjvm")]
@@ -19,68 +21,512 @@ struct Cli {
 enum Commands {
     /// 解析并显示class文件信息
     Parse {
-        /// class文件路径
-        #[arg(value_name = "FILE")]
-        file: PathBuf,
+        #[command(flatten)]
+        input: InputArgs,
 
         /// 显示详细信息
         #[arg(short, long)]
         verbose: bool,
+
+        /// 列出 static final 字段的常量值（包括可从 <clinit> 静态算出的值）
+        #[arg(long)]
+        constants: bool,
+
+        /// 输出格式：text 给人读，json 给其他工具读（--verbose 时包含每个方法的反汇编）
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+
+        /// 跳过 class 文件版本检查（默认拒绝 Java 8 以后的版本，它们可能用到不支持的字节码）
+        #[arg(long)]
+        force_version: bool,
+
+        #[command(flatten)]
+        limits: LimitArgs,
+    },
+
+    /// 反汇编class文件中的方法（类似 javap -c）
+    Disasm {
+        #[command(flatten)]
+        input: InputArgs,
+
+        /// 只反汇编指定名字的方法
+        #[arg(short, long)]
+        method: Option<String>,
+
+        #[command(flatten)]
+        limits: LimitArgs,
+    },
+
+    /// 加载（不运行）class 文件，显示加载开销：方法、字段、字节码、常量池和引用的类
+    Info {
+        #[command(flatten)]
+        input: InputArgs,
+
+        /// 沿常量池的类引用递归加载用到的类，打印依赖树和所有类的合计
+        #[arg(long)]
+        deep: bool,
+
+        /// 类路径：目录或 jar，用系统的路径分隔符隔开（也可以写作 -cp）。
+        /// 指定后 FILE 也可以是类名（如 com.example.Main），在类路径中查找
+        #[arg(long, visible_alias = "cp", value_name = "PATHS")]
+        classpath: Option<String>,
+
+        /// 跳过 class 文件版本检查（默认拒绝 Java 8 以后的版本，它们可能用到不支持的字节码）
+        #[arg(long)]
+        force_version: bool,
+
+        #[command(flatten)]
+        limits: LimitArgs,
     },
 
     /// 运行class文件中的方法
     Run {
-        /// class文件路径
-        #[arg(value_name = "FILE")]
-        file: PathBuf,
+        #[command(flatten)]
+        input: InputArgs,
 
         /// 要运行的方法名（如果不指定，则自动查找main方法）
         #[arg(short, long)]
         method: Option<String>,
 
-        /// 命令行参数（传递给main方法，暂未实现）
+        /// 类路径：目录或 jar，用系统的路径分隔符隔开（也可以写作 -cp）。
+        /// 指定后 FILE 也可以是类名（如 com.example.Main），在类路径中查找
+        #[arg(long, visible_alias = "cp", value_name = "PATHS")]
+        classpath: Option<String>,
+
+        /// 跳过 class 文件版本检查（默认拒绝 Java 8 以后的版本，它们可能用到不支持的字节码）
+        #[arg(long)]
+        force_version: bool,
+
+        #[command(flatten)]
+        limits: LimitArgs,
+
+        /// 监视字段访问并打印每次读写（格式 Class.field，可重复）
+        #[arg(long, value_name = "CLASS.FIELD")]
+        watch: Vec<String>,
+
+        /// 运行结束后打印已加载的类和堆直方图（输出顺序固定，可用于比较两次运行）
+        #[arg(long)]
+        stats: bool,
+
+        /// 逐条打印执行的指令、栈顶的值和被改写的局部变量（输出到标准错误）
+        #[arg(long)]
+        trace: bool,
+
+        /// 运行结束后打印执行最多的操作码和热点方法
+        #[arg(long)]
+        profile: bool,
+
+        /// 堆中最多容纳的存活对象数，超过时抛出 OutOfMemoryError
+        #[arg(long, value_name = "N")]
+        max_heap_objects: Option<usize>,
+
+        /// 最多执行的指令条数，超过时停止运行并报告停在哪里（防止死循环）
+        #[arg(long, value_name = "N")]
+        max_instructions: Option<u64>,
+
+        /// 运行结束后打印堆中每个对象的类和字段值（引用写作 @索引）
+        #[arg(long)]
+        dump_heap: bool,
+
+        /// 遇到不支持的操作码时：error 直接报错；report 停止运行并报告执行到哪里、
+        /// 出错指令前后的反汇编和栈轨迹（同时关闭加载时的字节码校验，否则含未知操作码的类无法加载）
+        #[arg(long, value_enum, value_name = "MODE", default_value = "error")]
+        on_unknown_opcode: UnknownOpcodeMode,
+
+        /// 每次 GC 向标准错误输出一行日志（如 "[gc] collected 123 objects in 0.4ms, 456 live"）
+        #[arg(long)]
+        gc_log: bool,
+
+        /// 命令行参数（作为 String[] 传递给main方法）
         #[arg(trailing_var_arg = true)]
         args: Vec<String>,
     },
 
+    /// 交互式调试：设置断点、单步执行、查看栈帧（输入 help 查看命令）
+    Debug {
+        #[command(flatten)]
+        input: InputArgs,
+
+        /// 要调试的静态方法名（如果不指定，则调试main方法）
+        #[arg(short, long)]
+        method: Option<String>,
+
+        /// 从文件读取调试命令（每行一条），而不是从标准输入交互读取
+        #[arg(long, value_name = "FILE")]
+        script: Option<PathBuf>,
+
+        /// 跳过 class 文件版本检查（默认拒绝 Java 8 以后的版本，它们可能用到不支持的字节码）
+        #[arg(long)]
+        force_version: bool,
+
+        #[command(flatten)]
+        limits: LimitArgs,
+    },
+
     /// 显示版本信息
     Version,
 }
-fn main() -> Result<()> {
-    run_class_file(
-        &PathBuf::from("examples/MainTest.class"),
-        Some("main"),
-        vec![],
-    )?;
-    Ok(())
-    // parse_class_file(&PathBuf::from("examples/HelloPrintln.class"), true)
+
+/// `run --on-unknown-opcode` 的取值
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum UnknownOpcodeMode {
+    Error,
+    Report,
 }
-// fn main() -> Result<()> {
-//     env_logger::init();
 
-//     let cli = Cli::parse();
+/// `parse` 的输出格式
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    Text,
+    Json,
+}
 
-//     match cli.command {
-//         Commands::Parse { file, verbose } => {
-//             parse_class_file(&file, verbose)?;
-//         }
-//         Commands::Run { file, method, args } => {
-//             run_class_file(&file, method.as_deref(), args)?;
-//         }
-//         Commands::Version => {
-//             println!("RSJVM version {}", env!("CARGO_PKG_VERSION"));
-//             println!("一个用于学习JVM原理的Rust实现");
-//         }
-//     }
+/// class 文件来源：文件路径、`-`（标准输入）或十六进制字符串
+#[derive(Args)]
+struct InputArgs {
+    /// class文件路径，`-` 表示从标准输入读取
+    #[arg(value_name = "FILE", required_unless_present = "class_bytes_hex")]
+    file: Option<PathBuf>,
 
-//     Ok(())
-// }
+    /// 直接以十六进制字符串给出class文件内容（可包含空白）
+    #[arg(long, value_name = "HEX", conflicts_with = "file")]
+    class_bytes_hex: Option<String>,
+}
+
+impl InputArgs {
+    fn source(&self) -> ClassSource {
+        match (&self.file, &self.class_bytes_hex) {
+            (_, Some(hex)) => ClassSource::Hex(hex.clone()),
+            (Some(path), None) if path.as_os_str() == "-" => ClassSource::Stdin,
+            (Some(path), None) => ClassSource::File(path.clone()),
+            (None, None) => unreachable!("clap requires FILE or --class-bytes-hex"),
+        }
+    }
+}
+
+impl InputArgs {
+    /// `run` 子命令的来源：FILE 不是已存在的文件、也不以 .class 结尾时是类名，在类路径中查找
+    /// （没有给出类路径时查找当前目录）
+    fn run_source(&self, class_path: Option<&[PathBuf]>) -> ClassSource {
+        match &self.file {
+            Some(path) if self.class_bytes_hex.is_none() && is_class_name(path) => ClassSource::Named {
+                class_name: internal_class_name(&path.to_string_lossy()),
+                class_path: class_path.map_or_else(|| vec![PathBuf::from(".")], <[_]>::to_vec),
+            },
+            _ => self.source(),
+        }
+    }
+}
+
+/// 命令行上的 FILE 是类名而不是 class 文件
+fn is_class_name(path: &Path) -> bool {
+    path.as_os_str() != "-"
+        && path.extension().is_none_or(|ext| ext != "class")
+        && !path.is_file()
+}
+
+/// class 文件的来源
+enum ClassSource {
+    File(PathBuf),
+    Stdin,
+    Hex(String),
+    /// 按类名（内部形式）在类路径中查找
+    Named {
+        class_name: String,
+        class_path: Vec<PathBuf>,
+    },
+}
+
+impl ClassSource {
+    /// 读取 class 文件的全部字节
+    fn read(&self) -> Result<Vec<u8>> {
+        use anyhow::Context;
+        use std::io::Read;
+
+        match self {
+            ClassSource::File(path) => {
+                std::fs::read(path).with_context(|| format!("failed to read {:?}", path))
+            }
+            ClassSource::Stdin => {
+                let mut bytes = Vec::new();
+                std::io::stdin()
+                    .read_to_end(&mut bytes)
+                    .context("failed to read class bytes from stdin")?;
+                Ok(bytes)
+            }
+            ClassSource::Hex(hex) => decode_hex(hex),
+            ClassSource::Named { .. } => unreachable!("named classes are read by the class loader"),
+        }
+    }
+
+    /// 按需加载其他类时使用的类路径：class 文件所在目录，stdin/十六进制输入使用当前目录，
+    /// 按类名查找时就是查找它的类路径
+    fn class_path(&self) -> Vec<PathBuf> {
+        match self {
+            ClassSource::File(path) => vec![class_path_of(path)],
+            ClassSource::Stdin | ClassSource::Hex(_) => vec![PathBuf::from(".")],
+            ClassSource::Named { class_path, .. } => class_path.clone(),
+        }
+    }
+}
+
+impl std::fmt::Display for ClassSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ClassSource::File(path) => write!(f, "{:?}", path),
+            ClassSource::Stdin => write!(f, "<stdin>"),
+            ClassSource::Hex(_) => write!(f, "<--class-bytes-hex>"),
+            ClassSource::Named { class_name, .. } => write!(f, "{}", class_name.replace('/', ".")),
+        }
+    }
+}
+
+/// 解码十六进制字符串（忽略空白）
+fn decode_hex(hex: &str) -> Result<Vec<u8>> {
+    let digits: Vec<(usize, char)> = hex
+        .char_indices()
+        .filter(|(_, c)| !c.is_whitespace())
+        .collect();
+    if !digits.len().is_multiple_of(2) {
+        return Err(anyhow::anyhow!(
+            "--class-bytes-hex has an odd number of hex digits ({})",
+            digits.len()
+        ));
+    }
+    digits
+        .chunks(2)
+        .map(|pair| {
+            let mut byte = 0u8;
+            for &(pos, c) in pair {
+                let digit = c.to_digit(16).ok_or_else(|| {
+                    anyhow::anyhow!("--class-bytes-hex: invalid hex digit {:?} at position {}", c, pos)
+                })?;
+                byte = byte * 16 + digit as u8;
+            }
+            Ok(byte)
+        })
+        .collect()
+}
+
+/// 读取并解析 class 文件，错误信息带上来源
+fn load_class_file(source: &ClassSource, options: &ParserOptions) -> Result<ClassFile> {
+    use anyhow::Context;
+
+    if let ClassSource::Named { class_name, class_path } = source {
+        // 与按需加载其他类相同的查找规则；找不到时的错误列出查找过的类路径
+        return ClassLoader::new(class_path.clone()).read_class(class_name);
+    }
+    let bytes = source.read()?;
+    ClassFile::from_bytes_with_options(&bytes, options)
+        .with_context(|| format!("failed to parse class file from {}", source))
+}
+
+/// 版本太新的 class 文件可能用到不支持的字节码，除非指定了 `--force-version`，否则拒绝
+fn check_version(class_file: &ClassFile, force_version: bool) -> Result<()> {
+    if force_version {
+        return Ok(());
+    }
+    rsjvm::classfile::version::check_class_version(
+        class_file,
+        rsjvm::classfile::version::DEFAULT_MAX_MAJOR_VERSION,
+    )
+}
+
+/// 解析限制（未指定的项使用默认值）
+#[derive(Args)]
+struct LimitArgs {
+    /// 常量池总字节数上限
+    #[arg(long, value_name = "BYTES")]
+    max_constant_pool_bytes: Option<usize>,
+
+    /// 单个 Utf8 常量的最大长度
+    #[arg(long, value_name = "BYTES")]
+    max_utf8_length: Option<usize>,
+
+    /// 每个类的最大方法数
+    #[arg(long, value_name = "N")]
+    max_methods: Option<usize>,
+
+    /// 单个方法字节码的最大长度
+    #[arg(long, value_name = "BYTES")]
+    max_code_length: Option<usize>,
+
+    /// 解析时检查常量池项之间的引用
+    #[arg(long)]
+    validate_constant_pool: bool,
+}
+
+impl LimitArgs {
+    fn to_options(&self) -> ParserOptions {
+        let defaults = ParserOptions::default();
+        ParserOptions {
+            max_constant_pool_bytes: self
+                .max_constant_pool_bytes
+                .unwrap_or(defaults.max_constant_pool_bytes),
+            max_utf8_length: self.max_utf8_length.unwrap_or(defaults.max_utf8_length),
+            max_methods: self.max_methods.unwrap_or(defaults.max_methods),
+            max_code_length: self.max_code_length.unwrap_or(defaults.max_code_length),
+            validate_constant_pool: self.validate_constant_pool,
+        }
+    }
+}
+
+/// 把 java 风格的 `-cp` 改写为 `--classpath`（clap 的短选项只能是一个字符）；
+/// `--` 之后交给 Java 程序的参数原样保留
+fn java_style_args(args: impl Iterator<Item = OsString>) -> Vec<OsString> {
+    let mut in_program_args = false;
+    args.map(|arg| {
+        in_program_args |= arg == "--";
+        if !in_program_args && arg == "-cp" {
+            OsString::from("--classpath")
+        } else {
+            arg
+        }
+    })
+    .collect()
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse_from(java_style_args(std::env::args_os()));
+    init_logging(matches!(cli.command, Commands::Run { gc_log: true, .. }));
+
+    match cli.command {
+        Commands::Parse {
+            input,
+            verbose,
+            constants,
+            format,
+            force_version,
+            limits,
+        } => {
+            let source = input.source();
+            match format {
+                OutputFormat::Text => parse_class_file(
+                    &source,
+                    verbose,
+                    constants,
+                    force_version,
+                    &limits.to_options(),
+                )?,
+                OutputFormat::Json => {
+                    let class_file = load_class_file(&source, &limits.to_options())?;
+                    check_version(&class_file, force_version)?;
+                    let report = rsjvm::classfile::report::ClassReport::new(&class_file, verbose)?;
+                    println!("{}", report.to_json()?);
+                }
+            }
+        }
+        Commands::Disasm {
+            input,
+            method,
+            limits,
+        } => {
+            disasm_class_file(&input.source(), method.as_deref(), &limits.to_options())?;
+        }
+        Commands::Info {
+            input,
+            deep,
+            classpath,
+            force_version,
+            limits,
+        } => {
+            let class_path = classpath.as_deref().map(split_class_path);
+            let source = input.run_source(class_path.as_deref());
+            info_class_file(
+                &source,
+                class_path.unwrap_or_else(|| source.class_path()),
+                deep,
+                force_version,
+                &limits.to_options(),
+            )?;
+        }
+        Commands::Run {
+            input,
+            method,
+            classpath,
+            force_version,
+            limits,
+            watch,
+            stats,
+            trace,
+            profile,
+            max_heap_objects,
+            max_instructions,
+            dump_heap,
+            on_unknown_opcode,
+            gc_log: _,
+            args,
+        } => {
+            let class_path = classpath.as_deref().map(split_class_path);
+            let source = input.run_source(class_path.as_deref());
+            run_class_file(
+                &source,
+                class_path.unwrap_or_else(|| source.class_path()),
+                method.as_deref(),
+                &limits.to_options(),
+                &watch,
+                RunFlags {
+                    stats,
+                    trace,
+                    profile,
+                    max_heap_objects,
+                    max_instructions,
+                    dump_heap,
+                    force_version,
+                    on_unknown_opcode,
+                },
+                args,
+            )?;
+        }
+        Commands::Debug {
+            input,
+            method,
+            script,
+            force_version,
+            limits,
+        } => {
+            debug_class_file(
+                &input.source(),
+                method.as_deref(),
+                script.as_deref(),
+                force_version,
+                &limits.to_options(),
+            )?;
+        }
+        Commands::Version => {
+            println!("RSJVM version {}", env!("CARGO_PKG_VERSION"));
+            println!("一个用于学习JVM原理的Rust实现");
+        }
+    }
+
+    Ok(())
+}
+
+/// 按 RUST_LOG 初始化日志；`gc_log` 时无论 RUST_LOG 如何都输出 GC 日志，且只输出消息本身
+fn init_logging(gc_log: bool) {
+    let mut builder = env_logger::Builder::from_default_env();
+    if gc_log {
+        builder
+            .filter_module("rsjvm::gc", log::LevelFilter::Info)
+            .format_timestamp(None)
+            .format_level(false)
+            .format_target(false);
+    }
+    builder.init();
+}
 
 /// 解析并显示class文件信息
-fn parse_class_file(path: &PathBuf, verbose: bool) -> Result<()> {
-    println!("正在解析: {:?}\n", path);
+fn parse_class_file(
+    source: &ClassSource,
+    verbose: bool,
+    constants: bool,
+    force_version: bool,
+    options: &ParserOptions,
+) -> Result<()> {
+    println!("正在解析: {}\n", source);
 
-    let class_file = ClassFile::from_file(path)?;
+    let class_file = load_class_file(source, options)?;
+    check_version(&class_file, force_version)?;
 
     // 基本信息
     println!("=== 基本信息 ===");
@@ -94,6 +540,9 @@ fn parse_class_file(path: &PathBuf, verbose: bool) -> Result<()> {
     println!("类名: {}", class_file.get_class_name()?);
     println!("父类: {}", class_file.get_super_class_name()?);
     println!("访问标志: 0x{:04X}", class_file.access_flags);
+    if let Some(source_file) = class_file.get_source_file()? {
+        println!("源文件: {}", source_file);
+    }
 
     // 接口
     if !class_file.interfaces.is_empty() {
@@ -118,6 +567,7 @@ fn parse_class_file(path: &PathBuf, verbose: bool) -> Result<()> {
         let name = class_file.constant_pool.get_utf8(method.name_index)?;
         let descriptor = class_file.constant_pool.get_utf8(method.descriptor_index)?;
         println!("  [{}] {} : {}", i, name, descriptor);
+        print_method_attributes(&method.attributes, &class_file.constant_pool)?;
 
         if verbose {
             // 尝试解析Code属性
@@ -131,7 +581,8 @@ fn parse_class_file(path: &PathBuf, verbose: bool) -> Result<()> {
 
                         if verbose {
                             println!("      bytecode:");
-                            print_bytecode(&code_attr.code);
+                            print_disassembly(&code_attr.code, &class_file.constant_pool, 8);
+                            print_debug_tables(&code_attr, &class_file.constant_pool)?;
                         }
                     }
                 }
@@ -139,6 +590,18 @@ fn parse_class_file(path: &PathBuf, verbose: bool) -> Result<()> {
         }
     }
 
+    // static final 常量
+    if constants {
+        let statics = rsjvm::classfile::static_constants::static_final_constants(&class_file)?;
+        println!("\n=== 静态常量 ({}) ===", statics.len());
+        for constant in &statics {
+            println!(
+                "  {} : {} = {}",
+                constant.name, constant.descriptor, constant.value
+            );
+        }
+    }
+
     // 常量池（详细模式）
     if verbose {
         println!(
@@ -150,7 +613,11 @@ fn parse_class_file(path: &PathBuf, verbose: bool) -> Result<()> {
                 continue; // 跳过索引0
             }
             if let Some(entry) = entry {
-                println!("  [{}] {:?}", i, entry);
+                // 引用其他项的常量按 javap 的写法展开，展开失败时退回原始结构
+                match rsjvm::interpreter::disasm::symbol(&class_file.constant_pool, i as u16) {
+                    Ok(symbol) => println!("  [{}] {}", i, symbol),
+                    Err(_) => println!("  [{}] {:?}", i, entry),
+                }
             }
         }
     }
@@ -158,6 +625,144 @@ fn parse_class_file(path: &PathBuf, verbose: bool) -> Result<()> {
     Ok(())
 }
 
+/// 打印方法的 throws 子句和泛型签名（有的话）
+fn print_method_attributes(
+    attributes: &[rsjvm::classfile::attribute::AttributeInfo],
+    cp: &rsjvm::classfile::constant_pool::ConstantPool,
+) -> Result<()> {
+    for attr in attributes {
+        match cp.get_utf8(attr.name_index)?.as_str() {
+            "Exceptions" => println!("      throws: {}", attr.parse_exceptions(cp)?.join(", ")),
+            "Signature" => println!("      signature: {}", attr.parse_signature(cp)?),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// 按 javap 的格式打印 Code 属性中的行号表和局部变量表（有的话）
+fn print_debug_tables(
+    code_attr: &rsjvm::classfile::attribute::CodeAttribute,
+    cp: &rsjvm::classfile::constant_pool::ConstantPool,
+) -> Result<()> {
+    let lines = code_attr.line_number_table(cp)?;
+    if !lines.is_empty() {
+        println!("      LineNumberTable:");
+        for (start_pc, line) in lines {
+            println!("        line {}: {}", line, start_pc);
+        }
+    }
+    let locals = code_attr.local_variable_table(cp)?;
+    if !locals.is_empty() {
+        println!("      LocalVariableTable:");
+        println!("        Start  Length  Slot  Name   Signature");
+        for entry in locals {
+            println!(
+                "        {:>5}  {:>6}  {:>4}  {:<5}  {}",
+                entry.start_pc,
+                entry.length,
+                entry.index,
+                cp.get_utf8(entry.name_index)?,
+                cp.get_utf8(entry.descriptor_index)?
+            );
+        }
+    }
+    Ok(())
+}
+
+/// 反汇编class文件中的方法
+/// 加载类并打印 [`ClassInfo`](rsjvm::runtime::ClassInfo)；`deep` 时递归加载引用的类，打印依赖树和合计
+fn info_class_file(
+    source: &ClassSource,
+    class_path: Vec<PathBuf>,
+    deep: bool,
+    force_version: bool,
+    options: &ParserOptions,
+) -> Result<()> {
+    let class_file = load_class_file(source, options)?;
+    check_version(&class_file, force_version)?;
+    let class_name = class_file.get_class_name()?;
+
+    let mut metaspace = rsjvm::runtime::Metaspace::new();
+    if force_version {
+        metaspace.set_max_major_version(None);
+    }
+    metaspace.load_class(class_file)?;
+    println!("{}", metaspace.class_info(&class_name)?);
+    if deep {
+        let tree = metaspace.load_dependencies(&ClassLoader::new(class_path), &class_name);
+        println!("\n依赖树:");
+        print!("{}", tree);
+        println!("\n{}", metaspace.load_summary());
+    }
+    Ok(())
+}
+
+fn disasm_class_file(source: &ClassSource, method_name: Option<&str>, options: &ParserOptions) -> Result<()> {
+    use anyhow::Context;
+
+    let class_file = load_class_file(source, options)?;
+    println!("类名: {}", class_file.get_class_name()?);
+
+    let mut found = false;
+    for method in &class_file.methods {
+        let name = class_file.constant_pool.get_utf8(method.name_index)?;
+        if method_name.is_some_and(|wanted| wanted != name) {
+            continue;
+        }
+        found = true;
+        let descriptor = class_file.constant_pool.get_utf8(method.descriptor_index)?;
+        println!("\n  {}:{}", name, descriptor);
+
+        let mut code = None;
+        for attr in &method.attributes {
+            if class_file.constant_pool.get_utf8(attr.name_index)? == "Code" {
+                code = Some(attr.parse_code_attribute()?);
+            }
+        }
+        let Some(code) = code else {
+            println!("    (没有 Code 属性)");
+            continue;
+        };
+        println!("    Code:");
+        let instructions =
+            rsjvm::interpreter::disasm::disassemble(&code.code, &class_file.constant_pool)
+                .with_context(|| format!("failed to disassemble {}:{}", name, descriptor))?;
+        for instruction in instructions {
+            println!("{}", indent(&instruction.to_string(), 4));
+        }
+    }
+
+    if let Some(name) = method_name.filter(|_| !found) {
+        return Err(anyhow::anyhow!("方法未找到: {}", name));
+    }
+    Ok(())
+}
+
+/// 打印反汇编结果；字节码无法反汇编时退回十六进制
+fn print_disassembly(code: &[u8], cp: &rsjvm::classfile::constant_pool::ConstantPool, width: usize) {
+    match rsjvm::interpreter::disasm::disassemble(code, cp) {
+        Ok(instructions) => {
+            for instruction in instructions {
+                println!("{}", indent(&instruction.to_string(), width));
+            }
+        }
+        Err(e) => {
+            println!("{}(无法反汇编: {})", " ".repeat(width), e);
+            print_bytecode(code);
+        }
+    }
+}
+
+/// 给多行文本的每一行加缩进
+fn indent(text: &str, width: usize) -> String {
+    let pad = " ".repeat(width);
+    text.lines()
+        .map(|line| format!("{}{}", pad, line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 /// 打印字节码（十六进制）
 fn print_bytecode(code: &[u8]) {
     for (i, chunk) in code.chunks(16).enumerate() {
@@ -169,102 +774,252 @@ fn print_bytecode(code: &[u8]) {
     }
 }
 
-/// 查找main方法
-fn find_main_method(class_file: &ClassFile) -> Result<&rsjvm::classfile::MethodInfo> {
-    const ACC_PUBLIC: u16 = 0x0001;
-    const ACC_STATIC: u16 = 0x0008;
+/// 没有 main 方法时的错误
+fn missing_main() -> anyhow::Error {
+    anyhow::anyhow!("找不到 public static void main(String[] args) 方法")
+}
 
-    for method in &class_file.methods {
-        let name = class_file.constant_pool.get_utf8(method.name_index)?;
-        let descriptor = class_file.constant_pool.get_utf8(method.descriptor_index)?;
+/// class 文件所在目录，作为默认类路径
+fn class_path_of(path: &Path) -> PathBuf {
+    path.parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or(Path::new("."))
+        .to_path_buf()
+}
 
-        // 检查是否是main方法
-        if name == "main" && descriptor == "([Ljava/lang/String;)V" {
-            // 检查访问标志：必须是 public static
-            if (method.access_flags & ACC_PUBLIC) != 0 && (method.access_flags & ACC_STATIC) != 0 {
-                return Ok(method);
-            }
+/// 选中的方法没有字节码（接口/抽象方法）时的错误，列出同一类路径下的实现类
+fn abstract_method_error(class_path: Vec<PathBuf>, class_name: &str, method_name: &str) -> anyhow::Error {
+    let mut message = format!(
+        "cannot execute abstract/interface method {}.{}; it has no bytecode \u{2014} did you mean to run an implementing class?",
+        class_name.replace('/', "."),
+        method_name
+    );
+    let candidates = ClassLoader::new(class_path.clone())
+        .find_implementations(class_name)
+        .unwrap_or_default();
+    if candidates.is_empty() {
+        message.push_str(&format!(
+            "\n  no implementing classes found in {:?}",
+            class_path
+        ));
+    } else {
+        message.push_str(&format!("\n  candidates in {:?}:", class_path));
+        for candidate in candidates {
+            message.push_str(&format!("\n    {}", candidate.replace('/', ".")));
         }
     }
+    anyhow::anyhow!(message)
+}
 
-    Err(anyhow::anyhow!(
-        "找不到 public static void main(String[] args) 方法"
-    ))
+/// `run` 子命令的开关
+struct RunFlags {
+    /// 运行结束后打印统计
+    stats: bool,
+    /// 逐条跟踪指令
+    trace: bool,
+    /// 打印性能剖析
+    profile: bool,
+    /// 堆中存活对象数上限
+    max_heap_objects: Option<usize>,
+    /// 指令条数上限
+    max_instructions: Option<u64>,
+    /// 运行结束后转储堆
+    dump_heap: bool,
+    /// 跳过 class 文件版本检查
+    force_version: bool,
+    /// 遇到不支持的操作码时怎么办
+    on_unknown_opcode: UnknownOpcodeMode,
+}
+
+/// 在调试器中运行class文件中的方法
+///
+/// 没有 `--script` 时从标准输入逐行读取命令
+fn debug_class_file(
+    source: &ClassSource,
+    method_name: Option<&str>,
+    script: Option<&Path>,
+    force_version: bool,
+    options: &ParserOptions,
+) -> Result<()> {
+    use rsjvm::debugger::repl::DebugSession;
+    use rsjvm::runtime::frame::JvmValue;
+    use std::io::BufRead;
+
+    let class_file = load_class_file(source, options)?;
+    let mut jvm = rsjvm::JvmBuilder::new()
+        .class_paths(source.class_path())
+        .force_version(force_version)
+        .build();
+    let class_name = jvm.load_class(class_file)?;
+    let class = jvm.interpreter().metaspace.get_class(&class_name)?;
+    let method = match method_name {
+        Some(name) => class
+            .methods_by_name(name)
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("方法未找到: {}", name))?,
+        None => class.find_main().ok_or_else(missing_main)?,
+    };
+    let method_key = rsjvm::runtime::metaspace::method_key(&method.name, &method.descriptor);
+    // main 方法收到空的 String[]
+    let args = if method_key == "main:([Ljava/lang/String;)V" {
+        vec![JvmValue::Reference(Some(jvm.interpreter_mut().new_string_array(&[])?))]
+    } else {
+        Vec::new()
+    };
+    println!("调试 {}.{}（输入 help 查看命令）", class_name, method_key);
+
+    let mut session = DebugSession::new(jvm.into_interpreter(), &class_name, &method_key, args);
+    let stdout = std::io::stdout();
+    let mut out = stdout.lock();
+    let input: Box<dyn BufRead> = match script {
+        Some(path) => {
+            use anyhow::Context;
+            let file = std::fs::File::open(path)
+                .with_context(|| format!("failed to open script {:?}", path))?;
+            Box::new(std::io::BufReader::new(file))
+        }
+        None => Box::new(std::io::stdin().lock()),
+    };
+    session.run(input, &mut out, script.is_some())
 }
 
 /// 运行class文件中的方法
-fn run_class_file(path: &PathBuf, method_name: Option<&str>, args: Vec<String>) -> Result<()> {
-    use rsjvm::interpreter::Interpreter;
+fn run_class_file(
+    source: &ClassSource,
+    class_path: Vec<PathBuf>,
+    method_name: Option<&str>,
+    options: &ParserOptions,
+    watches: &[String],
+    flags: RunFlags,
+    args: Vec<String>,
+) -> Result<()> {
     use rsjvm::runtime::frame::JvmValue;
 
-    println!("正在加载: {:?}\n", path);
+    println!("正在加载: {}\n", source);
 
-    let class_file = ClassFile::from_file(path)?;
+    let class_file = load_class_file(source, options)?;
     let class_name = class_file.get_class_name()?;
 
     println!("类名: {}", class_name);
 
+    // 其他类从类路径（默认是 class 文件所在目录）按需加载
+    let mut builder = rsjvm::JvmBuilder::new()
+        .class_paths(class_path.clone())
+        .force_version(flags.force_version);
+    if let Some(max) = flags.max_heap_objects {
+        builder = builder.max_heap_objects(max);
+    }
+    if let Some(max) = flags.max_instructions {
+        builder = builder.max_instructions(max);
+    }
+    if flags.on_unknown_opcode == UnknownOpcodeMode::Report {
+        builder = builder
+            .unknown_opcode_policy(rsjvm::interpreter::opcode_policy::UnknownOpcodePolicy::WarnAndStop)
+            .verify(false);
+    }
+    let mut jvm = builder.build();
+    let is_abstract_class = class_file.access_flags
+        & (rsjvm::classfile::access_flags::ACC_INTERFACE
+            | rsjvm::classfile::access_flags::ACC_ABSTRACT)
+        != 0;
+    // 加载类到 Metaspace（转移所有权）
+    let class_name_owned = jvm.load_class(class_file)?;
+    let class = jvm.interpreter().metaspace.get_class(&class_name_owned)?;
+
+    // 接口/抽象类：要运行的方法没有字节码时，提前给出提示
+    if is_abstract_class {
+        let target = method_name.unwrap_or("main");
+        if class.methods_by_name(target).iter().all(|m| m.is_abstract) {
+            return Err(abstract_method_error(class_path, &class_name, target));
+        }
+    }
+
     // 查找方法
     let (method, method_to_run) = if let Some(name) = method_name {
         // 用户指定了方法名
         println!("查找方法: {}", name);
-        let mut found_method = None;
-        for method in &class_file.methods {
-            let method_name = class_file.constant_pool.get_utf8(method.name_index)?;
-            if method_name == name {
-                found_method = Some(method);
-                break;
-            }
-        }
-        let method = found_method.ok_or_else(|| anyhow::anyhow!("方法未找到: {}", name))?;
-        (method, name.to_string())
+        let method = class
+            .methods_by_name(name)
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("方法未找到: {}", name))?;
+        (method.clone(), name.to_string())
     } else {
         // 自动查找main方法
         println!("自动查找main方法...");
-        let method = find_main_method(&class_file)?;
+        let method = class.find_main().ok_or_else(missing_main)?;
         println!("✓ 找到main方法");
-        (method, "main".to_string())
+        (method.clone(), "main".to_string())
     };
 
-    if !args.is_empty() {
-        println!("命令行参数: {:?} (注意：当前版本暂不支持传递参数)", args);
-    }
-
-    let descriptor = class_file.constant_pool.get_utf8(method.descriptor_index)?;
+    let descriptor = method.descriptor.to_string();
     println!("方法签名: {} : {}", method_to_run, descriptor);
 
-    // 查找Code属性
-    let mut code_attr = None;
-    for attr in &method.attributes {
-        let attr_name = class_file.constant_pool.get_utf8(attr.name_index)?;
-        if attr_name == "Code" {
-            code_attr = Some(attr.parse_code_attribute()?);
-            break;
+    // 只有 main 形式的方法（String[] 参数）能接收命令行参数
+    let takes_args = descriptor == "([Ljava/lang/String;)V";
+    if !args.is_empty() {
+        if takes_args {
+            println!("命令行参数: {:?}", args);
+        } else {
+            println!("命令行参数: {:?} (注意：方法不接受 String[] 参数，已忽略)", args);
         }
     }
 
-    let code = code_attr.ok_or_else(|| anyhow::anyhow!("方法没有Code属性"))?;
+    if method.is_abstract || method.is_native {
+        return Err(anyhow::anyhow!("方法没有Code属性"));
+    }
 
     println!("\n=== 方法信息 ===");
-    println!("max_stack: {}", code.max_stack);
-    println!("max_locals: {}", code.max_locals);
-    println!("code_length: {}", code.code.len());
+    println!("max_stack: {}", method.max_stack);
+    println!("max_locals: {}", method.max_locals);
+    println!("code_length: {}", method.code.len());
     println!("\n字节码:");
-    print_bytecode(&code.code);
+    print_bytecode(&method.code);
 
     // 执行方法
     println!("\n=== 开始执行 ===");
-    let mut interpreter = Interpreter::new();
+    for watch in watches {
+        let (class, field) = watch
+            .rsplit_once('.')
+            .ok_or_else(|| anyhow::anyhow!("--watch 需要 Class.field 格式: {}", watch))?;
+        jvm.interpreter_mut()
+            .set_field_watch(class, field, |event| println!("[watch] {}", event));
+    }
+    jvm.interpreter_mut().set_trace(flags.trace);
+    jvm.interpreter_mut().set_profiling(flags.profile);
 
-    // 加载类到 Metaspace（转移所有权）
-    let class_name_owned = interpreter.load_class(class_file)?;
-
-    match interpreter.execute_method_with_class(
-        &class_name_owned,
-        &code.code,
-        code.max_locals as usize,
-        code.max_stack as usize,
-    ) {
+    let result = if method_to_run == "main" && takes_args {
+        jvm.run_main(&class_name_owned, &args).map(|_| None)
+    } else if takes_args {
+        let args_array = jvm.interpreter_mut().new_string_array(&args)?;
+        jvm.call_static(
+            &class_name_owned,
+            &method_to_run,
+            &descriptor,
+            &[JvmValue::Reference(Some(args_array))],
+        )
+    } else {
+        jvm.interpreter_mut().execute_method_with_class(
+            &class_name_owned,
+            &rsjvm::runtime::metaspace::method_key(&method.name, &method.descriptor),
+            &method.code,
+            method.max_locals,
+            method.max_stack,
+        )
+    };
+    if let Some(profile) = jvm.interpreter_mut().take_profile() {
+        println!("\n{}", profile);
+    }
+    let interpreter = jvm.interpreter();
+    if flags.stats {
+        println!("\n{}", interpreter.run_stats());
+    }
+    if flags.dump_heap {
+        println!("\n{}", interpreter.heap_dump());
+    }
+
+    match result {
         Ok(return_value) => {
             println!("✓ 执行成功！");
 
@@ -283,7 +1038,15 @@ fn run_class_file(path: &PathBuf, method_name: Option<&str>, args: Vec<String>)
             }
         }
         Err(e) => {
-            println!("✗ 执行失败: {}", e);
+            match e.downcast_ref::<rsjvm::runtime::ExecutionError>() {
+                Some(error) => {
+                    println!("✗ 执行失败: {:#}", error.cause);
+                    for frame in &error.frames {
+                        println!("    at {}", frame);
+                    }
+                }
+                None => println!("✗ 执行失败: {}", e),
+            }
             return Err(e);
         }
     }
@@ -0,0 +1,285 @@
+//! # 类的加载开销
+//!
+//! 加载一个类时方法区记下了它的加载时刻和耗时，[`Metaspace::class_info`] 再从类元数据中
+//! 统计方法、字段、字节码和常量池，说明"加载这个类"到底往方法区放了什么。
+//! [`Metaspace::load_dependencies`] 从常量池的 Class 项出发，沿引用递归加载用户类，得到依赖树。
+//!
+//! ## 学习要点
+//! - 类文件中最大的部分往往是常量池，其中大多是 Utf8 项（名字、描述符、属性名）
+//! - 常量池的 Class 项列出了这个类用到的所有类：父类、new 的类、调用和字段访问的目标类、
+//!   catch 的异常类，所以它就是类的依赖关系
+//! - 类之间的引用可以成环（A 引用 B，B 也引用 A），递归加载时必须记录已经展开过的类
+//! - 真实 JVM 按需加载：解析到某个引用时才加载目标类，`--deep` 只是提前把它们都加载进来
+
+use super::metaspace::Metaspace;
+use crate::classfile::constant_pool::ConstantPoolEntry;
+use crate::classloader::ClassLoader;
+use crate::Result;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fmt;
+use std::time::Duration;
+
+/// 一个已加载类的统计
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClassInfo {
+    /// 类名
+    pub name: String,
+    /// 方法数
+    pub methods: usize,
+    /// 字段数（静态和实例）
+    pub fields: usize,
+    /// 所有方法的字节码总字节数
+    pub bytecode_bytes: usize,
+    /// 常量池项数（不计 0 号索引和 Long/Double 占用的第二个索引）
+    pub constant_pool_entries: usize,
+    /// 按类型统计的常量池项数，类型名见 [`ConstantPoolEntry::kind`]
+    pub constant_pool_kinds: BTreeMap<&'static str, usize>,
+    /// 常量池 Class 项引用的其他类（数组类取元素类），按名字排序
+    pub references: Vec<String>,
+    /// 第几个被加载的类
+    pub load_order: usize,
+    /// 加载完成的时刻（相对于方法区创建）
+    pub loaded_at: Duration,
+    /// 加载耗时
+    pub load_time: Duration,
+}
+
+/// 所有已加载类的合计
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LoadSummary {
+    /// 已加载的类数
+    pub classes: usize,
+    pub methods: usize,
+    pub fields: usize,
+    pub bytecode_bytes: usize,
+    pub constant_pool_entries: usize,
+    pub constant_pool_kinds: BTreeMap<&'static str, usize>,
+    /// 加载耗时之和
+    pub load_time: Duration,
+}
+
+/// 依赖树的节点
+#[derive(Debug, Clone, PartialEq)]
+pub struct DependencyNode {
+    /// 类名
+    pub class_name: String,
+    pub status: DependencyStatus,
+    /// 引用的类，只有第一次出现的已加载类才展开
+    pub children: Vec<DependencyNode>,
+}
+
+/// 依赖树中的类的状态
+#[derive(Debug, Clone, PartialEq)]
+pub enum DependencyStatus {
+    /// 已加载并展开
+    Loaded,
+    /// 树中已经展开过（包括引用成环的情况），不再重复展开
+    Repeated,
+    /// JDK 类：使用内置实现，没有 class 文件
+    Jdk,
+    /// 加载失败，值是错误信息
+    Missing(String),
+}
+
+impl Metaspace {
+    /// 已加载类的统计
+    pub fn class_info(&self, class_name: &str) -> Result<ClassInfo> {
+        let class = self.get_class(class_name)?;
+        let mut constant_pool_kinds = BTreeMap::new();
+        let mut references = BTreeSet::new();
+        let pool = &class.constant_pool;
+        for entry in pool.entries.iter().flatten() {
+            *constant_pool_kinds.entry(entry.kind()).or_insert(0) += 1;
+            if let ConstantPoolEntry::Class { name_index } = entry {
+                let name = pool.get_utf8(*name_index)?;
+                let name = match name.strip_prefix('[') {
+                    Some(array) => match array.trim_start_matches('[').strip_prefix('L') {
+                        Some(element) => element.trim_end_matches(';'),
+                        // 基本类型数组，如 [I
+                        None => continue,
+                    },
+                    None => &name,
+                };
+                if name != class_name {
+                    references.insert(name.to_string());
+                }
+            }
+        }
+        Ok(ClassInfo {
+            name: class_name.to_string(),
+            methods: class.methods.len(),
+            fields: class.fields.len(),
+            bytecode_bytes: class.methods.values().map(|m| m.code.len()).sum(),
+            constant_pool_entries: constant_pool_kinds.values().sum(),
+            constant_pool_kinds,
+            references: references.into_iter().collect(),
+            load_order: class.load_order,
+            loaded_at: class.loaded_at,
+            load_time: class.load_time,
+        })
+    }
+
+    /// 所有已加载类的合计
+    pub fn load_summary(&self) -> LoadSummary {
+        let mut summary = LoadSummary::default();
+        for class in self.classes_in_load_order() {
+            let Ok(info) = self.class_info(&class.name) else {
+                continue;
+            };
+            summary.classes += 1;
+            summary.methods += info.methods;
+            summary.fields += info.fields;
+            summary.bytecode_bytes += info.bytecode_bytes;
+            summary.constant_pool_entries += info.constant_pool_entries;
+            for (kind, count) in info.constant_pool_kinds {
+                *summary.constant_pool_kinds.entry(kind).or_insert(0) += count;
+            }
+            summary.load_time += info.load_time;
+        }
+        summary
+    }
+
+    /// 从 `class_name` 开始，沿常量池的 Class 项递归加载引用的用户类，返回依赖树
+    ///
+    /// 没有加载的类通过 `loader` 查找；找不到或加载失败的类记为 Missing，不中断遍历。
+    /// 每个类只展开一次，之后再出现时记为 Repeated，所以引用成环也能结束
+    pub fn load_dependencies(&mut self, loader: &ClassLoader, class_name: &str) -> DependencyNode {
+        let mut expanded = HashSet::new();
+        self.dependency_node(loader, class_name, &mut expanded)
+    }
+
+    fn dependency_node(
+        &mut self,
+        loader: &ClassLoader,
+        class_name: &str,
+        expanded: &mut HashSet<String>,
+    ) -> DependencyNode {
+        let leaf = |status| DependencyNode {
+            class_name: class_name.to_string(),
+            status,
+            children: Vec::new(),
+        };
+        if class_name.starts_with("java/") {
+            return leaf(DependencyStatus::Jdk);
+        }
+        if !expanded.insert(class_name.to_string()) {
+            return leaf(DependencyStatus::Repeated);
+        }
+        if !self.is_class_loaded(class_name) {
+            let loaded = loader
+                .read_class(class_name)
+                .and_then(|class_file| self.load_class(class_file));
+            if let Err(e) = loaded {
+                return leaf(DependencyStatus::Missing(format!("{:#}", e)));
+            }
+        }
+        let references = match self.class_info(class_name) {
+            Ok(info) => info.references,
+            Err(e) => return leaf(DependencyStatus::Missing(format!("{:#}", e))),
+        };
+        let children = references
+            .iter()
+            .map(|reference| self.dependency_node(loader, reference, expanded))
+            .collect();
+        DependencyNode {
+            class_name: class_name.to_string(),
+            status: DependencyStatus::Loaded,
+            children,
+        }
+    }
+}
+
+impl DependencyNode {
+    /// 树中所有节点的类名（先序，包括重复出现的）
+    pub fn class_names(&self) -> Vec<&str> {
+        let mut names = vec![self.class_name.as_str()];
+        for child in &self.children {
+            names.extend(child.class_names());
+        }
+        names
+    }
+
+    fn write_tree(&self, f: &mut fmt::Formatter, prefix: &str, last: bool, root: bool) -> fmt::Result {
+        let (branch, indent) = match (root, last) {
+            (true, _) => ("", ""),
+            (false, true) => ("└── ", "    "),
+            (false, false) => ("├── ", "│   "),
+        };
+        write!(f, "{}{}{}", prefix, branch, self.class_name)?;
+        match &self.status {
+            DependencyStatus::Loaded => writeln!(f)?,
+            DependencyStatus::Repeated => writeln!(f, " (已展开)")?,
+            DependencyStatus::Jdk => writeln!(f, " (JDK)")?,
+            DependencyStatus::Missing(error) => writeln!(f, " (加载失败: {})", error)?,
+        }
+        let prefix = format!("{}{}", prefix, indent);
+        for (i, child) in self.children.iter().enumerate() {
+            child.write_tree(f, &prefix, i + 1 == self.children.len(), false)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for DependencyNode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.write_tree(f, "", true, true)
+    }
+}
+
+/// 常量池各类型的项数，如 "Class 2, Methodref 1, Utf8 11"
+fn write_kinds(f: &mut fmt::Formatter, kinds: &BTreeMap<&'static str, usize>) -> fmt::Result {
+    for (i, (kind, count)) in kinds.iter().enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "{} {}", kind, count)?;
+    }
+    Ok(())
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+impl fmt::Display for ClassInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "类: {}", self.name)?;
+        writeln!(
+            f,
+            "  方法: {}, 字段: {}, 字节码: {} 字节",
+            self.methods, self.fields, self.bytecode_bytes
+        )?;
+        write!(f, "  常量池: {} 项 (", self.constant_pool_entries)?;
+        write_kinds(f, &self.constant_pool_kinds)?;
+        writeln!(f, ")")?;
+        writeln!(
+            f,
+            "  加载: 第 {} 个, 于 {:.3}ms, 耗时 {:.3}ms",
+            self.load_order,
+            millis(self.loaded_at),
+            millis(self.load_time)
+        )?;
+        write!(f, "  引用的类: ")?;
+        if self.references.is_empty() {
+            write!(f, "(无)")?;
+        }
+        write!(f, "{}", self.references.join(", "))
+    }
+}
+
+impl fmt::Display for LoadSummary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "已加载 {} 个类: 方法 {}, 字段 {}, 字节码 {} 字节, 加载耗时 {:.3}ms",
+            self.classes,
+            self.methods,
+            self.fields,
+            self.bytecode_bytes,
+            millis(self.load_time)
+        )?;
+        write!(f, "  常量池: {} 项 (", self.constant_pool_entries)?;
+        write_kinds(f, &self.constant_pool_kinds)?;
+        write!(f, ")")
+    }
+}
@@ -0,0 +1,206 @@
+//! # Java 异常
+//!
+//! 解释器执行中出现的运行时异常（除零、空指针、数组越界等）。
+//! 和普通的内部错误不同，它们会在堆上创建异常对象，
+//! 按异常表查找 catch 块，和 athrow 抛出的异常走同一条路径。
+//!
+//! ## 学习要点
+//! - JVM 规范规定了哪些指令会抛出哪些异常（如 idiv 抛 ArithmeticException）
+//! - 异常对象和普通对象一样分配在堆上
+//! - 没有被捕获的异常才会终止执行
+//! - 终止执行的错误带着出错时的栈轨迹（[`ExecutionError`]），从出错的方法一直列到入口方法
+
+use std::fmt;
+use std::time::Duration;
+
+/// 由 JVM 抛出的 Java 异常
+#[derive(Debug, Clone)]
+pub struct JavaException {
+    /// 异常类的内部名（如 "java/lang/ArithmeticException"）
+    pub class_name: String,
+    /// 异常信息（对应 Throwable.detailMessage）
+    pub message: Option<String>,
+}
+
+impl JavaException {
+    /// 创建异常
+    pub fn new(class_name: &str, message: impl Into<String>) -> Self {
+        JavaException {
+            class_name: class_name.to_string(),
+            message: Some(message.into()),
+        }
+    }
+
+    /// 创建没有异常信息的异常
+    pub fn of(class_name: &str) -> Self {
+        JavaException {
+            class_name: class_name.to_string(),
+            message: None,
+        }
+    }
+
+    /// 整数除以零
+    pub fn arithmetic(message: &str) -> Self {
+        Self::new("java/lang/ArithmeticException", message)
+    }
+
+    /// 对 null 引用的操作
+    pub fn null_pointer(message: impl Into<String>) -> Self {
+        Self::new("java/lang/NullPointerException", message)
+    }
+
+    /// 调用的方法是抽象方法（没有字节码可执行）
+    pub fn abstract_method(class_name: &str, name: &str, descriptor: &str) -> Self {
+        Self::new(
+            "java/lang/AbstractMethodError",
+            format!("{}.{}{}", class_name, name, descriptor),
+        )
+    }
+
+    /// 数组下标越界
+    pub fn array_index_out_of_bounds(index: i32, length: usize) -> Self {
+        Self::new(
+            "java/lang/ArrayIndexOutOfBoundsException",
+            format!("Index {} out of bounds for length {}", index, length),
+        )
+    }
+
+    /// 数组长度为负
+    pub fn negative_array_size(length: i32) -> Self {
+        Self::new("java/lang/NegativeArraySizeException", length.to_string())
+    }
+}
+
+impl fmt::Display for JavaException {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.message {
+            Some(message) => write!(f, "{}: {}", self.class_name, message),
+            None => write!(f, "{}", self.class_name),
+        }
+    }
+}
+
+impl std::error::Error for JavaException {}
+
+/// 栈轨迹中的一个栈帧
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrameInfo {
+    /// 类的内部名（如 "com/example/Foo"）
+    pub class_name: String,
+    pub method_name: String,
+    /// 方法描述符（如 "(II)I"）；手写字节码没有对应方法时为空
+    pub descriptor: String,
+    /// 出错的指令位置；调用者栈帧是调用指令之后的位置
+    pub pc: usize,
+    /// 操作数栈中值的个数
+    pub stack_size: usize,
+    /// 局部变量表的槽位数
+    pub max_locals: usize,
+    /// SourceFile 属性
+    pub source_file: Option<String>,
+    /// 行号表中 pc 对应的行号
+    pub line: Option<u16>,
+}
+
+impl fmt::Display for FrameInfo {
+    /// 如 `Calculator.divide(II)I pc=7 (Calculator.java:12)`
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}.{}{} pc={}",
+            self.class_name.replace('/', "."),
+            self.method_name,
+            self.descriptor,
+            self.pc
+        )?;
+        match (&self.source_file, self.line) {
+            (Some(file), Some(line)) => write!(f, " ({}:{})", file, line),
+            (Some(file), None) => write!(f, " ({})", file),
+            (None, _) => Ok(()),
+        }
+    }
+}
+
+/// 终止执行的错误及出错时的栈轨迹
+///
+/// `frames[0]` 是出错的方法，最后一项是入口方法
+#[derive(Debug)]
+pub struct ExecutionError {
+    /// 原始错误（内部错误或 "Uncaught exception: ..."）
+    pub cause: anyhow::Error,
+    pub frames: Vec<FrameInfo>,
+}
+
+impl fmt::Display for ExecutionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:#}", self.cause)?;
+        for frame in &self.frames {
+            write!(f, "\n\tat {}", frame)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ExecutionError {}
+
+/// 超出的执行限制
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExecutionLimit {
+    /// 指令条数上限
+    Instructions(u64),
+    /// 运行时间上限
+    Time(Duration),
+}
+
+/// 执行超出了 `max_instructions` 或 `time_limit`
+///
+/// 不是 Java 异常，程序不能捕获它；`location` 是停下时栈顶栈帧的位置
+#[derive(Debug, Clone)]
+pub struct ExecutionLimitExceeded {
+    pub limit: ExecutionLimit,
+    /// 已执行的指令条数
+    pub instructions: u64,
+    pub location: FrameInfo,
+}
+
+impl fmt::Display for ExecutionLimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.limit {
+            ExecutionLimit::Instructions(max) => write!(
+                f,
+                "execution limit exceeded: {} instructions executed (max_instructions={})",
+                self.instructions, max
+            )?,
+            ExecutionLimit::Time(limit) => write!(
+                f,
+                "execution limit exceeded: time limit of {:?} reached after {} instructions",
+                limit, self.instructions
+            )?,
+        }
+        write!(f, " at {}", self.location)
+    }
+}
+
+impl std::error::Error for ExecutionLimitExceeded {}
+
+/// 运行被 `CancelHandle::cancel()` 取消
+///
+/// `frames` 是停下时的栈轨迹，`frames[0]` 是栈顶
+#[derive(Debug, Clone)]
+pub struct ExecutionCancelled {
+    /// 取消前已执行的指令条数
+    pub instructions: u64,
+    pub frames: Vec<FrameInfo>,
+}
+
+impl fmt::Display for ExecutionCancelled {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "execution cancelled after {} instructions", self.instructions)?;
+        for frame in &self.frames {
+            write!(f, "\n\tat {}", frame)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ExecutionCancelled {}
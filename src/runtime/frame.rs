@@ -12,17 +12,93 @@
 //! - 操作数栈用于计算和传递参数
 //! - JVM是基于栈的虚拟机
 
+use crate::interpreter::decoded::DecodedMethod;
+use crate::runtime::exception::FrameInfo;
+use crate::runtime::heap::ObjRef;
+use crate::runtime::symbol::Symbol;
+use crate::runtime::metaspace::ExceptionTableEntry;
 use crate::Result;
 use anyhow::anyhow;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 
 /// JVM值类型
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum JvmValue {
     Int(i32),
     Long(i64),
     Float(f32),
     Double(f64),
-    Reference(Option<usize>), // 对象引用（堆上的索引）
+    Reference(Option<ObjRef>), // 对象引用（堆句柄）
+}
+
+impl JvmValue {
+    /// 是否是占两个槽位的值（category 2：long/double）
+    pub fn is_wide(&self) -> bool {
+        matches!(self, JvmValue::Long(_) | JvmValue::Double(_))
+    }
+
+    /// 非 null 引用指向的对象，其他值为 None
+    pub fn as_object_ref(&self) -> Option<ObjRef> {
+        match self {
+            JvmValue::Reference(object_ref) => *object_ref,
+            _ => None,
+        }
+    }
+
+    /// 值的种类，用于错误信息
+    pub fn kind(&self) -> &'static str {
+        match self {
+            JvmValue::Int(_) => "int",
+            JvmValue::Long(_) => "long",
+            JvmValue::Float(_) => "float",
+            JvmValue::Double(_) => "double",
+            JvmValue::Reference(_) => "reference",
+        }
+    }
+
+    /// 按字段描述符检查值的种类，并把 int 截断到 boolean/byte/char/short 的取值范围
+    ///
+    /// putfield、putstatic 和 xastore 存值前都经过这里：Z 只保留最低位，B/S 有符号截断，C 无符号截断；
+    /// 种类不符（如往 J 字段存 int）时报错
+    pub fn coerce_to_descriptor(self, descriptor: &str) -> Result<JvmValue> {
+        let first = descriptor.as_bytes().first().copied();
+        Ok(match (first, self) {
+            (Some(b'Z'), JvmValue::Int(v)) => JvmValue::Int(v & 1),
+            (Some(b'B'), JvmValue::Int(v)) => JvmValue::Int(v as i8 as i32),
+            (Some(b'C'), JvmValue::Int(v)) => JvmValue::Int(v as u16 as i32),
+            (Some(b'S'), JvmValue::Int(v)) => JvmValue::Int(v as i16 as i32),
+            (Some(b'I'), value @ JvmValue::Int(_))
+            | (Some(b'J'), value @ JvmValue::Long(_))
+            | (Some(b'F'), value @ JvmValue::Float(_))
+            | (Some(b'D'), value @ JvmValue::Double(_))
+            | (Some(b'L' | b'['), value @ JvmValue::Reference(_)) => value,
+            (_, value) => {
+                return Err(anyhow!(
+                    "expected {} for descriptor {}, got {}",
+                    descriptor_kind(descriptor),
+                    descriptor,
+                    value.kind()
+                ))
+            }
+        })
+    }
+}
+
+/// 字段描述符对应的 Java 类型名，如 J → "long"，`Ljava/lang/String;` → "reference"
+fn descriptor_kind(descriptor: &str) -> &'static str {
+    match descriptor.as_bytes().first() {
+        Some(b'Z') => "boolean",
+        Some(b'B') => "byte",
+        Some(b'C') => "char",
+        Some(b'S') => "short",
+        Some(b'I') => "int",
+        Some(b'J') => "long",
+        Some(b'F') => "float",
+        Some(b'D') => "double",
+        Some(b'L' | b'[') => "reference",
+        _ => "a valid field type",
+    }
 }
 
 /// 栈帧
@@ -32,22 +108,55 @@ pub struct Frame {
     local_vars: Vec<JvmValue>,
     /// 操作数栈
     operand_stack: Vec<JvmValue>,
+    /// 操作数栈已占用的槽位数（不超过 max_stack）
+    used_slots: usize,
 
     /// 动态链接 - 指向当前方法所属类的名称
     /// 用于解析符号引用
-    pub class_name: String,
+    pub class_name: Symbol,
 
-    /// 返回地址 - 方法正常返回后的指令位置（在调用者中的PC）
-    pub return_address: Option<usize>,
+    /// 当前执行的方法名（用于调试输出）
+    pub method_name: Symbol,
+
+    /// 当前方法的描述符，如 `(II)I`（用于栈轨迹）
+    pub descriptor: Symbol,
+
+    /// 程序计数器：下一条要执行的指令位置
+    ///
+    /// 调用其他方法时保存调用点之后的位置，被调用者返回后调用者从这里继续
+    pub pc: usize,
 
     /// 当前方法的字节码
-    /// 注意：这里使用 Vec 而不是引用，简化生命周期管理
-    pub code: Vec<u8>,
+    ///
+    /// 与 `MethodMetadata::code` 共享同一份数据：创建栈帧、执行指令时只增加引用计数，不复制字节码
+    pub code: Arc<[u8]>,
 
-    /// 操作数栈最大深度（用于调试）
+    /// 方法的预解码指令流（来自 `MethodMetadata::decoded`）；None 时逐字节解码执行
+    pub decoded: Option<Arc<DecodedMethod>>,
+
+    /// 操作数栈最大深度（槽位数，push 时检查）
     pub max_stack: usize,
     /// 局部变量表大小（用于调试）
     pub max_locals: usize,
+
+    /// 当前方法的异常表（ATHROW 时查找处理器）
+    pub exception_table: Vec<ExceptionTableEntry>,
+
+    /// 该栈帧执行的是哪个类的 `<clinit>`，正常返回时把这个类标记为已初始化
+    pub initializing_class: Option<String>,
+
+    /// synchronized 方法锁住的对象：实例方法是 this，静态方法是类的 Class 对象
+    pub monitor: Option<ObjRef>,
+    /// 是否已经获取了 `monitor`（被其他线程持有时，等调度回这个线程再获取）
+    pub monitor_entered: bool,
+
+    /// 性能剖析中这个方法的编号（开启剖析时在第一条指令执行前登记）
+    pub(crate) profile_slot: Option<usize>,
+
+    /// 方法调用 span（仅在启用 `tracing` feature 时存在）
+    /// 栈帧弹出时随之退出，保证 span 层级与调用层级一致
+    #[cfg(feature = "tracing")]
+    span: Option<crate::logging::InvocationSpan>,
 }
 
 impl Frame {
@@ -56,11 +165,22 @@ impl Frame {
         Frame {
             local_vars: vec![JvmValue::Int(0); max_locals],
             operand_stack: Vec::with_capacity(max_stack),
-            class_name: String::new(),  // 稍后设置
-            return_address: None,
-            code: Vec::new(),  // 稍后设置
+            used_slots: 0,
+            class_name: Symbol::default(), // 稍后设置
+            method_name: Symbol::default(),
+            descriptor: Symbol::default(),
+            pc: 0,
+            code: Arc::from([]),  // 稍后设置
+            decoded: None,
             max_stack,
             max_locals,
+            exception_table: Vec::new(),
+            initializing_class: None,
+            monitor: None,
+            monitor_entered: false,
+            profile_slot: None,
+            #[cfg(feature = "tracing")]
+            span: None,
         }
     }
 
@@ -68,21 +188,42 @@ impl Frame {
     pub fn new_with_context(
         max_locals: usize,
         max_stack: usize,
-        class_name: String,
-        code: Vec<u8>,
-        return_address: Option<usize>,
+        class_name: impl Into<Symbol>,
+        code: impl Into<Arc<[u8]>>,
     ) -> Self {
         Frame {
             local_vars: vec![JvmValue::Int(0); max_locals],
             operand_stack: Vec::with_capacity(max_stack),
-            class_name,
-            return_address,
-            code,
+            used_slots: 0,
+            class_name: class_name.into(),
+            method_name: Symbol::default(),
+            descriptor: Symbol::default(),
+            pc: 0,
+            code: code.into(),
+            decoded: None,
             max_stack,
             max_locals,
+            exception_table: Vec::new(),
+            initializing_class: None,
+            monitor: None,
+            monitor_entered: false,
+            profile_slot: None,
+            #[cfg(feature = "tracing")]
+            span: None,
         }
     }
 
+    /// 为该栈帧打开方法调用 span（启用 `tracing` feature 时）
+    /// 应在压入线程栈之前调用，此时当前 span 仍是调用者的 span
+    #[cfg(feature = "tracing")]
+    pub fn enter_span(&mut self, method_name: &str, descriptor: &str) {
+        self.span = Some(crate::logging::InvocationSpan::enter(
+            &self.class_name,
+            method_name,
+            descriptor,
+        ));
+    }
+
     // ==================== 局部变量表操作 ====================
 
     /// 获取局部变量
@@ -93,26 +234,70 @@ impl Frame {
     }
 
     /// 设置局部变量
+    ///
+    /// 写入 long/double 的第二个槽位会破坏这个值，前一个槽位随之失效
     pub fn set_local(&mut self, index: usize, value: JvmValue) -> Result<()> {
         if index >= self.local_vars.len() {
             return Err(anyhow!("Local variable index out of bounds: {}", index));
         }
+        if index > 0 && self.local_vars[index - 1].is_wide() {
+            self.local_vars[index - 1] = JvmValue::Int(0);
+        }
         self.local_vars[index] = value;
         Ok(())
     }
 
+    /// 读取 long/double 局部变量（占 index 和 index+1 两个槽位）
+    pub fn get_local_wide(&self, index: usize) -> Result<&JvmValue> {
+        if index + 1 >= self.local_vars.len() {
+            return Err(anyhow!("Local variable index out of bounds: {}", index + 1));
+        }
+        let value = &self.local_vars[index];
+        if !value.is_wide() {
+            return Err(anyhow!(
+                "Expected long/double in local variable {}, found {:?}",
+                index,
+                value
+            ));
+        }
+        Ok(value)
+    }
+
+    /// 设置 long/double 局部变量
+    ///
+    /// 值放在 index，index+1 只作占位（内容无意义，不能单独读取）
+    pub fn set_local_wide(&mut self, index: usize, value: JvmValue) -> Result<()> {
+        if index + 1 >= self.local_vars.len() {
+            return Err(anyhow!("Local variable index out of bounds: {}", index + 1));
+        }
+        self.set_local(index, value)?;
+        self.local_vars[index + 1] = JvmValue::Int(0);
+        Ok(())
+    }
+
     // ==================== 操作数栈操作 ====================
 
     /// 压栈
-    pub fn push(&mut self, value: JvmValue) {
+    ///
+    /// 栈中已占用的槽位加上新值超过 max_stack 时返回错误（long/double 占两个槽位）
+    pub fn push(&mut self, value: JvmValue) -> Result<()> {
+        let slots = if value.is_wide() { 2 } else { 1 };
+        if self.used_slots + slots > self.max_stack {
+            return Err(anyhow!("operand stack overflow: max_stack={}", self.max_stack));
+        }
+        self.used_slots += slots;
         self.operand_stack.push(value);
+        Ok(())
     }
 
     /// 弹栈
     pub fn pop(&mut self) -> Result<JvmValue> {
-        self.operand_stack
+        let value = self
+            .operand_stack
             .pop()
-            .ok_or_else(|| anyhow!("Operand stack is empty"))
+            .ok_or_else(|| anyhow!("Operand stack is empty"))?;
+        self.used_slots -= if value.is_wide() { 2 } else { 1 };
+        Ok(value)
     }
 
     /// 查看栈顶元素（不弹出）
@@ -122,6 +307,57 @@ impl Frame {
             .ok_or_else(|| anyhow!("Operand stack is empty"))
     }
 
+    /// 弹出栈顶恰好占 `slots` 个槽位的值，按入栈顺序返回
+    ///
+    /// 槽位模型：long/double 在操作数栈上是一个条目、占两个槽位，和局部变量表中
+    /// LSTORE/LLOAD 占用 n、n+1 两个槽位一致。所以 pop2/dup2 这类按槽位计数的指令
+    /// 遇到 long/double 时只处理一个条目；宽值跨过边界（如对 long 执行 pop）时返回错误，
+    /// 栈保持不变
+    pub fn pop_slots(&mut self, slots: usize) -> Result<Vec<JvmValue>> {
+        let mut taken = 0;
+        let mut entries = 0;
+        for value in self.operand_stack.iter().rev() {
+            if taken >= slots {
+                break;
+            }
+            taken += if value.is_wide() { 2 } else { 1 };
+            entries += 1;
+        }
+        if taken < slots {
+            return Err(anyhow!("Operand stack underflow: needs {} slots, has {}", slots, taken));
+        }
+        if taken > slots {
+            return Err(anyhow!(
+                "{}-slot stack operation would split a long/double value",
+                slots
+            ));
+        }
+        self.used_slots -= slots;
+        Ok(self.operand_stack.split_off(self.operand_stack.len() - entries))
+    }
+
+    /// dup 系列指令：复制栈顶 `slots` 个槽位，插到它下面 `depth` 个槽位之下
+    ///
+    /// dup = (1, 0)，dup_x1 = (1, 1)，dup_x2 = (1, 2)，dup2 = (2, 0)，dup2_x1 = (2, 1)，dup2_x2 = (2, 2)
+    pub fn dup_slots(&mut self, slots: usize, depth: usize) -> Result<()> {
+        let top = self.pop_slots(slots)?;
+        let under = self.pop_slots(depth)?;
+        for value in top.iter().cloned().chain(under).chain(top.iter().cloned()) {
+            self.push(value)?;
+        }
+        Ok(())
+    }
+
+    /// swap：交换栈顶两个单槽位的值
+    pub fn swap(&mut self) -> Result<()> {
+        let top = self.pop_slots(1)?;
+        let under = self.pop_slots(1)?;
+        for value in top.into_iter().chain(under) {
+            self.push(value)?;
+        }
+        Ok(())
+    }
+
     /// 弹出int值
     pub fn pop_int(&mut self) -> Result<i32> {
         match self.pop()? {
@@ -155,15 +391,81 @@ impl Frame {
     }
 
     /// 弹出引用
-    pub fn pop_ref(&mut self) -> Result<Option<usize>> {
+    pub fn pop_ref(&mut self) -> Result<Option<ObjRef>> {
         match self.pop()? {
             JvmValue::Reference(val) => Ok(val),
-            _ => Err(anyhow!("Expected Reference on stack")),
+            other => Err(anyhow!("Expected Reference on stack, found {:?}", other)),
         }
     }
 
-    /// 获取操作数栈大小
+    /// 清空操作数栈（进入异常处理器时）
+    pub fn clear_stack(&mut self) {
+        self.operand_stack.clear();
+        self.used_slots = 0;
+    }
+
+    /// 获取操作数栈大小（值的个数）
     pub fn stack_size(&self) -> usize {
         self.operand_stack.len()
     }
+
+    /// 操作数栈占用的槽位数：long/double 各占两个，与 max_stack 的计算方式一致
+    pub fn stack_slots(&self) -> usize {
+        self.used_slots
+    }
+
+    // ==================== 调试视图 ====================
+
+    /// 局部变量表（只读，按槽位索引）
+    pub fn locals(&self) -> &[JvmValue] {
+        &self.local_vars
+    }
+
+    /// 操作数栈（只读，栈底在前）
+    pub fn operands(&self) -> &[JvmValue] {
+        &self.operand_stack
+    }
+
+    /// 栈帧的快照：类、方法、pc 和两个表的大小（没有源文件和行号）
+    pub fn info(&self) -> FrameInfo {
+        FrameInfo {
+            class_name: self.class_name.to_string(),
+            method_name: self.method_name.to_string(),
+            descriptor: self.descriptor.to_string(),
+            pc: self.pc,
+            stack_size: self.operand_stack.len(),
+            max_locals: self.local_vars.len(),
+            source_file: None,
+            line: None,
+        }
+    }
+
+    /// 整体替换局部变量表和操作数栈（恢复执行快照时）
+    ///
+    /// 局部变量表的大小不能改变，操作数栈不能超过 max_stack
+    pub(crate) fn restore_values(&mut self, locals: Vec<JvmValue>, operands: Vec<JvmValue>) -> Result<()> {
+        if locals.len() != self.local_vars.len() {
+            return Err(anyhow!(
+                "expected {} local variables, got {}",
+                self.local_vars.len(),
+                locals.len()
+            ));
+        }
+        self.local_vars = locals;
+        self.clear_stack();
+        for value in operands {
+            self.push(value)?;
+        }
+        Ok(())
+    }
+
+    /// 局部变量和操作数栈中的所有值，以及 synchronized 方法锁住的对象（GC 改写引用时使用）
+    ///
+    /// 只能原地替换引用，不能改变值的类型，否则槽位计数会失效
+    pub(crate) fn roots_mut(
+        &mut self,
+    ) -> (impl Iterator<Item = &mut JvmValue>, Option<&mut ObjRef>) {
+        let values = self.local_vars.iter_mut().chain(self.operand_stack.iter_mut());
+        (values, self.monitor.as_mut())
+    }
 }
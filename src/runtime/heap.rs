@@ -6,31 +6,272 @@
 //! - 所有对象实例和数组都在堆上分配
 //! - 堆是垃圾回收的主要区域
 //! - 堆是线程共享的
+//! - 对象引用是「槽位索引 + 代数」：槽位被回收时代数加一，
+//!   指向旧对象的引用就不会误读复用该槽位的新对象
 //!
 //! ## 简化设计
 //! 这个实现使用简单的向量来模拟堆，实际JVM的堆管理要复杂得多
 
+use crate::classfile::descriptor::{jvm_default_value, FieldType};
+use crate::runtime::exception::JavaException;
 use crate::runtime::frame::JvmValue;
+use crate::runtime::layout::FieldLayout;
+use crate::runtime::nursery::Nursery;
+use crate::runtime::Symbol;
 use crate::Result;
 use anyhow::{anyhow, Ok};
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::Arc;
+
+/// 对象引用（句柄）
+///
+/// `index` 是堆槽位，`generation` 是分配时槽位的代数。
+/// 两个 u32 让 `Option<ObjRef>` 和 i64 一样只占 8 字节的数据部分
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct ObjRef {
+    /// 堆槽位索引
+    pub index: u32,
+    /// 槽位代数
+    pub generation: u32,
+}
+
+impl fmt::Display for ObjRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#x}#{}", self.index, self.generation)
+    }
+}
+
+/// 十六进制只输出槽位索引，相当于对象「地址」
+impl fmt::LowerHex for ObjRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::LowerHex::fmt(&self.index, f)
+    }
+}
 
 /// 对象实例
 #[derive(Debug, Clone)]
 pub struct Object {
-    /// 类名
-    pub class_name: String,
-    /// 字段值
-    pub fields: HashMap<String, crate::runtime::frame::JvmValue>,
+    /// 类名（数组为数组描述符，如 "[I"）
+    pub class_name: Symbol,
+    /// 对象内容：普通实例或数组
+    pub kind: ObjectKind,
+    /// 对象的监视器（synchronized 的锁）；None 表示没有线程持有
+    pub monitor: Option<Monitor>,
+    /// identity hash（第一次调用 `Object.hashCode()` 时由引用算出并保存，对象移动后不变）
+    pub identity_hash: Option<i32>,
+}
+
+/// 被线程持有的监视器
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Monitor {
+    /// 持有者的线程 id；None 是入口线程
+    pub owner: Option<i64>,
+    /// 重入次数（至少为 1）
+    pub count: u32,
+}
+
+impl Object {
+    /// 对象直接引用的其他对象（实例字段和数组元素中的非 null 引用）
+    pub fn references(&self) -> impl Iterator<Item = ObjRef> + '_ {
+        let values: Box<dyn Iterator<Item = &JvmValue>> = match &self.kind {
+            ObjectKind::Instance { fields, .. } => Box::new(fields.iter()),
+            ObjectKind::Array { elements, .. } => Box::new(elements.iter()),
+            ObjectKind::String(_) | ObjectKind::StringBuilder(_) => Box::new(std::iter::empty()),
+        };
+        values.filter_map(|value| match value {
+            JvmValue::Reference(Some(object_ref)) => Some(*object_ref),
+            _ => None,
+        })
+    }
+
+    /// 对象占用的槽位数：实例字段数或数组长度，字符串算一个
+    pub fn slot_count(&self) -> usize {
+        match &self.kind {
+            ObjectKind::Instance { fields, .. } => fields.len(),
+            ObjectKind::Array { elements, .. } => elements.len(),
+            ObjectKind::String(_) | ObjectKind::StringBuilder(_) => 1,
+        }
+    }
+}
+
+/// 实例字段的键：(声明字段的类, 字段名)
+///
+/// 子类可以声明与父类同名的字段（字段遮蔽），两者是对象里的两个不同槽位，
+/// 所以只用字段名做键是不够的
+pub type FieldKey = (Symbol, Symbol);
+
+/// 堆对象的种类
+#[derive(Debug, Clone)]
+pub enum ObjectKind {
+    /// 普通类实例
+    Instance {
+        /// 字段布局（同一个类的对象共享）
+        layout: Arc<FieldLayout>,
+        /// 字段值，按布局的槽位排列
+        fields: Vec<JvmValue>,
+    },
+    /// 数组
+    Array {
+        /// 元素类型
+        element_type: ArrayType,
+        /// 元素值（byte/char/short/boolean 也以 Int 存放）
+        elements: Vec<JvmValue>,
+    },
+    /// java/lang/String 实例，直接保存 Rust 字符串
+    String(String),
+    /// java/lang/StringBuilder 实例，内部缓冲区是 Rust 字符串
+    StringBuilder(String),
+}
+
+/// 数组的元素类型
+///
+/// 基本类型的数值即 newarray 指令的 atype 操作数
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ArrayType {
+    Boolean = 4,
+    Char = 5,
+    Float = 6,
+    Double = 7,
+    Byte = 8,
+    Short = 9,
+    Int = 10,
+    Long = 11,
+    /// 引用类型（对象数组、多维数组的外层），组件类型见 `Object::class_name`
+    Reference = 0,
+}
+
+impl ArrayType {
+    /// 从 newarray 的 atype 操作数解析
+    pub fn from_atype(atype: u8) -> Result<Self> {
+        match atype {
+            4 => Ok(ArrayType::Boolean),
+            5 => Ok(ArrayType::Char),
+            6 => Ok(ArrayType::Float),
+            7 => Ok(ArrayType::Double),
+            8 => Ok(ArrayType::Byte),
+            9 => Ok(ArrayType::Short),
+            10 => Ok(ArrayType::Int),
+            11 => Ok(ArrayType::Long),
+            _ => Err(anyhow!("Invalid newarray atype: {}", atype)),
+        }
+    }
+
+    /// 从组件类型描述符解析，如 "I" → Int，"Ljava/lang/String;" / "[I" → Reference
+    pub fn from_descriptor(descriptor: &str) -> Result<Self> {
+        match descriptor {
+            "Z" => Ok(ArrayType::Boolean),
+            "C" => Ok(ArrayType::Char),
+            "F" => Ok(ArrayType::Float),
+            "D" => Ok(ArrayType::Double),
+            "B" => Ok(ArrayType::Byte),
+            "S" => Ok(ArrayType::Short),
+            "I" => Ok(ArrayType::Int),
+            "J" => Ok(ArrayType::Long),
+            d if d.starts_with('L') || d.starts_with('[') => Ok(ArrayType::Reference),
+            _ => Err(anyhow!("Invalid array component descriptor: {}", descriptor)),
+        }
+    }
+
+    /// 元素的字段描述符，如 Int → "I"
+    pub fn descriptor(&self) -> &'static str {
+        match self {
+            ArrayType::Boolean => "Z",
+            ArrayType::Char => "C",
+            ArrayType::Float => "F",
+            ArrayType::Double => "D",
+            ArrayType::Byte => "B",
+            ArrayType::Short => "S",
+            ArrayType::Int => "I",
+            ArrayType::Long => "J",
+            ArrayType::Reference => "L",
+        }
+    }
+
+    /// 元素的字段类型；引用数组的元素按 Object 处理（组件类型见 `Object::class_name`）
+    pub fn field_type(&self) -> FieldType {
+        match self {
+            ArrayType::Boolean => FieldType::Boolean,
+            ArrayType::Char => FieldType::Char,
+            ArrayType::Float => FieldType::Float,
+            ArrayType::Double => FieldType::Double,
+            ArrayType::Byte => FieldType::Byte,
+            ArrayType::Short => FieldType::Short,
+            ArrayType::Int => FieldType::Int,
+            ArrayType::Long => FieldType::Long,
+            ArrayType::Reference => FieldType::Object("java/lang/Object".to_string()),
+        }
+    }
+}
+
+/// 堆的碎片情况
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Fragmentation {
+    /// 槽位总数
+    pub slots: usize,
+    /// 存活对象数
+    pub live: usize,
+    /// 最后一个存活对象之前的空槽位数（新对象会先填进这些空洞）
+    pub holes: usize,
+}
+
+impl Fragmentation {
+    /// 空洞占「最后一个存活对象之前所有槽位」的比例，0 表示完全紧凑
+    pub fn ratio(&self) -> f64 {
+        let span = self.live + self.holes;
+        if span == 0 {
+            0.0
+        } else {
+            self.holes as f64 / span as f64
+        }
+    }
+}
+
+/// 如果值引用了被移动的对象，改写为新引用
+pub fn relocate_value(value: &mut JvmValue, moved: &HashMap<ObjRef, ObjRef>) {
+    if let JvmValue::Reference(Some(object_ref)) = value {
+        if let Some(&new) = moved.get(object_ref) {
+            *object_ref = new;
+        }
+    }
+}
+
+/// 默认的 GC 阈值：两次回收之间最多分配这么多对象
+pub const DEFAULT_GC_THRESHOLD: usize = 4096;
+
+/// 堆槽位
+#[derive(Debug)]
+struct Slot {
+    /// 槽位代数，每回收一次加一
+    generation: u32,
+    /// 槽位中的对象，已回收时为 None
+    object: Option<Object>,
+    /// 新生代对象熬过的 minor GC 次数；老年代对象（以及不分代时的所有对象）为 None
+    age: Option<u8>,
 }
 
 /// 堆
 #[derive(Debug)]
 pub struct Heap {
     /// 对象存储（使用索引作为对象引用）
-    objects: Vec<Option<Object>>,
-    /// 空闲列表（已回收的对象索引）
-    free_list: Vec<usize>,
+    objects: Vec<Slot>,
+    /// 空闲列表（已回收的槽位索引）
+    free_list: Vec<u32>,
+    /// 上次回收以来分配的对象数（分代时是晋升到老年代的对象数）
+    allocated_since_gc: usize,
+    /// 分配数达到这个值时需要回收，None 表示不自动回收
+    gc_threshold: Option<usize>,
+    /// 存活对象数
+    live: usize,
+    /// 存活对象数上限，None 表示不限制
+    max_objects: Option<usize>,
+    /// 被持有的监视器个数
+    locked: usize,
+    /// 没有字段的布局，`allocate` 分配的对象共享
+    empty_layout: Arc<FieldLayout>,
+    /// 新生代，None 表示不分代
+    nursery: Option<Nursery>,
 }
 
 impl Heap {
@@ -39,70 +280,781 @@ impl Heap {
         Heap {
             objects: Vec::new(),
             free_list: Vec::new(),
+            allocated_since_gc: 0,
+            gc_threshold: Some(DEFAULT_GC_THRESHOLD),
+            live: 0,
+            max_objects: None,
+            locked: 0,
+            empty_layout: Arc::new(FieldLayout::new()),
+            nursery: None,
+        }
+    }
+
+    /// 创建最多容纳 `max_objects` 个存活对象的堆
+    pub fn with_capacity_limit(max_objects: usize) -> Self {
+        let mut heap = Self::new();
+        heap.set_max_objects(Some(max_objects));
+        heap
+    }
+
+    /// 设置存活对象数上限，None 表示不限制
+    pub fn set_max_objects(&mut self, max_objects: Option<usize>) {
+        self.max_objects = max_objects;
+    }
+
+    /// 存活对象数上限
+    pub fn max_objects(&self) -> Option<usize> {
+        self.max_objects
+    }
+
+    /// 是否已经达到存活对象数上限（再分配就会失败）
+    pub fn is_full(&self) -> bool {
+        self.max_objects.is_some_and(|max| self.live >= max)
+    }
+
+    /// 暂时取消上限执行 `f`
+    ///
+    /// 用于分配异常对象：OutOfMemoryError 本身也要放在堆上，
+    /// 真实的 JVM 会预先分配好它
+    pub fn without_limit<R>(&mut self, f: impl FnOnce(&mut Heap) -> R) -> R {
+        let max_objects = self.max_objects.take();
+        let result = f(self);
+        self.max_objects = max_objects;
+        result
+    }
+
+    /// 设置自动回收的阈值，None 表示不自动回收
+    pub fn set_gc_threshold(&mut self, threshold: Option<usize>) {
+        self.gc_threshold = threshold;
+    }
+
+    /// 自动回收的阈值
+    pub fn gc_threshold(&self) -> Option<usize> {
+        self.gc_threshold
+    }
+
+    /// 上次回收以来分配的对象数；分代时新对象先进入新生代，这里只计晋升到老年代的对象
+    pub fn allocated_since_gc(&self) -> usize {
+        self.allocated_since_gc
+    }
+
+    /// 分配压力是否已经达到阈值
+    pub fn needs_gc(&self) -> bool {
+        self.gc_threshold
+            .is_some_and(|threshold| self.allocated_since_gc >= threshold)
+    }
+
+    /// 回收完成后清零分配计数
+    pub fn reset_allocation_count(&mut self) {
+        self.allocated_since_gc = 0;
+    }
+
+    /// 打开分代：之后分配的对象进入新生代（见 `nursery`）
+    pub fn enable_nursery(&mut self, capacity: usize, promotion_age: u8) {
+        self.nursery = Some(Nursery::new(capacity, promotion_age));
+    }
+
+    /// 新生代，不分代时为 None
+    pub fn nursery(&self) -> Option<&Nursery> {
+        self.nursery.as_ref()
+    }
+
+    /// 新生代是否已满，需要 minor GC
+    pub fn needs_minor_gc(&self) -> bool {
+        self.nursery.as_ref().is_some_and(Nursery::is_full)
+    }
+
+    /// 引用是否指向新生代中的存活对象
+    pub fn is_young(&self, index: ObjRef) -> bool {
+        self.slot(index)
+            .is_ok_and(|slot| slot.object.is_some() && slot.age.is_some())
+    }
+
+    /// 写屏障：老年代对象 `holder` 中写入了新生代对象的引用时，把 `holder` 加入记忆集
+    fn write_barrier(&mut self, holder: ObjRef, target: Option<ObjRef>) {
+        let Some(target) = target else {
+            return;
+        };
+        if self.nursery.is_some() && !self.is_young(holder) && self.is_young(target) {
+            if let Some(nursery) = &mut self.nursery {
+                nursery.remembered.insert(holder);
+            }
+        }
+    }
+
+    /// minor GC 的清除阶段：回收 `reachable` 之外的新生代对象，存活的年龄加一，
+    /// 达到晋升年龄的晋升到老年代；最后重建记忆集。返回（回收数, 晋升数）
+    pub(crate) fn sweep_nursery(&mut self, reachable: &HashSet<ObjRef>) -> (usize, usize) {
+        let Some(nursery) = &mut self.nursery else {
+            return (0, 0);
+        };
+        let objects = std::mem::take(&mut nursery.objects);
+        let promotion_age = nursery.promotion_age;
+        let mut candidates: Vec<ObjRef> = nursery.remembered.drain().collect();
+        let mut survivors = Vec::new();
+        let mut collected = 0;
+        let mut promoted = 0;
+        for object_ref in objects {
+            if !self.is_young(object_ref) {
+                continue; // 已经被回收
+            }
+            if !reachable.contains(&object_ref) {
+                if self.free(object_ref).is_ok() {
+                    collected += 1;
+                }
+                continue;
+            }
+            let slot = &mut self.objects[object_ref.index as usize];
+            let age = slot.age.map_or(1, |age| age.saturating_add(1));
+            if age >= promotion_age {
+                slot.age = None;
+                promoted += 1;
+                // 刚晋升的对象可能引用还留在新生代的对象
+                candidates.push(object_ref);
+            } else {
+                slot.age = Some(age);
+                survivors.push(object_ref);
+            }
+        }
+
+        let remembered: HashSet<ObjRef> = candidates
+            .into_iter()
+            .filter(|&holder| {
+                !self.is_young(holder)
+                    && self
+                        .get(holder)
+                        .is_ok_and(|object| object.references().any(|target| self.is_young(target)))
+            })
+            .collect();
+        if let Some(nursery) = &mut self.nursery {
+            nursery.objects = survivors;
+            nursery.remembered = remembered;
+        }
+        self.allocated_since_gc += promoted;
+        (collected, promoted)
+    }
+
+    /// 把新生代的对象全部晋升（完整回收之后调用，老年代从此没有指向新生代的引用），返回晋升数
+    pub(crate) fn promote_nursery(&mut self) -> usize {
+        let Some(nursery) = &mut self.nursery else {
+            return 0;
+        };
+        nursery.remembered.clear();
+        let mut promoted = 0;
+        for object_ref in std::mem::take(&mut nursery.objects) {
+            if let Some(slot) = self.objects.get_mut(object_ref.index as usize) {
+                if slot.generation == object_ref.generation && slot.age.take().is_some() {
+                    promoted += 1;
+                }
+            }
+        }
+        promoted
+    }
+
+    /// 分配对象（不带任何字段，`set_field` 写入时再追加）
+    ///
+    /// 达到存活对象数上限时返回 OutOfMemoryError 错误（本节其他分配方法相同）
+    pub fn allocate(&mut self, class_name: impl Into<Symbol>) -> Result<ObjRef> {
+        let layout = self.empty_layout.clone();
+        self.allocate_instance(class_name, layout)
+    }
+
+    /// 按字段布局分配对象，字段取布局中的默认值
+    pub fn allocate_instance(
+        &mut self,
+        class_name: impl Into<Symbol>,
+        layout: Arc<FieldLayout>,
+    ) -> Result<ObjRef> {
+        let fields = layout.defaults().to_vec();
+        let obj = Object {
+            class_name: class_name.into(),
+            kind: ObjectKind::Instance { layout, fields },
+            monitor: None,
+            identity_hash: None,
+        };
+        self.store(obj)
+    }
+
+    /// 分配基本类型数组，元素初始化为默认值
+    ///
+    /// 长度为负时返回 NegativeArraySizeException 错误
+    pub fn allocate_array(&mut self, element_type: ArrayType, length: i32) -> Result<ObjRef> {
+        if element_type == ArrayType::Reference {
+            return Err(anyhow!("Use allocate_reference_array for reference arrays"));
+        }
+        let class_name = format!("[{}", element_type.descriptor());
+        self.allocate_array_object(class_name.into(), element_type, length)
+    }
+
+    /// 分配引用数组（元素初始化为 null）
+    ///
+    /// `component` 是组件类的内部名（如 "java/lang/String"）或数组描述符（如 "[I"）
+    pub fn allocate_reference_array(&mut self, component: &str, length: i32) -> Result<ObjRef> {
+        let class_name = if component.starts_with('[') {
+            format!("[{}", component)
+        } else {
+            format!("[L{};", component)
+        };
+        self.allocate_array_object(class_name.into(), ArrayType::Reference, length)
+    }
+
+    /// 分配多维数组（multianewarray）
+    ///
+    /// `descriptor` 是数组类型描述符（如 "[[I"），`counts` 依次是各维的长度，
+    /// 可以少于描述符的维数，剩余的维保持为 null
+    pub fn allocate_multi_array(&mut self, descriptor: &str, counts: &[i32]) -> Result<ObjRef> {
+        // 先检查所有维度，避免分配到一半才发现负数
+        if let Some(&negative) = counts.iter().find(|&&c| c < 0) {
+            return Err(JavaException::negative_array_size(negative).into());
         }
+
+        let component = descriptor
+            .strip_prefix('[')
+            .ok_or_else(|| anyhow!("Not an array descriptor: {}", descriptor))?;
+        let (&length, rest) = counts
+            .split_first()
+            .ok_or_else(|| anyhow!("multianewarray needs at least one dimension"))?;
+
+        let element_type = ArrayType::from_descriptor(component)?;
+        if element_type != ArrayType::Reference {
+            return self.allocate_array(element_type, length);
+        }
+
+        let array = self.allocate_array_object(descriptor.into(), element_type, length)?;
+        if !rest.is_empty() {
+            for i in 0..length {
+                let sub = self.allocate_multi_array(component, rest)?;
+                self.array_set(array, i, JvmValue::Reference(Some(sub)))?;
+            }
+        }
+        Ok(array)
     }
 
-    /// 分配对象
-    pub fn allocate(&mut self, class_name: String) -> usize {
+    fn allocate_array_object(
+        &mut self,
+        class_name: Symbol,
+        element_type: ArrayType,
+        length: i32,
+    ) -> Result<ObjRef> {
+        if length < 0 {
+            return Err(JavaException::negative_array_size(length).into());
+        }
+        let default = jvm_default_value(&element_type.field_type());
         let obj = Object {
             class_name,
-            fields: HashMap::new(),
+            kind: ObjectKind::Array {
+                element_type,
+                elements: vec![default; length as usize],
+            },
+            monitor: None,
+            identity_hash: None,
+        };
+        self.store(obj)
+    }
+
+    /// 分配 java/lang/String 对象
+    pub fn allocate_string(&mut self, value: &str) -> Result<ObjRef> {
+        let obj = Object {
+            class_name: "java/lang/String".into(),
+            kind: ObjectKind::String(value.to_string()),
+            monitor: None,
+            identity_hash: None,
         };
+        self.store(obj)
+    }
+
+    /// 读取 String 对象的内容
+    pub fn get_string(&self, index: ObjRef) -> Result<&str> {
+        match &self.get(index)?.kind {
+            ObjectKind::String(s) => Ok(s),
+            _ => Err(anyhow!("Object {} is not a java/lang/String", index)),
+        }
+    }
+
+    /// 把已分配的对象初始化为 StringBuilder（`StringBuilder.<init>`），初始内容为 `initial`
+    pub fn init_string_builder(&mut self, index: ObjRef, initial: &str) -> Result<()> {
+        self.get_mut(index)?.kind = ObjectKind::StringBuilder(initial.to_string());
+        Ok(())
+    }
+
+    /// StringBuilder 对象的缓冲区
+    pub fn string_builder_mut(&mut self, index: ObjRef) -> Result<&mut String> {
+        match &mut self.get_mut(index)?.kind {
+            ObjectKind::StringBuilder(buffer) => Ok(buffer),
+            _ => Err(anyhow!("Object {} is not an initialized java/lang/StringBuilder", index)),
+        }
+    }
 
-        // 尝试从空闲列表中获取索引
-        if let Some(index) = self.free_list.pop() {
-            self.objects[index] = Some(obj);
-            index
+    /// 对象最多的 `top` 个类，如 "Node: 8, [I: 2"（OutOfMemoryError 的信息）
+    fn usage_summary(&self, top: usize) -> String {
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for (_, obj) in self.iter() {
+            *counts.entry(&obj.class_name).or_default() += 1;
+        }
+        let mut counts: Vec<_> = counts.into_iter().collect();
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        counts
+            .iter()
+            .take(top)
+            .map(|(class, count)| format!("{}: {}", class, count))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// 把对象放入堆，返回引用
+    fn store(&mut self, obj: Object) -> Result<ObjRef> {
+        if let Some(max) = self.max_objects.filter(|&max| self.live >= max) {
+            return Err(JavaException::new(
+                "java/lang/OutOfMemoryError",
+                format!(
+                    "Java heap space: {}/{} objects live ({})",
+                    self.live,
+                    max,
+                    self.usage_summary(3)
+                ),
+            )
+            .into());
+        }
+        self.live += 1;
+        let age = match &self.nursery {
+            Some(_) => Some(0),
+            None => {
+                self.allocated_since_gc += 1;
+                None
+            }
+        };
+        // 尝试从空闲列表中获取索引，沿用槽位当前的代数
+        let object_ref = if let Some(index) = self.free_list.pop() {
+            let slot = &mut self.objects[index as usize];
+            slot.object = Some(obj);
+            slot.age = age;
+            ObjRef {
+                index,
+                generation: slot.generation,
+            }
         } else {
             // 否则添加到末尾
-            let index = self.objects.len();
-            self.objects.push(Some(obj));
-            index
+            let index = self.objects.len() as u32;
+            self.objects.push(Slot {
+                generation: 0,
+                object: Some(obj),
+                age,
+            });
+            ObjRef {
+                index,
+                generation: 0,
+            }
+        };
+        if let Some(nursery) = &mut self.nursery {
+            nursery.objects.push(object_ref);
+        }
+        Ok(object_ref)
+    }
+
+    /// 写入实例字段，`class_name` 是声明字段的类
+    ///
+    /// 传入 `&Symbol` 时只复制引用，传入 `&str` 时会创建新的符号
+    pub fn set_field(
+        &mut self,
+        index: ObjRef,
+        class_name: impl Into<Symbol>,
+        name: impl Into<Symbol>,
+        value: JvmValue,
+    ) -> Result<()> {
+        let key = (class_name.into(), name.into());
+        let target = value.as_object_ref();
+        match &mut self.get_mut(index)?.kind {
+            ObjectKind::Instance { layout, fields } => {
+                match layout.slot(&key) {
+                    Some(slot) => fields[slot] = value,
+                    // 布局中没有的字段（内置 JDK 对象）：这个对象换成自己的布局，追加一个槽位
+                    None => {
+                        Arc::make_mut(layout).push(key, value.clone());
+                        fields.push(value);
+                    }
+                }
+            }
+            _ => return Err(anyhow!("Cannot set field {} on a non-instance object", key.1)),
+        }
+        self.write_barrier(index, target);
+        Ok(())
+    }
+
+    /// 按槽位写入实例字段（putfield 的快速路径）
+    ///
+    /// 槽位上不是这个字段时（对象的布局与解析时不同）退回按名字写入
+    pub fn set_field_at(
+        &mut self,
+        index: ObjRef,
+        slot: usize,
+        class_name: &Symbol,
+        name: &Symbol,
+        value: JvmValue,
+    ) -> Result<()> {
+        let target = value.as_object_ref();
+        if let ObjectKind::Instance { layout, fields } = &mut self.get_mut(index)?.kind {
+            if layout.key(slot).is_some_and(|(owner, field)| owner == class_name && field == name) {
+                fields[slot] = value;
+                self.write_barrier(index, target);
+                return Ok(());
+            }
         }
+        self.set_field(index, class_name, name, value)
     }
 
-    pub fn set_field(&mut self, index: usize, name: String, value: JvmValue) -> Result<()> {
-        self.get_mut(index)?.fields.insert(name, value);
+    /// 读取实例字段，`class_name` 是声明字段的类
+    pub fn get_field(
+        &self,
+        index: ObjRef,
+        class_name: impl Into<Symbol>,
+        name: impl Into<Symbol>,
+    ) -> Result<JvmValue> {
+        let key = (class_name.into(), name.into());
+        match &self.get(index)?.kind {
+            ObjectKind::Instance { layout, fields } => layout
+                .slot(&key)
+                .map(|slot| fields[slot].clone())
+                .ok_or_else(|| anyhow!("Field not found: {}.{}", key.0, key.1)),
+            _ => Err(anyhow!("Cannot get field {} on a non-instance object", key.1)),
+        }
+    }
+
+    /// 按槽位读取实例字段（getfield 的快速路径），槽位不匹配时退回按名字读取
+    pub fn get_field_at(
+        &self,
+        index: ObjRef,
+        slot: usize,
+        class_name: &Symbol,
+        name: &Symbol,
+    ) -> Result<JvmValue> {
+        if let ObjectKind::Instance { layout, fields } = &self.get(index)?.kind {
+            if layout.key(slot).is_some_and(|(owner, field)| owner == class_name && field == name) {
+                return Ok(fields[slot].clone());
+            }
+        }
+        self.get_field(index, class_name, name)
+    }
+
+    /// 获取数组长度
+    pub fn array_length(&self, index: ObjRef) -> Result<usize> {
+        Ok(self.array_elements(index)?.len())
+    }
+
+    /// 读取数组元素，越界时返回 ArrayIndexOutOfBoundsException 错误
+    pub fn array_get(&self, index: ObjRef, element: i32) -> Result<JvmValue> {
+        let elements = self.array_elements(index)?;
+        let slot = Self::check_bounds(element, elements.len())?;
+        Ok(elements[slot].clone())
+    }
+
+    /// 写入数组元素（按元素类型检查并截断，见 [`JvmValue::coerce_to_descriptor`]），
+    /// 越界时返回 ArrayIndexOutOfBoundsException 错误
+    pub fn array_set(&mut self, index: ObjRef, element: i32, value: JvmValue) -> Result<()> {
+        let target = value.as_object_ref();
+        match &mut self.get_mut(index)?.kind {
+            ObjectKind::Array {
+                element_type,
+                elements,
+            } => {
+                let slot = Self::check_bounds(element, elements.len())?;
+                elements[slot] = value
+                    .coerce_to_descriptor(element_type.descriptor())
+                    .map_err(|e| anyhow!("Cannot store into array element: {}", e))?;
+            }
+            _ => return Err(anyhow!("Object {} is not an array", index)),
+        }
+        self.write_barrier(index, target);
         Ok(())
     }
 
-    pub fn get_field(&self, index: usize, name: &String) -> Result<JvmValue> {
-        self.get(index)?
-            .fields
-            .get(name)
-            .ok_or(anyhow!("Field not found"))
-            .map(|v| v.clone())
+    /// 获取数组的全部元素
+    pub fn array_elements(&self, index: ObjRef) -> Result<&[JvmValue]> {
+        match &self.get(index)?.kind {
+            ObjectKind::Array { elements, .. } => Ok(elements),
+            _ => Err(anyhow!("Object {} is not an array", index)),
+        }
+    }
+
+    fn check_bounds(element: i32, length: usize) -> Result<usize> {
+        if element < 0 || element as usize >= length {
+            return Err(JavaException::array_index_out_of_bounds(element, length).into());
+        }
+        Ok(element as usize)
+    }
+
+    /// 查找引用对应的槽位，校验代数
+    fn slot(&self, index: ObjRef) -> Result<&Slot> {
+        let slot = self
+            .objects
+            .get(index.index as usize)
+            .ok_or_else(|| anyhow!("Invalid object reference: {}", index))?;
+        if slot.generation != index.generation {
+            return Err(anyhow!(
+                "stale object reference {}: slot is at generation {}",
+                index,
+                slot.generation
+            ));
+        }
+        Ok(slot)
     }
 
     /// 获取对象
-    pub fn get(&self, index: usize) -> Result<&Object> {
-        self.objects
-            .get(index)
-            .and_then(|opt| opt.as_ref())
+    pub fn get(&self, index: ObjRef) -> Result<&Object> {
+        self.slot(index)?
+            .object
+            .as_ref()
             .ok_or_else(|| anyhow!("Invalid object reference: {}", index))
     }
 
     /// 获取可变对象
-    pub fn get_mut(&mut self, index: usize) -> Result<&mut Object> {
-        self.objects
-            .get_mut(index)
-            .and_then(|opt| opt.as_mut())
+    pub fn get_mut(&mut self, index: ObjRef) -> Result<&mut Object> {
+        self.slot(index)?;
+        self.objects[index.index as usize]
+            .object
+            .as_mut()
             .ok_or_else(|| anyhow!("Invalid object reference: {}", index))
     }
 
-    /// 释放对象（GC使用）
-    pub fn free(&mut self, index: usize) -> Result<()> {
-        if index >= self.objects.len() {
-            return Err(anyhow!("Invalid object reference: {}", index));
+    /// 对象的 identity hash：第一次使用时由对象引用算出，保存在对象里
+    ///
+    /// 整理会移动对象、改变引用，保存下来才能保证同一个对象的 hashCode 始终不变
+    pub fn identity_hash(&mut self, index: ObjRef) -> Result<i32> {
+        let object = self.get_mut(index)?;
+        let hash = *object.identity_hash.get_or_insert_with(|| {
+            // 槽位乘奇数常数再混入代数：相邻槽位的对象也得到差别很大的值
+            let mixed = index.index.wrapping_mul(0x9e37_79b1) ^ index.generation.rotate_left(16);
+            (mixed & 0x7fff_ffff) as i32
+        });
+        Ok(hash)
+    }
+
+    /// 线程 `owner` 获取对象的监视器，返回 false 表示监视器被其他线程持有
+    ///
+    /// 同一线程可以重复获取（重入），每次获取都要对应一次 `monitor_exit`
+    pub fn monitor_enter(&mut self, index: ObjRef, owner: Option<i64>) -> Result<bool> {
+        let object = self.get_mut(index)?;
+        match &mut object.monitor {
+            Some(monitor) if monitor.owner == owner => monitor.count += 1,
+            Some(_) => return Ok(false),
+            None => {
+                object.monitor = Some(Monitor { owner, count: 1 });
+                self.locked += 1;
+            }
+        }
+        Ok(true)
+    }
+
+    /// 线程 `owner` 释放一次对象的监视器；不是持有者时抛出 IllegalMonitorStateException
+    pub fn monitor_exit(&mut self, index: ObjRef, owner: Option<i64>) -> Result<()> {
+        let object = self.get_mut(index)?;
+        match &mut object.monitor {
+            Some(monitor) if monitor.owner == owner => {
+                monitor.count -= 1;
+                if monitor.count == 0 {
+                    object.monitor = None;
+                    self.locked -= 1;
+                }
+                Ok(())
+            }
+            _ => Err(JavaException::new(
+                "java/lang/IllegalMonitorStateException",
+                format!("current thread does not own the monitor of {}", object.class_name),
+            )
+            .into()),
+        }
+    }
+
+    /// 线程 `owner` 现在能否获取对象的监视器
+    pub fn monitor_available(&self, index: ObjRef, owner: Option<i64>) -> bool {
+        self.get(index)
+            .is_ok_and(|object| object.monitor.is_none_or(|monitor| monitor.owner == owner))
+    }
+
+    /// 释放所有监视器（上一次运行出错时可能留下没有释放的锁）
+    pub fn release_monitors(&mut self) {
+        if self.locked == 0 {
+            return;
         }
-        self.objects[index] = None;
-        self.free_list.push(index);
+        for object in self.objects.iter_mut().filter_map(|slot| slot.object.as_mut()) {
+            object.monitor = None;
+        }
+        self.locked = 0;
+    }
+
+    /// 释放对象（GC使用），槽位代数加一，旧引用从此失效
+    pub fn free(&mut self, index: ObjRef) -> Result<()> {
+        self.get(index)?;
+        let slot = &mut self.objects[index.index as usize];
+        if slot.object.take().is_some_and(|object| object.monitor.is_some()) {
+            self.locked -= 1;
+        }
+        slot.generation = slot.generation.wrapping_add(1);
+        self.live -= 1;
+        self.free_list.push(index.index);
         Ok(())
     }
 
     /// 获取堆中的对象数量
     pub fn object_count(&self) -> usize {
-        self.objects.iter().filter(|o| o.is_some()).count()
+        self.live
+    }
+
+    /// 当前的碎片情况
+    pub fn fragmentation(&self) -> Fragmentation {
+        let end = self
+            .objects
+            .iter()
+            .rposition(|slot| slot.object.is_some())
+            .map_or(0, |last| last + 1);
+        let live = self.objects[..end]
+            .iter()
+            .filter(|slot| slot.object.is_some())
+            .count();
+        Fragmentation {
+            slots: self.objects.len(),
+            live,
+            holes: end - live,
+        }
+    }
+
+    /// 整理：把存活对象按原顺序滑动到槽位的最前面，返回被移动对象的旧引用 → 新引用
+    ///
+    /// 移出的槽位代数加一，所以没有被改写的旧引用会报 stale object reference。
+    /// 调用者负责用返回的映射改写堆外的引用（根），堆内的引用由 `relocate_references` 改写
+    pub fn compact(&mut self) -> HashMap<ObjRef, ObjRef> {
+        // 新生代记录的是旧引用，整理前先全部晋升
+        self.promote_nursery();
+        let mut moved = HashMap::new();
+        let mut to = 0;
+        for from in 0..self.objects.len() {
+            if self.objects[from].object.is_none() {
+                continue;
+            }
+            if from != to {
+                let slot = &mut self.objects[from];
+                let object = slot.object.take();
+                let old = ObjRef {
+                    index: from as u32,
+                    generation: slot.generation,
+                };
+                slot.generation = slot.generation.wrapping_add(1);
+
+                // 目标槽位是空的，它的代数在回收时已经加过一，不会与旧引用冲突
+                let target = &mut self.objects[to];
+                target.object = object;
+                let new = ObjRef {
+                    index: to as u32,
+                    generation: target.generation,
+                };
+                moved.insert(old, new);
+            }
+            to += 1;
+        }
+
+        // 空闲列表从小到大弹出，新对象紧接在存活对象之后分配
+        self.free_list = (to as u32..self.objects.len() as u32).rev().collect();
+        moved
+    }
+
+    /// 按 `moved`（旧引用 → 新引用）改写所有对象字段和数组元素中的引用
+    pub fn relocate_references(&mut self, moved: &HashMap<ObjRef, ObjRef>) {
+        if moved.is_empty() {
+            return;
+        }
+        for object in self.objects.iter_mut().filter_map(|slot| slot.object.as_mut()) {
+            let values: Box<dyn Iterator<Item = &mut JvmValue>> = match &mut object.kind {
+                ObjectKind::Instance { fields, .. } => Box::new(fields.iter_mut()),
+                ObjectKind::Array { elements, .. } => Box::new(elements.iter_mut()),
+                ObjectKind::String(_) | ObjectKind::StringBuilder(_) => continue,
+            };
+            for value in values {
+                relocate_value(value, moved);
+            }
+        }
+    }
+
+    /// 堆槽位数量（包括已回收的空槽位），对象引用的索引都小于这个值
+    pub fn capacity(&self) -> usize {
+        self.objects.len()
+    }
+
+    /// 按引用从小到大遍历存活对象
+    pub fn iter(&self) -> impl Iterator<Item = (ObjRef, &Object)> {
+        self.objects.iter().enumerate().filter_map(|(index, slot)| {
+            let obj = slot.object.as_ref()?;
+            let index = ObjRef {
+                index: index as u32,
+                generation: slot.generation,
+            };
+            Some((index, obj))
+        })
+    }
+
+    /// 遍历所有槽位（包括已回收的），引用带着槽位当前的代数
+    pub(crate) fn slots(&self) -> impl Iterator<Item = (ObjRef, Option<&Object>)> {
+        self.objects.iter().enumerate().map(|(index, slot)| {
+            let index = ObjRef {
+                index: index as u32,
+                generation: slot.generation,
+            };
+            (index, slot.object.as_ref())
+        })
+    }
+
+    /// 每个槽位的代数、对象和新生代年龄（执行快照使用）
+    pub(crate) fn slot_states(&self) -> impl Iterator<Item = (u32, Option<&Object>, Option<u8>)> {
+        self.objects
+            .iter()
+            .map(|slot| (slot.generation, slot.object.as_ref(), slot.age))
+    }
+
+    /// 空闲列表，最后一项最先被复用
+    pub(crate) fn free_list(&self) -> &[u32] {
+        &self.free_list
+    }
+
+    /// 用快照中的槽位替换堆的全部内容，存活对象数和被持有的监视器数重新统计
+    ///
+    /// `young` 是新生代对象和记忆集；不分代的堆忽略它，所有对象都算老年代
+    pub(crate) fn restore_slots(
+        &mut self,
+        slots: Vec<(u32, Option<Object>, Option<u8>)>,
+        free_list: Vec<u32>,
+        allocated_since_gc: usize,
+        young: Option<(Vec<ObjRef>, HashSet<ObjRef>)>,
+    ) {
+        let generational = self.nursery.is_some();
+        self.objects = slots
+            .into_iter()
+            .map(|(generation, object, age)| Slot {
+                generation,
+                object,
+                age: age.filter(|_| generational),
+            })
+            .collect();
+        let live = self.objects.iter().filter_map(|slot| slot.object.as_ref());
+        self.live = live.clone().count();
+        self.locked = live.filter(|object| object.monitor.is_some()).count();
+        self.free_list = free_list;
+        self.allocated_since_gc = allocated_since_gc;
+        if let Some(nursery) = &mut self.nursery {
+            let (objects, remembered) = young.unwrap_or_default();
+            nursery.objects = objects;
+            nursery.remembered = remembered;
+        }
+    }
+
+    /// 某个类的所有存活对象（不包括子类的实例）
+    pub fn find_by_class(&self, class_name: &str) -> Vec<ObjRef> {
+        self.iter()
+            .filter(|(_, obj)| obj.class_name == class_name)
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// 引用是否指向存活对象（代数必须一致）
+    pub fn is_live(&self, index: ObjRef) -> bool {
+        self.get(index).is_ok()
     }
 }
 
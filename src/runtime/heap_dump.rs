@@ -0,0 +1,176 @@
+//! # 堆转储
+//!
+//! 把堆中每个槽位的内容整理成 [`HeapDump`]，用于调试对象图：
+//! 字段值被渲染成便于阅读的形式，引用写作 `@索引`，沿着它就能找到被引用的对象。
+//!
+//! ## 学习要点
+//! - 堆转储是对象图的快照：节点是对象，边是字段（或数组元素）中的引用
+//! - 真实 JVM 的 hprof 格式也记录每个对象的类、字段值和引用
+//! - 已回收的槽位同样列出，方便观察碎片和槽位复用
+
+use crate::runtime::frame::JvmValue;
+use crate::runtime::heap::{Heap, ObjectKind};
+use std::fmt;
+
+/// 整个堆的转储，按槽位索引排序
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeapDump {
+    /// 每个槽位一项
+    pub slots: Vec<SlotDump>,
+}
+
+/// 一个槽位
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlotDump {
+    /// 槽位索引
+    pub index: u32,
+    /// 槽位当前的代数
+    pub generation: u32,
+    /// 槽位中的对象，已回收时为 None
+    pub object: Option<ObjectDump>,
+}
+
+/// 一个存活对象
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObjectDump {
+    /// 类名（数组为数组描述符）
+    pub class_name: String,
+    /// 字段名 → 值，按名字排序
+    ///
+    /// 实例字段写作 `name`，继承来的字段写作 `Owner.name`；
+    /// 数组元素写作 `[i]`；String 只有一项 `value`
+    pub fields: Vec<(String, DumpValue)>,
+}
+
+/// 渲染后的值
+#[derive(Debug, Clone, PartialEq)]
+pub enum DumpValue {
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    Null,
+    /// 引用，值是被引用对象的槽位索引
+    Ref(u32),
+    /// String 对象的内容
+    Str(String),
+}
+
+impl From<&JvmValue> for DumpValue {
+    fn from(value: &JvmValue) -> Self {
+        match value {
+            JvmValue::Int(v) => DumpValue::Int(*v),
+            JvmValue::Long(v) => DumpValue::Long(*v),
+            JvmValue::Float(v) => DumpValue::Float(*v),
+            JvmValue::Double(v) => DumpValue::Double(*v),
+            JvmValue::Reference(None) => DumpValue::Null,
+            JvmValue::Reference(Some(object_ref)) => DumpValue::Ref(object_ref.index),
+        }
+    }
+}
+
+impl fmt::Display for DumpValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DumpValue::Int(v) => write!(f, "{}", v),
+            DumpValue::Long(v) => write!(f, "{}L", v),
+            DumpValue::Float(v) => write!(f, "{}f", v),
+            DumpValue::Double(v) => write!(f, "{}", v),
+            DumpValue::Null => write!(f, "null"),
+            DumpValue::Ref(index) => write!(f, "@{}", index),
+            DumpValue::Str(s) => write!(f, "{:?}", s),
+        }
+    }
+}
+
+impl HeapDump {
+    /// 存活对象（引用和对象）
+    pub fn objects(&self) -> impl Iterator<Item = (u32, &ObjectDump)> {
+        self.slots
+            .iter()
+            .filter_map(|slot| slot.object.as_ref().map(|object| (slot.index, object)))
+    }
+
+    /// 对象图的边：(引用方索引, 字段名, 被引用方索引)
+    pub fn edges(&self) -> Vec<(u32, &str, u32)> {
+        self.objects()
+            .flat_map(|(index, object)| {
+                object.fields.iter().filter_map(move |(name, value)| match value {
+                    DumpValue::Ref(target) => Some((index, name.as_str(), *target)),
+                    _ => None,
+                })
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for HeapDump {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "=== 堆转储 ({} 个存活对象, {} 个槽位) ===",
+            self.objects().count(),
+            self.slots.len()
+        )?;
+        for slot in &self.slots {
+            match &slot.object {
+                Some(object) => {
+                    writeln!(f, "@{} {}", slot.index, object.class_name)?;
+                    for (name, value) in &object.fields {
+                        writeln!(f, "    {} = {}", name, value)?;
+                    }
+                }
+                None => writeln!(f, "@{} <空闲> (代数 {})", slot.index, slot.generation)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Heap {
+    /// 转储整个堆
+    pub fn dump(&self) -> HeapDump {
+        let slots = self
+            .slots()
+            .map(|(object_ref, object)| SlotDump {
+                index: object_ref.index,
+                generation: object_ref.generation,
+                object: object.map(|object| ObjectDump {
+                    class_name: object.class_name.to_string(),
+                    fields: dump_fields(&object.class_name, &object.kind),
+                }),
+            })
+            .collect();
+        HeapDump { slots }
+    }
+
+}
+
+fn dump_fields(class_name: &str, kind: &ObjectKind) -> Vec<(String, DumpValue)> {
+    match kind {
+        ObjectKind::Instance { layout, fields } => {
+            let mut fields: Vec<_> = layout
+                .keys()
+                .iter()
+                .zip(fields)
+                .map(|((owner, name), value)| {
+                    let name = if owner == class_name {
+                        name.to_string()
+                    } else {
+                        format!("{}.{}", owner, name)
+                    };
+                    (name, DumpValue::from(value))
+                })
+                .collect();
+            fields.sort_by(|a, b| a.0.cmp(&b.0));
+            fields
+        }
+        ObjectKind::Array { elements, .. } => elements
+            .iter()
+            .enumerate()
+            .map(|(i, value)| (format!("[{}]", i), DumpValue::from(value)))
+            .collect(),
+        ObjectKind::String(s) => vec![("value".to_string(), DumpValue::Str(s.clone()))],
+        ObjectKind::StringBuilder(s) => vec![("buffer".to_string(), DumpValue::Str(s.clone()))],
+    }
+}
@@ -0,0 +1,76 @@
+//! # 对象字段布局
+//!
+//! 类第一次实例化时算出它的字段布局：所有实例字段（包括继承的）各占一个槽位，
+//! 对象的字段存放在按槽位排列的 `Vec<JvmValue>` 中。getfield/putfield 解析时把字段换算成槽位，
+//! 之后直接按下标访问，不再每次对字段名求哈希。
+//!
+//! ## 学习要点
+//! - 父类的字段排在前面，子类的字段接在后面：同一个字段在父类和所有子类的对象中槽位相同，
+//!   所以槽位可以缓存在字段引用上
+//! - 子类可以声明与父类同名的字段（字段遮蔽），两者是不同的槽位，键带上声明字段的类
+//! - 槽位数就是对象的大小（不算对象头），堆统计可以据此估算内存占用
+//! - 内置 JDK 对象（Class、Throwable、Integer 等）没有类元数据，第一次写某个字段时再追加槽位
+
+use super::frame::JvmValue;
+use super::heap::FieldKey;
+use std::collections::HashMap;
+
+/// 实例字段布局，同一个类的对象共享（`Arc<FieldLayout>`）
+#[derive(Debug, Clone, Default)]
+pub struct FieldLayout {
+    /// 每个槽位的字段：(声明字段的类, 字段名)
+    keys: Vec<FieldKey>,
+    /// 每个槽位的默认值，新对象的字段从这里复制
+    defaults: Vec<JvmValue>,
+    /// 字段 → 槽位，按名字访问（调试、转储、内置对象）时使用
+    slots: HashMap<FieldKey, usize>,
+}
+
+impl FieldLayout {
+    /// 空布局
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 追加一个字段，返回它的槽位；字段已经在布局中时返回原来的槽位
+    pub fn push(&mut self, key: FieldKey, default: JvmValue) -> usize {
+        if let Some(&slot) = self.slots.get(&key) {
+            return slot;
+        }
+        let slot = self.keys.len();
+        self.slots.insert(key.clone(), slot);
+        self.keys.push(key);
+        self.defaults.push(default);
+        slot
+    }
+
+    /// 字段的槽位
+    pub fn slot(&self, key: &FieldKey) -> Option<usize> {
+        self.slots.get(key).copied()
+    }
+
+    /// 槽位上的字段
+    pub fn key(&self, slot: usize) -> Option<&FieldKey> {
+        self.keys.get(slot)
+    }
+
+    /// 槽位数
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// 是否没有任何字段
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// 按槽位顺序的字段
+    pub fn keys(&self) -> &[FieldKey] {
+        &self.keys
+    }
+
+    /// 按槽位顺序的默认值
+    pub fn defaults(&self) -> &[JvmValue] {
+        &self.defaults
+    }
+}
@@ -12,12 +12,26 @@
 //! - 方法区是所有线程共享的
 //! - 类的元数据在首次使用时加载
 //! - 常量池解析采用延迟解析策略
-
-use crate::classfile::constant_pool::ConstantPoolEntry;
-use crate::classfile::{access_flags, ClassFile, MethodInfo};
+//! - 类也可以卸载：没有栈帧在执行它、也没有已加载的子类时，元数据可以释放
+
+use crate::classfile::constant_pool::{ConstantPool, ConstantPoolEntry};
+use crate::classfile::descriptor::{self, jvm_default_value, FieldType, MethodDescriptor};
+use crate::classfile::attribute::{find_attribute, BootstrapMethod, CodeAttribute};
+use crate::classfile::version::{check_class_version, DEFAULT_MAX_MAJOR_VERSION};
+use crate::classfile::{access_flags, ClassFile, FieldInfo, MethodInfo};
+use crate::interpreter::decoded::DecodedMethod;
+use crate::interpreter::verifier::verify_method;
+use crate::runtime::frame::JvmValue;
+use crate::runtime::thread::JvmThread;
+use crate::runtime::heap::FieldKey;
+use crate::runtime::layout::FieldLayout;
+use crate::runtime::{JavaException, Symbol};
 use crate::Result;
 use anyhow::anyhow;
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 /// 方法区 - 存储所有已加载类的元数据
 #[derive(Debug)]
@@ -25,13 +39,69 @@ pub struct Metaspace {
     /// 所有已加载的类
     /// Key: 完全限定类名 (如 "java/lang/Object", "com/example/MyClass")
     classes: HashMap<String, ClassMetadata>,
+    /// 下一个加载的类的序号
+    next_load_order: usize,
+    /// 符号引用解析的计数
+    resolution_stats: ResolutionStats,
+    /// 加载类时是否校验方法的字节码
+    verify: bool,
+    /// 允许加载的最高 class 文件主版本号，None 表示不检查
+    max_major_version: Option<u16>,
+    /// 内置 JDK 类（不加载到方法区）的静态字段：类名 → 字段名 → 值，如 `java/lang/System.out`
+    jdk_static_fields: HashMap<String, HashMap<String, JvmValue>>,
+    /// 方法区创建的时刻，类的加载时间相对于它记录
+    created: Instant,
+}
+
+/// 符号引用解析的计数
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ResolutionStats {
+    /// 解析得到否定结果（目标不存在或不支持）的次数，每个调用点记录一次
+    pub misses: usize,
+    /// 命中否定缓存、跳过解析的次数
+    pub negative_hits: usize,
+    /// invokestatic 命中调用点缓存、跳过方法查找的次数
+    pub call_site_hits: usize,
+}
+
+/// 方法区的占用统计
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MetaspaceStats {
+    /// 已加载的类数
+    pub classes: usize,
+    /// 所有类的方法总数
+    pub methods: usize,
+    /// 所有方法的字节码总字节数
+    pub bytecode_bytes: usize,
+    /// 运行时常量池中缓存的解析结果数（方法、字段、类引用和否定结果）
+    pub resolved_entries: usize,
+}
+
+/// 缓存的否定解析结果
+///
+/// 同一个调用点再次执行时直接采用这个结果，不再重复解析
+#[derive(Debug, Clone)]
+pub enum NegativeResolution {
+    /// 解析失败，再次执行时抛出同样的异常
+    Failed(JavaException),
+}
+
+/// 从 Code 属性中提取的方法执行信息
+#[derive(Default)]
+struct CodeInfo {
+    max_stack: usize,
+    max_locals: usize,
+    code: Vec<u8>,
+    local_variables: Vec<LocalVariable>,
+    line_numbers: Vec<(u16, u16)>,
+    exception_table: Vec<ExceptionTableEntry>,
 }
 
 /// 类元数据 - 运行时类的表示
 #[derive(Debug)]
 pub struct ClassMetadata {
     /// 类名
-    pub name: String,
+    pub name: Symbol,
 
     /// 父类名
     pub super_class: Option<String>,
@@ -43,28 +113,49 @@ pub struct ClassMetadata {
     pub access_flags: u16,
 
     /// 原始常量池（来自ClassFile）
-    pub constant_pool: Vec<Option<ConstantPoolEntry>>,
+    pub constant_pool: ConstantPool,
 
     /// 运行时常量池 - 符号引用解析缓存
     pub runtime_pool: RuntimeConstantPool,
 
     /// 方法表 - 快速查找方法
-    /// Key: "方法名:方法描述符" (如 "add:(II)I")
-    pub methods: HashMap<String, MethodMetadata>,
+    /// Key: "方法名:方法描述符" (如 "add:(II)I")，见 [`method_key`]；方法元数据共享，查找时不复制
+    pub methods: HashMap<Symbol, Arc<MethodMetadata>>,
 
     /// 字段表 - 快速查找字段
     /// Key: "字段名:字段描述符" (如 "count:I")
     pub fields: HashMap<String, FieldMetadata>,
 
+    /// 本类声明的实例字段（字段表的键），按声明顺序
+    pub instance_fields: Vec<String>,
+
+    /// 实例字段布局（包括继承的字段），第一次创建对象时计算，见 [`Metaspace::field_layout`]
+    pub field_layout: Option<Arc<FieldLayout>>,
+
     /// 静态字段的值存储
-    pub static_fields: HashMap<String, crate::runtime::frame::JvmValue>,
+    pub static_fields: HashMap<Symbol, JvmValue>,
 
     /// 类初始化状态
     pub state: ClassState,
+
+    /// 加载序号：第几个被加载到方法区的类（从 0 开始，单调递增）
+    pub load_order: usize,
+
+    /// 源文件名（SourceFile 属性），用于错误信息中的 "Foo.java:12"
+    pub source_file: Option<String>,
+
+    /// 引导方法表（BootstrapMethods 属性），invokedynamic 解析调用点时使用
+    pub bootstrap_methods: Vec<BootstrapMethod>,
+
+    /// 加载完成的时刻（相对于方法区创建），见 [`Metaspace::class_info`]
+    pub loaded_at: Duration,
+
+    /// 加载（解析方法和字段、校验字节码）花费的时间
+    pub load_time: Duration,
 }
 
 /// 类初始化状态
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum ClassState {
     /// 已加载 - class文件已读取并解析
     Loaded,
@@ -80,74 +171,231 @@ pub enum ClassState {
 #[derive(Debug)]
 pub struct RuntimeConstantPool {
     /// 已解析的方法引用
-    /// Key: 常量池索引, Value: 解析后的方法信息
-    pub resolved_methods: HashMap<u16, ResolvedMethodRef>,
+    /// Key: 常量池索引, Value: 解析后的方法信息（共享，命中缓存时不复制字符串）
+    pub resolved_methods: HashMap<u16, Arc<ResolvedMethodRef>>,
 
     /// 已解析的字段引用
     /// Key: 常量池索引, Value: 解析后的字段信息
-    pub resolved_fields: HashMap<u16, ResolvedFieldRef>,
+    pub resolved_fields: HashMap<u16, Arc<ResolvedFieldRef>>,
+
+    /// 改写为声明字段的类之后的字段引用（字段可能继承自父类或接口）
+    /// Key: 常量池索引；字段访问指令不必每次沿继承链查找
+    pub declared_fields: HashMap<u16, Arc<ResolvedFieldRef>>,
+
+    /// 已通过访问检查的方法引用
+    /// Key: 常量池索引；访问检查只在解析时做一次
+    pub access_checked: HashSet<u16>,
+
+    /// invokestatic 调用点缓存：方法引用最终调用的方法
+    /// Key: 常量池索引（同一个类中引用同一个常量池项的调用点调用同一个方法）
+    pub static_call_sites: HashMap<u16, Arc<StaticCallSite>>,
+
+    /// invokedynamic 字符串拼接调用点：引导方法只在第一次执行时“调用”一次
+    /// Key: InvokeDynamic 常量的索引
+    pub concat_sites: HashMap<u16, Arc<StringConcatSite>>,
 
     /// 已解析的类引用
     /// Key: 常量池索引, Value: 类名
-    pub resolved_classes: HashMap<u16, String>,
+    pub resolved_classes: HashMap<u16, Symbol>,
+
+    /// 否定解析结果（目标不存在或不支持）
+    /// Key: 常量池索引；加载新类或类路径变化时清空
+    pub negative: HashMap<u16, NegativeResolution>,
 }
 
 /// 已解析的方法引用
 #[derive(Debug, Clone)]
 pub struct ResolvedMethodRef {
     /// 方法所在的类名
-    pub class_name: String,
+    pub class_name: Symbol,
     /// 方法名
-    pub method_name: String,
+    pub method_name: Symbol,
     /// 方法描述符
-    pub descriptor: String,
+    pub descriptor: Symbol,
+    /// 方法表中的键 "方法名:描述符"，解析时拼接一次，虚方法分派时直接查表
+    pub method_key: Symbol,
+    /// 参数个数（操作数栈上的值个数），解析时从描述符算出一次
+    pub arg_count: usize,
+}
+
+/// 已链接的 invokestatic 调用点：再次执行时不需要查找方法、拼接方法键
+#[derive(Debug)]
+pub struct StaticCallSite {
+    /// 常量池中的方法引用
+    pub method_ref: Arc<ResolvedMethodRef>,
+    /// 声明方法的类（方法可能继承自父类）
+    pub owner: Symbol,
+    pub method: Arc<MethodMetadata>,
+    /// 参数个数（按描述符解析一次）
+    pub arg_count: usize,
+}
+
+/// 已链接的字符串拼接调用点（`StringConcatFactory.makeConcatWithConstants`）
+#[derive(Debug)]
+pub struct StringConcatSite {
+    /// 动态参数的类型描述符，按声明顺序，如 `["Ljava/lang/String;", "I"]`
+    pub arg_types: Vec<String>,
+    /// 配方展开后的片段，静态常量已经替换成文本
+    pub parts: Vec<ConcatPart>,
+}
+
+/// 字符串拼接配方中的一段
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConcatPart {
+    /// 原样输出的文本
+    Literal(String),
+    /// 第 n 个动态参数
+    Arg(usize),
 }
 
 /// 已解析的字段引用
 #[derive(Debug, Clone)]
 pub struct ResolvedFieldRef {
     /// 字段所在的类名
-    pub class_name: String,
+    pub class_name: Symbol,
     /// 字段名
-    pub field_name: String,
+    pub field_name: Symbol,
     /// 字段描述符
-    pub descriptor: String,
+    pub descriptor: Symbol,
+    /// 解析后的字段类型，未赋值时的默认值由它决定
+    pub field_type: FieldType,
+    /// 字段的访问标志；改写为声明字段的类之后才知道，之前（以及 JDK 类的字段）为 0
+    pub access_flags: u16,
+    /// 实例字段在对象布局中的槽位；同样在改写为声明字段的类之后才知道，静态字段为 None
+    pub slot: Option<usize>,
 }
 
 /// 方法元数据
 #[derive(Debug, Clone)]
 pub struct MethodMetadata {
     /// 方法名
-    pub name: String,
+    pub name: Symbol,
     /// 方法描述符 (如 "(II)I" 表示 int add(int, int))
-    pub descriptor: String,
+    pub descriptor: Symbol,
     /// 访问标志
     pub access_flags: u16,
     /// 操作数栈最大深度
     pub max_stack: usize,
     /// 局部变量表大小
     pub max_locals: usize,
-    /// 字节码
-    pub code: Vec<u8>,
+    /// 字节码（栈帧共享这份数据）
+    pub code: Arc<[u8]>,
+    /// 类加载时预解码的指令流；字节码无法完整解码时为 None，执行时逐字节解码
+    pub decoded: Option<Arc<DecodedMethod>>,
     /// 是否是静态方法
     pub is_static: bool,
     /// 是否是本地方法
     pub is_native: bool,
     /// 是否是抽象方法
     pub is_abstract: bool,
+    /// 局部变量表调试信息（来自 LocalVariableTable，可能为空）
+    pub local_variables: Vec<LocalVariable>,
+    /// 行号表 (start_pc, 行号)，按 start_pc 排序（来自 LineNumberTable，可能为空）
+    pub line_numbers: Vec<(u16, u16)>,
+    /// 异常表（try/catch/finally 的处理器，按 class 文件中的顺序）
+    pub exception_table: Vec<ExceptionTableEntry>,
+}
+
+impl MethodMetadata {
+    /// 是否是 synchronized 方法（调用时获取 this 或 Class 对象的监视器）
+    pub fn is_synchronized(&self) -> bool {
+        self.access_flags & access_flags::ACC_SYNCHRONIZED != 0
+    }
+
+    /// `pc` 处指令对应的源代码行号：start_pc 不超过 pc 的最后一项
+    pub fn line_number_at(&self, pc: usize) -> Option<u16> {
+        self.line_numbers
+            .iter()
+            .take_while(|&&(start_pc, _)| start_pc as usize <= pc)
+            .last()
+            .map(|&(_, line)| line)
+    }
+
+    /// 参数类型的描述符，如 `(I[[JLjava/lang/String;)V` → `["I", "[[J", "Ljava/lang/String;"]`
+    ///
+    /// 加载时已经检查过描述符的格式
+    pub fn parameter_descriptors(&self) -> Vec<String> {
+        descriptor::parameter_descriptors(&self.descriptor)
+            .map(|params| params.into_iter().map(str::to_string).collect())
+            .unwrap_or_default()
+    }
+
+    /// 返回类型的描述符，void 方法是 `V`
+    pub fn return_descriptor(&self) -> &str {
+        descriptor::return_descriptor(&self.descriptor).unwrap_or("V")
+    }
+
+    /// 是否是 `public static void main(String[])`
+    pub fn is_main(&self) -> bool {
+        self.name == "main"
+            && self.descriptor == "([Ljava/lang/String;)V"
+            && self.is_static
+            && self.access_flags & access_flags::ACC_PUBLIC != 0
+    }
+}
+
+/// 异常表项 - catch_type 已解析为类名
+#[derive(Debug, Clone)]
+pub struct ExceptionTableEntry {
+    /// 保护范围起点（包含）
+    pub start_pc: usize,
+    /// 保护范围终点（不包含）
+    pub end_pc: usize,
+    /// 处理器入口
+    pub handler_pc: usize,
+    /// 捕获的异常类；None 表示捕获所有异常（finally）
+    pub catch_type: Option<String>,
+}
+
+impl ExceptionTableEntry {
+    /// `pc` 是否在保护范围内
+    pub fn covers(&self, pc: usize) -> bool {
+        pc >= self.start_pc && pc < self.end_pc
+    }
+}
+
+/// 局部变量调试信息
+#[derive(Debug, Clone)]
+pub struct LocalVariable {
+    /// 变量名
+    pub name: String,
+    /// 类型描述符 (如 "J")
+    pub descriptor: String,
+    /// 起始槽位；long/double 还占用 slot + 1
+    pub slot: usize,
+    /// 有效范围起点（字节码偏移）
+    pub start_pc: usize,
+    /// 有效范围长度
+    pub length: usize,
+}
+
+impl LocalVariable {
+    /// 变量在 `pc` 处是否有效
+    pub fn is_live_at(&self, pc: usize) -> bool {
+        pc >= self.start_pc && pc < self.start_pc + self.length
+    }
+
+    /// 是否占两个槽位（long/double）
+    pub fn is_wide(&self) -> bool {
+        matches!(self.descriptor.as_str(), "J" | "D")
+    }
 }
 
 /// 字段元数据
 #[derive(Debug, Clone)]
 pub struct FieldMetadata {
     /// 字段名
-    pub name: String,
+    pub name: Symbol,
     /// 字段描述符 (如 "I" 表示 int, "Ljava/lang/String;" 表示 String)
     pub descriptor: String,
+    /// 解析后的字段类型
+    pub field_type: FieldType,
     /// 访问标志
     pub access_flags: u16,
     /// 是否是静态字段
     pub is_static: bool,
+    /// ConstantValue 属性给出的初始值（仅 static final 的数值常量）
+    pub constant_value: Option<JvmValue>,
 }
 
 impl Metaspace {
@@ -155,12 +403,29 @@ impl Metaspace {
     pub fn new() -> Self {
         Metaspace {
             classes: HashMap::new(),
+            next_load_order: 0,
+            resolution_stats: ResolutionStats::default(),
+            verify: true,
+            max_major_version: Some(DEFAULT_MAX_MAJOR_VERSION),
+            jdk_static_fields: HashMap::new(),
+            created: Instant::now(),
         }
     }
 
+    /// 打开或关闭加载时的字节码校验（默认打开）
+    pub fn set_verify(&mut self, verify: bool) {
+        self.verify = verify;
+    }
+
+    /// 允许加载的最高主版本号（默认 Java 8），None 表示不检查（`--force-version`）
+    pub fn set_max_major_version(&mut self, max_major_version: Option<u16>) {
+        self.max_major_version = max_major_version;
+    }
+
     /// 加载类
     /// 将ClassFile转换为ClassMetadata并存储
     pub fn load_class(&mut self, class_file: ClassFile) -> Result<()> {
+        let started = Instant::now();
         // 获取类名
         let class_name = class_file.get_class_name()?;
 
@@ -169,6 +434,11 @@ impl Metaspace {
             return Ok(());
         }
 
+        // 版本太新的类可能用到不支持的字节码，默认拒绝加载
+        if let Some(max) = self.max_major_version {
+            check_class_version(&class_file, max)?;
+        }
+
         // 获取父类名
         let super_class = if class_file.super_class == 0 {
             None
@@ -186,116 +456,230 @@ impl Metaspace {
         // 解析方法
         let methods = Self::parse_methods(&class_file)?;
 
+        // 校验字节码，不通过的类不会进入方法区
+        if self.verify {
+            for method in methods.values() {
+                verify_method(method, &class_file.constant_pool).map_err(|e| {
+                    JavaException::new(
+                        "java/lang/VerifyError",
+                        format!("{}.{}{}: {}", class_name, method.name, method.descriptor, e),
+                    )
+                })?;
+            }
+        }
+
         // 解析字段
-        let fields = Self::parse_fields(&class_file)?;
+        let (fields, instance_fields) = Self::parse_fields(&class_file)?;
 
         // 创建类元数据
-        let metadata = ClassMetadata {
-            name: class_name.clone(),
+        let mut metadata = ClassMetadata {
+            name: Symbol::from(&class_name),
             super_class,
             interfaces,
             access_flags: class_file.access_flags,
-            constant_pool: class_file.constant_pool.entries.clone(),
+            constant_pool: class_file.constant_pool.clone(),
             runtime_pool: RuntimeConstantPool::new(),
             methods,
             fields,
+            instance_fields,
+            field_layout: None,
             static_fields: HashMap::new(),
             state: ClassState::Loaded,
+            load_order: self.next_load_order,
+            source_file: class_file.get_source_file()?,
+            bootstrap_methods: class_file.get_bootstrap_methods()?,
+            loaded_at: Duration::ZERO,
+            load_time: Duration::ZERO,
         };
+        self.next_load_order += 1;
+        // 准备阶段：静态字段取 ConstantValue 初始值，其余为默认值
+        metadata.reset_static_fields();
+        metadata.load_time = started.elapsed();
+        metadata.loaded_at = self.created.elapsed();
 
         // 存储到方法区
         self.classes.insert(class_name, metadata);
+        // 已算好的布局可能漏掉了这个类（它是某个类未加载的父类）
+        for class in self.classes.values_mut() {
+            class.field_layout = None;
+        }
+        // 之前找不到的类现在可能存在了
+        self.clear_negative_resolutions();
 
         Ok(())
     }
 
     /// 解析方法表
-    fn parse_methods(class_file: &ClassFile) -> Result<HashMap<String, MethodMetadata>> {
+    fn parse_methods(class_file: &ClassFile) -> Result<HashMap<Symbol, Arc<MethodMetadata>>> {
         let mut methods = HashMap::new();
 
         for method in &class_file.methods {
             let name = class_file.constant_pool.get_utf8(method.name_index)?;
             let descriptor = class_file.constant_pool.get_utf8(method.descriptor_index)?;
+            if descriptor::validate_method_descriptor(&descriptor).is_err() {
+                return Err(illegal_signature("Method", &name, class_file, &descriptor));
+            }
 
             let is_static = (method.access_flags & access_flags::ACC_STATIC) != 0;
             let is_native = (method.access_flags & access_flags::ACC_NATIVE) != 0;
             let is_abstract = (method.access_flags & access_flags::ACC_ABSTRACT) != 0;
 
             // 查找Code属性
-            let (max_stack, max_locals, code) = if is_native || is_abstract {
+            let code_info = if is_native || is_abstract {
                 // native和abstract方法没有字节码
-                (0, 0, Vec::new())
+                CodeInfo::default()
             } else {
                 Self::extract_code_from_method(method, class_file)?
             };
 
+            let decoded = DecodedMethod::new(&code_info.code).ok().map(Arc::new);
             let method_metadata = MethodMetadata {
-                name: name.clone(),
-                descriptor: descriptor.clone(),
+                name: Symbol::from(&name),
+                descriptor: Symbol::from(&descriptor),
                 access_flags: method.access_flags,
-                max_stack,
-                max_locals,
-                code,
+                max_stack: code_info.max_stack,
+                max_locals: code_info.max_locals,
+                code: code_info.code.into(),
+                decoded,
                 is_static,
                 is_native,
                 is_abstract,
+                local_variables: code_info.local_variables,
+                line_numbers: code_info.line_numbers,
+                exception_table: code_info.exception_table,
             };
 
-            // Key格式: "方法名:描述符"
-            let key = format!("{}:{}", name, descriptor);
-            methods.insert(key, method_metadata);
+            methods.insert(method_key(&name, &descriptor).into(), Arc::new(method_metadata));
         }
 
         Ok(methods)
     }
 
-    /// 从方法属性中提取Code属性
+    /// 从方法属性中提取Code属性（以及其中的 LocalVariableTable）
     fn extract_code_from_method(
         method: &MethodInfo,
         class_file: &ClassFile,
-    ) -> Result<(usize, usize, Vec<u8>)> {
+    ) -> Result<CodeInfo> {
         for attr in &method.attributes {
             // 检查属性名是否为 "Code"
             let attr_name = class_file.constant_pool.get_utf8(attr.name_index)?;
             if attr_name == "Code" {
                 // 解析Code属性
                 let code_attr = attr.parse_code_attribute()?;
-                return Ok((
-                    code_attr.max_stack as usize,
-                    code_attr.max_locals as usize,
-                    code_attr.code.clone(),
-                ));
+                let local_variables = Self::extract_local_variables(&code_attr, class_file)?;
+                let line_numbers = code_attr.line_number_table(&class_file.constant_pool)?;
+                let mut exception_table = Vec::with_capacity(code_attr.exception_table.len());
+                for handler in &code_attr.exception_table {
+                    exception_table.push(ExceptionTableEntry {
+                        start_pc: handler.start_pc as usize,
+                        end_pc: handler.end_pc as usize,
+                        handler_pc: handler.handler_pc as usize,
+                        catch_type: if handler.catch_type == 0 {
+                            None
+                        } else {
+                            Some(class_file.constant_pool.get_class_name(handler.catch_type)?)
+                        },
+                    });
+                }
+                return Ok(CodeInfo {
+                    max_stack: code_attr.max_stack as usize,
+                    max_locals: code_attr.max_locals as usize,
+                    code: code_attr.code,
+                    local_variables,
+                    line_numbers,
+                    exception_table,
+                });
             }
         }
-        Err(anyhow!(
-            "Method {}:{} has no Code attribute",
-            class_file.constant_pool.get_utf8(method.name_index)?,
-            class_file.constant_pool.get_utf8(method.descriptor_index)?
-        ))
+        // 只有抽象方法和本地方法可以没有 Code 属性（JVMS 4.7.3）
+        Err(JavaException::new(
+            "java/lang/ClassFormatError",
+            format!(
+                "Absent Code attribute in method that is not native or abstract in class file {}: {}{}",
+                class_file.get_class_name()?,
+                class_file.constant_pool.get_utf8(method.name_index)?,
+                class_file.constant_pool.get_utf8(method.descriptor_index)?
+            ),
+        )
+        .into())
+    }
+
+    /// 读取 Code 属性中的 LocalVariableTable，把名字和描述符解析成字符串
+    ///
+    /// 没有用 -g 编译的类没有这个属性，返回空列表
+    fn extract_local_variables(
+        code_attr: &CodeAttribute,
+        class_file: &ClassFile,
+    ) -> Result<Vec<LocalVariable>> {
+        let mut local_variables = Vec::new();
+        for entry in code_attr.local_variable_table(&class_file.constant_pool)? {
+            local_variables.push(LocalVariable {
+                name: class_file.constant_pool.get_utf8(entry.name_index)?,
+                descriptor: class_file.constant_pool.get_utf8(entry.descriptor_index)?,
+                slot: entry.index as usize,
+                start_pc: entry.start_pc as usize,
+                length: entry.length as usize,
+            });
+        }
+        Ok(local_variables)
+    }
+
+    /// 读取字段的 ConstantValue 属性
+    ///
+    /// 字符串常量需要在堆上创建对象，这里只处理数值常量
+    fn extract_constant_value(
+        field: &FieldInfo,
+        class_file: &ClassFile,
+    ) -> Result<Option<JvmValue>> {
+        let cp = &class_file.constant_pool;
+        let Some(attr) = find_attribute(&field.attributes, cp, "ConstantValue")? else {
+            return Ok(None);
+        };
+        // String 常量需要在堆上分配，留给 <clinit>/LDC 处理
+        Ok(match cp.get(attr.parse_constant_value()?)? {
+            ConstantPoolEntry::Integer(v) => Some(JvmValue::Int(*v)),
+            ConstantPoolEntry::Long(v) => Some(JvmValue::Long(*v)),
+            ConstantPoolEntry::Float(v) => Some(JvmValue::Float(*v)),
+            ConstantPoolEntry::Double(v) => Some(JvmValue::Double(*v)),
+            _ => None,
+        })
     }
 
-    /// 解析字段表
-    fn parse_fields(class_file: &ClassFile) -> Result<HashMap<String, FieldMetadata>> {
+    /// 解析字段表，同时返回实例字段的键（按声明顺序）
+    fn parse_fields(class_file: &ClassFile) -> Result<(HashMap<String, FieldMetadata>, Vec<String>)> {
         let mut fields = HashMap::new();
+        let mut instance_fields = Vec::new();
 
         for field in &class_file.fields {
             let name = class_file.constant_pool.get_utf8(field.name_index)?;
             let descriptor = class_file.constant_pool.get_utf8(field.descriptor_index)?;
+            let field_type = FieldType::parse(&descriptor)
+                .map_err(|_| illegal_signature("Field", &name, class_file, &descriptor))?;
             let is_static = (field.access_flags & access_flags::ACC_STATIC) != 0;
+            let constant_value = if is_static {
+                Self::extract_constant_value(field, class_file)?
+            } else {
+                None
+            };
 
             let field_metadata = FieldMetadata {
-                name: name.clone(),
+                name: Symbol::from(&name),
                 descriptor: descriptor.clone(),
+                field_type,
                 access_flags: field.access_flags,
                 is_static,
+                constant_value,
             };
 
             // Key格式: "字段名:描述符"
             let key = format!("{}:{}", name, descriptor);
+            if !is_static {
+                instance_fields.push(key.clone());
+            }
             fields.insert(key, field_metadata);
         }
 
-        Ok(fields)
+        Ok((fields, instance_fields))
     }
 
     /// 获取类元数据
@@ -317,19 +701,588 @@ impl Metaspace {
         self.classes.contains_key(class_name)
     }
 
-    /// 获取已加载的类列表
+    /// 查找调用点的否定解析结果，命中时计数
+    pub fn negative_resolution(&mut self, class_name: &str, index: u16) -> Option<NegativeResolution> {
+        let negative = self
+            .classes
+            .get(class_name)?
+            .runtime_pool
+            .negative
+            .get(&index)
+            .cloned()?;
+        self.resolution_stats.negative_hits += 1;
+        Some(negative)
+    }
+
+    /// 记录调用点的否定解析结果
+    pub fn record_negative_resolution(
+        &mut self,
+        class_name: &str,
+        index: u16,
+        negative: NegativeResolution,
+    ) -> Result<()> {
+        self.get_class_mut(class_name)?
+            .runtime_pool
+            .negative
+            .insert(index, negative);
+        self.resolution_stats.misses += 1;
+        Ok(())
+    }
+
+    /// 查找 invokestatic 调用点缓存，命中时计数
+    pub fn static_call_site(&mut self, class_name: &str, index: u16) -> Option<Arc<StaticCallSite>> {
+        let site = self
+            .classes
+            .get(class_name)?
+            .runtime_pool
+            .static_call_sites
+            .get(&index)
+            .cloned()?;
+        self.resolution_stats.call_site_hits += 1;
+        Some(site)
+    }
+
+    /// 记录 invokestatic 调用点链接到的方法
+    pub fn cache_static_call_site(&mut self, class_name: &str, index: u16, site: StaticCallSite) -> Result<()> {
+        self.get_class_mut(class_name)?
+            .runtime_pool
+            .static_call_sites
+            .insert(index, Arc::new(site));
+        Ok(())
+    }
+
+    /// 清空所有否定解析结果（类定义或类路径变化后它们可能不再成立）
+    pub fn clear_negative_resolutions(&mut self) {
+        for class in self.classes.values_mut() {
+            class.runtime_pool.negative.clear();
+        }
+    }
+
+    /// 卸载类：删除它的元数据，以及其他类缓存的指向它的解析结果
+    ///
+    /// `thread` 上还有栈帧在执行这个类的方法，或者还有已加载的类继承/实现它时报错
+    pub fn unload_class(&mut self, class_name: &str, thread: &JvmThread) -> Result<()> {
+        if !self.classes.contains_key(class_name) {
+            return Err(anyhow!("Class not found: {}", class_name));
+        }
+        if let Some(frame) = thread.frames().find(|f| f.class_name == class_name) {
+            return Err(anyhow!(
+                "Cannot unload {}: {}.{} is still on the thread stack",
+                class_name,
+                frame.class_name,
+                frame.method_name
+            ));
+        }
+        let mut dependents: Vec<&str> = self
+            .classes
+            .values()
+            .filter(|c| {
+                c.super_class.as_deref() == Some(class_name)
+                    || c.interfaces.iter().any(|i| i == class_name)
+            })
+            .map(|c| c.name.as_str())
+            .collect();
+        if !dependents.is_empty() {
+            dependents.sort();
+            return Err(anyhow!(
+                "Cannot unload {}: still extended or implemented by {}",
+                class_name,
+                dependents.join(", ")
+            ));
+        }
+
+        self.classes.remove(class_name);
+        for class in self.classes.values_mut() {
+            let pool = &mut class.runtime_pool;
+            pool.resolved_methods.retain(|_, m| m.class_name != class_name);
+            pool.resolved_fields.retain(|_, f| f.class_name != class_name);
+            pool.declared_fields.retain(|_, f| f.class_name != class_name);
+            pool.static_call_sites.retain(|_, site| {
+                site.owner != class_name && site.method_ref.class_name != class_name
+            });
+            pool.resolved_classes.retain(|_, name| name != class_name);
+            // 类集合变了，否定结果和访问检查结果可能不再成立
+            pool.negative.clear();
+            pool.access_checked.clear();
+        }
+        Ok(())
+    }
+
+    /// 清空所有类的运行时常量池缓存，释放内存；之后的符号引用会重新解析
+    pub fn clear_runtime_pools(&mut self) {
+        for class in self.classes.values_mut() {
+            class.runtime_pool = RuntimeConstantPool::new();
+        }
+    }
+
+    /// 方法区的占用统计
+    pub fn stats(&self) -> MetaspaceStats {
+        let mut stats = MetaspaceStats {
+            classes: self.classes.len(),
+            ..Default::default()
+        };
+        for class in self.classes.values() {
+            stats.methods += class.methods.len();
+            stats.bytecode_bytes += class.methods.values().map(|m| m.code.len()).sum::<usize>();
+            let pool = &class.runtime_pool;
+            stats.resolved_entries += pool.resolved_methods.len()
+                + pool.resolved_fields.len()
+                + pool.declared_fields.len()
+                + pool.access_checked.len()
+                + pool.static_call_sites.len()
+                + pool.concat_sites.len()
+                + pool.resolved_classes.len()
+                + pool.negative.len();
+        }
+        stats
+    }
+
+    /// 符号引用解析的计数
+    pub fn resolution_stats(&self) -> ResolutionStats {
+        self.resolution_stats
+    }
+
+    /// 累计加载（解析常量池、链接方法）的次数，卸载后重新加载的类计入两次
+    pub fn load_count(&self) -> usize {
+        self.next_load_order
+    }
+
+    /// 获取已加载的类列表，按加载顺序排列
     pub fn loaded_classes(&self) -> Vec<String> {
-        self.classes.keys().cloned().collect()
+        self.classes_in_load_order()
+            .into_iter()
+            .map(|class| class.name.to_string())
+            .collect()
+    }
+
+    /// 所有已加载类的元数据，按加载顺序排列
+    ///
+    /// 对外报告（统计、列表）都应该用这个顺序，而不是 HashMap 的迭代顺序
+    pub fn classes_in_load_order(&self) -> Vec<&ClassMetadata> {
+        let mut classes: Vec<_> = self.classes.values().collect();
+        classes.sort_by_key(|class| class.load_order);
+        classes
+    }
+
+    /// 虚方法查找：从 `class_name` 开始沿父类链查找 name:descriptor，找不到时再找接口的 default 方法
+    ///
+    /// 返回 (声明该方法的类名, 方法元数据)
+    pub fn resolve_virtual_method(
+        &self,
+        class_name: &str,
+        name: &str,
+        descriptor: &str,
+    ) -> Result<(Symbol, Arc<MethodMetadata>)> {
+        self.dispatch_virtual(class_name, &method_key(name, descriptor), name, descriptor)
+    }
+
+    /// 按已解析的方法引用做虚方法查找，直接使用引用中缓存的方法键
+    pub fn resolve_virtual_method_ref(
+        &self,
+        class_name: &str,
+        method_ref: &ResolvedMethodRef,
+    ) -> Result<(Symbol, Arc<MethodMetadata>)> {
+        self.dispatch_virtual(
+            class_name,
+            &method_ref.method_key,
+            &method_ref.method_name,
+            &method_ref.descriptor,
+        )
     }
+
+    fn dispatch_virtual(
+        &self,
+        class_name: &str,
+        key: &str,
+        name: &str,
+        descriptor: &str,
+    ) -> Result<(Symbol, Arc<MethodMetadata>)> {
+        let mut found_abstract = false;
+        let mut current = Some(class_name);
+        while let Some(current_name) = current {
+            let Some(class) = self.classes.get(current_name) else {
+                break; // 父类未加载（如 java/lang/Object）
+            };
+            if let Some(method) = class.methods.get(key) {
+                if !method.is_abstract {
+                    return Ok((class.name.clone(), method.clone()));
+                }
+                found_abstract = true;
+            }
+            current = class.super_class.as_deref();
+        }
+        // 类链中没有实现时，使用接口的 default 方法（取第一个有方法体的）
+        for interface in self.hierarchy(class_name).1 {
+            if let Some(method) = interface.methods.get(key) {
+                if !method.is_abstract {
+                    return Ok((interface.name.clone(), method.clone()));
+                }
+                found_abstract = true;
+            }
+        }
+        let error = if found_abstract {
+            JavaException::abstract_method(class_name, name, descriptor)
+        } else {
+            JavaException::new(
+                "java/lang/NoSuchMethodError",
+                format!("{}.{}{}", class_name, name, descriptor),
+            )
+        };
+        Err(error.into())
+    }
+
+    /// 查找声明字段的类：从 `class_name` 开始沿 super_class 向上
+    ///
+    /// 找不到（如 JDK 类的字段）时返回 `class_name` 本身
+    pub fn resolve_field_owner(&self, class_name: &str, field_name: &str, descriptor: &str) -> Symbol {
+        match self.resolve_field_in_hierarchy(class_name, field_name, descriptor) {
+            Ok((owner, _)) => owner,
+            Err(_) => class_name.into(),
+        }
+    }
+
+    /// `class_name` 自身、父类链和所有（间接）接口，按 BFS 顺序：先类链，再接口
+    fn hierarchy(&self, class_name: &str) -> (Vec<&ClassMetadata>, Vec<&ClassMetadata>) {
+        let mut classes = Vec::new();
+        let mut pending_interfaces = Vec::new();
+        let mut current = Some(class_name);
+        while let Some(name) = current {
+            let Some(class) = self.classes.get(name) else {
+                break; // 父类未加载（如 java/lang/Object）
+            };
+            classes.push(class);
+            pending_interfaces.extend(class.interfaces.iter().map(String::as_str));
+            current = class.super_class.as_deref();
+        }
+
+        let mut interfaces = Vec::new();
+        let mut visited = HashSet::new();
+        let mut index = 0;
+        while index < pending_interfaces.len() {
+            let name = pending_interfaces[index];
+            index += 1;
+            if !visited.insert(name) {
+                continue;
+            }
+            if let Some(interface) = self.classes.get(name) {
+                interfaces.push(interface);
+                pending_interfaces.extend(interface.interfaces.iter().map(String::as_str));
+            }
+        }
+        (classes, interfaces)
+    }
+
+    /// 方法解析（JVMS 5.4.3.3 的简化版）：先沿父类链查找，再在父接口中查找
+    ///
+    /// 接口中优先选择有方法体的 default 方法。返回 (声明该方法的类名, 方法元数据)；
+    /// 找不到时抛出 NoSuchMethodError
+    pub fn resolve_method_in_hierarchy(
+        &self,
+        class_name: &str,
+        name: &str,
+        descriptor: &str,
+    ) -> Result<(Symbol, Arc<MethodMetadata>)> {
+        let key = method_key(name, descriptor);
+        let (classes, interfaces) = self.hierarchy(class_name);
+        if let Some((class, method)) = classes
+            .iter()
+            .find_map(|class| Some((class, class.methods.get(key.as_str())?)))
+        {
+            return Ok((class.name.clone(), method.clone()));
+        }
+
+        let mut abstract_method = None;
+        for interface in interfaces {
+            if let Some(method) = interface.methods.get(key.as_str()) {
+                if !method.is_abstract {
+                    return Ok((interface.name.clone(), method.clone()));
+                }
+                abstract_method.get_or_insert((interface.name.clone(), method.clone()));
+            }
+        }
+        abstract_method.ok_or_else(|| {
+            JavaException::new(
+                "java/lang/NoSuchMethodError",
+                format!("{}.{}{}", class_name, name, descriptor),
+            )
+            .into()
+        })
+    }
+
+    /// 字段解析（JVMS 5.4.3.2 的简化版）：先沿父类链查找，再查父接口的常量
+    ///
+    /// 返回 (声明该字段的类名, 字段元数据)；找不到时抛出 NoSuchFieldError
+    pub fn resolve_field_in_hierarchy(
+        &self,
+        class_name: &str,
+        field_name: &str,
+        descriptor: &str,
+    ) -> Result<(Symbol, FieldMetadata)> {
+        let key = format!("{}:{}", field_name, descriptor);
+        let (classes, interfaces) = self.hierarchy(class_name);
+        classes
+            .into_iter()
+            .chain(interfaces)
+            .find_map(|class| Some((class.name.clone(), class.fields.get(&key)?.clone())))
+            .ok_or_else(|| {
+                JavaException::new(
+                    "java/lang/NoSuchFieldError",
+                    format!("{}.{}:{}", class_name, field_name, descriptor),
+                )
+                .into()
+            })
+    }
+
+    /// 所有类（包括内置 JDK 类）的静态字段值（GC 把它们作为根，整理后原地改写引用）
+    pub(crate) fn static_values_mut(&mut self) -> impl Iterator<Item = &mut JvmValue> {
+        self.classes
+            .values_mut()
+            .flat_map(|class| class.static_fields.values_mut())
+            .chain(self.jdk_static_fields.values_mut().flat_map(|fields| fields.values_mut()))
+    }
+
+    /// 内置 JDK 类的静态字段（还没有赋值时为 None）
+    pub fn jdk_static_field(&self, class_name: &str, field_name: &str) -> Option<&JvmValue> {
+        self.jdk_static_fields.get(class_name)?.get(field_name)
+    }
+
+    /// 所有内置 JDK 类的静态字段：类名 → 字段名 → 值
+    pub(crate) fn jdk_static_fields(&self) -> &HashMap<String, HashMap<String, JvmValue>> {
+        &self.jdk_static_fields
+    }
+
+    /// 替换所有内置 JDK 类的静态字段（恢复执行快照时）
+    pub(crate) fn replace_jdk_static_fields(&mut self, fields: HashMap<String, HashMap<String, JvmValue>>) {
+        self.jdk_static_fields = fields;
+    }
+
+    /// 设置内置 JDK 类的静态字段
+    pub fn set_jdk_static_field(&mut self, class_name: &str, field_name: &str, value: JvmValue) {
+        self.jdk_static_fields
+            .entry(class_name.to_string())
+            .or_default()
+            .insert(field_name.to_string(), value);
+    }
+
+    /// 类的实例字段布局：从最顶层的已加载父类开始，依次接上每个类声明的实例字段，结果缓存在类元数据中
+    ///
+    /// 父类的字段总在前面，所以同一个字段在父类和子类的布局中槽位相同；未加载的类（JDK 类）布局为空
+    pub fn field_layout(&mut self, class_name: &str) -> Arc<FieldLayout> {
+        if let Some(layout) = self.classes.get(class_name).and_then(|class| class.field_layout.clone()) {
+            return layout;
+        }
+        let (classes, _) = self.hierarchy(class_name);
+        let mut layout = FieldLayout::new();
+        for class in classes.into_iter().rev() {
+            for key in &class.instance_fields {
+                let field = &class.fields[key];
+                layout.push(
+                    (class.name.clone(), field.name.clone()),
+                    jvm_default_value(&field.field_type),
+                );
+            }
+        }
+        let layout = Arc::new(layout);
+        if let Some(class) = self.classes.get_mut(class_name) {
+            class.field_layout = Some(layout.clone());
+        }
+        layout
+    }
+
+    /// 新对象的实例字段及其默认值，沿父类链收集（接口只有静态字段）
+    ///
+    /// 键带上声明字段的类，所以父类和子类的同名字段各占一个槽位
+    pub fn instance_field_defaults(&self, class_name: &str) -> HashMap<FieldKey, JvmValue> {
+        let (classes, _) = self.hierarchy(class_name);
+        classes
+            .into_iter()
+            .flat_map(|class| {
+                class.fields.values().filter(|field| !field.is_static).map(|field| {
+                    (
+                        (class.name.clone(), field.name.clone()),
+                        jvm_default_value(&field.field_type),
+                    )
+                })
+            })
+            .collect()
+    }
+
+    /// `sub` 类型的值能否赋给 `sup` 类型（checkcast/instanceof/异常匹配的判断）
+    ///
+    /// - 类：沿 super_class 和 interfaces 向上查找
+    /// - 数组：引用类型组件按组件类型判断（协变），基本类型组件必须相同；
+    ///   数组还可以赋给 Object、Cloneable 和 Serializable
+    /// - 未加载的 JDK 类（如 java/lang/RuntimeException）使用内置的继承关系
+    pub fn is_assignable_from(&self, sub: &str, sup: &str) -> bool {
+        if sub == sup || sup == "java/lang/Object" {
+            return true;
+        }
+
+        if let Some(sub_component) = sub.strip_prefix('[') {
+            return match sup.strip_prefix('[') {
+                Some(sup_component) => {
+                    match (reference_component(sub_component), reference_component(sup_component)) {
+                        (Some(a), Some(b)) => self.is_assignable_from(a, b),
+                        _ => false,
+                    }
+                }
+                None => matches!(sup, "java/lang/Cloneable" | "java/io/Serializable"),
+            };
+        }
+
+        let mut visited = HashSet::new();
+        let mut pending = vec![sub.to_string()];
+        while let Some(name) = pending.pop() {
+            if name == sup {
+                return true;
+            }
+            if !visited.insert(name.clone()) {
+                continue;
+            }
+            match self.classes.get(&name) {
+                Some(class) => {
+                    pending.extend(class.super_class.iter().cloned());
+                    pending.extend(class.interfaces.iter().cloned());
+                }
+                None => pending.extend(builtin_super_class(&name).map(str::to_string)),
+            }
+        }
+        false
+    }
+
+    /// `accessor` 类中的代码能否访问 `owner` 类声明的、访问标志为 `flags` 的成员（JVMS 5.4.4）
+    ///
+    /// - public：总是可以
+    /// - private：只有声明它的类
+    /// - protected：同一个包，或者 `owner` 的子类
+    /// - 默认（包访问）：同一个包
+    pub fn is_member_accessible(&self, accessor: &str, owner: &str, flags: u16) -> bool {
+        if accessor == owner || flags & access_flags::ACC_PUBLIC != 0 {
+            return true;
+        }
+        if flags & access_flags::ACC_PRIVATE != 0 {
+            return false;
+        }
+        package_name(accessor) == package_name(owner)
+            || (flags & access_flags::ACC_PROTECTED != 0 && self.is_assignable_from(accessor, owner))
+    }
+
+    /// 重置所有类的运行状态（静态字段和初始化状态），保留类元数据
+    pub fn reset_run_state(&mut self) {
+        // 内置 JDK 类的静态字段引用的是旧堆中的对象
+        self.jdk_static_fields.clear();
+        for class in self.classes.values_mut() {
+            class.reset_static_fields();
+            // 已经开始初始化的类回到 Linked，下次使用时重新执行 <clinit>
+            if matches!(class.state, ClassState::Initializing | ClassState::Initialized) {
+                class.state = ClassState::Linked;
+            }
+        }
+    }
+}
+
+/// 方法表的键："方法名:描述符"（如 "add:(II)I"）
+pub fn method_key(name: &str, descriptor: &str) -> String {
+    format!("{}:{}", name, descriptor)
+}
+
+/// 类所在的包（内部名最后一个 '/' 之前的部分），默认包为 ""
+pub fn package_name(class_name: &str) -> &str {
+    class_name.rsplit_once('/').map_or("", |(package, _)| package)
+}
+
+/// 数组组件描述符对应的引用类型名：`Ljava/lang/String;` → `java/lang/String`，
+/// `[I` → `[I`；基本类型返回 None
+fn reference_component(component: &str) -> Option<&str> {
+    if let Some(class_name) = component.strip_prefix('L') {
+        class_name.strip_suffix(';')
+    } else if component.starts_with('[') {
+        Some(component)
+    } else {
+        None
+    }
+}
+
+/// 常用 JDK 类（异常、线程、包装类）的父类（这些类不会被加载到 Metaspace）
+pub(crate) fn builtin_super_class(class_name: &str) -> Option<&'static str> {
+    let super_class = match class_name {
+        "java/lang/Throwable"
+        | "java/lang/Thread"
+        | "java/lang/Number"
+        | "java/io/PrintStream"
+        | "java/lang/Boolean"
+        | "java/lang/Character" => "java/lang/Object",
+        "java/lang/Integer" | "java/lang/Long" | "java/lang/Double" => "java/lang/Number",
+        "java/lang/Exception" | "java/lang/Error" => "java/lang/Throwable",
+        "java/lang/RuntimeException" => "java/lang/Exception",
+        "java/lang/ArithmeticException"
+        | "java/lang/ArrayStoreException"
+        | "java/lang/ClassCastException"
+        | "java/lang/IllegalArgumentException"
+        | "java/lang/IllegalStateException"
+        | "java/lang/IllegalMonitorStateException"
+        | "java/lang/IndexOutOfBoundsException"
+        | "java/lang/NegativeArraySizeException"
+        | "java/lang/NullPointerException"
+        | "java/lang/UnsupportedOperationException" => "java/lang/RuntimeException",
+        "java/lang/ArrayIndexOutOfBoundsException"
+        | "java/lang/StringIndexOutOfBoundsException" => "java/lang/IndexOutOfBoundsException",
+        "java/lang/NumberFormatException" | "java/lang/IllegalThreadStateException" => {
+            "java/lang/IllegalArgumentException"
+        }
+        "java/lang/ReflectiveOperationException" | "java/io/IOException" => "java/lang/Exception",
+        "java/lang/ClassNotFoundException" | "java/lang/InstantiationException" => {
+            "java/lang/ReflectiveOperationException"
+        }
+        "java/lang/LinkageError" | "java/lang/VirtualMachineError" => "java/lang/Error",
+        "java/lang/IncompatibleClassChangeError" | "java/lang/UnsatisfiedLinkError" => {
+            "java/lang/LinkageError"
+        }
+        "java/lang/InstantiationError"
+        | "java/lang/AbstractMethodError"
+        | "java/lang/NoSuchFieldError"
+        | "java/lang/NoSuchMethodError" => "java/lang/IncompatibleClassChangeError",
+        "java/lang/StackOverflowError" | "java/lang/OutOfMemoryError" => {
+            "java/lang/VirtualMachineError"
+        }
+        _ => return None,
+    };
+    Some(super_class)
+}
+
+/// 描述符格式错误的方法或字段：ClassFormatError
+fn illegal_signature(kind: &str, name: &str, class_file: &ClassFile, descriptor: &str) -> anyhow::Error {
+    let class_name = class_file.get_class_name().unwrap_or_default();
+    JavaException::new(
+        "java/lang/ClassFormatError",
+        format!("{} \"{}\" in class {} has illegal signature \"{}\"", kind, name, class_name, descriptor),
+    )
+    .into()
 }
 
 impl ClassMetadata {
-    /// 查找方法
-    /// 如果当前类没有，会递归查找父类（TODO: 后续实现）
+    /// SourceFile 属性给出的源文件名
+    pub fn source_file(&self) -> Option<&str> {
+        self.source_file.as_deref()
+    }
+
+    /// 把静态字段恢复到准备阶段的值：有 ConstantValue 的取常量，其余取默认值
+    ///
+    /// 默认值不显式存储，GETSTATIC 读不到时按描述符返回默认值
+    pub fn reset_static_fields(&mut self) {
+        self.static_fields.clear();
+        for field in self.fields.values() {
+            if let Some(value) = &field.constant_value {
+                self.static_fields.insert(field.name.clone(), value.clone());
+            }
+        }
+    }
+
+    /// 查找当前类声明的方法（不查找父类，继承的方法用 [`Metaspace::resolve_method_in_hierarchy`]）
     pub fn find_method(&self, name: &str, descriptor: &str) -> Result<&MethodMetadata> {
-        let key = format!("{}:{}", name, descriptor);
         self.methods
-            .get(&key)
+            .get(method_key(name, descriptor).as_str())
+            .map(|method| &**method)
             .ok_or_else(|| anyhow!("Method not found: {}.{}{}", self.name, name, descriptor))
     }
 
@@ -341,83 +1294,53 @@ impl ClassMetadata {
             .ok_or_else(|| anyhow!("Field not found: {}.{}{}", self.name, name, descriptor))
     }
 
-    /// 解析 NameAndType 条目（辅助方法）
-    /// 返回 (name, descriptor) 元组
-    fn resolve_name_and_type(&self, index: u16) -> Result<(String, String)> {
-        let nat_entry = self
-            .constant_pool
-            .get(index as usize)
-            .ok_or_else(|| anyhow!("Invalid NameAndType index: {}", index))?
-            .as_ref()
-            .ok_or_else(|| anyhow!("NameAndType entry is None"))?;
-
-        let (name_index, descriptor_index) = match nat_entry {
-            ConstantPoolEntry::NameAndType {
-                name_index,
-                descriptor_index,
-            } => (*name_index, *descriptor_index),
-            _ => return Err(anyhow!("Expected NameAndType entry")),
-        };
+    /// 当前类声明的所有方法（不含继承的方法），顺序不确定
+    pub fn declared_methods(&self) -> impl Iterator<Item = &MethodMetadata> {
+        self.methods.values().map(|method| &**method)
+    }
 
-        let name = self
-            .constant_pool
-            .get(name_index as usize)
-            .and_then(|e| e.as_ref())
-            .and_then(|e| {
-                if let ConstantPoolEntry::Utf8(s) = e {
-                    Some(s.clone())
-                } else {
-                    None
-                }
-            })
-            .ok_or_else(|| anyhow!("Invalid name in NameAndType"))?;
-
-        let descriptor = self
-            .constant_pool
-            .get(descriptor_index as usize)
-            .and_then(|e| e.as_ref())
-            .and_then(|e| {
-                if let ConstantPoolEntry::Utf8(s) = e {
-                    Some(s.clone())
-                } else {
-                    None
-                }
-            })
-            .ok_or_else(|| anyhow!("Invalid descriptor in NameAndType"))?;
+    /// 当前类声明的名为 `name` 的方法（重载的方法都返回），按描述符排序
+    pub fn methods_by_name(&self, name: &str) -> Vec<&MethodMetadata> {
+        let mut methods: Vec<_> = self.declared_methods().filter(|m| m.name == name).collect();
+        methods.sort_by(|a, b| a.descriptor.as_str().cmp(b.descriptor.as_str()));
+        methods
+    }
+
+    /// `public static void main(String[])` 方法
+    pub fn find_main(&self) -> Option<&MethodMetadata> {
+        self.find_method("main", "([Ljava/lang/String;)V")
+            .ok()
+            .filter(|method| method.is_main())
+    }
+
+    /// 当前类声明的所有字段（不含继承的字段），顺序不确定
+    pub fn declared_fields(&self) -> impl Iterator<Item = &FieldMetadata> {
+        self.fields.values()
+    }
+
+    /// 当前类声明的名为 `name` 的字段，按描述符排序
+    ///
+    /// Java 源代码中字段不能重名，但 class 文件允许同名不同类型的字段
+    pub fn fields_by_name(&self, name: &str) -> Vec<&FieldMetadata> {
+        let mut fields: Vec<_> = self.declared_fields().filter(|f| f.name == name).collect();
+        fields.sort_by(|a, b| a.descriptor.cmp(&b.descriptor));
+        fields
+    }
 
-        Ok((name, descriptor))
+    /// 解析 NameAndType 条目（辅助方法）
+    /// 返回 (name, descriptor) 元组
+    pub(crate) fn resolve_name_and_type(&self, index: u16) -> Result<(String, String)> {
+        self.constant_pool.get_name_and_type(index)
     }
 
-    pub fn resolve_class_ref(&mut self, index: u16) -> Result<String> {
+    pub fn resolve_class_ref(&mut self, index: u16) -> Result<Symbol> {
         // 1. 先检查缓存
         if let Some(class_name) = self.runtime_pool.resolved_classes.get(&index) {
             return Ok(class_name.clone()); // 🚀 缓存命中
         }
 
         // 2. 缓存未命中，解析常量池
-        let class_entry = self
-            .constant_pool
-            .get(index as usize)
-            .ok_or_else(|| anyhow!("Invalid class index: {}", index))?
-            .as_ref()
-            .ok_or_else(|| anyhow!("Class entry is None"))?;
-
-        let class_name = if let ConstantPoolEntry::Class { name_index } = class_entry {
-            let name_entry = self
-                .constant_pool
-                .get(*name_index as usize)
-                .ok_or_else(|| anyhow!("Invalid name index: {}", name_index))?
-                .as_ref()
-                .ok_or_else(|| anyhow!("Name entry is None"))?;
-
-            if let ConstantPoolEntry::Utf8(name) = name_entry {
-                name.clone()
-            } else {
-                return Err(anyhow!("Expected Utf8 for class name"));
-            }
-        } else {
-            return Err(anyhow!("Expected Class entry"));
-        };
+        let class_name = Symbol::from(self.constant_pool.get_class_name(index)?);
 
         // 3. 存入缓存
         self.runtime_pool
@@ -431,21 +1354,14 @@ impl ClassMetadata {
     pub fn resolve_method_ref(
         &mut self,
         index: u16,
-    ) -> Result<ResolvedMethodRef> {
+    ) -> Result<Arc<ResolvedMethodRef>> {
         // 先检查缓存
         if let Some(resolved) = self.runtime_pool.resolved_methods.get(&index) {
             return Ok(resolved.clone());
         }
 
         // 从常量池解析
-        let cp_entry = self
-            .constant_pool
-            .get(index as usize)
-            .ok_or_else(|| anyhow!("Invalid constant pool index: {}", index))?
-            .as_ref()
-            .ok_or_else(|| anyhow!("Constant pool entry is None at index: {}", index))?;
-
-        let (class_index, name_and_type_index) = match cp_entry {
+        let (class_index, name_and_type_index) = match self.constant_pool.get(index)? {
             ConstantPoolEntry::MethodRef {
                 class_index,
                 name_and_type_index,
@@ -469,11 +1385,14 @@ impl ClassMetadata {
         let (method_name, descriptor) = self.resolve_name_and_type(name_and_type_index)?;
 
         // 创建解析结果
-        let resolved = ResolvedMethodRef {
+        let arg_count = MethodDescriptor::parse(&descriptor)?.params.len();
+        let resolved = Arc::new(ResolvedMethodRef {
             class_name,
-            method_name,
-            descriptor,
-        };
+            method_key: method_key(&method_name, &descriptor).into(),
+            arg_count,
+            method_name: method_name.into(),
+            descriptor: descriptor.into(),
+        });
 
         // 缓存解析结果
         self.runtime_pool
@@ -487,21 +1406,14 @@ impl ClassMetadata {
     pub fn resolve_field_ref(
         &mut self,
         index: u16,
-    ) -> Result<ResolvedFieldRef> {
+    ) -> Result<Arc<ResolvedFieldRef>> {
         // 先检查缓存
         if let Some(resolved) = self.runtime_pool.resolved_fields.get(&index) {
             return Ok(resolved.clone());
         }
 
         // 从常量池解析
-        let cp_entry = self
-            .constant_pool
-            .get(index as usize)
-            .ok_or_else(|| anyhow!("Invalid constant pool index: {}", index))?
-            .as_ref()
-            .ok_or_else(|| anyhow!("Constant pool entry is None"))?;
-
-        let (class_index, name_and_type_index) = match cp_entry {
+        let (class_index, name_and_type_index) = match self.constant_pool.get(index)? {
             ConstantPoolEntry::FieldRef {
                 class_index,
                 name_and_type_index,
@@ -516,11 +1428,15 @@ impl ClassMetadata {
         let (field_name, descriptor) = self.resolve_name_and_type(name_and_type_index)?;
 
         // 创建解析结果
-        let resolved = ResolvedFieldRef {
+        let field_type = FieldType::parse(&descriptor)?;
+        let resolved = Arc::new(ResolvedFieldRef {
             class_name,
-            field_name,
-            descriptor,
-        };
+            field_name: field_name.into(),
+            descriptor: descriptor.into(),
+            field_type,
+            access_flags: 0,
+            slot: None,
+        });
 
         // 缓存解析结果
         self.runtime_pool
@@ -537,7 +1453,12 @@ impl RuntimeConstantPool {
         RuntimeConstantPool {
             resolved_methods: HashMap::new(),
             resolved_fields: HashMap::new(),
+            declared_fields: HashMap::new(),
+            access_checked: HashSet::new(),
             resolved_classes: HashMap::new(),
+            static_call_sites: HashMap::new(),
+            concat_sites: HashMap::new(),
+            negative: HashMap::new(),
         }
     }
 }
@@ -624,7 +1545,7 @@ mod tests {
         let class_meta = metaspace.get_class("ReturnOne")?;
 
         // ReturnOne 应该有多个方法（包括<init>）
-        assert!(class_meta.methods.len() > 0);
+        assert!(!class_meta.methods.is_empty());
 
         Ok(())
     }
@@ -671,6 +1592,23 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_loaded_classes_in_load_order() -> Result<()> {
+        for order in [["Calculator", "ReturnOne"], ["ReturnOne", "Calculator"]] {
+            let mut metaspace = Metaspace::new();
+            for name in order {
+                metaspace.load_class(ClassFile::from_file(format!("examples/{}.class", name))?)?;
+            }
+            // 重复加载不改变顺序
+            metaspace.load_class(ClassFile::from_file(format!("examples/{}.class", order[0]))?)?;
+
+            assert_eq!(metaspace.loaded_classes(), order);
+            assert_eq!(metaspace.get_class(order[0])?.load_order, 0);
+            assert_eq!(metaspace.get_class(order[1])?.load_order, 1);
+        }
+        Ok(())
+    }
+
     #[test]
     fn test_duplicate_class_load() -> Result<()> {
         let mut metaspace = Metaspace::new();
@@ -12,12 +12,31 @@
 //! - 堆是线程共享的，所有对象都在堆上分配
 //! - 方法区存储类的元数据
 
+pub mod class_info;
+pub mod exception;
 pub mod frame;
 pub mod heap;
+pub mod heap_dump;
+pub mod layout;
+pub mod nursery;
+pub mod symbol;
 pub mod thread;
 pub mod metaspace;
 
+pub use class_info::{ClassInfo, DependencyNode, DependencyStatus, LoadSummary};
+pub use exception::{
+    ExecutionCancelled, ExecutionError, ExecutionLimit, ExecutionLimitExceeded, FrameInfo,
+    JavaException,
+};
 pub use frame::Frame;
-pub use heap::Heap;
+pub use heap::{Heap, ObjRef};
+pub use heap_dump::HeapDump;
+pub use layout::FieldLayout;
+pub use nursery::Nursery;
+pub use symbol::Symbol;
 pub use thread::JvmThread;
-pub use metaspace::{Metaspace, ClassMetadata, MethodMetadata, FieldMetadata, ResolvedMethodRef};
+pub use metaspace::{
+    ClassMetadata, ConcatPart, ExceptionTableEntry, FieldMetadata, LocalVariable, Metaspace,
+    MetaspaceStats, MethodMetadata, NegativeResolution, ResolutionStats, ResolvedMethodRef,
+    StaticCallSite, StringConcatSite,
+};
@@ -0,0 +1,75 @@
+//! # 新生代（nursery）
+//!
+//! 分代模式下新对象先进入新生代。新生代满了就做一次 minor GC：只标记新生代对象，
+//! 根是线程栈、静态字段等，再加上记忆集中老年代对象的引用；熬过几次 minor GC 的对象晋升到老年代。
+//!
+//! ## 学习要点
+//! - 分代假说：大多数对象朝生夕死（StringBuilder、装箱的临时值），只扫描新生代就能回收大部分垃圾
+//! - minor GC 不遍历老年代，所以老年代对象对新生代对象的引用必须另外记下来（记忆集），否则会误回收
+//! - 写屏障：putfield/aastore 把新生代对象的引用写进老年代对象时，把老年代对象加入记忆集
+//! - 这里的新生代不是一块单独的内存，对象仍然在堆的槽位中，新生代只记录哪些槽位是新对象，
+//!   所以晋升不需要移动对象，引用也不用改写
+
+use super::heap::ObjRef;
+use std::collections::HashSet;
+
+/// 默认的新生代大小：新生代对象数达到这个值时做 minor GC
+pub const DEFAULT_NURSERY_SIZE: usize = 1024;
+
+/// 默认的晋升年龄：熬过这么多次 minor GC 后晋升到老年代
+pub const DEFAULT_PROMOTION_AGE: u8 = 2;
+
+/// 新生代
+#[derive(Debug)]
+pub struct Nursery {
+    /// 新生代对象，按分配顺序；已经被回收的对象（引用失效）在下一次 minor GC 时去掉
+    pub(crate) objects: Vec<ObjRef>,
+    /// 新生代对象数达到这个值时需要 minor GC
+    pub(crate) capacity: usize,
+    /// 熬过多少次 minor GC 后晋升
+    pub(crate) promotion_age: u8,
+    /// 记忆集：可能引用新生代对象的老年代对象
+    pub(crate) remembered: HashSet<ObjRef>,
+}
+
+impl Nursery {
+    /// 创建新生代；容量和晋升年龄至少为 1
+    pub fn new(capacity: usize, promotion_age: u8) -> Self {
+        Nursery {
+            objects: Vec::new(),
+            capacity: capacity.max(1),
+            promotion_age: promotion_age.max(1),
+            remembered: HashSet::new(),
+        }
+    }
+
+    /// 新生代对象数（包括还没有去掉的已回收对象）
+    pub fn len(&self) -> usize {
+        self.objects.len()
+    }
+
+    /// 新生代是否为空
+    pub fn is_empty(&self) -> bool {
+        self.objects.is_empty()
+    }
+
+    /// 是否需要 minor GC
+    pub fn is_full(&self) -> bool {
+        self.objects.len() >= self.capacity
+    }
+
+    /// 新生代大小
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// 晋升年龄
+    pub fn promotion_age(&self) -> u8 {
+        self.promotion_age
+    }
+
+    /// 记忆集中的老年代对象
+    pub fn remembered(&self) -> impl Iterator<Item = ObjRef> + '_ {
+        self.remembered.iter().copied()
+    }
+}
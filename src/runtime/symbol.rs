@@ -0,0 +1,153 @@
+//! # 符号
+//!
+//! 类名、方法名、字段名和描述符在执行过程中被反复复制：每个栈帧记录所属的类和方法，
+//! 每次调用按名字查找方法，每次字段访问按 (类名, 字段名) 查找字段。
+//! `Symbol` 是共享的不可变字符串，复制只增加引用计数，不分配内存。
+//!
+//! ## 学习要点
+//! - 真实 JVM 的常量池中的名字也是共享的符号（HotSpot 的 `Symbol*`），比较和复制都很便宜
+//! - `Symbol` 可以当作 `&str` 使用（`Deref`），在以 `Symbol` 为键的 HashMap 中也可以直接用 `&str` 查找（`Borrow`）
+//! - 只在创建时（加载类、解析常量池）分配一次，之后的热路径上只复制引用
+
+use std::borrow::Borrow;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+use std::sync::{Arc, OnceLock};
+
+/// 共享的不可变字符串（类名、方法名、字段名、描述符）
+#[derive(Clone, PartialOrd, Ord)]
+pub struct Symbol(Arc<str>);
+
+impl Symbol {
+    /// 创建符号（分配一次）
+    pub fn new(text: &str) -> Self {
+        Symbol(Arc::from(text))
+    }
+
+    /// 符号的文本
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Deref for Symbol {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Borrow<str> for Symbol {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for Symbol {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+// 相等和哈希都按文本计算，与 `str` 一致（`Borrow<str>` 的要求）；同一份数据直接比较指针
+impl PartialEq for Symbol {
+    fn eq(&self, other: &Symbol) -> bool {
+        Arc::ptr_eq(&self.0, &other.0) || self.0 == other.0
+    }
+}
+
+impl Eq for Symbol {}
+
+impl Hash for Symbol {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
+    }
+}
+
+impl PartialEq<str> for Symbol {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for Symbol {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl PartialEq<String> for Symbol {
+    fn eq(&self, other: &String) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<Symbol> for str {
+    fn eq(&self, other: &Symbol) -> bool {
+        self == other.as_str()
+    }
+}
+
+impl PartialEq<Symbol> for &str {
+    fn eq(&self, other: &Symbol) -> bool {
+        *self == other.as_str()
+    }
+}
+
+impl PartialEq<Symbol> for String {
+    fn eq(&self, other: &Symbol) -> bool {
+        self == other.as_str()
+    }
+}
+
+impl From<&str> for Symbol {
+    fn from(text: &str) -> Self {
+        Symbol::new(text)
+    }
+}
+
+impl From<String> for Symbol {
+    fn from(text: String) -> Self {
+        Symbol(Arc::from(text))
+    }
+}
+
+impl From<&String> for Symbol {
+    fn from(text: &String) -> Self {
+        Symbol::new(text)
+    }
+}
+
+impl From<&Symbol> for Symbol {
+    fn from(symbol: &Symbol) -> Self {
+        symbol.clone()
+    }
+}
+
+impl From<Symbol> for String {
+    fn from(symbol: Symbol) -> Self {
+        symbol.as_str().to_string()
+    }
+}
+
+// 空符号全局共享一份，新建栈帧时的默认方法名不分配内存
+impl Default for Symbol {
+    fn default() -> Self {
+        static EMPTY: OnceLock<Symbol> = OnceLock::new();
+        EMPTY.get_or_init(|| Symbol::new("")).clone()
+    }
+}
+
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl fmt::Debug for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&*self.0, f)
+    }
+}
@@ -6,8 +6,11 @@
 //! - 线程私有数据包括：虚拟机栈、本地方法栈、程序计数器
 //! - 每个方法调用都会创建一个新的栈帧
 //! - 方法返回时弹出栈帧
+//! - 程序计数器保存在每个栈帧里：调用时调用者的 pc 停在调用点之后，返回后直接从那里继续
+//! - 虚拟机栈的深度有上限，无限递归得到 StackOverflowError 而不是耗尽内存
 
 use super::Frame;
+use crate::runtime::exception::{FrameInfo, JavaException};
 use crate::Result;
 use anyhow::anyhow;
 
@@ -16,24 +19,59 @@ use anyhow::anyhow;
 pub struct JvmThread {
     /// 虚拟机栈（栈帧列表）
     stack: Vec<Frame>,
-
-    /// 程序计数器 (PC Register) - 线程级别
-    /// 指向当前正在执行的字节码指令地址
-    pub pc: usize,
+    /// 栈帧数上限
+    max_frames: usize,
 }
 
+/// 默认的栈帧数上限
+pub const DEFAULT_MAX_FRAMES: usize = 4096;
+
 impl JvmThread {
     /// 创建新线程
     pub fn new() -> Self {
+        Self::with_max_frames(DEFAULT_MAX_FRAMES)
+    }
+
+    /// 创建栈帧数上限为 `max_frames` 的线程
+    pub fn with_max_frames(max_frames: usize) -> Self {
         JvmThread {
             stack: Vec::new(),
-            pc: 0,
+            max_frames,
         }
     }
 
-    /// 压入新的栈帧
-    pub fn push_frame(&mut self, frame: Frame) {
+    /// 压入新的栈帧，栈满时抛出 StackOverflowError
+    pub fn push_frame(&mut self, frame: Frame) -> Result<()> {
+        if self.stack.len() >= self.max_frames {
+            return Err(self.stack_overflow());
+        }
         self.stack.push(frame);
+        Ok(())
+    }
+
+    /// 压入被调用方法的栈帧，调用者返回后从 `resume_pc` 继续
+    ///
+    /// 栈满时调用者的 pc 保持不变，StackOverflowError 从调用指令处抛出
+    pub fn push_callee(&mut self, frame: Frame, resume_pc: usize) -> Result<()> {
+        if self.stack.len() >= self.max_frames {
+            return Err(self.stack_overflow());
+        }
+        self.current_frame_mut()?.pc = resume_pc;
+        self.stack.push(frame);
+        Ok(())
+    }
+
+    fn stack_overflow(&self) -> anyhow::Error {
+        JavaException::new(
+            "java/lang/StackOverflowError",
+            format!("stack depth exceeded max_frames={}", self.max_frames),
+        )
+        .into()
+    }
+
+    /// 栈帧数上限
+    pub fn max_frames(&self) -> usize {
+        self.max_frames
     }
 
     /// 弹出栈帧
@@ -43,6 +81,11 @@ impl JvmThread {
             .ok_or_else(|| anyhow!("Stack is empty"))
     }
 
+    /// 弹出栈帧直到栈深度为 `depth`（运行出错后丢弃未完成的栈帧）
+    pub fn unwind_to(&mut self, depth: usize) {
+        self.stack.truncate(depth);
+    }
+
     /// 获取当前栈帧
     pub fn current_frame(&self) -> Result<&Frame> {
         self.stack.last().ok_or_else(|| anyhow!("Stack is empty"))
@@ -60,6 +103,25 @@ impl JvmThread {
         self.stack.len()
     }
 
+    /// 调用栈中的所有栈帧，栈顶（正在执行的方法）在前，每个栈帧带着自己的 pc
+    ///
+    /// `.rev()` 从入口方法开始遍历
+    pub fn frames(&self) -> impl DoubleEndedIterator<Item = &Frame> + ExactSizeIterator {
+        self.stack.iter().rev()
+    }
+
+    /// 调用栈中所有栈帧的可变视图（GC 改写引用、剖析重置方法编号时使用）
+    pub(crate) fn frames_mut(&mut self) -> impl Iterator<Item = &mut Frame> {
+        self.stack.iter_mut().rev()
+    }
+
+    /// 调用栈的快照，栈顶在前
+    ///
+    /// 线程不知道类的元数据，快照中没有源文件和行号；需要它们时用 `Interpreter::stack_trace`
+    pub fn backtrace(&self) -> Vec<FrameInfo> {
+        self.frames().map(Frame::info).collect()
+    }
+
     /// 获取当前方法的字节码
     pub fn current_code(&self) -> Result<&[u8]> {
         Ok(&self.current_frame()?.code)
@@ -0,0 +1,89 @@
+//! 测试抽象类：不能实例化，通过抽象类型调用时分派到子类的实现，
+//! 分派到抽象方法时抛出 AbstractMethodError
+
+use rsjvm::classfile::access_flags::{ACC_PUBLIC, ACC_STATIC};
+use rsjvm::classfile::builder::ClassFileBuilder;
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::{Jvm, JvmBuilder, Result};
+
+/// Mute extends Animal，没有实现 speak()（javac 不允许，用 ClassFileBuilder 构造）；
+/// `static Object create()` 创建一个 Mute，`static Object animal()` 尝试直接创建 Animal
+fn load_mute(jvm: &mut Jvm) -> Result<()> {
+    jvm.load_class_by_name("Animal")?;
+    let mute = ClassFileBuilder::new("Mute")
+        .super_class("Animal")
+        .method(ACC_PUBLIC, "<init>", "()V", 1, 1, |code| {
+            code.aload(0).invokespecial("Animal", "<init>", "()V").vreturn();
+        })
+        .method(ACC_PUBLIC | ACC_STATIC, "create", "()Ljava/lang/Object;", 2, 0, |code| {
+            code.new_object("Mute")
+                .dup()
+                .invokespecial("Mute", "<init>", "()V")
+                .areturn();
+        })
+        .method(ACC_PUBLIC | ACC_STATIC, "animal", "()Ljava/lang/Object;", 2, 0, |code| {
+            code.new_object("Animal")
+                .dup()
+                .invokespecial("Animal", "<init>", "()V")
+                .areturn();
+        })
+        .build()?;
+    jvm.load_class(mute)?;
+    Ok(())
+}
+
+#[test]
+fn test_call_abstract_method_through_base_type() -> Result<()> {
+    let mut jvm = JvmBuilder::new().class_path("examples").build();
+    let speak: i32 = jvm.call_static_typed("AbstractDispatch", "speakViaBase", "()I", ())?;
+    assert_eq!(speak, 3);
+    let twice: i32 = jvm.call_static_typed("AbstractDispatch", "speakTwiceViaBase", "()I", ())?;
+    assert_eq!(twice, 6);
+    Ok(())
+}
+
+#[test]
+fn test_new_abstract_class_fails() -> Result<()> {
+    let mut jvm = JvmBuilder::new().class_path("examples").build();
+    load_mute(&mut jvm)?;
+    let err = jvm.call_static("Mute", "animal", "()Ljava/lang/Object;", &[]).unwrap_err();
+    assert!(
+        err.to_string().contains("java/lang/InstantiationError: Animal is an abstract class"),
+        "{}",
+        err
+    );
+    Ok(())
+}
+
+#[test]
+fn test_dispatch_to_abstract_method_is_abstract_method_error() -> Result<()> {
+    let mut jvm = JvmBuilder::new().class_path("examples").build();
+    load_mute(&mut jvm)?;
+    let mute = jvm.call_static("Mute", "create", "()Ljava/lang/Object;", &[])?;
+    let mute = mute.expect("create returns the object");
+
+    // 虚方法查找走到 Animal.speak() 才停下：异常指明对象的类和方法签名
+    let err = jvm
+        .interpreter_mut()
+        .execute_method_with_args("Animal", "speakTwice:()I", vec![mute.clone()])
+        .unwrap_err();
+    assert!(err.to_string().contains("java/lang/AbstractMethodError: Mute.speak()I"), "{}", err);
+
+    // 是普通的 Java 异常，可以被 catch
+    let result = jvm.call_static("AbstractDispatch", "speakOrMinusOne", "(LAnimal;)I", &[mute])?;
+    assert!(matches!(result, Some(JvmValue::Int(-1))), "{:?}", result);
+    Ok(())
+}
+
+#[test]
+fn test_invoke_abstract_method_directly() -> Result<()> {
+    let mut jvm = JvmBuilder::new().class_path("examples").build();
+    load_mute(&mut jvm)?;
+    let mute = jvm.call_static("Mute", "create", "()Ljava/lang/Object;", &[])?.unwrap();
+    let err = jvm
+        .interpreter_mut()
+        .execute_method_with_args("Animal", "speak:()I", vec![mute])
+        .unwrap_err();
+    assert_eq!(err.to_string(), "java/lang/AbstractMethodError: Animal.speak()I");
+    Ok(())
+}
@@ -0,0 +1,194 @@
+//! 测试访问检查：private/protected/包访问成员的可见性，以及 final 字段只能在初始化方法中赋值
+//!
+//! javac 不会生成越权访问的字节码，这里用 ClassFileBuilder 直接构造
+
+use rsjvm::classfile::access_flags::{
+    ACC_FINAL, ACC_PRIVATE, ACC_PROTECTED, ACC_PUBLIC, ACC_STATIC,
+};
+use rsjvm::classfile::builder::{ClassFileBuilder, CodeBuilder};
+use rsjvm::interpreter::{Interpreter, InterpreterOptions};
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::Result;
+
+const PUBLIC_STATIC: u16 = ACC_PUBLIC | ACC_STATIC;
+
+fn constructor(code: &mut CodeBuilder, super_class: &str) {
+    code.aload(0).invokespecial(super_class, "<init>", "()V").vreturn();
+}
+
+/// acc/Target：各种可见性的方法和字段
+fn target() -> ClassFileBuilder {
+    ClassFileBuilder::new("acc/Target")
+        .field(ACC_PRIVATE, "hidden", "I")
+        .field(ACC_PUBLIC | ACC_FINAL, "fixed", "I")
+        .field(PUBLIC_STATIC | ACC_FINAL, "LIMIT", "I")
+        .method(ACC_PUBLIC, "<init>", "()V", 2, 1, |code| {
+            code.aload(0).invokespecial("java/lang/Object", "<init>", "()V");
+            code.aload(0).iconst(7).putfield("acc/Target", "fixed", "I").vreturn();
+        })
+        .method(ACC_STATIC, "<clinit>", "()V", 1, 0, |code| {
+            code.iconst(5).putstatic("acc/Target", "LIMIT", "I").vreturn();
+        })
+        .method(PUBLIC_STATIC, "open", "()I", 1, 0, |code| {
+            code.iconst(1).ireturn();
+        })
+        .method(ACC_PRIVATE | ACC_STATIC, "secret", "()I", 1, 0, |code| {
+            code.iconst(2).ireturn();
+        })
+        .method(ACC_PROTECTED | ACC_STATIC, "family", "()I", 1, 0, |code| {
+            code.iconst(3).ireturn();
+        })
+        .method(ACC_STATIC, "pkg", "()I", 1, 0, |code| {
+            code.iconst(4).ireturn();
+        })
+        .method(ACC_PRIVATE, "peek", "()I", 1, 1, |code| {
+            code.iconst(6).ireturn();
+        })
+        // 声明类中的普通方法也不能写 final 字段
+        .method(ACC_PUBLIC, "reset", "()V", 2, 1, |code| {
+            code.aload(0).iconst(0).putfield("acc/Target", "fixed", "I").vreturn();
+        })
+}
+
+/// 调用者类：每个方法都是 `static int name()`，调用 acc/Target 的一个成员
+fn caller(name: &str, super_class: &str) -> ClassFileBuilder {
+    let builder = ClassFileBuilder::new(name)
+        .super_class(super_class)
+        .method(ACC_PUBLIC, "<init>", "()V", 1, 1, |code| constructor(code, super_class));
+    ["open", "secret", "family", "pkg"].into_iter().fold(builder, |builder, method| {
+        builder.method(PUBLIC_STATIC, method, "()I", 1, 0, |code| {
+            code.invokestatic("acc/Target", method, "()I").ireturn();
+        })
+    })
+    .method(PUBLIC_STATIC, "hidden", "()I", 2, 0, |code| {
+        new_target(code).getfield("acc/Target", "hidden", "I").ireturn();
+    })
+    .method(PUBLIC_STATIC, "fixed", "()I", 2, 0, |code| {
+        new_target(code).getfield("acc/Target", "fixed", "I").ireturn();
+    })
+    .method(PUBLIC_STATIC, "limit", "()I", 1, 0, |code| {
+        code.getstatic("acc/Target", "LIMIT", "I").ireturn();
+    })
+    .method(PUBLIC_STATIC, "peek", "()I", 2, 0, |code| {
+        new_target(code).invokevirtual("acc/Target", "peek", "()I").ireturn();
+    })
+    .method(PUBLIC_STATIC, "writeFixed", "()I", 3, 0, |code| {
+        new_target(code).iconst(1).putfield("acc/Target", "fixed", "I").iconst(0).ireturn();
+    })
+    .method(PUBLIC_STATIC, "writeLimit", "()I", 1, 0, |code| {
+        code.iconst(1).putstatic("acc/Target", "LIMIT", "I").iconst(0).ireturn();
+    })
+    .method(PUBLIC_STATIC, "reset", "()I", 2, 0, |code| {
+        new_target(code).invokevirtual("acc/Target", "reset", "()V").iconst(0).ireturn();
+    })
+}
+
+fn new_target<'a, 'b>(code: &'a mut CodeBuilder<'b>) -> &'a mut CodeBuilder<'b> {
+    code.new_object("acc/Target")
+        .dup()
+        .invokespecial("acc/Target", "<init>", "()V")
+}
+
+fn interpreter(check_access: bool) -> Result<Interpreter> {
+    let mut interpreter = Interpreter::new_with_options(InterpreterOptions {
+        check_access,
+        ..Default::default()
+    });
+    interpreter.load_class(target().build()?)?;
+    interpreter.load_class(caller("acc/Neighbor", "java/lang/Object").build()?)?;
+    interpreter.load_class(caller("other/Sub", "acc/Target").build()?)?;
+    interpreter.load_class(caller("other/Stranger", "java/lang/Object").build()?)?;
+    Ok(interpreter)
+}
+
+/// 调用 `class_name.method()I`：成功时返回结果，失败时返回错误信息（含未捕获异常的栈轨迹）
+fn call(interpreter: &mut Interpreter, class_name: &str, method: &str) -> std::result::Result<i32, String> {
+    match interpreter.invoke_static(class_name, method, "()I", vec![]) {
+        Ok(Some(JvmValue::Int(value))) => Ok(value),
+        Ok(other) => panic!("unexpected result {:?}", other),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+#[test]
+fn test_method_visibility() -> Result<()> {
+    let mut interpreter = interpreter(true)?;
+    // public：任何类都可以
+    for class_name in ["acc/Neighbor", "other/Sub", "other/Stranger"] {
+        assert_eq!(call(&mut interpreter, class_name, "open"), Ok(1));
+    }
+
+    // private：只有声明它的类
+    let err = call(&mut interpreter, "acc/Neighbor", "secret").unwrap_err();
+    assert!(
+        err.contains("java/lang/IllegalAccessError: class acc/Neighbor tried to access private method acc/Target.secret()I"),
+        "{}",
+        err
+    );
+
+    // protected：同一个包或子类
+    assert_eq!(call(&mut interpreter, "acc/Neighbor", "family"), Ok(3));
+    assert_eq!(call(&mut interpreter, "other/Sub", "family"), Ok(3));
+    let err = call(&mut interpreter, "other/Stranger", "family").unwrap_err();
+    assert!(err.contains("tried to access protected method acc/Target.family()I"), "{}", err);
+
+    // 包访问：只有同一个包，子类也不行
+    assert_eq!(call(&mut interpreter, "acc/Neighbor", "pkg"), Ok(4));
+    let err = call(&mut interpreter, "other/Sub", "pkg").unwrap_err();
+    assert!(err.contains("class other/Sub tried to access package-private method acc/Target.pkg()I"), "{}", err);
+
+    // invokevirtual 调用其他类的 private 实例方法
+    let err = call(&mut interpreter, "other/Stranger", "peek").unwrap_err();
+    assert!(err.contains("tried to access private method acc/Target.peek()I"), "{}", err);
+    Ok(())
+}
+
+#[test]
+fn test_field_visibility() -> Result<()> {
+    let mut interpreter = interpreter(true)?;
+    let err = call(&mut interpreter, "acc/Neighbor", "hidden").unwrap_err();
+    assert!(
+        err.contains("java/lang/IllegalAccessError: class acc/Neighbor tried to access private field acc/Target.hidden"),
+        "{}",
+        err
+    );
+    Ok(())
+}
+
+#[test]
+fn test_final_field_writes_only_in_initializers() -> Result<()> {
+    let mut interpreter = interpreter(true)?;
+    // <init> 和 <clinit> 中的赋值是允许的
+    assert_eq!(call(&mut interpreter, "other/Stranger", "fixed"), Ok(7));
+    assert_eq!(call(&mut interpreter, "other/Stranger", "limit"), Ok(5));
+
+    let err = call(&mut interpreter, "other/Stranger", "writeFixed").unwrap_err();
+    assert!(
+        err.contains(
+            "java/lang/IllegalAccessError: Update to non-static final field acc/Target.fixed attempted \
+             from a different method (other/Stranger.writeFixed) than the initializer method <init>"
+        ),
+        "{}",
+        err
+    );
+    let err = call(&mut interpreter, "acc/Neighbor", "writeLimit").unwrap_err();
+    assert!(err.contains("Update to static final field acc/Target.LIMIT"), "{}", err);
+
+    // 声明类自己的普通方法也不行
+    let err = call(&mut interpreter, "acc/Neighbor", "reset").unwrap_err();
+    assert!(err.contains("(acc/Target.reset) than the initializer method <init>"), "{}", err);
+    Ok(())
+}
+
+#[test]
+fn test_access_checks_can_be_disabled() -> Result<()> {
+    let mut interpreter = interpreter(false)?;
+    assert_eq!(call(&mut interpreter, "other/Stranger", "secret"), Ok(2));
+    assert_eq!(call(&mut interpreter, "other/Stranger", "family"), Ok(3));
+    assert_eq!(call(&mut interpreter, "other/Sub", "pkg"), Ok(4));
+    assert_eq!(call(&mut interpreter, "other/Stranger", "hidden"), Ok(0));
+    assert_eq!(call(&mut interpreter, "other/Stranger", "writeFixed"), Ok(0));
+    assert_eq!(call(&mut interpreter, "other/Stranger", "writeLimit"), Ok(0));
+    assert_eq!(call(&mut interpreter, "other/Stranger", "limit"), Ok(1));
+    Ok(())
+}
@@ -0,0 +1,122 @@
+//! 测试 SourceFile、Exceptions、ConstantValue、Signature 属性的解析
+
+use rsjvm::classfile::attribute::Attribute;
+use rsjvm::classfile::ClassFile;
+use rsjvm::interpreter::Interpreter;
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::runtime::metaspace::ClassState;
+use rsjvm::Result;
+use std::process::Command;
+
+const CLASS: &str = "examples/Attributes.class";
+
+/// 按名字解码某个方法或字段的全部属性
+fn member_attributes(class_file: &ClassFile, member: &str) -> Result<Vec<Attribute>> {
+    let cp = &class_file.constant_pool;
+    let attributes = class_file
+        .methods
+        .iter()
+        .map(|m| (m.name_index, &m.attributes))
+        .chain(class_file.fields.iter().map(|f| (f.name_index, &f.attributes)))
+        .find(|(name_index, _)| cp.get_utf8(*name_index).unwrap() == member)
+        .map(|(_, attributes)| attributes)
+        .unwrap_or_else(|| panic!("member {} not found", member));
+    attributes.iter().map(|a| a.parse(cp)).collect()
+}
+
+#[test]
+fn test_typed_attributes() -> Result<()> {
+    let class_file = ClassFile::from_file(CLASS)?;
+    let cp = &class_file.constant_pool;
+
+    let class_attributes: Vec<Attribute> =
+        class_file.attributes.iter().map(|a| a.parse(cp)).collect::<Result<_>>()?;
+    assert!(class_attributes
+        .iter()
+        .any(|a| matches!(a, Attribute::SourceFile(name) if name == "Attributes.java")));
+    assert!(class_attributes.iter().any(|a| matches!(
+        a,
+        Attribute::Signature(s) if s == "<T::Ljava/lang/Comparable<TT;>;>Ljava/lang/Object;"
+    )));
+
+    let risky = member_attributes(&class_file, "risky")?;
+    let thrown = risky.iter().find_map(|a| match a {
+        Attribute::Exceptions(list) => Some(list.clone()),
+        _ => None,
+    });
+    assert_eq!(
+        thrown,
+        Some(vec!["java/io/IOException".to_string(), "java/lang/InterruptedException".to_string()])
+    );
+    assert!(risky.iter().any(|a| matches!(a, Attribute::Code(_))));
+
+    let wrap = member_attributes(&class_file, "wrap")?;
+    assert!(wrap.iter().any(|a| matches!(
+        a,
+        Attribute::Signature(s) if s == "<E:Ljava/lang/Object;>(TE;)Ljava/util/List<TE;>;"
+    )));
+
+    let names = member_attributes(&class_file, "names")?;
+    assert!(matches!(
+        names.as_slice(),
+        [Attribute::Signature(s)] if s == "Ljava/util/List<Ljava/lang/String;>;"
+    ));
+
+    let magic = member_attributes(&class_file, "MAGIC")?;
+    let index = match magic.as_slice() {
+        [Attribute::ConstantValue(index)] => *index,
+        other => panic!("MAGIC 期望只有 ConstantValue, 实际: {:?}", other),
+    };
+    assert!(matches!(cp.get(index)?, rsjvm::classfile::constant_pool::ConstantPoolEntry::Integer(42)));
+    Ok(())
+}
+
+#[test]
+fn test_malformed_constant_value_is_an_error() -> Result<()> {
+    let mut class_file = ClassFile::from_file(CLASS)?;
+    let attr = &mut class_file.fields[0].attributes[0];
+    attr.info.push(0);
+    let err = attr.parse(&class_file.constant_pool).unwrap_err();
+    assert_eq!(err.to_string(), "ConstantValue attribute must be 2 bytes long, got 3");
+    Ok(())
+}
+
+#[test]
+fn test_constant_values_are_set_before_clinit() -> Result<()> {
+    let mut interpreter = Interpreter::new();
+    interpreter.load_class(ClassFile::from_file(CLASS)?)?;
+
+    let class = interpreter.metaspace.get_class("Attributes")?;
+    assert_eq!(class.state, ClassState::Loaded);
+    assert_eq!(class.source_file(), Some("Attributes.java"));
+    assert!(matches!(class.static_fields.get("MAGIC"), Some(JvmValue::Int(42))));
+    assert!(matches!(class.static_fields.get("BIG"), Some(JvmValue::Long(v)) if *v == 1 << 40));
+    assert!(matches!(class.static_fields.get("RATIO"), Some(JvmValue::Double(v)) if *v == 0.5));
+    // 非常量字段要等 <clinit> 赋值
+    assert!(!class.static_fields.contains_key("initialized"));
+
+    let result = interpreter.invoke_static("Attributes", "initialized", "()I", vec![])?;
+    assert!(matches!(result, Some(JvmValue::Int(7))), "{:?}", result);
+    let class = interpreter.metaspace.get_class("Attributes")?;
+    assert_eq!(class.state, ClassState::Initialized);
+    assert!(matches!(class.static_fields.get("MAGIC"), Some(JvmValue::Int(42))));
+    Ok(())
+}
+
+#[test]
+fn test_parse_cli_shows_throws_and_source_file() {
+    let output = Command::new(env!("CARGO_BIN_EXE_rsjvm"))
+        .args(["parse", CLASS])
+        .output()
+        .expect("failed to run rsjvm");
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("源文件: Attributes.java"), "{}", stdout);
+    assert!(
+        stdout.contains(
+            "  [1] risky : ()V\n      throws: java/io/IOException, java/lang/InterruptedException\n"
+        ),
+        "{}",
+        stdout
+    );
+}
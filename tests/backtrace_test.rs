@@ -0,0 +1,71 @@
+//! 测试线程栈的只读视图：按栈顶在前遍历栈帧、栈帧快照和栈帧数上限
+
+use rsjvm::classfile::ClassFile;
+use rsjvm::interpreter::stepping::RunOutcome;
+use rsjvm::interpreter::Interpreter;
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::runtime::{Frame, JvmThread};
+use rsjvm::Result;
+
+fn ints(values: &[JvmValue]) -> Vec<i32> {
+    values
+        .iter()
+        .map(|v| match v {
+            JvmValue::Int(i) => *i,
+            other => panic!("expected int, got {:?}", other),
+        })
+        .collect()
+}
+
+#[test]
+fn test_backtrace_during_nested_invokestatic() -> Result<()> {
+    let mut interpreter = Interpreter::new();
+    interpreter.load_class(ClassFile::from_file("examples/Calculator.class")?)?;
+    // average → percent → divide，停在 divide 的 idiv 之前
+    interpreter.set_breakpoint("Calculator", "divide:(II)I", 2);
+    interpreter.start_method("Calculator", "average:(II)I", vec![JvmValue::Int(7), JvmValue::Int(2)])?;
+    assert!(matches!(interpreter.resume()?, RunOutcome::Breakpoint(_)));
+
+    let backtrace = interpreter.thread.backtrace();
+    let summary: Vec<_> = backtrace
+        .iter()
+        .map(|f| (f.method_name.as_str(), f.descriptor.as_str(), f.pc, f.stack_size, f.max_locals))
+        .collect();
+    // 调用者的 pc 停在调用指令之后，参数已经从操作数栈弹出
+    assert_eq!(
+        summary,
+        [
+            ("divide", "(II)I", 2, 2, 2),
+            ("percent", "(II)I", 8, 0, 2),
+            ("average", "(II)I", 5, 0, 2),
+        ]
+    );
+    assert!(backtrace.iter().all(|f| f.class_name == "Calculator" && f.line.is_none()));
+
+    // 带源文件和行号的版本
+    let trace = interpreter.stack_trace();
+    assert_eq!(trace[0].to_string(), "Calculator.divide(II)I pc=2 (Calculator.java:23)");
+    assert_eq!(trace[0].stack_size, 2);
+
+    // frames() 栈顶在前，从后往前是入口方法
+    let top = interpreter.thread.frames().next().unwrap();
+    assert_eq!(ints(top.locals()), [700, 2]);
+    assert_eq!(ints(top.operands()), [700, 2]);
+    let entry = interpreter.thread.frames().next_back().unwrap();
+    assert_eq!(ints(entry.locals()), [7, 2]);
+    assert_eq!(interpreter.thread.frames().len(), interpreter.thread.stack_depth());
+
+    assert!(matches!(interpreter.resume()?, RunOutcome::Finished(Some(JvmValue::Int(3)))));
+    assert!(interpreter.thread.backtrace().is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_push_frame_past_max_frames() {
+    let mut thread = JvmThread::with_max_frames(2);
+    thread.push_frame(Frame::new(0, 0)).unwrap();
+    thread.push_frame(Frame::new(0, 0)).unwrap();
+    let err = thread.push_frame(Frame::new(0, 0)).unwrap_err();
+    assert_eq!(err.to_string(), "java/lang/StackOverflowError: stack depth exceeded max_frames=2");
+    assert_eq!(thread.backtrace().len(), 2);
+}
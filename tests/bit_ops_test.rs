@@ -0,0 +1,80 @@
+//! 测试位运算、移位和 irem 指令
+//!
+//! 期望值是真实 JVM 的输出
+
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::{Jvm, JvmBuilder, Result};
+
+fn jvm() -> Jvm {
+    JvmBuilder::new().class_path("examples").build()
+}
+
+#[test]
+fn test_int_bit_ops() -> Result<()> {
+    let mut jvm = jvm();
+    let cases = [
+        ("shl", 1, 35, 8), // 移位量 & 0x1f：35 → 3
+        ("shl", 1, 31, i32::MIN),
+        ("shr", -7, 33, -4),
+        ("shr", -1, 31, -1),
+        ("ushr", -1, 28, 15),
+        ("ushr", -1, 32, -1),
+        ("and", -6, 0xff, 250),
+        ("or", 0x0f, -256, -241),
+        ("xor", -1, 0x55, -86),
+        ("rem", -7, 2, -1), // 余数符号跟随被除数
+        ("rem", 7, -2, 1),
+        ("rem", i32::MIN, -1, 0),
+    ];
+    for (method, a, b, expected) in cases {
+        let result: i32 = jvm.call_static_typed("BitOps", method, "(II)I", (a, b))?;
+        assert_eq!(result, expected, "{}({}, {})", method, a, b);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_long_shifts_take_int_amount() -> Result<()> {
+    let mut jvm = jvm();
+    let cases = [
+        ("lshl", 1, 65, 2), // 移位量 & 0x3f：65 → 1
+        ("lshl", 1, 63, i64::MIN),
+        ("lshr", -1, 70, -1),
+        ("lushr", -8, 60, 15),
+        ("lushr", -1, 64, -1),
+    ];
+    for (method, a, n, expected) in cases {
+        let result: i64 = jvm.call_static_typed("BitOps", method, "(JI)J", (a, n))?;
+        assert_eq!(result, expected, "{}({}, {})", method, a, n);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_long_bit_ops() -> Result<()> {
+    let mut jvm = jvm();
+    let cases = [
+        ("land", -1i64, 0xffff_0000_0000, 0xffff_0000_0000),
+        ("lor", 1 << 40, 1, (1 << 40) | 1),
+        ("lxor", -1, i64::MAX, i64::MIN),
+    ];
+    for (method, a, b, expected) in cases {
+        let result: i64 = jvm.call_static_typed("BitOps", method, "(JJ)J", (a, b))?;
+        assert_eq!(result, expected, "{}({}, {})", method, a, b);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_hash_mixing() -> Result<()> {
+    let result: i32 = jvm().call_static_typed("BitOps", "hash", "(II)I", (-123456789, 10))?;
+    assert_eq!(result, 1125845679);
+    Ok(())
+}
+
+#[test]
+fn test_irem_by_zero_throws() {
+    let args = [JvmValue::Int(5), JvmValue::Int(0)];
+    let err = jvm().call_static("BitOps", "rem", "(II)I", &args).unwrap_err();
+    assert!(err.to_string().contains("ArithmeticException"), "{}", err);
+}
@@ -0,0 +1,67 @@
+//! 测试包装类：自动装箱/拆箱、Integer 缓存、按值比较和 parseLong
+
+use rsjvm::runtime::ObjRef;
+use rsjvm::{Jvm, JvmBuilder, Result};
+
+fn jvm() -> Jvm {
+    JvmBuilder::new().class_path("examples").build()
+}
+
+#[test]
+fn test_box_unbox_add() -> Result<()> {
+    let mut jvm = jvm();
+    let result: i32 = jvm.call_static_typed("Boxing", "boxUnboxAdd", "()I", ())?;
+    assert_eq!(result, 6);
+    Ok(())
+}
+
+#[test]
+fn test_integer_cache() -> Result<()> {
+    let mut jvm = jvm();
+    let descriptor = "(I)Ljava/lang/Integer;";
+    let a: ObjRef = jvm.call_static_typed("Boxing", "box", descriptor, (100,))?;
+    let b: ObjRef = jvm.call_static_typed("Boxing", "box", descriptor, (100,))?;
+    assert_eq!(a, b);
+    let a: ObjRef = jvm.call_static_typed("Boxing", "box", descriptor, (1000,))?;
+    let b: ObjRef = jvm.call_static_typed("Boxing", "box", descriptor, (1000,))?;
+    assert_ne!(a, b);
+
+    let shared: bool = jvm.call_static_typed("Boxing", "smallValuesShared", "()Z", ())?;
+    assert!(shared);
+    let shared: bool = jvm.call_static_typed("Boxing", "largeValuesShared", "()Z", ())?;
+    assert!(!shared);
+    let equal: bool = jvm.call_static_typed("Boxing", "equalsByValue", "()Z", ())?;
+    assert!(equal);
+    Ok(())
+}
+
+#[test]
+fn test_other_wrappers() -> Result<()> {
+    let mut jvm = jvm();
+    let long: i64 = jvm.call_static_typed("Boxing", "longs", "()J", ())?;
+    assert_eq!(long, 42);
+    let double: f64 = jvm.call_static_typed("Boxing", "doubles", "()D", ())?;
+    assert_eq!(double, 2.5);
+    let boolean: bool = jvm.call_static_typed("Boxing", "booleans", "()Z", ())?;
+    assert!(boolean);
+    let char: char = jvm.call_static_typed("Boxing", "chars", "()C", ())?;
+    assert_eq!(char, 'b');
+    let text: String = jvm.call_static_typed("Boxing", "describe", "()Ljava/lang/String;", ())?;
+    assert_eq!(text, "-70.5falsez");
+    Ok(())
+}
+
+#[test]
+fn test_parse_long() -> Result<()> {
+    let mut jvm = jvm();
+    let descriptor = "(Ljava/lang/String;)J";
+    let value: i64 = jvm.call_static_typed("Boxing", "parseLong", descriptor, ("-9000000000",))?;
+    assert_eq!(value, -9_000_000_000);
+
+    let err = jvm
+        .call_static_typed::<i64>("Boxing", "parseLong", descriptor, ("12L",))
+        .unwrap_err();
+    let message = format!("{:#}", err);
+    assert!(message.contains("java/lang/NumberFormatException: For input string: \"12L\""), "{}", message);
+    Ok(())
+}
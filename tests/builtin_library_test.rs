@@ -0,0 +1,56 @@
+//! 测试内置类库：JDK 方法使用内置实现，没有实现的方法抛出 UnsatisfiedLinkError
+
+use rsjvm::{Jvm, JvmBuilder, Result};
+
+fn jvm() -> Jvm {
+    JvmBuilder::new().class_path("examples").build()
+}
+
+#[test]
+fn test_math_max_returns_value() -> Result<()> {
+    // 以前 Math.max 被跳过：两个参数留在栈上，返回的是 7 下面的 3
+    let mut jvm = jvm();
+    let max: i32 = jvm.call_static_typed("BuiltinLibrary", "maxOfConstants", "()I", ())?;
+    assert_eq!(max, 7);
+    let abs: i32 = jvm.call_static_typed("BuiltinLibrary", "absPlusOne", "(I)I", (-41,))?;
+    assert_eq!(abs, 42);
+    let min: i64 = jvm.call_static_typed("BuiltinLibrary", "longMin", "(JJ)J", (5i64, -1i64 << 40))?;
+    assert_eq!(min, -1 << 40);
+    Ok(())
+}
+
+#[test]
+fn test_parse_int() -> Result<()> {
+    let mut jvm = jvm();
+    let value: i32 = jvm.call_static_typed("BuiltinLibrary", "parse", "(Ljava/lang/String;)I", ("-123",))?;
+    assert_eq!(value, -123);
+    let value: i32 =
+        jvm.call_static_typed("BuiltinLibrary", "parseOrDefault", "(Ljava/lang/String;)I", ("12x",))?;
+    assert_eq!(value, -1);
+
+    let err = jvm
+        .call_static_typed::<i32>("BuiltinLibrary", "parse", "(Ljava/lang/String;)I", ("abc",))
+        .unwrap_err();
+    let message = format!("{:#}", err);
+    assert!(message.contains("java/lang/NumberFormatException: For input string: \"abc\""), "{}", message);
+    Ok(())
+}
+
+#[test]
+fn test_exception_constructor_sets_message() -> Result<()> {
+    let mut jvm = jvm();
+    let message: String = jvm.call_static_typed("BuiltinLibrary", "message", "()Ljava/lang/String;", ())?;
+    assert_eq!(message, "boom");
+    Ok(())
+}
+
+#[test]
+fn test_unimplemented_jdk_method_is_an_error() -> Result<()> {
+    let mut jvm = jvm();
+    let err = jvm
+        .call_static_typed::<()>("BuiltinLibrary", "unimplemented", "()V", ())
+        .unwrap_err();
+    let message = format!("{:#}", err);
+    assert!(message.contains("java/lang/UnsatisfiedLinkError: java.lang.Thread.yield()V"), "{}", message);
+    Ok(())
+}
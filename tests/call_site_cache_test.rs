@@ -0,0 +1,73 @@
+//! 测试 invokestatic 调用点缓存：每个调用点只解析一次，之后直接调用缓存的方法
+
+use rsjvm::{Jvm, JvmBuilder, Result};
+
+fn jvm() -> Jvm {
+    JvmBuilder::new().class_path("examples").build()
+}
+
+fn call_site_hits(jvm: &Jvm) -> usize {
+    jvm.interpreter().metaspace.resolution_stats().call_site_hits
+}
+
+#[test]
+fn test_call_site_resolved_once() -> Result<()> {
+    let mut jvm = jvm();
+    let sum: i32 = jvm.call_static_typed("ProfileDemo", "sumTo", "(I)I", (1000,))?;
+    assert_eq!(sum, 499500);
+    // 第一次调用解析并链接调用点，之后 999 次命中缓存
+    assert_eq!(call_site_hits(&jvm), 999);
+
+    let class = jvm.interpreter().metaspace.get_class("ProfileDemo")?;
+    let sites: Vec<_> = class.runtime_pool.static_call_sites.values().collect();
+    assert_eq!(sites.len(), 1);
+    assert_eq!(sites[0].owner, "ProfileDemo");
+    assert_eq!(sites[0].method.name, "add");
+    assert_eq!(sites[0].arg_count, 2);
+    Ok(())
+}
+
+#[test]
+fn test_inherited_static_method_is_cached_with_declaring_class() -> Result<()> {
+    let mut jvm = jvm();
+    for _ in 0..3 {
+        let result: i32 = jvm.call_static_typed("InheritUse", "callHelper", "()I", ())?;
+        assert_eq!(result, 42);
+    }
+    assert_eq!(call_site_hits(&jvm), 2);
+    let calls: i32 = jvm.call_static_typed("InheritUse", "readCalls", "()I", ())?;
+    assert_eq!(calls, 3);
+
+    let class = jvm.interpreter().metaspace.get_class("InheritUse")?;
+    let site = class.runtime_pool.static_call_sites.values().next().unwrap();
+    assert_eq!(site.method_ref.class_name, "InheritChild");
+    assert_eq!(site.owner, "InheritBase");
+    Ok(())
+}
+
+#[test]
+fn test_unloading_referenced_class_drops_call_site() -> Result<()> {
+    let mut jvm = jvm();
+    let _: i32 = jvm.call_static_typed("InheritUse", "callHelper", "()I", ())?;
+    jvm.interpreter_mut().unload_class("InheritChild")?;
+    let class = jvm.interpreter().metaspace.get_class("InheritUse")?;
+    assert!(class.runtime_pool.static_call_sites.is_empty());
+
+    // 再次执行时重新加载 InheritChild 并重新链接
+    let result: i32 = jvm.call_static_typed("InheritUse", "callHelper", "()I", ())?;
+    assert_eq!(result, 42);
+    assert_eq!(call_site_hits(&jvm), 0);
+    Ok(())
+}
+
+#[test]
+fn test_call_sites_survive_reset_run_state() -> Result<()> {
+    let mut jvm = jvm();
+    let _: i32 = jvm.call_static_typed("ProfileDemo", "sumTo", "(I)I", (10,))?;
+    jvm.interpreter_mut().reset_run_state();
+    // 调用点链接的是方法，不依赖静态字段和初始化状态
+    let sum: i32 = jvm.call_static_typed("ProfileDemo", "sumTo", "(I)I", (10,))?;
+    assert_eq!(sum, 45);
+    assert_eq!(call_site_hits(&jvm), 19);
+    Ok(())
+}
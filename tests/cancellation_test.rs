@@ -0,0 +1,74 @@
+//! 测试从其他线程取消正在运行的程序
+
+use rsjvm::interpreter::{Interpreter, InterpreterOptions};
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::runtime::ExecutionCancelled;
+use rsjvm::JvmBuilder;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+/// goto 0：永远不会结束
+const INFINITE_LOOP: [u8; 3] = [0xa7, 0x00, 0x00];
+
+#[test]
+fn test_cancel_infinite_loop_from_another_thread() {
+    let (token_tx, token_rx) = mpsc::channel();
+    let (result_tx, result_rx) = mpsc::channel();
+    thread::spawn(move || {
+        let mut interpreter = Interpreter::new_with_options(InterpreterOptions {
+            check_interval: 100,
+            ..Default::default()
+        });
+        token_tx.send(interpreter.cancellation_token()).unwrap();
+        let result = interpreter.execute_method_with_class(
+            "Spin",
+            "spin:()V",
+            &INFINITE_LOOP,
+            1,
+            1,
+        );
+        // 取消后线程栈已经清空，解释器可以继续使用
+        let depth = interpreter.thread.stack_depth();
+        result_tx.send((result, depth)).unwrap();
+    });
+
+    let token = token_rx.recv().unwrap();
+    thread::sleep(Duration::from_millis(50));
+    token.cancel();
+
+    let (result, depth) = result_rx
+        .recv_timeout(Duration::from_secs(10))
+        .expect("cancelled execution must terminate");
+    let err = result.expect_err("an infinite loop only ends by cancellation");
+    let cancelled = err
+        .downcast_ref::<ExecutionCancelled>()
+        .unwrap_or_else(|| panic!("expected ExecutionCancelled, got {:#}", err));
+    // 只在每 100 条指令时检查一次
+    assert!(cancelled.instructions > 0);
+    assert_eq!(cancelled.instructions % 100, 0);
+    assert_eq!(cancelled.frames.len(), 1);
+    assert_eq!(cancelled.frames[0].class_name, "Spin");
+    assert_eq!(depth, 0);
+    assert!(!token.is_cancelled(), "the request is consumed by the run it cancelled");
+}
+
+#[test]
+fn test_cancel_before_run_and_reuse() {
+    let mut jvm = JvmBuilder::new().class_path("examples").build();
+    let token = jvm.cancellation_token();
+    token.cancel();
+
+    let err = jvm.call_static("LongLoop", "sumLongs", "()J", &[]).unwrap_err();
+    let cancelled = err.downcast_ref::<ExecutionCancelled>().expect("cancelled");
+    assert_eq!(cancelled.instructions, 0);
+    assert!(
+        err.to_string().starts_with("execution cancelled after 0 instructions\n\tat LongLoop.sumLongs()J"),
+        "{}",
+        err
+    );
+
+    // 取消请求已被消耗，下一次运行正常完成
+    let result = jvm.call_static("LongLoop", "sumLongs", "()J", &[]).unwrap();
+    assert!(matches!(result, Some(JvmValue::Long(3000000499500))), "{:?}", result);
+}
@@ -0,0 +1,140 @@
+//! 测试 ClassFileBuilder：不依赖 JDK 在代码里构造 class
+
+use rsjvm::classfile::access_flags::{ACC_PUBLIC, ACC_STATIC};
+use rsjvm::classfile::builder::ClassFileBuilder;
+use rsjvm::classfile::constant_pool::ConstantPoolEntry;
+use rsjvm::classfile::ClassFile;
+use rsjvm::interpreter::Interpreter;
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::Result;
+
+const PUBLIC_STATIC: u16 = ACC_PUBLIC | ACC_STATIC;
+
+/// static int sum(int n) { int s = 0; for (int i = 1; i <= n; i++) s += i; return s; }
+/// static int twice(int n) { return sum(n) + sum(n); }
+fn sum_class() -> ClassFileBuilder {
+    ClassFileBuilder::new("Sum")
+        .field(PUBLIC_STATIC, "calls", "I")
+        .method(PUBLIC_STATIC, "sum", "(I)I", 2, 3, |code| {
+            let check = code.new_label();
+            let done = code.new_label();
+            code.getstatic("Sum", "calls", "I")
+                .iconst(1)
+                .iadd()
+                .putstatic("Sum", "calls", "I")
+                .iconst(0)
+                .istore(1)
+                .iconst(1)
+                .istore(2)
+                .bind(check)
+                .iload(2)
+                .iload(0)
+                .branch(0xa3, done) // if_icmpgt
+                .iload(1)
+                .iload(2)
+                .iadd()
+                .istore(1)
+                .iinc(2, 1)
+                .goto(check)
+                .bind(done)
+                .iload(1)
+                .ireturn();
+        })
+        .method(PUBLIC_STATIC, "twice", "(I)I", 2, 1, |code| {
+            code.iload(0)
+                .invokestatic("Sum", "sum", "(I)I")
+                .iload(0)
+                .invokestatic("Sum", "sum", "(I)I")
+                .iadd()
+                .ireturn();
+        })
+}
+
+#[test]
+fn test_built_class_runs() -> Result<()> {
+    let mut interpreter = Interpreter::new();
+    interpreter.load_class(sum_class().build()?)?;
+
+    let result = interpreter.invoke_static("Sum", "twice", "(I)I", vec![JvmValue::Int(10)])?;
+    assert!(matches!(result, Some(JvmValue::Int(110))), "{:?}", result);
+    let calls = interpreter.metaspace.get_class("Sum")?.static_fields.get("calls").cloned();
+    assert!(matches!(calls, Some(JvmValue::Int(2))), "{:?}", calls);
+    Ok(())
+}
+
+#[test]
+fn test_output_parses_back() -> Result<()> {
+    let bytes = sum_class().to_bytes()?;
+    let class_file = ClassFile::from_bytes(&bytes)?;
+    assert_eq!(class_file.get_class_name()?, "Sum");
+    assert_eq!(class_file.get_super_class_name()?, "java/lang/Object");
+    assert_eq!(class_file.methods.len(), 2);
+    assert_eq!(class_file.to_bytes()?, bytes);
+
+    // 两次 invokestatic Sum.sum 共用同一个 MethodRef
+    let method_refs = class_file
+        .constant_pool
+        .entries
+        .iter()
+        .filter(|e| matches!(e, Some(ConstantPoolEntry::MethodRef { .. })))
+        .count();
+    assert_eq!(method_refs, 1);
+    let sum_names = class_file
+        .constant_pool
+        .entries
+        .iter()
+        .filter(|e| matches!(e, Some(ConstantPoolEntry::Utf8(s)) if s == "Sum"))
+        .count();
+    assert_eq!(sum_names, 1);
+    Ok(())
+}
+
+/// 取出唯一方法的字节码
+fn code_of(builder: ClassFileBuilder) -> Result<Vec<u8>> {
+    let class_file = builder.build()?;
+    let code_attr = class_file.methods[0].attributes[0].parse_code_attribute()?;
+    Ok(code_attr.code)
+}
+
+#[test]
+fn test_constant_instruction_selection() -> Result<()> {
+    let code = code_of(ClassFileBuilder::new("Consts").method(PUBLIC_STATIC, "f", "()V", 2, 0, |code| {
+        code.iconst(-1).iconst(5).iconst(100).iconst(-200).iconst(100_000).lconst(1).lconst(7);
+    }))?;
+    assert_eq!(
+        code,
+        [
+            0x02, // iconst_m1
+            0x08, // iconst_5
+            0x10, 100, // bipush 100
+            0x11, 0xff, 0x38, // sipush -200
+            0x12, 0x08, // ldc #8 (Integer 100000)
+            0x0a, // lconst_1
+            0x14, 0x00, 0x09, // ldc2_w #9 (Long 7)
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_builder_errors() {
+    let err = ClassFileBuilder::new("Bad")
+        .method(PUBLIC_STATIC, "f", "()V", 1, 1, |code| {
+            let nowhere = code.new_label();
+            code.goto(nowhere);
+        })
+        .build()
+        .unwrap_err();
+    assert_eq!(format!("{:#}", err), "in method f()V: Label 0 is never bound");
+
+    let err = ClassFileBuilder::new("Bad")
+        .method(PUBLIC_STATIC, "g", "()V", 1, 1, |code| {
+            code.iconst(0).istore(300).vreturn();
+        })
+        .build()
+        .unwrap_err();
+    assert_eq!(
+        format!("{:#}", err),
+        "in method g()V: Local variable index 300 does not fit in one byte (wide is not supported)"
+    );
+}
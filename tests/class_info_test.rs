@@ -0,0 +1,141 @@
+//! 测试类的加载开销统计（ClassInfo / LoadSummary）、依赖树和 `rsjvm info` 子命令
+
+use rsjvm::classfile::ClassFile;
+use rsjvm::classloader::ClassLoader;
+use rsjvm::runtime::{DependencyStatus, Metaspace};
+use rsjvm::Result;
+use std::process::Command;
+
+fn load(metaspace: &mut Metaspace, class_name: &str) -> Result<()> {
+    metaspace.load_class(ClassFile::from_file(format!("examples/{}.class", class_name))?)
+}
+
+#[test]
+fn test_class_info_for_return_one() -> Result<()> {
+    let mut metaspace = Metaspace::new();
+    load(&mut metaspace, "ReturnOne")?;
+    let info = metaspace.class_info("ReturnOne")?;
+    assert_eq!(info.name, "ReturnOne");
+    // <init>、returnOne、addOne、calculate
+    assert_eq!(info.methods, 4);
+    assert_eq!(info.fields, 0);
+    // 5 + 2 + 8 + 12 字节
+    assert_eq!(info.bytecode_bytes, 27);
+    let kinds: Vec<_> = info.constant_pool_kinds.iter().map(|(k, v)| (*k, *v)).collect();
+    assert_eq!(kinds, [("Class", 2), ("Methodref", 1), ("NameAndType", 1), ("Utf8", 12)]);
+    assert_eq!(info.constant_pool_entries, 16);
+    assert_eq!(info.references, ["java/lang/Object"]);
+    assert_eq!(info.load_order, 0);
+    assert!(info.load_time <= info.loaded_at);
+
+    assert!(metaspace.class_info("Missing").is_err());
+    Ok(())
+}
+
+#[test]
+fn test_load_summary_adds_up_classes() -> Result<()> {
+    let mut metaspace = Metaspace::new();
+    load(&mut metaspace, "ReturnOne")?;
+    load(&mut metaspace, "Point")?;
+    let (a, b) = (metaspace.class_info("ReturnOne")?, metaspace.class_info("Point")?);
+    assert!(b.loaded_at >= a.loaded_at);
+
+    let summary = metaspace.load_summary();
+    assert_eq!(summary.classes, 2);
+    assert_eq!(summary.methods, a.methods + b.methods);
+    assert_eq!(summary.fields, a.fields + b.fields);
+    assert_eq!(summary.bytecode_bytes, a.bytecode_bytes + b.bytecode_bytes);
+    assert_eq!(summary.constant_pool_entries, a.constant_pool_entries + b.constant_pool_entries);
+    let utf8 = a.constant_pool_kinds["Utf8"] + b.constant_pool_kinds["Utf8"];
+    assert_eq!(summary.constant_pool_kinds["Utf8"], utf8);
+    assert_eq!(summary.load_time, a.load_time + b.load_time);
+    assert_eq!(summary.bytecode_bytes, metaspace.stats().bytecode_bytes);
+    Ok(())
+}
+
+#[test]
+fn test_dependency_walk_handles_cycles() -> Result<()> {
+    let mut metaspace = Metaspace::new();
+    load(&mut metaspace, "InfoRoot")?;
+    let tree = metaspace.load_dependencies(&ClassLoader::new(vec!["examples".into()]), "InfoRoot");
+    assert!(metaspace.is_class_loaded("InfoHelper"));
+
+    assert_eq!(tree.status, DependencyStatus::Loaded);
+    assert_eq!(
+        tree.class_names(),
+        ["InfoRoot", "InfoHelper", "InfoRoot", "java/lang/Object", "java/lang/Object"]
+    );
+    let helper = &tree.children[0];
+    assert_eq!(helper.status, DependencyStatus::Loaded);
+    // InfoHelper 引用回 InfoRoot：不再展开
+    assert_eq!(helper.children[0].status, DependencyStatus::Repeated);
+    assert!(helper.children[0].children.is_empty());
+    assert_eq!(tree.children[1].status, DependencyStatus::Jdk);
+    Ok(())
+}
+
+#[test]
+fn test_missing_dependency_does_not_stop_the_walk() -> Result<()> {
+    let mut metaspace = Metaspace::new();
+    load(&mut metaspace, "InfoRoot")?;
+    // 类路径中没有 InfoHelper
+    let tree = metaspace.load_dependencies(&ClassLoader::new(vec!["src".into()]), "InfoRoot");
+    let helper = &tree.children[0];
+    assert_eq!(helper.class_name, "InfoHelper");
+    assert!(
+        matches!(&helper.status, DependencyStatus::Missing(e) if e.contains("ClassNotFoundException")),
+        "{:?}",
+        helper.status
+    );
+    assert_eq!(tree.children[1].status, DependencyStatus::Jdk);
+    Ok(())
+}
+
+fn rsjvm_info(args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_rsjvm"))
+        .arg("info")
+        .args(args)
+        .output()
+        .expect("failed to run rsjvm");
+    assert!(output.status.success(), "{:?}", output);
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn test_cli_info() {
+    let stdout = rsjvm_info(&["examples/ReturnOne.class"]);
+    assert!(stdout.contains("类: ReturnOne"), "{}", stdout);
+    assert!(stdout.contains("方法: 4, 字段: 0, 字节码: 27 字节"), "{}", stdout);
+    let pool = "常量池: 16 项 (Class 2, Methodref 1, NameAndType 1, Utf8 12)";
+    assert!(stdout.contains(pool), "{}", stdout);
+    assert!(stdout.contains("引用的类: java/lang/Object"), "{}", stdout);
+    assert!(!stdout.contains("依赖树"), "{}", stdout);
+}
+
+#[test]
+fn test_cli_info_deep_lists_both_classes() {
+    let stdout = rsjvm_info(&["examples/InfoRoot.class", "--deep"]);
+    let tree: Vec<_> = stdout
+        .lines()
+        .skip_while(|line| *line != "依赖树:")
+        .skip(1)
+        .take_while(|line| !line.is_empty())
+        .collect();
+    assert_eq!(
+        tree,
+        [
+            "InfoRoot",
+            "├── InfoHelper",
+            "│   ├── InfoRoot (已展开)",
+            "│   └── java/lang/Object (JDK)",
+            "└── java/lang/Object (JDK)",
+        ],
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains("已加载 2 个类: 方法 6, 字段 1"), "{}", stdout);
+
+    // 也可以按类名在类路径中查找
+    let stdout = rsjvm_info(&["InfoRoot", "-cp", "examples", "--deep"]);
+    assert!(stdout.contains("├── InfoHelper"), "{}", stdout);
+}
@@ -0,0 +1,39 @@
+//! 测试类字面量：ldc 一个 CONSTANT_Class 常量得到类唯一的 java/lang/Class 对象
+
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::runtime::metaspace::ClassState;
+use rsjvm::{JvmBuilder, Result};
+
+#[test]
+fn test_class_literal_name() -> Result<()> {
+    let mut jvm = JvmBuilder::new().class_path("examples").build();
+    let name: String = jvm.call_static_typed("ClassLiteral", "literalName", "()Ljava/lang/String;", ())?;
+    assert_eq!(name, "ClassLiteral");
+    let name: String = jvm.call_static_typed("ClassLiteral", "arrayName", "()Ljava/lang/String;", ())?;
+    assert_eq!(name, "[I");
+
+    // 其他包中的类按需加载，但 ldc 不触发初始化
+    let name: String =
+        jvm.call_static_typed("ClassLiteral", "otherPackageName", "()Ljava/lang/String;", ())?;
+    assert_eq!(name, "cpdemo.util.Greeting");
+    let greeting = jvm.interpreter().metaspace.get_class("cpdemo/util/Greeting")?;
+    assert_eq!(greeting.state, ClassState::Loaded);
+    Ok(())
+}
+
+#[test]
+fn test_class_literal_is_unique_mirror() -> Result<()> {
+    let mut jvm = JvmBuilder::new().class_path("examples").build();
+    let first = jvm.call_static("ClassLiteral", "literal", "()Ljava/lang/Object;", &[])?;
+    let second = jvm.call_static("ClassLiteral", "literal", "()Ljava/lang/Object;", &[])?;
+    match (first, second) {
+        (Some(JvmValue::Reference(Some(a))), Some(JvmValue::Reference(Some(b)))) => {
+            assert_eq!(a, b);
+            assert_eq!(jvm.interpreter().heap.get(a)?.class_name, "java/lang/Class");
+        }
+        other => panic!("expected two references, got {:?}", other),
+    }
+    let same: bool = jvm.call_static_typed("ClassLiteral", "sameAsGetClass", "()Z", ())?;
+    assert!(same);
+    Ok(())
+}
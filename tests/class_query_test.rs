@@ -0,0 +1,115 @@
+//! 测试 ClassMetadata 的查询方法（按名字查找方法和字段、main 方法）和描述符解析
+
+use rsjvm::classfile::access_flags::{ACC_PUBLIC, ACC_STATIC};
+use rsjvm::classfile::builder::ClassFileBuilder;
+use rsjvm::classfile::descriptor::{parameter_count, parameter_descriptors, return_descriptor};
+use rsjvm::classfile::ClassFile;
+use rsjvm::interpreter::Interpreter;
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::Result;
+
+fn load(path: &str) -> Result<Interpreter> {
+    let mut interpreter = Interpreter::new();
+    interpreter.load_class(ClassFile::from_file(path)?)?;
+    Ok(interpreter)
+}
+
+#[test]
+fn test_parse_descriptors() -> Result<()> {
+    assert_eq!(parameter_descriptors("()V")?, Vec::<&str>::new());
+    assert_eq!(
+        parameter_descriptors("([[ILjava/lang/String;JD[Ljava/lang/Object;Z)V")?,
+        ["[[I", "Ljava/lang/String;", "J", "D", "[Ljava/lang/Object;", "Z"]
+    );
+    assert_eq!(parameter_count("([[J[[[Ljava/lang/String;)V")?, 2);
+    assert_eq!(return_descriptor("(I)[[J")?, "[[J");
+    assert_eq!(return_descriptor("()Ljava/lang/String;")?, "Ljava/lang/String;");
+    assert_eq!(return_descriptor("()V")?, "V");
+
+    for bad in ["II)V", "(II", "(Ljava/lang/String)V", "(V)V", "(I)", "(I)[V", "(I)VV", "(Q)V"] {
+        assert!(parameter_descriptors(bad).is_err() || return_descriptor(bad).is_err(), "{}", bad);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_method_queries() -> Result<()> {
+    let interpreter = load("examples/MethodQuery.class")?;
+    let class = interpreter.metaspace.get_class("MethodQuery")?;
+
+    // 重载的方法都返回，按描述符排序
+    let sums: Vec<&str> = class.methods_by_name("sum").iter().map(|m| m.descriptor.as_str()).collect();
+    assert_eq!(sums, ["(II)I", "([[I)[I", "([[JLjava/lang/String;)J"]);
+    assert!(class.methods_by_name("missing").is_empty());
+    // 构造器、<clinit>（没有）、main、sum×3、rows、rowsOfNew
+    assert_eq!(class.declared_methods().count(), 7);
+
+    let grid_sum = class.methods_by_name("sum")[2];
+    assert_eq!(grid_sum.parameter_descriptors(), ["[[J", "Ljava/lang/String;"]);
+    assert_eq!(grid_sum.return_descriptor(), "J");
+    let varargs = class.methods_by_name("sum")[1];
+    assert_eq!(varargs.parameter_descriptors(), ["[[I"]);
+    assert_eq!(varargs.return_descriptor(), "[I");
+
+    let main = class.find_main().expect("MethodQuery has main");
+    assert_eq!(main.parameter_descriptors(), ["[Ljava/lang/String;"]);
+    assert_eq!(main.return_descriptor(), "V");
+    Ok(())
+}
+
+#[test]
+fn test_field_queries() -> Result<()> {
+    let interpreter = load("examples/MethodQuery.class")?;
+    let class = interpreter.metaspace.get_class("MethodQuery")?;
+    let mut names: Vec<&str> = class.declared_fields().map(|f| f.name.as_str()).collect();
+    names.sort();
+    assert_eq!(names, ["count", "grid", "name"]);
+    let grid = class.fields_by_name("grid");
+    assert_eq!(grid.len(), 1);
+    assert_eq!(grid[0].descriptor, "[[J");
+    assert!(class.fields_by_name("count")[0].is_static);
+    Ok(())
+}
+
+#[test]
+fn test_find_main_requires_public_static() -> Result<()> {
+    // 实例方法 main 不是入口
+    let class_file = ClassFileBuilder::new("NotMain")
+        .method(ACC_PUBLIC, "main", "([Ljava/lang/String;)V", 0, 2, |code| {
+            code.vreturn();
+        })
+        .method(ACC_PUBLIC | ACC_STATIC, "main", "()V", 0, 0, |code| {
+            code.vreturn();
+        })
+        .build()?;
+    let mut interpreter = Interpreter::new();
+    interpreter.load_class(class_file)?;
+    let class = interpreter.metaspace.get_class("NotMain")?;
+    assert!(class.find_main().is_none());
+    assert_eq!(class.methods_by_name("main").len(), 2);
+    Ok(())
+}
+
+#[test]
+fn test_multidimensional_array_argument() -> Result<()> {
+    // rows([[I)I 只有一个参数，调用者的操作数栈上只弹出一个值
+    let mut interpreter = load("examples/MethodQuery.class")?;
+    let result = interpreter.invoke_static("MethodQuery", "rowsOfNew", "()I", vec![])?;
+    assert!(matches!(result, Some(JvmValue::Int(13))), "{:?}", result);
+    Ok(())
+}
+
+#[test]
+fn test_illegal_descriptor_is_class_format_error() -> Result<()> {
+    let class_file = ClassFileBuilder::new("BadSignature")
+        .method(ACC_PUBLIC | ACC_STATIC, "broken", "(I", 1, 1, |code| {
+            code.vreturn();
+        })
+        .build()?;
+    let err = Interpreter::new().load_class(class_file).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "java/lang/ClassFormatError: Method \"broken\" in class BadSignature has illegal signature \"(I\""
+    );
+    Ok(())
+}
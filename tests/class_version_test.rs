@@ -0,0 +1,91 @@
+//! 测试 class 文件版本检查：默认拒绝 Java 8 以后的类，可以跳过检查；
+//! 拒绝时的错误信息列出预检发现的不支持的指令
+
+use rsjvm::classfile::version::{check_class_version, unsupported_bytecode};
+use rsjvm::classfile::ClassFile;
+use rsjvm::{JvmBuilder, Result};
+use std::process::Command;
+
+const GREETER: &str = "examples/modern/modern/api/Greeter.class";
+
+#[test]
+fn test_newer_class_is_refused() -> Result<()> {
+    let mut jvm = JvmBuilder::new().build();
+    let err = jvm.load_class(ClassFile::from_file(GREETER)?).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "java/lang/UnsupportedClassVersionError: class modern/api/Greeter was compiled for Java 17 \
+         (major 61); rsjvm currently supports up to Java 8 — pass --force-version to try anyway"
+    );
+
+    // Java 8 的类不受影响
+    let mut jvm = JvmBuilder::new().class_path("examples").build();
+    let sum: i32 = jvm.call_static_typed("Calculator", "add", "(II)I", (2, 3))?;
+    assert_eq!(sum, 5);
+    Ok(())
+}
+
+#[test]
+fn test_force_version() -> Result<()> {
+    let mut jvm = JvmBuilder::new().force_version(true).build();
+    jvm.load_class(ClassFile::from_file(GREETER)?)?;
+    let answer: i32 = jvm.call_static_typed("modern/api/Greeter", "answer", "()I", ())?;
+    assert_eq!(answer, 42);
+
+    // 提高上限也可以
+    let mut jvm = JvmBuilder::new().max_major_version(61).build();
+    jvm.load_class(ClassFile::from_file(GREETER)?)?;
+    Ok(())
+}
+
+#[test]
+fn test_preflight_lists_unsupported_bytecode() -> Result<()> {
+    let class_file = ClassFile::from_file("examples/IndyConcat.class")?;
+    // 字符串拼接有 shim，只有 lambda 的 invokedynamic 执行不了
+    assert_eq!(
+        unsupported_bytecode(&class_file)?,
+        ["invokedynamic java/lang/invoke/LambdaMetafactory.metafactory in lambda()I"]
+    );
+    let err = check_class_version(&class_file, 52).unwrap_err();
+    assert!(
+        err.to_string().ends_with(
+            "rsjvm currently supports up to Java 8 — pass --force-version to try anyway \
+             (unsupported bytecode: invokedynamic java/lang/invoke/LambdaMetafactory.metafactory in lambda()I)"
+        ),
+        "{}",
+        err
+    );
+    assert!(check_class_version(&class_file, 55).is_ok());
+    assert!(unsupported_bytecode(&ClassFile::from_file(GREETER)?)?.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_cli_force_version() -> Result<()> {
+    let run = |extra: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_rsjvm"))
+            .arg("run")
+            .args(extra)
+            .args(["--method", "answer", GREETER])
+            .env("RUST_BACKTRACE", "0")
+            .output()
+    };
+    let output = run(&[])?;
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("pass --force-version to try anyway"), "{}", stderr);
+
+    let output = run(&["--force-version"])?;
+    assert!(output.status.success(), "{:?}", output);
+    assert!(String::from_utf8_lossy(&output.stdout).contains("42"));
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rsjvm"))
+        .args(["parse", GREETER])
+        .output()?;
+    assert!(!output.status.success());
+    let output = Command::new(env!("CARGO_BIN_EXE_rsjvm"))
+        .args(["parse", "--force-version", GREETER])
+        .output()?;
+    assert!(output.status.success(), "{:?}", output);
+    Ok(())
+}
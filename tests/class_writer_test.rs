@@ -0,0 +1,110 @@
+//! 测试 ClassFile::to_bytes：解析后写回的字节与原文件一致，以及从零构造 class
+
+use rsjvm::classfile::attribute::AttributeInfo;
+use rsjvm::classfile::constant_pool::{ConstantPool, ConstantPoolEntry};
+use rsjvm::classfile::{access_flags, ClassFile, MethodInfo};
+use rsjvm::interpreter::Interpreter;
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::Result;
+
+#[test]
+fn test_return_one_round_trip() -> Result<()> {
+    let original = std::fs::read("examples/ReturnOne.class")?;
+    let class_file = ClassFile::from_bytes(&original)?;
+    let written = class_file.to_bytes()?;
+    assert_eq!(written, original);
+
+    let reparsed = ClassFile::from_bytes(&written)?;
+    assert_eq!(reparsed.to_bytes()?, written);
+    assert_eq!(reparsed.get_class_name()?, "ReturnOne");
+    Ok(())
+}
+
+#[test]
+fn test_all_examples_round_trip() -> Result<()> {
+    let mut checked = 0;
+    // 包括带 Long/Double 常量的类和 module-info
+    for dir in ["examples", "examples/modern", "examples/modern/modern/api"] {
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("class") {
+                continue;
+            }
+            let original = std::fs::read(&path)?;
+            let written = ClassFile::from_bytes(&original)?.to_bytes()?;
+            assert!(written == original, "{} 写回后字节不一致", path.display());
+            checked += 1;
+        }
+    }
+    assert!(checked > 10, "only {} class files checked", checked);
+    Ok(())
+}
+
+/// 手工构造：public class Synth { public static long answer() { return 42L; } }
+fn synthetic_class() -> ClassFile {
+    let utf8 = |s: &str| Some(ConstantPoolEntry::Utf8(s.to_string()));
+    let constant_pool = ConstantPool {
+        entries: vec![
+            None,
+            utf8("Synth"),                                    // #1
+            Some(ConstantPoolEntry::Class { name_index: 1 }), // #2
+            utf8("java/lang/Object"),                         // #3
+            Some(ConstantPoolEntry::Class { name_index: 3 }), // #4
+            utf8("answer"),                                   // #5
+            utf8("()J"),                                      // #6
+            utf8("Code"),                                     // #7
+            Some(ConstantPoolEntry::Long(42)),                // #8
+            None,                                             // #9：Long 的第二个槽位
+        ],
+    };
+
+    let code = [0x14, 0x00, 0x08, 0xad]; // ldc2_w #8; lreturn
+    let mut info = vec![0x00, 0x02, 0x00, 0x00]; // max_stack = 2, max_locals = 0
+    info.extend_from_slice(&(code.len() as u32).to_be_bytes());
+    info.extend_from_slice(&code);
+    info.extend_from_slice(&[0x00, 0x00, 0x00, 0x00]); // 异常表、属性表都为空
+
+    ClassFile {
+        magic: 0xCAFEBABE,
+        minor_version: 0,
+        major_version: 52,
+        constant_pool,
+        access_flags: access_flags::ACC_PUBLIC | access_flags::ACC_SUPER,
+        this_class: 2,
+        super_class: 4,
+        interfaces: Vec::new(),
+        fields: Vec::new(),
+        methods: vec![MethodInfo {
+            access_flags: access_flags::ACC_PUBLIC | access_flags::ACC_STATIC,
+            name_index: 5,
+            descriptor_index: 6,
+            attributes: vec![AttributeInfo { name_index: 7, info }],
+        }],
+        attributes: Vec::new(),
+    }
+}
+
+#[test]
+fn test_synthetic_class_loads_and_runs() -> Result<()> {
+    let bytes = synthetic_class().to_bytes()?;
+    // 常量池计数写的是 entries.len()，Long 的第二个槽位不写内容
+    assert_eq!(&bytes[8..10], &[0x00, 0x0a]);
+
+    let class_file = ClassFile::from_bytes(&bytes)?;
+    assert_eq!(class_file.get_class_name()?, "Synth");
+    assert_eq!(class_file.to_bytes()?, bytes);
+
+    let mut interpreter = Interpreter::new();
+    interpreter.load_class(class_file)?;
+    let result = interpreter.invoke_static("Synth", "answer", "()J", vec![])?;
+    assert!(matches!(result, Some(JvmValue::Long(42))), "{:?}", result);
+    Ok(())
+}
+
+#[test]
+fn test_incomplete_constant_pool_is_rejected() {
+    let mut class_file = synthetic_class();
+    class_file.constant_pool.entries[5] = None;
+    let err = class_file.to_bytes().unwrap_err();
+    assert_eq!(err.to_string(), "Constant pool entry at 5 is None");
+}
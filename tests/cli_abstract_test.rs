@@ -0,0 +1,57 @@
+//! 测试 `rsjvm run` 遇到接口/抽象方法时的提示信息
+
+use std::path::PathBuf;
+use std::process::Command;
+
+/// 运行 rsjvm 命令，返回 (是否成功, stdout + stderr)
+fn rsjvm(args: &[&str]) -> (bool, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_rsjvm"))
+        .args(args)
+        .output()
+        .expect("failed to run rsjvm");
+    let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+    text.push_str(&String::from_utf8_lossy(&output.stderr));
+    (output.status.success(), text)
+}
+
+#[test]
+fn test_run_interface_lists_implementations() {
+    let (ok, output) = rsjvm(&["run", "examples/Greeter.class"]);
+    assert!(!ok);
+    assert!(
+        output.contains("cannot execute abstract/interface method Greeter.main; it has no bytecode"),
+        "{}",
+        output
+    );
+    assert!(output.contains("did you mean to run an implementing class?"), "{}", output);
+    assert!(output.contains("    HelloPlugin"), "{}", output);
+    assert!(output.contains("    ArgPlugin"), "{}", output);
+    assert!(!output.contains("PluginMain\n"), "{}", output);
+}
+
+#[test]
+fn test_run_abstract_method_lists_subclasses() {
+    let (ok, output) = rsjvm(&["run", "examples/Animal.class", "-m", "speak"]);
+    assert!(!ok);
+    assert!(
+        output.contains("cannot execute abstract/interface method Animal.speak"),
+        "{}",
+        output
+    );
+    assert!(output.contains("    Dog"), "{}", output);
+    assert!(!output.contains("Code"), "{}", output);
+}
+
+#[test]
+fn test_run_abstract_method_without_implementations() {
+    // 单独复制到临时目录，类路径中没有任何实现类
+    let dir = std::env::temp_dir().join(format!("rsjvm-cli-abstract-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let class_path: PathBuf = dir.join("Animal.class");
+    std::fs::copy("examples/Animal.class", &class_path).unwrap();
+
+    let (ok, output) = rsjvm(&["run", class_path.to_str().unwrap(), "-m", "speak"]);
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(!ok);
+    assert!(output.contains("no implementing classes found"), "{}", output);
+}
@@ -0,0 +1,51 @@
+//! 测试 `run -cp <类路径> <类名>`：按类名启动，引用的其他包中的类从同一个类路径按需加载
+
+use rsjvm::{JvmBuilder, Result};
+use std::process::{Command, Output};
+
+fn rsjvm(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_rsjvm"))
+        .args(args)
+        .env("RUST_BACKTRACE", "0")
+        .output()
+        .expect("failed to run rsjvm")
+}
+
+#[test]
+fn test_run_class_name_on_class_path() {
+    let output = rsjvm(&["run", "-cp", "examples", "cpdemo.Main", "Ada"]);
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.starts_with("正在加载: cpdemo.Main"), "{}", stdout);
+    assert!(stdout.contains("Hello, Ada!\n1\n"), "{}", stdout);
+
+    // 多个类路径用系统的路径分隔符隔开，按顺序查找
+    let class_path = std::env::join_paths(["examples/oop_demo", "examples"]).unwrap();
+    let output = rsjvm(&["run", "--classpath", class_path.to_str().unwrap(), "cpdemo/Main"]);
+    assert!(output.status.success(), "{:?}", output);
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Hello, world!\n"));
+}
+
+#[test]
+fn test_class_not_found_lists_searched_roots() {
+    let class_path = std::env::join_paths(["examples/oop_demo", "examples"]).unwrap();
+    let output = rsjvm(&["run", "-cp", class_path.to_str().unwrap(), "cpdemo.Missing"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("java/lang/ClassNotFoundException: cpdemo/Missing"), "{}", stderr);
+    assert!(stderr.contains(r#"searched ["examples/oop_demo", "examples"]"#), "{}", stderr);
+}
+
+#[test]
+fn test_load_class_by_name_through_facade() -> Result<()> {
+    let mut jvm = JvmBuilder::new()
+        .class_paths(["examples/oop_demo", "examples"])
+        .capture_output()
+        .build();
+    let class_name = jvm.load_class_by_name("cpdemo.Main")?;
+    assert_eq!(class_name, "cpdemo/Main");
+    jvm.run_main(&class_name, &["Grace".to_string()])?;
+    let output = String::from_utf8(jvm.take_captured_output())?;
+    assert_eq!(output, "Hello, Grace!\n1\n");
+    Ok(())
+}
@@ -0,0 +1,81 @@
+//! 测试 `rsjvm debug` 的脚本模式
+
+use rsjvm::classfile::ClassFile;
+use rsjvm::debugger::repl::DebugSession;
+use rsjvm::interpreter::Interpreter;
+use std::path::PathBuf;
+use std::process::Command;
+
+fn script(tag: &str, commands: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("rsjvm-debug-{}-{}.txt", tag, std::process::id()));
+    std::fs::write(&path, commands).unwrap();
+    path
+}
+
+fn debug(args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_rsjvm"))
+        .arg("debug")
+        .args(args)
+        .env("RUST_BACKTRACE", "0")
+        .output()
+        .expect("failed to run rsjvm");
+    assert!(output.status.success(), "{:?}", output);
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+#[test]
+fn test_script_steps_and_shows_locals() {
+    let path = script("locals", "step\nstep\nlocals\nstack\nquit\n");
+    let transcript = debug(&[
+        "examples/ReturnOne.class",
+        "-m",
+        "addOne",
+        "--script",
+        path.to_str().unwrap(),
+    ]);
+    std::fs::remove_file(&path).ok();
+
+    // iconst_1; istore_0 之后 local 0 是 1
+    assert!(
+        transcript.contains(
+            "(rsjvm) step\nExecuted ReturnOne.addOne pc=1: istore_0\n=>     2: iconst_0\n\
+             (rsjvm) locals\n  [0] 1\n  [1] 0\n(rsjvm) stack\n  (empty)\n(rsjvm) quit"
+        ),
+        "{}",
+        transcript
+    );
+}
+
+#[test]
+fn test_script_breakpoint_and_disasm() {
+    let path = script("break", "break 6\nrun\ndisasm\nstack\nrun\n");
+    let transcript = debug(&[
+        "examples/ReturnOne.class",
+        "-m",
+        "addOne",
+        "--script",
+        path.to_str().unwrap(),
+    ]);
+    std::fs::remove_file(&path).ok();
+
+    assert!(transcript.contains("Breakpoint hit: ReturnOne.addOne:()I pc=6\n=>     6: iadd"), "{}", transcript);
+    assert!(transcript.contains("       5: iload_1\n=>     6: iadd\n       7: ireturn"), "{}", transcript);
+    assert!(transcript.contains("  [0] 1\n  [1] 0\n"), "{}", transcript);
+    assert!(transcript.contains("Returned 1"), "{}", transcript);
+}
+
+#[test]
+fn test_unknown_and_invalid_commands() -> rsjvm::Result<()> {
+    let mut interpreter = Interpreter::new();
+    interpreter.load_class(ClassFile::from_file("examples/ReturnOne.class")?)?;
+    let mut session = DebugSession::new(interpreter, "ReturnOne", "returnOne:()I", Vec::new());
+    let mut out = Vec::new();
+    session.run("frobnicate\nbreak x\nbreak 99\nlocals\nstep\nstep\n".as_bytes(), &mut out, true)?;
+    let transcript = String::from_utf8(out)?;
+    assert!(transcript.contains("error: unknown command: frobnicate"), "{}", transcript);
+    assert!(transcript.contains("error: invalid pc: x"), "{}", transcript);
+    assert!(transcript.contains("error: pc 99 is past the end of returnOne:()I"), "{}", transcript);
+    assert!(transcript.contains("error: the program is not running"), "{}", transcript);
+    assert!(transcript.contains("Executed ReturnOne.returnOne pc=1: ireturn\nReturned 1"), "{}", transcript);
+    Ok(())
+}
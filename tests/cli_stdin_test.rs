@@ -0,0 +1,107 @@
+//! 测试 CLI 从标准输入和 --class-bytes-hex 读取class文件
+
+use rsjvm::classfile::ClassFile;
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn rsjvm(args: &[&str], stdin: &[u8]) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_rsjvm"))
+        .args(args)
+        .env("RUST_BACKTRACE", "0")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("failed to run rsjvm");
+    child.stdin.take().unwrap().write_all(stdin).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+/// 去掉第一行（"正在加载: <来源>"），其余输出应该与来源无关
+fn without_source_line(output: &Output) -> String {
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    stdout.split_once('\n').unwrap().1.to_string()
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[test]
+fn test_run_from_stdin_matches_file() {
+    let bytes = std::fs::read("examples/ReturnOne.class").unwrap();
+    let from_file = rsjvm(&["run", "examples/ReturnOne.class", "-m", "returnOne"], b"");
+    let from_stdin = rsjvm(&["run", "-", "-m", "returnOne"], &bytes);
+
+    assert!(from_stdin.status.success(), "{:?}", from_stdin);
+    assert!(String::from_utf8_lossy(&from_stdin.stdout).starts_with("正在加载: <stdin>"));
+    assert_eq!(without_source_line(&from_stdin), without_source_line(&from_file));
+    assert!(without_source_line(&from_stdin).contains("int: 1"));
+}
+
+#[test]
+fn test_parse_from_stdin_and_hex_match_file() {
+    let bytes = std::fs::read("examples/ReturnOne.class").unwrap();
+    let hex = to_hex(&bytes);
+    let from_file = rsjvm(&["parse", "examples/ReturnOne.class", "-v"], b"");
+    let from_stdin = rsjvm(&["parse", "-", "-v"], &bytes);
+    let from_hex = rsjvm(&["parse", "--class-bytes-hex", &hex, "-v"], b"");
+
+    assert!(from_stdin.status.success(), "{:?}", from_stdin);
+    assert!(from_hex.status.success(), "{:?}", from_hex);
+    assert_eq!(without_source_line(&from_stdin), without_source_line(&from_file));
+    assert_eq!(without_source_line(&from_hex), without_source_line(&from_file));
+}
+
+#[test]
+fn test_run_from_hex() {
+    let hex = to_hex(&std::fs::read("examples/ReturnOne.class").unwrap());
+    let output = rsjvm(&["run", "--class-bytes-hex", &hex, "-m", "returnOne"], b"");
+    assert!(output.status.success(), "{:?}", output);
+    assert!(without_source_line(&output).contains("int: 1"));
+}
+
+#[test]
+fn test_stdin_rejects_empty_and_non_class_input() {
+    let empty = rsjvm(&["run", "-"], b"");
+    assert!(!empty.status.success());
+    let stderr = String::from_utf8_lossy(&empty.stderr);
+    assert!(stderr.contains("failed to parse class file from <stdin>"), "{}", stderr);
+    assert!(stderr.contains("Empty input"), "{}", stderr);
+
+    let source = rsjvm(&["parse", "-"], b"public class ReturnOne {}\n");
+    assert!(!source.status.success());
+    let stderr = String::from_utf8_lossy(&source.stderr);
+    assert!(stderr.contains("Not a class file"), "{}", stderr);
+    assert!(stderr.contains("input looks like text"), "{}", stderr);
+}
+
+#[test]
+fn test_invalid_hex_is_reported() {
+    let output = rsjvm(&["parse", "--class-bytes-hex", "cafe babz"], b"");
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("invalid hex digit 'z' at position 8"), "{}", stderr);
+}
+
+#[test]
+fn test_parser_errors_for_non_class_bytes() {
+    let err = ClassFile::from_bytes(&[]).unwrap_err().to_string();
+    assert!(err.starts_with("Empty input"), "{}", err);
+
+    let err = ClassFile::from_bytes(&[0xCA, 0xFE]).unwrap_err().to_string();
+    assert!(err.contains("too short to be a class file: 2 bytes"), "{}", err);
+
+    let err = ClassFile::from_bytes(&[0xDE, 0xAD, 0xBE, 0xEF, 0x00]).unwrap_err().to_string();
+    assert_eq!(
+        err,
+        "Not a class file: invalid magic number 0xDEADBEEF, expected 0xCAFEBABE"
+    );
+
+    // 字节切片和文件走的是同一个解析器
+    let bytes = std::fs::read("examples/ReturnOne.class").unwrap();
+    let from_bytes = ClassFile::from_bytes(&bytes).unwrap();
+    let from_file = ClassFile::from_file("examples/ReturnOne.class").unwrap();
+    assert_eq!(from_bytes.get_class_name().unwrap(), from_file.get_class_name().unwrap());
+    assert_eq!(from_bytes.methods.len(), from_file.methods.len());
+}
@@ -0,0 +1,170 @@
+//! 测试常量池的构造 API（追加、去重、Long/Double 占两个索引）和项之间引用的检查
+
+use rsjvm::classfile::access_flags::{ACC_PUBLIC, ACC_STATIC};
+use rsjvm::classfile::builder::ClassFileBuilder;
+use rsjvm::classfile::constant_pool::{ConstantPool, ConstantPoolEntry};
+use rsjvm::classfile::parser::parse_class_file_with_options;
+use rsjvm::classfile::{ClassFile, ParserOptions};
+use rsjvm::Result;
+
+fn validation_error(pool: &ConstantPool) -> String {
+    pool.validate().unwrap_err().to_string()
+}
+
+#[test]
+fn test_push_reserves_second_slot_for_wide_entries() -> Result<()> {
+    let mut pool = ConstantPool::empty();
+    assert_eq!(pool.push(ConstantPoolEntry::Integer(1)), 1);
+    assert_eq!(pool.push(ConstantPoolEntry::Long(2)), 2);
+    assert_eq!(pool.push(ConstantPoolEntry::Double(3.0)), 4);
+    assert_eq!(pool.push(ConstantPoolEntry::Utf8("x".to_string())), 6);
+    assert_eq!(pool.entries.len(), 7);
+    assert!(pool.entries[3].is_none() && pool.entries[5].is_none());
+    // push 不去重
+    assert_eq!(pool.push(ConstantPoolEntry::Integer(1)), 7);
+    pool.validate()?;
+    Ok(())
+}
+
+#[test]
+fn test_interners_deduplicate() -> Result<()> {
+    let mut pool = ConstantPool::empty();
+    let object = pool.add_class("java/lang/Object");
+    assert_eq!(pool.add_class("java/lang/Object"), object);
+    // Class 引用的 Utf8 也被复用
+    assert_eq!(pool.add_utf8("java/lang/Object"), object - 1);
+
+    let println = pool.add_method_ref("java/io/PrintStream", "println", "(I)V");
+    let entries = pool.entries.len();
+    assert_eq!(pool.add_method_ref("java/io/PrintStream", "println", "(I)V"), println);
+    assert_eq!(pool.entries.len(), entries);
+    // 不同描述符是不同的方法引用，但类和方法名复用
+    let println_string = pool.add_method_ref("java/io/PrintStream", "println", "(Ljava/lang/String;)V");
+    assert_ne!(println_string, println);
+    assert_eq!(pool.entries.len(), entries + 3); // Utf8 描述符、NameAndType、Methodref
+
+    let field = pool.add_field_ref("java/lang/System", "out", "Ljava/io/PrintStream;");
+    assert_eq!(pool.add_field_ref("java/lang/System", "out", "Ljava/io/PrintStream;"), field);
+    assert_eq!(pool.add_string("hi"), pool.add_string("hi"));
+
+    // 浮点常量逐位比较：0.0 和 -0.0 是两项
+    let zero = pool.add(ConstantPoolEntry::Double(0.0));
+    assert_ne!(pool.add(ConstantPoolEntry::Double(-0.0)), zero);
+    assert_eq!(pool.add(ConstantPoolEntry::Double(0.0)), zero);
+
+    pool.validate()?;
+    assert_eq!(pool.get_class_name(object)?, "java/lang/Object");
+    Ok(())
+}
+
+#[test]
+fn test_validate_rejects_wrong_kind() {
+    let mut pool = ConstantPool::empty();
+    let method = pool.add_method_ref("A", "m", "()V");
+    pool.push(ConstantPoolEntry::Class { name_index: method });
+    assert_eq!(
+        validation_error(&pool),
+        format!(
+            "Constant pool entry #{} (Class) refers to #{}, expected Utf8 but found Methodref",
+            method + 1,
+            method
+        )
+    );
+
+    let mut pool = ConstantPool::empty();
+    let name = pool.add_utf8("A");
+    pool.push(ConstantPoolEntry::MethodRef {
+        class_index: name,
+        name_and_type_index: name,
+    });
+    assert_eq!(
+        validation_error(&pool),
+        "Constant pool entry #2 (Methodref) refers to #1, expected Class but found Utf8"
+    );
+}
+
+#[test]
+fn test_validate_rejects_dangling_indices() {
+    let mut pool = ConstantPool::empty();
+    pool.push(ConstantPoolEntry::String { string_index: 9 });
+    assert_eq!(
+        validation_error(&pool),
+        "Constant pool entry #1 (String) refers to #9, out of range (pool has 2 entries)"
+    );
+
+    let mut pool = ConstantPool::empty();
+    pool.push(ConstantPoolEntry::Class { name_index: 0 });
+    assert!(validation_error(&pool).contains("refers to #0"));
+
+    // 指向 Long 的第二个槽位
+    let mut pool = ConstantPool::empty();
+    pool.push(ConstantPoolEntry::Long(1));
+    pool.push(ConstantPoolEntry::Class { name_index: 2 });
+    assert!(validation_error(&pool).ends_with("expected Utf8 but the slot is empty"));
+}
+
+#[test]
+fn test_validate_method_handle_and_wide_slots() {
+    let mut pool = ConstantPool::empty();
+    let field = pool.add_field_ref("A", "f", "I");
+    pool.push(ConstantPoolEntry::MethodHandle {
+        reference_kind: 6, // REF_invokeStatic 不能指向字段
+        reference_index: field,
+    });
+    assert!(validation_error(&pool).contains("expected Methodref or InterfaceMethodref but found Fieldref"));
+
+    let mut pool = ConstantPool::empty();
+    pool.push(ConstantPoolEntry::MethodHandle {
+        reference_kind: 10,
+        reference_index: 1,
+    });
+    assert!(validation_error(&pool).contains("invalid reference kind 10"));
+
+    // Long 的第二个槽位被占用
+    let mut pool = ConstantPool::empty();
+    pool.push(ConstantPoolEntry::Long(1));
+    pool.entries[2] = Some(ConstantPoolEntry::Integer(0));
+    assert_eq!(
+        validation_error(&pool),
+        "Constant pool entry #1 (Long) must be followed by an unused slot"
+    );
+}
+
+/// 一个合法的类，常量池中的 Class 项被改成指向 Methodref
+fn corrupted_class() -> Result<ClassFile> {
+    let mut class_file = ClassFileBuilder::new("Corrupt")
+        .method(ACC_PUBLIC | ACC_STATIC, "run", "()V", 0, 0, |code| {
+            code.invokestatic("Corrupt", "run", "()V").vreturn();
+        })
+        .build()?;
+    let pool = &mut class_file.constant_pool.entries;
+    let method = pool
+        .iter()
+        .position(|e| matches!(e, Some(ConstantPoolEntry::MethodRef { .. })))
+        .unwrap() as u16;
+    let this_class = class_file.this_class as usize;
+    pool[this_class] = Some(ConstantPoolEntry::Class { name_index: method });
+    Ok(class_file)
+}
+
+#[test]
+fn test_parser_validates_when_enabled() -> Result<()> {
+    let bytes = corrupted_class()?.to_bytes()?;
+    // 默认不检查：可以解析，直到用到类名才出错
+    let lenient = parse_class_file_with_options(&bytes, &ParserOptions::default())?;
+    assert!(lenient.get_class_name().is_err());
+
+    let options = ParserOptions {
+        validate_constant_pool: true,
+        ..ParserOptions::default()
+    };
+    let err = parse_class_file_with_options(&bytes, &options).unwrap_err();
+    assert!(format!("{:#}", err).contains("(Class) refers to"), "{:#}", err);
+
+    // javac 生成的 class 文件都能通过检查
+    for name in ["HelloWorld", "IndyConcat", "MethodQuery"] {
+        let bytes = std::fs::read(format!("examples/{}.class", name))?;
+        parse_class_file_with_options(&bytes, &options)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,71 @@
+//! 测试类型转换指令（i2l、f2i、i2c 等）
+//!
+//! 期望值都是真实 JVM 的结果：窄化保留低位，浮点转整数饱和，NaN 转成 0
+
+use rsjvm::interpreter::Interpreter;
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::{JvmBuilder, Result};
+
+fn call(method: &str, descriptor: &str, arg: JvmValue) -> Result<Option<JvmValue>> {
+    let mut jvm = JvmBuilder::new().class_path("examples").build();
+    jvm.call_static("Conversions", method, descriptor, &[arg])
+}
+
+#[test]
+fn test_conversion_edge_cases() -> Result<()> {
+    use JvmValue::*;
+    let cases = [
+        // (int)(float)Integer.MAX_VALUE：float 舍入到 2^31，转回 int 饱和
+        ("f2i", "(F)I", Float(i32::MAX as f32), Int(i32::MAX)),
+        ("d2i", "(D)I", Double(f64::NAN), Int(0)),
+        ("f2i", "(F)I", Float(f32::NAN), Int(0)),
+        ("d2i", "(D)I", Double(-1e20), Int(i32::MIN)),
+        ("d2i", "(D)I", Double(-2.9), Int(-2)),
+        ("f2l", "(F)J", Float(f32::INFINITY), Long(i64::MAX)),
+        ("d2l", "(D)J", Double(f64::NEG_INFINITY), Long(i64::MIN)),
+        ("d2l", "(D)J", Double(f64::NAN), Long(0)),
+        ("i2b", "(I)B", Int(200), Int(-56)),
+        ("i2c", "(I)C", Int(-1), Int(65535)),
+        ("i2s", "(I)S", Int(70000), Int(4464)),
+        ("l2i", "(J)I", Long(0x1_0000_0005), Int(5)),
+        ("l2i", "(J)I", Long(-1), Int(-1)),
+        ("i2l", "(I)J", Int(-7), Long(-7)),
+        ("i2f", "(I)F", Int(16_777_217), Float(16_777_216.0)),
+        ("i2d", "(I)D", Int(i32::MIN), Double(-2147483648.0)),
+        ("l2f", "(J)F", Long(1 << 40), Float(1_099_511_627_776.0)),
+        ("l2d", "(J)D", Long(-3), Double(-3.0)),
+        ("f2d", "(F)D", Float(0.5), Double(0.5)),
+        ("d2f", "(D)F", Double(1e40), Float(f32::INFINITY)),
+    ];
+    for (method, descriptor, arg, expected) in cases {
+        let result = call(method, descriptor, arg.clone())?;
+        let matches = match (&result, &expected) {
+            (Some(Int(a)), Int(b)) => a == b,
+            (Some(Long(a)), Long(b)) => a == b,
+            (Some(Float(a)), Float(b)) => a.to_bits() == b.to_bits(),
+            (Some(Double(a)), Double(b)) => a.to_bits() == b.to_bits(),
+            _ => false,
+        };
+        assert!(matches, "{}({:?}) = {:?}, expected {:?}", method, arg, result, expected);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_mixed_type_division() -> Result<()> {
+    let mut jvm = JvmBuilder::new().class_path("examples").build();
+    let avg: f64 = jvm.call_static_typed("Conversions", "average", "(II)D", (7, 2))?;
+    assert_eq!(avg, 3.5);
+    Ok(())
+}
+
+#[test]
+fn test_conversion_type_mismatch() {
+    let code = [
+        0x04, // iconst_1
+        0x8a, // l2d：栈顶是 int 不是 long
+        0xaf, // dreturn
+    ];
+    let result = Interpreter::new().execute_method_with_class("Hand", "run:()D", &code, 0, 2);
+    assert!(result.is_err());
+}
@@ -0,0 +1,102 @@
+//! 测试栈帧快照：槽位编号与 javap 一致，long 占两个槽位，变量名按 LocalVariableTable 对齐
+//!
+//! SlotDemo.class 需要用 `javac -g` 编译
+
+use rsjvm::classfile::ClassFile;
+use rsjvm::debugger::{FrameSnapshot, SlotValue};
+use rsjvm::runtime::frame::{Frame, JvmValue};
+use rsjvm::runtime::{LocalVariable, Metaspace};
+use rsjvm::Result;
+
+/// SlotDemo.mix(I)J 的局部变量表和栈帧大小
+fn mix_method() -> Result<(Vec<LocalVariable>, usize, usize)> {
+    let mut metaspace = Metaspace::new();
+    metaspace.load_class(ClassFile::from_file("examples/SlotDemo.class")?)?;
+    let method = metaspace.get_class("SlotDemo")?.find_method("mix", "(I)J")?;
+    Ok((method.local_variables.clone(), method.max_locals, method.max_stack))
+}
+
+fn render_lines(snapshot: &FrameSnapshot) -> Vec<String> {
+    snapshot
+        .to_string()
+        .lines()
+        .map(|l| l.trim().to_string())
+        .collect()
+}
+
+#[test]
+fn test_local_variable_table_is_loaded() -> Result<()> {
+    let (locals, max_locals, _) = mix_method()?;
+    assert_eq!(max_locals, 4);
+    let big = locals.iter().find(|v| v.name == "big").expect("big in LVT");
+    assert_eq!((big.slot, big.descriptor.as_str()), (1, "J"));
+    assert!(big.is_wide());
+    Ok(())
+}
+
+#[test]
+fn test_long_local_spans_two_slots() -> Result<()> {
+    let (lvt, max_locals, max_stack) = mix_method()?;
+
+    // pc=11（ladd 之前）：big + (long) count 两个 long 在栈上
+    let mut frame = Frame::new(max_locals, max_stack);
+    frame.class_name = "SlotDemo".into();
+    frame.set_local(0, JvmValue::Int(7))?;
+    frame.set_local(1, JvmValue::Long(5_000_000_000))?;
+    frame.set_local(3, JvmValue::Int(8))?;
+    frame.push(JvmValue::Long(5_000_000_000))?;
+    frame.push(JvmValue::Long(8))?;
+
+    let snapshot = FrameSnapshot::capture(&frame, 11, &lvt);
+    let slots: Vec<usize> = snapshot.locals.iter().map(|s| s.slot).collect();
+    assert_eq!(slots, vec![0, 1, 2, 3]);
+    assert!(matches!(snapshot.locals[2].value, SlotValue::SecondHalf));
+    assert_eq!(snapshot.stack_depth(), 4);
+
+    let lines = render_lines(&snapshot);
+    for expected in [
+        "Frame SlotDemo @ pc=11",
+        "[0] base: I = 7",
+        "[1] big: J = 5000000000L",
+        "[2] big (2nd half)",
+        "[3] count: I = 8",
+        "operand stack (depth 4/4):",
+        "[0-1] 5000000000L",
+        "[2-3] 8L",
+    ] {
+        assert!(lines.iter().any(|l| l == expected), "missing {:?} in\n{}", expected, snapshot);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_names_follow_variable_scope() -> Result<()> {
+    let (lvt, max_locals, max_stack) = mix_method()?;
+
+    // pc=4（lstore_1 之后）：count 还不在作用域内
+    let mut frame = Frame::new(max_locals, max_stack);
+    frame.class_name = "SlotDemo".into();
+    frame.set_local(0, JvmValue::Int(7))?;
+    let snapshot = FrameSnapshot::capture(&frame, 4, &lvt);
+
+    // 槽位 1 按 LVT 声明是 long，即使值尚未写入也占两个槽位
+    assert!(matches!(snapshot.locals[2].value, SlotValue::SecondHalf));
+    assert_eq!(snapshot.locals[1].name.as_deref(), Some("big"));
+    assert_eq!(snapshot.locals[3].name, None);
+    assert!(render_lines(&snapshot).iter().any(|l| l == "[3] 0"));
+    Ok(())
+}
+
+#[test]
+fn test_without_local_variable_table() {
+    // 没有调试信息时按值的类别编号
+    let mut frame = Frame::new(3, 2);
+    frame.set_local(0, JvmValue::Double(1.5)).unwrap();
+    frame.set_local(2, JvmValue::Reference(None)).unwrap();
+    frame.push(JvmValue::Int(3)).unwrap();
+
+    let lines = render_lines(&FrameSnapshot::capture(&frame, 0, &[]));
+    for expected in ["[0] 1.5", "[1] (2nd half)", "[2] null", "[0] 3"] {
+        assert!(lines.iter().any(|l| l == expected), "missing {:?} in {:?}", expected, lines);
+    }
+}
@@ -0,0 +1,127 @@
+//! 测试预解码的指令流：解码结果、分支目标检查，以及按解码结果执行
+
+use rsjvm::interpreter::decoded::{decode_method, DecodedMethod, Instruction};
+use rsjvm::interpreter::instructions::opcodes::*;
+use rsjvm::interpreter::Interpreter;
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::{JvmBuilder, Result};
+
+#[test]
+fn test_decode_typed_operands() -> Result<()> {
+    // 0: sipush -2   3: wide iinc 300 -1000   9: wide iload 300   13: goto 0
+    let code = [
+        SIPUSH, 0xff, 0xfe, WIDE, IINC, 0x01, 0x2c, 0xfc, 0x18, WIDE, ILOAD, 0x01, 0x2c, GOTO, 0xff,
+        0xf3,
+    ];
+    let instructions = decode_method(&code)?;
+    assert_eq!(
+        instructions,
+        [
+            (0, Instruction::Sipush(-2)),
+            (
+                3,
+                Instruction::Iinc {
+                    index: 300,
+                    delta: -1000,
+                    wide: true
+                }
+            ),
+            (
+                9,
+                Instruction::Local {
+                    opcode: ILOAD,
+                    index: 300,
+                    wide: true
+                }
+            ),
+            (13, Instruction::Branch { opcode: GOTO, target: 0 }),
+        ]
+    );
+
+    let decoded = DecodedMethod::new(&code)?;
+    assert_eq!(decoded.index_of(9), Some(2));
+    assert_eq!(decoded.index_of(10), None);
+    assert_eq!(decoded.next_pc(2), 13);
+    assert_eq!(decoded.next_pc(3), code.len());
+    Ok(())
+}
+
+#[test]
+fn test_branch_into_middle_of_instruction_is_rejected() {
+    // 0: goto 4（sipush 的操作数）   3: sipush 0   6: ireturn
+    let code = [GOTO, 0x00, 0x04, SIPUSH, 0x00, 0x00, IRETURN];
+    assert!(decode_method(&code).is_ok());
+    let err = DecodedMethod::new(&code).unwrap_err().to_string();
+    assert!(err.contains("not the start of an instruction"), "{}", err);
+
+    let err = decode_method(&[GOTO, 0x00, 0x10]).unwrap_err().to_string();
+    assert!(err.contains("outside the code"), "{}", err);
+}
+
+#[test]
+fn test_loaded_methods_are_decoded() -> Result<()> {
+    let mut jvm = JvmBuilder::new().class_path("examples").build();
+    let sum: i32 = jvm.call_static_typed("ProfileDemo", "sumTo", "(I)I", (10,))?;
+    assert_eq!(sum, 45);
+
+    let class = jvm.interpreter().metaspace.get_class("ProfileDemo")?;
+    let method = &class.methods["sumTo:(I)I"];
+    let decoded = method.decoded.as_ref().expect("sumTo should be decoded");
+    assert_eq!(decoded.code_len(), method.code.len());
+    assert_eq!(decoded.instructions()[0].0, 0);
+    Ok(())
+}
+
+/// switch (key) { case 1: return 10; case 2: return 20; default: return -1; }
+fn switch_code(opcode: u8, key: i8) -> Vec<u8> {
+    // 0: bipush key   2: nop   3: switch（操作数从 pc 4 开始，不需要填充）
+    let mut code = vec![BIPUSH, key as u8, NOP, opcode];
+    // 偏移量相对于 switch 指令的 pc 3
+    let operands: &[i32] = if opcode == TABLESWITCH {
+        // default low high offsets[2]，之后的指令从 pc 24 开始
+        &[21, 1, 2, 23, 26]
+    } else {
+        // default npairs (1, offset) (2, offset)，之后的指令从 pc 28 开始
+        &[25, 2, 1, 27, 2, 30]
+    };
+    for operand in operands {
+        code.extend(operand.to_be_bytes());
+    }
+    code.extend([ICONST_M1, IRETURN, BIPUSH, 10, IRETURN, BIPUSH, 20, IRETURN]);
+    code
+}
+
+#[test]
+fn test_decoded_switch() -> Result<()> {
+    for opcode in [TABLESWITCH, LOOKUPSWITCH] {
+        assert!(DecodedMethod::new(&switch_code(opcode, 0)).is_ok());
+        for (key, expected) in [(1, 10), (2, 20), (0, -1), (3, -1), (i8::MIN, -1)] {
+            let mut interpreter = Interpreter::new();
+            let result = interpreter.execute_method_with_class(
+                "Switch",
+                "run:()I",
+                &switch_code(opcode, key),
+                0,
+                1,
+            )?;
+            assert!(
+                matches!(result, Some(JvmValue::Int(v)) if v == expected),
+                "key {}: {:?}",
+                key,
+                result
+            );
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_undecodable_code_runs_on_raw_path() -> Result<()> {
+    // ireturn 之后是未知操作码：整段无法解码，但执行不到那里
+    let code = [ICONST_3, IRETURN, 0xfe];
+    assert!(DecodedMethod::new(&code).is_err());
+    let mut interpreter = Interpreter::new();
+    let result = interpreter.execute_method_with_class("Raw", "run:()I", &code, 0, 1)?;
+    assert!(matches!(result, Some(JvmValue::Int(3))), "{:?}", result);
+    Ok(())
+}
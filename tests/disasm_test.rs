@@ -0,0 +1,142 @@
+//! 测试反汇编器和 `rsjvm disasm` 子命令
+
+use rsjvm::classfile::constant_pool::ConstantPool;
+use rsjvm::classfile::ClassFile;
+use rsjvm::interpreter::disasm::{disassemble, DisassembledInstruction};
+use rsjvm::Result;
+use std::process::Command;
+
+/// 反汇编 class 文件中的一个方法
+fn disassemble_method(path: &str, method: &str) -> Result<Vec<DisassembledInstruction>> {
+    let class_file = ClassFile::from_file(path)?;
+    for m in &class_file.methods {
+        if class_file.constant_pool.get_utf8(m.name_index)? != method {
+            continue;
+        }
+        for attr in &m.attributes {
+            if class_file.constant_pool.get_utf8(attr.name_index)? == "Code" {
+                let code = attr.parse_code_attribute()?;
+                return disassemble(&code.code, &class_file.constant_pool);
+            }
+        }
+    }
+    panic!("{} 中没有方法 {}", path, method);
+}
+
+fn mnemonics(instructions: &[DisassembledInstruction]) -> Vec<&str> {
+    instructions.iter().map(|i| i.mnemonic.as_str()).collect()
+}
+
+#[test]
+fn test_disassemble_straight_line_code() -> Result<()> {
+    let instructions = disassemble_method("examples/Calculator.class", "complex")?;
+    assert_eq!(
+        mnemonics(&instructions),
+        [
+            "iload_0", "iload_1", "iadd", "istore", "iload_2", "iload_3", "isub", "istore",
+            "iload", "iload", "imul", "ireturn"
+        ]
+    );
+    let pcs: Vec<usize> = instructions.iter().map(|i| i.pc).collect();
+    assert_eq!(pcs, [0, 1, 2, 3, 5, 6, 7, 8, 10, 12, 14, 15]);
+    assert_eq!(instructions[3].operand_bytes, [4]);
+    assert_eq!(instructions[3].to_string(), "    3: istore        4");
+    Ok(())
+}
+
+#[test]
+fn test_disassemble_resolves_constant_pool_symbols() -> Result<()> {
+    let instructions = disassemble_method("examples/oop_demo/Circle.class", "<init>")?;
+    assert_eq!(
+        mnemonics(&instructions),
+        ["aload_0", "iconst_0", "invokespecial", "aload_0", "dload_1", "putfield", "return"]
+    );
+    assert_eq!(
+        instructions[2].symbol.as_deref(),
+        Some("Method Shape.<init>:(I)V")
+    );
+    assert_eq!(
+        instructions[5].symbol.as_deref(),
+        Some("Field Circle.radius:D")
+    );
+    assert!(instructions[2].to_string().ends_with("// Method Shape.<init>:(I)V"));
+    Ok(())
+}
+
+#[test]
+fn test_disassemble_switch_targets_are_absolute() -> Result<()> {
+    let dense = disassemble_method("examples/SwitchTest.class", "dense")?;
+    assert_eq!(dense[1].mnemonic, "tableswitch");
+    // 填充到 4 字节对齐：pc 1 之后有 2 个填充字节
+    assert_eq!(dense[1].size(), 31);
+    assert_eq!(dense[1].branch_targets, [32, 35, 38, 41, 44]);
+    assert_eq!(dense[2].pc, 32);
+
+    let sparse = disassemble_method("examples/SwitchTest.class", "sparse")?;
+    assert_eq!(sparse[1].mnemonic, "lookupswitch");
+    assert_eq!(sparse[1].branch_targets, [36, 38, 40, 42]);
+    assert!(sparse[1].operands.contains("10000: 40"));
+
+    // 每个跳转目标都是某条指令的开头
+    let starts: Vec<usize> = sparse.iter().map(|i| i.pc).collect();
+    for target in &sparse[1].branch_targets {
+        assert!(starts.contains(target), "target {} is not an instruction", target);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_disassemble_wide_and_branches() -> Result<()> {
+    let cp = ConstantPool::new(1);
+    let code = [
+        0xc4, 0x84, 0x01, 0x00, 0x03, 0xe8, // wide iinc 256, 1000
+        0xc4, 0x15, 0x01, 0x2c, // wide iload 300
+        0x99, 0xff, 0xf6, // ifeq -10 → 0
+        0xb1, // return
+    ];
+    let instructions = disassemble(&code, &cp)?;
+    assert_eq!(mnemonics(&instructions), ["iinc_w", "iload_w", "ifeq", "return"]);
+    assert_eq!(instructions[0].operands, "256, 1000");
+    assert_eq!(instructions[0].size(), 6);
+    assert_eq!(instructions[1].operands, "300");
+    assert_eq!(instructions[2].branch_targets, [0]);
+    assert_eq!(instructions[3].pc, 13);
+    Ok(())
+}
+
+#[test]
+fn test_disassemble_rejects_bad_code() {
+    let cp = ConstantPool::new(1);
+    let err = disassemble(&[0x10], &cp).unwrap_err();
+    assert_eq!(err.to_string(), "Truncated operand for bipush at pc 0");
+
+    let err = disassemble(&[0x00, 0xa7, 0x00, 0x10], &cp).unwrap_err();
+    assert!(err.to_string().contains("outside the code"), "{}", err);
+
+    let err = disassemble(&[0xc4, 0x60], &cp).unwrap_err();
+    assert!(err.to_string().contains("after wide"), "{}", err);
+
+    let err = disassemble(&[0xb2, 0x00, 0x05], &cp).unwrap_err();
+    assert!(err.to_string().contains("Invalid constant pool index"), "{}", err);
+}
+
+#[test]
+fn test_cli_disasm_and_parse_verbose() {
+    let output = Command::new(env!("CARGO_BIN_EXE_rsjvm"))
+        .args(["disasm", "examples/SwitchTest.class", "-m", "dense0"])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("  dense0:()I\n    Code:\n        0: iconst_0\n"), "{}", stdout);
+    assert!(stdout.contains("invokestatic  #7"), "{}", stdout);
+    assert!(stdout.contains("// Method SwitchTest.dense:(I)I"), "{}", stdout);
+    assert!(!stdout.contains("sparse"), "{}", stdout);
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rsjvm"))
+        .args(["parse", "examples/SwitchTest.class", "--verbose"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("tableswitch   { // 0 to 3"), "{}", stdout);
+}
@@ -0,0 +1,121 @@
+//! 测试 invoke_static_typed：按描述符转换参数和返回值
+
+use rsjvm::classfile::ClassFile;
+use rsjvm::interpreter::embed::{JArg, JResult};
+use rsjvm::interpreter::Interpreter;
+use rsjvm::Result;
+
+fn load() -> Result<Interpreter> {
+    let mut interpreter = Interpreter::new();
+    interpreter.load_class(ClassFile::from_file("examples/EmbedTest.class")?)?;
+    Ok(interpreter)
+}
+
+#[test]
+fn test_bool_and_string_arguments() -> Result<()> {
+    let mut jvm = load()?;
+    let desc = "(ZLjava/lang/String;)I";
+    let call = |jvm: &mut Interpreter, args: &[JArg]| jvm.invoke_static_typed("EmbedTest", "score", desc, args);
+
+    assert_eq!(call(&mut jvm, &[JArg::Bool(true), JArg::Str("duke")])?, JResult::Int(15));
+    assert_eq!(call(&mut jvm, &[JArg::Bool(false), JArg::Str("duke")])?, JResult::Int(10));
+    assert_eq!(call(&mut jvm, &[JArg::Bool(true), JArg::Null])?, JResult::Int(5));
+    Ok(())
+}
+
+#[test]
+fn test_return_values_follow_descriptor() -> Result<()> {
+    let mut jvm = load()?;
+    assert_eq!(
+        jvm.invoke_static_typed("EmbedTest", "isPositive", "(I)Z", &[JArg::Int(3)])?,
+        JResult::Bool(true)
+    );
+    assert_eq!(
+        jvm.invoke_static_typed("EmbedTest", "isPositive", "(I)Z", &[JArg::Short(-3)])?,
+        JResult::Bool(false)
+    );
+    assert_eq!(jvm.invoke_static_typed("EmbedTest", "initial", "()C", &[])?, JResult::Char('J'));
+    assert_eq!(
+        jvm.invoke_static_typed("EmbedTest", "echoByte", "(B)B", &[JArg::Byte(-7)])?,
+        JResult::Byte(-7)
+    );
+    assert_eq!(
+        jvm.invoke_static_typed("EmbedTest", "echo", "(Ljava/lang/String;)Ljava/lang/String;", &[JArg::Str("hi")])?,
+        JResult::Str("hi".to_string())
+    );
+    assert_eq!(
+        jvm.invoke_static_typed("EmbedTest", "echo", "(Ljava/lang/String;)Ljava/lang/String;", &[JArg::Null])?,
+        JResult::Null
+    );
+    assert_eq!(jvm.invoke_static_typed("EmbedTest", "nothing", "()V", &[])?, JResult::Void);
+    Ok(())
+}
+
+#[test]
+fn test_primitive_widening() -> Result<()> {
+    let mut jvm = load()?;
+    // int → long，超出 i32 范围的结果
+    assert_eq!(
+        jvm.invoke_static_typed("EmbedTest", "twice", "(J)J", &[JArg::Int(i32::MAX)])?,
+        JResult::Long(2 * i32::MAX as i64)
+    );
+    assert_eq!(
+        jvm.invoke_static_typed("EmbedTest", "half", "(D)D", &[JArg::Float(3.0)])?,
+        JResult::Double(1.5)
+    );
+    Ok(())
+}
+
+#[test]
+fn test_arity_mismatch() -> Result<()> {
+    let mut jvm = load()?;
+    let err = jvm
+        .invoke_static_typed("EmbedTest", "score", "(ZLjava/lang/String;)I", &[JArg::Bool(true)])
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("expects 2 arguments, got 1"), "{}", err);
+    Ok(())
+}
+
+#[test]
+fn test_type_mismatch() -> Result<()> {
+    let mut jvm = load()?;
+    // int 不能当 boolean 传，long 不能窄化成 int
+    let err = jvm
+        .invoke_static_typed("EmbedTest", "score", "(ZLjava/lang/String;)I", &[JArg::Int(1), JArg::Null])
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("argument 0") && err.contains("as Z"), "{}", err);
+    let err = jvm
+        .invoke_static_typed("EmbedTest", "isPositive", "(I)Z", &[JArg::Long(1)])
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("cannot pass Long(1) as I"), "{}", err);
+    Ok(())
+}
+
+#[test]
+fn test_typed_calls_through_jvm() -> Result<()> {
+    let mut jvm = rsjvm::JvmBuilder::new().class_path("examples").build();
+    let desc = "(ZLjava/lang/String;)I";
+    let score: i32 = jvm.call_static_typed("EmbedTest", "score", desc, (true, "duke"))?;
+    assert_eq!(score, 15);
+    let positive: bool = jvm.call_static_typed("EmbedTest", "isPositive", "(I)Z", (-3,))?;
+    assert!(!positive);
+
+    let desc = "(Ljava/lang/String;)Ljava/lang/String;";
+    let name = String::from("hi");
+    let echoed: String = jvm.call_static_typed("EmbedTest", "echo", desc, (&name,))?;
+    assert_eq!(echoed, "hi");
+    let echoed: Option<String> = jvm.call_static_typed("EmbedTest", "echo", desc, (JArg::Null,))?;
+    assert_eq!(echoed, None);
+    jvm.call_static_typed::<()>("EmbedTest", "nothing", "()V", ())?;
+
+    // 类型检查仍然按描述符进行
+    let err = jvm
+        .call_static_typed::<bool>("EmbedTest", "isPositive", "(I)Z", (1i64,))
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("cannot pass Long(1) as I"), "{}", err);
+    Ok(())
+}
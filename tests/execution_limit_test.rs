@@ -0,0 +1,89 @@
+//! 测试指令预算和运行时间上限：死循环返回限制错误，而不是一直运行下去
+
+use rsjvm::interpreter::{Interpreter, InterpreterOptions};
+use rsjvm::runtime::{ExecutionLimit, ExecutionLimitExceeded};
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::JvmBuilder;
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+/// goto 0：永远不会结束
+const INFINITE_LOOP: [u8; 3] = [0xa7, 0x00, 0x00];
+
+fn run_loop(options: InterpreterOptions) -> ExecutionLimitExceeded {
+    let mut interpreter = Interpreter::new_with_options(options);
+    let err = interpreter
+        .execute_method_with_class("Spin", "spin:()V", &INFINITE_LOOP, 1, 1)
+        .expect_err("an infinite loop must hit the limit");
+    err.downcast::<ExecutionLimitExceeded>()
+        .unwrap_or_else(|e| panic!("expected ExecutionLimitExceeded, got {:#}", e))
+}
+
+#[test]
+fn test_instruction_budget_stops_infinite_loop() {
+    let exceeded = run_loop(InterpreterOptions {
+        max_instructions: Some(1000),
+        ..Default::default()
+    });
+    assert_eq!(exceeded.limit, ExecutionLimit::Instructions(1000));
+    assert_eq!(exceeded.instructions, 1000);
+    assert_eq!(exceeded.location.class_name, "Spin");
+    assert_eq!(exceeded.location.pc, 0);
+    assert!(
+        exceeded
+            .to_string()
+            .starts_with("execution limit exceeded: 1000 instructions executed (max_instructions=1000)"),
+        "{}",
+        exceeded
+    );
+}
+
+#[test]
+fn test_time_limit_stops_infinite_loop() {
+    let start = Instant::now();
+    let exceeded = run_loop(InterpreterOptions {
+        time_limit: Some(Duration::from_millis(50)),
+        ..Default::default()
+    });
+    assert_eq!(exceeded.limit, ExecutionLimit::Time(Duration::from_millis(50)));
+    assert!(exceeded.instructions > 0);
+    assert!(start.elapsed() < Duration::from_secs(10));
+}
+
+#[test]
+fn test_budget_is_per_run() {
+    // 每次调用重新计数：预算够一次运行，连续运行两次也不会超
+    let mut jvm = JvmBuilder::new()
+        .class_path("examples")
+        .max_instructions(20_000)
+        .build();
+    for _ in 0..2 {
+        let result = jvm.call_static("LongLoop", "sumLongs", "()J", &[]).unwrap();
+        assert!(matches!(result, Some(JvmValue::Long(3000000499500))), "{:?}", result);
+    }
+
+    let mut jvm = JvmBuilder::new()
+        .class_path("examples")
+        .max_instructions(100)
+        .build();
+    let err = jvm.call_static("LongLoop", "sumLongs", "()J", &[]).unwrap_err();
+    let exceeded = err.downcast_ref::<ExecutionLimitExceeded>().expect("limit error");
+    assert_eq!(exceeded.location.method_name, "sumLongs");
+}
+
+#[test]
+fn test_cli_max_instructions() {
+    let output = Command::new(env!("CARGO_BIN_EXE_rsjvm"))
+        .args(["run", "examples/LongLoop.class", "-m", "sumLongs", "--max-instructions", "100"])
+        .env("RUST_BACKTRACE", "0")
+        .output()
+        .expect("failed to run rsjvm");
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("execution limit exceeded: 100 instructions executed (max_instructions=100)"),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains("LongLoop.sumLongs()J"), "{}", stdout);
+}
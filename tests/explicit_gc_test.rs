@@ -0,0 +1,77 @@
+//! 测试 Java 代码主动请求的 GC（System.gc / Runtime.gc）、GC 统计和 `run --gc-log` 日志
+
+use rsjvm::classfile::ClassFile;
+use rsjvm::interpreter::{Interpreter, InterpreterOptions};
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::Result;
+use std::process::Command;
+
+fn interpreter() -> Result<Interpreter> {
+    // 关闭自动回收，统计中只有主动请求的回收
+    let mut interpreter = Interpreter::new_with_options(InterpreterOptions {
+        gc_threshold: None,
+        ..Default::default()
+    });
+    interpreter.load_class(ClassFile::from_file("examples/ExplicitGc.class")?)?;
+    Ok(interpreter)
+}
+
+#[test]
+fn test_system_gc_and_runtime_gc_collect() -> Result<()> {
+    let mut interpreter = interpreter()?;
+    assert_eq!(interpreter.gc_stats().collections, 0);
+
+    let result = interpreter.invoke_static("ExplicitGc", "run", "(I)I", vec![JvmValue::Int(4)])?;
+    assert!(matches!(result, Some(JvmValue::Int(19_800))), "{:?}", result);
+
+    let stats = interpreter.gc_stats().clone();
+    // 两次 System.gc()，两次 Runtime.getRuntime().gc()
+    assert_eq!(stats.collections, 4);
+    // 每轮的临时数组中，最后一个还在局部变量里，下一轮才被回收
+    assert!(stats.objects_collected >= 4 * 99, "{}", stats);
+    assert!(stats.last_pause <= stats.worst_pause);
+    assert!(stats.total_pause >= stats.worst_pause);
+    assert_eq!(stats.live_objects, interpreter.heap.object_count());
+
+    // 再运行一轮，计数继续累加
+    interpreter.invoke_static("ExplicitGc", "run", "(I)I", vec![JvmValue::Int(1)])?;
+    assert_eq!(interpreter.gc_stats().collections, 5);
+    assert!(interpreter.gc_stats().objects_collected > stats.objects_collected);
+    Ok(())
+}
+
+#[test]
+fn test_runtime_is_a_singleton() -> Result<()> {
+    let mut interpreter = interpreter()?;
+    let result = interpreter.invoke_static("ExplicitGc", "sameRuntime", "()Z", vec![])?;
+    assert!(matches!(result, Some(JvmValue::Int(1))), "{:?}", result);
+    // 单例挂在方法区中，回收后仍然存活
+    interpreter.collect_garbage();
+    assert_eq!(interpreter.heap.find_by_class("java/lang/Runtime").len(), 1);
+    Ok(())
+}
+
+#[test]
+fn test_cli_gc_log() {
+    let output = Command::new(env!("CARGO_BIN_EXE_rsjvm"))
+        .args(["run", "examples/ExplicitGc.class", "--gc-log"])
+        .env_remove("RUST_LOG")
+        .output()
+        .expect("failed to run rsjvm");
+    assert!(output.status.success(), "{:?}", output);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    let lines: Vec<_> = stderr.lines().filter(|line| line.starts_with("[gc] ")).collect();
+    assert_eq!(lines.len(), 3, "{}", stderr);
+    for line in lines {
+        assert!(line.starts_with("[gc] collected "), "{}", line);
+        assert!(line.contains(" objects in ") && line.contains("ms, ") && line.ends_with(" live"), "{}", line);
+    }
+
+    // 不加 --gc-log 时没有 GC 日志
+    let output = Command::new(env!("CARGO_BIN_EXE_rsjvm"))
+        .args(["run", "examples/ExplicitGc.class"])
+        .env_remove("RUST_LOG")
+        .output()
+        .expect("failed to run rsjvm");
+    assert!(!String::from_utf8_lossy(&output.stderr).contains("[gc]"));
+}
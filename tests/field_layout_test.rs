@@ -0,0 +1,116 @@
+//! 测试对象字段布局：父类字段在前、遮蔽字段各占一个槽位、getfield/putfield 缓存槽位，
+//! 以及内置 JDK 对象按需追加字段
+
+use rsjvm::classfile::ClassFile;
+use rsjvm::classloader::ClassLoader;
+use rsjvm::interpreter::Interpreter;
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::runtime::{Heap, Metaspace, Symbol};
+use rsjvm::Result;
+
+fn interpreter() -> Result<Interpreter> {
+    let mut interpreter = Interpreter::with_class_loader(ClassLoader::new(vec!["examples".into()]));
+    interpreter.ensure_class_loaded("FieldShadowing")?;
+    interpreter.ensure_class_loaded("FieldDefaults")?;
+    interpreter.ensure_class_loaded("ShadowChild")?;
+    Ok(interpreter)
+}
+
+fn slots(metaspace: &mut Metaspace, class_name: &str) -> Vec<String> {
+    metaspace
+        .field_layout(class_name)
+        .keys()
+        .iter()
+        .map(|(owner, name)| format!("{}.{}", owner, name))
+        .collect()
+}
+
+fn load(metaspace: &mut Metaspace, class_name: &str) -> Result<()> {
+    let bytes = std::fs::read(format!("examples/{}.class", class_name))?;
+    metaspace.load_class(ClassFile::from_bytes(&bytes)?)
+}
+
+#[test]
+fn test_superclass_fields_come_first() -> Result<()> {
+    let mut interpreter = interpreter()?;
+    let metaspace = &mut interpreter.metaspace;
+    assert_eq!(
+        slots(metaspace, "FieldDefaults"),
+        ["ShadowParent.x", "FieldDefaults.count", "FieldDefaults.total", "FieldDefaults.name"]
+    );
+    // 遮蔽的字段是两个槽位，父类的在前
+    assert_eq!(slots(metaspace, "ShadowChild"), ["ShadowParent.x", "ShadowChild.x"]);
+    assert_eq!(slots(metaspace, "ShadowParent"), ["ShadowParent.x"]);
+    // JDK 类没有类元数据
+    assert!(metaspace.field_layout("java/lang/Object").is_empty());
+
+    let layout = metaspace.field_layout("FieldDefaults");
+    assert!(matches!(
+        layout.defaults(),
+        [JvmValue::Int(0), JvmValue::Int(0), JvmValue::Long(0), JvmValue::Reference(None)]
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_layout_is_recomputed_when_superclass_loads_later() -> Result<()> {
+    let mut metaspace = Metaspace::new();
+    load(&mut metaspace, "ShadowChild")?;
+    assert_eq!(slots(&mut metaspace, "ShadowChild"), ["ShadowChild.x"]);
+    load(&mut metaspace, "ShadowParent")?;
+    assert_eq!(slots(&mut metaspace, "ShadowChild"), ["ShadowParent.x", "ShadowChild.x"]);
+    Ok(())
+}
+
+#[test]
+fn test_objects_are_allocated_with_all_slots() -> Result<()> {
+    let mut interpreter = interpreter()?;
+    let layout = interpreter.metaspace.field_layout("FieldDefaults");
+    let object = interpreter.heap.allocate_instance("FieldDefaults", layout)?;
+    assert_eq!(interpreter.heap.get(object)?.slot_count(), 4);
+    assert!(matches!(interpreter.heap.get_field(object, "FieldDefaults", "total")?, JvmValue::Long(0)));
+
+    let histogram = interpreter.run_stats().heap_histogram;
+    let entry = histogram.iter().find(|e| e.class_name == "FieldDefaults").unwrap();
+    assert_eq!((entry.count, entry.slots), (1, 4));
+    Ok(())
+}
+
+#[test]
+fn test_field_refs_cache_slots() -> Result<()> {
+    let mut interpreter = interpreter()?;
+    let result = interpreter.invoke_static("FieldShadowing", "bothViews", "()I", vec![])?;
+    assert!(matches!(result, Some(JvmValue::Int(507))), "{:?}", result);
+
+    let class = interpreter.metaspace.get_class("FieldShadowing")?;
+    let mut cached: Vec<_> = class
+        .runtime_pool
+        .declared_fields
+        .values()
+        .map(|f| (format!("{}.{}", f.class_name, f.field_name), f.slot))
+        .collect();
+    cached.sort();
+    assert_eq!(
+        cached,
+        [("ShadowChild.x".to_string(), Some(1)), ("ShadowParent.x".to_string(), Some(0))]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_slot_mismatch_falls_back_to_name_lookup() -> Result<()> {
+    let mut heap = Heap::new();
+    let object = heap.allocate("java/lang/Integer")?;
+    let (class, name) = (Symbol::from("java/lang/Integer"), Symbol::from("value"));
+    // 内置对象没有预先算好的布局，写入时追加槽位
+    heap.set_field_at(object, 3, &class, &name, JvmValue::Int(42))?;
+    assert!(matches!(heap.get_field_at(object, 0, &class, &name)?, JvmValue::Int(42)));
+    assert!(matches!(heap.get_field_at(object, 7, &class, &name)?, JvmValue::Int(42)));
+    assert_eq!(heap.get(object)?.slot_count(), 1);
+
+    // 追加的槽位只属于这个对象
+    let other = heap.allocate("java/lang/Integer")?;
+    assert!(heap.get_field(other, "java/lang/Integer", "value").is_err());
+    assert_eq!(heap.get(other)?.slot_count(), 0);
+    Ok(())
+}
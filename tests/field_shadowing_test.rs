@@ -0,0 +1,65 @@
+//! 测试实例字段按声明类存放（字段遮蔽）以及 NEW 时的字段默认值
+
+use rsjvm::classloader::ClassLoader;
+use rsjvm::interpreter::Interpreter;
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::Result;
+
+fn interpreter() -> Result<Interpreter> {
+    let mut interpreter = Interpreter::with_class_loader(ClassLoader::new(vec!["examples".into()]));
+    interpreter.ensure_class_loaded("FieldShadowing")?;
+    Ok(interpreter)
+}
+
+fn call(interpreter: &mut Interpreter, method: &str, descriptor: &str) -> Result<Option<JvmValue>> {
+    interpreter.invoke_static("FieldShadowing", method, descriptor, vec![])
+}
+
+#[test]
+fn test_shadowed_fields_are_separate_slots() -> Result<()> {
+    let mut interpreter = interpreter()?;
+    // 父类构造器写 ShadowParent.x = 1，子类构造器写 ShadowChild.x = 2
+    let result = call(&mut interpreter, "parentView", "()I")?;
+    assert!(matches!(result, Some(JvmValue::Int(1))), "{:?}", result);
+    let result = call(&mut interpreter, "childView", "()I")?;
+    assert!(matches!(result, Some(JvmValue::Int(2))), "{:?}", result);
+    let result = call(&mut interpreter, "bothViews", "()I")?;
+    assert!(matches!(result, Some(JvmValue::Int(507))), "{:?}", result);
+    Ok(())
+}
+
+#[test]
+fn test_new_object_fields_start_at_default_values() -> Result<()> {
+    let mut interpreter = interpreter()?;
+    let result = call(&mut interpreter, "intDefault", "()I")?;
+    assert!(matches!(result, Some(JvmValue::Int(0))), "{:?}", result);
+    let result = call(&mut interpreter, "longDefault", "()J")?;
+    assert!(matches!(result, Some(JvmValue::Long(0))), "{:?}", result);
+    let result = call(&mut interpreter, "referenceDefault", "()Ljava/lang/Object;")?;
+    assert!(matches!(result, Some(JvmValue::Reference(None))), "{:?}", result);
+    Ok(())
+}
+
+#[test]
+fn test_instance_field_defaults_walk_the_superclass_chain() -> Result<()> {
+    let mut interpreter = interpreter()?;
+    interpreter.ensure_class_loaded("FieldDefaults")?;
+    let defaults = interpreter.metaspace.instance_field_defaults("FieldDefaults");
+    let mut keys: Vec<_> = defaults.keys().cloned().collect();
+    keys.sort();
+    let expected = [
+        ("FieldDefaults", "count"),
+        ("FieldDefaults", "name"),
+        ("FieldDefaults", "total"),
+        ("ShadowParent", "x"),
+    ];
+    assert_eq!(
+        keys,
+        expected.map(|(c, f)| (c.into(), f.into())).to_vec()
+    );
+    assert!(matches!(
+        defaults[&("FieldDefaults".into(), "total".into())],
+        JvmValue::Long(0)
+    ));
+    Ok(())
+}
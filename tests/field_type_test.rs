@@ -0,0 +1,175 @@
+//! 测试字段和数组元素的类型检查：putfield/putstatic/xastore 按描述符检查值的种类，
+//! 并把 int 截断到 boolean/byte/char/short 的范围
+//!
+//! javac 不会生成类型不符的字节码，这里用 ClassFileBuilder 直接构造；
+//! 这样的字节码也过不了加载时的校验（操作数栈的槽位数对不上），所以关闭校验，看执行时的检查
+
+use rsjvm::classfile::access_flags::{ACC_PUBLIC, ACC_STATIC};
+use rsjvm::classfile::builder::{ClassFileBuilder, CodeBuilder};
+use rsjvm::classfile::ClassFile;
+use rsjvm::classloader::ClassLoader;
+use rsjvm::interpreter::instructions::opcodes::{IASTORE, NEWARRAY};
+use rsjvm::interpreter::{Interpreter, InterpreterOptions};
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::Result;
+
+const PUBLIC_STATIC: u16 = ACC_PUBLIC | ACC_STATIC;
+
+fn new_holder<'a, 'b>(code: &'a mut CodeBuilder<'b>) -> &'a mut CodeBuilder<'b> {
+    code.new_object("Holder")
+        .dup()
+        .invokespecial("Holder", "<init>", "()V")
+}
+
+/// Holder：各种类型的字段，每个静态方法做一次存取
+fn holder() -> ClassFileBuilder {
+    ClassFileBuilder::new("Holder")
+        .field(ACC_PUBLIC, "l", "J")
+        .field(ACC_PUBLIC, "i", "I")
+        .field(ACC_PUBLIC, "b", "B")
+        .field(ACC_PUBLIC, "c", "C")
+        .field(ACC_PUBLIC, "z", "Z")
+        .field(PUBLIC_STATIC, "count", "I")
+        .method(ACC_PUBLIC, "<init>", "()V", 1, 1, |code| {
+            code.aload(0).invokespecial("java/lang/Object", "<init>", "()V").vreturn();
+        })
+        .method(PUBLIC_STATIC, "intIntoLong", "()I", 3, 0, |code| {
+            new_holder(code).iconst(5).putfield("Holder", "l", "J").iconst(0).ireturn();
+        })
+        .method(PUBLIC_STATIC, "refIntoInt", "()I", 3, 0, |code| {
+            new_holder(code).dup().putfield("Holder", "i", "I").iconst(0).ireturn();
+        })
+        .method(PUBLIC_STATIC, "longIntoStatic", "()I", 2, 0, |code| {
+            code.lconst(7).putstatic("Holder", "count", "I").iconst(0).ireturn();
+        })
+        .method(PUBLIC_STATIC, "byteField", "(I)I", 3, 1, |code| {
+            new_holder(code)
+                .dup()
+                .iload(0)
+                .putfield("Holder", "b", "B")
+                .getfield("Holder", "b", "B")
+                .ireturn();
+        })
+        .method(PUBLIC_STATIC, "charField", "(I)I", 3, 1, |code| {
+            new_holder(code)
+                .dup()
+                .iload(0)
+                .putfield("Holder", "c", "C")
+                .getfield("Holder", "c", "C")
+                .ireturn();
+        })
+        .method(PUBLIC_STATIC, "booleanField", "(I)I", 3, 1, |code| {
+            new_holder(code)
+                .dup()
+                .iload(0)
+                .putfield("Holder", "z", "Z")
+                .getfield("Holder", "z", "Z")
+                .ireturn();
+        })
+        // long[] a = new long[1]; a[0] = 5（iastore 存 int）
+        .method(PUBLIC_STATIC, "intIntoLongArray", "()I", 3, 0, |code| {
+            code.iconst(1).op(NEWARRAY).bytes(&[11]);
+            code.iconst(0).iconst(5).op(IASTORE).iconst(0).ireturn();
+        })
+}
+
+fn interpreter() -> Result<Interpreter> {
+    let mut interpreter = Interpreter::new_with_options(InterpreterOptions {
+        verify: false,
+        ..Default::default()
+    });
+    interpreter.load_class(holder().build()?)?;
+    Ok(interpreter)
+}
+
+fn call(interpreter: &mut Interpreter, method: &str, descriptor: &str, args: Vec<JvmValue>) -> Result<i32> {
+    match interpreter.invoke_static("Holder", method, descriptor, args)? {
+        Some(JvmValue::Int(value)) => Ok(value),
+        other => panic!("unexpected result {:?}", other),
+    }
+}
+
+fn error(interpreter: &mut Interpreter, method: &str) -> String {
+    call(interpreter, method, "()I", vec![]).unwrap_err().to_string()
+}
+
+#[test]
+fn test_mismatched_field_stores() -> Result<()> {
+    let mut interpreter = interpreter()?;
+    let err = error(&mut interpreter, "intIntoLong");
+    assert!(
+        err.contains("Cannot store into field Holder.l: expected long for descriptor J, got int"),
+        "{}",
+        err
+    );
+    let err = error(&mut interpreter, "refIntoInt");
+    assert!(
+        err.contains("Cannot store into field Holder.i: expected int for descriptor I, got reference"),
+        "{}",
+        err
+    );
+    let err = error(&mut interpreter, "longIntoStatic");
+    assert!(
+        err.contains("Cannot store into field Holder.count: expected int for descriptor I, got long"),
+        "{}",
+        err
+    );
+    Ok(())
+}
+
+#[test]
+fn test_mismatched_array_store() -> Result<()> {
+    let mut interpreter = interpreter()?;
+    let err = error(&mut interpreter, "intIntoLongArray");
+    assert!(
+        err.contains("Cannot store into array element: expected long for descriptor J, got int"),
+        "{}",
+        err
+    );
+    Ok(())
+}
+
+/// ArrayStoreCheck.java：引用数组按运行时的组件类型检查存入的对象
+#[test]
+fn test_array_store_checks_component_type() -> Result<()> {
+    let loader = ClassLoader::new(vec!["examples".into()]);
+    let mut interpreter = Interpreter::with_class_loader(loader);
+    interpreter.load_class(ClassFile::from_file("examples/ArrayStoreCheck.class")?)?;
+    let mut call = |method: &str| interpreter.invoke_static("ArrayStoreCheck", method, "()I", vec![]);
+
+    let err = call("wrongType").unwrap_err().to_string();
+    assert!(err.contains("java/lang/ArrayStoreException: java.lang.Integer"), "{}", err);
+    // 异常可以被 catch
+    assert!(matches!(call("caught")?, Some(JvmValue::Int(1))));
+    assert!(matches!(call("compatible")?, Some(JvmValue::Int(4))));
+    Ok(())
+}
+
+#[test]
+fn test_small_int_fields_are_truncated() -> Result<()> {
+    let mut interpreter = interpreter()?;
+    let mut store = |method: &str, value: i32| call(&mut interpreter, method, "(I)I", vec![JvmValue::Int(value)]);
+    // byte：有符号截断，300 = 0x12C → 0x2C
+    assert_eq!(store("byteField", 300)?, 44);
+    assert_eq!(store("byteField", 200)?, -56);
+    // char：无符号截断
+    assert_eq!(store("charField", -1)?, 0xFFFF);
+    assert_eq!(store("charField", 0x1_0041)?, 0x41);
+    // boolean：只保留最低位
+    assert_eq!(store("booleanField", 2)?, 0);
+    assert_eq!(store("booleanField", 3)?, 1);
+    Ok(())
+}
+
+#[test]
+fn test_coerce_to_descriptor() -> Result<()> {
+    assert!(matches!(JvmValue::Int(300).coerce_to_descriptor("S")?, JvmValue::Int(300)));
+    assert!(matches!(JvmValue::Int(40000).coerce_to_descriptor("S")?, JvmValue::Int(-25536)));
+    assert!(matches!(
+        JvmValue::Reference(None).coerce_to_descriptor("[I")?,
+        JvmValue::Reference(None)
+    ));
+    let err = JvmValue::Float(1.0).coerce_to_descriptor("D").unwrap_err();
+    assert_eq!(err.to_string(), "expected double for descriptor D, got float");
+    Ok(())
+}
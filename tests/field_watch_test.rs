@@ -0,0 +1,156 @@
+//! 测试字段监视：GETFIELD/PUTFIELD/GETSTATIC/PUTSTATIC 的回调事件
+
+use rsjvm::classfile::ClassFile;
+use rsjvm::interpreter::watch::{FieldAccessEvent, FieldAccessKind};
+use rsjvm::interpreter::Interpreter;
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::runtime::metaspace::method_key;
+use rsjvm::Result;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+fn load() -> Result<Interpreter> {
+    let mut interpreter = Interpreter::new();
+    interpreter.load_class(ClassFile::from_file("examples/FieldWatchDemo.class")?)?;
+    interpreter.load_class(ClassFile::from_file("examples/SubCounter.class")?)?;
+    Ok(interpreter)
+}
+
+/// 注册监视，返回记录事件的列表
+fn record(interpreter: &mut Interpreter, class: &str, field: &str) -> Rc<RefCell<Vec<FieldAccessEvent>>> {
+    let events = Rc::new(RefCell::new(Vec::new()));
+    let sink = events.clone();
+    interpreter.set_field_watch(class, field, move |event| sink.borrow_mut().push(event.clone()));
+    events
+}
+
+fn run_static_int(interpreter: &mut Interpreter, method_name: &str) -> Result<i32> {
+    let (code, max_locals, max_stack) = {
+        let class_meta = interpreter.metaspace.get_class("FieldWatchDemo")?;
+        let method = class_meta.find_method(method_name, "()I")?;
+        (method.code.clone(), method.max_locals, method.max_stack)
+    };
+    match interpreter.execute_method_with_class(
+        "FieldWatchDemo",
+        &method_key(method_name, "()I"),
+        &code,
+        max_locals,
+        max_stack,
+    )? {
+        Some(JvmValue::Int(v)) => Ok(v),
+        other => panic!("expected int, got {:?}", other),
+    }
+}
+
+/// 把事件简化成 (读/写, 旧值, 新值, 访问方法)
+fn summarize(events: &[FieldAccessEvent]) -> Vec<(FieldAccessKind, i32, Option<i32>, String)> {
+    let int = |v: &JvmValue| match v {
+        JvmValue::Int(i) => *i,
+        other => panic!("expected int, got {:?}", other),
+    };
+    events
+        .iter()
+        .map(|e| {
+            (
+                e.kind,
+                int(&e.old_value),
+                e.new_value.as_ref().map(int),
+                e.accessor_method.clone(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_instance_field_events() -> Result<()> {
+    use FieldAccessKind::{Read, Write};
+
+    let mut interpreter = load()?;
+    let events = record(&mut interpreter, "FieldWatchDemo", "count");
+    assert_eq!(run_static_int(&mut interpreter, "run")?, 2);
+
+    let events = events.borrow();
+    assert_eq!(
+        summarize(&events),
+        vec![
+            (Write, 0, Some(0), "<init>".to_string()),
+            (Read, 0, None, "bump".to_string()),
+            (Write, 0, Some(1), "bump".to_string()),
+            (Read, 1, None, "bump".to_string()),
+            (Write, 1, Some(2), "bump".to_string()),
+            (Read, 2, None, "run".to_string()),
+        ]
+    );
+    // 同一个对象，字段名不会混入 other
+    assert!(events.iter().all(|e| e.field_name == "count" && e.object == events[0].object));
+    assert!(events[0].object.is_some());
+    assert_eq!(events[1].accessor_class, "FieldWatchDemo");
+    Ok(())
+}
+
+#[test]
+fn test_static_field_events() -> Result<()> {
+    use FieldAccessKind::{Read, Write};
+
+    let mut interpreter = load()?;
+    let events = record(&mut interpreter, "FieldWatchDemo", "total");
+    run_static_int(&mut interpreter, "run")?;
+
+    let events = events.borrow();
+    assert_eq!(
+        summarize(&events),
+        vec![
+            (Read, 0, None, "run".to_string()),
+            (Write, 0, Some(10), "run".to_string()),
+            (Read, 10, None, "run".to_string()),
+            (Write, 10, Some(20), "run".to_string()),
+        ]
+    );
+    assert!(events.iter().all(|e| e.object.is_none()));
+    Ok(())
+}
+
+#[test]
+fn test_inherited_field_matches_declaring_class() -> Result<()> {
+    let mut interpreter = load()?;
+    // SubCounter 的字段引用 SubCounter.count，按声明类 FieldWatchDemo 匹配
+    let declared = record(&mut interpreter, "FieldWatchDemo", "count");
+    let subclass = record(&mut interpreter, "SubCounter", "count");
+    assert_eq!(run_static_int(&mut interpreter, "runSub")?, 2);
+
+    assert!(subclass.borrow().is_empty());
+    let declared = declared.borrow();
+    assert!(declared.iter().all(|e| e.class_name == "FieldWatchDemo"));
+    assert!(declared.iter().any(|e| e.accessor_class == "SubCounter" && e.kind == FieldAccessKind::Write));
+    Ok(())
+}
+
+#[test]
+fn test_unrelated_watch_does_not_fire() -> Result<()> {
+    let mut interpreter = load()?;
+    let events = record(&mut interpreter, "FieldWatchDemo", "missing");
+    let other = record(&mut interpreter, "FieldWatchDemo", "other");
+    run_static_int(&mut interpreter, "run")?;
+    assert!(events.borrow().is_empty());
+    // other 在构造器中写一次，bump 中各写一次
+    assert_eq!(other.borrow().len(), 3);
+    Ok(())
+}
+
+#[test]
+fn test_cli_watch_prints_accesses() {
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_rsjvm"))
+        .args(["run", "examples/FieldWatchDemo.class", "-m", "run"])
+        .args(["--watch", "FieldWatchDemo.total"])
+        .output()
+        .expect("failed to run rsjvm");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{}", stdout);
+    let watch_lines: Vec<&str> = stdout.lines().filter(|l| l.starts_with("[watch]")).collect();
+    assert_eq!(watch_lines.len(), 4, "{}", stdout);
+    assert!(
+        watch_lines[1].contains("write FieldWatchDemo.total (static): Int(0) -> Int(10)"),
+        "{}",
+        watch_lines[1]
+    );
+}
@@ -0,0 +1,111 @@
+//! 测试标记-整理：存活对象滑到堆的前部，根和对象字段中的引用都被改写
+
+use rsjvm::classfile::ClassFile;
+use rsjvm::gc::{GarbageCollector, GcRootSet, GcStrategy};
+use rsjvm::interpreter::{Interpreter, InterpreterOptions};
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::runtime::heap::Fragmentation;
+use rsjvm::runtime::Heap;
+use rsjvm::Result;
+
+#[test]
+fn test_compaction_keeps_alternating_survivors_intact() -> Result<()> {
+    let mut heap = Heap::new();
+    let mut gc = GarbageCollector::with_strategy(GcStrategy::MarkCompact);
+
+    // 10 个对象，偶数下标的存活：前 4 个由根直接引用，最后一个只挂在数组里
+    let objects = (0..10)
+        .map(|_| heap.allocate("Node".to_string()))
+        .collect::<Result<Vec<_>>>()?;
+    for (i, &object) in objects.iter().enumerate() {
+        heap.set_field(object, "Node", "value", JvmValue::Int(i as i32))?;
+    }
+    let holder = heap.allocate_reference_array("Node", 1)?;
+    heap.array_set(holder, 0, JvmValue::Reference(Some(objects[8])))?;
+    // 存活对象之间互相引用：0 -> 2 -> 4 -> 6
+    for pair in [0, 2, 4, 6].windows(2) {
+        let next = JvmValue::Reference(Some(objects[pair[1]]));
+        heap.set_field(objects[pair[0]], "Node", "next", next)?;
+    }
+
+    let mut locals: Vec<JvmValue> = [0, 2, 4, 6]
+        .iter()
+        .map(|&i| JvmValue::Reference(Some(objects[i])))
+        .collect();
+    let mut holder_root = holder;
+    let mut roots = GcRootSet::new();
+    for local in &mut locals {
+        roots.add_value(local);
+    }
+    roots.add_handle(&mut holder_root);
+    assert_eq!(gc.collect_roots(&mut heap, roots), 5);
+
+    let event = &gc.events()[0];
+    assert!(event.compact.is_some());
+    assert_eq!(event.before, Fragmentation { slots: 11, live: 11, holes: 0 });
+    assert_eq!(event.after, Fragmentation { slots: 11, live: 6, holes: 0 });
+
+    // 所有存活对象都在前 6 个槽位中，字段值没有变
+    let survivors: Vec<_> = locals
+        .iter()
+        .map(|local| match local {
+            JvmValue::Reference(Some(object_ref)) => *object_ref,
+            other => panic!("{:?}", other),
+        })
+        .collect();
+    for (&object_ref, expected) in survivors.iter().zip([0, 2, 4, 6]) {
+        assert!(object_ref.index < 6, "{}", object_ref);
+        assert!(matches!(heap.get_field(object_ref, "Node", "value")?, JvmValue::Int(v) if v == expected));
+    }
+    for pair in survivors.windows(2) {
+        assert!(matches!(heap.get_field(pair[0], "Node", "next")?, JvmValue::Reference(Some(r)) if r == pair[1]));
+    }
+    let last = match heap.array_get(holder_root, 0)? {
+        JvmValue::Reference(Some(object_ref)) => object_ref,
+        other => panic!("{:?}", other),
+    };
+    assert!(matches!(heap.get_field(last, "Node", "value")?, JvmValue::Int(8)));
+
+    // 移动前的引用已经失效
+    let err = heap.get(objects[4]).unwrap_err();
+    assert!(err.to_string().contains("stale object reference"), "{}", err);
+
+    // 新对象紧接在存活对象之后分配
+    assert_eq!(heap.allocate("Node".to_string())?.index, 6);
+    Ok(())
+}
+
+#[test]
+fn test_mark_sweep_leaves_holes() {
+    let mut heap = Heap::new();
+    let mut gc = GarbageCollector::new();
+    let objects: Vec<_> = (0..4).map(|_| heap.allocate("Node".to_string()).unwrap()).collect();
+    gc.add_root(objects[1]);
+    gc.add_root(objects[3]);
+    gc.collect(&mut heap);
+
+    let event = &gc.events()[0];
+    assert_eq!(event.compact, None);
+    assert_eq!(event.after, Fragmentation { slots: 4, live: 2, holes: 2 });
+    assert_eq!(event.after.ratio(), 0.5);
+    assert!(heap.is_live(objects[3]));
+}
+
+#[test]
+fn test_interpreter_runs_with_mark_compact() -> Result<()> {
+    let mut interpreter = Interpreter::new_with_options(InterpreterOptions {
+        gc_threshold: Some(100),
+        gc_strategy: GcStrategy::MarkCompact,
+        ..Default::default()
+    });
+    interpreter.load_class(ClassFile::from_file("examples/GcPressure.class")?)?;
+    let result = interpreter.invoke_static("GcPressure", "keepChain", "(I)I", vec![JvmValue::Int(2000)])?;
+    assert!(matches!(result, Some(JvmValue::Int(2000))), "{:?}", result);
+    assert!(interpreter.gc_stats().collections > 0);
+    assert!(!interpreter.gc_stats().compact_time.is_zero());
+
+    interpreter.collect_garbage();
+    assert_eq!(interpreter.heap.fragmentation().holes, 0);
+    assert_eq!(interpreter.heap.object_count(), 2000);
+    Ok(())
+}
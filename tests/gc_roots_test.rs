@@ -0,0 +1,57 @@
+//! 测试自动 GC：根来自线程栈和静态字段，分配压力达到阈值时自动回收
+
+use rsjvm::classfile::ClassFile;
+use rsjvm::interpreter::{Interpreter, InterpreterOptions};
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::Result;
+
+fn interpreter(gc_threshold: Option<usize>) -> Result<Interpreter> {
+    let mut interpreter = Interpreter::new_with_options(InterpreterOptions {
+        gc_threshold,
+        ..Default::default()
+    });
+    interpreter.load_class(ClassFile::from_file("examples/GcPressure.class")?)?;
+    Ok(interpreter)
+}
+
+fn call(interpreter: &mut Interpreter, method: &str, n: i32) -> Result<i32> {
+    match interpreter.invoke_static("GcPressure", method, "(I)I", vec![JvmValue::Int(n)])? {
+        Some(JvmValue::Int(v)) => Ok(v),
+        other => panic!("{} 期望返回 Int, 实际: {:?}", method, other),
+    }
+}
+
+#[test]
+fn test_temporary_objects_are_collected() -> Result<()> {
+    let mut interpreter = interpreter(Some(1000))?;
+    // 每轮一个对象和一个数组，共 20000 次分配
+    assert_eq!(call(&mut interpreter, "churn", 10_000)?, 49_995_000);
+    // 存活对象不超过阈值加上回收时仍然可达的几个对象
+    let live = interpreter.heap.object_count();
+    assert!(live <= 1010, "{}", live);
+    let stats = interpreter.gc_stats();
+    assert!(stats.collections >= 19, "{}", stats);
+    assert!(stats.objects_collected >= 18_000, "{}", stats);
+    Ok(())
+}
+
+#[test]
+fn test_objects_reachable_from_statics_survive() -> Result<()> {
+    let mut interpreter = interpreter(Some(100))?;
+    assert_eq!(call(&mut interpreter, "keepChain", 5000)?, 5000);
+    assert!(interpreter.gc_stats().collections > 0);
+
+    // 链表挂在静态字段上，手动回收也不会释放；循环中的 garbage 都被回收了
+    interpreter.collect_garbage();
+    assert_eq!(interpreter.heap.object_count(), 5000);
+    Ok(())
+}
+
+#[test]
+fn test_automatic_gc_can_be_disabled() -> Result<()> {
+    let mut interpreter = interpreter(None)?;
+    call(&mut interpreter, "churn", 1000)?;
+    assert_eq!(interpreter.gc_stats().collections, 0);
+    assert_eq!(interpreter.heap.object_count(), 2000);
+    Ok(())
+}
@@ -0,0 +1,105 @@
+// GC 停顿记录和 Chrome trace 导出测试
+
+use rsjvm::gc::GarbageCollector;
+use rsjvm::runtime::Heap;
+
+/// 模拟小堆上的大量分配：对象数达到上限时回收，只保留少数根
+fn allocate_with_limit(heap: &mut Heap, gc: &mut GarbageCollector, total: usize, limit: usize) {
+    for i in 0..total {
+        if heap.object_count() >= limit {
+            gc.collect(heap);
+        }
+        let obj = heap.allocate("Temp".to_string()).unwrap();
+        if i % 100 == 0 {
+            gc.add_root(obj);
+        }
+    }
+}
+
+/// 从 trace JSON 中取出 GC 事件（不含阶段子事件）的 dur 字段
+fn gc_event_durations(trace: &str) -> Vec<f64> {
+    trace
+        .lines()
+        .filter(|line| line.contains(r#""cat":"gc","#))
+        .map(|line| {
+            let dur = line.split(r#""dur":"#).nth(1).unwrap();
+            let end = dur.find(',').unwrap();
+            dur[..end].parse().unwrap()
+        })
+        .collect()
+}
+
+#[test]
+fn test_gc_events_recorded_per_collection() {
+    let mut heap = Heap::new();
+    let mut gc = GarbageCollector::new();
+    allocate_with_limit(&mut heap, &mut gc, 1000, 50);
+
+    let stats = gc.stats();
+    assert!(stats.collections > 10, "expected many collections: {:?}", stats);
+    assert_eq!(gc.events().len(), stats.collections);
+
+    // 每次回收都把堆压回根的数量
+    let collected: usize = gc.events().iter().map(|e| e.collected).sum();
+    assert_eq!(stats.objects_collected, collected);
+    assert!(heap.object_count() <= 50);
+
+    // 事件编号连续、开始时间单调
+    for (i, pair) in gc.events().windows(2).enumerate() {
+        assert_eq!(pair[0].id, i + 1);
+        assert!(pair[0].start <= pair[1].start);
+    }
+
+    // 各阶段之和等于总停顿，最长停顿不超过总停顿
+    assert_eq!(
+        stats.root_scan_time + stats.mark_time + stats.sweep_time,
+        stats.total_pause
+    );
+    let worst = gc.events().iter().map(|e| e.pause()).max().unwrap();
+    assert_eq!(stats.worst_pause, worst);
+}
+
+#[test]
+fn test_chrome_trace_matches_stats() {
+    let mut heap = Heap::new();
+    let mut gc = GarbageCollector::new();
+    allocate_with_limit(&mut heap, &mut gc, 2000, 64);
+
+    let trace = gc.chrome_trace();
+    assert!(trace.starts_with(r#"{"traceEvents":["#));
+    assert!(trace.contains(r#""name":"thread_name","ph":"M","pid":1,"tid":2,"args":{"name":"GC"}"#));
+
+    let durations = gc_event_durations(&trace);
+    assert_eq!(durations.len(), gc.stats().collections);
+
+    // trace 里的微秒保留三位小数，误差不超过每个事件 1ns
+    let summed: f64 = durations.iter().sum();
+    let expected = gc.stats().total_pause.as_nanos() as f64 / 1000.0;
+    let tolerance = durations.len() as f64 * 0.001;
+    assert!(
+        (summed - expected).abs() <= tolerance,
+        "summed {}us vs stats {}us",
+        summed,
+        expected
+    );
+
+    // 每次回收有三个阶段子事件
+    assert_eq!(
+        trace.matches(r#""cat":"gc.phase""#).count(),
+        durations.len() * 3
+    );
+}
+
+#[test]
+fn test_gc_stats_display() {
+    let mut heap = Heap::new();
+    let mut gc = GarbageCollector::new();
+    heap.allocate("Temp".to_string()).unwrap();
+    gc.collect(&mut heap);
+
+    let summary = gc.stats().to_string();
+    assert!(summary.starts_with("GC: 1 collections, total pause "));
+    assert!(summary.contains("worst pause"));
+    assert!(summary.contains("sweep"));
+    assert_eq!(gc.stats().worst_pause, gc.stats().total_pause);
+}
@@ -0,0 +1,178 @@
+//! 测试分代回收：minor GC 只扫描新生代，老年代→新生代的引用（记忆集）让新对象存活，
+//! 熬过几次 minor GC 的对象晋升
+
+use rsjvm::classfile::ClassFile;
+use rsjvm::gc::{GarbageCollector, GcKind, GcRootSet, GcStrategy};
+use rsjvm::interpreter::{Interpreter, InterpreterOptions};
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::runtime::Heap;
+use rsjvm::Result;
+
+const NURSERY: usize = 100;
+
+fn interpreter() -> Result<Interpreter> {
+    let mut interpreter = Interpreter::new_with_options(InterpreterOptions {
+        gc_strategy: GcStrategy::Generational {
+            nursery_size: NURSERY,
+            promotion_age: 2,
+        },
+        ..Default::default()
+    });
+    interpreter.load_class(ClassFile::from_file("examples/GenerationalDemo.class")?)?;
+    interpreter.load_class(ClassFile::from_file("examples/GenNode.class")?)?;
+    Ok(interpreter)
+}
+
+fn call(interpreter: &mut Interpreter, method: &str, n: i32) -> Result<i32> {
+    match interpreter.invoke_static("GenerationalDemo", method, "(I)I", vec![JvmValue::Int(n)])? {
+        Some(JvmValue::Int(v)) => Ok(v),
+        other => panic!("{} 期望返回 Int, 实际: {:?}", method, other),
+    }
+}
+
+/// 建好 1000 个节点的长期存活对象图，完整回收一次让它们全部进入老年代
+fn interpreter_with_old_graph() -> Result<Interpreter> {
+    let mut interpreter = interpreter()?;
+    assert_eq!(call(&mut interpreter, "buildGraph", 1000)?, 1000);
+    interpreter.collect_garbage();
+    assert_eq!(interpreter.heap.nursery().unwrap().len(), 0);
+    Ok(interpreter)
+}
+
+#[test]
+fn test_default_strategy_is_not_generational() {
+    let interpreter = Interpreter::new();
+    assert!(interpreter.heap.nursery().is_none());
+    assert_eq!(InterpreterOptions::default().gc_strategy, GcStrategy::MarkSweep);
+}
+
+#[test]
+fn test_allocation_loop_triggers_minor_collections_only() -> Result<()> {
+    let mut interpreter = interpreter_with_old_graph()?;
+    let before = interpreter.gc_stats().clone();
+    let events_before = interpreter.gc_events().len();
+
+    assert_eq!(call(&mut interpreter, "churn", 10_000)?, 49_995_000);
+
+    let stats = interpreter.gc_stats();
+    let minors = stats.minor_collections - before.minor_collections;
+    assert!(minors >= 50, "{}", stats);
+    // 没有完整回收，临时对象都没有熬到晋升
+    assert_eq!(stats.collections - before.collections, minors);
+    assert_eq!(stats.objects_promoted, before.objects_promoted, "{}", stats);
+    assert!(stats.objects_collected - before.objects_collected >= 9_000, "{}", stats);
+
+    // 每次 minor GC 只标记新生代对象，从不遍历 1000 个节点的老年代对象图
+    for event in &interpreter.gc_events()[events_before..] {
+        assert_eq!(event.kind, GcKind::Minor);
+        assert!(event.marked <= NURSERY, "{}", event);
+        assert_eq!(event.promoted, 0);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_builtin_allocations_trigger_minor_collections() -> Result<()> {
+    // Integer.valueOf 和字符串拼接的结果在内置方法中分配，同样要在新生代满时触发 minor GC
+    for (method, expected) in [("boxChurn", 59_995_000), ("concatChurn", 48_890)] {
+        let mut interpreter = interpreter_with_old_graph()?;
+        let before = interpreter.gc_stats().clone();
+        assert_eq!(call(&mut interpreter, method, 10_000)?, expected);
+
+        let stats = interpreter.gc_stats();
+        let minors = stats.minor_collections - before.minor_collections;
+        assert!(minors >= 50, "{}: {}", method, stats);
+        assert_eq!(stats.collections - before.collections, minors, "{}: {}", method, stats);
+        let collected = stats.objects_collected - before.objects_collected;
+        assert!(collected >= 9_000, "{}: {}", method, stats);
+        assert!(interpreter.heap.nursery().unwrap().len() <= NURSERY, "{}", method);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_old_to_young_references_survive_minor_collections() -> Result<()> {
+    let mut interpreter = interpreter_with_old_graph()?;
+    let minors = interpreter.gc_stats().minor_collections;
+
+    // 每轮的 churn(300) 触发约三次 minor GC，期间新对象只被老年代对象引用
+    assert_eq!(call(&mut interpreter, "linkYoung", 20)?, 3 * (20 * 19 / 2));
+    assert!(interpreter.gc_stats().minor_collections - minors >= 40);
+    // 对象图本身完好
+    let count = interpreter.invoke_static("GenerationalDemo", "count", "()I", vec![])?;
+    assert!(matches!(count, Some(JvmValue::Int(1000))), "{:?}", count);
+    Ok(())
+}
+
+#[test]
+fn test_survivors_are_promoted_after_enough_minor_collections() -> Result<()> {
+    let mut heap = Heap::new();
+    heap.enable_nursery(10, 2);
+    let mut gc = GarbageCollector::with_strategy(GcStrategy::generational());
+
+    let kept = heap.allocate("Node")?;
+    let garbage = heap.allocate("Node")?;
+    gc.add_root(kept);
+    assert!(heap.is_young(kept));
+
+    assert_eq!(gc.collect_minor(&mut heap, GcRootSet::new()), 1);
+    assert!(!heap.is_live(garbage));
+    assert!(heap.is_young(kept), "一次 minor GC 后还在新生代");
+    gc.collect_minor(&mut heap, GcRootSet::new());
+    assert!(heap.is_live(kept) && !heap.is_young(kept), "两次之后晋升");
+    assert_eq!(gc.stats().objects_promoted, 1);
+    assert_eq!(gc.stats().minor_collections, 2);
+    Ok(())
+}
+
+#[test]
+fn test_write_barrier_records_old_to_young_references() -> Result<()> {
+    let mut heap = Heap::new();
+    heap.enable_nursery(10, 1);
+    let mut gc = GarbageCollector::with_strategy(GcStrategy::generational());
+
+    let old = heap.allocate("Node")?;
+    let old_array = heap.allocate_reference_array("Node", 2)?;
+    gc.add_root(old);
+    gc.add_root(old_array);
+    gc.collect_minor(&mut heap, GcRootSet::new());
+    assert!(!heap.is_young(old) && !heap.is_young(old_array));
+
+    // putfield 和 aastore 各写入一个新生代对象
+    let by_field = heap.allocate("Node")?;
+    let by_element = heap.allocate("Node")?;
+    let unreferenced = heap.allocate("Node")?;
+    heap.set_field(old, "Node", "next", JvmValue::Reference(Some(by_field)))?;
+    heap.array_set(old_array, 1, JvmValue::Reference(Some(by_element)))?;
+    let mut remembered: Vec<_> = heap.nursery().unwrap().remembered().collect();
+    remembered.sort();
+    assert_eq!(remembered, [old, old_array]);
+
+    // minor GC 不遍历老年代，只靠记忆集找到这两个对象
+    assert_eq!(gc.collect_minor(&mut heap, GcRootSet::new()), 1);
+    assert!(heap.is_live(by_field) && heap.is_live(by_element));
+    assert!(!heap.is_live(unreferenced));
+    // 都晋升了，老年代不再引用新生代对象，记忆集清空
+    assert_eq!(heap.nursery().unwrap().remembered().count(), 0);
+
+    // 老年代对象之间的引用不进入记忆集
+    heap.set_field(by_field, "Node", "next", JvmValue::Reference(Some(old)))?;
+    assert_eq!(heap.nursery().unwrap().remembered().count(), 0);
+    Ok(())
+}
+
+#[test]
+fn test_full_collection_promotes_the_nursery() -> Result<()> {
+    let mut heap = Heap::new();
+    heap.enable_nursery(10, 5);
+    let mut gc = GarbageCollector::with_strategy(GcStrategy::generational());
+    let kept = heap.allocate("Node")?;
+    heap.allocate("Node")?;
+    gc.add_root(kept);
+
+    assert_eq!(gc.collect(&mut heap), 1);
+    assert!(heap.is_live(kept) && !heap.is_young(kept));
+    assert_eq!(gc.events()[0].kind, GcKind::Full);
+    assert_eq!(gc.events()[0].promoted, 1);
+    Ok(())
+}
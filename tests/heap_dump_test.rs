@@ -0,0 +1,94 @@
+//! 测试堆转储：类名、字段名和引用边
+
+use rsjvm::classloader::ClassLoader;
+use rsjvm::interpreter::Interpreter;
+use rsjvm::runtime::heap_dump::{DumpValue, HeapDump};
+use rsjvm::Result;
+use std::process::Command;
+
+fn run_object_graph() -> Result<Interpreter> {
+    let mut interpreter = Interpreter::with_class_loader(ClassLoader::new(vec!["examples".into()]));
+    interpreter.ensure_class_loaded("ObjectGraph")?;
+    interpreter.run_main("ObjectGraph", &[])?;
+    Ok(interpreter)
+}
+
+fn field<'a>(dump: &'a HeapDump, index: u32, name: &str) -> &'a DumpValue {
+    let object = dump.slots[index as usize].object.as_ref().expect("live object");
+    &object.fields.iter().find(|(n, _)| n == name).expect(name).1
+}
+
+#[test]
+fn test_dump_contains_classes_fields_and_edges() -> Result<()> {
+    let interpreter = run_object_graph()?;
+    let dump = interpreter.heap_dump();
+
+    let nodes = interpreter.heap.find_by_class("GraphNode");
+    assert_eq!(nodes.len(), 3);
+    let [a, b, c] = [nodes[0].index, nodes[1].index, nodes[2].index];
+    assert_eq!(dump.slots[a as usize].object.as_ref().unwrap().class_name, "GraphNode");
+
+    let names: Vec<_> = dump.slots[a as usize]
+        .object
+        .as_ref()
+        .unwrap()
+        .fields
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect();
+    assert_eq!(names, ["children", "label", "left", "right", "value"]);
+    assert_eq!(field(&dump, a, "value"), &DumpValue::Int(1));
+    assert_eq!(field(&dump, b, "right"), &DumpValue::Null);
+
+    let label = match field(&dump, a, "label") {
+        DumpValue::Ref(index) => *index,
+        other => panic!("{:?}", other),
+    };
+    assert_eq!(field(&dump, label, "value"), &DumpValue::Str("root".to_string()));
+
+    let edges = dump.edges();
+    for edge in [(a, "left", b), (a, "right", c), (c, "left", a)] {
+        assert!(edges.contains(&edge), "{:?} not in {:?}", edge, edges);
+    }
+    let children = interpreter.heap.find_by_class("[LGraphNode;");
+    assert_eq!(children.len(), 1);
+    let children = children[0].index;
+    assert!(edges.contains(&(a, "children", children)));
+    assert!(edges.contains(&(children, "[0]", b)));
+    assert!(edges.contains(&(children, "[1]", c)));
+
+    // 文本形式里引用写作 @索引
+    let text = dump.to_string();
+    assert!(text.contains(&format!("@{} GraphNode\n", a)), "{}", text);
+    assert!(text.contains(&format!("    left = @{}\n", b)), "{}", text);
+    assert!(text.contains("    value = \"root\"\n"), "{}", text);
+    Ok(())
+}
+
+#[test]
+fn test_dump_shows_free_slots() -> Result<()> {
+    let mut interpreter = run_object_graph()?;
+    let live = interpreter.heap.object_count();
+    // args 数组只在 main 的栈帧里，main 返回后就是垃圾
+    assert_eq!(interpreter.collect_garbage(), 1);
+
+    let dump = interpreter.heap_dump();
+    assert_eq!(dump.objects().count(), live - 1);
+    let free: Vec<_> = dump.slots.iter().filter(|slot| slot.object.is_none()).collect();
+    assert_eq!(free.len(), 1);
+    assert_eq!(free[0].generation, 1);
+    assert!(dump.to_string().contains("<空闲> (代数 1)"));
+    Ok(())
+}
+
+#[test]
+fn test_cli_dump_heap() {
+    let output = Command::new(env!("CARGO_BIN_EXE_rsjvm"))
+        .args(["run", "examples/ObjectGraph.class", "--dump-heap"])
+        .output()
+        .expect("failed to run rsjvm");
+    assert!(output.status.success());
+    let text = String::from_utf8_lossy(&output.stdout);
+    assert!(text.contains("=== 堆转储 (6 个存活对象, 6 个槽位) ==="), "{}", text);
+    assert!(text.contains("@5 [LGraphNode;\n    [0] = @3\n    [1] = @4\n"), "{}", text);
+}
@@ -0,0 +1,53 @@
+//! 测试带代数的对象引用：槽位被回收复用后，旧引用失效
+
+use rsjvm::gc::GarbageCollector;
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::runtime::{Heap, ObjRef};
+use rsjvm::Result;
+
+#[test]
+fn test_stale_reference_after_gc_and_reuse() -> Result<()> {
+    let mut heap = Heap::new();
+    let mut gc = GarbageCollector::new();
+
+    let kept = heap.allocate("Kept".to_string())?;
+    let old = heap.allocate("Old".to_string())?;
+    heap.set_field(old, "Old", "x", JvmValue::Int(1))?;
+    gc.add_root(kept);
+    assert_eq!(gc.collect(&mut heap), 1);
+
+    // 新对象复用了 old 的槽位，但代数不同
+    let new = heap.allocate("New".to_string())?;
+    assert_eq!(new.index, old.index);
+    assert_eq!(new.generation, old.generation + 1);
+    assert!(!heap.is_live(old));
+    assert!(heap.is_live(new));
+
+    for err in [
+        heap.get(old).map(|_| ()).unwrap_err(),
+        heap.get_field(old, "Old", "x").map(|_| ()).unwrap_err(),
+        heap.set_field(old, "Old", "x", JvmValue::Int(2)).unwrap_err(),
+        heap.free(old).unwrap_err(),
+    ] {
+        assert!(err.to_string().contains("stale object reference"), "{}", err);
+    }
+
+    assert_eq!(heap.get(new)?.class_name, "New");
+    heap.set_field(new, "New", "y", JvmValue::Int(3))?;
+    assert!(matches!(heap.get_field(new, "New", "y")?, JvmValue::Int(3)));
+    assert_eq!(heap.get(kept)?.class_name, "Kept");
+    Ok(())
+}
+
+#[test]
+fn test_reference_stays_small() {
+    assert_eq!(std::mem::size_of::<ObjRef>(), 8);
+    assert_eq!(std::mem::size_of::<JvmValue>(), 16);
+
+    let r = ObjRef {
+        index: 26,
+        generation: 3,
+    };
+    assert_eq!(r.to_string(), "0x1a#3");
+    assert_eq!(format!("{:x}", r), "1a");
+}
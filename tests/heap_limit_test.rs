@@ -0,0 +1,83 @@
+//! 测试堆上限：先尝试 GC，回收不出空间时抛出 OutOfMemoryError
+
+use rsjvm::classfile::ClassFile;
+use rsjvm::interpreter::{Interpreter, InterpreterOptions};
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::runtime::{Heap, JavaException};
+use rsjvm::Result;
+
+fn interpreter(max_heap_objects: usize) -> Result<Interpreter> {
+    let mut interpreter = Interpreter::new_with_options(InterpreterOptions {
+        max_heap_objects: Some(max_heap_objects),
+        ..Default::default()
+    });
+    interpreter.load_class(ClassFile::from_file("examples/HeapLimit.class")?)?;
+    Ok(interpreter)
+}
+
+fn call(interpreter: &mut Interpreter, method: &str, n: i32) -> Result<Option<JvmValue>> {
+    interpreter.invoke_static("HeapLimit", method, "(I)I", vec![JvmValue::Int(n)])
+}
+
+#[test]
+fn test_unreferenced_objects_fit_after_gc() -> Result<()> {
+    let mut interpreter = interpreter(10)?;
+    let result = call(&mut interpreter, "churn", 20)?;
+    assert!(matches!(result, Some(JvmValue::Int(20))), "{:?}", result);
+    assert!(interpreter.heap.object_count() <= 10);
+    assert!(interpreter.gc_stats().collections > 0);
+    Ok(())
+}
+
+#[test]
+fn test_builtin_allocations_collect_before_oom() -> Result<()> {
+    // Integer.valueOf 在内置方法中分配，堆满时同样先回收再重试
+    let mut interpreter = interpreter(10)?;
+    let result = call(&mut interpreter, "boxChurn", 100)?;
+    assert!(matches!(result, Some(JvmValue::Int(104_950))), "{:?}", result);
+    assert!(interpreter.heap.object_count() <= 10);
+    assert!(interpreter.gc_stats().collections > 0);
+    Ok(())
+}
+
+#[test]
+fn test_referenced_objects_exceed_limit() -> Result<()> {
+    let mut interpreter = interpreter(10)?;
+    let err = call(&mut interpreter, "retain", 20).unwrap_err();
+    let message = format!("{:#}", err);
+    assert!(message.contains("java/lang/OutOfMemoryError"), "{}", message);
+    assert!(
+        message.contains("Java heap space: 10/10 objects live (HeapLimit: 10)"),
+        "{}",
+        message
+    );
+    Ok(())
+}
+
+#[test]
+fn test_out_of_memory_error_can_be_caught() -> Result<()> {
+    let mut interpreter = interpreter(10)?;
+    let result = call(&mut interpreter, "catchOom", 20)?;
+    assert!(matches!(result, Some(JvmValue::Int(-1))), "{:?}", result);
+
+    // 异常处理之后链表不可达，可以继续分配
+    let result = call(&mut interpreter, "retain", 5)?;
+    assert!(matches!(result, Some(JvmValue::Int(5))), "{:?}", result);
+    Ok(())
+}
+
+#[test]
+fn test_heap_capacity_limit() -> Result<()> {
+    let mut heap = Heap::with_capacity_limit(2);
+    let first = heap.allocate("A".to_string())?;
+    heap.allocate_string("b")?;
+    assert!(heap.is_full());
+    let err = heap.allocate_array(rsjvm::runtime::heap::ArrayType::Int, 1).unwrap_err();
+    let exception = err.downcast::<JavaException>().unwrap();
+    assert_eq!(exception.class_name, "java/lang/OutOfMemoryError");
+
+    heap.free(first)?;
+    heap.allocate("C".to_string())?;
+    assert_eq!(heap.object_count(), 2);
+    Ok(())
+}
@@ -0,0 +1,94 @@
+//! 测试 invokedynamic 字符串拼接：Java 9+ 的 javac 把 `"a" + x + "b"` 编译成
+//! StringConcatFactory.makeConcatWithConstants 调用点（IndyConcat 用 --release 11 编译）
+
+use rsjvm::classfile::ClassFile;
+use rsjvm::{Jvm, JvmBuilder, Result};
+
+/// IndyConcat 是 Java 11 的 class 文件（主版本号 55），超过默认支持的版本
+fn jvm() -> Jvm {
+    JvmBuilder::new().class_path("examples").max_major_version(55).build()
+}
+
+#[test]
+fn test_bootstrap_methods_attribute() -> Result<()> {
+    let class_file = ClassFile::from_file("examples/IndyConcat.class")?;
+    let bootstrap_methods = class_file.get_bootstrap_methods()?;
+    // 五种拼接配方加一个 lambda（sameAsLiteral 和 wrap 的配方相同，共用一个引导方法）
+    assert_eq!(bootstrap_methods.len(), 6);
+    assert_eq!(bootstrap_methods[0].arguments.len(), 1);
+    // 含控制字符的字面量作为额外的静态常量
+    assert_eq!(bootstrap_methods[2].arguments.len(), 3);
+
+    // Java 8 编译的类没有这个属性
+    let concat = ClassFile::from_file("examples/Concat.class")?;
+    assert!(concat.get_bootstrap_methods()?.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_concat_with_int() -> Result<()> {
+    let mut jvm = jvm();
+    let text: String = jvm.call_static_typed("IndyConcat", "wrap", "(I)Ljava/lang/String;", (5,))?;
+    assert_eq!(text, "a5b");
+    let text: String = jvm.call_static_typed("IndyConcat", "wrap", "(I)Ljava/lang/String;", (-12,))?;
+    assert_eq!(text, "a-12b");
+    Ok(())
+}
+
+#[test]
+fn test_concat_typed_arguments() -> Result<()> {
+    let mut jvm = jvm();
+    let text: String = jvm.call_static_typed(
+        "IndyConcat",
+        "mixed",
+        "(Ljava/lang/String;JZCD)Ljava/lang/String;",
+        ("total", 7i64, true, 'x', 0.5f64),
+    )?;
+    assert_eq!(text, "total: 7 true x 0.5");
+    Ok(())
+}
+
+#[test]
+fn test_concat_static_constants() -> Result<()> {
+    let mut jvm = jvm();
+    let text: String =
+        jvm.call_static_typed("IndyConcat", "withControlChars", "(I)Ljava/lang/String;", (3,))?;
+    assert_eq!(text, "\u{1}3\u{2}");
+    Ok(())
+}
+
+#[test]
+fn test_concat_call_site_reused_in_loop() -> Result<()> {
+    let mut jvm = jvm();
+    let text: String = jvm.call_static_typed("IndyConcat", "loop", "(I)Ljava/lang/String;", (12,))?;
+    assert_eq!(text, "01234567891011");
+    Ok(())
+}
+
+#[test]
+fn test_concat_results_are_not_interned() -> Result<()> {
+    let mut jvm = JvmBuilder::new()
+        .class_path("examples")
+        .max_major_version(55)
+        .max_heap_objects(100)
+        .build();
+    let same: bool = jvm.call_static_typed("IndyConcat", "sameAsLiteral", "(I)Z", (1,))?;
+    assert!(!same);
+    // 驻留的字符串是 GC 根：如果拼接结果被驻留，1000 个不同的字符串会超过堆上限
+    let total: i32 = jvm.call_static_typed("IndyConcat", "churn", "(I)I", (1000,))?;
+    assert_eq!(total, 3890);
+    Ok(())
+}
+
+#[test]
+fn test_unsupported_bootstrap() -> Result<()> {
+    let mut jvm = jvm();
+    let err = jvm.call_static("IndyConcat", "lambda", "()I", &[]).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("unsupported invokedynamic bootstrap: java/lang/invoke/LambdaMetafactory.metafactory"),
+        "{}",
+        err
+    );
+    Ok(())
+}
@@ -0,0 +1,92 @@
+//! 测试沿继承层次解析方法和字段：子类只有编译器生成的构造器，其余都继承自父类
+
+use rsjvm::classfile::ClassFile;
+use rsjvm::classloader::ClassLoader;
+use rsjvm::interpreter::Interpreter;
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::Result;
+
+fn interpreter() -> Result<Interpreter> {
+    let mut interpreter = Interpreter::with_class_loader(ClassLoader::new(vec!["examples".into()]));
+    interpreter.load_class(ClassFile::from_file("examples/InheritUse.class")?)?;
+    Ok(interpreter)
+}
+
+fn call(interpreter: &mut Interpreter, method: &str, descriptor: &str, args: Vec<JvmValue>) -> Result<i32> {
+    match interpreter.invoke_static("InheritUse", method, descriptor, args)? {
+        Some(JvmValue::Int(v)) => Ok(v),
+        other => panic!("{} 期望返回 Int, 实际: {:?}", method, other),
+    }
+}
+
+#[test]
+fn test_inherited_static_method_and_field() -> Result<()> {
+    let mut interpreter = interpreter()?;
+    assert_eq!(call(&mut interpreter, "callHelper", "()I", vec![])?, 42);
+    // calls 只存在于声明它的 InheritBase 中
+    assert_eq!(call(&mut interpreter, "readCalls", "()I", vec![])?, 1);
+    assert_eq!(call(&mut interpreter, "writeCalls", "(I)I", vec![JvmValue::Int(7)])?, 7);
+
+    let base = interpreter.metaspace.get_class("InheritBase")?;
+    assert!(matches!(base.static_fields.get("calls"), Some(JvmValue::Int(7))));
+    let child = interpreter.metaspace.get_class("InheritChild")?;
+    assert!(child.static_fields.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_inherited_instance_field_and_method() -> Result<()> {
+    let mut interpreter = interpreter()?;
+    assert_eq!(call(&mut interpreter, "fields", "()I", vec![])?, 10);
+    Ok(())
+}
+
+#[test]
+fn test_metaspace_hierarchy_lookups() -> Result<()> {
+    let mut interpreter = interpreter()?;
+    interpreter.ensure_class_loaded("InheritChild")?;
+    let metaspace = &interpreter.metaspace;
+
+    let (owner, method) = metaspace.resolve_method_in_hierarchy("InheritChild", "helper", "(I)I")?;
+    assert_eq!(owner, "InheritBase");
+    assert!(method.is_static);
+
+    // 父类没有实现，父接口的 default 方法
+    let (owner, method) = metaspace.resolve_method_in_hierarchy("InheritChild", "answer", "()I")?;
+    assert_eq!(owner, "InheritIface");
+    assert!(!method.is_abstract);
+
+    let (owner, field) = metaspace.resolve_field_in_hierarchy("InheritChild", "value", "I")?;
+    assert_eq!((owner.as_str(), field.is_static), ("InheritBase", false));
+    let (owner, field) = metaspace.resolve_field_in_hierarchy("InheritChild", "ANSWER", "I")?;
+    assert_eq!(owner, "InheritIface");
+    assert!(matches!(field.constant_value, Some(JvmValue::Int(42))));
+
+    let err = metaspace.resolve_method_in_hierarchy("InheritChild", "missing", "()V").unwrap_err();
+    assert_eq!(err.to_string(), "java/lang/NoSuchMethodError: InheritChild.missing()V");
+    let err = metaspace.resolve_field_in_hierarchy("InheritChild", "missing", "I").unwrap_err();
+    assert_eq!(err.to_string(), "java/lang/NoSuchFieldError: InheritChild.missing:I");
+    Ok(())
+}
+
+#[test]
+fn test_invoke_static_api_finds_inherited_methods() -> Result<()> {
+    let mut interpreter = interpreter()?;
+    interpreter.ensure_class_loaded("InheritChild")?;
+    let result = interpreter.invoke_static("InheritChild", "helper", "(I)I", vec![JvmValue::Int(4)])?;
+    assert!(matches!(result, Some(JvmValue::Int(8))), "{:?}", result);
+    Ok(())
+}
+
+#[test]
+fn test_default_method_dispatch() -> Result<()> {
+    let mut interpreter = interpreter()?;
+    // invokevirtual InheritChild.answer 和 invokeinterface InheritIface.answer
+    assert_eq!(call(&mut interpreter, "defaultViaClass", "()I", vec![])?, 42);
+    assert_eq!(call(&mut interpreter, "defaultViaInterface", "()I", vec![])?, 42);
+
+    let (owner, method) = interpreter.metaspace.resolve_virtual_method("InheritChild", "answer", "()I")?;
+    assert_eq!(owner, "InheritIface");
+    assert!(!method.is_abstract);
+    Ok(())
+}
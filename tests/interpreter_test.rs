@@ -2,6 +2,8 @@
 //!
 //! 运行: cargo test
 
+#![allow(deprecated)]
+
 use rsjvm::interpreter::Interpreter;
 use rsjvm::runtime::frame::JvmValue;
 
@@ -123,7 +125,7 @@ fn test_frame_operations() {
     let mut frame = Frame::new(5, 10);
 
     // 测试压栈和弹栈
-    frame.push(JvmValue::Int(42));
+    frame.push(JvmValue::Int(42)).unwrap();
     assert_eq!(frame.stack_size(), 1);
 
     let val = frame.pop_int().unwrap();
@@ -0,0 +1,144 @@
+//! 测试从 jar 文件加载类
+
+use rsjvm::classfile::access_flags::{ACC_PUBLIC, ACC_STATIC};
+use rsjvm::classfile::builder::ClassFileBuilder;
+use rsjvm::classloader::ClassLoader;
+use rsjvm::interpreter::Interpreter;
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::Result;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+fn temp_dir(tag: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("rsjvm-jar-{}-{}", tag, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// 一个只有 `static int <method>()` 的类，返回 `value`
+fn class_returning(name: &str, method: &str, value: i32) -> Result<Vec<u8>> {
+    ClassFileBuilder::new(name)
+        .method(ACC_PUBLIC | ACC_STATIC, method, "()I", 1, 0, |code| {
+            code.iconst(value).ireturn();
+        })
+        .to_bytes()
+}
+
+/// JarMain.run() { return com.example.Helper.value() + 1; }
+fn jar_main() -> Result<Vec<u8>> {
+    ClassFileBuilder::new("JarMain")
+        .method(ACC_PUBLIC | ACC_STATIC, "run", "()I", 2, 0, |code| {
+            code.invokestatic("com/example/Helper", "value", "()I")
+                .iconst(1)
+                .iadd()
+                .ireturn();
+        })
+        .to_bytes()
+}
+
+fn write_jar(path: &Path, entries: &[(&str, Vec<u8>)]) -> Result<()> {
+    let mut jar = ZipWriter::new(std::fs::File::create(path)?);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    jar.add_directory("META-INF/", options)?;
+    jar.start_file("META-INF/MANIFEST.MF", options)?;
+    jar.write_all(b"Manifest-Version: 1.0\r\n\r\n")?;
+    for (name, bytes) in entries {
+        jar.start_file(*name, options)?;
+        jar.write_all(bytes)?;
+    }
+    jar.finish()?;
+    Ok(())
+}
+
+fn run(class_paths: Vec<PathBuf>) -> Result<Option<JvmValue>> {
+    let mut interpreter = Interpreter::with_class_loader(ClassLoader::new(class_paths));
+    interpreter.ensure_class_loaded("JarMain")?;
+    interpreter.invoke_static("JarMain", "run", "()I", vec![])
+}
+
+#[test]
+fn test_load_classes_from_jar() -> Result<()> {
+    let dir = temp_dir("load");
+    let jar = dir.join("app.jar");
+    write_jar(
+        &jar,
+        &[
+            ("JarMain.class", jar_main()?),
+            ("com/example/Helper.class", class_returning("com/example/Helper", "value", 41)?),
+        ],
+    )?;
+
+    let mut interpreter = Interpreter::with_class_loader(ClassLoader::new(vec![jar.clone()]));
+    interpreter.ensure_class_loaded("JarMain")?;
+    let result = interpreter.invoke_static("JarMain", "run", "()I", vec![])?;
+    assert!(matches!(result, Some(JvmValue::Int(42))), "{:?}", result);
+    assert_eq!(interpreter.metaspace.loaded_classes(), ["JarMain", "com/example/Helper"]);
+
+    // scan_class_path 也会列出 jar 里的类
+    let names: Vec<String> = ClassLoader::new(vec![jar])
+        .scan_class_path()
+        .iter()
+        .map(|c| c.get_class_name().unwrap())
+        .collect();
+    assert_eq!(names, ["JarMain", "com/example/Helper"]);
+    std::fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_class_path_order_across_dirs_and_jars() -> Result<()> {
+    let dir = temp_dir("order");
+    let first = dir.join("first.jar");
+    let second = dir.join("second.jar");
+    let classes = dir.join("classes");
+    write_jar(&first, &[("JarMain.class", jar_main()?)])?;
+    write_jar(
+        &second,
+        &[("com/example/Helper.class", class_returning("com/example/Helper", "value", 1)?)],
+    )?;
+    std::fs::create_dir_all(classes.join("com/example"))?;
+    std::fs::write(
+        classes.join("com/example/Helper.class"),
+        class_returning("com/example/Helper", "value", 100)?,
+    )?;
+
+    // 目录在前：用目录里的 Helper
+    let result = run(vec![first.clone(), classes.clone(), second.clone()])?;
+    assert!(matches!(result, Some(JvmValue::Int(101))), "{:?}", result);
+
+    // jar 在前：用 jar 里的 Helper
+    let result = run(vec![first, second, classes])?;
+    assert!(matches!(result, Some(JvmValue::Int(2))), "{:?}", result);
+    std::fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_corrupt_jar_names_the_file() -> Result<()> {
+    let dir = temp_dir("corrupt");
+    let jar = dir.join("broken.jar");
+    std::fs::write(&jar, b"this is not a zip file")?;
+
+    let err = run(vec![jar.clone()]).unwrap_err();
+    let message = format!("{:#}", err);
+    assert!(message.contains(&format!("Corrupt jar file {}", jar.display())), "{}", message);
+    std::fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_missing_class_in_jar_is_class_not_found() -> Result<()> {
+    let dir = temp_dir("missing");
+    let jar = dir.join("app.jar");
+    write_jar(&jar, &[("JarMain.class", jar_main()?)])?;
+
+    let err = run(vec![jar]).unwrap_err();
+    let message = format!("{:#}", err);
+    assert!(message.contains("java/lang/ClassNotFoundException"), "{}", message);
+    assert!(message.contains("com/example/Helper"), "{}", message);
+    std::fs::remove_dir_all(&dir)?;
+    Ok(())
+}
@@ -0,0 +1,97 @@
+//! 测试 LineNumberTable/LocalVariableTable 的解析，以及错误信息中的源代码位置
+
+use rsjvm::classfile::ClassFile;
+use rsjvm::interpreter::{Interpreter, InterpreterOptions};
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::runtime::ExecutionError;
+use rsjvm::Result;
+use std::process::Command;
+
+const CLASS: &str = "examples/LineNumbers.class";
+
+/// compute 方法的 Code 属性在 ClassFile 中的位置
+fn code_attribute_position(class_file: &ClassFile) -> (usize, usize) {
+    let name = |index: u16| class_file.constant_pool.get_utf8(index).unwrap();
+    let m = class_file.methods.iter().position(|m| name(m.name_index) == "compute").unwrap();
+    let a = class_file.methods[m]
+        .attributes
+        .iter()
+        .position(|a| name(a.name_index) == "Code")
+        .unwrap();
+    (m, a)
+}
+
+#[test]
+fn test_code_attribute_tables() -> Result<()> {
+    let class_file = ClassFile::from_file(CLASS)?;
+    let (m, a) = code_attribute_position(&class_file);
+    let code_attr = class_file.methods[m].attributes[a].parse_code_attribute()?;
+    let cp = &class_file.constant_pool;
+
+    assert_eq!(code_attr.line_number_table(cp)?, vec![(0, 8), (4, 9), (8, 10)]);
+
+    let locals: Vec<(String, u16, u16, u16)> = code_attr
+        .local_variable_table(cp)?
+        .iter()
+        .map(|e| (cp.get_utf8(e.name_index).unwrap(), e.index, e.start_pc, e.length))
+        .collect();
+    assert_eq!(
+        locals,
+        vec![
+            ("a".to_string(), 0, 0, 10),
+            ("b".to_string(), 1, 4, 6),
+            ("c".to_string(), 2, 8, 2)
+        ]
+    );
+    assert_eq!(class_file.get_source_file()?, Some("LineNumbers.java".to_string()));
+    Ok(())
+}
+
+#[test]
+fn test_pc_to_line_mapping() -> Result<()> {
+    let mut interpreter = Interpreter::new();
+    interpreter.load_class(ClassFile::from_file(CLASS)?)?;
+    let class = interpreter.metaspace.get_class("LineNumbers")?;
+    assert_eq!(class.source_file.as_deref(), Some("LineNumbers.java"));
+
+    let method = class.methods.get("compute:(I)I").unwrap();
+    let lines: Vec<Option<u16>> = (0..method.code.len()).map(|pc| method.line_number_at(pc)).collect();
+    let expected = [8, 8, 8, 8, 9, 9, 9, 9, 10, 10].map(Some);
+    assert_eq!(lines, expected);
+    Ok(())
+}
+
+#[test]
+fn test_errors_name_the_source_line() -> Result<()> {
+    // max_stack 改成 1：pc 1 的 iconst_2 溢出，属于第 8 行
+    let mut class_file = ClassFile::from_file(CLASS)?;
+    let (m, a) = code_attribute_position(&class_file);
+    class_file.methods[m].attributes[a].info[0..2].copy_from_slice(&[0, 1]);
+
+    let mut interpreter = Interpreter::new_with_options(InterpreterOptions {
+        verify: false,
+        ..Default::default()
+    });
+    interpreter.load_class(class_file)?;
+    let err = interpreter
+        .invoke_static("LineNumbers", "compute", "(I)I", vec![JvmValue::Int(3)])
+        .unwrap_err();
+    let error = err.downcast_ref::<ExecutionError>().expect("ExecutionError");
+    assert_eq!(error.cause.to_string(), "operand stack overflow: max_stack=1");
+    assert_eq!(error.frames[0].to_string(), "LineNumbers.compute(I)I pc=1 (LineNumbers.java:8)");
+    Ok(())
+}
+
+#[test]
+fn test_parse_verbose_prints_tables() {
+    let output = Command::new(env!("CARGO_BIN_EXE_rsjvm"))
+        .args(["parse", CLASS, "-v"])
+        .env("RUST_BACKTRACE", "0")
+        .output()
+        .expect("failed to run rsjvm");
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("LineNumberTable:\n        line 8: 0\n        line 9: 4\n"), "{}", stdout);
+    assert!(stdout.contains("LocalVariableTable:"), "{}", stdout);
+    assert!(stdout.contains("            4       6     1  b      I"), "{}", stdout);
+}
@@ -0,0 +1,137 @@
+//! 测试格式错误的 class 文件：截断、常量池计数与内容不符、随机改写常量池字节，
+//! 解析和加载都应该返回错误而不是 panic
+
+use rsjvm::classfile::access_flags::{ACC_PUBLIC, ACC_STATIC};
+use rsjvm::classfile::builder::ClassFileBuilder;
+use rsjvm::classfile::constant_pool::{ConstantPool, ConstantPoolEntry};
+use rsjvm::classfile::ClassFile;
+use rsjvm::interpreter::{Interpreter, InterpreterOptions};
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::Result;
+
+fn calculator() -> Vec<u8> {
+    std::fs::read("examples/Calculator.class").unwrap()
+}
+
+/// 解析并加载（能解析的话），再调用一个方法；只关心不 panic
+fn parse_and_load(bytes: &[u8]) -> Result<()> {
+    let class_file = ClassFile::from_bytes(bytes)?;
+    let mut interpreter = Interpreter::new();
+    interpreter.load_class(class_file)?;
+    interpreter.invoke_static("Calculator", "average", "(II)I", vec![JvmValue::Int(7), JvmValue::Int(2)])?;
+    Ok(())
+}
+
+#[test]
+fn test_every_truncation_is_an_error() {
+    let bytes = calculator();
+    for len in 0..bytes.len() {
+        assert!(ClassFile::from_bytes(&bytes[..len]).is_err(), "truncated to {} bytes", len);
+    }
+    assert!(parse_and_load(&bytes).is_ok());
+}
+
+#[test]
+fn test_wrong_constant_pool_counts() {
+    let bytes = calculator();
+    let actual = u16::from_be_bytes([bytes[8], bytes[9]]);
+    for count in [0, 1, 2, actual / 2, actual - 1, actual + 1, actual + 100, u16::MAX] {
+        let mut patched = bytes.clone();
+        patched[8..10].copy_from_slice(&count.to_be_bytes());
+        // 计数偏小时后面的常量被当成类头解析，偏大时把类头当成常量：都不能 panic
+        let _ = parse_and_load(&patched);
+    }
+
+    let mut zero = bytes.clone();
+    zero[8..10].copy_from_slice(&0u16.to_be_bytes());
+    let err = ClassFile::from_bytes(&zero).unwrap_err();
+    assert!(err.to_string().contains("Invalid constant pool count 0"), "{}", err);
+}
+
+#[test]
+fn test_long_in_last_slot_is_rejected() {
+    let mut bytes = vec![0xCA, 0xFE, 0xBA, 0xBE, 0, 0, 0, 52];
+    bytes.extend_from_slice(&2u16.to_be_bytes()); // 只有 #1，放不下 Long 的第二个槽位
+    bytes.push(5); // CONSTANT_Long
+    bytes.extend_from_slice(&42i64.to_be_bytes());
+    bytes.extend_from_slice(&[0; 10]);
+    let err = ClassFile::from_bytes(&bytes).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Long constant at constant pool index 1 needs two slots but the pool has only 2 entries"
+    );
+}
+
+#[test]
+fn test_scrambled_constant_pool_bytes() {
+    let bytes = calculator();
+    // 常量池从第 10 字节开始，固定种子的线性同余生成器，结果可复现
+    let mut seed: u64 = 0x2545F4914F6CDD1D;
+    let mut next = move || {
+        seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        (seed >> 33) as usize
+    };
+    for _ in 0..500 {
+        let mut mutated = bytes.clone();
+        for _ in 0..1 + next() % 4 {
+            let at = 10 + next() % 400.min(mutated.len() - 10);
+            mutated[at] = next() as u8;
+        }
+        let _ = parse_and_load(&mutated);
+    }
+}
+
+#[test]
+fn test_set_out_of_range() {
+    let mut pool = ConstantPool::new(3);
+    assert!(pool.set(2, ConstantPoolEntry::Integer(1)).is_ok());
+    let err = pool.set(3, ConstantPoolEntry::Integer(1)).unwrap_err();
+    assert_eq!(err.to_string(), "Constant pool index 3 out of range (pool has 3 entries)");
+    assert!(pool.set(0, ConstantPoolEntry::Integer(1)).is_err());
+}
+
+#[test]
+fn test_typed_accessors() -> Result<()> {
+    let mut pool = ConstantPool::empty();
+    let int = pool.push(ConstantPoolEntry::Integer(-7));
+    let float = pool.push(ConstantPoolEntry::Float(1.5));
+    let long = pool.push(ConstantPoolEntry::Long(1 << 40));
+    let double = pool.push(ConstantPoolEntry::Double(2.25));
+    let string = pool.add_string("hi");
+    assert_eq!(pool.get_integer(int)?, -7);
+    assert_eq!(pool.get_float(float)?, 1.5);
+    assert_eq!(pool.get_long(long)?, 1 << 40);
+    assert_eq!(pool.get_double(double)?, 2.25);
+    assert_eq!(pool.get_string(string)?, "hi");
+    assert_eq!(
+        pool.get_integer(string).unwrap_err().to_string(),
+        format!("Expected Integer at index {}, got String", string)
+    );
+    // Long 的第二个槽位
+    assert!(pool.get_long(long + 1).is_err());
+    assert!(pool.get_string(99).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_dangling_constant_pool_index_in_code() -> Result<()> {
+    // invokestatic #0x7777 和 ldc #0x77：常量池里没有这两项
+    let class_file = ClassFileBuilder::new("Dangling")
+        .method(ACC_PUBLIC | ACC_STATIC, "call", "()V", 1, 0, |code| {
+            code.bytes(&[0xb8, 0x77, 0x77]).vreturn();
+        })
+        .method(ACC_PUBLIC | ACC_STATIC, "load", "()I", 1, 0, |code| {
+            code.bytes(&[0x12, 0x77]).ireturn();
+        })
+        .build()?;
+    let mut interpreter = Interpreter::new_with_options(InterpreterOptions {
+        verify: false,
+        ..Default::default()
+    });
+    interpreter.load_class(class_file)?;
+    let err = interpreter.invoke_static("Dangling", "call", "()V", vec![]).unwrap_err();
+    assert!(err.to_string().contains("Invalid constant pool index: 30583"), "{}", err);
+    let err = interpreter.invoke_static("Dangling", "load", "()I", vec![]).unwrap_err();
+    assert!(err.to_string().contains("Invalid constant pool index: 119"), "{}", err);
+    Ok(())
+}
@@ -0,0 +1,100 @@
+//! 测试方法区统计、类卸载和运行时常量池清理
+
+use rsjvm::classfile::ClassFile;
+use rsjvm::classloader::ClassLoader;
+use rsjvm::interpreter::Interpreter;
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::runtime::Frame;
+use rsjvm::Result;
+
+fn load(interpreter: &mut Interpreter, name: &str) -> Result<()> {
+    interpreter.load_class(ClassFile::from_file(format!("examples/{}.class", name))?)?;
+    Ok(())
+}
+
+#[test]
+fn test_unload_class() -> Result<()> {
+    let mut interpreter = Interpreter::new();
+    load(&mut interpreter, "ReturnOne")?;
+    load(&mut interpreter, "Calculator")?;
+
+    let before = interpreter.metaspace.stats();
+    assert_eq!(before.classes, 2);
+    let return_one = interpreter.metaspace.get_class("ReturnOne")?;
+    let return_one_methods = return_one.methods.len();
+    let return_one_bytes: usize = return_one.methods.values().map(|m| m.code.len()).sum();
+
+    interpreter.unload_class("ReturnOne")?;
+    assert!(!interpreter.metaspace.is_class_loaded("ReturnOne"));
+    assert!(interpreter.metaspace.is_class_loaded("Calculator"));
+    assert_eq!(interpreter.metaspace.loaded_classes(), ["Calculator"]);
+
+    let after = interpreter.metaspace.stats();
+    assert_eq!(after.classes, 1);
+    assert_eq!(after.methods, before.methods - return_one_methods);
+    assert_eq!(after.bytecode_bytes, before.bytecode_bytes - return_one_bytes);
+
+    let err = interpreter.invoke_static("ReturnOne", "returnOne", "()I", vec![]).unwrap_err();
+    assert_eq!(err.to_string(), "Class not found: ReturnOne");
+    let err = interpreter.unload_class("ReturnOne").unwrap_err();
+    assert_eq!(err.to_string(), "Class not found: ReturnOne");
+
+    // 卸载后可以重新加载
+    load(&mut interpreter, "ReturnOne")?;
+    let result = interpreter.invoke_static("ReturnOne", "returnOne", "()I", vec![])?;
+    assert!(matches!(result, Some(JvmValue::Int(1))), "{:?}", result);
+    Ok(())
+}
+
+#[test]
+fn test_unload_refuses_active_and_extended_classes() -> Result<()> {
+    let mut interpreter = Interpreter::new();
+    load(&mut interpreter, "Animal")?;
+    load(&mut interpreter, "Dog")?;
+
+    let err = interpreter.unload_class("Animal").unwrap_err();
+    assert_eq!(err.to_string(), "Cannot unload Animal: still extended or implemented by Dog");
+
+    let mut frame = Frame::new(0, 0);
+    frame.class_name = "Dog".into();
+    frame.method_name = "bark".into();
+    interpreter.thread.push_frame(frame)?;
+    let err = interpreter.unload_class("Dog").unwrap_err();
+    assert_eq!(err.to_string(), "Cannot unload Dog: Dog.bark is still on the thread stack");
+
+    interpreter.thread.pop_frame()?;
+    interpreter.unload_class("Dog")?;
+    interpreter.unload_class("Animal")?;
+    assert_eq!(interpreter.metaspace.stats().classes, 0);
+    Ok(())
+}
+
+fn run_chain(interpreter: &mut Interpreter) -> Result<()> {
+    let result = interpreter.invoke_static("ChainA", "run", "()I", vec![])?;
+    assert!(matches!(result, Some(JvmValue::Int(26))), "{:?}", result);
+    Ok(())
+}
+
+#[test]
+fn test_runtime_pools_after_unload_and_clear() -> Result<()> {
+    let mut interpreter = Interpreter::with_class_loader(ClassLoader::new(vec!["examples".into()]));
+    load(&mut interpreter, "ChainA")?;
+    run_chain(&mut interpreter)?;
+    let resolved = interpreter.metaspace.stats().resolved_entries;
+    assert!(resolved > 0);
+
+    // ChainB 缓存的 ChainC.square 解析结果随 ChainC 一起丢弃，下次调用时按需重新加载
+    interpreter.unload_class("ChainC")?;
+    assert!(interpreter.metaspace.stats().resolved_entries < resolved);
+    run_chain(&mut interpreter)?;
+    assert!(interpreter.metaspace.is_class_loaded("ChainC"));
+
+    interpreter.metaspace.clear_runtime_pools();
+    assert_eq!(interpreter.metaspace.stats().resolved_entries, 0);
+    run_chain(&mut interpreter)?;
+    assert_eq!(interpreter.metaspace.stats().resolved_entries, resolved);
+
+    let text = interpreter.run_stats().to_string();
+    assert!(text.contains(&format!("已缓存的解析结果: {}", resolved)), "{}", text);
+    Ok(())
+}
@@ -0,0 +1,102 @@
+//! 测试 Java 9+/11 新增的常量池类型（Module、Package、Dynamic）和 Java 17 class 文件
+
+use rsjvm::classfile::constant_pool::ConstantPoolEntry;
+use rsjvm::classfile::ClassFile;
+use rsjvm::interpreter::disasm::symbol;
+use rsjvm::interpreter::{Interpreter, InterpreterOptions};
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::Result;
+
+#[test]
+fn test_module_info_constants() -> Result<()> {
+    let class_file = ClassFile::from_file("examples/modern/module-info.class")?;
+    assert_eq!(class_file.major_version, 61);
+    assert_eq!(class_file.get_java_version(), "Java 17");
+
+    let cp = &class_file.constant_pool;
+    let symbols: Vec<String> = (1..cp.entries.len() as u16)
+        .filter(|&i| {
+            matches!(
+                cp.entries[i as usize],
+                Some(ConstantPoolEntry::Module { .. }) | Some(ConstantPoolEntry::Package { .. })
+            )
+        })
+        .map(|i| symbol(cp, i).unwrap())
+        .collect();
+    assert!(symbols.contains(&"Module modern".to_string()), "{:?}", symbols);
+    assert!(symbols.contains(&"Module java.base".to_string()), "{:?}", symbols);
+    assert!(symbols.contains(&"Package modern/api".to_string()), "{:?}", symbols);
+    Ok(())
+}
+
+/// 这些类都比默认支持的 Java 8 新，跳过版本检查
+fn forced_interpreter() -> Interpreter {
+    Interpreter::new_with_options(InterpreterOptions {
+        max_major_version: None,
+        ..Default::default()
+    })
+}
+
+#[test]
+fn test_java17_class_runs() -> Result<()> {
+    let class_file = ClassFile::from_file("examples/modern/modern/api/Greeter.class")?;
+    assert_eq!(class_file.get_java_version(), "Java 17");
+
+    let mut interpreter = forced_interpreter();
+    interpreter.load_class(class_file)?;
+    let result = interpreter.invoke_static("modern/api/Greeter", "answer", "()I", vec![])?;
+    assert!(matches!(result, Some(JvmValue::Int(42))), "{:?}", result);
+    Ok(())
+}
+
+/// 常量池：#1 "value" #2 "I" #3 NameAndType #4 Dynamic #5 "Condy" #6 Class
+fn condy_class() -> Vec<u8> {
+    let mut bytes = vec![0xCA, 0xFE, 0xBA, 0xBE, 0x00, 0x00, 0x00, 55];
+    bytes.extend_from_slice(&7u16.to_be_bytes()); // constant_pool_count
+    for s in ["value", "I"] {
+        bytes.push(1);
+        bytes.extend_from_slice(&(s.len() as u16).to_be_bytes());
+        bytes.extend_from_slice(s.as_bytes());
+    }
+    bytes.extend_from_slice(&[12, 0, 1, 0, 2]); // NameAndType value:I
+    bytes.extend_from_slice(&[17, 0, 0, 0, 3]); // Dynamic #0:value:I
+    bytes.extend_from_slice(&[1, 0, 5]);
+    bytes.extend_from_slice(b"Condy");
+    bytes.extend_from_slice(&[7, 0, 5]); // Class Condy
+    bytes.extend_from_slice(&0x0021u16.to_be_bytes()); // access_flags
+    bytes.extend_from_slice(&6u16.to_be_bytes()); // this_class
+    bytes.extend_from_slice(&[0; 10]); // super_class、接口、字段、方法、属性都为空
+    bytes
+}
+
+#[test]
+fn test_dynamic_constant() -> Result<()> {
+    let class_file = ClassFile::from_bytes(&condy_class())?;
+    assert_eq!(class_file.get_java_version(), "Java 11");
+    assert!(matches!(
+        class_file.constant_pool.get(4)?,
+        ConstantPoolEntry::Dynamic {
+            bootstrap_method_attr_index: 0,
+            name_and_type_index: 3
+        }
+    ));
+    assert_eq!(symbol(&class_file.constant_pool, 4)?, "Dynamic #0:value:I");
+
+    // 不执行 ldc 的话，含 Dynamic 常量的类可以正常加载
+    let mut interpreter = forced_interpreter();
+    interpreter.load_class(class_file)?;
+    assert!(interpreter.metaspace.get_class("Condy").is_ok());
+    Ok(())
+}
+
+#[test]
+fn test_java_version_names() -> Result<()> {
+    let mut class_file = ClassFile::from_bytes(&condy_class())?;
+    for (major, name) in [(45, "Java 1.1"), (48, "Java 1.4"), (49, "Java 5"), (52, "Java 8"), (65, "Java 21")] {
+        class_file.major_version = major;
+        assert_eq!(class_file.get_java_version(), name);
+    }
+    class_file.major_version = 99;
+    assert_eq!(class_file.get_java_version(), "Java (version 99)");
+    Ok(())
+}
@@ -0,0 +1,79 @@
+//! 测试 monitorenter/monitorexit 和 synchronized 方法：重入、不配对的 monitorexit，
+//! 以及多个线程在监视器保护下对共享字段的互斥访问
+
+use rsjvm::interpreter::instructions::opcodes::*;
+use rsjvm::interpreter::Interpreter;
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::{Jvm, JvmBuilder, Result};
+
+fn jvm() -> Jvm {
+    JvmBuilder::new().class_path("examples").build()
+}
+
+#[test]
+fn test_monitor_is_reentrant() -> Result<()> {
+    // int[] a = new int[1]; 两次 monitorenter a，两次 monitorexit a，返回 a.length
+    let code = [
+        ICONST_1, NEWARRAY, 10, ASTORE_0, ALOAD_0, MONITORENTER, ALOAD_0, MONITORENTER, ALOAD_0,
+        MONITOREXIT, ALOAD_0, MONITOREXIT, ALOAD_0, ARRAYLENGTH, IRETURN,
+    ];
+    let mut interpreter = Interpreter::new();
+    let result = interpreter.execute_method_with_class("Reentrant", "run:()I", &code, 1, 1)?;
+    assert!(matches!(result, Some(JvmValue::Int(1))), "{:?}", result);
+
+    let mut jvm = jvm();
+    let depth: i32 = jvm.call_static_typed("SyncDemo", "reentrant", "()I", ())?;
+    assert_eq!(depth, 5);
+    Ok(())
+}
+
+#[test]
+fn test_unbalanced_monitorexit_throws() {
+    // 没有获取过监视器就 monitorexit
+    let code = [ICONST_1, NEWARRAY, 10, MONITOREXIT, RETURN];
+    let err = Interpreter::new()
+        .execute_method_with_class("Unbalanced", "run:()V", &code, 0, 1)
+        .unwrap_err();
+    assert!(
+        format!("{:#}", err).contains("java/lang/IllegalMonitorStateException"),
+        "{:#}",
+        err
+    );
+
+    // 重入一次却释放两次
+    let code = [
+        ICONST_1, NEWARRAY, 10, ASTORE_0, ALOAD_0, MONITORENTER, ALOAD_0, MONITOREXIT, ALOAD_0,
+        MONITOREXIT, RETURN,
+    ];
+    let err = Interpreter::new()
+        .execute_method_with_class("Unbalanced", "run:()V", &code, 1, 1)
+        .unwrap_err();
+    assert!(
+        format!("{:#}", err).contains("java/lang/IllegalMonitorStateException"),
+        "{:#}",
+        err
+    );
+}
+
+#[test]
+fn test_synchronized_increments_are_not_lost() -> Result<()> {
+    let mut jvm = jvm();
+    // 不加锁时时间片在读和写之间用完，两个线程的自增会互相覆盖
+    let unsafe_count: i32 = jvm.call_static_typed("SyncDemo", "race", "(I)I", (0,))?;
+    assert!(unsafe_count < 400, "{}", unsafe_count);
+
+    // synchronized 实例方法、synchronized 代码块、static synchronized 方法
+    for mode in 1..=3 {
+        let count: i32 = jvm.call_static_typed("SyncDemo", "race", "(I)I", (mode,))?;
+        assert_eq!(count, 400, "mode {}", mode);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_monitor_released_when_exception_leaves_method() -> Result<()> {
+    let mut jvm = jvm();
+    let count: i32 = jvm.call_static_typed("SyncDemo", "releasedOnException", "()I", ())?;
+    assert_eq!(count, 200);
+    Ok(())
+}
@@ -0,0 +1,86 @@
+//! 测试本地方法注册表：Rust 函数实现 native 方法
+
+use rsjvm::classfile::ClassFile;
+use rsjvm::interpreter::Interpreter;
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::Result;
+use anyhow::anyhow;
+
+fn load() -> Result<Interpreter> {
+    let mut interpreter = Interpreter::new();
+    interpreter.load_class(ClassFile::from_file("examples/NativeDemo.class")?)?;
+    Ok(interpreter)
+}
+
+fn call_int(interpreter: &mut Interpreter, method: &str, arg: i32) -> Result<i32> {
+    match interpreter.invoke_static("NativeDemo", method, "(I)I", vec![JvmValue::Int(arg)])? {
+        Some(JvmValue::Int(v)) => Ok(v),
+        other => Err(anyhow!("expected int, got {:?}", other)),
+    }
+}
+
+#[test]
+fn test_static_native_called_from_bytecode() -> Result<()> {
+    let mut interpreter = load()?;
+    interpreter.register_native(
+        "NativeDemo",
+        "twice",
+        "(I)I",
+        Box::new(|_, args| match args {
+            [JvmValue::Int(x)] => Ok(Some(JvmValue::Int(x * 2))),
+            _ => Err(anyhow!("bad arguments {:?}", args)),
+        }),
+    );
+    assert_eq!(call_int(&mut interpreter, "callTwice", 20)?, 41);
+    // 从 Rust 直接调用 native 方法也走注册表
+    assert_eq!(call_int(&mut interpreter, "twice", 5)?, 10);
+    Ok(())
+}
+
+#[test]
+fn test_instance_native_receives_this() -> Result<()> {
+    let mut interpreter = load()?;
+    interpreter.register_native(
+        "NativeDemo",
+        "scale",
+        "(I)I",
+        Box::new(|interpreter, args| match args {
+            [JvmValue::Reference(Some(this)), JvmValue::Int(x)] => {
+                match interpreter.heap.get_field(*this, "NativeDemo", "factor")? {
+                    JvmValue::Int(factor) => Ok(Some(JvmValue::Int(factor * x))),
+                    other => Err(anyhow!("bad factor {:?}", other)),
+                }
+            }
+            _ => Err(anyhow!("bad arguments {:?}", args)),
+        }),
+    );
+    assert_eq!(call_int(&mut interpreter, "callScale", 7)?, 21);
+    Ok(())
+}
+
+#[test]
+fn test_unregistered_native_is_unsatisfied_link_error() -> Result<()> {
+    let mut interpreter = load()?;
+    let err = interpreter
+        .invoke_static("NativeDemo", "callMissing", "()V", vec![])
+        .unwrap_err();
+    let message = format!("{:#}", err);
+    assert!(message.contains("java/lang/UnsatisfiedLinkError"), "{}", message);
+    assert!(message.contains("NativeDemo.missing()V"), "{}", message);
+    Ok(())
+}
+
+#[test]
+fn test_builtin_natives() -> Result<()> {
+    let mut interpreter = load()?;
+    assert!(interpreter.natives().contains("java/lang/Math", "max", "(II)I"));
+    assert_eq!(call_int(&mut interpreter, "clamp", -5)?, 0);
+    assert_eq!(call_int(&mut interpreter, "clamp", 42)?, 42);
+    assert_eq!(call_int(&mut interpreter, "clamp", 1000)?, 100);
+
+    match interpreter.invoke_static("NativeDemo", "now", "()J", vec![])? {
+        Some(JvmValue::Long(millis)) => assert!(millis > 1_600_000_000_000, "{}", millis),
+        other => panic!("expected long, got {:?}", other),
+    }
+    Ok(())
+}
@@ -0,0 +1,153 @@
+//! 测试嵌入 API 创建对象（new_instance / Interpreter::instantiate）和调用实例方法（call_virtual）
+
+use rsjvm::gc::GcStrategy;
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::{Jvm, JvmBuilder, Result};
+
+fn jvm() -> Jvm {
+    JvmBuilder::new().class_path("examples").build()
+}
+
+fn int(value: Option<JvmValue>) -> i32 {
+    match value {
+        Some(JvmValue::Int(v)) => v,
+        other => panic!("期望 Int, 实际: {:?}", other),
+    }
+}
+
+fn text(jvm: &Jvm, value: Option<JvmValue>) -> String {
+    match value {
+        Some(JvmValue::Reference(Some(s))) => jvm.interpreter().heap.get_string(s).unwrap().to_string(),
+        other => panic!("期望 String, 实际: {:?}", other),
+    }
+}
+
+#[test]
+fn test_new_instance_runs_constructor() -> Result<()> {
+    let mut jvm = jvm();
+    let point = jvm.new_instance("Point", "(II)V", &[JvmValue::Int(3), JvmValue::Int(4)])?;
+    // 构造器看到的 origin 来自 <clinit>
+    assert_eq!(int(jvm.call_virtual(&point, "getX", "()I", &[])?), 103);
+    assert_eq!(int(jvm.call_virtual(&point, "getY", "()I", &[])?), 104);
+
+    // 无参构造器委托给 this(0, 0)
+    let origin = jvm.new_instance("Point", "()V", &[])?;
+    assert_eq!(int(jvm.call_virtual(&origin, "getX", "()I", &[])?), 100);
+    assert_eq!(int(jvm.call_static("Point", "created", "()I", &[])?), 2);
+    Ok(())
+}
+
+#[test]
+fn test_call_virtual_with_arguments_and_void_result() -> Result<()> {
+    let mut jvm = jvm();
+    let a = jvm.new_instance("Point", "(II)V", &[JvmValue::Int(0), JvmValue::Int(0)])?;
+    let b = jvm.new_instance("Point", "(II)V", &[JvmValue::Int(3), JvmValue::Int(4)])?;
+    let distance = jvm.call_virtual(&a, "distanceSquared", "(LPoint;)J", &[jvm.value(&b)?])?;
+    assert!(matches!(distance, Some(JvmValue::Long(25))), "{:?}", distance);
+
+    let moved = jvm.call_virtual(&a, "move", "(II)V", &[JvmValue::Int(1), JvmValue::Int(-1)])?;
+    assert!(moved.is_none());
+    let described = jvm.call_virtual(&a, "describe", "()Ljava/lang/String;", &[])?;
+    assert_eq!(text(&jvm, described), "(101, 99)");
+    Ok(())
+}
+
+#[test]
+fn test_call_virtual_dispatches_on_runtime_class() -> Result<()> {
+    let mut jvm = jvm();
+    let args = [JvmValue::Int(1), JvmValue::Int(2), JvmValue::Int(3)];
+    let point = jvm.new_instance("Point3D", "(III)V", &args)?;
+    // 覆盖的方法
+    let described = jvm.call_virtual(&point, "describe", "()Ljava/lang/String;", &[])?;
+    assert_eq!(text(&jvm, described), "(101, 102, 3)");
+    // 继承的方法
+    assert_eq!(int(jvm.call_virtual(&point, "getY", "()I", &[])?), 102);
+    // 没有覆盖的 JDK 方法使用内置实现
+    let hash = jvm.call_virtual(&point, "hashCode", "()I", &[])?;
+    assert!(matches!(hash, Some(JvmValue::Int(_))), "{:?}", hash);
+    Ok(())
+}
+
+#[test]
+fn test_constructors_are_not_inherited() {
+    let mut jvm = jvm();
+    let err = jvm
+        .new_instance("Point3D", "(II)V", &[JvmValue::Int(1), JvmValue::Int(2)])
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("java/lang/NoSuchMethodError"), "{}", err);
+    assert!(err.contains("Point3D.<init>(II)V (constructors are not inherited)"), "{}", err);
+}
+
+#[test]
+fn test_errors() -> Result<()> {
+    let mut jvm = jvm();
+    let err = jvm.new_instance("Missing", "()V", &[]).unwrap_err().to_string();
+    assert!(err.contains("ClassNotFoundException"), "{}", err);
+
+    let err = jvm.pin(&JvmValue::Reference(None)).unwrap_err();
+    assert!(err.to_string().contains("NullPointerException"), "{}", err);
+
+    let point = jvm.new_instance("Point", "()V", &[])?;
+    let err = jvm.call_virtual(&point, "getZ", "()I", &[]).unwrap_err().to_string();
+    assert!(err.contains("getZ"), "{}", err);
+    // 参数个数不对时不执行方法
+    assert!(jvm.call_virtual(&point, "getX", "()I", &[JvmValue::Int(1)]).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_instantiate_jdk_class() -> Result<()> {
+    let mut jvm = jvm();
+    let interpreter = jvm.interpreter_mut();
+    let message = JvmValue::Reference(Some(interpreter.heap.allocate_string("boom")?));
+    let exception =
+        interpreter.instantiate("java/lang/IllegalStateException", "(Ljava/lang/String;)V", vec![message])?;
+    let result =
+        interpreter.invoke_virtual(exception, "getMessage", "()Ljava/lang/String;", vec![])?;
+    assert_eq!(text(&jvm, result), "boom");
+    Ok(())
+}
+
+#[test]
+fn test_handles_survive_gc() -> Result<()> {
+    for strategy in [GcStrategy::MarkSweep, GcStrategy::MarkCompact] {
+        let mut jvm = JvmBuilder::new()
+            .class_path("examples")
+            .gc_threshold(Some(8))
+            .gc_strategy(strategy)
+            .build();
+        // 先留下垃圾：整理时后面的对象会被移到前面
+        for i in 0..4 {
+            let garbage = jvm.new_instance("Point", "(II)V", &[JvmValue::Int(i), JvmValue::Int(i)])?;
+            jvm.release(garbage);
+        }
+        let point = jvm.new_instance("Point", "(II)V", &[JvmValue::Int(3), JvmValue::Int(4)])?;
+        // 构造器运行期间多次触发 GC，Trail 对象本身也会被移动
+        let trail = jvm.new_instance("Trail", "(I)V", &[JvmValue::Int(10)])?;
+        jvm.interpreter_mut().collect_garbage();
+
+        assert_eq!(int(jvm.call_virtual(&point, "getX", "()I", &[])?), 103, "{:?}", strategy);
+        assert_eq!(int(jvm.call_virtual(&trail, "sumX", "()I", &[])?), 1045, "{:?}", strategy);
+        let origin = jvm.new_instance("Point", "()V", &[])?;
+        jvm.interpreter_mut().collect_garbage();
+        let distance =
+            jvm.call_virtual(&origin, "distanceSquared", "(LPoint;)J", &[jvm.value(&point)?])?;
+        assert!(matches!(distance, Some(JvmValue::Long(25))), "{:?}: {:?}", strategy, distance);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_released_handle_is_collected() -> Result<()> {
+    let mut jvm = jvm();
+    let point = jvm.new_instance("Point", "()V", &[])?;
+    let object = jvm.interpreter().resolve(&point)?;
+    jvm.interpreter_mut().collect_garbage();
+    assert!(jvm.interpreter().heap.get(object).is_ok());
+
+    jvm.release(point);
+    jvm.interpreter_mut().collect_garbage();
+    assert!(jvm.interpreter().heap.get(object).is_err());
+    Ok(())
+}
@@ -0,0 +1,67 @@
+//! 测试 java/lang/Object 的内置方法：identity hashCode、equals、getClass、toString
+
+use rsjvm::gc::GcStrategy;
+use rsjvm::{Jvm, JvmBuilder, Result};
+
+fn jvm() -> Jvm {
+    JvmBuilder::new().class_path("examples").build()
+}
+
+#[test]
+fn test_identity_hash_code() -> Result<()> {
+    let mut jvm = jvm();
+    let stable: bool = jvm.call_static_typed("ObjectMethods", "sameHashTwice", "()Z", ())?;
+    assert!(stable);
+    let different: bool = jvm.call_static_typed("ObjectMethods", "differentHashes", "()Z", ())?;
+    assert!(different);
+    Ok(())
+}
+
+#[test]
+fn test_equals_and_get_class() -> Result<()> {
+    let mut jvm = jvm();
+    let identity: bool = jvm.call_static_typed("ObjectMethods", "equalsIsIdentity", "()Z", ())?;
+    assert!(identity);
+    let same: bool = jvm.call_static_typed("ObjectMethods", "sameClassObject", "()Z", ())?;
+    assert!(same);
+    let name: String =
+        jvm.call_static_typed("ObjectMethods", "className", "()Ljava/lang/String;", ())?;
+    assert_eq!(name, "ObjectMethods$Plain");
+    Ok(())
+}
+
+#[test]
+fn test_to_string_without_override() -> Result<()> {
+    let mut jvm = jvm();
+    let hash: i32 = jvm.call_static_typed("ObjectMethods", "keepAfterGarbage", "()I", ())?;
+    let text: String =
+        jvm.call_static_typed("ObjectMethods", "keptToString", "()Ljava/lang/String;", ())?;
+    assert_eq!(text, format!("ObjectMethods$Plain@{:x}", hash));
+
+    // toString 使用子类覆盖的 hashCode
+    let text: String =
+        jvm.call_static_typed("ObjectMethods", "fixedHashToString", "()Ljava/lang/String;", ())?;
+    assert_eq!(text, "ObjectMethods$FixedHash@ff");
+    Ok(())
+}
+
+#[test]
+fn test_hash_code_survives_compaction() -> Result<()> {
+    let mut jvm = JvmBuilder::new()
+        .class_path("examples")
+        .gc_strategy(GcStrategy::MarkCompact)
+        .gc_threshold(None)
+        .build();
+    let hash: i32 = jvm.call_static_typed("ObjectMethods", "keepAfterGarbage", "()I", ())?;
+    let before = jvm.interpreter().heap.find_by_class("ObjectMethods$Plain");
+    assert_eq!(before.len(), 101);
+
+    jvm.interpreter_mut().collect_garbage();
+    let after = jvm.interpreter().heap.find_by_class("ObjectMethods$Plain");
+    assert_eq!(after.len(), 1);
+    assert_ne!(after[0], before[100], "the kept object should have moved");
+
+    let moved: i32 = jvm.call_static_typed("ObjectMethods", "keptHash", "()I", ())?;
+    assert_eq!(moved, hash);
+    Ok(())
+}
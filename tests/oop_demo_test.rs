@@ -0,0 +1,104 @@
+//! 面向对象里程碑的验收测试：examples/oop_demo
+//!
+//! 失败时指出最先出问题的是哪一项子功能（对应哪条指令），按这个顺序实现
+
+use rsjvm::classfile::ClassFile;
+use rsjvm::classloader::ClassLoader;
+use rsjvm::interpreter::Interpreter;
+use std::process::Command;
+
+const DEMO_DIR: &str = "examples/oop_demo";
+
+/// 场景用到的指令和对应的子功能
+const SUB_FEATURES: &[(u8, &str, &str)] = &[
+    (0xBB, "new", "对象分配"),
+    (0xB7, "invokespecial", "带参数的构造器 / super(...)"),
+    (0xB5, "putfield", "构造器中设置字段"),
+    (0xB4, "getfield", "读取字段"),
+    (0xBD, "anewarray", "引用数组分配"),
+    (0x53, "aastore", "引用数组写入"),
+    (0x32, "aaload", "引用数组读取"),
+    (0xBE, "arraylength", "按数组长度循环"),
+    (0xB6, "invokevirtual", "虚方法动态分派"),
+    (0x6B, "dmul", "double 运算"),
+    (0x63, "dadd", "double 运算"),
+    (0xAF, "dreturn", "返回 double"),
+    (0xB2, "getstatic", "System.out"),
+];
+
+/// 从错误信息中找出失败的指令，给出它对应的子功能
+fn diagnose(output: &str) -> String {
+    if let Some(pos) = output.find("Unknown opcode: 0x") {
+        let hex = &output[pos + "Unknown opcode: 0x".len()..][..2];
+        let opcode = u8::from_str_radix(hex, 16).unwrap();
+        return match SUB_FEATURES.iter().find(|(op, _, _)| *op == opcode) {
+            Some((_, name, feature)) => format!("首先失败的指令: {} ({})", name, feature),
+            None => format!("首先失败的指令: 0x{:02X}", opcode),
+        };
+    }
+    for (_, name, feature) in SUB_FEATURES {
+        if output.to_lowercase().contains(name) {
+            return format!("错误涉及 {} ({})", name, feature);
+        }
+    }
+    "未能定位到具体指令".to_string()
+}
+
+#[test]
+fn test_oop_demo_matches_expected_output() {
+    let output = Command::new(env!("CARGO_BIN_EXE_rsjvm"))
+        .args(["run", &format!("{}/OopDemo.class", DEMO_DIR)])
+        .env("RUST_BACKTRACE", "0")
+        .output()
+        .expect("failed to run rsjvm");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        output.status.success(),
+        "OopDemo 执行失败，{}\nstdout:\n{}\nstderr:\n{}",
+        diagnose(&format!("{}{}", stdout, stderr)),
+        stdout,
+        stderr
+    );
+
+    let program = stdout.split_once("=== 开始执行 ===\n").unwrap().1;
+    let program = program.split("✓ 执行成功").next().unwrap();
+    let expected = std::fs::read_to_string(format!("{}/OopDemo.expected", DEMO_DIR)).unwrap();
+    for (i, (actual, wanted)) in program.lines().zip(expected.lines()).enumerate() {
+        // 偶数行是 getSides() 的 int，奇数行和最后一行是 double
+        let feature = if i % 2 == 0 && i + 1 < expected.lines().count() {
+            "getfield / 继承的 getSides()"
+        } else {
+            "println(double) 的格式"
+        };
+        assert_eq!(actual, wanted, "第 {} 行输出不同（{}）", i + 1, feature);
+    }
+    assert_eq!(program, expected);
+}
+
+#[test]
+fn test_oop_demo_run_main() -> rsjvm::Result<()> {
+    let mut interpreter =
+        Interpreter::with_class_loader(ClassLoader::new(vec![DEMO_DIR.into()]));
+    let class_file = ClassFile::from_file(format!("{}/OopDemo.class", DEMO_DIR))?;
+    let class_name = interpreter.load_class(class_file)?;
+    interpreter.run_main(&class_name, &[])?;
+
+    // 按需加载了整个类层次
+    for class in ["Shape", "Circle", "Rect"] {
+        assert!(interpreter.metaspace.is_class_loaded(class), "{} 未加载", class);
+    }
+    // 三个形状、Shape[] 和 String[] 参数都还在堆上（没有 GC）
+    let histogram = interpreter.run_stats().heap_histogram;
+    let count = |name: &str| {
+        histogram
+            .iter()
+            .find(|e| e.class_name == name)
+            .map_or(0, |e| e.count)
+    };
+    assert_eq!(count("Circle"), 2);
+    assert_eq!(count("Rect"), 1);
+    assert_eq!(count("[LShape;"), 1);
+    assert_eq!(count("[Ljava/lang/String;"), 1);
+    Ok(())
+}
@@ -0,0 +1,138 @@
+//! 测试截断的操作数和越界的分支目标只返回错误，不会让进程 panic
+
+use rsjvm::interpreter::trace::TraceEvent;
+use rsjvm::interpreter::Interpreter;
+use rsjvm::runtime::frame::JvmValue;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
+
+fn run(code: &[u8]) -> rsjvm::Result<Option<JvmValue>> {
+    Interpreter::new().execute_method_with_class("Fuzz", "run:()I", code, 4, 4)
+}
+
+fn run_err(code: &[u8]) -> String {
+    match run(code) {
+        Ok(value) => panic!("expected an error, got {:?}", value),
+        Err(e) => format!("{:#}", e),
+    }
+}
+
+#[test]
+fn test_truncated_operands_are_reported() {
+    // pc 7 的 goto 只剩一个偏移字节
+    let code = [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xa7];
+    let err = run_err(&code);
+    assert!(err.contains("truncated instruction goto at pc 7 (code length 8)"), "{}", err);
+
+    let err = run_err(&[0x10]);
+    assert!(err.contains("truncated instruction bipush at pc 0 (code length 1)"), "{}", err);
+
+    let err = run_err(&[0x11, 0x01]);
+    assert!(err.contains("truncated instruction sipush at pc 0 (code length 2)"), "{}", err);
+
+    let err = run_err(&[0x03, 0x3b, 0x84, 0x00]);
+    assert!(err.contains("truncated instruction iinc at pc 2 (code length 4)"), "{}", err);
+
+    let err = run_err(&[0xb2, 0x00]);
+    assert!(err.contains("truncated instruction getstatic at pc 0 (code length 2)"), "{}", err);
+
+    // tableswitch 的 default/low/high 不完整
+    let err = run_err(&[0x03, 0xaa, 0x00, 0x00, 0x00, 0x00, 0x00]);
+    assert!(err.contains("truncated instruction tableswitch at pc 1 (code length 7)"), "{}", err);
+}
+
+#[test]
+fn test_branch_targets_must_land_in_code() {
+    // goto -5
+    let err = run_err(&[0x00, 0xa7, 0xff, 0xfb]);
+    assert!(
+        err.contains("branch target -4 of goto at pc 1 is outside the code (code length 4)"),
+        "{}",
+        err
+    );
+
+    // ifeq +100
+    let err = run_err(&[0x03, 0x99, 0x00, 0x64, 0xac]);
+    assert!(
+        err.contains("branch target 101 of ifeq at pc 1 is outside the code (code length 5)"),
+        "{}",
+        err
+    );
+
+    // 条件不成立时不检查目标：iconst_1; ifeq +100; iconst_1; ireturn
+    let result = run(&[0x04, 0x99, 0x00, 0x64, 0x04, 0xac]).unwrap();
+    assert!(matches!(result, Some(JvmValue::Int(1))), "{:?}", result);
+
+    // lookupswitch 没有匹配项时跳到 default = -1000
+    let code = [
+        0x03, 0xab, 0x00, 0x00, // iconst_0; lookupswitch + 填充
+        0xff, 0xff, 0xfc, 0x18, // default
+        0x00, 0x00, 0x00, 0x00, // npairs = 0
+    ];
+    let err = run_err(&code);
+    assert!(err.contains("branch target -999 of lookupswitch at pc 1"), "{}", err);
+}
+
+/// 钩子用来中止长时间运行的随机程序（随机跳转很容易形成死循环）
+struct StepLimit;
+
+const STEP_LIMIT: usize = 2_000;
+
+fn quiet_step_limit_panics() {
+    static INIT: Once = Once::new();
+    INIT.call_once(|| {
+        let default = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if info.payload().downcast_ref::<StepLimit>().is_none() {
+                default(info);
+            }
+        }));
+    });
+}
+
+/// 固定种子的 xorshift，保证失败可复现
+struct XorShift(u64);
+
+impl XorShift {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+}
+
+#[test]
+fn test_random_code_never_panics() {
+    quiet_step_limit_panics();
+    let mut rng = XorShift(0x2545_f491_4f6c_dd1d);
+
+    for round in 0..3_000 {
+        let len = 1 + (rng.next() % 24) as usize;
+        let code: Vec<u8> = (0..len)
+            .map(|_| match rng.next() as u8 {
+                // 堆还没有大小上限，随机数组长度可能耗尽内存
+                0xbc | 0xbd | 0xc5 => 0x00,
+                byte => byte,
+            })
+            .collect();
+
+        let outcome = panic::catch_unwind(AssertUnwindSafe(|| {
+            let mut interpreter = Interpreter::new();
+            let mut steps = 0;
+            interpreter.set_trace_hook(move |_: &TraceEvent| {
+                steps += 1;
+                if steps > STEP_LIMIT {
+                    panic::panic_any(StepLimit);
+                }
+            });
+            interpreter.execute_method_with_class("Fuzz", "run:()I", &code, 4, 4)
+        }));
+
+        if let Err(payload) = outcome {
+            if payload.downcast_ref::<StepLimit>().is_none() {
+                panic!("round {}: code {:02x?} panicked", round, code);
+            }
+        }
+    }
+}
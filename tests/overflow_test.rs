@@ -0,0 +1,69 @@
+//! 测试整数运算的溢出语义
+//!
+//! 解释器用 wrapping 运算实现，debug 和 release 构建得到相同的回绕结果
+
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::{Jvm, JvmBuilder, Result};
+
+fn jvm() -> Jvm {
+    JvmBuilder::new().class_path("examples").build()
+}
+
+#[test]
+fn test_int_arithmetic_wraps() -> Result<()> {
+    let mut jvm = jvm();
+    let cases = [
+        ("add", i32::MAX, 1, i32::MIN),
+        ("sub", i32::MIN, 1, i32::MAX),
+        ("mul", 65536, 65536, 0),
+        ("mul", i32::MAX, 2, -2),
+        ("div", i32::MIN, -1, i32::MIN),
+        ("div", -7, 2, -3), // 向零取整
+        ("rem", i32::MIN, -1, 0),
+        ("rem", -7, 3, -1),
+    ];
+    for (method, a, b, expected) in cases {
+        let result: i32 = jvm.call_static_typed("IntOverflow", method, "(II)I", (a, b))?;
+        assert_eq!(result, expected, "{}({}, {})", method, a, b);
+    }
+    let negated: i32 = jvm.call_static_typed("IntOverflow", "neg", "(I)I", (i32::MIN,))?;
+    assert_eq!(negated, i32::MIN);
+    let negated: i32 = jvm.call_static_typed("IntOverflow", "neg", "(I)I", (5,))?;
+    assert_eq!(negated, -5);
+    Ok(())
+}
+
+#[test]
+fn test_long_arithmetic_wraps() -> Result<()> {
+    let mut jvm = jvm();
+    let cases = [
+        ("ladd", i64::MAX, 1i64, i64::MIN),
+        ("lsub", i64::MIN, 1, i64::MAX),
+        ("lmul", 1 << 32, 1 << 32, 0),
+        ("ldiv", i64::MIN, -1, i64::MIN),
+        ("ldiv", -7, 2, -3),
+        ("lrem", i64::MIN, -1, 0),
+        ("lrem", 7, -3, 1),
+    ];
+    for (method, a, b, expected) in cases {
+        let result: i64 = jvm.call_static_typed("IntOverflow", method, "(JJ)J", (a, b))?;
+        assert_eq!(result, expected, "{}({}, {})", method, a, b);
+    }
+    let negated: i64 = jvm.call_static_typed("IntOverflow", "lneg", "(J)J", (i64::MIN,))?;
+    assert_eq!(negated, i64::MIN);
+    Ok(())
+}
+
+#[test]
+fn test_division_by_zero_throws() {
+    let mut jvm = jvm();
+    for (method, descriptor, args) in [
+        ("div", "(II)I", [JvmValue::Int(1), JvmValue::Int(0)]),
+        ("rem", "(II)I", [JvmValue::Int(1), JvmValue::Int(0)]),
+        ("ldiv", "(JJ)J", [JvmValue::Long(1), JvmValue::Long(0)]),
+        ("lrem", "(JJ)J", [JvmValue::Long(1), JvmValue::Long(0)]),
+    ] {
+        let err = jvm.call_static("IntOverflow", method, descriptor, &args).unwrap_err();
+        assert!(err.to_string().contains("/ by zero"), "{}: {}", method, err);
+    }
+}
@@ -0,0 +1,69 @@
+//! 测试 `parse --format json`：报告中的版本、标志、类名、常量池和方法的 Code 信息
+
+use rsjvm::classfile::ClassFile;
+use rsjvm::classfile::report::{flag_names, ClassReport, FlagTarget};
+use rsjvm::Result;
+use serde_json::Value;
+use std::process::Command;
+
+fn method<'a>(report: &'a Value, name: &str) -> &'a Value {
+    report["methods"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|m| m["name"] == name)
+        .unwrap_or_else(|| panic!("no method {}", name))
+}
+
+#[test]
+fn test_parse_json_cli() -> Result<()> {
+    let output = Command::new(env!("CARGO_BIN_EXE_rsjvm"))
+        .args(["parse", "--format", "json", "examples/ReturnOne.class"])
+        .env("RUST_BACKTRACE", "0")
+        .output()?;
+    assert!(output.status.success(), "{:?}", output);
+    // 标准输出只有 JSON 文档，没有给人读的提示行
+    let report: Value = serde_json::from_slice(&output.stdout)?;
+
+    assert_eq!(report["version"]["major"], 52);
+    assert_eq!(report["version"]["java"], "Java 8");
+    assert_eq!(report["access_flags"]["raw"], 0x21);
+    assert_eq!(report["access_flags"]["names"], serde_json::json!(["ACC_PUBLIC", "ACC_SUPER"]));
+    assert_eq!(report["this_class"], "ReturnOne");
+    assert_eq!(report["super_class"], "java/lang/Object");
+    assert_eq!(report["source_file"], "ReturnOne.java");
+
+    let constants = report["constant_pool"].as_array().unwrap();
+    assert_eq!(constants[0]["index"], 1);
+    assert!(constants.iter().any(|c| c["tag"] == "Methodref"
+        && c["value"] == "Method java/lang/Object.<init>:()V"));
+
+    let return_one = method(&report, "returnOne");
+    assert_eq!(return_one["descriptor"], "()I");
+    assert_eq!(return_one["access_flags"]["names"], serde_json::json!(["ACC_PUBLIC", "ACC_STATIC"]));
+    assert_eq!(return_one["code"]["max_stack"], 1);
+    assert_eq!(return_one["code"]["max_locals"], 0);
+    assert_eq!(return_one["code"]["code_length"], 2);
+    // 没有 --verbose 时不包含反汇编
+    assert!(return_one["code"].get("disassembly").is_none());
+    Ok(())
+}
+
+#[test]
+fn test_report_disassembly_when_verbose() -> Result<()> {
+    let class_file = ClassFile::from_file("examples/ReturnOne.class")?;
+    let report: Value = serde_json::from_str(&ClassReport::new(&class_file, true)?.to_json()?)?;
+    let calculate = method(&report, "calculate");
+    assert_eq!(calculate["code"]["disassembly"][0], "    0: bipush        10");
+    assert_eq!(calculate["code"]["disassembly"].as_array().unwrap().len(), 10);
+    Ok(())
+}
+
+#[test]
+fn test_flag_names_depend_on_target() {
+    // 0x0020 对类是 ACC_SUPER，对方法是 ACC_SYNCHRONIZED；0x0040 对字段是 volatile，对方法是 bridge
+    assert_eq!(flag_names(0x0020, FlagTarget::Class), ["ACC_SUPER"]);
+    assert_eq!(flag_names(0x0020, FlagTarget::Method), ["ACC_SYNCHRONIZED"]);
+    assert_eq!(flag_names(0x0040, FlagTarget::Field), ["ACC_VOLATILE"]);
+    assert_eq!(flag_names(0x0040, FlagTarget::Method), ["ACC_BRIDGE"]);
+}
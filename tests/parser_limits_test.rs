@@ -0,0 +1,136 @@
+//! 测试解析限制（ParserOptions）
+//!
+//! 手工构造超限的 class 文件字节。超限项后面的内容故意截断：
+//! 如果解析器先分配/读取再检查，会得到 EOF 错误而不是限制错误。
+
+use rsjvm::classfile::{ClassFile, ParserOptions};
+
+/// 最小 class 文件头：魔数 + 版本号
+fn header() -> Vec<u8> {
+    let mut bytes = vec![0xCA, 0xFE, 0xBA, 0xBE];
+    bytes.extend_from_slice(&0u16.to_be_bytes()); // minor
+    bytes.extend_from_slice(&52u16.to_be_bytes()); // major (Java 8)
+    bytes
+}
+
+fn push_utf8(bytes: &mut Vec<u8>, s: &str) {
+    bytes.push(1); // CONSTANT_Utf8
+    bytes.extend_from_slice(&(s.len() as u16).to_be_bytes());
+    bytes.extend_from_slice(s.as_bytes());
+}
+
+/// 常量池 [#1 Utf8 "Code"]，随后是空的类头、接口、字段
+fn class_prefix() -> Vec<u8> {
+    let mut bytes = header();
+    bytes.extend_from_slice(&2u16.to_be_bytes()); // constant_pool_count
+    push_utf8(&mut bytes, "Code");
+    bytes.extend_from_slice(&0x0021u16.to_be_bytes()); // access_flags
+    bytes.extend_from_slice(&0u16.to_be_bytes()); // this_class
+    bytes.extend_from_slice(&0u16.to_be_bytes()); // super_class
+    bytes.extend_from_slice(&0u16.to_be_bytes()); // interfaces_count
+    bytes.extend_from_slice(&0u16.to_be_bytes()); // fields_count
+    bytes
+}
+
+fn assert_limit_error(bytes: &[u8], options: &ParserOptions, name: &str, observed: usize) {
+    let err = ClassFile::from_bytes_with_options(bytes, options)
+        .expect_err("expected parse limit error")
+        .to_string();
+    assert!(err.contains(name), "error should name {}: {}", name, err);
+    assert!(
+        err.contains(&format!("= {}", observed)),
+        "error should report observed value {}: {}",
+        observed,
+        err
+    );
+}
+
+#[test]
+fn test_utf8_length_limit() {
+    // 声明 60000 字节的 Utf8，但只给出 10 字节
+    let mut bytes = header();
+    bytes.extend_from_slice(&2u16.to_be_bytes());
+    bytes.push(1);
+    bytes.extend_from_slice(&60000u16.to_be_bytes());
+    bytes.extend_from_slice(&[b'a'; 10]);
+
+    let options = ParserOptions {
+        max_utf8_length: 1024,
+        ..ParserOptions::default()
+    };
+    assert_limit_error(&bytes, &options, "max_utf8_length", 60000);
+}
+
+#[test]
+fn test_constant_pool_bytes_limit() {
+    // 1000 个 Utf8，每个 3 + 100 字节
+    let mut bytes = header();
+    bytes.extend_from_slice(&1001u16.to_be_bytes());
+    let payload = "x".repeat(100);
+    for _ in 0..1000 {
+        push_utf8(&mut bytes, &payload);
+    }
+
+    let options = ParserOptions {
+        max_constant_pool_bytes: 1000,
+        ..ParserOptions::default()
+    };
+    // 第 10 个条目使累计字节数达到 10 * 103 = 1030 > 1000
+    assert_limit_error(&bytes, &options, "max_constant_pool_bytes", 1030);
+}
+
+#[test]
+fn test_max_methods_limit() {
+    // 声明 5000 个方法，但没有任何方法内容
+    let mut bytes = class_prefix();
+    bytes.extend_from_slice(&5000u16.to_be_bytes());
+
+    let options = ParserOptions {
+        max_methods: 100,
+        ..ParserOptions::default()
+    };
+    assert_limit_error(&bytes, &options, "max_methods", 5000);
+}
+
+#[test]
+fn test_code_length_limit() {
+    // 一个方法，Code 属性声明 code_length = 65000
+    let mut bytes = class_prefix();
+    bytes.extend_from_slice(&1u16.to_be_bytes()); // methods_count
+    bytes.extend_from_slice(&0x0009u16.to_be_bytes()); // access_flags
+    bytes.extend_from_slice(&1u16.to_be_bytes()); // name_index
+    bytes.extend_from_slice(&1u16.to_be_bytes()); // descriptor_index
+    bytes.extend_from_slice(&1u16.to_be_bytes()); // attributes_count
+    bytes.extend_from_slice(&1u16.to_be_bytes()); // "Code"
+    bytes.extend_from_slice(&8u32.to_be_bytes()); // attribute_length（只含头部）
+    bytes.extend_from_slice(&1u16.to_be_bytes()); // max_stack
+    bytes.extend_from_slice(&1u16.to_be_bytes()); // max_locals
+    bytes.extend_from_slice(&65000u32.to_be_bytes()); // code_length
+
+    let options = ParserOptions {
+        max_code_length: 1024,
+        ..ParserOptions::default()
+    };
+    assert_limit_error(&bytes, &options, "max_code_length", 65000);
+}
+
+#[test]
+fn test_oversized_attribute_length_rejected() {
+    // attribute_length 声明 4GB，不能按此分配
+    let mut bytes = class_prefix();
+    bytes.extend_from_slice(&0u16.to_be_bytes()); // methods_count
+    bytes.extend_from_slice(&1u16.to_be_bytes()); // attributes_count
+    bytes.extend_from_slice(&1u16.to_be_bytes());
+    bytes.extend_from_slice(&u32::MAX.to_be_bytes());
+
+    let err = ClassFile::from_bytes(&bytes).unwrap_err().to_string();
+    assert!(err.contains("exceeds remaining"), "{}", err);
+}
+
+#[test]
+fn test_default_limits_accept_real_class() -> rsjvm::Result<()> {
+    let class_file =
+        ClassFile::from_file_with_options("examples/Calculator.class", &ParserOptions::default())?;
+    assert!(!class_file.methods.is_empty());
+    Ok(())
+}
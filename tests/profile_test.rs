@@ -0,0 +1,70 @@
+//! 测试性能剖析：操作码计数、方法调用次数和指令数
+
+use rsjvm::interpreter::instructions::opcodes::{IADD, IINC, INVOKESTATIC};
+use rsjvm::{Jvm, JvmBuilder, Result};
+use std::process::Command;
+
+fn profiled() -> Jvm {
+    let mut jvm = JvmBuilder::new().class_path("examples").build();
+    jvm.interpreter_mut().set_profiling(true);
+    jvm
+}
+
+#[test]
+fn test_counts_match_loop_trips() -> Result<()> {
+    let mut jvm = profiled();
+    let sum: i32 = jvm.call_static_typed("ProfileDemo", "sumTo", "(I)I", (250,))?;
+    assert_eq!(sum, 31125);
+
+    let profile = jvm.interpreter_mut().take_profile().expect("profiling enabled");
+    assert_eq!(profile.opcode_count(IADD), 250);
+    assert_eq!(profile.opcode_count(IINC), 250);
+    assert_eq!(profile.opcode_count(INVOKESTATIC), 250);
+
+    let add = profile.method("ProfileDemo", "add:(II)I").unwrap();
+    // iload_0; iload_1; iadd; ireturn
+    assert_eq!((add.invocations, add.instructions), (250, 1000));
+    let sum_to = profile.method("ProfileDemo", "sumTo:(I)I").unwrap();
+    assert_eq!(sum_to.invocations, 1);
+    assert_eq!(profile.total_instructions(), add.instructions + sum_to.instructions);
+    assert_eq!(profile.hottest_methods(1)[0].method_key, "sumTo:(I)I");
+    Ok(())
+}
+
+#[test]
+fn test_take_profile_restarts_counting() -> Result<()> {
+    let mut jvm = profiled();
+    let _: i32 = jvm.call_static_typed("ProfileDemo", "add", "(II)I", (1, 2))?;
+    let first = jvm.interpreter_mut().take_profile().unwrap();
+    assert_eq!(first.total_instructions(), 4);
+
+    let _: i32 = jvm.call_static_typed("ProfileDemo", "sumTo", "(I)I", (3,))?;
+    let second = jvm.interpreter_mut().take_profile().unwrap();
+    assert_eq!(second.method("ProfileDemo", "add:(II)I").unwrap().invocations, 3);
+    Ok(())
+}
+
+#[test]
+fn test_disabled_profiling_collects_nothing() -> Result<()> {
+    let mut jvm = JvmBuilder::new().class_path("examples").build();
+    let _: i32 = jvm.call_static_typed("ProfileDemo", "sumTo", "(I)I", (10,))?;
+    assert!(jvm.interpreter_mut().take_profile().is_none());
+    Ok(())
+}
+
+#[test]
+fn test_run_profile_flag_prints_tables() {
+    let output = Command::new(env!("CARGO_BIN_EXE_rsjvm"))
+        .args(["run", "examples/ProfileDemo.class", "--profile"])
+        .output()
+        .expect("failed to run rsjvm");
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("=== 性能剖析 (1314 条指令) ==="), "{}", stdout);
+    assert!(stdout.contains("  iadd                    100    7.6%"), "{}", stdout);
+    assert!(
+        stdout.contains("  ProfileDemo.add:(II)I                         100          400"),
+        "{}",
+        stdout
+    );
+}
@@ -0,0 +1,83 @@
+//! 测试 Interpreter::reset_run_state：同一个解释器上连续运行多个程序
+
+use rsjvm::classfile::ClassFile;
+use rsjvm::classloader::ClassLoader;
+use rsjvm::interpreter::Interpreter;
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::runtime::metaspace::method_key;
+use rsjvm::Result;
+
+fn run_static(interpreter: &mut Interpreter, class_name: &str, method_name: &str) -> Result<i32> {
+    let (code, max_locals, max_stack) = {
+        let class_meta = interpreter.metaspace.get_class(class_name)?;
+        let method = class_meta.find_method(method_name, "()I")?;
+        (method.code.clone(), method.max_locals, method.max_stack)
+    };
+    match interpreter.execute_method_with_class(
+        class_name,
+        &method_key(method_name, "()I"),
+        &code,
+        max_locals,
+        max_stack,
+    )? {
+        Some(JvmValue::Int(v)) => Ok(v),
+        other => panic!("expected int, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_reset_restores_pristine_statics() -> Result<()> {
+    let mut interpreter = Interpreter::new();
+    let class_file = ClassFile::from_file("examples/StaticCounter.class")?;
+    let class_name = interpreter.load_class(class_file)?;
+
+    // 第一次运行：counter 变为 2
+    run_static(&mut interpreter, &class_name, "increment")?;
+    assert_eq!(run_static(&mut interpreter, &class_name, "increment")?, 2);
+    interpreter.heap.allocate("StaticCounter".to_string())?;
+
+    interpreter.reset_run_state();
+
+    // 静态字段和堆都被清空，类元数据保留
+    assert!(interpreter
+        .metaspace
+        .get_class(&class_name)?
+        .static_fields
+        .is_empty());
+    assert_eq!(interpreter.heap.object_count(), 0);
+    assert_eq!(interpreter.thread.stack_depth(), 0);
+
+    // 第二次运行看到的是初始状态
+    assert_eq!(run_static(&mut interpreter, &class_name, "increment")?, 1);
+    Ok(())
+}
+
+fn interpreter_with_loader() -> Interpreter {
+    Interpreter::with_class_loader(ClassLoader::new(vec!["examples".into()]))
+}
+
+#[test]
+fn test_reset_is_cheaper_than_recreation() -> Result<()> {
+    const RUNS: usize = 200;
+
+    // 每次重新创建解释器：每次都要重新解析、加载和链接
+    let mut loads = 0;
+    for _ in 0..RUNS {
+        let mut interpreter = interpreter_with_loader();
+        interpreter.ensure_class_loaded("StaticCounter")?;
+        run_static(&mut interpreter, "StaticCounter", "setAndGet")?;
+        loads += interpreter.metaspace.load_count();
+    }
+    assert_eq!(loads, RUNS);
+
+    // 复用解释器：只重置运行状态，类元数据只加载一次
+    let mut interpreter = interpreter_with_loader();
+    for _ in 0..RUNS {
+        interpreter.reset_run_state();
+        interpreter.ensure_class_loaded("StaticCounter")?;
+        run_static(&mut interpreter, "StaticCounter", "setAndGet")?;
+    }
+    assert_eq!(interpreter.metaspace.load_count(), 1);
+    assert_eq!(interpreter.metaspace.loaded_classes(), vec!["StaticCounter"]);
+    Ok(())
+}
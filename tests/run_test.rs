@@ -3,6 +3,8 @@
 //! 这个测试模拟完整的加载class文件 -> 解析 -> 执行的流程
 //! 运行: cargo test --test run_test -- --nocapture
 
+#![allow(deprecated)]
+
 use rsjvm::classfile::ClassFile;
 use rsjvm::interpreter::Interpreter;
 use rsjvm::runtime::frame::JvmValue;
@@ -272,7 +274,7 @@ fn test_all_methods_in_return_one() {
             .methods
             .iter()
             .find(|m| class_file.constant_pool.get_utf8(m.name_index).unwrap() == method_name)
-            .expect(&format!("Method {} not found", method_name));
+            .unwrap_or_else(|| panic!("Method {} not found", method_name));
 
         let code = method
             .attributes
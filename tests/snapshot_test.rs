@@ -0,0 +1,146 @@
+//! 测试执行快照：循环执行到一半存档，运行结束后恢复，再次运行得到相同的结果
+
+use rsjvm::classfile::ClassFile;
+use rsjvm::gc::GcStrategy;
+use rsjvm::interpreter::snapshot::ExecutionSnapshot;
+use rsjvm::interpreter::stepping::RunOutcome;
+use rsjvm::interpreter::{Interpreter, InterpreterOptions};
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::runtime::metaspace::ClassState;
+use rsjvm::Result;
+
+/// SnapshotLoop.run(10) 的返回值：385 + 285 + 11 * 1000 + 10 * 100000
+const RESULT: i32 = 1_011_670;
+
+fn interpreter(options: InterpreterOptions) -> Result<Interpreter> {
+    let mut interpreter = Interpreter::new_with_options(options);
+    interpreter.load_class(ClassFile::from_file("examples/SnapshotLoop.class")?)?;
+    Ok(interpreter)
+}
+
+/// 开始执行 run(10)，单步执行到第 5 次循环的条件判断（pc 12，i 在局部变量 3）
+fn run_to_iteration_5(interpreter: &mut Interpreter) -> Result<()> {
+    interpreter.start_method("SnapshotLoop", "run:(I)I", vec![JvmValue::Int(10)])?;
+    loop {
+        let step = interpreter.step()?;
+        assert!(!step.is_finished());
+        let i = interpreter.current_locals()?.get(3);
+        if step.next_pc == Some(12) && matches!(i, Some(JvmValue::Int(5))) {
+            return Ok(());
+        }
+    }
+}
+
+fn finish(interpreter: &mut Interpreter) -> Result<i32> {
+    match interpreter.resume()? {
+        RunOutcome::Finished(Some(JvmValue::Int(result))) => Ok(result),
+        other => panic!("expected an int result, got {:?}", other),
+    }
+}
+
+fn calls(interpreter: &Interpreter) -> Result<i32> {
+    let class = interpreter.metaspace.get_class("SnapshotLoop")?;
+    match class.static_fields.get("calls") {
+        Some(JvmValue::Int(calls)) => Ok(*calls),
+        other => panic!("expected SnapshotLoop.calls, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_restore_replays_to_the_same_result() -> Result<()> {
+    let mut interpreter = interpreter(InterpreterOptions::default())?;
+    run_to_iteration_5(&mut interpreter)?;
+    let checkpoint = interpreter.snapshot()?;
+    assert_eq!(checkpoint.frames.len(), 1);
+    assert_eq!(calls(&interpreter)?, 4);
+
+    assert_eq!(finish(&mut interpreter)?, RESULT);
+    assert_eq!(calls(&interpreter)?, 10);
+    assert!(!interpreter.is_paused());
+
+    // 回到第 5 次循环：局部变量、静态字段和堆上的 StringBuilder 都回到存档时的状态
+    interpreter.restore(&checkpoint)?;
+    assert!(interpreter.is_paused());
+    assert!(matches!(interpreter.current_locals()?[3], JvmValue::Int(5)));
+    assert_eq!(calls(&interpreter)?, 4);
+    let digits = interpreter.heap.find_by_class("java/lang/StringBuilder");
+    assert_eq!(digits.len(), 1);
+    assert_eq!(interpreter.heap.string_builder_mut(digits[0])?.as_str(), "1234");
+    assert_eq!(finish(&mut interpreter)?, RESULT);
+
+    // 同一个快照可以反复恢复
+    interpreter.restore(&checkpoint)?;
+    assert_eq!(finish(&mut interpreter)?, RESULT);
+    Ok(())
+}
+
+#[test]
+fn test_json_round_trip_into_a_new_interpreter() -> Result<()> {
+    let mut interpreter = interpreter(InterpreterOptions::default())?;
+    run_to_iteration_5(&mut interpreter)?;
+    let json = interpreter.snapshot()?.to_json()?;
+    assert!(json.contains("\"method_name\":\"run\""), "{}", json);
+    assert_eq!(finish(&mut interpreter)?, RESULT);
+
+    let checkpoint = ExecutionSnapshot::from_json(&json)?;
+    assert_eq!(checkpoint.to_json()?, json);
+    let mut restored = self::interpreter(InterpreterOptions::default())?;
+    restored.restore(&checkpoint)?;
+    assert_eq!(calls(&restored)?, 4);
+    assert_eq!(finish(&mut restored)?, RESULT);
+    Ok(())
+}
+
+#[test]
+fn test_restore_with_generational_gc() -> Result<()> {
+    let options = InterpreterOptions {
+        gc_strategy: GcStrategy::generational(),
+        ..Default::default()
+    };
+    let mut interpreter = interpreter(options)?;
+    run_to_iteration_5(&mut interpreter)?;
+    let checkpoint = interpreter.snapshot()?;
+    assert!(checkpoint.heap.nursery.as_ref().is_some_and(|young| !young.is_empty()));
+    assert_eq!(finish(&mut interpreter)?, RESULT);
+
+    interpreter.restore(&checkpoint)?;
+    interpreter.collect_minor_garbage();
+    assert_eq!(finish(&mut interpreter)?, RESULT);
+    Ok(())
+}
+
+#[test]
+fn test_restore_requires_loaded_classes() -> Result<()> {
+    let mut interpreter = interpreter(InterpreterOptions::default())?;
+    run_to_iteration_5(&mut interpreter)?;
+    let checkpoint = interpreter.snapshot()?;
+
+    let mut empty = Interpreter::new();
+    let err = empty.restore(&checkpoint).unwrap_err();
+    assert!(format!("{:#}", err).contains("Class not found: SnapshotLoop"), "{:#}", err);
+    assert!(!empty.is_paused());
+    assert_eq!(empty.thread.stack_depth(), 0);
+    assert_eq!(empty.heap.object_count(), 0);
+    Ok(())
+}
+
+#[test]
+fn test_snapshot_between_runs_has_no_frames() -> Result<()> {
+    let mut interpreter = interpreter(InterpreterOptions::default())?;
+    let before = interpreter.snapshot()?;
+    assert!(before.frames.is_empty());
+
+    let args = || vec![JvmValue::Int(10)];
+    let result = interpreter.invoke_static("SnapshotLoop", "run", "(I)I", args())?;
+    assert!(matches!(result, Some(JvmValue::Int(RESULT))));
+
+    // 回到 <clinit> 执行之前：静态字段回到默认值，下次调用重新初始化类
+    interpreter.restore(&before)?;
+    assert!(!interpreter.is_paused());
+    let class = interpreter.metaspace.get_class("SnapshotLoop")?;
+    assert_ne!(class.state, ClassState::Initialized);
+    assert!(class.static_fields.is_empty());
+    let result = interpreter.invoke_static("SnapshotLoop", "run", "(I)I", args())?;
+    assert!(matches!(result, Some(JvmValue::Int(RESULT))));
+    Ok(())
+}
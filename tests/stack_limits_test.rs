@@ -0,0 +1,91 @@
+//! 测试操作数栈的 max_stack 检查和线程栈的栈帧数上限
+
+use rsjvm::classfile::ClassFile;
+use rsjvm::interpreter::{Interpreter, InterpreterOptions};
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::runtime::Frame;
+use rsjvm::Result;
+
+fn load_overflow(options: InterpreterOptions) -> Result<Interpreter> {
+    let mut interpreter = Interpreter::new_with_options(options);
+    interpreter.load_class(ClassFile::from_file("examples/Overflow.class")?)?;
+    Ok(interpreter)
+}
+
+fn depth(interpreter: &Interpreter) -> Result<i32> {
+    match interpreter.metaspace.get_class("Overflow")?.static_fields.get("depth") {
+        Some(JvmValue::Int(v)) => Ok(*v),
+        other => panic!("Overflow.depth 期望是 Int, 实际: {:?}", other),
+    }
+}
+
+#[test]
+fn test_push_past_max_stack_errors() -> Result<()> {
+    let mut frame = Frame::new(0, 3);
+    frame.push(JvmValue::Long(1))?;
+    frame.push(JvmValue::Int(2))?;
+    let err = frame.push(JvmValue::Int(3)).unwrap_err();
+    assert_eq!(err.to_string(), "operand stack overflow: max_stack=3");
+    assert_eq!(frame.stack_slots(), 3);
+
+    // 弹出 long 释放两个槽位
+    frame.pop()?;
+    frame.pop()?;
+    frame.push(JvmValue::Double(1.0))?;
+    frame.push(JvmValue::Int(3))?;
+    assert_eq!(frame.stack_slots(), 3);
+    Ok(())
+}
+
+#[test]
+fn test_bytecode_exceeding_max_stack_errors() {
+    let code = [
+        0x04, // iconst_1
+        0x05, // iconst_2
+        0x60, // iadd
+        0xac, // ireturn
+    ];
+    let err = Interpreter::new()
+        .execute_method_with_class("Hand", "run:()I", &code, 0, 1)
+        .unwrap_err();
+    assert!(format!("{:#}", err).contains("operand stack overflow: max_stack=1"), "{:#}", err);
+
+    let result = Interpreter::new().execute_method_with_class(
+        "Hand",
+        "run:()I",
+        &code,
+        0,
+        2,
+    ).unwrap();
+    assert!(matches!(result, Some(JvmValue::Int(3))), "{:?}", result);
+}
+
+#[test]
+fn test_unbounded_recursion_is_stack_overflow() -> Result<()> {
+    let mut interpreter = load_overflow(InterpreterOptions {
+        max_frames: 100,
+        ..Default::default()
+    })?;
+    let err = interpreter.invoke_static("Overflow", "recurse", "()I", vec![]).unwrap_err();
+    let message = format!("{:#}", err);
+    assert!(message.contains("java/lang/StackOverflowError"), "{}", message);
+    assert!(message.contains("max_frames=100"), "{}", message);
+    // 100 个栈帧各执行一次 depth++，第 101 次调用压栈失败
+    assert_eq!(depth(&interpreter)?, 100);
+    assert_eq!(interpreter.thread.stack_depth(), 0);
+    Ok(())
+}
+
+#[test]
+fn test_stack_overflow_can_be_caught() -> Result<()> {
+    let mut interpreter = load_overflow(InterpreterOptions::default())?;
+    assert_eq!(interpreter.options().max_frames, rsjvm::runtime::thread::DEFAULT_MAX_FRAMES);
+    let result = interpreter.invoke_static("Overflow", "catchOverflow", "()I", vec![])?;
+    assert!(matches!(result, Some(JvmValue::Int(-1))), "{:?}", result);
+    assert_eq!(interpreter.thread.stack_depth(), 0);
+
+    // reset_run_state 之后仍然使用同样的上限
+    interpreter.reset_run_state();
+    assert_eq!(interpreter.thread.max_frames(), rsjvm::runtime::thread::DEFAULT_MAX_FRAMES);
+    Ok(())
+}
@@ -0,0 +1,96 @@
+//! 测试栈操作指令：pop/pop2、dup 系列和 swap
+//!
+//! long/double 在操作数栈上是一个条目、占两个槽位，pop2/dup2 遇到它们时只处理一个条目
+
+use rsjvm::interpreter::Interpreter;
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::Result;
+
+fn run(code: &[u8], max_locals: usize) -> Result<Option<JvmValue>> {
+    Interpreter::new().execute_method_with_class("Hand", "run:()I", code, max_locals, 8)
+}
+
+/// 执行 `code` 后栈上剩 `count` 个 int，把它们按从栈底到栈顶的顺序拼成十进制数返回
+fn stack_digits(code: &[u8], count: u8) -> Result<i32> {
+    let mut full = code.to_vec();
+    for local in 0..count {
+        full.extend([0x36, local]); // istore local（先存栈顶）
+    }
+    full.push(0x03); // iconst_0
+    for local in (0..count).rev() {
+        full.extend([0x10, 10, 0x68, 0x15, local, 0x60]); // bipush 10; imul; iload local; iadd
+    }
+    full.push(0xac); // ireturn
+    match run(&full, count as usize)? {
+        Some(JvmValue::Int(v)) => Ok(v),
+        other => panic!("expected int, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_category_one_forms() -> Result<()> {
+    // iconst_1 iconst_2 iconst_3 iconst_4
+    let base = [0x04, 0x05, 0x06, 0x07];
+    let with = |opcode: u8| [&base[..], &[opcode]].concat();
+    assert_eq!(stack_digits(&with(0x57), 3)?, 123); // pop
+    assert_eq!(stack_digits(&with(0x58), 2)?, 12); // pop2
+    assert_eq!(stack_digits(&with(0x59), 5)?, 12344); // dup
+    assert_eq!(stack_digits(&with(0x5a), 5)?, 12434); // dup_x1
+    assert_eq!(stack_digits(&with(0x5b), 5)?, 14234); // dup_x2
+    assert_eq!(stack_digits(&with(0x5c), 6)?, 123434); // dup2
+    assert_eq!(stack_digits(&with(0x5d), 6)?, 134234); // dup2_x1
+    assert_eq!(stack_digits(&with(0x5e), 6)?, 341234); // dup2_x2
+    assert_eq!(stack_digits(&with(0x5f), 4)?, 1243); // swap
+    Ok(())
+}
+
+#[test]
+fn test_category_two_forms() -> Result<()> {
+    // iconst_3; lconst_1; pop2; ireturn → pop2 弹出整个 long
+    assert!(matches!(run(&[0x06, 0x0a, 0x58, 0xac], 0)?, Some(JvmValue::Int(3))));
+
+    // lconst_1; dup2; ladd; lreturn → dup2 复制整个 long
+    assert!(matches!(run(&[0x0a, 0x5c, 0x61, 0xad], 0)?, Some(JvmValue::Long(2))));
+
+    // lconst_1; iconst_5; dup_x2 → 5, 1L, 5；istore_0; pop2; iload_0; iadd → 10
+    let code = [0x0a, 0x08, 0x5b, 0x3b, 0x58, 0x1a, 0x60, 0xac];
+    assert!(matches!(run(&code, 1)?, Some(JvmValue::Int(10))));
+
+    // iconst_5; lconst_1; dup2_x1 → 1L, 5, 1L；lstore_0; pop; lload_0; ladd → 2L
+    let code = [0x08, 0x0a, 0x5d, 0x3f, 0x57, 0x1e, 0x61, 0xad];
+    assert!(matches!(run(&code, 2)?, Some(JvmValue::Long(2))));
+
+    // lconst_0; lconst_1; dup2_x2 → 1L, 0L, 1L；lsub; lsub → 1 - (0 - 1) = 2
+    let code = [0x09, 0x0a, 0x5e, 0x65, 0x65, 0xad];
+    assert!(matches!(run(&code, 0)?, Some(JvmValue::Long(2))));
+
+    // iconst_1; iconst_2; lconst_1; dup2_x2 → 1L, 1, 2, 1L
+    // lstore_0; isub; istore_2; pop2; iload_2; ireturn → 1 - 2 = -1
+    let code = [0x04, 0x05, 0x0a, 0x5e, 0x3f, 0x64, 0x3d, 0x58, 0x1c, 0xac];
+    assert!(matches!(run(&code, 3)?, Some(JvmValue::Int(-1))));
+
+    // lconst_1; iconst_1; iconst_2; dup2_x2 → 1, 2, 1L, 1, 2
+    // isub; istore_0; pop2; isub; iload_0; iadd → (1 - 2) + (1 - 2) = -2
+    let code = [0x0a, 0x04, 0x05, 0x5e, 0x64, 0x3b, 0x58, 0x64, 0x1a, 0x60, 0xac];
+    assert!(matches!(run(&code, 1)?, Some(JvmValue::Int(-2))));
+    Ok(())
+}
+
+#[test]
+fn test_splitting_a_long_is_an_error() {
+    // lconst_1; pop
+    let err = run(&[0x0a, 0x57, 0xb1], 0).unwrap_err();
+    assert!(format!("{:#}", err).contains("1-slot stack operation would split a long/double value"), "{:#}", err);
+
+    // iconst_1; lconst_1; swap
+    let err = run(&[0x04, 0x0a, 0x5f, 0xb1], 0).unwrap_err();
+    assert!(format!("{:#}", err).contains("would split a long/double value"), "{:#}", err);
+
+    // lconst_1; iconst_1; dup2 → 栈顶两个槽位是 int 和半个 long
+    let err = run(&[0x0a, 0x04, 0x5c, 0xb1], 0).unwrap_err();
+    assert!(format!("{:#}", err).contains("2-slot stack operation would split"), "{:#}", err);
+
+    // iconst_1; pop2
+    let err = run(&[0x04, 0x58, 0xb1], 0).unwrap_err();
+    assert!(format!("{:#}", err).contains("Operand stack underflow: needs 2 slots, has 1"), "{:#}", err);
+}
@@ -0,0 +1,107 @@
+//! 测试终止执行的错误携带的栈轨迹（ExecutionError）
+
+use rsjvm::interpreter::Interpreter;
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::runtime::{ExecutionError, FrameInfo};
+use rsjvm::JvmBuilder;
+use std::process::Command;
+
+/// (stack_size, max_locals)：出错时操作数栈中的值个数和局部变量表的槽位数
+fn frame(method: &str, descriptor: &str, pc: usize, line: u16, sizes: (usize, usize)) -> FrameInfo {
+    FrameInfo {
+        class_name: "Calculator".to_string(),
+        method_name: method.to_string(),
+        descriptor: descriptor.to_string(),
+        pc,
+        stack_size: sizes.0,
+        max_locals: sizes.1,
+        source_file: Some("Calculator.java".to_string()),
+        line: Some(line),
+    }
+}
+
+#[test]
+fn test_uncaught_exception_lists_every_frame() {
+    let mut jvm = JvmBuilder::new().class_path("examples").build();
+    let args = [JvmValue::Int(10), JvmValue::Int(0)];
+    let err = jvm.call_static("Calculator", "average", "(II)I", &args).unwrap_err();
+    let error = err.downcast_ref::<ExecutionError>().expect("ExecutionError");
+    assert_eq!(
+        error.cause.to_string(),
+        "Uncaught exception: java/lang/ArithmeticException: / by zero"
+    );
+    // divide 停在 idiv；调用者的 pc 是调用指令之后的位置，行号按调用指令查找
+    assert_eq!(
+        error.frames,
+        vec![
+            frame("divide", "(II)I", 2, 23, (0, 2)),
+            frame("percent", "(II)I", 8, 32, (0, 2)),
+            frame("average", "(II)I", 5, 28, (0, 2)),
+        ]
+    );
+
+    let rendered = err.to_string();
+    assert!(
+        rendered.ends_with(
+            "\n\tat Calculator.divide(II)I pc=2 (Calculator.java:23)\
+             \n\tat Calculator.percent(II)I pc=8 (Calculator.java:32)\
+             \n\tat Calculator.average(II)I pc=5 (Calculator.java:28)"
+        ),
+        "{}",
+        rendered
+    );
+}
+
+#[test]
+fn test_internal_error_keeps_live_frames() {
+    let code = [
+        0x03, // iconst_0
+        0x5f, // swap：栈上只有一个值
+        0xac, // ireturn
+    ];
+    let err = Interpreter::new()
+        .execute_method_with_class("Hand", "run:()I", &code, 0, 2)
+        .unwrap_err();
+    let error = err.downcast_ref::<ExecutionError>().expect("ExecutionError");
+    assert!(error.cause.to_string().contains("underflow"), "{:#}", error.cause);
+    assert_eq!(error.frames.len(), 1);
+    assert_eq!((error.frames[0].class_name.as_str(), error.frames[0].pc), ("Hand", 1));
+    assert_eq!(error.frames[0].line, None);
+}
+
+#[test]
+fn test_entry_frame_named_by_method_key() {
+    // twin 和 third 的字节码相同：入口栈帧的方法名和行号来自方法键，而不是按字节码反查。
+    // 每轮换一个新的 Jvm，方法表的遍历顺序随之变化
+    for _ in 0..8 {
+        let mut jvm = JvmBuilder::new().class_path("examples").build();
+        jvm.load_class_by_name("CallChain").unwrap();
+        let interpreter = jvm.interpreter_mut();
+        let class = interpreter.metaspace.get_class("CallChain").unwrap();
+        let method = class.find_method("twin", "()I").unwrap();
+        let (code, max_locals, max_stack) = (method.code.clone(), method.max_locals, method.max_stack);
+        let err = interpreter
+            .execute_method_with_class("CallChain", "twin:()I", &code, max_locals, max_stack)
+            .unwrap_err();
+        let error = err.downcast_ref::<ExecutionError>().expect("ExecutionError");
+        assert_eq!(error.frames.len(), 1);
+        assert_eq!(error.frames[0].method_name, "twin");
+        assert_eq!(error.frames[0].line, Some(58));
+    }
+}
+
+#[test]
+fn test_run_prints_stack_trace() {
+    let output = Command::new(env!("CARGO_BIN_EXE_rsjvm"))
+        .args(["run", "examples/CallChain.class", "-m", "third"])
+        .output()
+        .expect("failed to run rsjvm");
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("Uncaught exception: java/lang/IllegalStateException"),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains("    at CallChain.third()I pc=7 (CallChain.java:"), "{}", stdout);
+}
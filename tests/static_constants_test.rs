@@ -0,0 +1,62 @@
+//! 测试 static final 常量分析（rsjvm parse --constants）
+
+use rsjvm::classfile::static_constants::{static_final_constants, ConstValue, StaticFinalValue};
+use rsjvm::classfile::ClassFile;
+use rsjvm::Result;
+
+fn constants() -> Result<Vec<(String, StaticFinalValue)>> {
+    let class_file = ClassFile::from_file("examples/StaticConstants.class")?;
+    Ok(static_final_constants(&class_file)?
+        .into_iter()
+        .map(|c| (c.name, c.value))
+        .collect())
+}
+
+fn value_of(constants: &[(String, StaticFinalValue)], name: &str) -> StaticFinalValue {
+    constants
+        .iter()
+        .find(|(n, _)| n == name)
+        .map(|(_, v)| v.clone())
+        .unwrap_or_else(|| panic!("field {} not listed", name))
+}
+
+#[test]
+fn test_constant_value_attribute() -> Result<()> {
+    let constants = constants()?;
+    assert_eq!(
+        value_of(&constants, "N"),
+        StaticFinalValue::ConstantValue(ConstValue::Int(10240))
+    );
+    assert_eq!(
+        value_of(&constants, "NAME"),
+        StaticFinalValue::ConstantValue(ConstValue::String("rsjvm".to_string()))
+    );
+    Ok(())
+}
+
+#[test]
+fn test_foldable_clinit_assignment() -> Result<()> {
+    let constants = constants()?;
+    // A = 6; B = A * 7L; 由 <clinit> 求值得到
+    assert_eq!(
+        value_of(&constants, "A"),
+        StaticFinalValue::Computed(ConstValue::Int(6))
+    );
+    assert_eq!(
+        value_of(&constants, "B"),
+        StaticFinalValue::Computed(ConstValue::Long(42))
+    );
+    Ok(())
+}
+
+#[test]
+fn test_non_foldable_is_runtime_initialized() -> Result<()> {
+    let constants = constants()?;
+    // START = System.nanoTime() 需要方法调用
+    assert_eq!(
+        value_of(&constants, "START"),
+        StaticFinalValue::RuntimeInitialized
+    );
+    assert_eq!(constants.len(), 5);
+    Ok(())
+}
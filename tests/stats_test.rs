@@ -0,0 +1,59 @@
+//! 测试运行统计的顺序保证
+
+use rsjvm::classfile::ClassFile;
+use rsjvm::interpreter::Interpreter;
+use rsjvm::Result;
+use std::process::Command;
+
+#[test]
+fn test_run_stats_order() -> Result<()> {
+    let mut interpreter = Interpreter::new();
+    interpreter.load_class(ClassFile::from_file("examples/ReturnOne.class")?)?;
+    interpreter.load_class(ClassFile::from_file("examples/Calculator.class")?)?;
+
+    for _ in 0..3 {
+        interpreter.heap.allocate("Zebra".to_string())?;
+        interpreter.heap.allocate_string("s")?;
+    }
+    interpreter.heap.allocate("Apple".to_string())?;
+    interpreter.heap.allocate("Mango".to_string())?;
+
+    let stats = interpreter.run_stats();
+    let classes: Vec<_> = stats.classes.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(classes, ["ReturnOne", "Calculator"]);
+
+    // 数量降序，数量相同按类名
+    let histogram: Vec<_> = stats
+        .heap_histogram
+        .iter()
+        .map(|e| (e.class_name.as_str(), e.count))
+        .collect();
+    assert_eq!(
+        histogram,
+        [("Zebra", 3), ("java/lang/String", 3), ("Apple", 1), ("Mango", 1)]
+    );
+    assert_eq!(stats.live_objects, 8);
+    Ok(())
+}
+
+fn run_with_stats() -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_rsjvm"))
+        .args(["run", "examples/RefArrayTest.class", "-m", "twoD", "--stats"])
+        .output()
+        .expect("failed to run rsjvm");
+    assert!(output.status.success(), "{:?}", output);
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn test_cli_stats_output_is_stable() {
+    let first = run_with_stats();
+    let second = run_with_stats();
+    assert_eq!(first, second);
+
+    assert!(first.contains("=== 已加载的类 (1) ==="), "{}", first);
+    assert!(first.contains("RefArrayTest"), "{}", first);
+    let histogram = first.split("=== 堆 (3 个存活对象) ===").nth(1).unwrap();
+    let rows: Vec<_> = histogram.lines().skip(2).take(2).map(str::trim).collect();
+    assert_eq!(rows, ["2  [I", "1  [[I"]);
+}
@@ -0,0 +1,60 @@
+//! 测试程序输出重定向：System.out 写到解释器持有的 writer
+
+use rsjvm::interpreter::stdio::SharedBuffer;
+use rsjvm::classloader::ClassLoader;
+use rsjvm::interpreter::Interpreter;
+use rsjvm::{JvmBuilder, Result};
+
+#[test]
+fn test_jvm_captures_program_output() -> Result<()> {
+    let mut jvm = JvmBuilder::new().class_path("examples").capture_output().build();
+    jvm.run_main("HelloPrintln", &["a".to_string(), "b".to_string()])?;
+    assert_eq!(jvm.take_captured_output(), b"Hello, println!\n42\n\nargs=2\n");
+    // 取出后缓冲区清空
+    assert!(jvm.take_captured_output().is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_stdout_can_be_replaced() -> Result<()> {
+    let mut interpreter = Interpreter::with_class_loader(ClassLoader::new(vec!["examples".into()]));
+    let buffer = SharedBuffer::new();
+    interpreter.set_stdout(Box::new(buffer.clone()));
+    interpreter.ensure_class_loaded("HelloPrintln")?;
+    interpreter.run_main("HelloPrintln", &[])?;
+    assert_eq!(buffer.contents(), "Hello, println!\n42\n\nargs=0\n");
+    Ok(())
+}
+
+#[test]
+fn test_captured_concatenation() -> Result<()> {
+    let mut jvm = JvmBuilder::new().class_path("examples").capture_output().build();
+    jvm.run_main("Concat", &[])?;
+    assert_eq!(jvm.take_captured_output(), b"sum=30\n");
+    Ok(())
+}
+
+#[test]
+fn test_print_overloads_follow_descriptor() -> Result<()> {
+    let mut jvm = JvmBuilder::new().class_path("examples").capture_output().build();
+    jvm.run_main("PrintOverloads", &[])?;
+    let output = String::from_utf8(jvm.take_captured_output())?;
+    assert_eq!(
+        output,
+        "true\nA\n1234567890123\n1.0\n0.5\n1.0E-5\ntext\nnull\n\na1bfalse2.5-3\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_system_out_is_a_heap_object() -> Result<()> {
+    let mut jvm = JvmBuilder::new().class_path("examples").capture_output().build();
+    jvm.run_main("PassPrintStream", &[])?;
+    assert_eq!(jvm.take_captured_output(), b"hello local\nhello field\ntrue\n");
+
+    // System.out 只创建一次，GC 时作为静态字段的值被保留
+    let interpreter = jvm.interpreter_mut();
+    interpreter.collect_garbage();
+    assert_eq!(interpreter.heap.find_by_class("java/io/PrintStream").len(), 1);
+    Ok(())
+}
@@ -0,0 +1,86 @@
+//! 测试单步执行与断点
+
+use rsjvm::classfile::ClassFile;
+use rsjvm::interpreter::stepping::{Breakpoint, RunOutcome};
+use rsjvm::interpreter::Interpreter;
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::Result;
+
+fn interpreter() -> Result<Interpreter> {
+    let mut interpreter = Interpreter::new();
+    interpreter.load_class(ClassFile::from_file("examples/CallChain.class")?)?;
+    Ok(interpreter)
+}
+
+fn ints(values: &[JvmValue]) -> Vec<i32> {
+    values
+        .iter()
+        .map(|v| match v {
+            JvmValue::Int(i) => *i,
+            other => panic!("expected int, got {:?}", other),
+        })
+        .collect()
+}
+
+#[test]
+fn test_breakpoint_step_and_resume() -> Result<()> {
+    let mut interpreter = interpreter()?;
+    interpreter.set_breakpoint("CallChain", "bottom:(I)I", 0);
+    interpreter.start_method("CallChain", "top:(I)I", vec![JvmValue::Int(5)])?;
+
+    // top(5) 调用 middle(6)，middle 调用 bottom(6)
+    let hit = match interpreter.resume()? {
+        RunOutcome::Breakpoint(hit) => hit,
+        other => panic!("expected breakpoint, got {:?}", other),
+    };
+    assert_eq!(
+        hit,
+        Breakpoint {
+            class_name: "CallChain".to_string(),
+            method_key: "bottom:(I)I".to_string(),
+            pc: 0
+        }
+    );
+    assert_eq!(ints(interpreter.current_locals()?), [6]);
+    assert!(interpreter.current_stack()?.is_empty());
+
+    // bottom: iload_0; iconst_2; imul; ireturn
+    let step = interpreter.step()?;
+    assert_eq!((step.mnemonic, step.pc, step.next_pc), ("iload_0", 0, Some(1)));
+    let step = interpreter.step()?;
+    assert_eq!((step.mnemonic, step.pc, step.next_pc), ("iconst_2", 1, Some(2)));
+    assert_eq!(ints(interpreter.current_stack()?), [6, 2]);
+
+    // 第二次调用 middle(7) 再次到达断点
+    match interpreter.resume()? {
+        RunOutcome::Breakpoint(hit) => assert_eq!((hit.method_key.as_str(), hit.pc), ("bottom:(I)I", 0)),
+        other => panic!("expected breakpoint, got {:?}", other),
+    }
+    assert_eq!(ints(interpreter.current_locals()?), [7]);
+
+    // a = 6 * 2 + 1, b = 7 * 2 + 1
+    match interpreter.resume()? {
+        RunOutcome::Finished(Some(JvmValue::Int(v))) => assert_eq!(v, 1315),
+        other => panic!("expected return value, got {:?}", other),
+    }
+    assert!(!interpreter.is_paused());
+    assert_eq!(interpreter.thread.stack_depth(), 0);
+    Ok(())
+}
+
+#[test]
+fn test_step_to_completion() -> Result<()> {
+    let mut interpreter = interpreter()?;
+    interpreter.start_method("CallChain", "bottom:(I)I", vec![JvmValue::Int(21)])?;
+    let mnemonics: Vec<_> = std::iter::from_fn(|| {
+        interpreter.is_paused().then(|| interpreter.step().unwrap())
+    })
+    .collect();
+    let names: Vec<_> = mnemonics.iter().map(|s| s.mnemonic).collect();
+    assert_eq!(names, ["iload_0", "iconst_2", "imul", "ireturn"]);
+    let last = mnemonics.last().unwrap();
+    assert!(last.is_finished());
+    assert!(matches!(last.return_value, Some(JvmValue::Int(42))));
+    assert!(interpreter.step().is_err());
+    Ok(())
+}
@@ -0,0 +1,44 @@
+//! 测试内置的 StringBuilder：字符串拼接编译成的 append 链
+
+use rsjvm::{JvmBuilder, Result};
+use std::process::Command;
+
+#[test]
+fn test_concatenation_of_each_append_overload() -> Result<()> {
+    let mut jvm = JvmBuilder::new().class_path("examples").build();
+    let desc = "(IJZCLjava/lang/String;)Ljava/lang/String;";
+    let text: String =
+        jvm.call_static_typed("Concat", "describe", desc, (-7, 1i64 << 40, true, 'é', "duke"))?;
+    assert_eq!(text, "i=-7, l=1099511627776, z=true, c=é, s=duke");
+
+    // null 字符串拼接成 "null"
+    let args = (0, 0i64, false, 'x', rsjvm::interpreter::embed::JArg::Null);
+    let text: String = jvm.call_static_typed("Concat", "describe", desc, args)?;
+    assert_eq!(text, "i=0, l=0, z=false, c=x, s=null");
+
+    let length: i32 = jvm.call_static_typed("Concat", "length", "(Ljava/lang/String;)I", ("ab",))?;
+    assert_eq!(length, 4);
+    Ok(())
+}
+
+#[test]
+fn test_to_string_allocates_a_new_string() -> Result<()> {
+    let mut jvm = JvmBuilder::new().class_path("examples").max_heap_objects(100).build();
+    let same: bool = jvm.call_static_typed("Concat", "sameAsLiteral", "()Z", ())?;
+    assert!(!same);
+    // 驻留的字符串是 GC 根：如果 toString 的结果被驻留，1000 个不同的字符串会超过堆上限
+    let total: i32 = jvm.call_static_typed("Concat", "churn", "(I)I", (1000,))?;
+    assert_eq!(total, 3890);
+    Ok(())
+}
+
+#[test]
+fn test_println_with_concatenation() {
+    let output = Command::new(env!("CARGO_BIN_EXE_rsjvm"))
+        .args(["run", "examples/Concat.class"])
+        .output()
+        .expect("failed to run rsjvm");
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("=== 开始执行 ===\nsum=30\n"), "{}", stdout);
+}
@@ -0,0 +1,138 @@
+//! 测试 String 的内置方法：内容比较、JDK 一致的 hashCode，以及按 UTF-16 编码单元计的
+//! length/charAt/substring
+
+use rsjvm::classfile::ClassFile;
+use rsjvm::interpreter::Interpreter;
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::Result;
+
+/// 一个非 BMP 字符（U+1F600，UTF-16 中是代理对 D83D DE00）夹在两个 ASCII 字符中间
+const EMOJI: &str = "a\u{1F600}b";
+
+fn interpreter() -> Result<Interpreter> {
+    let mut interpreter = Interpreter::new();
+    interpreter.load_class(ClassFile::from_file("examples/StringOps.class")?)?;
+    Ok(interpreter)
+}
+
+fn string(interpreter: &mut Interpreter, text: &str) -> Result<JvmValue> {
+    Ok(JvmValue::Reference(Some(interpreter.heap.allocate_string(text)?)))
+}
+
+fn call_int(
+    interpreter: &mut Interpreter,
+    method: &str,
+    descriptor: &str,
+    args: Vec<JvmValue>,
+) -> Result<i32> {
+    match interpreter.invoke_static("StringOps", method, descriptor, args)? {
+        Some(JvmValue::Int(v)) => Ok(v),
+        other => panic!("{} 期望返回 Int, 实际: {:?}", method, other),
+    }
+}
+
+fn hash(interpreter: &mut Interpreter, text: &str) -> Result<i32> {
+    let s = string(interpreter, text)?;
+    call_int(interpreter, "hash", "(Ljava/lang/String;)I", vec![s])
+}
+
+fn char_at(interpreter: &mut Interpreter, text: &str, index: i32) -> Result<i32> {
+    let s = string(interpreter, text)?;
+    call_int(interpreter, "charAt", "(Ljava/lang/String;I)I", vec![s, JvmValue::Int(index)])
+}
+
+fn substring(interpreter: &mut Interpreter, text: &str, begin: i32, end: i32) -> Result<String> {
+    let s = string(interpreter, text)?;
+    let args = vec![s, JvmValue::Int(begin), JvmValue::Int(end)];
+    let descriptor = "(Ljava/lang/String;II)Ljava/lang/String;";
+    match interpreter.invoke_static("StringOps", "substring", descriptor, args)? {
+        Some(JvmValue::Reference(Some(result))) => Ok(interpreter.heap.get_string(result)?.to_string()),
+        other => panic!("substring 期望返回 String, 实际: {:?}", other),
+    }
+}
+
+#[test]
+fn test_hash_code_matches_jdk() -> Result<()> {
+    let mut interpreter = interpreter()?;
+    assert_eq!(hash(&mut interpreter, "hello")?, 99162322);
+    assert_eq!(hash(&mut interpreter, "")?, 0);
+    // 溢出回绕
+    assert_eq!(hash(&mut interpreter, "hello, world")?, -640608884);
+    // 按 UTF-16 编码单元计算：'a' 之后是两个代理项
+    let expected = (((97 * 31 + 0xD83D) * 31 + 0xDE00) * 31) + 98;
+    assert_eq!(hash(&mut interpreter, EMOJI)?, expected);
+    Ok(())
+}
+
+#[test]
+fn test_length_and_char_at_count_utf16_units() -> Result<()> {
+    let mut interpreter = interpreter()?;
+    let s = string(&mut interpreter, EMOJI)?;
+    assert_eq!(call_int(&mut interpreter, "length", "(Ljava/lang/String;)I", vec![s])?, 4);
+    assert_eq!(EMOJI.len(), 6, "Rust 的字节数不同");
+
+    assert_eq!(char_at(&mut interpreter, EMOJI, 0)?, 'a' as i32);
+    assert_eq!(char_at(&mut interpreter, EMOJI, 1)?, 0xD83D);
+    assert_eq!(char_at(&mut interpreter, EMOJI, 2)?, 0xDE00);
+    assert_eq!(char_at(&mut interpreter, EMOJI, 3)?, 'b' as i32);
+    assert_eq!(char_at(&mut interpreter, "中文", 1)?, '文' as i32);
+    Ok(())
+}
+
+#[test]
+fn test_char_at_out_of_bounds_throws() -> Result<()> {
+    let mut interpreter = interpreter()?;
+    let err = char_at(&mut interpreter, EMOJI, 4).unwrap_err();
+    let message = format!("{:#}", err);
+    assert!(message.contains("StringIndexOutOfBoundsException"), "{}", message);
+    assert!(message.contains("Index 4 out of bounds for length 4"), "{}", message);
+    assert!(char_at(&mut interpreter, "abc", -1).is_err());
+
+    // Java 代码能用 IndexOutOfBounds 的子类捕获
+    let s = string(&mut interpreter, "abc")?;
+    let args = vec![s, JvmValue::Int(3)];
+    assert_eq!(call_int(&mut interpreter, "outOfBounds", "(Ljava/lang/String;I)I", args)?, -1);
+    Ok(())
+}
+
+#[test]
+fn test_substring() -> Result<()> {
+    let mut interpreter = interpreter()?;
+    assert_eq!(substring(&mut interpreter, "hello, world", 7, 12)?, "world");
+    assert_eq!(substring(&mut interpreter, "hello", 2, 2)?, "");
+    assert_eq!(substring(&mut interpreter, EMOJI, 1, 3)?, "\u{1F600}");
+    assert_eq!(substring(&mut interpreter, EMOJI, 3, 4)?, "b");
+
+    for (begin, end) in [(-1, 2), (3, 2), (0, 5)] {
+        let err = substring(&mut interpreter, EMOJI, begin, end).unwrap_err();
+        let message = format!("{:#}", err);
+        assert!(message.contains("StringIndexOutOfBoundsException"), "{}", message);
+        assert!(message.contains(&format!("begin {}, end {}, length 4", begin, end)), "{}", message);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_equals_compares_contents() -> Result<()> {
+    let mut interpreter = interpreter()?;
+    assert_eq!(call_int(&mut interpreter, "equalsBuilt", "()Z", vec![])?, 1);
+
+    let desc = "(Ljava/lang/Object;Ljava/lang/Object;)Z";
+    let (a, b) = (string(&mut interpreter, "same")?, string(&mut interpreter, "same")?);
+    assert_eq!(call_int(&mut interpreter, "equalsViaObject", desc, vec![a, b])?, 1);
+    let (a, b) = (string(&mut interpreter, "same")?, string(&mut interpreter, "diff")?);
+    assert_eq!(call_int(&mut interpreter, "equalsViaObject", desc, vec![a, b])?, 0);
+    // 不是 String 的对象不相等
+    let a = string(&mut interpreter, "same")?;
+    let b = JvmValue::Reference(Some(interpreter.heap.allocate("java/lang/Object")?));
+    assert_eq!(call_int(&mut interpreter, "equalsViaObject", desc, vec![a, b])?, 0);
+    Ok(())
+}
+
+#[test]
+fn test_char_at_loop() -> Result<()> {
+    let mut interpreter = interpreter()?;
+    let s = string(&mut interpreter, "education")?;
+    assert_eq!(call_int(&mut interpreter, "vowels", "(Ljava/lang/String;)I", vec![s])?, 5);
+    Ok(())
+}
@@ -0,0 +1,40 @@
+//! 测试符号：按文本比较和查找、可读的 Display，以及栈帧和方法区共享同一份类名
+
+use rsjvm::runtime::Symbol;
+use rsjvm::{JvmBuilder, Result};
+use std::collections::HashMap;
+
+#[test]
+fn test_symbol_compares_and_displays_as_text() {
+    let a = Symbol::new("java/lang/String");
+    let b: Symbol = String::from("java/lang/String").into();
+    assert_eq!(a, b);
+    assert_eq!(a, "java/lang/String");
+    assert_eq!("java/lang/String", a);
+    assert_ne!(a, Symbol::new("java/lang/Object"));
+    assert_eq!(format!("{}", a), "java/lang/String");
+    assert_eq!(format!("{:?}", a), "\"java/lang/String\"");
+
+    // 以符号为键的表可以直接用 &str 查找
+    let mut table = HashMap::new();
+    table.insert(Symbol::new("add:(II)I"), 1);
+    assert_eq!(table.get("add:(II)I"), Some(&1));
+    assert_eq!(Symbol::default(), "");
+}
+
+#[test]
+fn test_method_table_keys_and_names_are_symbols() -> Result<()> {
+    let mut jvm = JvmBuilder::new().class_path("examples").build();
+    let total: i32 = jvm.call_static_typed("AllocLoop", "run", "(I)I", (10,))?;
+    assert_eq!(total, 90);
+
+    let class = jvm.interpreter().metaspace.get_class("AllocLoop")?;
+    assert_eq!(class.name, "AllocLoop");
+    let method = &class.methods["add:(I)V"];
+    assert_eq!((method.name.as_str(), method.descriptor.as_str()), ("add", "(I)V"));
+
+    let resolved = class.runtime_pool.resolved_methods.values();
+    assert!(resolved.clone().any(|m| m.method_key == "twice:(I)I" && m.class_name == "AllocLoop"));
+    assert!(resolved.clone().any(|m| m.method_key == "add:(I)V"));
+    Ok(())
+}
@@ -0,0 +1,84 @@
+//! 测试基本类型数组：newarray、xaload/xastore、arraylength
+
+use rsjvm::classfile::ClassFile;
+use rsjvm::interpreter::Interpreter;
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::runtime::heap::ArrayType;
+use rsjvm::runtime::Heap;
+use rsjvm::runtime::metaspace::method_key;
+use rsjvm::Result;
+
+/// 加载 ArrayTest 并执行一个无参静态方法
+fn run_static(method_name: &str, descriptor: &str) -> Result<Option<JvmValue>> {
+    let mut interpreter = Interpreter::new();
+    let class_file = ClassFile::from_file("examples/ArrayTest.class")?;
+    let class_name = interpreter.load_class(class_file)?;
+
+    let (code, max_locals, max_stack) = {
+        let class_meta = interpreter.metaspace.get_class(&class_name)?;
+        let method = class_meta.find_method(method_name, descriptor)?;
+        (method.code.clone(), method.max_locals, method.max_stack)
+    };
+
+    interpreter.execute_method_with_class(
+        &class_name,
+        &method_key(method_name, descriptor),
+        &code,
+        max_locals,
+        max_stack,
+    )
+}
+
+#[test]
+fn test_fill_int_array_and_sum() -> Result<()> {
+    match run_static("sumIntArray", "()I")? {
+        Some(JvmValue::Int(100)) => (),
+        other => panic!("expected 100, got {:?}", other),
+    }
+    Ok(())
+}
+
+#[test]
+fn test_narrow_element_types() -> Result<()> {
+    match run_static("byteArray", "()I")? {
+        Some(JvmValue::Int(44)) => (),
+        other => panic!("expected 44, got {:?}", other),
+    }
+    match run_static("charAndShort", "()I")? {
+        Some(JvmValue::Int(1131)) => (),
+        other => panic!("expected 1131, got {:?}", other),
+    }
+    match run_static("longArray", "()J")? {
+        Some(JvmValue::Long(5_000_000_000)) => (),
+        other => panic!("expected 5000000000, got {:?}", other),
+    }
+    Ok(())
+}
+
+#[test]
+fn test_store_truncates_to_element_type() -> Result<()> {
+    let mut heap = Heap::new();
+    let bytes = heap.allocate_array(ArrayType::Byte, 1)?;
+    heap.array_set(bytes, 0, JvmValue::Int(200))?;
+    assert!(matches!(heap.array_get(bytes, 0)?, JvmValue::Int(-56)));
+
+    let chars = heap.allocate_array(ArrayType::Char, 1)?;
+    heap.array_set(chars, 0, JvmValue::Int(-1))?;
+    assert!(matches!(heap.array_get(chars, 0)?, JvmValue::Int(0xFFFF)));
+
+    assert_eq!(heap.array_length(chars)?, 1);
+    Ok(())
+}
+
+#[test]
+fn test_array_index_out_of_bounds() {
+    let err = run_static("outOfBounds", "()I").unwrap_err().to_string();
+    assert!(err.contains("ArrayIndexOutOfBoundsException"), "{}", err);
+    assert!(err.contains("Index 5 out of bounds for length 5"), "{}", err);
+}
+
+#[test]
+fn test_negative_array_size() {
+    let err = run_static("negativeSize", "()I").unwrap_err().to_string();
+    assert!(err.contains("NegativeArraySizeException"), "{}", err);
+}
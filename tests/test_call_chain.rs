@@ -0,0 +1,50 @@
+//! 测试每个栈帧自己的 pc：多层调用返回后调用者从正确位置继续
+
+use rsjvm::classfile::ClassFile;
+use rsjvm::interpreter::Interpreter;
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::Result;
+
+fn call_int(interpreter: &mut Interpreter, method: &str, args: Vec<JvmValue>) -> Result<i32> {
+    let descriptor = if args.is_empty() { "()I" } else { "(I)I" };
+    match interpreter.invoke_static("CallChain", method, descriptor, args)? {
+        Some(JvmValue::Int(v)) => Ok(v),
+        other => panic!("CallChain.{} 期望返回 Int, 实际: {:?}", method, other),
+    }
+}
+
+fn load_call_chain() -> Result<Interpreter> {
+    let mut interpreter = Interpreter::new();
+    interpreter.load_class(ClassFile::from_file("examples/CallChain.class")?)?;
+    Ok(interpreter)
+}
+
+#[test]
+fn test_three_deep_static_chain() -> Result<()> {
+    let mut interpreter = load_call_chain()?;
+    // middle(2) = 5, middle(3) = 7
+    assert_eq!(call_int(&mut interpreter, "top", vec![JvmValue::Int(1)])?, 507);
+    assert_eq!(interpreter.thread.stack_depth(), 0);
+    assert_eq!(interpreter.thread.frames().len(), 0);
+    Ok(())
+}
+
+#[test]
+fn test_three_deep_virtual_chain() -> Result<()> {
+    let mut interpreter = load_call_chain()?;
+    assert_eq!(call_int(&mut interpreter, "instanceChain", vec![])?, 31);
+    assert_eq!(interpreter.thread.stack_depth(), 0);
+    Ok(())
+}
+
+#[test]
+fn test_exception_unwinds_to_caller_pc() -> Result<()> {
+    let mut interpreter = load_call_chain()?;
+    assert_eq!(call_int(&mut interpreter, "catchFromDepth", vec![])?, 42);
+    assert_eq!(interpreter.thread.stack_depth(), 0);
+
+    // 展开后没有残留的 pc 状态，后续调用正常
+    assert_eq!(call_int(&mut interpreter, "top", vec![JvmValue::Int(2)])?, 709);
+    assert_eq!(interpreter.thread.stack_depth(), 0);
+    Ok(())
+}
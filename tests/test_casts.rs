@@ -0,0 +1,85 @@
+//! 测试 checkcast / instanceof：类层次、接口、数组协变和 null
+
+use rsjvm::classfile::ClassFile;
+use rsjvm::interpreter::Interpreter;
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::runtime::metaspace::method_key;
+use rsjvm::Result;
+
+const CLASSES: [&str; 8] = [
+    "Electric", "Vehicle", "Car", "SportsCar", "Bike", "Tesla", "ModelS", "CastTest",
+];
+
+fn load() -> Result<Interpreter> {
+    let mut interpreter = Interpreter::new();
+    for class in CLASSES {
+        interpreter.load_class(ClassFile::from_file(format!("examples/{}.class", class))?)?;
+    }
+    Ok(interpreter)
+}
+
+/// 执行 CastTest 的一个 ()I 静态方法
+fn run_static_int(method_name: &str) -> Result<i32> {
+    let mut interpreter = load()?;
+    let (code, max_locals, max_stack) = {
+        let class_meta = interpreter.metaspace.get_class("CastTest")?;
+        let method = class_meta.find_method(method_name, "()I")?;
+        (method.code.clone(), method.max_locals, method.max_stack)
+    };
+    match interpreter.execute_method_with_class(
+        "CastTest",
+        &method_key(method_name, "()I"),
+        &code,
+        max_locals,
+        max_stack,
+    )? {
+        Some(JvmValue::Int(v)) => Ok(v),
+        other => panic!("expected int, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_instanceof() -> Result<()> {
+    // 每一位对应 CastTest 中的一次 instanceof
+    assert_eq!(run_static_int("instanceofPositive")?, 0b111111);
+    assert_eq!(run_static_int("instanceofNegative")?, 0);
+    assert_eq!(run_static_int("instanceofNull")?, 0);
+    // SportsCar[] 是 Vehicle[] 和 Object[]，不是 Bike[]；int[] 不是 Object[]
+    assert_eq!(run_static_int("instanceofArrays")?, 0b01011);
+    Ok(())
+}
+
+#[test]
+fn test_checkcast() -> Result<()> {
+    assert_eq!(run_static_int("castUp")?, 1);
+    assert_eq!(run_static_int("castNull")?, 1);
+    assert_eq!(run_static_int("castFailsCaught")?, -1);
+
+    let err = run_static_int("castFails").unwrap_err().to_string();
+    assert!(
+        err.contains("java/lang/ClassCastException: class Bike cannot be cast to class Car"),
+        "{}",
+        err
+    );
+    Ok(())
+}
+
+#[test]
+fn test_is_assignable_from() -> Result<()> {
+    let interpreter = load()?;
+    let metaspace = &interpreter.metaspace;
+    assert!(metaspace.is_assignable_from("SportsCar", "Vehicle"));
+    assert!(metaspace.is_assignable_from("ModelS", "Electric"));
+    assert!(!metaspace.is_assignable_from("Vehicle", "Car"));
+    assert!(!metaspace.is_assignable_from("Bike", "Electric"));
+    assert!(metaspace.is_assignable_from("[[LSportsCar;", "[[LVehicle;"));
+    assert!(metaspace.is_assignable_from("[[I", "[Ljava/lang/Object;"));
+    assert!(metaspace.is_assignable_from("[I", "java/lang/Cloneable"));
+    assert!(!metaspace.is_assignable_from("[I", "[J"));
+    // 未加载的 JDK 异常类使用内置继承关系
+    assert!(metaspace.is_assignable_from(
+        "java/lang/ArrayIndexOutOfBoundsException",
+        "java/lang/RuntimeException"
+    ));
+    Ok(())
+}
@@ -0,0 +1,72 @@
+//! 测试解释器通过 ClassLoader 按需加载类
+
+use rsjvm::classfile::ClassFile;
+use rsjvm::classloader::ClassLoader;
+use rsjvm::interpreter::Interpreter;
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::Result;
+use std::path::PathBuf;
+use std::process::Command;
+
+fn run(interpreter: &mut Interpreter) -> Result<Option<JvmValue>> {
+    interpreter.load_class(ClassFile::from_file("examples/ChainA.class")?)?;
+    interpreter.invoke_static("ChainA", "run", "()I", vec![])
+}
+
+#[test]
+fn test_classes_loaded_on_demand() -> Result<()> {
+    let mut interpreter = Interpreter::with_class_loader(ClassLoader::new(vec!["examples".into()]));
+    match run(&mut interpreter)? {
+        Some(JvmValue::Int(26)) => {}
+        other => panic!("期望返回 Int(26), 实际: {:?}", other),
+    }
+
+    // ChainA → ChainB → ChainC → 父类 ChainBase，按使用顺序加载
+    assert_eq!(
+        interpreter.metaspace.loaded_classes(),
+        ["ChainA", "ChainB", "ChainC", "ChainBase"]
+    );
+    let calls = interpreter.metaspace.get_class("ChainB")?.static_fields.get("calls").cloned();
+    assert!(matches!(calls, Some(JvmValue::Int(1))), "{:?}", calls);
+    Ok(())
+}
+
+#[test]
+fn test_without_class_loader_requires_preloading() -> Result<()> {
+    let mut interpreter = Interpreter::new();
+    let err = format!("{:#}", run(&mut interpreter).unwrap_err());
+    assert!(err.contains("Class ChainB not loaded"), "{}", err);
+    Ok(())
+}
+
+#[test]
+fn test_missing_class_mentions_class_path() -> Result<()> {
+    // 类路径中只有 ChainA 和 ChainB，ChainC 找不到
+    let dir = std::env::temp_dir().join(format!("rsjvm-missing-class-{}", std::process::id()));
+    std::fs::create_dir_all(&dir)?;
+    for name in ["ChainA", "ChainB"] {
+        std::fs::copy(
+            PathBuf::from(format!("examples/{}.class", name)),
+            dir.join(format!("{}.class", name)),
+        )?;
+    }
+
+    let mut interpreter = Interpreter::with_class_loader(ClassLoader::new(vec![dir.clone()]));
+    let err = run(&mut interpreter).unwrap_err().to_string();
+    std::fs::remove_dir_all(&dir)?;
+
+    assert!(err.contains("java/lang/ClassNotFoundException: ChainC"), "{}", err);
+    assert!(err.contains(&format!("{:?}", dir)), "{}", err);
+    Ok(())
+}
+
+#[test]
+fn test_cli_loads_classes_from_class_file_directory() {
+    let output = Command::new(env!("CARGO_BIN_EXE_rsjvm"))
+        .args(["run", "examples/ChainA.class"])
+        .output()
+        .expect("failed to run rsjvm");
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("=== 开始执行 ===\n26\n"), "{}", stdout);
+}
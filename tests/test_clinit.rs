@@ -0,0 +1,82 @@
+//! 测试 <clinit> 静态初始化器的执行时机和顺序
+
+use rsjvm::classfile::ClassFile;
+use rsjvm::classloader::ClassLoader;
+use rsjvm::interpreter::Interpreter;
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::runtime::metaspace::ClassState;
+use rsjvm::Result;
+use std::process::Command;
+
+fn load() -> Result<Interpreter> {
+    let mut interpreter = Interpreter::with_class_loader(ClassLoader::new(vec!["examples".into()]));
+    interpreter.load_class(ClassFile::from_file("examples/StaticInit.class")?)?;
+    Ok(interpreter)
+}
+
+fn call_int(interpreter: &mut Interpreter, method: &str) -> Result<i32> {
+    match interpreter.invoke_static("StaticInit", method, "()I", vec![])? {
+        Some(JvmValue::Int(v)) => Ok(v),
+        other => panic!("StaticInit.{} 期望返回 Int, 实际: {:?}", method, other),
+    }
+}
+
+#[test]
+fn test_static_initializer_runs_before_first_use() -> Result<()> {
+    let mut interpreter = load()?;
+    assert_eq!(
+        interpreter.metaspace.get_class("StaticInit")?.state,
+        ClassState::Loaded
+    );
+
+    assert_eq!(call_int(&mut interpreter, "getX")?, 42);
+    assert_eq!(call_int(&mut interpreter, "tableValue")?, 43);
+    assert_eq!(
+        interpreter.metaspace.get_class("StaticInit")?.state,
+        ClassState::Initialized
+    );
+    Ok(())
+}
+
+#[test]
+fn test_superclass_initialized_first() -> Result<()> {
+    let mut interpreter = load()?;
+    assert_eq!(call_int(&mut interpreter, "initOrder")?, 21);
+    Ok(())
+}
+
+#[test]
+fn test_static_initializer_runs_once() -> Result<()> {
+    let mut interpreter = load()?;
+    assert_eq!(call_int(&mut interpreter, "newTriggersInit")?, 1);
+    assert_eq!(call_int(&mut interpreter, "newTriggersInit")?, 1);
+    Ok(())
+}
+
+#[test]
+fn test_reentrant_initialization_terminates() -> Result<()> {
+    let mut interpreter = load()?;
+    // helper() 在 b 赋值前读到默认值 0
+    assert_eq!(call_int(&mut interpreter, "selfReference")?, 1);
+    Ok(())
+}
+
+#[test]
+fn test_reset_reruns_static_initializer() -> Result<()> {
+    let mut interpreter = load()?;
+    assert_eq!(call_int(&mut interpreter, "newTriggersInit")?, 1);
+    interpreter.reset_run_state();
+    assert_eq!(call_int(&mut interpreter, "newTriggersInit")?, 1);
+    Ok(())
+}
+
+#[test]
+fn test_cli_main_sees_initialized_static() {
+    let output = Command::new(env!("CARGO_BIN_EXE_rsjvm"))
+        .args(["run", "examples/StaticInit.class"])
+        .output()
+        .expect("failed to run rsjvm");
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("=== 开始执行 ===\n42\n"), "{}", stdout);
+}
@@ -0,0 +1,65 @@
+//! 测试 athrow 和异常表分派：本方法捕获、跨栈帧捕获、finally（catch_type = 0）和未捕获异常
+
+use rsjvm::classfile::ClassFile;
+use rsjvm::interpreter::Interpreter;
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::runtime::metaspace::method_key;
+use rsjvm::Result;
+
+/// 加载 ExceptionTest 并执行一个 ()I 静态方法
+fn run_static_int(method_name: &str) -> Result<i32> {
+    let mut interpreter = Interpreter::new();
+    interpreter.load_class(ClassFile::from_file("examples/MyException.class")?)?;
+    let class_name = interpreter.load_class(ClassFile::from_file("examples/ExceptionTest.class")?)?;
+
+    let (code, max_locals, max_stack) = {
+        let class_meta = interpreter.metaspace.get_class(&class_name)?;
+        let method = class_meta.find_method(method_name, "()I")?;
+        (method.code.clone(), method.max_locals, method.max_stack)
+    };
+
+    let result = interpreter.execute_method_with_class(
+        &class_name,
+        &method_key(method_name, "()I"),
+        &code,
+        max_locals,
+        max_stack,
+    );
+    // 无论是否捕获，结束后线程栈都应为空
+    assert_eq!(interpreter.thread.stack_depth(), 0);
+    match result? {
+        Some(JvmValue::Int(v)) => Ok(v),
+        other => panic!("expected int, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_caught_in_same_method() -> Result<()> {
+    assert_eq!(run_static_int("caught")?, 1);
+    // catch (RuntimeException) 捕获子类 MyException
+    assert_eq!(run_static_int("caughtBySuperclass")?, 2);
+    Ok(())
+}
+
+#[test]
+fn test_caught_in_caller() -> Result<()> {
+    // thrower() 的栈帧被弹出，局部变量 before 保持抛出前的值
+    assert_eq!(run_static_int("caughtInCaller")?, 8);
+    // 跳过不匹配的 catch (ArithmeticException)
+    assert_eq!(run_static_int("matchingHandler")?, 4);
+    Ok(())
+}
+
+#[test]
+fn test_finally_handler() -> Result<()> {
+    // finally（catch_type = 0）先执行并重新抛出，外层 catch 再捕获
+    assert_eq!(run_static_int("finallyThenCatch")?, 111);
+    assert_eq!(run_static_int("finallyWithoutException")?, 12);
+    Ok(())
+}
+
+#[test]
+fn test_uncaught_exception() {
+    let err = run_static_int("uncaught").unwrap_err().to_string();
+    assert!(err.contains("Uncaught exception: MyException"), "{}", err);
+}
@@ -0,0 +1,92 @@
+//! 测试 NEW 的类加载和 InstantiationError（接口、抽象类、数组类）
+
+use rsjvm::classfile::constant_pool::ConstantPoolEntry;
+use rsjvm::classfile::{access_flags, ClassFile};
+use rsjvm::classloader::ClassLoader;
+use rsjvm::interpreter::Interpreter;
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::Result;
+
+/// 加载 InstantiationTest，Widget 的访问标志加上 `extra_flags` 后再加载
+fn load_with_widget_flags(extra_flags: u16) -> Result<Interpreter> {
+    let mut interpreter = Interpreter::new();
+    interpreter.load_class(ClassFile::from_file("examples/InstantiationTest.class")?)?;
+    let mut widget = ClassFile::from_file("examples/Widget.class")?;
+    widget.access_flags |= extra_flags;
+    interpreter.load_class(widget)?;
+    Ok(interpreter)
+}
+
+fn make(interpreter: &mut Interpreter) -> Result<Option<JvmValue>> {
+    interpreter.invoke_static("InstantiationTest", "make", "()Ljava/lang/Object;", vec![])
+}
+
+#[test]
+fn test_new_interface_is_instantiation_error() -> Result<()> {
+    let mut interpreter =
+        load_with_widget_flags(access_flags::ACC_INTERFACE | access_flags::ACC_ABSTRACT)?;
+    let err = make(&mut interpreter).unwrap_err().to_string();
+    assert!(
+        err.contains("java/lang/InstantiationError: Widget is an interface"),
+        "{}",
+        err
+    );
+    Ok(())
+}
+
+#[test]
+fn test_new_abstract_class_is_instantiation_error() -> Result<()> {
+    let mut interpreter = load_with_widget_flags(access_flags::ACC_ABSTRACT)?;
+    let err = make(&mut interpreter).unwrap_err().to_string();
+    assert!(
+        err.contains("java/lang/InstantiationError: Widget is an abstract class"),
+        "{}",
+        err
+    );
+    Ok(())
+}
+
+#[test]
+fn test_new_array_class_is_instantiation_error() -> Result<()> {
+    // 把常量池里的类名 Widget 改成数组描述符，new 的目标就成了数组类
+    let mut class_file = ClassFile::from_file("examples/InstantiationTest.class")?;
+    for entry in class_file.constant_pool.entries.iter_mut().flatten() {
+        if let ConstantPoolEntry::Utf8(s) = entry {
+            if s == "Widget" {
+                *s = "[LWidget;".to_string();
+            }
+        }
+    }
+    let mut interpreter = Interpreter::new();
+    interpreter.load_class(class_file)?;
+
+    let err = make(&mut interpreter).unwrap_err().to_string();
+    assert!(
+        err.contains("java/lang/InstantiationError: [LWidget; is an array class"),
+        "{}",
+        err
+    );
+    Ok(())
+}
+
+#[test]
+fn test_new_concrete_class_loads_it_on_demand() -> Result<()> {
+    // 只显式加载入口类，Widget 由 NEW 通过类加载器加载
+    let mut interpreter = Interpreter::with_class_loader(ClassLoader::new(vec!["examples".into()]));
+    interpreter.load_class(ClassFile::from_file("examples/InstantiationTest.class")?)?;
+    assert!(!interpreter.metaspace.is_class_loaded("Widget"));
+
+    match make(&mut interpreter)? {
+        Some(JvmValue::Reference(Some(ptr))) => {
+            assert_eq!(interpreter.heap.get(ptr)?.class_name, "Widget")
+        }
+        other => panic!("期望返回对象引用, 实际: {:?}", other),
+    }
+    assert!(interpreter.metaspace.is_class_loaded("Widget"));
+
+    match interpreter.invoke_static("InstantiationTest", "makeAndUse", "()I", vec![])? {
+        Some(JvmValue::Int(7)) => {}
+        other => panic!("期望返回 Int(7), 实际: {:?}", other),
+    }
+    Ok(())
+}
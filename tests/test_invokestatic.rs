@@ -1,109 +1,102 @@
 //! 测试 invokestatic 指令
 
+use rsjvm::classfile::access_flags::{ACC_PUBLIC, ACC_STATIC};
+use rsjvm::classfile::builder::ClassFileBuilder;
 use rsjvm::classfile::ClassFile;
-use rsjvm::interpreter::Interpreter;
 use rsjvm::runtime::frame::JvmValue;
-use rsjvm::Result;
+use rsjvm::{JvmBuilder, Result};
+
+/// 与 examples/TestInvokeStatic.java 等价的类，直接在代码里构造：
+///
+/// ```java
+/// public static void main(String[] args) { int a = sum_a_and_b(199, 299); }
+/// public static int sum_a_and_b(int a, int b) { return a + b; }
+/// ```
+fn test_invoke_static_class() -> Result<ClassFile> {
+    let bytes = ClassFileBuilder::new("TestInvokeStatic")
+        .method(ACC_PUBLIC | ACC_STATIC, "main", "([Ljava/lang/String;)V", 2, 2, |code| {
+            code.iconst(199)
+                .iconst(299)
+                .invokestatic("TestInvokeStatic", "sum_a_and_b", "(II)I")
+                .istore(1)
+                .vreturn();
+        })
+        .method(ACC_PUBLIC | ACC_STATIC, "sum_a_and_b", "(II)I", 2, 2, |code| {
+            code.iload(0).iload(1).iadd().ireturn();
+        })
+        .to_bytes()?;
+    ClassFile::from_bytes(&bytes)
+}
 
 #[test]
 fn test_invokestatic_simple() -> Result<()> {
-    // 1. 创建解释器
-    let mut interpreter = Interpreter::new();
-
-    // 2. 加载 TestInvokeStatic 类
-    let class_file = ClassFile::from_file("examples/TestInvokeStatic.class")?;
-    let class_name = interpreter.load_class(class_file)?;
-
-    // 3. 获取 main 方法（克隆数据以避免借用冲突）
-    let (code, max_locals, max_stack) = {
-        let class_meta = interpreter.metaspace.get_class(&class_name)?;
-        let main_method = class_meta.find_method("main", "([Ljava/lang/String;)V")?;
-        (main_method.code.clone(), main_method.max_locals, main_method.max_stack)
-    };
-
-    // 4. 执行 main 方法（会调用 sum_a_and_b）
-    let result = interpreter.execute_method_with_class(
-        &class_name,
-        &code,
-        max_locals,
-        max_stack,
+    // TestInvokeStatic 由 ClassFileBuilder 构造，不依赖 .class 文件
+    let mut jvm = JvmBuilder::new().build();
+    jvm.load_class(test_invoke_static_class()?)?;
+
+    // main 方法会调用 sum_a_and_b；main 是 void，应该没有返回值
+    let result = jvm.call_static(
+        "TestInvokeStatic",
+        "main",
+        "([Ljava/lang/String;)V",
+        &[JvmValue::Reference(None)],
     )?;
-
-    // main 方法是 void，应该没有返回值
     assert!(result.is_none());
-
     Ok(())
 }
 
 #[test]
 fn test_invokestatic_with_return_value() -> Result<()> {
-    // 1. 创建解释器
-    let mut interpreter = Interpreter::new();
-
-    // 2. 加载类
-    let class_file = ClassFile::from_file("examples/TestInvokeStatic.class")?;
-    let class_name = interpreter.load_class(class_file)?;
-
-    // 3. 获取方法信息（克隆以避免借用冲突）
-    let (code, max_locals, max_stack) = {
-        let class_meta = interpreter.metaspace.get_class(&class_name)?;
-        let method = class_meta.find_method("sum_a_and_b", "(II)I")?;
-        (method.code.clone(), method.max_locals, method.max_stack)
-    };
-
-    // 4. 创建栈帧并设置参数
-    let mut frame = rsjvm::runtime::Frame::new(max_locals, max_stack);
-    frame.set_local(0, JvmValue::Int(10))?;
-    frame.set_local(1, JvmValue::Int(20))?;
-
-    // 5. 执行方法
-    let result = interpreter.execute_method_in_frame(&code, &mut frame, &class_name)?;
-
-    // 6. 验证结果
-    assert!(result.is_some());
-    if let Some(JvmValue::Int(val)) = result {
-        assert_eq!(val, 30);
-    } else {
-        panic!("Expected Int return value");
-    }
-
+    let mut jvm = JvmBuilder::new().class_path("examples").build();
+    let args = [JvmValue::Int(10), JvmValue::Int(20)];
+    let result = jvm.call_static("TestInvokeStatic", "sum_a_and_b", "(II)I", &args)?;
+    assert!(matches!(result, Some(JvmValue::Int(30))), "{:?}", result);
     Ok(())
 }
 
 #[test]
 fn test_invokestatic_multiple_calls() -> Result<()> {
     // 测试多次调用同一个方法
-    let mut interpreter = Interpreter::new();
-
-    let class_file = ClassFile::from_file("examples/TestInvokeStatic.class")?;
-    let class_name = interpreter.load_class(class_file)?;
-
-    // 获取方法信息
-    let (code, max_locals, max_stack) = {
-        let class_meta = interpreter.metaspace.get_class(&class_name)?;
-        let method = class_meta.find_method("sum_a_and_b", "(II)I")?;
-        (method.code.clone(), method.max_locals, method.max_stack)
+    let mut jvm = JvmBuilder::new().class_path("examples").build();
+    let mut sum = |a, b| {
+        jvm.call_static("TestInvokeStatic", "sum_a_and_b", "(II)I", &[JvmValue::Int(a), JvmValue::Int(b)])
     };
+    assert!(matches!(sum(1, 2)?, Some(JvmValue::Int(3))));
+    assert!(matches!(sum(100, 200)?, Some(JvmValue::Int(300))));
+    Ok(())
+}
 
-    // 第一次调用
-    let mut frame1 = rsjvm::runtime::Frame::new(max_locals, max_stack);
-    frame1.set_local(0, JvmValue::Int(1))?;
-    frame1.set_local(1, JvmValue::Int(2))?;
-    let result1 = interpreter.execute_method_in_frame(&code, &mut frame1, &class_name)?;
-
-    // 第二次调用
-    let mut frame2 = rsjvm::runtime::Frame::new(max_locals, max_stack);
-    frame2.set_local(0, JvmValue::Int(100))?;
-    frame2.set_local(1, JvmValue::Int(200))?;
-    let result2 = interpreter.execute_method_in_frame(&code, &mut frame2, &class_name)?;
-
-    // 验证结果
-    if let (Some(JvmValue::Int(v1)), Some(JvmValue::Int(v2))) = (result1, result2) {
-        assert_eq!(v1, 3);
-        assert_eq!(v2, 300);
-    } else {
-        panic!("Expected Int return values");
-    }
+#[test]
+fn test_load_class_file_adds_its_directory() -> Result<()> {
+    let mut jvm = JvmBuilder::new().build();
+    assert_eq!(jvm.load_class_file("examples/TestInvokeStatic.class")?, "TestInvokeStatic");
+    jvm.run_main("TestInvokeStatic", &[])?;
+    // 同目录的其他类按需加载
+    let result = jvm.call_static("ReturnOne", "returnOne", "()I", &[])?;
+    assert!(matches!(result, Some(JvmValue::Int(1))), "{:?}", result);
+    Ok(())
+}
 
+#[test]
+fn test_invokestatic_typed() -> Result<()> {
+    let mut jvm = JvmBuilder::new().class_path("examples").build();
+    let r: i32 = jvm.call_static_typed("TestInvokeStatic", "sum_a_and_b", "(II)I", (10, 20))?;
+    assert_eq!(r, 30);
+    let r: i32 = jvm.call_static_typed("TestInvokeStatic", "sum_a_and_b", "(II)I", [-1, 1])?;
+    assert_eq!(r, 0);
+
+    let err = jvm
+        .call_static_typed::<i32>("TestInvokeStatic", "sum_a_and_b", "(II)I", (1, 2, 3))
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "TestInvokeStatic.sum_a_and_b: descriptor expects (II)I but 3 args supplied"
+    );
+
+    // 返回值类型不匹配
+    let err = jvm
+        .call_static_typed::<bool>("TestInvokeStatic", "sum_a_and_b", "(II)I", (1, 2))
+        .unwrap_err();
+    assert_eq!(err.to_string(), "return value Int(3) cannot be converted to bool");
     Ok(())
 }
@@ -0,0 +1,60 @@
+//! 测试 invokevirtual 动态分派：按对象运行时类型沿 super_class 查找方法
+
+use rsjvm::classfile::ClassFile;
+use rsjvm::interpreter::Interpreter;
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::runtime::metaspace::method_key;
+use rsjvm::Result;
+
+/// 加载 VirtualDispatch 及其用到的类，执行一个 ()I 静态方法
+fn run_static_int(method_name: &str) -> Result<i32> {
+    let mut interpreter = Interpreter::new();
+    for class in ["Shape", "Square", "BigSquare", "VirtualDispatch"] {
+        interpreter.load_class(ClassFile::from_file(format!("examples/{}.class", class))?)?;
+    }
+
+    let (code, max_locals, max_stack) = {
+        let class_meta = interpreter.metaspace.get_class("VirtualDispatch")?;
+        let method = class_meta.find_method(method_name, "()I")?;
+        (method.code.clone(), method.max_locals, method.max_stack)
+    };
+
+    match interpreter.execute_method_with_class(
+        "VirtualDispatch",
+        &method_key(method_name, "()I"),
+        &code,
+        max_locals,
+        max_stack,
+    )? {
+        Some(JvmValue::Int(v)) => Ok(v),
+        other => panic!("expected int, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_override_through_base_type() -> Result<()> {
+    // 变量类型是 Shape，实际执行 Square.area
+    assert_eq!(run_static_int("squareArea")?, 25);
+    assert_eq!(run_static_int("baseArea")?, 0);
+    Ok(())
+}
+
+#[test]
+fn test_inherited_override() -> Result<()> {
+    // BigSquare 没有 area()，向上找到 Square.area
+    assert_eq!(run_static_int("inheritedArea")?, 400);
+    Ok(())
+}
+
+#[test]
+fn test_dispatch_from_superclass_method() -> Result<()> {
+    assert_eq!(run_static_int("selfCall")?, 18);
+    Ok(())
+}
+
+#[test]
+fn test_null_receiver() {
+    let err = run_static_int("callOnNull").unwrap_err().to_string();
+    assert!(err.contains("java/lang/NullPointerException"), "{}", err);
+    assert!(err.contains("Shape.area"), "{}", err);
+}
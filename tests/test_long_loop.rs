@@ -0,0 +1,66 @@
+//! 测试 long/float/double 循环：lcmp/dcmpg/fcmpl + ifxx 分支、两槽位局部变量
+
+use rsjvm::classfile::ClassFile;
+use rsjvm::interpreter::Interpreter;
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::runtime::metaspace::method_key;
+use rsjvm::Result;
+
+/// 加载 LongLoop 并执行一个无参静态方法，返回其返回值
+fn run_static(method_name: &str, descriptor: &str) -> Result<Option<JvmValue>> {
+    let mut interpreter = Interpreter::new();
+    let class_file = ClassFile::from_file("examples/LongLoop.class")?;
+    let class_name = interpreter.load_class(class_file)?;
+
+    let (code, max_locals, max_stack) = {
+        let class_meta = interpreter.metaspace.get_class(&class_name)?;
+        let method = class_meta.find_method(method_name, descriptor)?;
+        (method.code.clone(), method.max_locals, method.max_stack)
+    };
+
+    interpreter.execute_method_with_class(
+        &class_name,
+        &method_key(method_name, descriptor),
+        &code,
+        max_locals,
+        max_stack,
+    )
+}
+
+#[test]
+fn test_long_loop_exceeds_i32() -> Result<()> {
+    // sum(3000000000 .. 3000001000) = 1000 * 3000000000 + 999 * 1000 / 2，超出 i32 范围
+    match run_static("sumLongs", "()J")? {
+        Some(JvmValue::Long(v)) => assert_eq!(v, 3_000_000_499_500),
+        other => panic!("expected long, got {:?}", other),
+    }
+    Ok(())
+}
+
+#[test]
+fn test_long_argument_occupies_two_slots() -> Result<()> {
+    // countDown(long n) 中 n 占 local[0..2]，计数器从 local[2] 开始
+    match run_static("countDownFromBig", "()J")? {
+        Some(JvmValue::Long(v)) => assert_eq!(v, 1667),
+        other => panic!("expected long, got {:?}", other),
+    }
+    Ok(())
+}
+
+#[test]
+fn test_double_loop() -> Result<()> {
+    match run_static("sumDoubles", "()D")? {
+        Some(JvmValue::Double(d)) => assert!((d - 9950.0).abs() < 1e-9, "got {}", d),
+        other => panic!("expected double, got {:?}", other),
+    }
+    Ok(())
+}
+
+#[test]
+fn test_float_loop() -> Result<()> {
+    match run_static("sumFloats", "()F")? {
+        Some(JvmValue::Float(f)) => assert!((f - 1.0).abs() < 1e-5, "got {}", f),
+        other => panic!("expected float, got {:?}", other),
+    }
+    Ok(())
+}
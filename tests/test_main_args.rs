@@ -0,0 +1,100 @@
+//! 测试 main 方法的 String[] 参数和 execute_method_with_args 的参数布局
+
+use rsjvm::classfile::ClassFile;
+use rsjvm::interpreter::Interpreter;
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::Result;
+use std::process::Command;
+
+fn load_print_args() -> Result<Interpreter> {
+    let mut interpreter = Interpreter::new();
+    interpreter.load_class(ClassFile::from_file("examples/PrintArgs.class")?)?;
+    Ok(interpreter)
+}
+
+/// 运行 PrintArgs 并返回程序自身的输出（"=== 开始执行 ===" 之后、"✓ 执行成功" 之前）
+fn run_print_args(args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_rsjvm"))
+        .args(["run", "examples/PrintArgs.class"])
+        .args(args)
+        .env("RUST_BACKTRACE", "0")
+        .output()
+        .expect("failed to run rsjvm");
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let program = stdout.split_once("=== 开始执行 ===\n").unwrap().1;
+    program.split("✓ 执行成功").next().unwrap().to_string()
+}
+
+#[test]
+fn test_cli_passes_args_to_main() {
+    assert_eq!(run_print_args(&["hello", "world"]), "2\nhello\n");
+    assert_eq!(run_print_args(&[]), "0\n");
+}
+
+#[test]
+fn test_main_receives_string_array() -> Result<()> {
+    let mut interpreter = load_print_args()?;
+    let array = interpreter
+        .heap
+        .allocate_reference_array("java/lang/String", 1)?;
+    let arg = interpreter.heap.allocate_string("only")?;
+    interpreter
+        .heap
+        .array_set(array, 0, JvmValue::Reference(Some(arg)))?;
+
+    let result = interpreter.execute_method_with_args(
+        "PrintArgs",
+        "main:([Ljava/lang/String;)V",
+        vec![JvmValue::Reference(Some(array))],
+    )?;
+    assert!(result.is_none());
+    Ok(())
+}
+
+#[test]
+fn test_long_args_take_two_slots() -> Result<()> {
+    let mut interpreter = load_print_args()?;
+    let result = interpreter.execute_method_with_args(
+        "PrintArgs",
+        "afterLong:(JI)I",
+        vec![JvmValue::Long(1 << 40), JvmValue::Int(7)],
+    )?;
+    assert!(matches!(result, Some(JvmValue::Int(7))), "{:?}", result);
+    Ok(())
+}
+
+#[test]
+fn test_instance_method_takes_this_first() -> Result<()> {
+    let mut interpreter = load_print_args()?;
+    let this = interpreter.heap.allocate("PrintArgs".to_string())?;
+    let result = interpreter.execute_method_with_args(
+        "PrintArgs",
+        "secondLong:(JJ)J",
+        vec![
+            JvmValue::Reference(Some(this)),
+            JvmValue::Long(-1),
+            JvmValue::Long(42),
+        ],
+    )?;
+    assert!(matches!(result, Some(JvmValue::Long(42))), "{:?}", result);
+    Ok(())
+}
+
+#[test]
+fn test_wrong_arg_count_rejected() -> Result<()> {
+    let mut interpreter = load_print_args()?;
+    let err = interpreter
+        .execute_method_with_args("PrintArgs", "afterLong:(JI)I", vec![JvmValue::Long(1)])
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "PrintArgs.afterLong(JI)I expects 2 arguments, got 1"
+    );
+
+    let err = interpreter
+        .execute_method_with_args("PrintArgs", "missing:()V", vec![])
+        .unwrap_err();
+    assert!(err.to_string().contains("missing:()V"), "{}", err);
+    Ok(())
+}
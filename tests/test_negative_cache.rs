@@ -0,0 +1,92 @@
+//! 测试否定解析缓存：同一个调用点的失败/跳过结果只解析一次
+
+use rsjvm::classfile::ClassFile;
+use rsjvm::classloader::ClassLoader;
+use rsjvm::interpreter::Interpreter;
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::runtime::ResolutionStats;
+use rsjvm::Result;
+use std::path::PathBuf;
+
+fn call_int(interpreter: &mut Interpreter, method: &str) -> Result<i32> {
+    match interpreter.invoke_static("NegativeCache", method, "()I", vec![])? {
+        Some(JvmValue::Int(v)) => Ok(v),
+        other => panic!("NegativeCache.{} 期望返回 Int, 实际: {:?}", method, other),
+    }
+}
+
+/// 只包含 NegativeCache.class 的临时类路径（没有 Ghost.class）
+fn class_path_without_ghost(tag: &str) -> Result<PathBuf> {
+    let dir = std::env::temp_dir().join(format!("rsjvm-negative-{}-{}", tag, std::process::id()));
+    std::fs::create_dir_all(&dir)?;
+    std::fs::copy("examples/NegativeCache.class", dir.join("NegativeCache.class"))?;
+    Ok(dir)
+}
+
+#[test]
+fn test_unimplemented_jdk_call_resolved_once() -> Result<()> {
+    let mut interpreter = Interpreter::new();
+    interpreter.load_class(ClassFile::from_file("examples/NegativeCache.class")?)?;
+
+    // 每次都抛出 UnsatisfiedLinkError 并被捕获
+    assert_eq!(call_int(&mut interpreter, "unlinkedLoop")?, 1000);
+    assert_eq!(
+        interpreter.metaspace.resolution_stats(),
+        ResolutionStats {
+            misses: 1,
+            negative_hits: 999,
+            call_site_hits: 0
+        }
+    );
+    Ok(())
+}
+
+#[test]
+fn test_missing_class_resolved_once() -> Result<()> {
+    let dir = class_path_without_ghost("missing")?;
+    let mut interpreter = Interpreter::with_class_loader(ClassLoader::new(vec![dir.clone()]));
+    interpreter.load_class(ClassFile::from_file(dir.join("NegativeCache.class"))?)?;
+
+    // 每次都抛出同样的异常并被捕获
+    assert_eq!(call_int(&mut interpreter, "missingLoop")?, 1000);
+    let stats = interpreter.run_stats().resolution;
+    std::fs::remove_dir_all(&dir)?;
+    assert_eq!(stats.misses, 1);
+    assert_eq!(stats.negative_hits, 999);
+    Ok(())
+}
+
+#[test]
+fn test_class_path_change_invalidates_negatives() -> Result<()> {
+    let dir = class_path_without_ghost("invalidate")?;
+    let mut interpreter = Interpreter::with_class_loader(ClassLoader::new(vec![dir.clone()]));
+    interpreter.load_class(ClassFile::from_file(dir.join("NegativeCache.class"))?)?;
+
+    let err = call_int(&mut interpreter, "callGhost").unwrap_err().to_string();
+    assert!(err.contains("java/lang/ClassNotFoundException: Ghost"), "{}", err);
+    // 再次执行直接使用缓存的异常
+    let err = call_int(&mut interpreter, "callGhost").unwrap_err().to_string();
+    assert!(err.contains("java/lang/ClassNotFoundException: Ghost"), "{}", err);
+    assert_eq!(interpreter.metaspace.resolution_stats().negative_hits, 1);
+
+    // 加入包含 Ghost.class 的类路径后重新解析
+    interpreter.add_class_path("examples");
+    let result = call_int(&mut interpreter, "callGhost");
+    std::fs::remove_dir_all(&dir)?;
+    assert_eq!(result?, 13);
+    assert_eq!(interpreter.metaspace.resolution_stats().negative_hits, 1);
+    Ok(())
+}
+
+#[test]
+fn test_loading_class_invalidates_negatives() -> Result<()> {
+    let dir = class_path_without_ghost("define")?;
+    let mut interpreter = Interpreter::with_class_loader(ClassLoader::new(vec![dir.clone()]));
+    interpreter.load_class(ClassFile::from_file(dir.join("NegativeCache.class"))?)?;
+    std::fs::remove_dir_all(&dir)?;
+
+    assert!(call_int(&mut interpreter, "callGhost").is_err());
+    interpreter.load_class(ClassFile::from_file("examples/Ghost.class")?)?;
+    assert_eq!(call_int(&mut interpreter, "callGhost")?, 13);
+    Ok(())
+}
@@ -0,0 +1,71 @@
+//! 测试 ACONST_NULL、IFNULL/IFNONNULL、IF_ACMPEQ/IF_ACMPNE
+
+use rsjvm::classfile::ClassFile;
+use rsjvm::interpreter::Interpreter;
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::Result;
+
+fn load() -> Result<Interpreter> {
+    let mut interpreter = Interpreter::new();
+    interpreter.load_class(ClassFile::from_file("examples/NullCheck.class")?)?;
+    Ok(interpreter)
+}
+
+fn call(jvm: &mut Interpreter, method: &str, descriptor: &str, args: Vec<JvmValue>) -> i32 {
+    match jvm.invoke_static("NullCheck", method, descriptor, args) {
+        Ok(Some(JvmValue::Int(v))) => v,
+        result => panic!("NullCheck.{} 期望返回 Int, 实际: {:?}", method, result),
+    }
+}
+
+#[test]
+fn test_ifnonnull_with_null_and_new_object() -> Result<()> {
+    let mut jvm = load()?;
+    assert_eq!(call(&mut jvm, "check", "(Ljava/lang/Object;)I", vec![JvmValue::Reference(None)]), 1);
+
+    let obj = jvm.heap.allocate("NullCheck".to_string())?;
+    assert_eq!(call(&mut jvm, "check", "(Ljava/lang/Object;)I", vec![JvmValue::Reference(Some(obj))]), 0);
+
+    // 方法内部 NEW 出来的对象
+    assert_eq!(call(&mut jvm, "freshObject", "()I", vec![]), 0);
+    Ok(())
+}
+
+#[test]
+fn test_aconst_null_and_ifnull() -> Result<()> {
+    let mut jvm = load()?;
+    assert_eq!(call(&mut jvm, "nullLocal", "()I", vec![]), 1);
+    Ok(())
+}
+
+#[test]
+fn test_if_acmp_compares_identity() -> Result<()> {
+    let mut jvm = load()?;
+    let desc = "(Ljava/lang/Object;Ljava/lang/Object;)I";
+    let a = jvm.heap.allocate("NullCheck".to_string())?;
+    let b = jvm.heap.allocate("NullCheck".to_string())?;
+    let r = |ptr| JvmValue::Reference(ptr);
+
+    assert_eq!(call(&mut jvm, "same", desc, vec![r(Some(a)), r(Some(a))]), 1);
+    assert_eq!(call(&mut jvm, "same", desc, vec![r(Some(a)), r(Some(b))]), 0);
+    assert_eq!(call(&mut jvm, "same", desc, vec![r(None), r(None)]), 1);
+    assert_eq!(call(&mut jvm, "same", desc, vec![r(Some(a)), r(None)]), 0);
+
+    assert_eq!(call(&mut jvm, "differ", desc, vec![r(Some(a)), r(Some(b))]), 1);
+    assert_eq!(call(&mut jvm, "differ", desc, vec![r(None), r(None)]), 0);
+
+    assert_eq!(call(&mut jvm, "identity", "()I", vec![]), 101);
+    Ok(())
+}
+
+#[test]
+fn test_non_reference_is_type_error() -> Result<()> {
+    let mut jvm = load()?;
+    let err = jvm
+        .invoke_static("NullCheck", "check", "(Ljava/lang/Object;)I", vec![JvmValue::Int(7)])
+        .unwrap_err();
+    let message = format!("{:#}", err);
+    assert!(message.contains("ifnonnull at pc 1"), "{}", message);
+    assert!(message.contains("Expected Reference on stack, found Int(7)"), "{}", message);
+    Ok(())
+}
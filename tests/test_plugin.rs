@@ -0,0 +1,86 @@
+//! 测试 Class.forName + newInstance 插件模式：按名字加载类、反射构造、invokeinterface 分派
+
+use rsjvm::classfile::ClassFile;
+use rsjvm::classloader::ClassLoader;
+use rsjvm::interpreter::Interpreter;
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::runtime::metaspace::method_key;
+use rsjvm::Result;
+
+/// 只加载 PluginMain，其余类交给类加载器按需查找
+fn plugin_interpreter() -> Result<Interpreter> {
+    let mut interpreter = Interpreter::with_class_loader(ClassLoader::new(vec!["examples".into()]));
+    interpreter.load_class(ClassFile::from_file("examples/PluginMain.class")?)?;
+    Ok(interpreter)
+}
+
+fn run_static(interpreter: &mut Interpreter, method_name: &str) -> Result<Option<JvmValue>> {
+    let (code, max_locals, max_stack) = {
+        let class_meta = interpreter.metaspace.get_class("PluginMain")?;
+        let method = class_meta.find_method(method_name, "()I")?;
+        (method.code.clone(), method.max_locals, method.max_stack)
+    };
+    interpreter.execute_method_with_class(
+        "PluginMain",
+        &method_key(method_name, "()I"),
+        &code,
+        max_locals,
+        max_stack,
+    )
+}
+
+#[test]
+fn test_for_name_new_instance() -> Result<()> {
+    let mut interpreter = plugin_interpreter()?;
+    assert!(!interpreter.metaspace.is_class_loaded("HelloPlugin"));
+
+    match run_static(&mut interpreter, "run")? {
+        // 构造器把 bonus 设为 22，greet(20) = 42
+        Some(JvmValue::Int(v)) => assert_eq!(v, 42),
+        other => panic!("expected int, got {:?}", other),
+    }
+    assert!(interpreter.metaspace.is_class_loaded("HelloPlugin"));
+    Ok(())
+}
+
+#[test]
+fn test_interface_default_method() -> Result<()> {
+    let mut interpreter = plugin_interpreter()?;
+    // HelloPlugin 没有覆盖 greetTwice，invokeinterface 使用 Greeter 的 default 方法：
+    // greet(greet(20)) = 20 + 22 + 22
+    match run_static(&mut interpreter, "runDefault")? {
+        Some(JvmValue::Int(v)) => assert_eq!(v, 64),
+        other => panic!("expected int, got {:?}", other),
+    }
+    Ok(())
+}
+
+#[test]
+fn test_for_name_missing_class() -> Result<()> {
+    let mut interpreter = plugin_interpreter()?;
+    let err = run_static(&mut interpreter, "loadMissing").unwrap_err().to_string();
+    assert!(err.contains("java/lang/ClassNotFoundException"), "{}", err);
+    assert!(err.contains("NoSuchPlugin"), "{}", err);
+    Ok(())
+}
+
+#[test]
+fn test_new_instance_without_no_arg_constructor() -> Result<()> {
+    let mut interpreter = plugin_interpreter()?;
+    let err = run_static(&mut interpreter, "loadWithoutDefaultConstructor")
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("java/lang/InstantiationException"), "{}", err);
+    Ok(())
+}
+
+#[test]
+fn test_class_mirror_is_unique() -> Result<()> {
+    let mut interpreter = plugin_interpreter()?;
+    interpreter.ensure_class_loaded("HelloPlugin")?;
+    let a = interpreter.class_mirror("HelloPlugin")?;
+    let b = interpreter.class_mirror("HelloPlugin")?;
+    assert_eq!(a, b);
+    assert_ne!(a, interpreter.class_mirror("PluginMain")?);
+    Ok(())
+}
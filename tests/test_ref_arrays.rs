@@ -0,0 +1,97 @@
+//! 测试引用数组：anewarray、aaload/aastore、multianewarray
+
+use rsjvm::classfile::ClassFile;
+use rsjvm::interpreter::Interpreter;
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::runtime::metaspace::method_key;
+use rsjvm::Result;
+
+/// 加载 RefArrayTest 并执行一个无参静态方法
+fn run_static(
+    interpreter: &mut Interpreter,
+    method_name: &str,
+    descriptor: &str,
+) -> Result<Option<JvmValue>> {
+    let class_file = ClassFile::from_file("examples/RefArrayTest.class")?;
+    let class_name = interpreter.load_class(class_file)?;
+
+    let (code, max_locals, max_stack) = {
+        let class_meta = interpreter.metaspace.get_class(&class_name)?;
+        let method = class_meta.find_method(method_name, descriptor)?;
+        (method.code.clone(), method.max_locals, method.max_stack)
+    };
+
+    interpreter.execute_method_with_class(
+        &class_name,
+        &method_key(method_name, descriptor),
+        &code,
+        max_locals,
+        max_stack,
+    )
+}
+
+#[test]
+fn test_string_array_defaults_to_null() -> Result<()> {
+    let mut interpreter = Interpreter::new();
+    let array = match run_static(&mut interpreter, "makeStrings", "()[Ljava/lang/String;")? {
+        Some(JvmValue::Reference(Some(ptr))) => ptr,
+        other => panic!("expected array reference, got {:?}", other),
+    };
+
+    assert_eq!(interpreter.heap.get(array)?.class_name, "[Ljava/lang/String;");
+    assert_eq!(interpreter.heap.array_length(array)?, 3);
+    assert!(interpreter
+        .heap
+        .array_elements(array)?
+        .iter()
+        .all(|e| matches!(e, JvmValue::Reference(None))));
+
+    // 存入一个对象引用再读回
+    let obj = interpreter.heap.allocate("java/lang/String".to_string())?;
+    interpreter
+        .heap
+        .array_set(array, 1, JvmValue::Reference(Some(obj)))?;
+    match interpreter.heap.array_get(array, 1)? {
+        JvmValue::Reference(Some(ptr)) => assert_eq!(ptr, obj),
+        other => panic!("expected reference, got {:?}", other),
+    }
+    Ok(())
+}
+
+#[test]
+fn test_store_new_objects_and_load_back() -> Result<()> {
+    let mut interpreter = Interpreter::new();
+    match run_static(&mut interpreter, "storeAndLoad", "()I")? {
+        Some(JvmValue::Int(30)) => (),
+        other => panic!("expected 30, got {:?}", other),
+    }
+    Ok(())
+}
+
+#[test]
+fn test_multianewarray_2d() -> Result<()> {
+    let mut interpreter = Interpreter::new();
+    match run_static(&mut interpreter, "twoD", "()I")? {
+        Some(JvmValue::Int(235)) => (),
+        other => panic!("expected 235, got {:?}", other),
+    }
+    Ok(())
+}
+
+#[test]
+fn test_store_into_null_array() {
+    let mut interpreter = Interpreter::new();
+    let err = run_static(&mut interpreter, "storeIntoNull", "()V")
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("NullPointerException"), "{}", err);
+}
+
+#[test]
+fn test_aaload_out_of_bounds() {
+    let mut interpreter = Interpreter::new();
+    let err = run_static(&mut interpreter, "loadOutOfBounds", "()Ljava/lang/Object;")
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("Index 2 out of bounds for length 2"), "{}", err);
+}
@@ -0,0 +1,64 @@
+//! 测试 JVM 抛出的运行时异常（除零、空指针、数组越界）按异常表分派
+
+use rsjvm::classfile::ClassFile;
+use rsjvm::interpreter::Interpreter;
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::runtime::metaspace::method_key;
+use rsjvm::Result;
+
+/// 加载 RuntimeExceptionTest 并执行一个 ()I 静态方法
+fn run_static_int(method_name: &str) -> Result<i32> {
+    let mut interpreter = Interpreter::new();
+    let class_file = ClassFile::from_file("examples/RuntimeExceptionTest.class")?;
+    let class_name = interpreter.load_class(class_file)?;
+
+    let (code, max_locals, max_stack) = {
+        let class_meta = interpreter.metaspace.get_class(&class_name)?;
+        let method = class_meta.find_method(method_name, "()I")?;
+        (method.code.clone(), method.max_locals, method.max_stack)
+    };
+
+    match interpreter.execute_method_with_class(
+        &class_name,
+        &method_key(method_name, "()I"),
+        &code,
+        max_locals,
+        max_stack,
+    )? {
+        Some(JvmValue::Int(v)) => Ok(v),
+        other => panic!("expected int, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_divide_by_zero_caught() -> Result<()> {
+    assert_eq!(run_static_int("divideByZero")?, -1);
+    assert_eq!(run_static_int("divideCaughtAsRuntimeException")?, -2);
+    // 被调用方法中除零，在调用者中捕获
+    assert_eq!(run_static_int("divideInCallee")?, -6);
+    Ok(())
+}
+
+#[test]
+fn test_null_field_access_caught() -> Result<()> {
+    assert_eq!(run_static_int("nullFieldRead")?, -3);
+    Ok(())
+}
+
+#[test]
+fn test_array_index_out_of_bounds_caught() -> Result<()> {
+    assert_eq!(run_static_int("arrayIndexOutOfBounds")?, -4);
+    // ArrayIndexOutOfBoundsException 是 IndexOutOfBoundsException 的子类
+    assert_eq!(run_static_int("caughtAsIndexOutOfBounds")?, -5);
+    Ok(())
+}
+
+#[test]
+fn test_uncaught_runtime_exception() {
+    let err = run_static_int("notCaughtByWrongType").unwrap_err().to_string();
+    assert!(
+        err.contains("Uncaught exception: java/lang/ArithmeticException: / by zero"),
+        "{}",
+        err
+    );
+}
@@ -0,0 +1,79 @@
+//! 测试 getstatic / putstatic 指令
+
+use rsjvm::classfile::ClassFile;
+use rsjvm::interpreter::Interpreter;
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::runtime::metaspace::method_key;
+use rsjvm::Result;
+
+/// 在已加载 StaticCounter 的解释器上执行一个无参静态方法
+fn run_static(
+    interpreter: &mut Interpreter,
+    class_name: &str,
+    method_name: &str,
+    descriptor: &str,
+) -> Result<Option<JvmValue>> {
+    let (code, max_locals, max_stack) = {
+        let class_meta = interpreter.metaspace.get_class(class_name)?;
+        let method = class_meta.find_method(method_name, descriptor)?;
+        (method.code.clone(), method.max_locals, method.max_stack)
+    };
+    interpreter.execute_method_with_class(
+        class_name,
+        &method_key(method_name, descriptor),
+        &code,
+        max_locals,
+        max_stack,
+    )
+}
+
+#[test]
+fn test_putstatic_then_getstatic() -> Result<()> {
+    let mut interpreter = Interpreter::new();
+    let class_file = ClassFile::from_file("examples/StaticCounter.class")?;
+    let class_name = interpreter.load_class(class_file)?;
+
+    match run_static(&mut interpreter, &class_name, "setAndGet", "()I")? {
+        Some(JvmValue::Int(5)) => (),
+        other => panic!("expected 5, got {:?}", other),
+    }
+
+    // 直接从 Metaspace 读取静态字段
+    let class_meta = interpreter.metaspace.get_class(&class_name)?;
+    match class_meta.static_fields.get("counter") {
+        Some(JvmValue::Int(5)) => (),
+        other => panic!("expected counter = 5, got {:?}", other),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_static_field_persists_across_calls() -> Result<()> {
+    let mut interpreter = Interpreter::new();
+    let class_file = ClassFile::from_file("examples/StaticCounter.class")?;
+    let class_name = interpreter.load_class(class_file)?;
+
+    // 未赋值时从 0 开始
+    run_static(&mut interpreter, &class_name, "increment", "()I")?;
+    match run_static(&mut interpreter, &class_name, "increment", "()I")? {
+        Some(JvmValue::Int(2)) => (),
+        other => panic!("expected 2, got {:?}", other),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_getstatic_default_value() -> Result<()> {
+    let mut interpreter = Interpreter::new();
+    let class_file = ClassFile::from_file("examples/StaticCounter.class")?;
+    let class_name = interpreter.load_class(class_file)?;
+
+    match run_static(&mut interpreter, &class_name, "readDefault", "()J")? {
+        Some(JvmValue::Long(0)) => (),
+        other => panic!("expected 0L, got {:?}", other),
+    }
+
+    Ok(())
+}
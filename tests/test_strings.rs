@@ -0,0 +1,64 @@
+//! 测试 String 常量：ldc 分配堆上的 String 对象并驻留，println 打印字符串内容
+
+use rsjvm::classfile::ClassFile;
+use rsjvm::interpreter::embed::JResult;
+use rsjvm::interpreter::Interpreter;
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::Result;
+use std::process::Command;
+
+fn load() -> Result<Interpreter> {
+    let mut interpreter = Interpreter::new();
+    interpreter.load_class(ClassFile::from_file("examples/HelloString.class")?)?;
+    Ok(interpreter)
+}
+
+#[test]
+fn test_ldc_string_is_heap_string() -> Result<()> {
+    let mut jvm = load()?;
+    match jvm.invoke_static("HelloString", "greeting", "()Ljava/lang/String;", vec![])? {
+        Some(JvmValue::Reference(Some(ptr))) => {
+            assert_eq!(jvm.heap.get(ptr)?.class_name, "java/lang/String");
+            assert_eq!(jvm.heap.get_string(ptr)?, "Hello, RSJVM");
+        }
+        other => panic!("期望返回 String 引用, 实际: {:?}", other),
+    }
+    Ok(())
+}
+
+#[test]
+fn test_ldc_of_same_literal_is_interned() -> Result<()> {
+    let mut jvm = load()?;
+    let call = |jvm: &mut Interpreter, method| jvm.invoke_static_typed("HelloString", method, "()Z", &[]);
+
+    assert_eq!(call(&mut jvm, "sameLiteral")?, JResult::Bool(true));
+    assert_eq!(call(&mut jvm, "sameAcrossMethods")?, JResult::Bool(true));
+    assert_eq!(call(&mut jvm, "differentLiterals")?, JResult::Bool(false));
+
+    // 多次调用也不会重复分配
+    let before = jvm.heap.object_count();
+    call(&mut jvm, "sameLiteral")?;
+    assert_eq!(jvm.heap.object_count(), before);
+
+    assert_eq!(jvm.intern_string("Hello")?, jvm.intern_string("Hello")?);
+    Ok(())
+}
+
+#[test]
+fn test_println_string_end_to_end() {
+    let output = Command::new(env!("CARGO_BIN_EXE_rsjvm"))
+        .args(["run", "examples/HelloString.class"])
+        .output()
+        .expect("failed to run rsjvm");
+    assert!(output.status.success(), "{:?}", output);
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let printed: Vec<_> = stdout
+        .split("=== 开始执行 ===\n")
+        .nth(1)
+        .unwrap()
+        .lines()
+        .take(3)
+        .collect();
+    assert_eq!(printed, ["Hello", "Hello, RSJVM", "42"]);
+}
@@ -0,0 +1,57 @@
+//! 测试 tableswitch / lookupswitch 指令
+
+use rsjvm::classfile::ClassFile;
+use rsjvm::interpreter::Interpreter;
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::runtime::metaspace::method_key;
+use rsjvm::Result;
+
+/// 加载 SwitchTest 并执行一个 ()I 静态方法
+fn run_static_int(method_name: &str) -> Result<i32> {
+    let mut interpreter = Interpreter::new();
+    let class_file = ClassFile::from_file("examples/SwitchTest.class")?;
+    let class_name = interpreter.load_class(class_file)?;
+
+    let (code, max_locals, max_stack) = {
+        let class_meta = interpreter.metaspace.get_class(&class_name)?;
+        let method = class_meta.find_method(method_name, "()I")?;
+        (method.code.clone(), method.max_locals, method.max_stack)
+    };
+
+    match interpreter.execute_method_with_class(
+        &class_name,
+        &method_key(method_name, "()I"),
+        &code,
+        max_locals,
+        max_stack,
+    )? {
+        Some(JvmValue::Int(v)) => Ok(v),
+        other => panic!("expected int, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_tableswitch_cases() -> Result<()> {
+    assert_eq!(run_static_int("dense0")?, 10);
+    assert_eq!(run_static_int("dense1")?, 11);
+    assert_eq!(run_static_int("dense2")?, 12);
+    assert_eq!(run_static_int("dense3")?, 13);
+    Ok(())
+}
+
+#[test]
+fn test_tableswitch_default() -> Result<()> {
+    // 高于 high 和低于 low 都走 default
+    assert_eq!(run_static_int("denseDefault")?, -1);
+    assert_eq!(run_static_int("denseNegative")?, -1);
+    Ok(())
+}
+
+#[test]
+fn test_lookupswitch_cases() -> Result<()> {
+    assert_eq!(run_static_int("sparse1")?, 1);
+    assert_eq!(run_static_int("sparse100")?, 2);
+    assert_eq!(run_static_int("sparse10000")?, 3);
+    assert_eq!(run_static_int("sparseDefault")?, 0);
+    Ok(())
+}
@@ -0,0 +1,137 @@
+//! 测试 long/double 在局部变量表中占两个槽位
+
+use rsjvm::classfile::ClassFile;
+use rsjvm::interpreter::Interpreter;
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::runtime::Frame;
+use rsjvm::Result;
+
+fn load_wide_args() -> Result<Interpreter> {
+    let mut interpreter = Interpreter::new();
+    interpreter.load_class(ClassFile::from_file("examples/WideArgs.class")?)?;
+    Ok(interpreter)
+}
+
+#[test]
+fn test_param_after_long_uses_correct_slot() -> Result<()> {
+    let mut interpreter = load_wide_args()?;
+    // a=1, b=10, c=4：c 在槽位 3，循环 3 次
+    let result = interpreter.invoke_static(
+        "WideArgs",
+        "f",
+        "(IJI)J",
+        vec![JvmValue::Int(1), JvmValue::Long(10), JvmValue::Int(4)],
+    )?;
+    assert!(matches!(result, Some(JvmValue::Long(40))), "{:?}", result);
+    Ok(())
+}
+
+#[test]
+fn test_invokestatic_passes_wide_args() -> Result<()> {
+    let mut interpreter = load_wide_args()?;
+    let result = interpreter.invoke_static("WideArgs", "callF", "()J", vec![])?;
+    assert!(matches!(result, Some(JvmValue::Long(40))), "{:?}", result);
+
+    let result = interpreter.invoke_static("WideArgs", "callG", "()D", vec![])?;
+    assert!(matches!(result, Some(JvmValue::Double(v)) if v == 6.5), "{:?}", result);
+    Ok(())
+}
+
+#[test]
+fn test_invokespecial_passes_wide_args() -> Result<()> {
+    let mut interpreter = load_wide_args()?;
+    let result = interpreter.invoke_static("WideArgs", "callH", "()J", vec![])?;
+    assert!(matches!(result, Some(JvmValue::Long(42))), "{:?}", result);
+    Ok(())
+}
+
+#[test]
+fn test_arg_width_must_match_descriptor() -> Result<()> {
+    let mut interpreter = load_wide_args()?;
+    let err = interpreter
+        .invoke_static(
+            "WideArgs",
+            "f",
+            "(IJI)J",
+            vec![JvmValue::Int(1), JvmValue::Int(10), JvmValue::Int(4)],
+        )
+        .unwrap_err();
+    assert!(err.to_string().contains("J parameter"), "{}", err);
+    Ok(())
+}
+
+#[test]
+fn test_frame_wide_slots() -> Result<()> {
+    let mut frame = Frame::new(3, 4);
+    frame.set_local_wide(1, JvmValue::Long(5))?;
+    assert!(matches!(frame.get_local_wide(1)?, JvmValue::Long(5)));
+    // 第二个槽位不是一个 long 的起点
+    assert!(frame.get_local_wide(0).is_err());
+    // 越界：index+1 必须在局部变量表内
+    assert!(frame.set_local_wide(2, JvmValue::Long(1)).is_err());
+
+    // 覆盖第二个槽位后，原来的 long 失效
+    frame.set_local(2, JvmValue::Int(9))?;
+    assert!(frame.get_local_wide(1).is_err());
+
+    frame.push(JvmValue::Double(1.0))?;
+    frame.push(JvmValue::Int(1))?;
+    assert_eq!(frame.stack_size(), 2);
+    assert_eq!(frame.stack_slots(), 3);
+    Ok(())
+}
+
+/// 手写字节码：wide 前缀把局部变量索引扩展到 16 位
+fn run_hand(code: &[u8], max_locals: usize) -> Result<Option<JvmValue>> {
+    Interpreter::new().execute_method_with_class("Hand", "run:()I", code, max_locals, 4)
+}
+
+#[test]
+fn test_wide_istore_iload_beyond_255() -> Result<()> {
+    let code = [
+        0x11, 0x04, 0xd2, // sipush 1234
+        0xc4, 0x36, 0x01, 0x2c, // wide istore 300
+        0xc4, 0x15, 0x01, 0x2c, // wide iload 300
+        0xac, // ireturn
+    ];
+    let result = run_hand(&code, 301)?;
+    assert!(matches!(result, Some(JvmValue::Int(1234))), "{:?}", result);
+    Ok(())
+}
+
+#[test]
+fn test_wide_iinc_uses_16_bit_delta() -> Result<()> {
+    let code = [
+        0x03, // iconst_0
+        0xc4, 0x36, 0x01, 0x2c, // wide istore 300
+        0xc4, 0x84, 0x01, 0x2c, 0xfc, 0x18, // wide iinc 300, -1000
+        0xc4, 0x15, 0x01, 0x2c, // wide iload 300
+        0xac, // ireturn
+    ];
+    let result = run_hand(&code, 301)?;
+    assert!(matches!(result, Some(JvmValue::Int(-1000))), "{:?}", result);
+    Ok(())
+}
+
+#[test]
+fn test_wide_lstore_lload() -> Result<()> {
+    let code = [
+        0x0a, // lconst_1
+        0xc4, 0x37, 0x01, 0x00, // wide lstore 256
+        0xc4, 0x16, 0x01, 0x00, // wide lload 256
+        0xad, // lreturn
+    ];
+    let result = run_hand(&code, 258)?;
+    assert!(matches!(result, Some(JvmValue::Long(1))), "{:?}", result);
+    Ok(())
+}
+
+#[test]
+fn test_wide_index_out_of_range() {
+    let code = [
+        0x03, // iconst_0
+        0xc4, 0x36, 0x01, 0x2c, // wide istore 300
+        0xb1, // return
+    ];
+    assert!(run_hand(&code, 300).is_err());
+}
@@ -0,0 +1,68 @@
+//! 测试 java/lang/Thread：start/join、守护线程、线程中未捕获的异常，以及入口方法返回后等待线程结束
+
+use rsjvm::{Jvm, JvmBuilder, Result};
+use std::process::Command;
+
+fn jvm() -> Jvm {
+    JvmBuilder::new().class_path("examples").build()
+}
+
+#[test]
+fn test_two_threads_increment_their_own_fields() -> Result<()> {
+    let mut jvm = jvm();
+    // join 之前入口线程读到的字段是 0：结果说明 join 等到了两个线程结束
+    let result: i32 = jvm.call_static_typed("ThreadDemo", "runBoth", "()I", ())?;
+    assert_eq!(result, 1000 * 10000 + 1000);
+    Ok(())
+}
+
+#[test]
+fn test_thread_is_not_alive_after_join() -> Result<()> {
+    let mut jvm = jvm();
+    let alive: bool = jvm.call_static_typed("ThreadDemo", "aliveAfterJoin", "()Z", ())?;
+    assert!(!alive);
+    Ok(())
+}
+
+#[test]
+fn test_starting_twice_throws() -> Result<()> {
+    let mut jvm = jvm();
+    let rejected: bool = jvm.call_static_typed("ThreadDemo", "startTwice", "()Z", ())?;
+    assert!(rejected);
+    Ok(())
+}
+
+#[test]
+fn test_daemon_thread_does_not_keep_the_run_alive() -> Result<()> {
+    let mut jvm = jvm();
+    let result: i32 = jvm.call_static_typed("ThreadDemo", "daemonSpinner", "()I", ())?;
+    assert_eq!(result, 7);
+    Ok(())
+}
+
+#[test]
+fn test_uncaught_exception_ends_only_that_thread() -> Result<()> {
+    let mut jvm = JvmBuilder::new().class_path("examples").capture_output().build();
+    let result: i32 = jvm.call_static_typed("ThreadDemo", "survivesThrowingThread", "()I", ())?;
+    assert_eq!(result, 1);
+    // 报告写到解释器的错误输出，而不是进程的 stderr
+    let errors = String::from_utf8(jvm.take_captured_errors()).unwrap();
+    assert!(errors.starts_with("Exception in thread \"Thread-"), "{}", errors);
+    assert!(errors.contains("java/lang/IllegalStateException"), "{}", errors);
+    assert!(errors.contains("boom"), "{}", errors);
+    assert!(jvm.take_captured_output().is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_run_waits_for_non_daemon_threads() {
+    let output = Command::new(env!("CARGO_BIN_EXE_rsjvm"))
+        .args(["run", "examples/ThreadDemo.class"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{}", stdout);
+    let main = stdout.find("main done").expect("main output");
+    let worker = stdout.find("worker done 12497500").expect("worker output");
+    assert!(main < worker, "{}", stdout);
+}
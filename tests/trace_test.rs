@@ -0,0 +1,148 @@
+//! 测试指令跟踪钩子和 `run --trace`
+
+use rsjvm::classfile::ClassFile;
+use rsjvm::interpreter::trace::TraceEvent;
+use rsjvm::interpreter::Interpreter;
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::Result;
+use std::cell::RefCell;
+use std::process::Command;
+use std::rc::Rc;
+
+/// 安装收集所有事件的钩子
+fn collect_events(interpreter: &mut Interpreter) -> Rc<RefCell<Vec<TraceEvent>>> {
+    let events = Rc::new(RefCell::new(Vec::new()));
+    let sink = events.clone();
+    interpreter.set_trace_hook(move |event: &TraceEvent| sink.borrow_mut().push(event.clone()));
+    events
+}
+
+fn ints(values: &[JvmValue]) -> Vec<i32> {
+    values
+        .iter()
+        .map(|v| match v {
+            JvmValue::Int(i) => *i,
+            other => panic!("expected int, got {:?}", other),
+        })
+        .collect()
+}
+
+#[test]
+fn test_trace_hand_written_program() -> Result<()> {
+    let code = [
+        0x05, // iconst_2
+        0x3b, // istore_0
+        0x1a, // iload_0
+        0x06, // iconst_3
+        0x68, // imul
+        0x10, 0x07, // bipush 7
+        0x60, // iadd
+        0xac, // ireturn
+    ];
+    let mut interpreter = Interpreter::new();
+    let events = collect_events(&mut interpreter);
+    let result = interpreter.execute_method_with_class("Hand", "run:()I", &code, 1, 2)?;
+    assert!(matches!(result, Some(JvmValue::Int(13))), "{:?}", result);
+
+    let events = events.borrow();
+    let sequence: Vec<(usize, &str)> = events.iter().map(|e| (e.pc, e.mnemonic)).collect();
+    assert_eq!(
+        sequence,
+        [
+            (0, "iconst_2"),
+            (1, "istore_0"),
+            (2, "iload_0"),
+            (3, "iconst_3"),
+            (4, "imul"),
+            (5, "bipush"),
+            (7, "iadd"),
+            (8, "ireturn"),
+        ]
+    );
+    let stacks: Vec<Vec<i32>> = events.iter().map(|e| ints(&e.stack_top)).collect();
+    assert_eq!(
+        stacks,
+        [vec![2], vec![], vec![2], vec![2, 3], vec![6], vec![6, 7], vec![13], vec![]]
+    );
+
+    // 只有 istore_0 改写了局部变量
+    let changed: Vec<(usize, usize)> = events
+        .iter()
+        .enumerate()
+        .flat_map(|(i, e)| e.changed_locals.iter().map(move |(slot, _)| (i, *slot)))
+        .collect();
+    assert_eq!(changed, [(1, 0)]);
+    assert!(matches!(events[1].changed_locals[0].1, JvmValue::Int(2)));
+    assert!(events.iter().all(|e| e.depth == 1 && e.class_name == "Hand"));
+
+    assert_eq!(
+        events[3].to_string(),
+        "Hand.run    3: iconst_3        stack=[2, 3]"
+    );
+    assert_eq!(events[1].to_string(), "Hand.run    1: istore_0        stack=[] local[0]=2");
+    Ok(())
+}
+
+#[test]
+fn test_trace_follows_calls() -> Result<()> {
+    let mut interpreter = Interpreter::new();
+    interpreter.load_class(ClassFile::from_file("examples/CallChain.class")?)?;
+    let events = collect_events(&mut interpreter);
+    interpreter.invoke_static("CallChain", "middle", "(I)I", vec![JvmValue::Int(4)])?;
+
+    let events = events.borrow();
+    let calls: Vec<(usize, &str, &str)> = events
+        .iter()
+        .map(|e| (e.depth, e.method_name.as_str(), e.mnemonic))
+        .collect();
+    assert_eq!(
+        calls,
+        [
+            (1, "middle", "iload_0"),
+            (1, "middle", "invokestatic"),
+            (2, "bottom", "iload_0"),
+            (2, "bottom", "iconst_2"),
+            (2, "bottom", "imul"),
+            (2, "bottom", "ireturn"),
+            (1, "middle", "iconst_1"),
+            (1, "middle", "iadd"),
+            (1, "middle", "istore_1"),
+            (1, "middle", "iload_1"),
+            (1, "middle", "ireturn"),
+        ]
+    );
+    // 返回的栈帧已经弹出；返回值出现在调用者的栈上
+    assert!(events[5].stack_top.is_empty());
+    assert_eq!(ints(&events[6].stack_top), [8, 1]);
+    Ok(())
+}
+
+#[test]
+fn test_trace_disabled_and_cleared() -> Result<()> {
+    let code = [0x04, 0xac]; // iconst_1; ireturn
+    let mut interpreter = Interpreter::new();
+    let events = collect_events(&mut interpreter);
+    interpreter.clear_trace_hook();
+    interpreter.execute_method_with_class("Hand", "run:()I", &code, 0, 1)?;
+    assert!(events.borrow().is_empty());
+
+    let events = collect_events(&mut interpreter);
+    interpreter.set_trace(false);
+    interpreter.execute_method_with_class("Hand", "run:()I", &code, 0, 1)?;
+    assert!(events.borrow().is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_cli_trace_writes_to_stderr() {
+    let output = Command::new(env!("CARGO_BIN_EXE_rsjvm"))
+        .args(["run", "examples/CallChain.class", "-m", "bottom", "--trace"])
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!stdout.contains("[trace]"), "{}", stdout);
+    let lines: Vec<&str> = stderr.lines().filter(|l| l.starts_with("[trace]")).collect();
+    assert_eq!(lines.len(), 4, "{}", stderr);
+    assert!(lines[2].contains("CallChain.bottom    2: imul"), "{}", stderr);
+}
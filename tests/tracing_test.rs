@@ -0,0 +1,118 @@
+//! 测试 tracing 集成：每次方法调用一个 span，层级与调用结构一致
+//!
+//! 运行: cargo test --features tracing --test tracing_test
+
+#![cfg(feature = "tracing")]
+
+use rsjvm::classfile::ClassFile;
+use rsjvm::interpreter::Interpreter;
+use rsjvm::Result;
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+/// 记录下来的 span：(method 字段, 父 span 的 method 字段)
+#[derive(Debug, Default)]
+struct Recorded {
+    /// 按 span id 顺序保存 (method, parent_id)
+    spans: Vec<(String, Option<u64>)>,
+    /// 当前已进入的 span 栈
+    entered: Vec<u64>,
+    /// 事件数量（指令 trace 等）
+    events: usize,
+}
+
+/// 一个最小的测试 subscriber，只关心 span 的父子关系
+#[derive(Clone, Default)]
+struct RecordingSubscriber(Arc<Mutex<Recorded>>);
+
+struct MethodVisitor(String);
+
+impl Visit for MethodVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "method" {
+            self.0 = format!("{:?}", value);
+        }
+    }
+}
+
+impl Subscriber for RecordingSubscriber {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, attrs: &Attributes<'_>) -> Id {
+        let mut visitor = MethodVisitor(String::new());
+        attrs.record(&mut visitor);
+
+        let mut recorded = self.0.lock().unwrap();
+        let parent = if attrs.is_contextual() {
+            recorded.entered.last().copied()
+        } else {
+            attrs.parent().map(|id| id.into_u64())
+        };
+        recorded.spans.push((visitor.0, parent));
+        Id::from_u64(recorded.spans.len() as u64)
+    }
+
+    fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, _event: &Event<'_>) {
+        self.0.lock().unwrap().events += 1;
+    }
+
+    fn enter(&self, span: &Id) {
+        self.0.lock().unwrap().entered.push(span.into_u64());
+    }
+
+    fn exit(&self, span: &Id) {
+        let mut recorded = self.0.lock().unwrap();
+        assert_eq!(recorded.entered.pop(), Some(span.into_u64()));
+    }
+}
+
+#[test]
+fn test_span_hierarchy_matches_call_structure() -> Result<()> {
+    let subscriber = RecordingSubscriber::default();
+    let recorded = subscriber.0.clone();
+
+    tracing::subscriber::with_default(subscriber, || -> Result<()> {
+        let mut interpreter = Interpreter::new();
+        let class_file = ClassFile::from_file("examples/TestInvokeStatic.class")?;
+        let class_name = interpreter.load_class(class_file)?;
+
+        let (code, max_locals, max_stack) = {
+            let class_meta = interpreter.metaspace.get_class(&class_name)?;
+            let main_method = class_meta.find_method("main", "([Ljava/lang/String;)V")?;
+            (main_method.code.clone(), main_method.max_locals, main_method.max_stack)
+        };
+
+        // main -> sum_a_and_b
+        interpreter.execute_method_with_class(
+            &class_name,
+            "main:([Ljava/lang/String;)V",
+            &code,
+            max_locals,
+            max_stack,
+        )?;
+        Ok(())
+    })?;
+
+    let recorded = recorded.lock().unwrap();
+    assert_eq!(
+        recorded.spans,
+        vec![
+            ("main".to_string(), None),
+            ("sum_a_and_b".to_string(), Some(1)),
+        ]
+    );
+    // 所有 span 都已退出
+    assert!(recorded.entered.is_empty());
+    // 指令 trace 作为事件发出
+    assert!(recorded.events > 0);
+
+    Ok(())
+}
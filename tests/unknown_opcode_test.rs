@@ -0,0 +1,182 @@
+//! 测试未知操作码的处理策略：报错（默认）、停止并报告（WarnAndStop）、交给处理函数模拟（Trap）
+
+use rsjvm::classfile::access_flags::{ACC_PUBLIC, ACC_STATIC};
+use rsjvm::classfile::builder::ClassFileBuilder;
+use rsjvm::classfile::ClassFile;
+use rsjvm::interpreter::opcode_policy::{TrapAction, UnknownOpcodePolicy, UnknownOpcodeReport};
+use rsjvm::interpreter::{Interpreter, InterpreterOptions};
+use rsjvm::runtime::frame::JvmValue;
+use rsjvm::runtime::JavaException;
+use rsjvm::Result;
+use std::process::Command;
+
+/// 不存在的操作码，这里把它当作 swap
+const MYSTERY: u8 = 0xCB;
+
+/// `swapped()` 计算 1, 2 交换后相减：模拟了 swap 得 1，没有交换是 -1
+fn mystery_class() -> Result<ClassFile> {
+    ClassFileBuilder::new("Mystery")
+        .method(ACC_PUBLIC | ACC_STATIC, "swapped", "()I", 2, 0, |code| {
+            code.iconst(1).iconst(2).op(MYSTERY).isub().ireturn();
+        })
+        .build()
+}
+
+fn interpreter(policy: UnknownOpcodePolicy) -> Result<Interpreter> {
+    // 加载时的校验会拒绝含未知操作码的方法
+    let mut interpreter = Interpreter::new_with_options(InterpreterOptions {
+        verify: false,
+        unknown_opcode_policy: policy,
+        ..Default::default()
+    });
+    interpreter.load_class(mystery_class()?)?;
+    Ok(interpreter)
+}
+
+fn swapped(interpreter: &mut Interpreter) -> Result<Option<JvmValue>> {
+    interpreter.invoke_static("Mystery", "swapped", "()I", vec![])
+}
+
+/// 把 MYSTERY 当作 swap 的处理函数
+fn emulate_swap(opcode: u8, pc: usize, frame: &mut rsjvm::runtime::Frame) -> Result<TrapAction> {
+    if opcode != MYSTERY {
+        return Ok(TrapAction::Abort);
+    }
+    let (top, below) = (frame.pop()?, frame.pop()?);
+    frame.push(top)?;
+    frame.push(below)?;
+    Ok(TrapAction::Emulated { next_pc: pc + 1 })
+}
+
+#[test]
+fn test_error_is_the_default() -> Result<()> {
+    let default = InterpreterOptions::default().unknown_opcode_policy;
+    assert!(matches!(default, UnknownOpcodePolicy::Error));
+    let mut interpreter = interpreter(UnknownOpcodePolicy::default())?;
+    let err = swapped(&mut interpreter).unwrap_err();
+    assert!(format!("{:#}", err).contains("Unknown opcode: 0xCB at pc 2"), "{:#}", err);
+    assert!(err.downcast_ref::<UnknownOpcodeReport>().is_none());
+    Ok(())
+}
+
+#[test]
+fn test_verifier_rejects_unknown_opcodes() -> Result<()> {
+    let mut interpreter = Interpreter::new();
+    let err = interpreter.load_class(mystery_class()?).unwrap_err();
+    assert!(err.to_string().contains("VerifyError"), "{}", err);
+    Ok(())
+}
+
+#[test]
+fn test_warn_and_stop_reports_where_execution_stopped() -> Result<()> {
+    let mut interpreter = interpreter(UnknownOpcodePolicy::WarnAndStop)?;
+    let err = swapped(&mut interpreter).unwrap_err();
+    let report = err.downcast_ref::<UnknownOpcodeReport>().expect("UnknownOpcodeReport");
+    assert_eq!(report.opcode, MYSTERY);
+    assert_eq!(report.pc(), 2);
+    // iconst_1、iconst_2，以及这条未知指令
+    assert_eq!(report.instructions, 3);
+    assert_eq!(report.frames.len(), 1);
+    assert_eq!(report.frames[0].method_name, "swapped");
+
+    let context: Vec<_> = report.context.iter().map(|i| (i.pc, i.mnemonic.as_str())).collect();
+    assert_eq!(
+        context,
+        [(0, "iconst_1"), (1, "iconst_2"), (2, "unknown"), (3, "isub"), (4, "ireturn")]
+    );
+    let text = report.to_string();
+    let headline = "unknown opcode 0xCB (unknown) after 3 instructions at Mystery.swapped()I pc=2";
+    assert!(text.starts_with(headline), "{}", text);
+    assert!(text.contains("\n=>     2: unknown"), "{}", text);
+    assert!(text.contains("\n       3: isub"), "{}", text);
+
+    // 栈帧已经弹出，解释器可以继续使用
+    assert_eq!(interpreter.thread.stack_depth(), 0);
+    Ok(())
+}
+
+#[test]
+fn test_context_is_limited_to_three_instructions_each_side() -> Result<()> {
+    let class_file = ClassFileBuilder::new("Long")
+        .method(ACC_PUBLIC | ACC_STATIC, "run", "()I", 8, 0, |code| {
+            for i in 0..5 {
+                code.iconst(i);
+            }
+            code.op(MYSTERY);
+            for _ in 0..4 {
+                code.iadd();
+            }
+            code.ireturn();
+        })
+        .build()?;
+    let mut interpreter = Interpreter::new_with_options(InterpreterOptions {
+        verify: false,
+        unknown_opcode_policy: UnknownOpcodePolicy::WarnAndStop,
+        ..Default::default()
+    });
+    interpreter.load_class(class_file)?;
+    let err = interpreter.invoke_static("Long", "run", "()I", vec![]).unwrap_err();
+    let report = err.downcast_ref::<UnknownOpcodeReport>().unwrap();
+    let pcs: Vec<_> = report.context.iter().map(|i| i.pc).collect();
+    assert_eq!(pcs, [2, 3, 4, 5, 6, 7, 8]);
+    Ok(())
+}
+
+#[test]
+fn test_trap_handler_emulates_the_opcode() -> Result<()> {
+    let mut interpreter = interpreter(UnknownOpcodePolicy::trap(emulate_swap))?;
+    let result = swapped(&mut interpreter)?;
+    assert!(matches!(result, Some(JvmValue::Int(1))), "{:?}", result);
+    // 每次执行到都调用处理函数
+    let result = swapped(&mut interpreter)?;
+    assert!(matches!(result, Some(JvmValue::Int(1))), "{:?}", result);
+    Ok(())
+}
+
+#[test]
+fn test_trap_handler_can_abort_or_throw() -> Result<()> {
+    let mut aborting = interpreter(UnknownOpcodePolicy::trap(|_, _, _| Ok(TrapAction::Abort)))?;
+    let err = swapped(&mut aborting).unwrap_err();
+    assert!(format!("{:#}", err).contains("Unknown opcode: 0xCB at pc 2"), "{:#}", err);
+
+    // 处理函数返回的 Java 异常和其他指令抛出的一样处理
+    let mut throwing = interpreter(UnknownOpcodePolicy::trap(|opcode, _, _| {
+        let message = format!("opcode {}", opcode);
+        Err(JavaException::new("java/lang/UnsupportedOperationException", message).into())
+    }))?;
+    let err = swapped(&mut throwing).unwrap_err();
+    let message = format!("{:#}", err);
+    assert!(message.contains("java/lang/UnsupportedOperationException: opcode 203"), "{}", message);
+    Ok(())
+}
+
+#[test]
+fn test_cli_report_mode() -> Result<()> {
+    let dir = std::env::temp_dir().join(format!("rsjvm-unknown-opcode-{}", std::process::id()));
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join("Mystery.class");
+    std::fs::write(&path, mystery_class()?.to_bytes()?)?;
+
+    let run = |mode: &str| {
+        Command::new(env!("CARGO_BIN_EXE_rsjvm"))
+            .arg("run")
+            .arg(&path)
+            .args(["-m", "swapped", &format!("--on-unknown-opcode={}", mode)])
+            .output()
+            .expect("failed to run rsjvm")
+    };
+    let output = run("report");
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("unknown opcode 0xCB (unknown) after 3 instructions"), "{}", stdout);
+    assert!(stdout.contains("=>     2: unknown"), "{}", stdout);
+    assert!(stdout.contains("1: iconst_2") && stdout.contains("3: isub"), "{}", stdout);
+
+    // 默认模式：加载时的校验就拒绝了这个类
+    let output = run("error");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("VerifyError"), "{:?}", output);
+
+    std::fs::remove_dir_all(&dir)?;
+    Ok(())
+}
@@ -0,0 +1,164 @@
+//! 测试加载时的字节码校验
+
+use rsjvm::classfile::constant_pool::ConstantPool;
+use rsjvm::classfile::ClassFile;
+use rsjvm::interpreter::verifier::verify_method;
+use rsjvm::interpreter::{Interpreter, InterpreterOptions};
+use rsjvm::runtime::metaspace::ExceptionTableEntry;
+use rsjvm::runtime::MethodMetadata;
+use rsjvm::Result;
+
+fn method(code: &[u8], max_stack: usize, max_locals: usize) -> MethodMetadata {
+    MethodMetadata {
+        name: "test".into(),
+        descriptor: "()V".into(),
+        access_flags: 0x0009,
+        max_stack,
+        max_locals,
+        code: code.into(),
+        decoded: None,
+        is_static: true,
+        is_native: false,
+        is_abstract: false,
+        local_variables: Vec::new(),
+        line_numbers: Vec::new(),
+        exception_table: Vec::new(),
+    }
+}
+
+fn verify_err(method: &MethodMetadata) -> String {
+    let cp = ConstantPool { entries: vec![None] };
+    match verify_method(method, &cp) {
+        Ok(()) => panic!("expected a verify error for {:02x?}", method.code),
+        Err(e) => e.to_string(),
+    }
+}
+
+#[test]
+fn test_valid_code_passes() {
+    let cp = ConstantPool { entries: vec![None] };
+    // int i = 0; while (i < 10) i++; return i;
+    let code = [
+        0x03, // 0: iconst_0
+        0x3b, // 1: istore_0
+        0x1a, // 2: iload_0
+        0x10, 0x0a, // 3: bipush 10
+        0xa2, 0x00, 0x09, // 5: if_icmpge 14
+        0x84, 0x00, 0x01, // 8: iinc 0, 1
+        0xa7, 0xff, 0xf7, // 11: goto 2
+        0x1a, // 14: iload_0
+        0xac, // 15: ireturn
+    ];
+    verify_method(&method(&code, 2, 1), &cp).unwrap();
+}
+
+#[test]
+fn test_branch_into_middle_of_instruction() {
+    // goto 4 落在 bipush 的操作数上
+    let code = [0xa7, 0x00, 0x04, 0x10, 0x05, 0xb1];
+    let err = verify_err(&method(&code, 1, 0));
+    assert_eq!(err, "Branch target 4 of goto at pc 0 is not the start of an instruction");
+}
+
+#[test]
+fn test_stack_underflow() {
+    let code = [0x04, 0x60, 0xac]; // iconst_1; iadd; ireturn
+    let err = verify_err(&method(&code, 2, 0));
+    assert_eq!(err, "Stack underflow at pc 1 (iadd): needs 2 slots, stack has 1");
+}
+
+#[test]
+fn test_exceeding_max_stack() {
+    let code = [0x09, 0x04, 0x57, 0x58, 0xb1]; // lconst_0; iconst_1; pop; pop2; return
+    let err = verify_err(&method(&code, 2, 0));
+    assert_eq!(err, "Stack overflow at pc 1 (iconst_1): depth 3 exceeds max_stack 2");
+}
+
+#[test]
+fn test_local_index_out_of_range() {
+    let code = [0x15, 0x03, 0xac]; // iload 3; ireturn
+    let err = verify_err(&method(&code, 1, 3));
+    assert_eq!(err, "Local variable 3 of iload at pc 0 is out of range (max_locals 3)");
+
+    // lstore_2 占 2、3 两个槽位
+    let code = [0x09, 0x41, 0xb1];
+    let err = verify_err(&method(&code, 2, 3));
+    assert_eq!(err, "Local variable 3 of lstore_2 at pc 1 is out of range (max_locals 3)");
+}
+
+#[test]
+fn test_inconsistent_depth_at_join() {
+    // iconst_0; ifeq 6; iconst_1; nop; return
+    // 跳转路径到 pc 6 时栈为空，顺序路径多一个 int
+    let code = [0x03, 0x99, 0x00, 0x05, 0x04, 0x00, 0xb1];
+    let err = verify_err(&method(&code, 1, 0));
+    assert_eq!(err, "Inconsistent stack depth at pc 6: 1 vs 0");
+}
+
+#[test]
+fn test_falling_off_the_end_and_truncation() {
+    let err = verify_err(&method(&[0x04, 0x3b], 1, 1));
+    assert_eq!(err, "Execution falls off the end of the code after istore_0 at pc 1");
+
+    let err = verify_err(&method(&[0x10], 1, 0));
+    assert_eq!(err, "Truncated operand for bipush at pc 0");
+}
+
+#[test]
+fn test_exception_handler_must_be_instruction_start() {
+    let mut m = method(&[0x10, 0x01, 0xac], 1, 0);
+    m.exception_table.push(ExceptionTableEntry {
+        start_pc: 0,
+        end_pc: 2,
+        handler_pc: 1,
+        catch_type: None,
+    });
+    let err = verify_err(&m);
+    assert_eq!(err, "Exception handler pc 1 is not the start of an instruction");
+}
+
+/// 把 ReturnOne.returnOne 的 max_stack 改成 0
+fn corrupted_return_one() -> Result<ClassFile> {
+    let mut class_file = ClassFile::from_file("examples/ReturnOne.class")?;
+    let cp = &class_file.constant_pool;
+    let name = |index: u16| cp.get_utf8(index).unwrap();
+    let m = class_file.methods.iter().position(|m| name(m.name_index) == "returnOne").unwrap();
+    let a = class_file.methods[m]
+        .attributes
+        .iter()
+        .position(|a| name(a.name_index) == "Code")
+        .unwrap();
+    let code = &mut class_file.methods[m].attributes[a];
+    code.info[0..2].copy_from_slice(&[0, 0]);
+    Ok(class_file)
+}
+
+#[test]
+fn test_load_class_rejects_corrupted_method() -> Result<()> {
+    let mut interpreter = Interpreter::new();
+    let err = interpreter.load_class(corrupted_return_one()?).unwrap_err();
+    let message = format!("{:#}", err);
+    assert!(message.contains("java/lang/VerifyError"), "{}", message);
+    assert!(
+        message.contains(
+            "ReturnOne.returnOne()I: Stack overflow at pc 0 (iconst_1): depth 1 exceeds max_stack 0"
+        ),
+        "{}",
+        message
+    );
+    assert!(interpreter.metaspace.get_class("ReturnOne").is_err());
+    Ok(())
+}
+
+#[test]
+fn test_verification_can_be_disabled() -> Result<()> {
+    let mut interpreter = Interpreter::new_with_options(InterpreterOptions {
+        verify: false,
+        ..Default::default()
+    });
+    interpreter.load_class(corrupted_return_one()?)?;
+    // 没有校验时，问题到执行时才暴露
+    let err = interpreter.invoke_static("ReturnOne", "returnOne", "()I", vec![]).unwrap_err();
+    assert!(format!("{:#}", err).contains("operand stack overflow: max_stack=0"), "{:#}", err);
+    Ok(())
+}